@@ -0,0 +1,177 @@
+// Inbound acknowledgments (MCCI_IN200101UV01) recorded per case
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::Result;
+use modql::field::Fields;
+use serde::{Deserialize, Serialize};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::types::Uuid;
+use sqlx::FromRow;
+
+// -- CaseAcknowledgment
+
+#[derive(Debug, Clone, Fields, FromRow, Serialize)]
+pub struct CaseAcknowledgment {
+	pub id: Uuid,
+	pub case_id: Uuid,
+
+	// ACK.M.1 - Acknowledgement Batch Number
+	pub ack_batch_number: Option<String>,
+
+	// ACK.A.1 - ICSR Batch Number
+	pub batch_number: Option<String>,
+
+	// ACK.A.4 - Transmission Acknowledgement Code
+	pub batch_ack_code: Option<String>, // AA, AE, AR
+
+	// ACK.B.r.1 - ICSR Message Number
+	pub message_number: Option<String>,
+
+	// ACK.B.r.2 - Local Report Number
+	pub local_report_number: Option<String>,
+
+	// ACK.B.r.6 - Acknowledgement Code for an ICSR Message
+	pub ack_code: Option<String>, // CA, CR
+
+	// ACK.B.r.7 / ACK.A.5 - Error / Warning Message or Comment
+	pub error_text: Option<String>,
+
+	// ACK.M.4 - Acknowledgement Date of Batch Transmission
+	pub ack_date: Option<String>,
+
+	// Timestamps
+	pub created_at: OffsetDateTime,
+	pub updated_at: OffsetDateTime,
+	pub created_by: Uuid,
+	pub updated_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Fields, Deserialize)]
+pub struct CaseAcknowledgmentForCreate {
+	pub case_id: Uuid,
+	pub ack_batch_number: Option<String>,
+	pub batch_number: Option<String>,
+	pub batch_ack_code: Option<String>,
+	pub message_number: Option<String>,
+	pub local_report_number: Option<String>,
+	pub ack_code: Option<String>,
+	pub error_text: Option<String>,
+	pub ack_date: Option<String>,
+}
+
+// -- BMC
+
+pub struct CaseAcknowledgmentBmc;
+impl DbBmc for CaseAcknowledgmentBmc {
+	const TABLE: &'static str = "case_acknowledgments";
+}
+
+impl CaseAcknowledgmentBmc {
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		data: CaseAcknowledgmentForCreate,
+	) -> Result<Uuid> {
+		mm.dbx().begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			mm.dbx(),
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+
+		let sql = format!(
+			"INSERT INTO {} (case_id, ack_batch_number, batch_number, batch_ack_code,
+			                 message_number, local_report_number, ack_code, error_text,
+			                 ack_date, created_at, updated_at, created_by)
+			 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, now(), now(), $10)
+			 RETURNING id",
+			Self::TABLE
+		);
+		let (id,) = mm
+			.dbx()
+			.fetch_one(
+				sqlx::query_as::<_, (Uuid,)>(&sql)
+					.bind(data.case_id)
+					.bind(data.ack_batch_number)
+					.bind(data.batch_number)
+					.bind(data.batch_ack_code)
+					.bind(data.message_number)
+					.bind(data.local_report_number)
+					.bind(data.ack_code)
+					.bind(data.error_text)
+					.bind(data.ack_date)
+					.bind(ctx.user_id()),
+			)
+			.await?;
+
+		mm.dbx().commit_txn().await?;
+		Ok(id)
+	}
+
+	pub async fn list_by_case(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<Vec<CaseAcknowledgment>> {
+		let sql = format!(
+			"SELECT * FROM {} WHERE case_id = $1 ORDER BY created_at DESC",
+			Self::TABLE
+		);
+		let acks = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, CaseAcknowledgment>(&sql).bind(case_id))
+			.await?;
+		Ok(acks)
+	}
+
+	/// Finds the submitted case an ICSR acknowledgment refers to.
+	///
+	/// `identifiers` holds the tokens of ACK.B.r.1, which senders fill with the
+	/// N.2.r.1 message number, the C.1.1 safety report id, or both. Cases from
+	/// the acknowledged batch win over other matches, then the latest version.
+	pub async fn find_case_for_message(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		identifiers: &[String],
+		batch_number: Option<&str>,
+	) -> Result<Option<Uuid>> {
+		let sql = "SELECT c.id
+			 FROM cases c
+			 LEFT JOIN message_headers mh ON mh.case_id = c.id
+			 WHERE mh.message_number = ANY($1) OR c.safety_report_id = ANY($1)
+			 ORDER BY (mh.batch_number IS NOT DISTINCT FROM $2) DESC,
+			          (mh.message_number = ANY($1)) DESC NULLS LAST,
+			          c.version DESC
+			 LIMIT 1";
+		let row = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, (Uuid,)>(sql)
+					.bind(identifiers)
+					.bind(batch_number),
+			)
+			.await?;
+		Ok(row.map(|(id,)| id))
+	}
+
+	/// Lists the cases whose message header carries the given N.1.2 batch number.
+	pub async fn find_cases_for_batch(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		batch_number: &str,
+	) -> Result<Vec<Uuid>> {
+		let sql = "SELECT case_id FROM message_headers
+			 WHERE batch_number = $1
+			 ORDER BY created_at";
+		let rows = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, (Uuid,)>(sql).bind(batch_number))
+			.await?;
+		Ok(rows.into_iter().map(|(id,)| id).collect())
+	}
+}
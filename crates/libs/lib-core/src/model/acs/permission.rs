@@ -55,6 +55,9 @@ pub enum Resource {
 	// Terminology
 	Terminology,
 
+	// Validation
+	ValidationRule,
+
 	// XML operations
 	XmlExport,
 	XmlImport,
@@ -494,6 +497,16 @@ pub const AUDIT_LIST: Permission = Permission::new(Resource::AuditLog, Action::L
 // Terminology permissions
 pub const TERMINOLOGY_READ: Permission =
 	Permission::new(Resource::Terminology, Action::Read);
+pub const TERMINOLOGY_UPDATE: Permission =
+	Permission::new(Resource::Terminology, Action::Update);
+pub const TERMINOLOGY_IMPORT: Permission =
+	Permission::new(Resource::Terminology, Action::Import);
+
+// Validation rule permissions
+pub const VALIDATION_RULE_READ: Permission =
+	Permission::new(Resource::ValidationRule, Action::Read);
+pub const VALIDATION_RULE_UPDATE: Permission =
+	Permission::new(Resource::ValidationRule, Action::Update);
 
 // XML permissions
 pub const XML_EXPORT: Permission =
@@ -700,6 +713,11 @@ fn admin_permissions() -> &'static [Permission] {
 		AUDIT_LIST,
 		// Terminology
 		TERMINOLOGY_READ,
+		TERMINOLOGY_UPDATE,
+		TERMINOLOGY_IMPORT,
+		// Validation rules
+		VALIDATION_RULE_READ,
+		VALIDATION_RULE_UPDATE,
 		// XML
 		XML_EXPORT,
 		XML_IMPORT,
@@ -894,6 +912,8 @@ fn manager_permissions() -> &'static [Permission] {
 		AUDIT_LIST,
 		// Terminology
 		TERMINOLOGY_READ,
+		// Validation rules - view effective severities
+		VALIDATION_RULE_READ,
 		// XML
 		XML_EXPORT,
 		XML_IMPORT,
@@ -1052,6 +1072,8 @@ fn user_permissions() -> &'static [Permission] {
 		ORG_READ,
 		// Terminology
 		TERMINOLOGY_READ,
+		// Validation rules - view effective severities
+		VALIDATION_RULE_READ,
 		// XML - export only
 		XML_EXPORT,
 	]
@@ -1143,6 +1165,8 @@ fn viewer_permissions() -> &'static [Permission] {
 		USER_LIST,
 		// Organization - read own
 		ORG_READ,
+		// Validation rules - view effective severities
+		VALIDATION_RULE_READ,
 		// XML - export only (viewing)
 		XML_EXPORT,
 	]
@@ -1209,6 +1233,14 @@ mod tests {
 		assert!(!has_permission(ROLE_MANAGER, USER_CREATE));
 		// Manager cannot manage organizations
 		assert!(!has_permission(ROLE_MANAGER, ORG_CREATE));
+		// Terminology releases are loaded and activated by admins only
+		assert!(has_permission(ROLE_ADMIN, TERMINOLOGY_IMPORT));
+		assert!(!has_permission(ROLE_MANAGER, TERMINOLOGY_IMPORT));
+		assert!(!has_permission(ROLE_MANAGER, TERMINOLOGY_UPDATE));
+		// Validation rules are edited by admins only
+		assert!(has_permission(ROLE_ADMIN, VALIDATION_RULE_UPDATE));
+		assert!(has_permission(ROLE_MANAGER, VALIDATION_RULE_READ));
+		assert!(!has_permission(ROLE_MANAGER, VALIDATION_RULE_UPDATE));
 	}
 
 	#[test]
@@ -0,0 +1,487 @@
+// Audit trail export for inspections: field-level CSV/JSON with a SHA-256
+// manifest. Exports are produced on demand; periodic packages are left to an
+// external scheduler calling the export endpoint.
+
+use crate::ctx::Ctx;
+use crate::model::audit::AuditLogBmc;
+use crate::model::base::DbBmc;
+use crate::model::case::CaseBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::Result;
+use lib_utils::time::format_time;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::Uuid;
+use sqlx::FromRow;
+use std::collections::{BTreeSet, HashMap};
+
+// The generated code clashes with `model::Result` at module scope.
+mod date_format {
+	time::serde::format_description!(pub iso_date, Date, "[year]-[month]-[day]");
+}
+use date_format::iso_date;
+
+pub const AUDIT_EXPORT_CSV_FILE: &str = "audit_trail.csv";
+pub const AUDIT_EXPORT_JSON_FILE: &str = "audit_trail.json";
+
+/// Columns pointing from a child row to its parent. Audit rows of a case's
+/// children are found through these values in `new_values`/`old_values`, so
+/// rows of since-deleted children are exported as well.
+const PARENT_KEYS: &[&str] = &[
+	"case_id",
+	"patient_id",
+	"parent_id",
+	"death_info_id",
+	"drug_id",
+	"reaction_id",
+	"drug_reaction_assessment_id",
+	"narrative_id",
+	"study_information_id",
+];
+
+const CSV_HEADER: &[&str] = &[
+	"audit_id",
+	"timestamp",
+	"user_id",
+	"user_name",
+	"action",
+	"table_name",
+	"record_id",
+	"field",
+	"old_value",
+	"new_value",
+];
+
+// -- Types
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditExportParams {
+	/// Export one case; every case of the organization when omitted.
+	pub case_id: Option<Uuid>,
+	pub organization_id: Option<Uuid>,
+	/// First day included (UTC), `YYYY-MM-DD`.
+	#[serde(default, with = "iso_date::option")]
+	pub from: Option<Date>,
+	/// Last day included (UTC), `YYYY-MM-DD`.
+	#[serde(default, with = "iso_date::option")]
+	pub to: Option<Date>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditFieldChange {
+	pub field: String,
+	pub old_value: Option<String>,
+	pub new_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditExportEntry {
+	pub audit_id: i64,
+	/// RFC 3339, UTC.
+	pub timestamp: String,
+	pub user_id: Uuid,
+	pub user_name: Option<String>,
+	pub action: String,
+	pub table_name: String,
+	pub record_id: Uuid,
+	pub changes: Vec<AuditFieldChange>,
+}
+
+/// Content of `audit_trail.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditExportDocument {
+	pub generated_at: String,
+	pub generated_by: Uuid,
+	pub case_id: Option<Uuid>,
+	pub organization_id: Option<Uuid>,
+	#[serde(with = "iso_date::option")]
+	pub from: Option<Date>,
+	#[serde(with = "iso_date::option")]
+	pub to: Option<Date>,
+	pub entries: Vec<AuditExportEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditExportFile {
+	pub file_name: String,
+	pub content_type: String,
+	pub sha256: String,
+	pub size_bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditExportManifest {
+	pub generated_at: String,
+	pub generated_by: Uuid,
+	pub case_id: Option<Uuid>,
+	pub organization_id: Option<Uuid>,
+	#[serde(with = "iso_date::option")]
+	pub from: Option<Date>,
+	#[serde(with = "iso_date::option")]
+	pub to: Option<Date>,
+	pub entry_count: usize,
+	pub hash_algorithm: String,
+	pub files: Vec<AuditExportFile>,
+}
+
+/// The exported files and the manifest holding their SHA-256 digests.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditExportPackage {
+	pub manifest: AuditExportManifest,
+	pub csv: String,
+	pub json: String,
+}
+
+#[derive(FromRow)]
+struct AuditExportRow {
+	id: i64,
+	table_name: String,
+	record_id: Uuid,
+	action: String,
+	user_id: Uuid,
+	old_values: Option<JsonValue>,
+	new_values: Option<JsonValue>,
+	created_at: OffsetDateTime,
+}
+
+// -- BMC
+
+pub struct AuditExportBmc;
+
+impl AuditExportBmc {
+	/// Builds the export package for `params`: every audit row of the selected
+	/// cases and of all their child tables, oldest first.
+	pub async fn export(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		params: AuditExportParams,
+	) -> Result<AuditExportPackage> {
+		if let Some(case_id) = params.case_id {
+			// Fails for cases outside the caller's organization.
+			CaseBmc::get(ctx, mm, case_id).await?;
+		}
+
+		let case_ids: Vec<Uuid> = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, (Uuid,)>(&format!(
+					"SELECT id FROM {}
+					 WHERE ($1::uuid IS NULL OR id = $1)
+					   AND ($2::uuid IS NULL OR organization_id = $2)",
+					CaseBmc::TABLE
+				))
+				.bind(params.case_id)
+				.bind(params.organization_id),
+			)
+			.await?
+			.into_iter()
+			.map(|(id,)| id)
+			.collect();
+
+		let rows = Self::load_rows(ctx, mm, &case_ids, &params).await?;
+		let user_names = Self::user_names(mm, &rows).await?;
+
+		let entries: Vec<AuditExportEntry> = rows
+			.into_iter()
+			.map(|row| AuditExportEntry {
+				audit_id: row.id,
+				timestamp: format_time(row.created_at),
+				user_name: user_names.get(&row.user_id).cloned(),
+				user_id: row.user_id,
+				changes: field_changes(
+					&row.action,
+					row.old_values.as_ref(),
+					row.new_values.as_ref(),
+				),
+				action: row.action,
+				table_name: row.table_name,
+				record_id: row.record_id,
+			})
+			.collect();
+
+		let generated_at = format_time(OffsetDateTime::now_utc());
+		let csv = to_csv(&entries);
+		let entry_count = entries.len();
+		let document = AuditExportDocument {
+			generated_at: generated_at.clone(),
+			generated_by: ctx.user_id(),
+			case_id: params.case_id,
+			organization_id: params.organization_id,
+			from: params.from,
+			to: params.to,
+			entries,
+		};
+		let json = serde_json::to_string_pretty(&document)
+			.expect("audit export document serializes");
+
+		let manifest = AuditExportManifest {
+			generated_at,
+			generated_by: ctx.user_id(),
+			case_id: params.case_id,
+			organization_id: params.organization_id,
+			from: params.from,
+			to: params.to,
+			entry_count,
+			hash_algorithm: "SHA-256".to_string(),
+			files: vec![
+				export_file(AUDIT_EXPORT_CSV_FILE, "text/csv", &csv),
+				export_file(AUDIT_EXPORT_JSON_FILE, "application/json", &json),
+			],
+		};
+
+		Ok(AuditExportPackage {
+			manifest,
+			csv,
+			json,
+		})
+	}
+
+	/// Reads the audit rows of `case_ids` and their descendants. `audit_logs`
+	/// is only readable by the auditor role, which is assumed for the duration
+	/// of a dedicated transaction.
+	async fn load_rows(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_ids: &[Uuid],
+		params: &AuditExportParams,
+	) -> Result<Vec<AuditExportRow>> {
+		if case_ids.is_empty() {
+			return Ok(Vec::new());
+		}
+		let from = params.from.map(|d| d.midnight().assume_utc());
+		// Exclusive upper bound; nothing follows the last representable day.
+		let to = params
+			.to
+			.and_then(Date::next_day)
+			.map(|d| d.midnight().assume_utc());
+		let parent_keys: Vec<String> =
+			PARENT_KEYS.iter().map(|k| k.to_string()).collect();
+
+		let sql = format!(
+			"WITH RECURSIVE records(record_id) AS (
+			     SELECT unnest($1::uuid[])
+			     UNION
+			     SELECT a.record_id
+			     FROM {table} a
+			     JOIN records r ON EXISTS (
+			         SELECT 1 FROM unnest($2::text[]) AS k(key)
+			         WHERE a.new_values ->> k.key = r.record_id::text
+			            OR a.old_values ->> k.key = r.record_id::text
+			     )
+			 )
+			 SELECT a.id, a.table_name, a.record_id, a.action, a.user_id,
+			        a.old_values, a.new_values, a.created_at
+			 FROM {table} a
+			 WHERE a.record_id IN (SELECT record_id FROM records)
+			   AND ($3::timestamptz IS NULL OR a.created_at >= $3)
+			   AND ($4::timestamptz IS NULL OR a.created_at < $4)
+			 ORDER BY a.created_at, a.id",
+			table = AuditLogBmc::TABLE
+		);
+
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		let res = async {
+			// Safety of the role switch:
+			// - callers are gated on `AuditLog.List` (`require_audit_permission`);
+			// - the transaction is owned by this function on its own connection,
+			//   so no other request's statements run under the auditor role;
+			// - SET LOCAL ends with the transaction, and only the read-only
+			//   SELECT below runs before the explicit switch back.
+			dbx.execute(sqlx::query("SET LOCAL ROLE e2br3_auditor_role"))
+				.await?;
+			let rows = dbx
+				.fetch_all(
+					sqlx::query_as::<_, AuditExportRow>(&sql)
+						.bind(case_ids)
+						.bind(&parent_keys)
+						.bind(from)
+						.bind(to),
+				)
+				.await;
+			// Back to the app role even on error, before anything else runs.
+			dbx.execute(sqlx::query("SET LOCAL ROLE e2br3_app_role"))
+				.await?;
+			Ok::<_, crate::model::Error>(rows?)
+		}
+		.await;
+		match res {
+			Ok(rows) => {
+				dbx.commit_txn().await?;
+				Ok(rows)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	/// Display names ("First Last (username)") of the users in `rows`.
+	async fn user_names(
+		mm: &ModelManager,
+		rows: &[AuditExportRow],
+	) -> Result<HashMap<Uuid, String>> {
+		let user_ids: Vec<Uuid> = rows
+			.iter()
+			.map(|row| row.user_id)
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect();
+		if user_ids.is_empty() {
+			return Ok(HashMap::new());
+		}
+		let users: Vec<(Uuid, String, Option<String>, Option<String>)> = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as(
+					"SELECT id, username, first_name, last_name
+					 FROM users WHERE id = ANY($1)",
+				)
+				.bind(&user_ids),
+			)
+			.await?;
+		Ok(users
+			.into_iter()
+			.map(|(id, username, first_name, last_name)| {
+				let full_name = [first_name, last_name]
+					.into_iter()
+					.flatten()
+					.filter(|part| !part.trim().is_empty())
+					.collect::<Vec<_>>()
+					.join(" ");
+				let name = if full_name.is_empty() {
+					username
+				} else {
+					format!("{full_name} ({username})")
+				};
+				(id, name)
+			})
+			.collect())
+	}
+}
+
+// -- Rendering
+
+/// Field-level before/after values of one audit row. Updates keep only the
+/// fields that changed; creates and deletes list every field.
+pub fn field_changes(
+	action: &str,
+	old_values: Option<&JsonValue>,
+	new_values: Option<&JsonValue>,
+) -> Vec<AuditFieldChange> {
+	let old = old_values.and_then(JsonValue::as_object);
+	let new = new_values.and_then(JsonValue::as_object);
+	let fields: BTreeSet<&String> = old
+		.into_iter()
+		.chain(new)
+		.flat_map(|values| values.keys())
+		.collect();
+
+	fields
+		.into_iter()
+		.filter_map(|field| {
+			let old_value = old.and_then(|v| v.get(field));
+			let new_value = new.and_then(|v| v.get(field));
+			if action == "UPDATE" && old_value == new_value {
+				return None;
+			}
+			Some(AuditFieldChange {
+				field: field.clone(),
+				old_value: old_value.and_then(value_text),
+				new_value: new_value.and_then(value_text),
+			})
+		})
+		.collect()
+}
+
+fn value_text(value: &JsonValue) -> Option<String> {
+	match value {
+		JsonValue::Null => None,
+		JsonValue::String(s) => Some(s.clone()),
+		other => Some(other.to_string()),
+	}
+}
+
+/// One line per changed field; rows without field values get a single line
+/// with empty field columns.
+pub fn to_csv(entries: &[AuditExportEntry]) -> String {
+	let mut csv = String::new();
+	push_csv_line(&mut csv, CSV_HEADER.iter().map(|h| Some(*h)));
+	for entry in entries {
+		let audit_id = entry.audit_id.to_string();
+		let user_id = entry.user_id.to_string();
+		let record_id = entry.record_id.to_string();
+		let prefix = [
+			Some(audit_id.as_str()),
+			Some(entry.timestamp.as_str()),
+			Some(user_id.as_str()),
+			entry.user_name.as_deref(),
+			Some(entry.action.as_str()),
+			Some(entry.table_name.as_str()),
+			Some(record_id.as_str()),
+		];
+		if entry.changes.is_empty() {
+			push_csv_line(&mut csv, prefix.into_iter().chain([None, None, None]));
+		}
+		for change in &entry.changes {
+			push_csv_line(
+				&mut csv,
+				prefix.into_iter().chain([
+					Some(change.field.as_str()),
+					change.old_value.as_deref(),
+					change.new_value.as_deref(),
+				]),
+			);
+		}
+	}
+	csv
+}
+
+fn push_csv_line<'a>(
+	csv: &mut String,
+	cells: impl Iterator<Item = Option<&'a str>>,
+) {
+	for (index, cell) in cells.enumerate() {
+		if index > 0 {
+			csv.push(',');
+		}
+		let cell = cell.unwrap_or_default();
+		if cell.contains([',', '"', '\n', '\r']) {
+			csv.push('"');
+			csv.push_str(&cell.replace('"', "\"\""));
+			csv.push('"');
+		} else {
+			csv.push_str(cell);
+		}
+	}
+	csv.push_str("\r\n");
+}
+
+pub fn sha256_hex(content: &[u8]) -> String {
+	format!("{:x}", Sha256::digest(content))
+}
+
+fn export_file(
+	file_name: &str,
+	content_type: &str,
+	content: &str,
+) -> AuditExportFile {
+	AuditExportFile {
+		file_name: file_name.to_string(),
+		content_type: content_type.to_string(),
+		sha256: sha256_hex(content.as_bytes()),
+		size_bytes: content.len(),
+	}
+}
@@ -30,6 +30,9 @@ pub struct Case {
 	pub submitted_by: Option<Uuid>,
 	pub submitted_at: Option<OffsetDateTime>,
 
+	// Versioning: set once a follow-up copies this case into version N+1
+	pub superseded_by: Option<Uuid>,
+
 	// Raw imported XML (optional)
 	pub raw_xml: Option<Vec<u8>>,
 	pub dirty_c: bool,
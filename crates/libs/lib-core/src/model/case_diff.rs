@@ -0,0 +1,938 @@
+// Structured diff between case versions, section by section (C through H)
+
+use crate::ctx::Ctx;
+use crate::model::case::{Case, CaseBmc};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::types::Uuid;
+use std::collections::{BTreeMap, HashMap};
+
+pub const DIFF_SECTIONS: &[&str] = &["C", "D", "E", "F", "G", "H", "J"];
+
+// -- Types
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CaseDiffParams {
+	/// Version to compare from; the version before `to` when omitted.
+	pub from: Option<i32>,
+	/// Version to compare to; the version of the requested case when omitted.
+	pub to: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseDiff {
+	pub safety_report_id: String,
+	pub from: CaseDiffSide,
+	pub to: CaseDiffSide,
+	pub sections: Vec<SectionDiff>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseDiffSide {
+	pub case_id: Uuid,
+	pub version: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionDiff {
+	pub section: &'static str,
+	pub changes: Vec<ItemDiff>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffChange {
+	Added,
+	Removed,
+	Changed,
+}
+
+/// One item of a section, e.g. the whole of C.1 or reaction E.i[2].
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemDiff {
+	/// Element group, e.g. `E.i` or `G.k.4.r`.
+	pub group: &'static str,
+	/// Position of the item, e.g. `G.k[1] / G.k.4.r[2]`.
+	pub path: String,
+	pub sequence_number: Option<i32>,
+	pub change: DiffChange,
+	pub fields: Vec<FieldDiff>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldDiff {
+	/// E2B element number, e.g. `E.i.7`.
+	pub element: &'static str,
+	pub field: &'static str,
+	pub old: Option<String>,
+	pub new: Option<String>,
+}
+
+/// How the items of a table are told apart within their parent.
+#[derive(Clone, Copy)]
+enum DiffKey {
+	/// At most one row per parent.
+	Single,
+	/// Repeating rows matched by `sequence_number`.
+	Sequence,
+	/// G.k.9.i rows, matched by the sequence number of the assessed reaction.
+	Reaction,
+	/// Regional rows of several elements in one table, matched by
+	/// `element_id` and `sequence_number`; the element id names the item.
+	Element,
+}
+
+/// A section table and the E2B element of every compared column. Columns
+/// not listed (ids, audit columns, attachments) are left out of the diff.
+pub(crate) struct DiffTable {
+	table: &'static str,
+	section: &'static str,
+	pub(crate) group: &'static str,
+	/// Parent table and the column referencing it; `None` for case-level tables.
+	parent: Option<(&'static str, &'static str)>,
+	key: DiffKey,
+	elements: &'static [(&'static str, &'static str)],
+}
+
+const fn table(
+	table: &'static str,
+	section: &'static str,
+	group: &'static str,
+	parent: Option<(&'static str, &'static str)>,
+	key: DiffKey,
+	elements: &'static [(&'static str, &'static str)],
+) -> DiffTable {
+	DiffTable {
+		table,
+		section,
+		group,
+		parent,
+		key,
+		elements,
+	}
+}
+
+/// Compared tables, parents before children.
+pub(crate) const DIFF_TABLES: &[DiffTable] = &[
+	// Section C
+	table(
+		"safety_report_identification",
+		"C",
+		"C.1",
+		None,
+		DiffKey::Single,
+		&[
+			("transmission_date", "C.1.2"),
+			("report_type", "C.1.3"),
+			("date_first_received_from_source", "C.1.4"),
+			("date_of_most_recent_information", "C.1.5"),
+			("fulfil_expedited_criteria", "C.1.7"),
+			("local_criteria_report_type", "FDA.C.1.7.1"),
+			("worldwide_unique_id", "C.1.8.1"),
+			("nullification_code", "C.1.11.1"),
+			("nullification_reason", "C.1.11.2"),
+			("combination_product_report_indicator", "FDA.C.1.12"),
+		],
+	),
+	table(
+		"documents_held_by_sender",
+		"C",
+		"C.1.6.1.r",
+		None,
+		DiffKey::Sequence,
+		&[("title", "C.1.6.1.r.1")],
+	),
+	table(
+		"other_case_identifiers",
+		"C",
+		"C.1.9.1.r",
+		None,
+		DiffKey::Sequence,
+		&[
+			("source_of_identifier", "C.1.9.1.r.1"),
+			("case_identifier", "C.1.9.1.r.2"),
+		],
+	),
+	table(
+		"linked_report_numbers",
+		"C",
+		"C.1.10.r",
+		None,
+		DiffKey::Sequence,
+		&[("linked_report_number", "C.1.10.r")],
+	),
+	table(
+		"primary_sources",
+		"C",
+		"C.2.r",
+		None,
+		DiffKey::Sequence,
+		&[
+			("reporter_title", "C.2.r.1.1"),
+			("reporter_given_name", "C.2.r.1.2"),
+			("reporter_middle_name", "C.2.r.1.3"),
+			("reporter_family_name", "C.2.r.1.4"),
+			("organization", "C.2.r.2.1"),
+			("department", "C.2.r.2.2"),
+			("street", "C.2.r.2.3"),
+			("city", "C.2.r.2.4"),
+			("state", "C.2.r.2.5"),
+			("postcode", "C.2.r.2.6"),
+			("telephone", "C.2.r.2.7"),
+			("email", "FDA.C.2.r.2.8"),
+			("country_code", "C.2.r.3"),
+			("qualification", "C.2.r.4"),
+			("kr_other_health_professional", "C.2.r.4.KR.1"),
+			("primary_source_regulatory", "C.2.r.5"),
+		],
+	),
+	table(
+		"sender_information",
+		"C",
+		"C.3",
+		None,
+		DiffKey::Single,
+		&[
+			("sender_type", "C.3.1"),
+			("kr_health_professional_type", "C.3.1.KR.1"),
+			("organization_name", "C.3.2"),
+			("department", "C.3.3.1"),
+			("person_title", "C.3.3.2"),
+			("person_given_name", "C.3.3.3"),
+			("person_middle_name", "C.3.3.4"),
+			("person_family_name", "C.3.3.5"),
+			("street_address", "C.3.4.1"),
+			("city", "C.3.4.2"),
+			("state", "C.3.4.3"),
+			("postcode", "C.3.4.4"),
+			("country_code", "C.3.4.5"),
+			("telephone", "C.3.4.6"),
+			("fax", "C.3.4.7"),
+			("email", "C.3.4.8"),
+		],
+	),
+	table(
+		"literature_references",
+		"C",
+		"C.4.r",
+		None,
+		DiffKey::Sequence,
+		&[("reference_text", "C.4.r.1")],
+	),
+	table(
+		"study_information",
+		"C",
+		"C.5",
+		None,
+		DiffKey::Single,
+		&[
+			("study_name", "C.5.2"),
+			("sponsor_study_number", "C.5.3"),
+			("study_type_reaction", "C.5.4"),
+			("kr_other_study_type", "C.5.4.KR.1"),
+		],
+	),
+	table(
+		"study_registration_numbers",
+		"C",
+		"C.5.1.r",
+		Some(("study_information", "study_information_id")),
+		DiffKey::Sequence,
+		&[
+			("registration_number", "C.5.1.r.1"),
+			("country_code", "C.5.1.r.2"),
+		],
+	),
+	// Section D
+	table(
+		"patient_information",
+		"D",
+		"D",
+		None,
+		DiffKey::Single,
+		&[
+			("patient_initials", "D.1"),
+			("patient_given_name", "D.1"),
+			("patient_family_name", "D.1"),
+			("birth_date", "D.2.1"),
+			("age_at_time_of_onset", "D.2.2a"),
+			("age_unit", "D.2.2b"),
+			("gestation_period", "D.2.2.1a"),
+			("gestation_period_unit", "D.2.2.1b"),
+			("age_group", "D.2.3"),
+			("weight_kg", "D.3"),
+			("height_cm", "D.4"),
+			("sex", "D.5"),
+			("last_menstrual_period_date", "D.6"),
+			("medical_history_text", "D.7.2"),
+			("concomitant_therapy", "D.7.3"),
+			("race_code", "FDA.D.11"),
+			("ethnicity_code", "FDA.D.12"),
+		],
+	),
+	table(
+		"patient_identifiers",
+		"D",
+		"D.1.1.r",
+		Some(("patient_information", "patient_id")),
+		DiffKey::Sequence,
+		&[
+			("identifier_type_code", "D.1.1"),
+			("identifier_value", "D.1.1"),
+		],
+	),
+	table(
+		"medical_history_episodes",
+		"D",
+		"D.7.1.r",
+		Some(("patient_information", "patient_id")),
+		DiffKey::Sequence,
+		&[
+			("meddra_version", "D.7.1.r.1a"),
+			("meddra_code", "D.7.1.r.1b"),
+			("start_date", "D.7.1.r.2"),
+			("continuing", "D.7.1.r.3"),
+			("end_date", "D.7.1.r.4"),
+			("comments", "D.7.1.r.5"),
+			("family_history", "D.7.1.r.6"),
+		],
+	),
+	table(
+		"past_drug_history",
+		"D",
+		"D.8.r",
+		Some(("patient_information", "patient_id")),
+		DiffKey::Sequence,
+		&[
+			("drug_name", "D.8.r.1"),
+			("kr_product_code_version", "D.8.r.1.KR.1a"),
+			("kr_product_code", "D.8.r.1.KR.1b"),
+			("mpid", "D.8.r.2b"),
+			("mpid_version", "D.8.r.2a"),
+			("phpid", "D.8.r.3b"),
+			("phpid_version", "D.8.r.3a"),
+			("start_date", "D.8.r.4"),
+			("end_date", "D.8.r.5"),
+			("indication_meddra_version", "D.8.r.6a"),
+			("indication_meddra_code", "D.8.r.6b"),
+			("reaction_meddra_version", "D.8.r.7a"),
+			("reaction_meddra_code", "D.8.r.7b"),
+		],
+	),
+	table(
+		"patient_death_information",
+		"D",
+		"D.9",
+		Some(("patient_information", "patient_id")),
+		DiffKey::Single,
+		&[("date_of_death", "D.9.1"), ("autopsy_performed", "D.9.3")],
+	),
+	table(
+		"reported_causes_of_death",
+		"D",
+		"D.9.2.r",
+		Some(("patient_death_information", "death_info_id")),
+		DiffKey::Sequence,
+		&[
+			("meddra_version", "D.9.2.r.1a"),
+			("meddra_code", "D.9.2.r.1b"),
+		],
+	),
+	table(
+		"autopsy_causes_of_death",
+		"D",
+		"D.9.4.r",
+		Some(("patient_death_information", "death_info_id")),
+		DiffKey::Sequence,
+		&[
+			("meddra_version", "D.9.4.r.1a"),
+			("meddra_code", "D.9.4.r.1b"),
+		],
+	),
+	table(
+		"parent_information",
+		"D",
+		"D.10",
+		Some(("patient_information", "patient_id")),
+		DiffKey::Single,
+		&[
+			("parent_identification", "D.10.1"),
+			("parent_birth_date", "D.10.2.1"),
+			("parent_age", "D.10.2.2a"),
+			("parent_age_unit", "D.10.2.2b"),
+			("last_menstrual_period_date", "D.10.3"),
+			("weight_kg", "D.10.4"),
+			("height_cm", "D.10.5"),
+			("sex", "D.10.6"),
+			("medical_history_text", "D.10.7.2"),
+		],
+	),
+	table(
+		"parent_medical_history",
+		"D",
+		"D.10.7.1.r",
+		Some(("parent_information", "parent_id")),
+		DiffKey::Sequence,
+		&[
+			("meddra_version", "D.10.7.1.r.1a"),
+			("meddra_code", "D.10.7.1.r.1b"),
+			("start_date", "D.10.7.1.r.2"),
+			("continuing", "D.10.7.1.r.3"),
+			("end_date", "D.10.7.1.r.4"),
+			("comments", "D.10.7.1.r.5"),
+		],
+	),
+	table(
+		"parent_past_drug_history",
+		"D",
+		"D.10.8.r",
+		Some(("parent_information", "parent_id")),
+		DiffKey::Sequence,
+		&[
+			("drug_name", "D.10.8.r.1"),
+			("kr_product_code_version", "D.10.8.r.1.KR.1a"),
+			("kr_product_code", "D.10.8.r.1.KR.1b"),
+			("mpid", "D.10.8.r.2b"),
+			("mpid_version", "D.10.8.r.2a"),
+			("phpid", "D.10.8.r.3b"),
+			("phpid_version", "D.10.8.r.3a"),
+			("start_date", "D.10.8.r.4"),
+			("end_date", "D.10.8.r.5"),
+			("indication_meddra_version", "D.10.8.r.6a"),
+			("indication_meddra_code", "D.10.8.r.6b"),
+			("reaction_meddra_version", "D.10.8.r.7a"),
+			("reaction_meddra_code", "D.10.8.r.7b"),
+		],
+	),
+	// Section E
+	table(
+		"reactions",
+		"E",
+		"E.i",
+		None,
+		DiffKey::Sequence,
+		&[
+			("primary_source_reaction", "E.i.1.1a"),
+			("reaction_language", "E.i.1.1b"),
+			("reaction_meddra_version", "E.i.2.1a"),
+			("reaction_meddra_code", "E.i.2.1b"),
+			("term_highlighted", "E.i.3.1"),
+			("serious", "E.i.3.2"),
+			("criteria_death", "E.i.3.2a"),
+			("criteria_life_threatening", "E.i.3.2b"),
+			("criteria_hospitalization", "E.i.3.2c"),
+			("criteria_disabling", "E.i.3.2d"),
+			("criteria_congenital_anomaly", "E.i.3.2e"),
+			("criteria_other_medically_important", "E.i.3.2f"),
+			("required_intervention", "FDA.E.i.3.2h"),
+			("start_date", "E.i.4"),
+			("end_date", "E.i.5"),
+			("duration_value", "E.i.6a"),
+			("duration_unit", "E.i.6b"),
+			("outcome", "E.i.7"),
+			("medical_confirmation", "E.i.8"),
+			("country_code", "E.i.9"),
+		],
+	),
+	// Section F
+	table(
+		"test_results",
+		"F",
+		"F.r",
+		None,
+		DiffKey::Sequence,
+		&[
+			("test_date", "F.r.1"),
+			("test_name", "F.r.2.1"),
+			("test_meddra_version", "F.r.2.2a"),
+			("test_meddra_code", "F.r.2.2b"),
+			("test_result_code", "F.r.3.1"),
+			("test_result_value", "F.r.3.2"),
+			("test_result_unit", "F.r.3.3"),
+			("result_unstructured", "F.r.3.4"),
+			("normal_low_value", "F.r.4"),
+			("normal_high_value", "F.r.5"),
+			("comments", "F.r.6"),
+			("more_info_available", "F.r.7"),
+		],
+	),
+	// Section G
+	table(
+		"drug_information",
+		"G",
+		"G.k",
+		None,
+		DiffKey::Sequence,
+		&[
+			("drug_characterization", "G.k.1"),
+			("mpid_version", "G.k.2.1.1a"),
+			("mpid", "G.k.2.1.1b"),
+			("phpid_version", "G.k.2.1.2a"),
+			("phpid", "G.k.2.1.2b"),
+			("medicinal_product", "G.k.2.2"),
+			("brand_name", "G.k.2.2"),
+			("obtain_drug_country", "G.k.2.4"),
+			("investigational_product_blinded", "G.k.2.5"),
+			("manufacturer_country", "G.k.3.2"),
+			("manufacturer_name", "G.k.3.3"),
+			("batch_lot_number", "G.k.4.r.7"),
+			("dosage_text", "G.k.4.r.8"),
+			("parent_route", "G.k.4.r.11.1"),
+			("parent_route_termid_version", "G.k.4.r.11.2a"),
+			("parent_route_termid", "G.k.4.r.11.2b"),
+			("parent_dosage_text", "G.k.4.r.8"),
+			("action_taken", "G.k.8"),
+			("rechallenge", "G.k.9.i.4"),
+			("fda_additional_info_coded", "FDA.G.k.10a"),
+			("kr_product_code_version", "G.k.2.1.KR.1a"),
+			("kr_product_code", "G.k.2.1.KR.1b"),
+		],
+	),
+	table(
+		"drug_active_substances",
+		"G",
+		"G.k.2.3.r",
+		Some(("drug_information", "drug_id")),
+		DiffKey::Sequence,
+		&[
+			("substance_name", "G.k.2.3.r.1"),
+			("kr_ingredient_code_version", "G.k.2.3.r.1.KR.1a"),
+			("kr_ingredient_code", "G.k.2.3.r.1.KR.1b"),
+			("substance_termid_version", "G.k.2.3.r.2a"),
+			("substance_termid", "G.k.2.3.r.2b"),
+			("strength_value", "G.k.2.3.r.3a"),
+			("strength_unit", "G.k.2.3.r.3b"),
+		],
+	),
+	table(
+		"dosage_information",
+		"G",
+		"G.k.4.r",
+		Some(("drug_information", "drug_id")),
+		DiffKey::Sequence,
+		&[
+			("dose_value", "G.k.4.r.1a"),
+			("dose_unit", "G.k.4.r.1b"),
+			("number_of_units", "G.k.4.r.2"),
+			("frequency_value", "G.k.4.r.2"),
+			("frequency_unit", "G.k.4.r.3"),
+			("first_administration_date", "G.k.4.r.4"),
+			("first_administration_time", "G.k.4.r.4"),
+			("last_administration_date", "G.k.4.r.5"),
+			("last_administration_time", "G.k.4.r.5"),
+			("duration_value", "G.k.4.r.6a"),
+			("duration_unit", "G.k.4.r.6b"),
+			("batch_lot_number", "G.k.4.r.7"),
+			("dosage_text", "G.k.4.r.8"),
+			("dose_form", "G.k.4.r.9.1"),
+			("dose_form_termid_version", "G.k.4.r.9.2a"),
+			("dose_form_termid", "G.k.4.r.9.2b"),
+			("route_of_administration", "G.k.4.r.10.1"),
+			("parent_route", "G.k.4.r.11.1"),
+			("parent_route_termid_version", "G.k.4.r.11.2a"),
+			("parent_route_termid", "G.k.4.r.11.2b"),
+		],
+	),
+	table(
+		"drug_indications",
+		"G",
+		"G.k.7.r",
+		Some(("drug_information", "drug_id")),
+		DiffKey::Sequence,
+		&[
+			("indication_text", "G.k.7.r.1"),
+			("indication_meddra_version", "G.k.7.r.2a"),
+			("indication_meddra_code", "G.k.7.r.2b"),
+		],
+	),
+	table(
+		"drug_device_characteristics",
+		"G",
+		"G.k (device characteristics)",
+		Some(("drug_information", "drug_id")),
+		DiffKey::Sequence,
+		&[
+			("code", "code"),
+			("code_system", "code_system"),
+			("code_display_name", "code_display_name"),
+			("value_type", "value_type"),
+			("value_value", "value_value"),
+			("value_code", "value_code"),
+			("value_code_system", "value_code_system"),
+			("value_display_name", "value_display_name"),
+		],
+	),
+	table(
+		"drug_recurrence_information",
+		"G",
+		"G.k.8.r",
+		Some(("drug_information", "drug_id")),
+		DiffKey::Sequence,
+		&[
+			("rechallenge_action", "G.k.8.r.1"),
+			("reaction_meddra_version", "G.k.8.r.2a"),
+			("reaction_meddra_code", "G.k.8.r.2b"),
+			("reaction_recurred", "G.k.8.r.3"),
+		],
+	),
+	table(
+		"drug_reaction_assessments",
+		"G",
+		"G.k.9.i",
+		Some(("drug_information", "drug_id")),
+		DiffKey::Reaction,
+		&[
+			("time_interval_value", "G.k.9.i.3.1a"),
+			("time_interval_unit", "G.k.9.i.3.1b"),
+			("recurrence_action", "G.k.9.i.4"),
+			("recurrence_meddra_version", "G.k.9.i.4"),
+			("recurrence_meddra_code", "G.k.9.i.4"),
+			("reaction_recurred", "G.k.9.i.4"),
+		],
+	),
+	table(
+		"relatedness_assessments",
+		"G",
+		"G.k.9.i.2.r",
+		Some(("drug_reaction_assessments", "drug_reaction_assessment_id")),
+		DiffKey::Sequence,
+		&[
+			("source_of_assessment", "G.k.9.i.2.r.1"),
+			("method_of_assessment", "G.k.9.i.2.r.2"),
+			("result_of_assessment", "G.k.9.i.2.r.3"),
+			("kr_method_of_assessment", "G.k.9.i.2.r.2.KR.1"),
+			("kr_result_who_umc", "G.k.9.i.2.r.3.KR.1"),
+			("kr_result_korean", "G.k.9.i.2.r.3.KR.2"),
+		],
+	),
+	// Section H
+	table(
+		"narrative_information",
+		"H",
+		"H",
+		None,
+		DiffKey::Single,
+		&[
+			("case_narrative", "H.1"),
+			("reporter_comments", "H.2"),
+			("sender_comments", "H.4"),
+		],
+	),
+	table(
+		"sender_diagnoses",
+		"H",
+		"H.3.r",
+		Some(("narrative_information", "narrative_id")),
+		DiffKey::Sequence,
+		&[
+			("diagnosis_meddra_version", "H.3.r.1a"),
+			("diagnosis_meddra_code", "H.3.r.1b"),
+		],
+	),
+	table(
+		"case_summary_information",
+		"H",
+		"H.5.r",
+		Some(("narrative_information", "narrative_id")),
+		DiffKey::Sequence,
+		&[
+			("summary_type", "H.5.r"),
+			("summary_text", "H.5.r.1a"),
+			("language_code", "H.5.r.1b"),
+		],
+	),
+	// Section J (PMDA)
+	table(
+		"case_jp_elements",
+		"J",
+		"J",
+		None,
+		DiffKey::Element,
+		&[("value", "J")],
+	),
+];
+
+/// The compared content of one case version: every item keyed by its path.
+#[derive(Debug, Default)]
+pub struct CaseDocument {
+	items: BTreeMap<String, DocumentItem>,
+}
+
+#[derive(Debug)]
+struct DocumentItem {
+	table: &'static DiffTable,
+	sequence_number: Option<i32>,
+	fields: BTreeMap<&'static str, String>,
+}
+
+impl std::fmt::Debug for DiffTable {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.table)
+	}
+}
+
+impl CaseDocument {
+	/// Adds an item of the table with element group `group`. `parent` is the
+	/// path of the enclosing item for nested groups. Returns the item path.
+	pub(crate) fn push(
+		&mut self,
+		group: &str,
+		parent: Option<&str>,
+		sequence_number: Option<i32>,
+		values: impl IntoIterator<Item = (&'static str, Option<String>)>,
+	) -> String {
+		let table = DIFF_TABLES
+			.iter()
+			.find(|table| table.group == group)
+			.expect("diff group is listed in DIFF_TABLES");
+		self.insert(table, table.group, parent, sequence_number, values)
+	}
+
+	/// Adds an item named `label`, the table group unless the table is keyed
+	/// by element.
+	fn insert(
+		&mut self,
+		table: &'static DiffTable,
+		label: &str,
+		parent: Option<&str>,
+		sequence_number: Option<i32>,
+		values: impl IntoIterator<Item = (&'static str, Option<String>)>,
+	) -> String {
+		let path = item_path(label, parent, sequence_number);
+		let fields = values
+			.into_iter()
+			.filter(|(field, _)| {
+				table.elements.iter().any(|(column, _)| column == field)
+			})
+			.filter_map(|(field, value)| value.map(|value| (field, value)))
+			.collect();
+		self.items.insert(
+			path.clone(),
+			DocumentItem {
+				table,
+				sequence_number,
+				fields,
+			},
+		);
+		path
+	}
+}
+
+fn item_path(
+	label: &str,
+	parent: Option<&str>,
+	sequence_number: Option<i32>,
+) -> String {
+	let own = match sequence_number {
+		Some(seq) => format!("{label}[{seq}]"),
+		None => label.to_string(),
+	};
+	match parent {
+		Some(parent) => format!("{parent} / {own}"),
+		None => own,
+	}
+}
+
+/// Text form of a column value, so DB rows and parsed XML compare equal.
+pub(crate) fn diff_value(value: &JsonValue) -> Option<String> {
+	match value {
+		JsonValue::Null => None,
+		JsonValue::String(text) => Some(text.clone()),
+		JsonValue::Number(number) => Some(number.to_string()),
+		JsonValue::Bool(flag) => Some(flag.to_string()),
+		other => Some(other.to_string()),
+	}
+}
+
+/// Compares two documents item by item, matching items by path.
+pub fn diff_documents(old: &CaseDocument, new: &CaseDocument) -> Vec<SectionDiff> {
+	let mut paths: Vec<&String> = old.items.keys().chain(new.items.keys()).collect();
+	paths.sort_by_key(|path| {
+		let item = new.items.get(*path).or_else(|| old.items.get(*path));
+		let order = item.map(|item| table_order(item.table)).unwrap_or_default();
+		(order, path.as_str())
+	});
+	paths.dedup();
+
+	let mut sections: Vec<SectionDiff> = DIFF_SECTIONS
+		.iter()
+		.map(|section| SectionDiff {
+			section,
+			changes: Vec::new(),
+		})
+		.collect();
+	for path in paths {
+		let (old_item, new_item) = (old.items.get(path), new.items.get(path));
+		let Some(item) = new_item.or(old_item) else {
+			continue;
+		};
+		let change = match (old_item, new_item) {
+			(None, Some(_)) => DiffChange::Added,
+			(Some(_), None) => DiffChange::Removed,
+			_ => DiffChange::Changed,
+		};
+		let fields: Vec<FieldDiff> = item
+			.table
+			.elements
+			.iter()
+			.filter_map(|(field, element)| {
+				let old = old_item.and_then(|item| item.fields.get(field)).cloned();
+				let new = new_item.and_then(|item| item.fields.get(field)).cloned();
+				(old != new).then_some(FieldDiff {
+					element,
+					field,
+					old,
+					new,
+				})
+			})
+			.collect();
+		if change == DiffChange::Changed && fields.is_empty() {
+			continue;
+		}
+		if let Some(section) =
+			sections.iter_mut().find(|s| s.section == item.table.section)
+		{
+			section.changes.push(ItemDiff {
+				group: item.table.group,
+				path: path.clone(),
+				sequence_number: item.sequence_number,
+				change,
+				fields,
+			});
+		}
+	}
+	sections
+}
+
+fn table_order(table: &DiffTable) -> usize {
+	DIFF_TABLES
+		.iter()
+		.position(|t| std::ptr::eq(t, table))
+		.unwrap_or_default()
+}
+
+// -- BMC
+
+pub struct CaseDiffBmc;
+
+impl CaseDiffBmc {
+	/// Diffs two versions of the safety report `case_id` belongs to. By
+	/// default the case itself is compared against the version before it.
+	pub async fn diff_versions(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+		params: CaseDiffParams,
+	) -> Result<CaseDiff> {
+		let case = CaseBmc::get(ctx, mm, case_id).await?;
+		let to_version = params.to.unwrap_or(case.version);
+		let from_version = params.from.unwrap_or(to_version - 1);
+		let from = Self::get_version(mm, &case, from_version).await?;
+		let to = Self::get_version(mm, &case, to_version).await?;
+
+		let old = Self::load_document(mm, from.id).await?;
+		let new = Self::load_document(mm, to.id).await?;
+
+		Ok(CaseDiff {
+			safety_report_id: case.safety_report_id,
+			from: CaseDiffSide {
+				case_id: from.id,
+				version: from.version,
+			},
+			to: CaseDiffSide {
+				case_id: to.id,
+				version: to.version,
+			},
+			sections: diff_documents(&old, &new),
+		})
+	}
+
+	/// Gets the case holding `version` of the safety report of `case`.
+	async fn get_version(mm: &ModelManager, case: &Case, version: i32) -> Result<Case> {
+		if version == case.version {
+			return Ok(case.clone());
+		}
+		mm.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, Case>(
+					"SELECT * FROM cases
+					 WHERE organization_id = $1 AND safety_report_id = $2 AND version = $3",
+				)
+				.bind(case.organization_id)
+				.bind(&case.safety_report_id)
+				.bind(version),
+			)
+			.await?
+			.ok_or(Error::EntityNotFound {
+				entity: "case_versions",
+				id: version as i64,
+			})
+	}
+
+	/// Loads the compared columns of every section table of a case.
+	pub async fn load_document(mm: &ModelManager, case_id: Uuid) -> Result<CaseDocument> {
+		let mut document = CaseDocument::default();
+		let mut paths: HashMap<&'static str, HashMap<Uuid, String>> = HashMap::new();
+
+		for table in DIFF_TABLES {
+			let (parent_ids, parent_paths) = match table.parent {
+				Some((parent_table, _)) => {
+					let parent_paths = paths.get(parent_table).cloned().unwrap_or_default();
+					(parent_paths.keys().copied().collect::<Vec<_>>(), parent_paths)
+				}
+				None => (vec![case_id], HashMap::new()),
+			};
+			if parent_ids.is_empty() {
+				continue;
+			}
+
+			let fk = table.parent.map(|(_, fk)| fk).unwrap_or("case_id");
+			let (key_sql, join_sql) = match table.key {
+				DiffKey::Single => ("NULL::int", ""),
+				DiffKey::Sequence | DiffKey::Element => ("t.sequence_number", ""),
+				DiffKey::Reaction => {
+					("r.sequence_number", "JOIN reactions r ON r.id = t.reaction_id")
+				}
+			};
+			let sql = format!(
+				"SELECT t.id, t.{fk}, {key_sql}, to_jsonb(t)
+				 FROM {table} t {join_sql}
+				 WHERE t.{fk} = ANY($1)",
+				table = table.table
+			);
+			let rows = mm
+				.dbx()
+				.fetch_all(
+					sqlx::query_as::<_, (Uuid, Uuid, Option<i32>, JsonValue)>(&sql)
+						.bind(&parent_ids),
+				)
+				.await?;
+
+			let table_paths = paths.entry(table.table).or_default();
+			for (id, parent_id, sequence_number, row) in rows {
+				let values = table.elements.iter().map(|(column, _)| {
+					(*column, row.get(*column).and_then(diff_value))
+				});
+				let label = match table.key {
+					DiffKey::Element => row
+						.get("element_id")
+						.and_then(JsonValue::as_str)
+						.unwrap_or(table.group),
+					_ => table.group,
+				};
+				let path = document.insert(
+					table,
+					label,
+					parent_paths.get(&parent_id).map(String::as_str),
+					sequence_number,
+					values,
+				);
+				table_paths.insert(id, path);
+			}
+		}
+
+		Ok(document)
+	}
+}
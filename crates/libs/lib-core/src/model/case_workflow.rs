@@ -0,0 +1,343 @@
+// Case lifecycle workflow: allowed status transitions and who may perform them
+
+use crate::ctx::Ctx;
+use crate::model::acs::{
+	has_permission, Permission, CASE_APPROVE, CASE_UPDATE, XML_IMPORT,
+};
+use crate::model::base::DbBmc;
+use crate::model::case::{Case, CaseBmc, CaseForUpdate};
+use crate::model::reporting_deadline::CaseReportingDeadlineBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::types::time::OffsetDateTime;
+use sqlx::types::Uuid;
+
+// -- Statuses
+
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_CHECKED: &str = "checked";
+pub const STATUS_VALIDATED: &str = "validated";
+pub const STATUS_SUBMITTED: &str = "submitted";
+pub const STATUS_ACKNOWLEDGED: &str = "acknowledged";
+pub const STATUS_REJECTED: &str = "rejected";
+pub const STATUS_ARCHIVED: &str = "archived";
+pub const STATUS_NULLIFIED: &str = "nullified";
+
+/// Every value accepted by the `case_status_valid` constraint.
+pub const CASE_STATUSES: &[&str] = &[
+	STATUS_DRAFT,
+	STATUS_CHECKED,
+	STATUS_VALIDATED,
+	STATUS_SUBMITTED,
+	STATUS_ACKNOWLEDGED,
+	STATUS_REJECTED,
+	STATUS_ARCHIVED,
+	STATUS_NULLIFIED,
+];
+
+// -- Transitions
+
+/// Who drives a transition. `Validator` and `Acknowledgment` moves are made by
+/// the profile validator and inbound ACK processing, never by a user edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionTrigger {
+	Manual,
+	Validator,
+	Acknowledgment,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CaseTransition {
+	pub from: &'static str,
+	pub to: &'static str,
+	pub trigger: TransitionTrigger,
+	pub permission: Permission,
+	/// `audit_logs.action` recorded on top of the trigger-generated UPDATE.
+	pub audit_action: Option<&'static str>,
+}
+
+const fn transition(
+	from: &'static str,
+	to: &'static str,
+	trigger: TransitionTrigger,
+	permission: Permission,
+	audit_action: Option<&'static str>,
+) -> CaseTransition {
+	CaseTransition {
+		from,
+		to,
+		trigger,
+		permission,
+		audit_action,
+	}
+}
+
+use TransitionTrigger::{Acknowledgment, Manual, Validator};
+
+pub const CASE_TRANSITIONS: &[CaseTransition] = &[
+	// Data entry
+	transition(STATUS_DRAFT, STATUS_CHECKED, Manual, CASE_UPDATE, None),
+	transition(STATUS_CHECKED, STATUS_DRAFT, Manual, CASE_UPDATE, None),
+	transition(STATUS_VALIDATED, STATUS_DRAFT, Manual, CASE_UPDATE, None),
+	transition(STATUS_REJECTED, STATUS_DRAFT, Manual, CASE_UPDATE, None),
+	// Validation (profile validator only)
+	transition(STATUS_DRAFT, STATUS_VALIDATED, Validator, CASE_UPDATE, None),
+	transition(
+		STATUS_CHECKED,
+		STATUS_VALIDATED,
+		Validator,
+		CASE_UPDATE,
+		None,
+	),
+	// Submission
+	transition(
+		STATUS_VALIDATED,
+		STATUS_SUBMITTED,
+		Manual,
+		CASE_APPROVE,
+		Some("SUBMIT"),
+	),
+	// Regulator acknowledgment (ACK.B.r.6)
+	transition(
+		STATUS_VALIDATED,
+		STATUS_ACKNOWLEDGED,
+		Acknowledgment,
+		XML_IMPORT,
+		None,
+	),
+	transition(
+		STATUS_VALIDATED,
+		STATUS_REJECTED,
+		Acknowledgment,
+		XML_IMPORT,
+		None,
+	),
+	transition(
+		STATUS_SUBMITTED,
+		STATUS_ACKNOWLEDGED,
+		Acknowledgment,
+		XML_IMPORT,
+		None,
+	),
+	transition(
+		STATUS_SUBMITTED,
+		STATUS_REJECTED,
+		Acknowledgment,
+		XML_IMPORT,
+		None,
+	),
+	transition(
+		STATUS_ACKNOWLEDGED,
+		STATUS_REJECTED,
+		Acknowledgment,
+		XML_IMPORT,
+		None,
+	),
+	transition(
+		STATUS_REJECTED,
+		STATUS_ACKNOWLEDGED,
+		Acknowledgment,
+		XML_IMPORT,
+		None,
+	),
+	// Closing
+	transition(
+		STATUS_ACKNOWLEDGED,
+		STATUS_ARCHIVED,
+		Manual,
+		CASE_APPROVE,
+		None,
+	),
+	transition(
+		STATUS_SUBMITTED,
+		STATUS_NULLIFIED,
+		Manual,
+		CASE_APPROVE,
+		Some("NULLIFY"),
+	),
+	transition(
+		STATUS_ACKNOWLEDGED,
+		STATUS_NULLIFIED,
+		Manual,
+		CASE_APPROVE,
+		Some("NULLIFY"),
+	),
+	transition(
+		STATUS_REJECTED,
+		STATUS_NULLIFIED,
+		Manual,
+		CASE_APPROVE,
+		Some("NULLIFY"),
+	),
+];
+
+/// Looks up the transition `from -> to` available to `trigger`.
+pub fn find_transition(
+	from: &str,
+	to: &str,
+	trigger: TransitionTrigger,
+) -> Option<&'static CaseTransition> {
+	CASE_TRANSITIONS
+		.iter()
+		.find(|t| t.from == from && t.to == to && t.trigger == trigger)
+}
+
+/// Statuses reachable from `from` by `trigger`.
+pub fn next_statuses(from: &str, trigger: TransitionTrigger) -> Vec<&'static str> {
+	CASE_TRANSITIONS
+		.iter()
+		.filter(|t| t.from == from && t.trigger == trigger)
+		.map(|t| t.to)
+		.collect()
+}
+
+// -- BMC
+
+pub struct CaseWorkflowBmc;
+
+impl CaseWorkflowBmc {
+	/// Moves a case to `to` if the workflow allows it for `trigger` and the
+	/// caller's role holds the transition's permission. Moving to the current
+	/// status is a no-op.
+	///
+	/// Submission stamps `submitted_by`/`submitted_at`; transitions carrying an
+	/// audit action (SUBMIT, NULLIFY) also append that entry to `audit_logs`,
+	/// in the same transaction as the status change. The reporting deadline is
+	/// recomputed, since the status decides whether the clock is open.
+	pub async fn transition(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+		to: &str,
+		trigger: TransitionTrigger,
+		reason: Option<String>,
+	) -> Result<Case> {
+		Self::update_with_transition(ctx, mm, case_id, None, to, trigger, reason)
+			.await
+	}
+
+	/// Applies `data` (when given) and then moves the case to `to`, both in one
+	/// transaction, so a refused transition leaves the field edits unapplied.
+	/// `data.status` and the submission stamps are ignored; the workflow owns
+	/// them.
+	pub async fn update_with_transition(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+		data: Option<CaseForUpdate>,
+		to: &str,
+		trigger: TransitionTrigger,
+		reason: Option<String>,
+	) -> Result<Case> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+
+		let res = async {
+			if let Some(mut data) = data {
+				data.status = None;
+				data.submitted_by = None;
+				data.submitted_at = None;
+				CaseBmc::update(ctx, &mm, case_id, data).await?;
+			}
+			Self::transition_in_txn(ctx, &mm, case_id, to, trigger, reason).await
+		}
+		.await;
+		match res {
+			Ok(case) => {
+				dbx.commit_txn().await?;
+				Ok(case)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	/// [`Self::transition`] for callers that already hold a transaction on
+	/// `mm`, so the status change commits or rolls back with their own writes.
+	pub(crate) async fn transition_in_txn(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+		to: &str,
+		trigger: TransitionTrigger,
+		reason: Option<String>,
+	) -> Result<Case> {
+		let case = CaseBmc::get(ctx, mm, case_id).await?;
+		if case.status == to {
+			return Ok(case);
+		}
+
+		let transition =
+			find_transition(&case.status, to, trigger).ok_or_else(|| {
+				Error::CaseTransitionNotAllowed {
+					from: case.status.clone(),
+					to: to.to_string(),
+				}
+			})?;
+		if !has_permission(ctx.role(), transition.permission) {
+			return Err(Error::CaseTransitionPermissionDenied {
+				required_permission: transition.permission.to_string(),
+			});
+		}
+
+		let submitting = transition.to == STATUS_SUBMITTED;
+		CaseBmc::update(
+			ctx,
+			mm,
+			case_id,
+			CaseForUpdate {
+				safety_report_id: None,
+				dg_prd_key: None,
+				status: Some(transition.to.to_string()),
+				validation_profile: None,
+				submitted_by: submitting.then(|| ctx.user_id()),
+				submitted_at: submitting.then(OffsetDateTime::now_utc),
+				raw_xml: None,
+				dirty_c: None,
+				dirty_d: None,
+				dirty_e: None,
+				dirty_f: None,
+				dirty_g: None,
+				dirty_h: None,
+			},
+		)
+		.await?;
+
+		CaseReportingDeadlineBmc::refresh(ctx, mm, case_id).await?;
+
+		if let Some(action) = transition.audit_action {
+			// No RETURNING: the app role may append to audit_logs but not read it.
+			let sql = "INSERT INTO audit_logs (table_name, record_id, action, user_id, old_values, new_values) VALUES ($1, $2, $3, $4, $5, $6)";
+			mm.dbx()
+				.execute(
+					sqlx::query(sql)
+						.bind(CaseBmc::TABLE)
+						.bind(case_id)
+						.bind(action)
+						.bind(ctx.user_id())
+						.bind(json!({ "status": transition.from }))
+						.bind(json!({
+							"status": transition.to,
+							"reason": reason,
+						})),
+				)
+				.await?;
+		}
+
+		CaseBmc::get(ctx, mm, case_id).await
+	}
+}
@@ -0,0 +1,494 @@
+// Coding assistant: ranked MedDRA LLT / WHODrug candidates for verbatim text
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::terminology_version::{
+	default_version_condition, TerminologyDictionary, TerminologyVersionBmc,
+};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use sqlx::types::Uuid;
+
+// -- Types
+
+/// Dictionary a verbatim term is coded against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodingDictionary {
+	Meddra,
+	Whodrug,
+}
+
+impl CodingDictionary {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Meddra => "meddra",
+			Self::Whodrug => "whodrug",
+		}
+	}
+}
+
+/// How a candidate was found. A candidate found several ways lists each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodingMatchSource {
+	/// Previously accepted for this verbatim in the organization
+	Memory,
+	/// Term equals the verbatim (case and spacing ignored)
+	Exact,
+	/// Verbatim names a PT or non-current LLT (MedDRA), or another name or the
+	/// single ingredient of the drug record (WHODrug)
+	Synonym,
+	FullText,
+	Fuzzy,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodingSuggestParams {
+	pub verbatim: String,
+	pub dictionary: CodingDictionary,
+	/// Defaults to the organization's active version
+	pub version: Option<String>,
+	pub language: Option<String>,
+	#[serde(default = "default_limit")]
+	pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+	10
+}
+
+/// A ranked coding candidate; `score` is between 0 and 1.
+#[derive(Debug, Clone, Serialize)]
+pub struct CodingSuggestion {
+	pub dictionary: CodingDictionary,
+	pub code: String,
+	pub term: String,
+	pub version: String,
+	pub language: String,
+	/// MedDRA only: the PT of the LLT
+	pub pt_code: Option<String>,
+	pub score: f64,
+	pub sources: Vec<CodingMatchSource>,
+	/// Times this code was accepted for the verbatim in the organization
+	pub accept_count: i32,
+}
+
+/// A code picked by the user for a verbatim term.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodingAcceptance {
+	pub dictionary: CodingDictionary,
+	pub verbatim: String,
+	pub code: String,
+	/// Defaults to the organization's active version
+	pub version: Option<String>,
+	pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct CodingMemoryEntry {
+	pub id: Uuid,
+	pub dictionary: String,
+	pub verbatim: String,
+	pub code: String,
+	pub version: String,
+	pub term: String,
+	pub accept_count: i32,
+	pub last_accepted_at: OffsetDateTime,
+}
+
+/// Lookup key of a verbatim term: lower-cased with whitespace collapsed.
+pub fn verbatim_key(verbatim: &str) -> String {
+	verbatim
+		.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ")
+		.to_lowercase()
+}
+
+// -- Scoring
+
+const SCORE_EXACT: f64 = 1.0;
+const SCORE_SYNONYM: f64 = 0.9;
+/// Memory hits score 0.9 plus 0.01 per acceptance, up to 1.0; fuzzy memory
+/// hits are scaled by the similarity of the verbatim keys.
+const SCORE_MEMORY: f64 = 0.9;
+const MEMORY_ACCEPT_BONUS: f64 = 0.01;
+const MEMORY_ACCEPT_CAP: i32 = 10;
+/// Full-text hits score 0.5 to 0.8 by trigram similarity, fuzzy hits 0 to 0.8.
+const SCORE_FULL_TEXT: f64 = 0.5;
+const SCORE_SIMILARITY: f64 = 0.8;
+
+/// Candidate rows fetched per source before merging.
+const SOURCE_LIMIT: i64 = 50;
+
+#[derive(FromRow)]
+struct CandidateRow {
+	code: String,
+	term: String,
+	pt_code: Option<String>,
+	source: String,
+	score: f64,
+	accept_count: i32,
+}
+
+fn source_of(name: &str) -> CodingMatchSource {
+	match name {
+		"memory" => CodingMatchSource::Memory,
+		"exact" => CodingMatchSource::Exact,
+		"synonym" => CodingMatchSource::Synonym,
+		"full_text" => CodingMatchSource::FullText,
+		_ => CodingMatchSource::Fuzzy,
+	}
+}
+
+// -- Queries
+
+// Every query binds: $1 verbatim, $2 verbatim key, $3 version, $4 language,
+// $5 per-source limit, $6 organization, $7 dictionary.
+
+const MEMORY_SQL: &str = "
+	SELECT code, accept_count,
+	       CASE WHEN verbatim_key = $2 THEN 1.0::float8
+	            ELSE similarity(verbatim_key, $2)::float8 END AS key_similarity
+	FROM coding_memory
+	WHERE organization_id = $6 AND dictionary = $7
+	  AND (verbatim_key = $2 OR verbatim_key % $2)";
+
+fn meddra_sql() -> String {
+	format!(
+		"WITH memory AS ({MEMORY_SQL})
+		SELECT t.code, t.term, t.pt_code, 'memory' AS source,
+		       m.key_similarity * ({SCORE_MEMORY}
+		           + {MEMORY_ACCEPT_BONUS} * LEAST(m.accept_count, {MEMORY_ACCEPT_CAP}))::float8 AS score,
+		       m.accept_count
+		FROM memory m
+		JOIN meddra_terms t ON t.code = m.code
+		WHERE t.version = $3 AND t.language = $4 AND t.level = 'LLT'
+		  AND t.is_current AND t.active
+		UNION ALL
+		SELECT code, term, pt_code, 'exact', {SCORE_EXACT}::float8, 0
+		FROM meddra_terms
+		WHERE version = $3 AND language = $4 AND level = 'LLT' AND is_current AND active
+		  AND lower(term) = $2
+		UNION ALL
+		SELECT l.code, l.term, l.pt_code, 'synonym', {SCORE_SYNONYM}::float8, 0
+		FROM meddra_terms s
+		JOIN meddra_terms l
+		  ON l.version = s.version AND l.language = s.language
+		 AND l.code = COALESCE(s.pt_code, s.code) AND l.level = 'LLT'
+		WHERE s.version = $3 AND s.language = $4 AND lower(s.term) = $2
+		  AND (s.level = 'PT' OR (s.level = 'LLT' AND NOT s.is_current))
+		  AND l.is_current AND l.active
+		UNION ALL
+		(SELECT code, term, pt_code, 'full_text',
+		        {SCORE_FULL_TEXT} + ({SCORE_SIMILARITY} - {SCORE_FULL_TEXT})
+		            * similarity(lower(term), $2)::float8,
+		        0
+		 FROM meddra_terms
+		 WHERE version = $3 AND language = $4 AND level = 'LLT' AND is_current AND active
+		   AND to_tsvector('english', term) @@ plainto_tsquery('english', $1)
+		 ORDER BY 5 DESC LIMIT $5)
+		UNION ALL
+		(SELECT code, term, pt_code, 'fuzzy',
+		        {SCORE_SIMILARITY} * similarity(lower(term), $2)::float8, 0
+		 FROM meddra_terms
+		 WHERE version = $3 AND language = $4 AND level = 'LLT' AND is_current AND active
+		   AND lower(term) % $2
+		 ORDER BY 5 DESC LIMIT $5)"
+	)
+}
+
+fn whodrug_sql() -> String {
+	format!(
+		"WITH memory AS ({MEMORY_SQL})
+		SELECT p.code, p.drug_name AS term, NULL::text AS pt_code, 'memory' AS source,
+		       m.key_similarity * ({SCORE_MEMORY}
+		           + {MEMORY_ACCEPT_BONUS} * LEAST(m.accept_count, {MEMORY_ACCEPT_CAP}))::float8 AS score,
+		       m.accept_count
+		FROM memory m
+		JOIN whodrug_products p ON p.code = m.code
+		WHERE p.version = $3 AND p.language = $4 AND p.active
+		UNION ALL
+		SELECT code, drug_name, NULL, 'exact', {SCORE_EXACT}::float8, 0
+		FROM whodrug_products
+		WHERE version = $3 AND language = $4 AND active AND lower(drug_name) = $2
+		UNION ALL
+		SELECT code, drug_name, NULL, 'synonym', {SCORE_SYNONYM}::float8, 0
+		FROM whodrug_products
+		WHERE version = $3 AND language = $4 AND active
+		  AND sequence_number_1 = '01' AND sequence_number_2 = '001'
+		  AND lower(drug_name) <> $2
+		  AND drug_record_number IN (
+		      SELECT drug_record_number FROM whodrug_products
+		      WHERE version = $3 AND language = $4 AND lower(drug_name) = $2
+		      UNION
+		      SELECT left(product_code, 6) FROM whodrug_ingredients
+		      WHERE version = $3 AND language = $4
+		      GROUP BY product_code
+		      HAVING count(*) = 1 AND bool_and(lower(substance_name) = $2)
+		  )
+		UNION ALL
+		(SELECT code, drug_name, NULL, 'full_text',
+		        {SCORE_FULL_TEXT} + ({SCORE_SIMILARITY} - {SCORE_FULL_TEXT})
+		            * similarity(lower(drug_name), $2)::float8,
+		        0
+		 FROM whodrug_products
+		 WHERE version = $3 AND language = $4 AND active
+		   AND to_tsvector('english', drug_name) @@ plainto_tsquery('english', $1)
+		 ORDER BY 5 DESC LIMIT $5)
+		UNION ALL
+		(SELECT code, drug_name, NULL, 'fuzzy',
+		        {SCORE_SIMILARITY} * similarity(lower(drug_name), $2)::float8, 0
+		 FROM whodrug_products
+		 WHERE version = $3 AND language = $4 AND active
+		   AND lower(drug_name) % $2
+		 ORDER BY 5 DESC LIMIT $5)"
+	)
+}
+
+// -- Bmc
+
+pub struct CodingAssistantBmc;
+impl DbBmc for CodingAssistantBmc {
+	const TABLE: &'static str = "coding_memory";
+}
+
+impl CodingAssistantBmc {
+	/// Candidates for `params.verbatim`, best first. Exact, synonym, full-text
+	/// and trigram matches are merged per code, keeping the best score, and
+	/// codes the organization accepted for a similar verbatim are boosted.
+	pub async fn suggest(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		params: &CodingSuggestParams,
+	) -> Result<Vec<CodingSuggestion>> {
+		let key = verbatim_key(&params.verbatim);
+		if key.is_empty() {
+			return Ok(Vec::new());
+		}
+		let Some((version, language)) = Self::resolve_release(
+			ctx,
+			mm,
+			params.dictionary,
+			params.version.as_deref(),
+			params.language.as_deref(),
+			None,
+		)
+		.await?
+		else {
+			return Ok(Vec::new());
+		};
+
+		let sql = match params.dictionary {
+			CodingDictionary::Meddra => meddra_sql(),
+			CodingDictionary::Whodrug => whodrug_sql(),
+		};
+		let rows = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, CandidateRow>(&sql)
+					.bind(params.verbatim.trim())
+					.bind(&key)
+					.bind(&version)
+					.bind(&language)
+					.bind(SOURCE_LIMIT)
+					.bind(ctx.organization_id())
+					.bind(params.dictionary.as_str()),
+			)
+			.await?;
+
+		let mut suggestions: Vec<CodingSuggestion> = Vec::new();
+		for row in rows {
+			let source = source_of(&row.source);
+			match suggestions.iter_mut().find(|s| s.code == row.code) {
+				Some(found) => {
+					found.score = found.score.max(row.score);
+					found.accept_count = found.accept_count.max(row.accept_count);
+					if !found.sources.contains(&source) {
+						found.sources.push(source);
+					}
+				}
+				None => suggestions.push(CodingSuggestion {
+					dictionary: params.dictionary,
+					code: row.code,
+					term: row.term,
+					version: version.clone(),
+					language: language.clone(),
+					pt_code: row.pt_code,
+					score: row.score,
+					sources: vec![source],
+					accept_count: row.accept_count,
+				}),
+			}
+		}
+		suggestions.sort_by(|a, b| {
+			b.score
+				.total_cmp(&a.score)
+				.then_with(|| b.accept_count.cmp(&a.accept_count))
+				.then_with(|| a.term.cmp(&b.term))
+		});
+		suggestions.truncate(params.limit.max(0) as usize);
+		Ok(suggestions)
+	}
+
+	/// Records that `acceptance.code` was picked for the verbatim term, so
+	/// later suggestions for the same or a similar verbatim rank it first.
+	pub async fn accept(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		acceptance: CodingAcceptance,
+	) -> Result<CodingMemoryEntry> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::accept_in_txn(ctx, &mm, &acceptance).await {
+			Ok(entry) => {
+				dbx.commit_txn().await?;
+				Ok(entry)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	async fn accept_in_txn(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		acceptance: &CodingAcceptance,
+	) -> Result<CodingMemoryEntry> {
+		let key = verbatim_key(&acceptance.verbatim);
+		let not_found = || Error::CodingTermNotFound {
+			dictionary: acceptance.dictionary.as_str().to_string(),
+			code: acceptance.code.clone(),
+			version: acceptance.version.clone(),
+		};
+		if key.is_empty() {
+			return Err(not_found());
+		}
+		let Some((version, language)) = Self::resolve_release(
+			ctx,
+			mm,
+			acceptance.dictionary,
+			acceptance.version.as_deref(),
+			acceptance.language.as_deref(),
+			Some(&acceptance.code),
+		)
+		.await?
+		else {
+			return Err(not_found());
+		};
+		let term_sql = match acceptance.dictionary {
+			CodingDictionary::Meddra => {
+				"SELECT term FROM meddra_terms
+				 WHERE code = $1 AND version = $2 AND language = $3 AND level = 'LLT'"
+			}
+			CodingDictionary::Whodrug => {
+				"SELECT drug_name FROM whodrug_products
+				 WHERE code = $1 AND version = $2 AND language = $3"
+			}
+		};
+		let term: Option<(String,)> = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as(term_sql)
+					.bind(&acceptance.code)
+					.bind(&version)
+					.bind(&language),
+			)
+			.await?;
+		let Some((term,)) = term else {
+			return Err(not_found());
+		};
+
+		let sql = format!(
+			"INSERT INTO {} (organization_id, dictionary, verbatim, verbatim_key,
+			                 code, version, term, created_by)
+			 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+			 ON CONFLICT (organization_id, dictionary, verbatim_key, code) DO UPDATE
+			 SET accept_count = {0}.accept_count + 1,
+			     last_accepted_at = NOW(),
+			     verbatim = EXCLUDED.verbatim,
+			     version = EXCLUDED.version,
+			     term = EXCLUDED.term,
+			     updated_by = EXCLUDED.created_by
+			 RETURNING id, dictionary, verbatim, code, version, term,
+			           accept_count, last_accepted_at",
+			Self::TABLE
+		);
+		let entry = mm
+			.dbx()
+			.fetch_one(
+				sqlx::query_as::<_, CodingMemoryEntry>(&sql)
+					.bind(ctx.organization_id())
+					.bind(acceptance.dictionary.as_str())
+					.bind(acceptance.verbatim.trim())
+					.bind(&key)
+					.bind(&acceptance.code)
+					.bind(&version)
+					.bind(&term)
+					.bind(ctx.user_id()),
+			)
+			.await?;
+		Ok(entry)
+	}
+
+	/// Version and language to code against: the requested version, else the
+	/// organization's active one, else the most recently loaded release not
+	/// registered through the import endpoints (holding `code`, when given).
+	async fn resolve_release(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		dictionary: CodingDictionary,
+		version: Option<&str>,
+		language: Option<&str>,
+		code: Option<&str>,
+	) -> Result<Option<(String, String)>> {
+		let (table, terminology) = match dictionary {
+			CodingDictionary::Meddra => {
+				("meddra_terms", TerminologyDictionary::Meddra)
+			}
+			CodingDictionary::Whodrug => {
+				("whodrug_products", TerminologyDictionary::Whodrug)
+			}
+		};
+		let version = match version {
+			Some(version) => Some(version.to_string()),
+			None => {
+				TerminologyVersionBmc::active_version(ctx, mm, terminology).await?
+			}
+		};
+		let sql = format!(
+			"SELECT version, language FROM {table}
+			 WHERE {}
+			   AND language = $2
+			   AND ($3::text IS NULL OR code = $3)
+			 ORDER BY created_at DESC
+			 LIMIT 1",
+			default_version_condition(terminology, "$1")
+		);
+		let release = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, (String, String)>(&sql)
+					.bind(version)
+					.bind(language.unwrap_or("en"))
+					.bind(code),
+			)
+			.await?;
+		Ok(release)
+	}
+}
@@ -5,13 +5,15 @@ use crate::model::base::base_uuid;
 use crate::model::base::DbBmc;
 use crate::model::modql_utils::uuid_to_sea_value;
 use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::partial_date::PartialDate;
+use crate::model::whodrug::WhodrugIngredientBmc;
 use crate::model::ModelManager;
-use crate::model::Result;
+use crate::model::{Error, Result};
 use modql::field::Fields;
 use modql::filter::{FilterNodes, ListOptions, OpValsValue};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::types::time::{Date, OffsetDateTime, Time};
+use sqlx::types::time::{OffsetDateTime, Time};
 use sqlx::types::Uuid;
 use sqlx::FromRow;
 
@@ -70,6 +72,10 @@ pub struct DrugInformation {
 	// FDA.G.k.10a - Additional Information on Drug (coded)
 	pub fda_additional_info_coded: Option<String>,
 
+	// G.k.2.1.KR.1a/b - MFDS Product Code (MFDS)
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
+
 	// Timestamps
 	pub created_at: OffsetDateTime,
 	pub updated_at: OffsetDateTime,
@@ -105,6 +111,8 @@ pub struct DrugInformationForUpdate {
 	pub parent_route_termid_version: Option<String>,
 	pub parent_dosage_text: Option<String>,
 	pub fda_additional_info_coded: Option<String>,
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
 }
 
 // -- DrugActiveSubstance
@@ -125,6 +133,11 @@ pub struct DrugActiveSubstance {
 	// G.k.2.3.r.3 - Strength
 	pub strength_value: Option<Decimal>,
 	pub strength_unit: Option<String>,
+
+	// G.k.2.3.r.1.KR.1a/b - MFDS Ingredient Code (MFDS)
+	pub kr_ingredient_code_version: Option<String>,
+	pub kr_ingredient_code: Option<String>,
+
 	pub created_at: OffsetDateTime,
 	pub updated_at: OffsetDateTime,
 	pub created_by: Uuid,
@@ -140,6 +153,8 @@ pub struct DrugActiveSubstanceForCreate {
 	pub substance_termid_version: Option<String>,
 	pub strength_value: Option<Decimal>,
 	pub strength_unit: Option<String>,
+	pub kr_ingredient_code_version: Option<String>,
+	pub kr_ingredient_code: Option<String>,
 }
 
 #[derive(Fields, Deserialize)]
@@ -149,6 +164,16 @@ pub struct DrugActiveSubstanceForUpdate {
 	pub substance_termid_version: Option<String>,
 	pub strength_value: Option<Decimal>,
 	pub strength_unit: Option<String>,
+	pub kr_ingredient_code_version: Option<String>,
+	pub kr_ingredient_code: Option<String>,
+}
+
+/// A WHODrug product picked for a drug; `version` defaults to the latest
+/// loaded version holding the product.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DrugActiveSubstanceFromWhodrug {
+	pub code: String,
+	pub version: Option<String>,
 }
 
 #[derive(FilterNodes, Deserialize, Default)]
@@ -178,11 +203,11 @@ pub struct DosageInformation {
 	pub frequency_unit: Option<String>,
 
 	// G.k.4.r.4 - Date/Time of First Administration
-	pub first_administration_date: Option<Date>,
+	pub first_administration_date: Option<PartialDate>,
 	pub first_administration_time: Option<Time>,
 
 	// G.k.4.r.5 - Date/Time of Last Administration
-	pub last_administration_date: Option<Date>,
+	pub last_administration_date: Option<PartialDate>,
 	pub last_administration_time: Option<Time>,
 
 	// G.k.4.r.6 - Duration
@@ -224,9 +249,9 @@ pub struct DosageInformationForCreate {
 	pub number_of_units: Option<i32>,
 	pub frequency_value: Option<Decimal>,
 	pub frequency_unit: Option<String>,
-	pub first_administration_date: Option<Date>,
+	pub first_administration_date: Option<PartialDate>,
 	pub first_administration_time: Option<Time>,
-	pub last_administration_date: Option<Date>,
+	pub last_administration_date: Option<PartialDate>,
 	pub last_administration_time: Option<Time>,
 	pub duration_value: Option<Decimal>,
 	pub duration_unit: Option<String>,
@@ -250,9 +275,9 @@ pub struct DosageInformationForUpdate {
 	pub number_of_units: Option<i32>,
 	pub frequency_value: Option<Decimal>,
 	pub frequency_unit: Option<String>,
-	pub first_administration_date: Option<Date>,
+	pub first_administration_date: Option<PartialDate>,
 	pub first_administration_time: Option<Time>,
-	pub last_administration_date: Option<Date>,
+	pub last_administration_date: Option<PartialDate>,
 	pub last_administration_time: Option<Time>,
 	pub duration_value: Option<Decimal>,
 	pub duration_unit: Option<String>,
@@ -307,7 +332,7 @@ pub struct DrugIndicationForCreate {
 	pub indication_meddra_code: Option<String>,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct DrugIndicationForUpdate {
 	pub indication_text: Option<String>,
 	pub indication_meddra_version: Option<String>,
@@ -471,8 +496,10 @@ impl DrugInformationBmc {
 			     parent_route_termid_version = COALESCE($17, parent_route_termid_version),
 			     parent_dosage_text = COALESCE($18, parent_dosage_text),
 			     fda_additional_info_coded = COALESCE($19, fda_additional_info_coded),
+			     kr_product_code_version = COALESCE($20, kr_product_code_version),
+			     kr_product_code = COALESCE($21, kr_product_code),
 			     updated_at = now(),
-			     updated_by = $20
+			     updated_by = $22
 			 WHERE id = $1",
 			Self::TABLE
 		);
@@ -499,6 +526,8 @@ impl DrugInformationBmc {
 					.bind(drug_u.parent_route_termid_version)
 					.bind(drug_u.parent_dosage_text)
 					.bind(drug_u.fda_additional_info_coded)
+					.bind(drug_u.kr_product_code_version)
+					.bind(drug_u.kr_product_code)
 					.bind(ctx.user_id()),
 			)
 			.await?;
@@ -590,8 +619,10 @@ impl DrugInformationBmc {
 			     parent_route_termid_version = COALESCE($18, parent_route_termid_version),
 			     parent_dosage_text = COALESCE($19, parent_dosage_text),
 			     fda_additional_info_coded = COALESCE($20, fda_additional_info_coded),
+			     kr_product_code_version = COALESCE($21, kr_product_code_version),
+			     kr_product_code = COALESCE($22, kr_product_code),
 			     updated_at = now(),
-			     updated_by = $21
+			     updated_by = $23
 			 WHERE id = $1 AND case_id = $2",
 			Self::TABLE
 		);
@@ -619,6 +650,8 @@ impl DrugInformationBmc {
 					.bind(drug_u.parent_route_termid_version)
 					.bind(drug_u.parent_dosage_text)
 					.bind(drug_u.fda_additional_info_coded)
+					.bind(drug_u.kr_product_code_version)
+					.bind(drug_u.kr_product_code)
 					.bind(ctx.user_id()),
 			)
 			.await?;
@@ -732,6 +765,119 @@ impl DrugActiveSubstanceBmc {
 	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: Uuid) -> Result<()> {
 		base_uuid::delete::<Self>(ctx, mm, id).await
 	}
+
+	/// Adds the active ingredients of a WHODrug product as G.k.2.3.r
+	/// substances of the drug, after any it already has; substances already
+	/// present by name are skipped. Returns all substances of the drug.
+	pub async fn create_from_whodrug(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		drug_id: Uuid,
+		pick: DrugActiveSubstanceFromWhodrug,
+	) -> Result<Vec<DrugActiveSubstance>> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::create_from_whodrug_in_txn(ctx, &mm, drug_id, pick).await {
+			Ok(substances) => {
+				dbx.commit_txn().await?;
+				Ok(substances)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	async fn create_from_whodrug_in_txn(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		drug_id: Uuid,
+		pick: DrugActiveSubstanceFromWhodrug,
+	) -> Result<Vec<DrugActiveSubstance>> {
+		DrugInformationBmc::get(ctx, mm, drug_id).await?;
+		let release: Option<(String, String)> = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as(
+					"SELECT version, language FROM whodrug_products
+					 WHERE code = $1 AND ($2::text IS NULL OR version = $2)
+					 ORDER BY created_at DESC, (language = 'en') DESC
+					 LIMIT 1",
+				)
+				.bind(&pick.code)
+				.bind(pick.version.as_deref()),
+			)
+			.await?;
+		let Some((version, language)) = release else {
+			return Err(Error::WhodrugProductNotFound {
+				code: pick.code,
+				version: pick.version,
+			});
+		};
+		let ingredients = WhodrugIngredientBmc::list_for_products(
+			ctx,
+			mm,
+			&version,
+			&language,
+			&[pick.code],
+		)
+		.await?;
+
+		let sql = format!(
+			"SELECT * FROM {} WHERE drug_id = $1 ORDER BY sequence_number",
+			Self::TABLE
+		);
+		let existing = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, DrugActiveSubstance>(&sql).bind(drug_id))
+			.await?;
+		let mut names: Vec<String> = existing
+			.iter()
+			.filter_map(|s| s.substance_name.as_deref())
+			.map(str::to_lowercase)
+			.collect();
+		let mut sequence_number =
+			existing.iter().map(|s| s.sequence_number).max().unwrap_or(0);
+		for ingredient in ingredients {
+			let name = ingredient.substance_name.to_lowercase();
+			if names.contains(&name) {
+				continue;
+			}
+			names.push(name);
+			sequence_number += 1;
+			Self::create(
+				ctx,
+				mm,
+				DrugActiveSubstanceForCreate {
+					drug_id,
+					sequence_number,
+					substance_name: Some(ingredient.substance_name),
+					substance_termid: None,
+					substance_termid_version: None,
+					strength_value: None,
+					strength_unit: None,
+					kr_ingredient_code_version: None,
+					kr_ingredient_code: None,
+				},
+			)
+			.await?;
+		}
+
+		let substances = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, DrugActiveSubstance>(&sql).bind(drug_id))
+			.await?;
+		Ok(substances)
+	}
 }
 
 pub struct DosageInformationBmc;
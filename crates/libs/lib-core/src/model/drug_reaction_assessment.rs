@@ -53,7 +53,7 @@ pub struct DrugReactionAssessmentForCreate {
 	pub reaction_id: Uuid,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct DrugReactionAssessmentForUpdate {
 	pub time_interval_value: Option<Decimal>,
 	pub time_interval_unit: Option<String>,
@@ -89,6 +89,13 @@ pub struct RelatednessAssessment {
 	// G.k.9.i.2.r.3 - Result of Assessment
 	pub result_of_assessment: Option<String>,
 
+	// G.k.9.i.2.r.2.KR.1 - KR Method of Assessment (MFDS)
+	pub kr_method_of_assessment: Option<String>,
+
+	// G.k.9.i.2.r.3.KR.1 / KR.2 - KR Result of Assessment (MFDS)
+	pub kr_result_who_umc: Option<String>,
+	pub kr_result_korean: Option<String>,
+
 	// Timestamps
 	pub created_at: OffsetDateTime,
 	pub updated_at: OffsetDateTime,
@@ -107,6 +114,9 @@ pub struct RelatednessAssessmentForUpdate {
 	pub source_of_assessment: Option<String>,
 	pub method_of_assessment: Option<String>,
 	pub result_of_assessment: Option<String>,
+	pub kr_method_of_assessment: Option<String>,
+	pub kr_result_who_umc: Option<String>,
+	pub kr_result_korean: Option<String>,
 }
 
 #[derive(FilterNodes, Deserialize, Default)]
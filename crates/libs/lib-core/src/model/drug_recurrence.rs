@@ -46,7 +46,7 @@ pub struct DrugRecurrenceInformationForCreate {
 	pub sequence_number: i32,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct DrugRecurrenceInformationForUpdate {
 	pub rechallenge_action: Option<String>,
 	pub reaction_meddra_version: Option<String>,
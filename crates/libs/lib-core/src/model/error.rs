@@ -26,6 +26,64 @@ pub enum Error {
 
 	CountFail,
 
+	// -- Case workflow
+	CaseTransitionNotAllowed {
+		from: String,
+		to: String,
+	},
+	CaseTransitionPermissionDenied {
+		required_permission: String,
+	},
+	CaseSuperseded {
+		case_id: sqlx::types::Uuid,
+		superseded_by: sqlx::types::Uuid,
+	},
+
+	// -- Terminology
+	TerminologyLoad {
+		file: String,
+		message: String,
+	},
+	MeddraVersionNotLoaded {
+		version: String,
+		language: String,
+	},
+	WhodrugProductNotFound {
+		code: String,
+		version: Option<String>,
+	},
+	CodingTermNotFound {
+		dictionary: String,
+		code: String,
+		version: Option<String>,
+	},
+	TerminologyVersionExists {
+		dictionary: String,
+		version: String,
+		language: String,
+	},
+	TerminologyVersionNotFound {
+		dictionary: String,
+		version: String,
+		language: String,
+	},
+
+	// -- Validation rules
+	ValidationRuleInvalid {
+		code: String,
+		message: String,
+	},
+
+	// -- Units
+	UcumInvalidUnit {
+		unit: String,
+		message: String,
+	},
+	UcumNotConvertible {
+		from: String,
+		to: String,
+	},
+
 	// -- DB
 	UserAlreadyExists {
 		email: String,
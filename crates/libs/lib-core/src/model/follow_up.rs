@@ -0,0 +1,476 @@
+// Follow-up, amendment and nullification versions: deep copy of a case into
+// version N+1
+
+use crate::ctx::Ctx;
+use crate::model::audit::{CaseVersionBmc, CaseVersionForCreate};
+use crate::model::base::DbBmc;
+use crate::model::case::{Case, CaseBmc, CaseForCreate, CaseForUpdate};
+use crate::model::case_workflow::{
+	CaseWorkflowBmc, TransitionTrigger, STATUS_DRAFT, STATUS_NULLIFIED,
+};
+use crate::model::reporting_deadline::CaseReportingDeadlineBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::Uuid;
+
+/// C.1.11.1 Nullification/Amendment Code values.
+pub const NULLIFICATION_CODE: &str = "1";
+pub const AMENDMENT_CODE: &str = "2";
+
+// -- Types
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CaseFollowUpForCreate {
+	/// C.1.5 of the new version; today (UTC) when omitted.
+	pub date_of_most_recent_information: Option<Date>,
+	/// Marks the new version as an amendment (C.1.11.1 = 2) instead of a
+	/// follow-up carrying new information.
+	#[serde(default)]
+	pub amendment: bool,
+	/// C.1.11.2 reason, kept only for amendments.
+	pub amendment_reason: Option<String>,
+	pub change_reason: Option<String>,
+}
+
+/// A section table copied into the follow-up. Rows are selected through
+/// `parent_fk`, every column in `remap` is rewritten to the copied parent and
+/// `overrides` replace a column with an SQL expression over the source row `t`.
+struct SectionTable {
+	table: &'static str,
+	parent_fk: &'static str,
+	remap: &'static [&'static str],
+	overrides: &'static [(&'static str, &'static str)],
+}
+
+const fn section(
+	table: &'static str,
+	parent_fk: &'static str,
+	remap: &'static [&'static str],
+) -> SectionTable {
+	SectionTable {
+		table,
+		parent_fk,
+		remap,
+		overrides: &[],
+	}
+}
+
+/// Maps a source row id to its copy; `{}` is the column holding the old id.
+const REMAP_SQL: &str = "(SELECT r.new_id FROM unnest($1::uuid[], $2::uuid[]) AS r(old_id, new_id) WHERE r.old_id = t.\"{}\")";
+
+/// Parents come before their children so their new ids are already known.
+const SECTION_TABLES: &[SectionTable] = &[
+	// Section N: a new message (N.2.r.1) that is not part of any batch yet
+	SectionTable {
+		table: "message_headers",
+		parent_fk: "case_id",
+		remap: &["case_id"],
+		overrides: &[
+			(
+				"message_number",
+				"'MSG-' || (SELECT r.new_id FROM unnest($1::uuid[], $2::uuid[]) AS r(old_id, new_id) WHERE r.old_id = t.case_id)",
+			),
+			("batch_number", "NULL"),
+			("batch_transmission_date", "NULL"),
+		],
+	},
+	// Section C
+	section("safety_report_identification", "case_id", &["case_id"]),
+	section("sender_information", "case_id", &["case_id"]),
+	section("literature_references", "case_id", &["case_id"]),
+	section("documents_held_by_sender", "case_id", &["case_id"]),
+	section("study_information", "case_id", &["case_id"]),
+	section(
+		"study_registration_numbers",
+		"study_information_id",
+		&["study_information_id"],
+	),
+	section("primary_sources", "case_id", &["case_id"]),
+	section("receiver_information", "case_id", &["case_id"]),
+	section("other_case_identifiers", "case_id", &["case_id"]),
+	section("linked_report_numbers", "case_id", &["case_id"]),
+	// Section D
+	section("patient_information", "case_id", &["case_id"]),
+	section("patient_identifiers", "patient_id", &["patient_id"]),
+	section("medical_history_episodes", "patient_id", &["patient_id"]),
+	section("past_drug_history", "patient_id", &["patient_id"]),
+	section("patient_death_information", "patient_id", &["patient_id"]),
+	section("reported_causes_of_death", "death_info_id", &["death_info_id"]),
+	section("autopsy_causes_of_death", "death_info_id", &["death_info_id"]),
+	section("parent_information", "patient_id", &["patient_id"]),
+	section("parent_medical_history", "parent_id", &["parent_id"]),
+	section("parent_past_drug_history", "parent_id", &["parent_id"]),
+	// Section E / F
+	section("reactions", "case_id", &["case_id"]),
+	section("test_results", "case_id", &["case_id"]),
+	// Section G
+	section("drug_information", "case_id", &["case_id"]),
+	section("drug_active_substances", "drug_id", &["drug_id"]),
+	section("dosage_information", "drug_id", &["drug_id"]),
+	section("drug_indications", "drug_id", &["drug_id"]),
+	section("drug_device_characteristics", "drug_id", &["drug_id"]),
+	section("drug_recurrence_information", "drug_id", &["drug_id"]),
+	section(
+		"drug_reaction_assessments",
+		"drug_id",
+		&["drug_id", "reaction_id"],
+	),
+	section(
+		"relatedness_assessments",
+		"drug_reaction_assessment_id",
+		&["drug_reaction_assessment_id"],
+	),
+	// Section H
+	section("narrative_information", "case_id", &["case_id"]),
+	section("sender_diagnoses", "narrative_id", &["narrative_id"]),
+	section("case_summary_information", "narrative_id", &["narrative_id"]),
+	// Section J (PMDA)
+	section("case_jp_elements", "case_id", &["case_id"]),
+];
+
+/// Columns set by the copy itself rather than taken from the source row.
+const AUDIT_COLUMNS: &[&str] =
+	&["id", "created_at", "updated_at", "created_by", "updated_by"];
+
+/// What the new version changes on top of the copied sections.
+struct NewVersion {
+	status: &'static str,
+	date_of_most_recent_information: Date,
+	nullification_code: Option<&'static str>,
+	nullification_reason: Option<String>,
+	change_reason: String,
+}
+
+/// Old -> new row ids of everything copied so far, the case itself included.
+struct IdMap {
+	old: Vec<Uuid>,
+	new: Vec<Uuid>,
+}
+
+// -- BMC
+
+pub struct CaseFollowUpBmc;
+
+impl CaseFollowUpBmc {
+	/// Copies `case_id` and every section into a new draft case with the next
+	/// version of the same safety report id, then marks the source case as
+	/// superseded, which makes it read-only (see `19-case-follow-up-versions.sql`).
+	///
+	/// The copy gets a fresh N.2.r.1 message number, C.1.5 from `data`, and
+	/// C.1.11 set to amendment or cleared. Returns the new case id.
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+		data: CaseFollowUpForCreate,
+	) -> Result<Uuid> {
+		let source = Self::get_latest(ctx, mm, case_id).await?;
+		let (nullification_code, nullification_reason) = if data.amendment {
+			(Some(AMENDMENT_CODE), data.amendment_reason)
+		} else {
+			(None, None)
+		};
+		let change_reason = data.change_reason.unwrap_or_else(|| {
+			if data.amendment {
+				format!("Amendment of version {}", source.version)
+			} else {
+				format!("Follow-up of version {}", source.version)
+			}
+		});
+		let version = NewVersion {
+			status: STATUS_DRAFT,
+			date_of_most_recent_information: data
+				.date_of_most_recent_information
+				.unwrap_or_else(|| OffsetDateTime::now_utc().date()),
+			nullification_code,
+			nullification_reason,
+			change_reason,
+		};
+
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+
+		match Self::copy_case(ctx, mm, &source, version).await {
+			Ok(new_id) => {
+				dbx.commit_txn().await?;
+				Ok(new_id)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	/// Nullifies a submitted case: creates the nullification report as version
+	/// N+1 (C.1.11.1 = 1, C.1.11.2 = `reason`) and moves the source case to
+	/// `nullified` through the workflow, which records the NULLIFY audit entry
+	/// and requires the Approve permission. Returns the new case id.
+	///
+	/// The version is created `draft`; `nullify_case_xml` exports the minimal
+	/// nullification ICSR from it and moves it to `validated` once that report
+	/// passes validation.
+	pub async fn nullify(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+		reason: String,
+	) -> Result<Uuid> {
+		let source = Self::get_latest(ctx, mm, case_id).await?;
+		let version = NewVersion {
+			status: STATUS_DRAFT,
+			date_of_most_recent_information: OffsetDateTime::now_utc().date(),
+			nullification_code: Some(NULLIFICATION_CODE),
+			nullification_reason: Some(reason.clone()),
+			change_reason: format!("Nullification of version {}", source.version),
+		};
+
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+
+		let res = async {
+			let new_id = Self::copy_case(ctx, mm, &source, version).await?;
+			CaseWorkflowBmc::transition_in_txn(
+				ctx,
+				mm,
+				source.id,
+				STATUS_NULLIFIED,
+				TransitionTrigger::Manual,
+				Some(reason),
+			)
+			.await?;
+			Ok(new_id)
+		}
+		.await;
+		match res {
+			Ok(new_id) => {
+				dbx.commit_txn().await?;
+				Ok(new_id)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	/// Gets a case that has not been superseded by a later version yet.
+	async fn get_latest(ctx: &Ctx, mm: &ModelManager, case_id: Uuid) -> Result<Case> {
+		let case = CaseBmc::get(ctx, mm, case_id).await?;
+		if let Some(superseded_by) = case.superseded_by {
+			return Err(Error::CaseSuperseded {
+				case_id,
+				superseded_by,
+			});
+		}
+		Ok(case)
+	}
+
+	async fn copy_case(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		source: &Case,
+		data: NewVersion,
+	) -> Result<Uuid> {
+		let dbx = mm.dbx();
+		let (max_version,) = dbx
+			.fetch_one(
+				sqlx::query_as::<_, (Option<i32>,)>(
+					"SELECT max(version) FROM cases WHERE safety_report_id = $1",
+				)
+				.bind(&source.safety_report_id),
+			)
+			.await?;
+		let version = max_version.unwrap_or(source.version).max(source.version) + 1;
+
+		let new_id = CaseBmc::create(
+			ctx,
+			mm,
+			CaseForCreate {
+				organization_id: source.organization_id,
+				safety_report_id: source.safety_report_id.clone(),
+				dg_prd_key: source.dg_prd_key.clone(),
+				status: Some(data.status.to_string()),
+				validation_profile: source.validation_profile.clone(),
+				version: Some(version),
+			},
+		)
+		.await?;
+
+		let mut ids = IdMap {
+			old: vec![source.id],
+			new: vec![new_id],
+		};
+		for section in SECTION_TABLES {
+			copy_section(ctx, mm, section, &mut ids).await?;
+		}
+
+		let most_recent = data.date_of_most_recent_information;
+		dbx.execute(
+			sqlx::query(
+				"UPDATE safety_report_identification
+				 SET date_of_most_recent_information = $2,
+				     nullification_code = $3,
+				     nullification_reason = $4
+				 WHERE case_id = $1",
+			)
+			.bind(new_id)
+			.bind(most_recent)
+			.bind(data.nullification_code)
+			.bind(data.nullification_reason.clone()),
+		)
+		.await?;
+
+		// Raw XML stays the export base; section C always differs from it now.
+		CaseBmc::update(
+			ctx,
+			mm,
+			new_id,
+			CaseForUpdate {
+				safety_report_id: None,
+				dg_prd_key: None,
+				status: None,
+				validation_profile: None,
+				submitted_by: None,
+				submitted_at: None,
+				raw_xml: source.raw_xml.clone(),
+				dirty_c: Some(true),
+				dirty_d: Some(source.dirty_d),
+				dirty_e: Some(source.dirty_e),
+				dirty_f: Some(source.dirty_f),
+				dirty_g: Some(source.dirty_g),
+				dirty_h: Some(source.dirty_h),
+			},
+		)
+		.await?;
+
+		CaseVersionBmc::create(
+			ctx,
+			mm,
+			CaseVersionForCreate {
+				case_id: new_id,
+				version,
+				snapshot: json!({
+					"source_case_id": source.id,
+					"source_version": source.version,
+					"nullification_code": data.nullification_code,
+					"date_of_most_recent_information": most_recent.to_string(),
+				}),
+				change_reason: Some(data.change_reason),
+			},
+		)
+		.await?;
+
+		dbx.execute(
+			sqlx::query(&format!(
+				"UPDATE {} SET superseded_by = $2 WHERE id = $1",
+				CaseBmc::TABLE
+			))
+			.bind(source.id)
+			.bind(new_id),
+		)
+		.await?;
+
+		CaseReportingDeadlineBmc::refresh(ctx, mm, new_id).await?;
+
+		Ok(new_id)
+	}
+}
+
+/// Copies the rows of one section table whose parent was copied already,
+/// recording their new ids in `ids`.
+async fn copy_section(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	section: &SectionTable,
+	ids: &mut IdMap,
+) -> Result<()> {
+	let dbx = mm.dbx();
+	let rows = dbx
+		.fetch_all(
+			sqlx::query_as::<_, (Uuid,)>(&format!(
+				"SELECT id FROM {} WHERE {} = ANY($1)",
+				section.table, section.parent_fk
+			))
+			.bind(&ids.old),
+		)
+		.await?;
+	if rows.is_empty() {
+		return Ok(());
+	}
+	for (old_id,) in rows {
+		ids.old.push(old_id);
+		ids.new.push(Uuid::new_v4());
+	}
+
+	let columns = dbx
+		.fetch_all(
+			sqlx::query_as::<_, (String,)>(
+				"SELECT column_name::text FROM information_schema.columns
+				 WHERE table_schema = current_schema() AND table_name = $1
+				   AND is_generated = 'NEVER'
+				 ORDER BY ordinal_position",
+			)
+			.bind(section.table),
+		)
+		.await?;
+	let columns: Vec<String> = columns
+		.into_iter()
+		.map(|(name,)| name)
+		.filter(|name| !AUDIT_COLUMNS.contains(&name.as_str()))
+		.collect();
+
+	let mut insert_columns = vec!["id".to_string(), "created_by".to_string()];
+	let mut select_values = vec!["m.new_id".to_string(), "$3".to_string()];
+	for column in &columns {
+		insert_columns.push(format!("\"{column}\""));
+		let value = section
+			.overrides
+			.iter()
+			.find(|(name, _)| name == column)
+			.map(|(_, expr)| expr.to_string());
+		if let Some(value) = value {
+			select_values.push(value);
+		} else if section.remap.contains(&column.as_str()) {
+			select_values.push(REMAP_SQL.replace("{}", column));
+		} else {
+			select_values.push(format!("t.\"{column}\""));
+		}
+	}
+
+	let sql = format!(
+		"INSERT INTO {table} ({columns})
+		 SELECT {values}
+		 FROM {table} t
+		 JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = t.id",
+		table = section.table,
+		columns = insert_columns.join(", "),
+		values = select_values.join(", "),
+	);
+	dbx.execute(
+		sqlx::query(&sql)
+			.bind(&ids.old)
+			.bind(&ids.new)
+			.bind(ctx.user_id()),
+	)
+	.await?;
+	Ok(())
+}
@@ -0,0 +1,145 @@
+// J.2 - J.20 - PMDA (Japan) regional data elements
+
+use crate::ctx::Ctx;
+use crate::model::base::base_uuid;
+use crate::model::base::DbBmc;
+use crate::model::modql_utils::uuid_to_sea_value;
+use crate::model::ModelManager;
+use crate::model::Result;
+use modql::field::Fields;
+use modql::filter::{FilterNodes, ListOptions, OpValsValue};
+use serde::{Deserialize, Serialize};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::types::Uuid;
+use sqlx::FromRow;
+
+/// J.2 Reporting Category (coded, list `jp_reporting_category`).
+pub const JP_REPORTING_CATEGORY_ELEMENT: &str = "J.2";
+/// J.4 Reporting Deadline (coded, list `jp_reporting_deadline`).
+pub const JP_REPORTING_DEADLINE_ELEMENT: &str = "J.4";
+
+/// J element numbers carried by the PMDA profile (J.2 to J.20).
+pub const JP_ELEMENT_RANGE: std::ops::RangeInclusive<u32> = 2..=20;
+
+/// Whether `element_id` names a PMDA regional element (`J.2` ... `J.20`).
+pub fn is_jp_element_id(element_id: &str) -> bool {
+	element_id
+		.trim()
+		.strip_prefix("J.")
+		.and_then(|number| number.parse::<u32>().ok())
+		.is_some_and(|number| JP_ELEMENT_RANGE.contains(&number))
+}
+
+/// Whether the element carries a code rather than free text.
+pub fn is_coded_jp_element(element_id: &str) -> bool {
+	matches!(
+		element_id.trim(),
+		JP_REPORTING_CATEGORY_ELEMENT | JP_REPORTING_DEADLINE_ELEMENT
+	)
+}
+
+// -- CaseJpElement
+
+#[derive(Debug, Clone, Fields, FromRow, Serialize)]
+pub struct CaseJpElement {
+	pub id: Uuid,
+	pub case_id: Uuid,
+
+	// Element ID (J.2 ... J.20)
+	pub element_id: String,
+	pub sequence_number: i32,
+
+	// Code for J.2/J.4, text otherwise
+	pub value: Option<String>,
+
+	// Timestamps
+	pub created_at: OffsetDateTime,
+	pub updated_at: OffsetDateTime,
+	pub created_by: Uuid,
+	pub updated_by: Option<Uuid>,
+}
+
+#[derive(Fields, Deserialize)]
+pub struct CaseJpElementForCreate {
+	pub case_id: Uuid,
+	pub element_id: String,
+	pub sequence_number: i32,
+	pub value: Option<String>,
+}
+
+#[derive(Fields, Deserialize)]
+pub struct CaseJpElementForUpdate {
+	pub value: Option<String>,
+}
+
+#[derive(FilterNodes, Deserialize, Default)]
+pub struct CaseJpElementFilter {
+	#[modql(to_sea_value_fn = "uuid_to_sea_value")]
+	pub case_id: Option<OpValsValue>,
+	pub element_id: Option<OpValsValue>,
+}
+
+// -- BMC
+
+pub struct CaseJpElementBmc;
+impl DbBmc for CaseJpElementBmc {
+	const TABLE: &'static str = "case_jp_elements";
+}
+
+impl CaseJpElementBmc {
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		data: CaseJpElementForCreate,
+	) -> Result<Uuid> {
+		base_uuid::create::<Self, _>(ctx, mm, data).await
+	}
+
+	pub async fn get(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: Uuid,
+	) -> Result<CaseJpElement> {
+		base_uuid::get::<Self, _>(ctx, mm, id).await
+	}
+
+	pub async fn list(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filters: Option<Vec<CaseJpElementFilter>>,
+		list_options: Option<ListOptions>,
+	) -> Result<Vec<CaseJpElement>> {
+		base_uuid::list::<Self, _, _>(ctx, mm, filters, list_options).await
+	}
+
+	/// All J elements of a case, in element then sequence order.
+	pub async fn list_by_case(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<Vec<CaseJpElement>> {
+		let sql = format!(
+			"SELECT * FROM {} WHERE case_id = $1
+			 ORDER BY length(element_id), element_id, sequence_number",
+			Self::TABLE
+		);
+		let elements = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, CaseJpElement>(&sql).bind(case_id))
+			.await?;
+		Ok(elements)
+	}
+
+	pub async fn update(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: Uuid,
+		data: CaseJpElementForUpdate,
+	) -> Result<()> {
+		base_uuid::update::<Self, _>(ctx, mm, id, data).await
+	}
+
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: Uuid) -> Result<()> {
+		base_uuid::delete::<Self>(ctx, mm, id).await
+	}
+}
@@ -0,0 +1,667 @@
+// MedDRA hierarchy (LLT -> PT -> HLT -> HLGT -> SOC) and ASCII distribution loader
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use sqlx::types::Uuid;
+use std::collections::{HashMap, HashSet};
+
+pub const LEVEL_LLT: &str = "LLT";
+pub const LEVEL_PT: &str = "PT";
+pub const LEVEL_HLT: &str = "HLT";
+pub const LEVEL_HLGT: &str = "HLGT";
+pub const LEVEL_SOC: &str = "SOC";
+
+// -- Types
+
+/// One PT -> HLT -> HLGT -> SOC placement with the term names of each level.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct MeddraPath {
+	pub version: String,
+	pub language: String,
+	pub pt_code: String,
+	pub pt_term: Option<String>,
+	pub hlt_code: String,
+	pub hlt_term: Option<String>,
+	pub hlgt_code: String,
+	pub hlgt_term: Option<String>,
+	pub soc_code: String,
+	pub soc_term: Option<String>,
+	pub primary_soc: bool,
+}
+
+/// Reactions of a case that share a primary SOC. `soc_code` is `None` for
+/// reactions that are uncoded or coded against a version not loaded.
+#[derive(Debug, Clone, Serialize)]
+pub struct SocReactionGroup {
+	pub soc_code: Option<String>,
+	pub soc_term: Option<String>,
+	pub reaction_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, FromRow)]
+struct ReactionSocRow {
+	reaction_id: Uuid,
+	soc_code: Option<String>,
+	soc_term: Option<String>,
+}
+
+/// A term row of a MedDRA release, as read from the ASCII files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeddraAsciiTerm {
+	pub code: String,
+	pub term: String,
+	pub level: &'static str,
+	/// LLT: the PT it belongs to; PT: its own code.
+	pub pt_code: Option<String>,
+	/// LLT currency (`llt_currency = 'Y'`); always true for other levels.
+	pub is_current: bool,
+}
+
+/// A PT placement of a MedDRA release, as read from the ASCII files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeddraAsciiLink {
+	pub pt_code: String,
+	pub hlt_code: String,
+	pub hlgt_code: String,
+	pub soc_code: String,
+	pub primary_soc: bool,
+}
+
+/// A MedDRA release parsed from its ASCII distribution files.
+#[derive(Debug, Clone, Default)]
+pub struct MeddraRelease {
+	/// From `meddra_release.asc` when present.
+	pub version: Option<String>,
+	/// ISO 639-1 code derived from `meddra_release.asc` when present.
+	pub language: Option<String>,
+	pub terms: Vec<MeddraAsciiTerm>,
+	pub hierarchy: Vec<MeddraAsciiLink>,
+}
+
+impl MeddraRelease {
+	pub fn count(&self, level: &str) -> usize {
+		self.terms.iter().filter(|t| t.level == level).count()
+	}
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeddraLoadResult {
+	pub version: String,
+	pub language: String,
+	pub llt_count: usize,
+	pub pt_count: usize,
+	pub hlt_count: usize,
+	pub hlgt_count: usize,
+	pub soc_count: usize,
+	pub hierarchy_count: usize,
+}
+
+// -- ASCII parsing
+
+/// Parses the MedDRA ASCII distribution (`llt.asc`, `pt.asc`, `hlt.asc`,
+/// `hlgt.asc`, `soc.asc` and `mdhier.asc`, or `hlt_pt.asc`, `hlgt_hlt.asc`
+/// and `soc_hlgt.asc` in its place). Files are matched by name, case
+/// insensitively and ignoring directories; other files are skipped.
+pub fn parse_meddra_ascii(files: &[(String, Vec<u8>)]) -> Result<MeddraRelease> {
+	let mut by_name: HashMap<String, &[u8]> = HashMap::new();
+	for (name, content) in files {
+		let base = name
+			.rsplit(['/', '\\'])
+			.next()
+			.unwrap_or(name)
+			.to_lowercase();
+		by_name.insert(base, content.as_slice());
+	}
+	let file =
+		|name: &str| by_name.get(name).map(|content| AscFile::new(name, content));
+	let required = |name: &str| {
+		file(name).ok_or_else(|| Error::TerminologyLoad {
+			file: name.to_string(),
+			message: "file missing from the MedDRA distribution".to_string(),
+		})
+	};
+
+	let mut release = MeddraRelease::default();
+	if let Some(release_file) = file("meddra_release.asc") {
+		if let Some(fields) = release_file.rows().next() {
+			release.version = fields.first().filter(|v| !v.is_empty()).cloned();
+			release.language = fields.get(1).and_then(|l| language_code(l));
+		}
+	}
+
+	// llt_code$llt_name$pt_code$...$llt_currency$...
+	for (line, fields) in required("llt.asc")?.numbered_rows() {
+		let [code, term, pt_code] = fields_at(&fields, "llt.asc", line, [0, 1, 2])?;
+		release.terms.push(MeddraAsciiTerm {
+			code,
+			term,
+			level: LEVEL_LLT,
+			pt_code: Some(pt_code),
+			is_current: fields.get(9).is_none_or(|c| !c.eq_ignore_ascii_case("N")),
+		});
+	}
+	// pt_code$pt_name$null_field$pt_soc_code$...
+	let mut pt_primary_soc = HashMap::new();
+	for (line, fields) in required("pt.asc")?.numbered_rows() {
+		let [code, term] = fields_at(&fields, "pt.asc", line, [0, 1])?;
+		if let Some(soc) = fields.get(3).filter(|s| !s.is_empty()) {
+			pt_primary_soc.insert(code.clone(), soc.clone());
+		}
+		release.terms.push(MeddraAsciiTerm {
+			pt_code: Some(code.clone()),
+			code,
+			term,
+			level: LEVEL_PT,
+			is_current: true,
+		});
+	}
+
+	// pt_code$hlt_code$hlgt_code$soc_code$pt_name$hlt_name$hlgt_name$soc_name$
+	// soc_abbrev$null_field$pt_soc_code$primary_soc_fg$
+	let mut names: HashMap<(&'static str, String), String> = HashMap::new();
+	if let Some(mdhier) = file("mdhier.asc") {
+		for (line, fields) in mdhier.numbered_rows() {
+			let [pt_code, hlt_code, hlgt_code, soc_code] =
+				fields_at(&fields, "mdhier.asc", line, [0, 1, 2, 3])?;
+			for (level, code, idx) in [
+				(LEVEL_HLT, &hlt_code, 5),
+				(LEVEL_HLGT, &hlgt_code, 6),
+				(LEVEL_SOC, &soc_code, 7),
+			] {
+				if let Some(name) = fields.get(idx).filter(|n| !n.is_empty()) {
+					names.insert((level, code.clone()), name.clone());
+				}
+			}
+			let primary_soc = fields
+				.get(11)
+				.map(|f| f.eq_ignore_ascii_case("Y"))
+				.unwrap_or_else(|| pt_primary_soc.get(&pt_code) == Some(&soc_code));
+			release.hierarchy.push(MeddraAsciiLink {
+				pt_code,
+				hlt_code,
+				hlgt_code,
+				soc_code,
+				primary_soc,
+			});
+		}
+	} else {
+		let hlt_pt = pairs(required("hlt_pt.asc")?)?;
+		let mut hlgts_of_hlt: HashMap<String, Vec<String>> = HashMap::new();
+		for (hlgt, hlt) in pairs(required("hlgt_hlt.asc")?)? {
+			hlgts_of_hlt.entry(hlt).or_default().push(hlgt);
+		}
+		let mut socs_of_hlgt: HashMap<String, Vec<String>> = HashMap::new();
+		for (soc, hlgt) in pairs(required("soc_hlgt.asc")?)? {
+			socs_of_hlgt.entry(hlgt).or_default().push(soc);
+		}
+		for (hlt_code, pt_code) in hlt_pt {
+			for hlgt_code in hlgts_of_hlt.get(&hlt_code).into_iter().flatten() {
+				for soc_code in socs_of_hlgt.get(hlgt_code).into_iter().flatten() {
+					release.hierarchy.push(MeddraAsciiLink {
+						primary_soc: pt_primary_soc.get(&pt_code) == Some(soc_code),
+						pt_code: pt_code.clone(),
+						hlt_code: hlt_code.clone(),
+						hlgt_code: hlgt_code.clone(),
+						soc_code: soc_code.clone(),
+					});
+				}
+			}
+		}
+	}
+
+	// hlt.asc, hlgt.asc, soc.asc: code$name$...; names from mdhier.asc fill in
+	// for a missing file.
+	for (file_name, level) in [
+		("hlt.asc", LEVEL_HLT),
+		("hlgt.asc", LEVEL_HLGT),
+		("soc.asc", LEVEL_SOC),
+	] {
+		if let Some(level_file) = file(file_name) {
+			for (line, fields) in level_file.numbered_rows() {
+				let [code, term] = fields_at(&fields, file_name, line, [0, 1])?;
+				names.insert((level, code), term);
+			}
+		}
+	}
+	if names.is_empty() {
+		return Err(Error::TerminologyLoad {
+			file: "hlt.asc".to_string(),
+			message: "no HLT/HLGT/SOC names: provide hlt.asc, hlgt.asc and soc.asc or mdhier.asc".to_string(),
+		});
+	}
+	let mut upper: Vec<_> = names.into_iter().collect();
+	upper.sort_by(|a, b| {
+		level_rank(a.0 .0)
+			.cmp(&level_rank(b.0 .0))
+			.then_with(|| a.0 .1.cmp(&b.0 .1))
+	});
+	release
+		.terms
+		.extend(
+			upper
+				.into_iter()
+				.map(|((level, code), term)| MeddraAsciiTerm {
+					code,
+					term,
+					level,
+					pt_code: None,
+					is_current: true,
+				}),
+		);
+
+	Ok(release)
+}
+
+/// A `$`-delimited MedDRA ASCII file.
+struct AscFile<'a> {
+	name: String,
+	content: std::borrow::Cow<'a, str>,
+}
+
+impl<'a> AscFile<'a> {
+	fn new(name: &str, content: &'a [u8]) -> Self {
+		Self {
+			name: name.to_string(),
+			content: String::from_utf8_lossy(content),
+		}
+	}
+
+	fn rows(&self) -> impl Iterator<Item = Vec<String>> + '_ {
+		self.numbered_rows().map(|(_, fields)| fields)
+	}
+
+	/// Non-empty rows with their 1-based line number.
+	fn numbered_rows(&self) -> impl Iterator<Item = (usize, Vec<String>)> + '_ {
+		self.content
+			.lines()
+			.enumerate()
+			.map(|(idx, line)| {
+				(idx + 1, line.trim_start_matches('\u{feff}').trim_end())
+			})
+			.filter(|(_, line)| !line.is_empty())
+			.map(|(idx, line)| {
+				let line = line.strip_suffix('$').unwrap_or(line);
+				(idx, line.split('$').map(|f| f.trim().to_string()).collect())
+			})
+	}
+}
+
+fn fields_at<const N: usize>(
+	fields: &[String],
+	file: &str,
+	line: usize,
+	idx: [usize; N],
+) -> Result<[String; N]> {
+	let mut out: [String; N] = std::array::from_fn(|_| String::new());
+	for (slot, i) in out.iter_mut().zip(idx) {
+		match fields.get(i).filter(|f| !f.is_empty()) {
+			Some(value) => *slot = value.clone(),
+			None => {
+				return Err(Error::TerminologyLoad {
+					file: file.to_string(),
+					message: format!("line {line}: field {} is empty", i + 1),
+				})
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Two-column link files (`hlt_pt.asc`, `hlgt_hlt.asc`, `soc_hlgt.asc`).
+fn pairs(file: AscFile<'_>) -> Result<Vec<(String, String)>> {
+	file.numbered_rows()
+		.map(|(line, fields)| {
+			let [parent, child] = fields_at(&fields, &file.name, line, [0, 1])?;
+			Ok((parent, child))
+		})
+		.collect()
+}
+
+fn level_rank(level: &str) -> u8 {
+	match level {
+		LEVEL_SOC => 0,
+		LEVEL_HLGT => 1,
+		LEVEL_HLT => 2,
+		LEVEL_PT => 3,
+		_ => 4,
+	}
+}
+
+/// MedDRA release language name to ISO 639-1.
+fn language_code(name: &str) -> Option<String> {
+	let code = match name.trim().to_lowercase().as_str() {
+		"english" => "en",
+		"japanese" => "ja",
+		"chinese" => "zh",
+		"korean" => "ko",
+		"french" => "fr",
+		"german" => "de",
+		"spanish" => "es",
+		"italian" => "it",
+		"portuguese" | "portuguese (brazilian)" => "pt",
+		"dutch" => "nl",
+		"czech" => "cs",
+		"hungarian" => "hu",
+		"russian" => "ru",
+		"greek" => "el",
+		"swedish" => "sv",
+		"latvian" => "lv",
+		"polish" => "pl",
+		"arabic" => "ar",
+		other if other.len() == 2 => return Some(other.to_string()),
+		_ => return None,
+	};
+	Some(code.to_string())
+}
+
+// -- Bmc
+
+pub struct MeddraHierarchyBmc;
+impl DbBmc for MeddraHierarchyBmc {
+	const TABLE: &'static str = "meddra_hierarchy";
+}
+
+impl MeddraHierarchyBmc {
+	/// Loads a MedDRA ASCII distribution as `version`/`language`, replacing
+	/// any terms and hierarchy already loaded for that version and language.
+	/// `version` and `language` default to `meddra_release.asc`.
+	pub async fn load_ascii(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		version: Option<&str>,
+		language: Option<&str>,
+		files: &[(String, Vec<u8>)],
+	) -> Result<MeddraLoadResult> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::load_ascii_in_txn(&mm, version, language, files).await {
+			Ok(result) => {
+				dbx.commit_txn().await?;
+				Ok(result)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	/// `load_ascii` within the caller's transaction.
+	pub(crate) async fn load_ascii_in_txn(
+		mm: &ModelManager,
+		version: Option<&str>,
+		language: Option<&str>,
+		files: &[(String, Vec<u8>)],
+	) -> Result<MeddraLoadResult> {
+		let release = parse_meddra_ascii(files)?;
+		let version = version
+			.map(str::to_string)
+			.or_else(|| release.version.clone())
+			.ok_or_else(|| Error::TerminologyLoad {
+				file: "meddra_release.asc".to_string(),
+				message:
+					"MedDRA version not given and not found in the distribution"
+						.to_string(),
+			})?;
+		let language = language
+			.map(str::to_string)
+			.or_else(|| release.language.clone())
+			.unwrap_or_else(|| "en".to_string());
+
+		Self::write_release(mm, &version, &language, &release).await?;
+
+		Ok(MeddraLoadResult {
+			llt_count: release.count(LEVEL_LLT),
+			pt_count: release.count(LEVEL_PT),
+			hlt_count: release.count(LEVEL_HLT),
+			hlgt_count: release.count(LEVEL_HLGT),
+			soc_count: release.count(LEVEL_SOC),
+			hierarchy_count: release.hierarchy.len(),
+			version,
+			language,
+		})
+	}
+
+	async fn write_release(
+		mm: &ModelManager,
+		version: &str,
+		language: &str,
+		release: &MeddraRelease,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+		dbx.execute(
+			sqlx::query(&format!(
+				"DELETE FROM {} WHERE version = $1 AND language = $2",
+				Self::TABLE
+			))
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+		dbx.execute(
+			sqlx::query(
+				"DELETE FROM meddra_terms WHERE version = $1 AND language = $2",
+			)
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+
+		let mut codes = Vec::with_capacity(release.terms.len());
+		let mut terms = Vec::with_capacity(release.terms.len());
+		let mut levels = Vec::with_capacity(release.terms.len());
+		let mut pt_codes = Vec::with_capacity(release.terms.len());
+		let mut currents = Vec::with_capacity(release.terms.len());
+		let mut seen = HashSet::new();
+		for term in &release.terms {
+			if !seen.insert((term.level, term.code.as_str())) {
+				continue;
+			}
+			codes.push(term.code.as_str());
+			terms.push(term.term.as_str());
+			levels.push(term.level);
+			pt_codes.push(term.pt_code.as_deref());
+			currents.push(term.is_current);
+		}
+		dbx.execute(
+			sqlx::query(
+				"INSERT INTO meddra_terms
+				     (code, term, level, pt_code, is_current, version, language, active)
+				 SELECT u.code, u.term, u.level, u.pt_code, u.is_current, $6, $7, true
+				 FROM unnest($1::text[], $2::text[], $3::text[], $4::text[], $5::bool[])
+				      AS u(code, term, level, pt_code, is_current)",
+			)
+			.bind(&codes)
+			.bind(&terms)
+			.bind(&levels)
+			.bind(&pt_codes)
+			.bind(&currents)
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+
+		let links = &release.hierarchy;
+		dbx.execute(
+			sqlx::query(&format!(
+				"INSERT INTO {}
+				     (pt_code, hlt_code, hlgt_code, soc_code, primary_soc, version, language)
+				 SELECT DISTINCT u.pt_code, u.hlt_code, u.hlgt_code, u.soc_code,
+				        u.primary_soc, $6, $7
+				 FROM unnest($1::text[], $2::text[], $3::text[], $4::text[], $5::bool[])
+				      AS u(pt_code, hlt_code, hlgt_code, soc_code, primary_soc)",
+				Self::TABLE
+			))
+			.bind(links.iter().map(|l| l.pt_code.as_str()).collect::<Vec<_>>())
+			.bind(
+				links
+					.iter()
+					.map(|l| l.hlt_code.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				links
+					.iter()
+					.map(|l| l.hlgt_code.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				links
+					.iter()
+					.map(|l| l.soc_code.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(links.iter().map(|l| l.primary_soc).collect::<Vec<_>>())
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+		Ok(())
+	}
+
+	/// PT -> HLT -> HLGT -> SOC paths of the given PTs, primary SOC first.
+	pub async fn paths_for_pts(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		version: &str,
+		language: &str,
+		pt_codes: &[String],
+		primary_only: bool,
+	) -> Result<Vec<MeddraPath>> {
+		let sql = format!(
+			"SELECT h.version, h.language,
+			        h.pt_code, pt.term AS pt_term,
+			        h.hlt_code, hlt.term AS hlt_term,
+			        h.hlgt_code, hlgt.term AS hlgt_term,
+			        h.soc_code, soc.term AS soc_term,
+			        h.primary_soc
+			 FROM {table} h
+			 LEFT JOIN meddra_terms pt ON pt.code = h.pt_code AND pt.level = 'PT'
+			      AND pt.version = h.version AND pt.language = h.language
+			 LEFT JOIN meddra_terms hlt ON hlt.code = h.hlt_code AND hlt.level = 'HLT'
+			      AND hlt.version = h.version AND hlt.language = h.language
+			 LEFT JOIN meddra_terms hlgt ON hlgt.code = h.hlgt_code AND hlgt.level = 'HLGT'
+			      AND hlgt.version = h.version AND hlgt.language = h.language
+			 LEFT JOIN meddra_terms soc ON soc.code = h.soc_code AND soc.level = 'SOC'
+			      AND soc.version = h.version AND soc.language = h.language
+			 WHERE h.version = $1 AND h.language = $2 AND h.pt_code = ANY($3)
+			   AND (h.primary_soc OR NOT $4)
+			 ORDER BY h.pt_code, h.primary_soc DESC, h.soc_code, h.hlgt_code, h.hlt_code",
+			table = Self::TABLE
+		);
+		let paths = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, MeddraPath>(&sql)
+					.bind(version)
+					.bind(language)
+					.bind(pt_codes)
+					.bind(primary_only),
+			)
+			.await?;
+		Ok(paths)
+	}
+
+	/// Full hierarchy of an LLT or PT code, primary SOC path first. Without a
+	/// version the most recently loaded version holding the code is used.
+	pub async fn paths_for_code(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		code: &str,
+		version: Option<&str>,
+	) -> Result<Vec<MeddraPath>> {
+		let found: Option<(String, String, String)> = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as(
+					"SELECT COALESCE(pt_code, code), version, language
+					 FROM meddra_terms
+					 WHERE code = $1 AND level IN ('LLT', 'PT')
+					   AND ($2::text IS NULL OR version = $2)
+					 ORDER BY created_at DESC, (language = 'en') DESC, (level = 'PT') DESC
+					 LIMIT 1",
+				)
+				.bind(code)
+				.bind(version),
+			)
+			.await?;
+		let Some((pt_code, version, language)) = found else {
+			return Ok(Vec::new());
+		};
+		Self::paths_for_pts(ctx, mm, &version, &language, &[pt_code], false).await
+	}
+
+	/// Reactions of a case grouped by the primary SOC of their MedDRA code,
+	/// in reaction order; uncoded or unresolved reactions come last.
+	pub async fn reactions_by_soc(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<Vec<SocReactionGroup>> {
+		let sql = format!(
+			"SELECT r.id AS reaction_id, p.soc_code, p.soc_term
+			 FROM reactions r
+			 LEFT JOIN LATERAL (
+			     SELECT h.soc_code, soc.term AS soc_term
+			     FROM meddra_terms t
+			     JOIN {table} h ON h.version = t.version AND h.language = t.language
+			          AND h.pt_code = COALESCE(t.pt_code, t.code) AND h.primary_soc
+			     LEFT JOIN meddra_terms soc ON soc.code = h.soc_code AND soc.level = 'SOC'
+			          AND soc.version = h.version AND soc.language = h.language
+			     WHERE t.code = r.reaction_meddra_code
+			       AND t.version = r.reaction_meddra_version
+			       AND t.level IN ('LLT', 'PT')
+			     ORDER BY (t.language = 'en') DESC
+			     LIMIT 1
+			 ) p ON true
+			 WHERE r.case_id = $1
+			 ORDER BY r.sequence_number",
+			table = Self::TABLE
+		);
+		let rows = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, ReactionSocRow>(&sql).bind(case_id))
+			.await?;
+
+		let mut groups: Vec<SocReactionGroup> = Vec::new();
+		let mut unresolved = Vec::new();
+		for row in rows {
+			let Some(soc_code) = row.soc_code else {
+				unresolved.push(row.reaction_id);
+				continue;
+			};
+			match groups
+				.iter_mut()
+				.find(|g| g.soc_code.as_deref() == Some(soc_code.as_str()))
+			{
+				Some(group) => group.reaction_ids.push(row.reaction_id),
+				None => groups.push(SocReactionGroup {
+					soc_code: Some(soc_code),
+					soc_term: row.soc_term,
+					reaction_ids: vec![row.reaction_id],
+				}),
+			}
+		}
+		if !unresolved.is_empty() {
+			groups.push(SocReactionGroup {
+				soc_code: None,
+				soc_term: None,
+				reaction_ids: unresolved,
+			});
+		}
+		Ok(groups)
+	}
+}
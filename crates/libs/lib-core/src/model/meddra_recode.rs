@@ -0,0 +1,584 @@
+// MedDRA recoding: move case MedDRA codes onto a newly loaded MedDRA version
+
+use crate::ctx::Ctx;
+use crate::model::drug::{DrugIndicationBmc, DrugIndicationForUpdate};
+use crate::model::drug_reaction_assessment::{
+	DrugReactionAssessmentBmc, DrugReactionAssessmentForUpdate,
+};
+use crate::model::drug_recurrence::{
+	DrugRecurrenceInformationBmc, DrugRecurrenceInformationForUpdate,
+};
+use crate::model::meddra::{LEVEL_LLT, LEVEL_PT};
+use crate::model::narrative::{SenderDiagnosisBmc, SenderDiagnosisForUpdate};
+use crate::model::parent_history::{
+	ParentMedicalHistoryBmc, ParentMedicalHistoryForUpdate,
+	ParentPastDrugHistoryBmc, ParentPastDrugHistoryForUpdate,
+};
+use crate::model::patient::{
+	AutopsyCauseOfDeathBmc, AutopsyCauseOfDeathForUpdate, MedicalHistoryEpisodeBmc,
+	MedicalHistoryEpisodeForUpdate, PastDrugHistoryBmc, PastDrugHistoryForUpdate,
+	ReportedCauseOfDeathBmc, ReportedCauseOfDeathForUpdate,
+};
+use crate::model::reaction::{ReactionBmc, ReactionForUpdate};
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::test_result::{TestResultBmc, TestResultForUpdate};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::Uuid;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// -- Types
+
+/// A MedDRA-coded field of the case tables: a `{prefix}_version` /
+/// `{prefix}_code` column pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeddraCodedField {
+	MedicalHistory,
+	PastDrugIndication,
+	PastDrugReaction,
+	ReportedCauseOfDeath,
+	AutopsyCauseOfDeath,
+	ParentMedicalHistory,
+	ParentPastDrugIndication,
+	ParentPastDrugReaction,
+	Reaction,
+	TestName,
+	DrugIndication,
+	DrugRecurrence,
+	AssessmentRecurrence,
+	SenderDiagnosis,
+}
+
+impl MeddraCodedField {
+	pub const ALL: [MeddraCodedField; 14] = [
+		Self::MedicalHistory,
+		Self::PastDrugIndication,
+		Self::PastDrugReaction,
+		Self::ReportedCauseOfDeath,
+		Self::AutopsyCauseOfDeath,
+		Self::ParentMedicalHistory,
+		Self::ParentPastDrugIndication,
+		Self::ParentPastDrugReaction,
+		Self::Reaction,
+		Self::TestName,
+		Self::DrugIndication,
+		Self::DrugRecurrence,
+		Self::AssessmentRecurrence,
+		Self::SenderDiagnosis,
+	];
+
+	pub fn table(self) -> &'static str {
+		match self {
+			Self::MedicalHistory => "medical_history_episodes",
+			Self::PastDrugIndication | Self::PastDrugReaction => "past_drug_history",
+			Self::ReportedCauseOfDeath => "reported_causes_of_death",
+			Self::AutopsyCauseOfDeath => "autopsy_causes_of_death",
+			Self::ParentMedicalHistory => "parent_medical_history",
+			Self::ParentPastDrugIndication | Self::ParentPastDrugReaction => {
+				"parent_past_drug_history"
+			}
+			Self::Reaction => "reactions",
+			Self::TestName => "test_results",
+			Self::DrugIndication => "drug_indications",
+			Self::DrugRecurrence => "drug_recurrence_information",
+			Self::AssessmentRecurrence => "drug_reaction_assessments",
+			Self::SenderDiagnosis => "sender_diagnoses",
+		}
+	}
+
+	/// Column prefix of the `_version` / `_code` pair.
+	pub fn prefix(self) -> &'static str {
+		match self {
+			Self::MedicalHistory
+			| Self::ReportedCauseOfDeath
+			| Self::AutopsyCauseOfDeath
+			| Self::ParentMedicalHistory => "meddra",
+			Self::PastDrugIndication
+			| Self::ParentPastDrugIndication
+			| Self::DrugIndication => "indication_meddra",
+			Self::PastDrugReaction
+			| Self::ParentPastDrugReaction
+			| Self::Reaction
+			| Self::DrugRecurrence => "reaction_meddra",
+			Self::TestName => "test_meddra",
+			Self::AssessmentRecurrence => "recurrence_meddra",
+			Self::SenderDiagnosis => "diagnosis_meddra",
+		}
+	}
+
+	/// Joins from the field's table (`x`) up to its case, and the case id.
+	fn case_join(self) -> (&'static str, &'static str) {
+		match self {
+			Self::MedicalHistory
+			| Self::PastDrugIndication
+			| Self::PastDrugReaction => (
+				"JOIN patient_information p ON p.id = x.patient_id",
+				"p.case_id",
+			),
+			Self::ReportedCauseOfDeath | Self::AutopsyCauseOfDeath => (
+				"JOIN patient_death_information d ON d.id = x.death_info_id
+				 JOIN patient_information p ON p.id = d.patient_id",
+				"p.case_id",
+			),
+			Self::ParentMedicalHistory
+			| Self::ParentPastDrugIndication
+			| Self::ParentPastDrugReaction => (
+				"JOIN parent_information pa ON pa.id = x.parent_id
+				 JOIN patient_information p ON p.id = pa.patient_id",
+				"p.case_id",
+			),
+			Self::Reaction | Self::TestName => ("", "x.case_id"),
+			Self::DrugIndication
+			| Self::DrugRecurrence
+			| Self::AssessmentRecurrence => {
+				("JOIN drug_information d ON d.id = x.drug_id", "d.case_id")
+			}
+			Self::SenderDiagnosis => (
+				"JOIN narrative_information n ON n.id = x.narrative_id",
+				"n.case_id",
+			),
+		}
+	}
+
+	/// Writes the new version and code through the owning Bmc so the audit
+	/// trigger records the change under the caller's context.
+	async fn apply(
+		self,
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: Uuid,
+		version: String,
+		code: String,
+	) -> Result<()> {
+		let (version, code) = (Some(version), Some(code));
+		match self {
+			Self::MedicalHistory => {
+				let data = MedicalHistoryEpisodeForUpdate {
+					meddra_version: version,
+					meddra_code: code,
+					..Default::default()
+				};
+				MedicalHistoryEpisodeBmc::update(ctx, mm, id, data).await
+			}
+			Self::PastDrugIndication => {
+				let data = PastDrugHistoryForUpdate {
+					indication_meddra_version: version,
+					indication_meddra_code: code,
+					..Default::default()
+				};
+				PastDrugHistoryBmc::update(ctx, mm, id, data).await
+			}
+			Self::PastDrugReaction => {
+				let data = PastDrugHistoryForUpdate {
+					reaction_meddra_version: version,
+					reaction_meddra_code: code,
+					..Default::default()
+				};
+				PastDrugHistoryBmc::update(ctx, mm, id, data).await
+			}
+			Self::ReportedCauseOfDeath => {
+				let data = ReportedCauseOfDeathForUpdate {
+					meddra_version: version,
+					meddra_code: code,
+				};
+				ReportedCauseOfDeathBmc::update(ctx, mm, id, data).await
+			}
+			Self::AutopsyCauseOfDeath => {
+				let data = AutopsyCauseOfDeathForUpdate {
+					meddra_version: version,
+					meddra_code: code,
+				};
+				AutopsyCauseOfDeathBmc::update(ctx, mm, id, data).await
+			}
+			Self::ParentMedicalHistory => {
+				let data = ParentMedicalHistoryForUpdate {
+					meddra_version: version,
+					meddra_code: code,
+					..Default::default()
+				};
+				ParentMedicalHistoryBmc::update(ctx, mm, id, data).await
+			}
+			Self::ParentPastDrugIndication => {
+				let data = ParentPastDrugHistoryForUpdate {
+					indication_meddra_version: version,
+					indication_meddra_code: code,
+					..Default::default()
+				};
+				ParentPastDrugHistoryBmc::update(ctx, mm, id, data).await
+			}
+			Self::ParentPastDrugReaction => {
+				let data = ParentPastDrugHistoryForUpdate {
+					reaction_meddra_version: version,
+					reaction_meddra_code: code,
+					..Default::default()
+				};
+				ParentPastDrugHistoryBmc::update(ctx, mm, id, data).await
+			}
+			Self::Reaction => {
+				let data = ReactionForUpdate {
+					reaction_meddra_version: version,
+					reaction_meddra_code: code,
+					..Default::default()
+				};
+				ReactionBmc::update(ctx, mm, id, data).await
+			}
+			Self::TestName => {
+				let data = TestResultForUpdate {
+					test_meddra_version: version,
+					test_meddra_code: code,
+					..Default::default()
+				};
+				TestResultBmc::update(ctx, mm, id, data).await
+			}
+			Self::DrugIndication => {
+				let data = DrugIndicationForUpdate {
+					indication_meddra_version: version,
+					indication_meddra_code: code,
+					..Default::default()
+				};
+				DrugIndicationBmc::update(ctx, mm, id, data).await
+			}
+			Self::DrugRecurrence => {
+				let data = DrugRecurrenceInformationForUpdate {
+					reaction_meddra_version: version,
+					reaction_meddra_code: code,
+					..Default::default()
+				};
+				DrugRecurrenceInformationBmc::update(ctx, mm, id, data).await
+			}
+			Self::AssessmentRecurrence => {
+				let data = DrugReactionAssessmentForUpdate {
+					recurrence_meddra_version: version,
+					recurrence_meddra_code: code,
+					..Default::default()
+				};
+				DrugReactionAssessmentBmc::update(ctx, mm, id, data).await
+			}
+			Self::SenderDiagnosis => {
+				let data = SenderDiagnosisForUpdate {
+					diagnosis_meddra_version: version,
+					diagnosis_meddra_code: code,
+				};
+				SenderDiagnosisBmc::update(ctx, mm, id, data).await
+			}
+		}
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeddraRecodeParams {
+	pub target_version: String,
+	pub language: Option<String>,
+	/// Only report what would change; nothing is written.
+	#[serde(default)]
+	pub dry_run: bool,
+	/// Restrict the run to these cases; all cases of the organization otherwise.
+	pub case_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeddraRecodeReason {
+	/// Current in the target version; only the version changes.
+	VersionUpdate,
+	/// LLT flagged non-current in the target version; replaced by its PT.
+	NonCurrentLlt,
+	/// Code absent from the target version; replaced by the PT it belonged
+	/// to in its recorded version.
+	NotInTargetVersion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeddraRecodeChange {
+	pub field: MeddraCodedField,
+	pub table: &'static str,
+	pub record_id: Uuid,
+	pub old_version: Option<String>,
+	pub old_code: String,
+	pub new_version: String,
+	pub new_code: String,
+	pub reason: MeddraRecodeReason,
+}
+
+/// A coded value with no equivalent in the target version; left untouched.
+#[derive(Debug, Clone, Serialize)]
+pub struct MeddraRecodeUnresolved {
+	pub field: MeddraCodedField,
+	pub table: &'static str,
+	pub record_id: Uuid,
+	pub version: Option<String>,
+	pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseRecodeReport {
+	pub case_id: Uuid,
+	pub changes: Vec<MeddraRecodeChange>,
+	pub unresolved: Vec<MeddraRecodeUnresolved>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeddraRecodeResult {
+	pub target_version: String,
+	pub language: String,
+	pub dry_run: bool,
+	pub cases_scanned: usize,
+	pub codes_scanned: usize,
+	pub changed: usize,
+	pub unresolved: usize,
+	/// Only cases with at least one change or unresolved code.
+	pub cases: Vec<CaseRecodeReport>,
+}
+
+#[derive(Debug, FromRow)]
+struct CodedRow {
+	record_id: Uuid,
+	case_id: Uuid,
+	version: Option<String>,
+	code: String,
+}
+
+#[derive(Debug, FromRow)]
+struct TargetTermRow {
+	code: String,
+	level: String,
+	pt_code: Option<String>,
+	is_current: bool,
+}
+
+// -- BMC
+
+pub struct MeddraRecodeBmc;
+
+impl MeddraRecodeBmc {
+	/// Recodes every MedDRA-coded field of the organization's cases (or of
+	/// `case_ids`) to `target_version`. Superseded case versions are frozen
+	/// and skipped. Outside a dry run all changes are applied in one
+	/// transaction.
+	pub async fn run(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		params: MeddraRecodeParams,
+	) -> Result<MeddraRecodeResult> {
+		let language = params.language.clone().unwrap_or_else(|| "en".to_string());
+
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::run_in_txn(ctx, &mm, &params, &language).await {
+			Ok(result) => {
+				if params.dry_run {
+					dbx.rollback_txn().await?;
+				} else {
+					dbx.commit_txn().await?;
+				}
+				Ok(result)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	async fn run_in_txn(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		params: &MeddraRecodeParams,
+		language: &str,
+	) -> Result<MeddraRecodeResult> {
+		let target = params.target_version.as_str();
+		let loaded: bool = mm
+			.dbx()
+			.fetch_one(
+				sqlx::query_as::<_, (bool,)>(
+					"SELECT EXISTS (
+					     SELECT 1 FROM meddra_terms
+					     WHERE version = $1 AND language = $2 AND level = 'LLT'
+					 )",
+				)
+				.bind(target)
+				.bind(language),
+			)
+			.await?
+			.0;
+		if !loaded {
+			return Err(Error::MeddraVersionNotLoaded {
+				version: target.to_string(),
+				language: language.to_string(),
+			});
+		}
+
+		let mut coded = Vec::new();
+		for field in MeddraCodedField::ALL {
+			let (joins, case_ref) = field.case_join();
+			let sql = format!(
+				"SELECT x.id AS record_id, {case_ref} AS case_id,
+				        x.{prefix}_version AS version, x.{prefix}_code AS code
+				 FROM {table} x
+				 {joins}
+				 JOIN cases c ON c.id = {case_ref}
+				 WHERE x.{prefix}_code IS NOT NULL
+				   AND c.superseded_by IS NULL
+				   AND ($1::uuid[] IS NULL OR c.id = ANY($1))
+				 ORDER BY x.id",
+				prefix = field.prefix(),
+				table = field.table(),
+			);
+			let rows = mm
+				.dbx()
+				.fetch_all(
+					sqlx::query_as::<_, CodedRow>(&sql)
+						.bind(params.case_ids.as_deref()),
+				)
+				.await?;
+			coded.extend(rows.into_iter().map(|row| (field, row)));
+		}
+
+		// PTs the codes belonged to in their recorded version, for codes the
+		// target version no longer has.
+		let codes: Vec<String> = coded
+			.iter()
+			.map(|(_, row)| row.code.clone())
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect();
+		let recorded_pts: HashMap<(String, String), String> = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, (String, String, String)>(
+					"SELECT DISTINCT ON (code, version) code, version, COALESCE(pt_code, code)
+					 FROM meddra_terms
+					 WHERE code = ANY($1) AND level IN ('LLT', 'PT') AND version <> $2
+					 ORDER BY code, version, (language = $3) DESC, (level = 'LLT') DESC",
+				)
+				.bind(&codes)
+				.bind(target)
+				.bind(language),
+			)
+			.await?
+			.into_iter()
+			.map(|(code, version, pt_code)| ((code, version), pt_code))
+			.collect();
+
+		let mut lookup: Vec<String> = codes.clone();
+		lookup.extend(recorded_pts.values().cloned());
+		let target_terms: HashMap<String, TargetTermRow> = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, TargetTermRow>(
+					"SELECT code, level, pt_code, is_current
+					 FROM meddra_terms
+					 WHERE version = $1 AND language = $2 AND code = ANY($3)
+					   AND level IN ('LLT', 'PT')",
+				)
+				.bind(target)
+				.bind(language)
+				.bind(&lookup),
+			)
+			.await?
+			.into_iter()
+			.fold(HashMap::new(), |mut terms, row| {
+				// Prefer the LLT row: it carries the currency flag.
+				if row.level == LEVEL_LLT || !terms.contains_key(&row.code) {
+					terms.insert(row.code.clone(), row);
+				}
+				terms
+			});
+
+		let mut reports: BTreeMap<Uuid, CaseRecodeReport> = BTreeMap::new();
+		let mut cases_scanned = HashSet::new();
+		let codes_scanned = coded.len();
+		let (mut changed, mut unresolved) = (0, 0);
+		for (field, row) in coded {
+			cases_scanned.insert(row.case_id);
+			let mapped = match target_terms.get(&row.code) {
+				Some(term) if term.level == LEVEL_LLT && !term.is_current => term
+					.pt_code
+					.clone()
+					.map(|pt| (pt, MeddraRecodeReason::NonCurrentLlt)),
+				Some(_) if row.version.as_deref() == Some(target) => continue,
+				Some(_) => {
+					Some((row.code.clone(), MeddraRecodeReason::VersionUpdate))
+				}
+				None => row
+					.version
+					.clone()
+					.and_then(|version| {
+						recorded_pts.get(&(row.code.clone(), version))
+					})
+					.filter(|pt| {
+						target_terms.get(*pt).is_some_and(|t| {
+							t.level == LEVEL_PT
+								|| (t.level == LEVEL_LLT && t.is_current)
+						})
+					})
+					.map(|pt| (pt.clone(), MeddraRecodeReason::NotInTargetVersion)),
+			};
+
+			let report =
+				reports
+					.entry(row.case_id)
+					.or_insert_with(|| CaseRecodeReport {
+						case_id: row.case_id,
+						changes: Vec::new(),
+						unresolved: Vec::new(),
+					});
+			let Some((new_code, reason)) = mapped else {
+				unresolved += 1;
+				report.unresolved.push(MeddraRecodeUnresolved {
+					field,
+					table: field.table(),
+					record_id: row.record_id,
+					version: row.version,
+					code: row.code,
+				});
+				continue;
+			};
+
+			if !params.dry_run {
+				field
+					.apply(
+						ctx,
+						mm,
+						row.record_id,
+						target.to_string(),
+						new_code.clone(),
+					)
+					.await?;
+			}
+			changed += 1;
+			report.changes.push(MeddraRecodeChange {
+				field,
+				table: field.table(),
+				record_id: row.record_id,
+				old_version: row.version,
+				old_code: row.code,
+				new_version: target.to_string(),
+				new_code,
+				reason,
+			});
+		}
+
+		Ok(MeddraRecodeResult {
+			target_version: target.to_string(),
+			language: language.to_string(),
+			dry_run: params.dry_run,
+			cases_scanned: cases_scanned.len(),
+			codes_scanned,
+			changed,
+			unresolved,
+			cases: reports.into_values().collect(),
+		})
+	}
+}
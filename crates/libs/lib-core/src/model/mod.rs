@@ -29,6 +29,10 @@ pub mod store;
 pub mod case;
 pub mod organization;
 pub mod user; // E2B users table (UUID-based) // Organizations table // Core cases table
+pub mod case_workflow; // Status transitions and the permission each one needs
+pub mod follow_up; // Follow-up/amendment versions copied into version N+1
+pub mod case_diff; // Section-by-section diff between case versions
+pub mod reporting_deadline; // Expedited reporting clock and due dates per case
 
 // E2B(R3) Section C - Safety Report Identification
 pub mod safety_report; // Safety report ID, sender info, primary sources, literature refs, study info
@@ -51,6 +55,9 @@ pub mod narrative; // Case narrative, sender diagnoses, case summaries
 // E2B(R3) Section N - Message Headers
 pub mod message_header; // Batch/message transmission headers
 
+// E2B(R3) ACK - Inbound Acknowledgments
+pub mod acknowledgment; // Regulator ACK codes and error text per submitted case
+
 // E2B(R3) Section A - Receiver Information
 pub mod receiver; // Receiver details for routing to regulatory authorities
 
@@ -63,20 +70,34 @@ pub mod drug_recurrence; // Structured rechallenge/recurrence data
 // E2B(R3) C.1.9.r / C.1.10.r - Case Identifiers
 pub mod case_identifiers; // Other case identifiers and linked report numbers
 
+// PMDA J.2 - J.20 - Japanese regional data elements
+pub mod jp_element; // Reporting category, reporting deadline and other J elements
+
 // E2B(R3) D.10.7 / D.10.8 - Parent History
 pub mod parent_history; // Parent medical history and past drug history
 
 // Controlled Terminologies
 pub mod terminology; // MedDRA, WHODrug, ISO countries, E2B code lists
+pub mod meddra; // MedDRA hierarchy and ASCII distribution loader
+pub mod meddra_recode; // Recoding case MedDRA fields to a new MedDRA version
+pub mod whodrug; // WHODrug Global B3 loader, ingredients and ATC classification
+pub mod coding_assistant; // Ranked MedDRA/WHODrug suggestions and coding memory
+pub mod terminology_version; // Terminology package import and per-organization activation
 
 // Audit and Versioning
 pub mod audit; // Audit logs and case versions
+pub mod audit_export; // Field-level audit trail export with SHA-256 manifest
 
 // Presave Templates
 pub mod presave_template; // Reusable pre-case templates and template audit history
 
+// Validation Rules
+pub mod validation_rule; // Stored rule definitions and per-organization severity overrides
+
 // Utilities
 pub mod modql_utils;
+pub mod partial_date; // E2B dates precise to year, month, day, minute or second
+pub mod ucum; // UCUM unit parsing, synonym normalization and conversion
 
 pub use self::error::{Error, Result};
 
@@ -75,7 +75,7 @@ pub struct SenderDiagnosisForCreate {
 	pub diagnosis_meddra_code: Option<String>,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct SenderDiagnosisForUpdate {
 	pub diagnosis_meddra_version: Option<String>,
 	pub diagnosis_meddra_code: Option<String>,
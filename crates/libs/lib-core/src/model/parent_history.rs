@@ -5,12 +5,13 @@ use crate::ctx::Ctx;
 use crate::model::base::base_uuid;
 use crate::model::base::DbBmc;
 use crate::model::modql_utils::uuid_to_sea_value;
+use crate::model::partial_date::PartialDate;
 use crate::model::ModelManager;
 use crate::model::Result;
 use modql::field::Fields;
 use modql::filter::{FilterNodes, ListOptions, OpValsValue};
 use serde::{Deserialize, Serialize};
-use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::time::OffsetDateTime;
 use sqlx::types::Uuid;
 use sqlx::FromRow;
 
@@ -30,13 +31,13 @@ pub struct ParentMedicalHistory {
 	pub meddra_code: Option<String>,
 
 	// D.10.7.1.r.2 - Start Date
-	pub start_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
 
 	// D.10.7.1.r.3 - Continuing
 	pub continuing: Option<bool>,
 
 	// D.10.7.1.r.4 - End Date
-	pub end_date: Option<Date>,
+	pub end_date: Option<PartialDate>,
 
 	// D.10.7.1.r.5 - Comments
 	pub comments: Option<String>,
@@ -55,13 +56,13 @@ pub struct ParentMedicalHistoryForCreate {
 	pub meddra_code: Option<String>,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct ParentMedicalHistoryForUpdate {
 	pub meddra_version: Option<String>,
 	pub meddra_code: Option<String>,
-	pub start_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
 	pub continuing: Option<bool>,
-	pub end_date: Option<Date>,
+	pub end_date: Option<PartialDate>,
 	pub comments: Option<String>,
 }
 
@@ -92,11 +93,15 @@ pub struct ParentPastDrugHistory {
 	pub phpid: Option<String>,
 	pub phpid_version: Option<String>,
 
+	// D.10.8.r.1.KR.1a/b - MFDS Product Code (MFDS)
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
+
 	// D.10.8.r.4 - Start Date
-	pub start_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
 
 	// D.10.8.r.5 - End Date
-	pub end_date: Option<Date>,
+	pub end_date: Option<PartialDate>,
 
 	// D.10.8.r.6a/b - Indication (MedDRA)
 	pub indication_meddra_version: Option<String>,
@@ -120,15 +125,17 @@ pub struct ParentPastDrugHistoryForCreate {
 	pub drug_name: Option<String>,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct ParentPastDrugHistoryForUpdate {
 	pub drug_name: Option<String>,
 	pub mpid: Option<String>,
 	pub mpid_version: Option<String>,
 	pub phpid: Option<String>,
 	pub phpid_version: Option<String>,
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub indication_meddra_version: Option<String>,
 	pub indication_meddra_code: Option<String>,
 	pub reaction_meddra_version: Option<String>,
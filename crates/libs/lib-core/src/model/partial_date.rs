@@ -0,0 +1,359 @@
+// Partial dates: E2B(R3) date elements precise to year, month, day, minute or
+// second (HL7 TS `CCYY[MM[DD[hhmm[ss]]]][+/-ZZzz]`)
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{Decode, Encode, Postgres, Type};
+use std::fmt;
+use time::{Date, Month};
+
+/// How much of a [`PartialDate`] is known, from least to most precise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatePrecision {
+	Year,
+	Month,
+	Day,
+	Minute,
+	Second,
+}
+
+impl DatePrecision {
+	/// Number of digits of the E2B representation.
+	pub fn digits(self) -> usize {
+		match self {
+			Self::Year => 4,
+			Self::Month => 6,
+			Self::Day => 8,
+			Self::Minute => 12,
+			Self::Second => 14,
+		}
+	}
+
+	pub fn from_digits(digits: usize) -> Option<Self> {
+		match digits {
+			4 => Some(Self::Year),
+			6 => Some(Self::Month),
+			8 => Some(Self::Day),
+			12 => Some(Self::Minute),
+			14 => Some(Self::Second),
+			_ => None,
+		}
+	}
+
+	/// E2B format notation, as used by the "minimum precision" rules.
+	pub fn format(self) -> &'static str {
+		match self {
+			Self::Year => "CCYY",
+			Self::Month => "CCYYMM",
+			Self::Day => "CCYYMMDD",
+			Self::Minute => "CCYYMMDDhhmm",
+			Self::Second => "CCYYMMDDhhmmss",
+		}
+	}
+
+	/// Accepts the E2B notation (`CCYYMMDD`) or the lowercase name (`day`).
+	pub fn parse(value: &str) -> Option<Self> {
+		let value = value.trim();
+		[
+			Self::Year,
+			Self::Month,
+			Self::Day,
+			Self::Minute,
+			Self::Second,
+		]
+		.into_iter()
+		.find(|p| {
+			p.format().eq_ignore_ascii_case(value)
+				|| format!("{p:?}").eq_ignore_ascii_case(value)
+		})
+	}
+}
+
+/// A date known to [`DatePrecision`]. Components beyond the precision are
+/// not stored; the value displays, serializes and persists as the E2B string
+/// it was read from (e.g. `2023`, `202311`, `20231105`, `202311051230+0900`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PartialDate {
+	year: i32,
+	month: Option<Month>,
+	day: Option<u8>,
+	hour: Option<u8>,
+	minute: Option<u8>,
+	second: Option<u8>,
+	/// Timezone offset in minutes, when given.
+	offset: Option<i16>,
+}
+
+impl PartialDate {
+	pub fn year_only(year: i32) -> Option<Self> {
+		(0..=9999).contains(&year).then_some(Self {
+			year,
+			month: None,
+			day: None,
+			hour: None,
+			minute: None,
+			second: None,
+			offset: None,
+		})
+	}
+
+	pub fn year_month(year: i32, month: Month) -> Option<Self> {
+		Self::year_only(year).map(|date| Self {
+			month: Some(month),
+			..date
+		})
+	}
+
+	/// Parses the E2B form, tolerating ISO `YYYY[-MM[-DD]]` input.
+	/// Returns `None` for other digit counts and impossible dates.
+	pub fn parse(value: &str) -> Option<Self> {
+		let value = value.trim();
+		if value.len() >= 5 && value.as_bytes()[4] == b'-' {
+			return Self::parse_iso(value);
+		}
+
+		let digits_end = value
+			.find(|c: char| !c.is_ascii_digit())
+			.unwrap_or(value.len());
+		let (digits, mut rest) = value.split_at(digits_end);
+		// Fractions of a second are beyond the supported precision.
+		if let Some(fraction) = rest.strip_prefix('.') {
+			rest = fraction.trim_start_matches(|c: char| c.is_ascii_digit());
+		}
+		let offset = match rest {
+			"" => None,
+			_ => Some(parse_offset(rest)?),
+		};
+		Self::from_digits(digits, offset)
+	}
+
+	fn parse_iso(value: &str) -> Option<Self> {
+		let parts: Vec<&str> = value.split('-').collect();
+		let valid = parts.len() <= 3
+			&& parts[0].len() == 4
+			&& parts[1..].iter().all(|p| p.len() == 2)
+			&& parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()));
+		if !valid {
+			return None;
+		}
+		Self::from_digits(&parts.concat(), None)
+	}
+
+	fn from_digits(digits: &str, offset: Option<i16>) -> Option<Self> {
+		let precision = DatePrecision::from_digits(digits.len())?;
+		let part = |start: usize| -> Option<u8> {
+			digits.get(start..start + 2).and_then(|s| s.parse().ok())
+		};
+		let mut date = Self::year_only(digits[0..4].parse().ok()?)?;
+		date.offset = offset;
+		if precision >= DatePrecision::Month {
+			date.month = Some(Month::try_from(part(4)?).ok()?);
+		}
+		if precision >= DatePrecision::Day {
+			let day = part(6)?;
+			Date::from_calendar_date(date.year, date.month?, day).ok()?;
+			date.day = Some(day);
+		}
+		if precision >= DatePrecision::Minute {
+			date.hour = Some(part(8).filter(|h| *h < 24)?);
+			date.minute = Some(part(10).filter(|m| *m < 60)?);
+		}
+		if precision >= DatePrecision::Second {
+			date.second = Some(part(12).filter(|s| *s < 60)?);
+		}
+		Some(date)
+	}
+
+	pub fn precision(&self) -> DatePrecision {
+		if self.second.is_some() {
+			DatePrecision::Second
+		} else if self.minute.is_some() {
+			DatePrecision::Minute
+		} else if self.day.is_some() {
+			DatePrecision::Day
+		} else if self.month.is_some() {
+			DatePrecision::Month
+		} else {
+			DatePrecision::Year
+		}
+	}
+
+	/// Whether the value is at least as precise as `minimum`.
+	pub fn meets(&self, minimum: DatePrecision) -> bool {
+		self.precision() >= minimum
+	}
+
+	pub fn year(&self) -> i32 {
+		self.year
+	}
+
+	pub fn month(&self) -> Option<Month> {
+		self.month
+	}
+
+	pub fn day(&self) -> Option<u8> {
+		self.day
+	}
+
+	/// The calendar date, when known to the day.
+	pub fn to_date(&self) -> Option<Date> {
+		Date::from_calendar_date(self.year, self.month?, self.day?).ok()
+	}
+
+	/// First day of the period the value covers.
+	pub fn earliest(&self) -> Date {
+		let month = self.month.unwrap_or(Month::January);
+		Date::from_calendar_date(self.year, month, self.day.unwrap_or(1))
+			.expect("validated partial date")
+	}
+
+	/// Last day of the period the value covers.
+	pub fn latest(&self) -> Date {
+		let month = self.month.unwrap_or(Month::December);
+		let day = self
+			.day
+			.unwrap_or_else(|| month.length(self.year));
+		Date::from_calendar_date(self.year, month, day).expect("validated partial date")
+	}
+}
+
+fn parse_offset(value: &str) -> Option<i16> {
+	let (sign, digits) = match value.as_bytes().first()? {
+		b'+' => (1, &value[1..]),
+		b'-' => (-1, &value[1..]),
+		_ => return None,
+	};
+	if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+		return None;
+	}
+	let hours: i16 = digits[0..2].parse().ok()?;
+	let minutes: i16 = digits[2..4].parse().ok()?;
+	(hours <= 14 && minutes < 60).then_some(sign * (hours * 60 + minutes))
+}
+
+impl From<Date> for PartialDate {
+	fn from(date: Date) -> Self {
+		Self {
+			year: date.year(),
+			month: Some(date.month()),
+			day: Some(date.day()),
+			hour: None,
+			minute: None,
+			second: None,
+			offset: None,
+		}
+	}
+}
+
+impl fmt::Display for PartialDate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:04}", self.year)?;
+		let parts = [
+			self.month.map(u8::from),
+			self.day,
+			self.hour,
+			self.minute,
+			self.second,
+		];
+		for part in parts.into_iter().map_while(|p| p) {
+			write!(f, "{part:02}")?;
+		}
+		if let Some(offset) = self.offset {
+			let sign = if offset < 0 { '-' } else { '+' };
+			let offset = offset.unsigned_abs();
+			write!(f, "{sign}{:02}{:02}", offset / 60, offset % 60)?;
+		}
+		Ok(())
+	}
+}
+
+// -- Serde: the E2B string. Deserialization also takes `time::Date`'s
+// `[year, ordinal]` form that full dates used before.
+
+impl Serialize for PartialDate {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+impl<'de> Deserialize<'de> for PartialDate {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct PartialDateVisitor;
+
+		impl<'de> Visitor<'de> for PartialDateVisitor {
+			type Value = PartialDate;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("an E2B date string such as \"2023\", \"202311\" or \"20231105\"")
+			}
+
+			fn visit_str<E: de::Error>(self, value: &str) -> Result<PartialDate, E> {
+				PartialDate::parse(value).ok_or_else(|| {
+					E::invalid_value(de::Unexpected::Str(value), &self)
+				})
+			}
+
+			fn visit_seq<A: SeqAccess<'de>>(
+				self,
+				mut seq: A,
+			) -> Result<PartialDate, A::Error> {
+				let year: i32 = seq
+					.next_element()?
+					.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+				let ordinal: u16 = seq
+					.next_element()?
+					.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+				Date::from_ordinal_date(year, ordinal)
+					.map(PartialDate::from)
+					.map_err(de::Error::custom)
+			}
+		}
+
+		deserializer.deserialize_any(PartialDateVisitor)
+	}
+}
+
+// -- Persistence: stored as the E2B string in VARCHAR columns.
+
+impl Type<Postgres> for PartialDate {
+	fn type_info() -> PgTypeInfo {
+		<String as Type<Postgres>>::type_info()
+	}
+
+	fn compatible(ty: &PgTypeInfo) -> bool {
+		<String as Type<Postgres>>::compatible(ty)
+	}
+}
+
+impl Encode<'_, Postgres> for PartialDate {
+	fn encode_by_ref(
+		&self,
+		buf: &mut PgArgumentBuffer,
+	) -> Result<IsNull, BoxDynError> {
+		<String as Encode<Postgres>>::encode(self.to_string(), buf)
+	}
+}
+
+impl<'r> Decode<'r, Postgres> for PartialDate {
+	fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+		let value = <&str as Decode<Postgres>>::decode(value)?;
+		PartialDate::parse(value)
+			.ok_or_else(|| format!("invalid partial date '{value}'").into())
+	}
+}
+
+impl From<PartialDate> for sea_query::Value {
+	fn from(value: PartialDate) -> Self {
+		sea_query::Value::String(Some(Box::new(value.to_string())))
+	}
+}
+
+impl sea_query::Nullable for PartialDate {
+	fn null() -> sea_query::Value {
+		sea_query::Value::String(None)
+	}
+}
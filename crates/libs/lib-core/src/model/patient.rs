@@ -5,13 +5,14 @@ use crate::model::base::base_uuid;
 use crate::model::base::DbBmc;
 use crate::model::modql_utils::uuid_to_sea_value;
 use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::partial_date::PartialDate;
 use crate::model::ModelManager;
 use crate::model::Result;
 use modql::field::Fields;
 use modql::filter::{FilterNodes, ListOptions, OpValsString, OpValsValue};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::time::OffsetDateTime;
 use sqlx::types::Uuid;
 use sqlx::FromRow;
 
@@ -28,7 +29,7 @@ pub struct PatientInformation {
 	pub patient_family_name: Option<String>,
 
 	// D.2 - Age
-	pub birth_date: Option<Date>,
+	pub birth_date: Option<PartialDate>,
 	pub age_at_time_of_onset: Option<Decimal>,
 	pub age_unit: Option<String>,
 	pub gestation_period: Option<Decimal>,
@@ -45,7 +46,7 @@ pub struct PatientInformation {
 	pub ethnicity_code: Option<String>,
 
 	// D.6 - Last Menstrual Period
-	pub last_menstrual_period_date: Option<Date>,
+	pub last_menstrual_period_date: Option<PartialDate>,
 
 	// D.7.2 - Medical history
 	pub medical_history_text: Option<String>,
@@ -72,7 +73,7 @@ pub struct PatientInformationForUpdate {
 	pub patient_initials: Option<String>,
 	pub patient_given_name: Option<String>,
 	pub patient_family_name: Option<String>,
-	pub birth_date: Option<Date>,
+	pub birth_date: Option<PartialDate>,
 	pub age_at_time_of_onset: Option<Decimal>,
 	pub age_unit: Option<String>,
 	pub gestation_period: Option<Decimal>,
@@ -83,7 +84,7 @@ pub struct PatientInformationForUpdate {
 	pub sex: Option<String>,
 	pub race_code: Option<String>,
 	pub ethnicity_code: Option<String>,
-	pub last_menstrual_period_date: Option<Date>,
+	pub last_menstrual_period_date: Option<PartialDate>,
 	pub medical_history_text: Option<String>,
 	pub concomitant_therapy: Option<bool>,
 }
@@ -145,9 +146,9 @@ pub struct MedicalHistoryEpisode {
 	pub meddra_code: Option<String>,
 
 	// D.7.1.r.2-4
-	pub start_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
 	pub continuing: Option<bool>,
-	pub end_date: Option<Date>,
+	pub end_date: Option<PartialDate>,
 	pub comments: Option<String>,
 	pub family_history: Option<bool>,
 
@@ -164,13 +165,13 @@ pub struct MedicalHistoryEpisodeForCreate {
 	pub meddra_code: Option<String>,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct MedicalHistoryEpisodeForUpdate {
 	pub meddra_version: Option<String>,
 	pub meddra_code: Option<String>,
-	pub start_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
 	pub continuing: Option<bool>,
-	pub end_date: Option<Date>,
+	pub end_date: Option<PartialDate>,
 	pub comments: Option<String>,
 	pub family_history: Option<bool>,
 }
@@ -199,9 +200,13 @@ pub struct PastDrugHistory {
 	pub phpid: Option<String>,
 	pub phpid_version: Option<String>,
 
+	// D.8.r.1.KR.1a/b - MFDS Product Code (MFDS)
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
+
 	// D.8.r.4-5 - Dates
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 
 	// D.8.r.6a - Indication
 	pub indication_meddra_version: Option<String>,
@@ -226,23 +231,27 @@ pub struct PastDrugHistoryForCreate {
 	pub mpid_version: Option<String>,
 	pub phpid: Option<String>,
 	pub phpid_version: Option<String>,
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub indication_meddra_version: Option<String>,
 	pub indication_meddra_code: Option<String>,
 	pub reaction_meddra_version: Option<String>,
 	pub reaction_meddra_code: Option<String>,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct PastDrugHistoryForUpdate {
 	pub drug_name: Option<String>,
 	pub mpid: Option<String>,
 	pub mpid_version: Option<String>,
 	pub phpid: Option<String>,
 	pub phpid_version: Option<String>,
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub indication_meddra_version: Option<String>,
 	pub indication_meddra_code: Option<String>,
 	pub reaction_meddra_version: Option<String>,
@@ -264,7 +273,7 @@ pub struct PatientDeathInformation {
 	pub patient_id: Uuid,
 
 	// D.9.1 - Date of Death
-	pub date_of_death: Option<Date>,
+	pub date_of_death: Option<PartialDate>,
 
 	// D.9.3 - Autopsy
 	pub autopsy_performed: Option<bool>,
@@ -278,13 +287,13 @@ pub struct PatientDeathInformation {
 #[derive(Fields, Deserialize)]
 pub struct PatientDeathInformationForCreate {
 	pub patient_id: Uuid,
-	pub date_of_death: Option<Date>,
+	pub date_of_death: Option<PartialDate>,
 	pub autopsy_performed: Option<bool>,
 }
 
 #[derive(Fields, Deserialize)]
 pub struct PatientDeathInformationForUpdate {
-	pub date_of_death: Option<Date>,
+	pub date_of_death: Option<PartialDate>,
 	pub autopsy_performed: Option<bool>,
 }
 
@@ -372,10 +381,10 @@ pub struct ParentInformation {
 	pub patient_id: Uuid,
 
 	pub parent_identification: Option<String>,
-	pub parent_birth_date: Option<Date>,
+	pub parent_birth_date: Option<PartialDate>,
 	pub parent_age: Option<Decimal>,
 	pub parent_age_unit: Option<String>,
-	pub last_menstrual_period_date: Option<Date>,
+	pub last_menstrual_period_date: Option<PartialDate>,
 	pub weight_kg: Option<Decimal>,
 	pub height_cm: Option<Decimal>,
 	pub sex: Option<String>,
@@ -397,10 +406,10 @@ pub struct ParentInformationForCreate {
 #[derive(Fields, Deserialize)]
 pub struct ParentInformationForUpdate {
 	pub parent_identification: Option<String>,
-	pub parent_birth_date: Option<Date>,
+	pub parent_birth_date: Option<PartialDate>,
 	pub parent_age: Option<Decimal>,
 	pub parent_age_unit: Option<String>,
-	pub last_menstrual_period_date: Option<Date>,
+	pub last_menstrual_period_date: Option<PartialDate>,
 	pub weight_kg: Option<Decimal>,
 	pub height_cm: Option<Decimal>,
 	pub sex: Option<String>,
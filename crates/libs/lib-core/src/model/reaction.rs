@@ -3,14 +3,16 @@
 use crate::ctx::Ctx;
 use crate::model::base::DbBmc;
 use crate::model::modql_utils::uuid_to_sea_value;
+use crate::model::reporting_deadline::CaseReportingDeadlineBmc;
 use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::partial_date::PartialDate;
 use crate::model::ModelManager;
 use crate::model::Result;
 use modql::field::Fields;
 use modql::filter::{FilterNodes, OpValsBool, OpValsValue};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::time::OffsetDateTime;
 use sqlx::types::Uuid;
 use sqlx::FromRow;
 
@@ -47,8 +49,8 @@ pub struct Reaction {
 	pub required_intervention: Option<String>,
 
 	// E.i.4-6 - Timing
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub duration_value: Option<Decimal>,
 	pub duration_unit: Option<String>,
 
@@ -75,7 +77,7 @@ pub struct ReactionForCreate {
 	pub primary_source_reaction: String,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct ReactionForUpdate {
 	pub primary_source_reaction: Option<String>,
 	pub reaction_language: Option<String>,
@@ -90,8 +92,8 @@ pub struct ReactionForUpdate {
 	pub criteria_congenital_anomaly: Option<bool>,
 	pub criteria_other_medically_important: Option<bool>,
 	pub required_intervention: Option<String>,
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub duration_value: Option<Decimal>,
 	pub duration_unit: Option<String>,
 	pub outcome: Option<String>,
@@ -144,6 +146,12 @@ impl ReactionBmc {
 					.bind(ctx.user_id()),
 			)
 			.await?;
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(
+			ctx,
+			mm,
+			reaction_c.case_id,
+		)
+		.await?;
 
 		mm.dbx().commit_txn().await?;
 		Ok(id)
@@ -201,13 +209,14 @@ impl ReactionBmc {
 			     country_code = COALESCE($21, country_code),
 			     updated_at = now(),
 			     updated_by = $22
-			 WHERE id = $1",
+			 WHERE id = $1
+			 RETURNING case_id",
 			Self::TABLE
 		);
 		let result = mm
 			.dbx()
-			.execute(
-				sqlx::query(&sql)
+			.fetch_optional(
+				sqlx::query_as::<_, (Uuid,)>(&sql)
 					.bind(id)
 					.bind(reaction_u.primary_source_reaction)
 					.bind(reaction_u.reaction_language)
@@ -232,13 +241,15 @@ impl ReactionBmc {
 					.bind(ctx.user_id()),
 			)
 			.await?;
-		if result == 0 {
+		let Some((case_id,)) = result else {
 			mm.dbx().rollback_txn().await?;
 			return Err(crate::model::Error::EntityUuidNotFound {
 				entity: Self::TABLE,
 				id,
 			});
-		}
+		};
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(())
 	}
@@ -361,6 +372,8 @@ impl ReactionBmc {
 				id,
 			});
 		}
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(())
 	}
@@ -375,15 +388,23 @@ impl ReactionBmc {
 		)
 		.await?;
 
-		let sql = format!("DELETE FROM {} WHERE id = $1", Self::TABLE);
-		let result = mm.dbx().execute(sqlx::query(&sql).bind(id)).await?;
-		if result == 0 {
+		let sql = format!(
+			"DELETE FROM {} WHERE id = $1 RETURNING case_id",
+			Self::TABLE
+		);
+		let result = mm
+			.dbx()
+			.fetch_optional(sqlx::query_as::<_, (Uuid,)>(&sql).bind(id))
+			.await?;
+		let Some((case_id,)) = result else {
 			mm.dbx().rollback_txn().await?;
 			return Err(crate::model::Error::EntityUuidNotFound {
 				entity: Self::TABLE,
 				id,
 			});
-		}
+		};
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(())
 	}
@@ -416,6 +437,8 @@ impl ReactionBmc {
 				id,
 			});
 		}
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(())
 	}
@@ -0,0 +1,488 @@
+// Regulatory reporting clock: expedited due date per case
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::case_workflow::{
+	STATUS_CHECKED, STATUS_DRAFT, STATUS_REJECTED, STATUS_VALIDATED,
+};
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::Uuid;
+use time::Duration;
+
+/// C.1.3 Type of Report: report from study.
+pub const REPORT_TYPE_STUDY: &str = "2";
+
+/// Statuses whose clock is still running: nothing accepted has been sent yet.
+pub const OPEN_STATUSES: &[&str] = &[
+	STATUS_DRAFT,
+	STATUS_CHECKED,
+	STATUS_VALIDATED,
+	STATUS_REJECTED,
+];
+
+// -- Engine
+
+/// Expedited timelines of one regulatory profile, in calendar days.
+#[derive(Debug, Clone, Copy)]
+pub struct ProfileTimelines {
+	pub profile: &'static str,
+	/// Fatal or life-threatening reactions from a study (SUSARs).
+	pub fatal_or_life_threatening_study_days: i64,
+	/// Every other expedited report.
+	pub expedited_days: i64,
+}
+
+/// FDA (21 CFR 312.32 / 314.80), MFDS, EMA, PMDA and ICH E2A/E2D all apply 7
+/// days to fatal or life-threatening study reports and 15 days otherwise.
+pub const PROFILE_TIMELINES: &[ProfileTimelines] = &[
+	ProfileTimelines {
+		profile: "fda",
+		fatal_or_life_threatening_study_days: 7,
+		expedited_days: 15,
+	},
+	ProfileTimelines {
+		profile: "mfds",
+		fatal_or_life_threatening_study_days: 7,
+		expedited_days: 15,
+	},
+	ProfileTimelines {
+		profile: "ema",
+		fatal_or_life_threatening_study_days: 7,
+		expedited_days: 15,
+	},
+	ProfileTimelines {
+		profile: "pmda",
+		fatal_or_life_threatening_study_days: 7,
+		expedited_days: 15,
+	},
+	ProfileTimelines {
+		profile: "ich",
+		fatal_or_life_threatening_study_days: 7,
+		expedited_days: 15,
+	},
+];
+
+/// Profile used for cases without one, as for XML export.
+pub const DEFAULT_PROFILE: &str = "fda";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlineBasis {
+	FatalOrLifeThreatening,
+	Serious,
+	/// C.1.7 is set although no reaction is flagged serious.
+	Expedited,
+	NotExpedited,
+}
+
+impl DeadlineBasis {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::FatalOrLifeThreatening => "fatal_or_life_threatening",
+			Self::Serious => "serious",
+			Self::Expedited => "expedited",
+			Self::NotExpedited => "not_expedited",
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportingClockInput {
+	pub validation_profile: Option<String>,
+	pub version: i32,
+	/// C.1.3
+	pub report_type: String,
+	/// C.1.4
+	pub date_first_received_from_source: Date,
+	/// C.1.5
+	pub date_of_most_recent_information: Date,
+	/// C.1.7
+	pub fulfil_expedited_criteria: bool,
+	/// Any E.i.3.2 seriousness on the case.
+	pub serious: bool,
+	/// Any E.i.3.2a (death) or E.i.3.2b (life threatening) on the case.
+	pub fatal_or_life_threatening: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportingClock {
+	pub validation_profile: &'static str,
+	pub clock_start_date: Date,
+	pub timeline_days: Option<i32>,
+	pub due_date: Option<Date>,
+	pub basis: DeadlineBasis,
+}
+
+pub fn profile_timelines(profile: Option<&str>) -> &'static ProfileTimelines {
+	let profile = profile
+		.map(|p| p.trim().to_ascii_lowercase())
+		.unwrap_or_default();
+	PROFILE_TIMELINES
+		.iter()
+		.find(|t| t.profile == profile)
+		.or_else(|| {
+			PROFILE_TIMELINES
+				.iter()
+				.find(|t| t.profile == DEFAULT_PROFILE)
+		})
+		.expect("default profile has timelines")
+}
+
+/// Computes the expedited due date of a case.
+///
+/// The clock starts at C.1.4 for the initial report and at C.1.5 for a
+/// follow-up version, which is due on the same timeline from receipt of the
+/// new information. Cases not flagged by C.1.7 get no due date.
+pub fn compute_reporting_clock(input: &ReportingClockInput) -> ReportingClock {
+	let timelines = profile_timelines(input.validation_profile.as_deref());
+	let clock_start_date = if input.version > 1 {
+		input.date_of_most_recent_information
+	} else {
+		input.date_first_received_from_source
+	};
+
+	let (basis, days) = if !input.fulfil_expedited_criteria {
+		(DeadlineBasis::NotExpedited, None)
+	} else if input.fatal_or_life_threatening
+		&& input.report_type.trim() == REPORT_TYPE_STUDY
+	{
+		(
+			DeadlineBasis::FatalOrLifeThreatening,
+			Some(timelines.fatal_or_life_threatening_study_days),
+		)
+	} else if input.serious {
+		(DeadlineBasis::Serious, Some(timelines.expedited_days))
+	} else {
+		(DeadlineBasis::Expedited, Some(timelines.expedited_days))
+	};
+
+	ReportingClock {
+		validation_profile: timelines.profile,
+		clock_start_date,
+		timeline_days: days.map(|days| days as i32),
+		due_date: days.map(|days| clock_start_date + Duration::days(days)),
+		basis,
+	}
+}
+
+// -- Types
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct CaseReportingDeadline {
+	pub id: Uuid,
+	pub case_id: Uuid,
+	pub validation_profile: String,
+	pub clock_start_date: Date,
+	pub timeline_days: Option<i32>,
+	pub due_date: Option<Date>,
+	pub basis: String,
+	pub created_at: OffsetDateTime,
+	pub updated_at: OffsetDateTime,
+	pub created_by: Uuid,
+	pub updated_by: Option<Uuid>,
+}
+
+/// Largest `within_days` look-ahead accepted by [`CaseReportingDeadlineBmc::list_due`].
+pub const MAX_WITHIN_DAYS: i64 = 3650;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CaseDeadlineFilter {
+	/// Also list cases due within this many days; only overdue and due-today
+	/// cases when omitted.
+	pub within_days: Option<i64>,
+	pub organization_id: Option<Uuid>,
+}
+
+/// An open case that is overdue or due soon.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct CaseDeadlineListItem {
+	pub case_id: Uuid,
+	pub organization_id: Uuid,
+	pub safety_report_id: String,
+	pub version: i32,
+	pub status: String,
+	pub validation_profile: String,
+	pub clock_start_date: Date,
+	pub timeline_days: Option<i32>,
+	pub due_date: Option<Date>,
+	pub basis: String,
+	/// Negative once overdue.
+	pub days_remaining: i32,
+	pub overdue: bool,
+}
+
+#[derive(FromRow)]
+struct ClockRow {
+	case_id: Uuid,
+	validation_profile: Option<String>,
+	version: i32,
+	report_type: String,
+	date_first_received_from_source: Date,
+	date_of_most_recent_information: Date,
+	fulfil_expedited_criteria: bool,
+	serious: bool,
+	fatal_or_life_threatening: bool,
+}
+
+// -- BMC
+
+pub struct CaseReportingDeadlineBmc;
+impl DbBmc for CaseReportingDeadlineBmc {
+	const TABLE: &'static str = "case_reporting_deadlines";
+}
+
+impl CaseReportingDeadlineBmc {
+	/// Recomputes and stores the deadline of one case. Returns `None` while
+	/// the case has no C.1 safety report identification.
+	pub async fn refresh(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<Option<CaseReportingDeadline>> {
+		Self::refresh_cases(ctx, mm, Some(case_id)).await?;
+		Self::find_by_case(ctx, mm, case_id).await
+	}
+
+	/// Recomputes the deadline of `case_id` inside the caller's open
+	/// transaction, rolling it back on failure. Called by the safety report
+	/// and reaction writes so C.1.4/C.1.5 dates and seriousness edits show up
+	/// in [`Self::list_due`] without waiting for [`Self::refresh_open`].
+	pub(crate) async fn refresh_in_txn_or_rollback(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<()> {
+		if let Err(err) = Self::refresh_cases(ctx, mm, Some(case_id)).await {
+			let _ = mm.dbx().rollback_txn().await;
+			return Err(err);
+		}
+		Ok(())
+	}
+
+	/// Recomputes the deadlines of every open, current case visible to `ctx`.
+	/// Meant for a periodic job (`POST /api/cases/deadlines/refresh`), not for
+	/// read paths.
+	pub async fn refresh_open(ctx: &Ctx, mm: &ModelManager) -> Result<()> {
+		Self::refresh_cases(ctx, mm, None).await
+	}
+
+	pub async fn find_by_case(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<Option<CaseReportingDeadline>> {
+		let sql = format!("SELECT * FROM {} WHERE case_id = $1", Self::TABLE);
+		let deadline = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, CaseReportingDeadline>(&sql).bind(case_id),
+			)
+			.await?;
+		Ok(deadline)
+	}
+
+	pub async fn get_by_case(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<CaseReportingDeadline> {
+		Self::find_by_case(ctx, mm, case_id).await?.ok_or(
+			Error::EntityUuidNotFound {
+				entity: Self::TABLE,
+				id: case_id,
+			},
+		)
+	}
+
+	/// Lists open cases that are overdue or due within `filter.within_days`
+	/// (clamped to `0..=MAX_WITHIN_DAYS`), earliest due date first. Reads the
+	/// stored deadlines; case writes, status transitions, safety report and
+	/// reaction edits, and [`Self::refresh_open`] keep them current.
+	pub async fn list_due(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		filter: CaseDeadlineFilter,
+	) -> Result<Vec<CaseDeadlineListItem>> {
+		let today = OffsetDateTime::now_utc().date();
+		let within_days = filter.within_days.unwrap_or(0).clamp(0, MAX_WITHIN_DAYS);
+		let horizon = today.saturating_add(Duration::days(within_days));
+		let open: Vec<String> =
+			OPEN_STATUSES.iter().map(|s| s.to_string()).collect();
+		let sql = format!(
+			"SELECT c.id AS case_id, c.organization_id, c.safety_report_id, c.version,
+			        c.status, d.validation_profile, d.clock_start_date, d.timeline_days,
+			        d.due_date, d.basis,
+			        (d.due_date - $1::date) AS days_remaining,
+			        (d.due_date < $1::date) AS overdue
+			 FROM {} d
+			 JOIN cases c ON c.id = d.case_id
+			 WHERE d.due_date IS NOT NULL AND d.due_date <= $2
+			   AND c.status = ANY($3) AND c.superseded_by IS NULL
+			   AND ($4::uuid IS NULL OR c.organization_id = $4)
+			 ORDER BY d.due_date, c.safety_report_id",
+			Self::TABLE
+		);
+		let items = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, CaseDeadlineListItem>(&sql)
+					.bind(today)
+					.bind(horizon)
+					.bind(&open)
+					.bind(filter.organization_id),
+			)
+			.await?;
+		Ok(items)
+	}
+
+	async fn delete_stale(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Uuid,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		let sql = format!("DELETE FROM {} WHERE case_id = $1", Self::TABLE);
+		match dbx.execute(sqlx::query(&sql).bind(case_id)).await {
+			Ok(_) => {
+				dbx.commit_txn().await?;
+				Ok(())
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err.into())
+			}
+		}
+	}
+
+	/// Computes the clock of `case_id`, or of every open case when `None`, and
+	/// upserts the ones that changed.
+	async fn refresh_cases(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		case_id: Option<Uuid>,
+	) -> Result<()> {
+		let open: Vec<String> =
+			OPEN_STATUSES.iter().map(|s| s.to_string()).collect();
+		let sql =
+			"SELECT c.id AS case_id, c.validation_profile, c.version, s.report_type,
+			        s.date_first_received_from_source, s.date_of_most_recent_information,
+			        s.fulfil_expedited_criteria,
+			        COALESCE(bool_or(r.serious), false) AS serious,
+			        COALESCE(bool_or(r.criteria_death OR r.criteria_life_threatening), false)
+			            AS fatal_or_life_threatening
+			 FROM cases c
+			 JOIN safety_report_identification s ON s.case_id = c.id
+			 LEFT JOIN reactions r ON r.case_id = c.id
+			 WHERE CASE WHEN $1::uuid IS NULL
+			            THEN c.status = ANY($2) AND c.superseded_by IS NULL
+			            ELSE c.id = $1 END
+			 GROUP BY c.id, s.id";
+		let rows = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, ClockRow>(sql).bind(case_id).bind(&open))
+			.await?;
+		if rows.is_empty() {
+			// A case whose C.1 section was removed no longer has a clock.
+			if let Some(case_id) = case_id {
+				Self::delete_stale(ctx, mm, case_id).await?;
+			}
+			return Ok(());
+		}
+
+		let mut case_ids = Vec::with_capacity(rows.len());
+		let mut profiles = Vec::with_capacity(rows.len());
+		let mut clock_starts = Vec::with_capacity(rows.len());
+		let mut timeline_days = Vec::with_capacity(rows.len());
+		let mut due_dates = Vec::with_capacity(rows.len());
+		let mut bases = Vec::with_capacity(rows.len());
+		for row in rows {
+			let clock = compute_reporting_clock(&ReportingClockInput {
+				validation_profile: row.validation_profile,
+				version: row.version,
+				report_type: row.report_type,
+				date_first_received_from_source: row.date_first_received_from_source,
+				date_of_most_recent_information: row.date_of_most_recent_information,
+				fulfil_expedited_criteria: row.fulfil_expedited_criteria,
+				serious: row.serious,
+				fatal_or_life_threatening: row.fatal_or_life_threatening,
+			});
+			case_ids.push(row.case_id);
+			profiles.push(clock.validation_profile.to_string());
+			clock_starts.push(clock.clock_start_date);
+			timeline_days.push(clock.timeline_days);
+			due_dates.push(clock.due_date);
+			bases.push(clock.basis.as_str().to_string());
+		}
+
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		// Only changed clocks are written, so reading deadlines does not flood
+		// the audit log.
+		let sql = format!(
+			"INSERT INTO {table} AS d
+			     (case_id, validation_profile, clock_start_date, timeline_days, due_date,
+			      basis, created_by)
+			 SELECT u.case_id, u.validation_profile, u.clock_start_date, u.timeline_days,
+			        u.due_date, u.basis, $7
+			 FROM unnest($1::uuid[], $2::text[], $3::date[], $4::int[], $5::date[],
+			             $6::text[])
+			      AS u(case_id, validation_profile, clock_start_date, timeline_days,
+			           due_date, basis)
+			 ON CONFLICT (case_id) DO UPDATE
+			 SET validation_profile = EXCLUDED.validation_profile,
+			     clock_start_date = EXCLUDED.clock_start_date,
+			     timeline_days = EXCLUDED.timeline_days,
+			     due_date = EXCLUDED.due_date,
+			     basis = EXCLUDED.basis,
+			     updated_by = $7
+			 WHERE (d.validation_profile, d.clock_start_date, d.timeline_days, d.due_date,
+			        d.basis)
+			       IS DISTINCT FROM
+			       (EXCLUDED.validation_profile, EXCLUDED.clock_start_date,
+			        EXCLUDED.timeline_days, EXCLUDED.due_date, EXCLUDED.basis)",
+			table = Self::TABLE
+		);
+		let res = dbx
+			.execute(
+				sqlx::query(&sql)
+					.bind(&case_ids)
+					.bind(&profiles)
+					.bind(&clock_starts)
+					.bind(&timeline_days)
+					.bind(&due_dates)
+					.bind(&bases)
+					.bind(ctx.user_id()),
+			)
+			.await;
+		match res {
+			Ok(_) => {
+				dbx.commit_txn().await?;
+				Ok(())
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err.into())
+			}
+		}
+	}
+}
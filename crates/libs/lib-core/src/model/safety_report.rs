@@ -4,6 +4,7 @@ use crate::ctx::Ctx;
 use crate::model::base::base_uuid;
 use crate::model::base::DbBmc;
 use crate::model::modql_utils::uuid_to_sea_value;
+use crate::model::reporting_deadline::CaseReportingDeadlineBmc;
 use crate::model::store::set_full_context_dbx_or_rollback;
 use crate::model::ModelManager;
 use crate::model::Result;
@@ -92,6 +93,8 @@ pub struct SenderInformation {
 
 	// C.3.1 - Sender Type (MANDATORY)
 	pub sender_type: String,
+	// C.3.1.KR.1 - Health Professional Type (MFDS)
+	pub kr_health_professional_type: Option<String>,
 
 	// C.3.2 - Sender's Organisation (MANDATORY)
 	pub organization_name: String,
@@ -130,6 +133,7 @@ pub struct SenderInformationForCreate {
 #[derive(Fields, Deserialize)]
 pub struct SenderInformationForUpdate {
 	pub sender_type: Option<String>,
+	pub kr_health_professional_type: Option<String>,
 	pub organization_name: Option<String>,
 	pub department: Option<String>,
 	pub street_address: Option<String>,
@@ -181,6 +185,8 @@ pub struct PrimarySource {
 
 	// C.2.r.4 - Qualification (MANDATORY within primary source)
 	pub qualification: Option<String>,
+	// C.2.r.4.KR.1 - Other Health Professional Type (MFDS)
+	pub kr_other_health_professional: Option<String>,
 
 	// C.2.r.5 - Primary Source for Regulatory Purposes (MANDATORY)
 	pub primary_source_regulatory: Option<String>,
@@ -215,6 +221,7 @@ pub struct PrimarySourceForUpdate {
 	pub country_code: Option<String>,
 	pub email: Option<String>,
 	pub qualification: Option<String>,
+	pub kr_other_health_professional: Option<String>,
 	pub primary_source_regulatory: Option<String>,
 }
 
@@ -326,6 +333,8 @@ pub struct StudyInformation {
 	pub study_name: Option<String>,
 	pub sponsor_study_number: Option<String>,
 	pub study_type_reaction: Option<String>,
+	// C.5.4.KR.1 - Other Studies Type (MFDS)
+	pub kr_other_study_type: Option<String>,
 
 	pub created_at: OffsetDateTime,
 	pub updated_at: OffsetDateTime,
@@ -345,6 +354,7 @@ pub struct StudyInformationForUpdate {
 	pub study_name: Option<String>,
 	pub sponsor_study_number: Option<String>,
 	pub study_type_reaction: Option<String>,
+	pub kr_other_study_type: Option<String>,
 }
 
 #[derive(FilterNodes, Deserialize, Default)]
@@ -431,6 +441,8 @@ impl SafetyReportIdentificationBmc {
 					.bind(ctx.user_id()),
 			)
 			.await?;
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, data.case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(id)
 	}
@@ -506,6 +518,8 @@ impl SafetyReportIdentificationBmc {
 				id: case_id,
 			});
 		}
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(())
 	}
@@ -533,6 +547,8 @@ impl SafetyReportIdentificationBmc {
 				id: case_id,
 			});
 		}
+		CaseReportingDeadlineBmc::refresh_in_txn_or_rollback(ctx, mm, case_id)
+			.await?;
 		mm.dbx().commit_txn().await?;
 		Ok(())
 	}
@@ -2,6 +2,13 @@
 
 use crate::ctx::Ctx;
 use crate::model::base::DbBmc;
+use crate::model::meddra::{MeddraHierarchyBmc, MeddraPath, LEVEL_LLT, LEVEL_PT};
+use crate::model::terminology_version::{
+	default_version_condition, TerminologyDictionary, TerminologyVersionBmc,
+};
+use crate::model::whodrug::{
+	WhodrugIngredient, WhodrugIngredientBmc, WhodrugProductAtc,
+};
 use crate::model::ModelManager;
 use crate::model::Result;
 use modql::field::Fields;
@@ -9,6 +16,7 @@ use modql::filter::{FilterNodes, OpValsBool, OpValsString};
 use serde::{Deserialize, Serialize};
 use sqlx::types::time::OffsetDateTime;
 use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
 
 // -- MeddraTerm
 
@@ -22,6 +30,16 @@ pub struct MeddraTerm {
 	pub language: String,
 	pub active: bool,
 	pub created_at: OffsetDateTime,
+	pub pt_code: Option<String>, // LLT: its PT; PT: itself
+	pub is_current: bool,        // LLT currency flag
+}
+
+/// A MedDRA search result with the PT and primary SOC path of LLT/PT hits.
+#[derive(Debug, Clone, Serialize)]
+pub struct MeddraSearchHit {
+	#[serde(flatten)]
+	pub term: MeddraTerm,
+	pub primary_path: Option<MeddraPath>,
 }
 
 #[derive(Fields, Deserialize)]
@@ -31,6 +49,7 @@ pub struct MeddraTermForCreate {
 	pub level: String,
 	pub version: String,
 	pub language: Option<String>,
+	pub pt_code: Option<String>,
 }
 
 #[derive(FilterNodes, Deserialize, Default)]
@@ -53,6 +72,18 @@ pub struct WhodrugProduct {
 	pub language: String,
 	pub active: bool,
 	pub created_at: OffsetDateTime,
+	pub drug_record_number: Option<String>,
+	pub sequence_number_1: Option<String>,
+	pub sequence_number_2: Option<String>,
+}
+
+/// A WHODrug search result with its active ingredients and ATC codes.
+#[derive(Debug, Clone, Serialize)]
+pub struct WhodrugSearchHit {
+	#[serde(flatten)]
+	pub product: WhodrugProduct,
+	pub ingredients: Vec<WhodrugIngredient>,
+	pub atc: Vec<WhodrugProductAtc>,
 }
 
 #[derive(Fields, Deserialize)]
@@ -96,6 +127,44 @@ pub struct E2bCodeList {
 	pub description: Option<String>,
 	pub sort_order: Option<i32>,
 	pub active: bool,
+	/// Code system OID sent in `@codeSystem`
+	pub oid: Option<String>,
+	/// Validation profile using the list: `ich`, `fda`, `mfds`, `ema` or `pmda`
+	pub profile: String,
+}
+
+/// Active codes of every E2B code list, loaded once per import, validation
+/// or export so coded fields are checked against the database.
+#[derive(Debug, Clone, Default)]
+pub struct CodeListCatalog {
+	lists: HashMap<String, HashSet<String>>,
+}
+
+impl CodeListCatalog {
+	pub fn from_codes<I, L, C>(codes: I) -> Self
+	where
+		I: IntoIterator<Item = (L, C)>,
+		L: Into<String>,
+		C: Into<String>,
+	{
+		let mut lists: HashMap<String, HashSet<String>> = HashMap::new();
+		for (list_name, code) in codes {
+			lists
+				.entry(list_name.into())
+				.or_default()
+				.insert(code.into());
+		}
+		Self { lists }
+	}
+
+	pub fn has_list(&self, list_name: &str) -> bool {
+		self.lists.contains_key(list_name)
+	}
+
+	/// Whether `code` is in the list; `None` when the list is not loaded.
+	pub fn contains(&self, list_name: &str, code: &str) -> Option<bool> {
+		self.lists.get(list_name).map(|codes| codes.contains(code))
+	}
 }
 
 #[derive(Fields, Deserialize)]
@@ -121,48 +190,96 @@ impl DbBmc for MeddraTermBmc {
 }
 
 impl MeddraTermBmc {
+	/// Terms matching `query`. Without a version, the organization's active
+	/// MedDRA version is searched (see `TerminologyVersionBmc::activate`).
 	pub async fn search(
-		_ctx: &Ctx,
+		ctx: &Ctx,
 		mm: &ModelManager,
 		query: &str,
 		version: Option<&str>,
 		limit: i64,
 	) -> Result<Vec<MeddraTerm>> {
-		let sql = if let Some(_ver) = version {
-			format!(
-				"SELECT * FROM {} WHERE term ILIKE $1 AND version = $2 AND active = true ORDER BY term LIMIT $3",
-				Self::TABLE
-			)
-		} else {
-			format!(
-				"SELECT * FROM {} WHERE term ILIKE $1 AND active = true ORDER BY term LIMIT $2",
-				Self::TABLE
-			)
-		};
-
-		let search_pattern = format!("%{query}%");
-
-		let terms = if let Some(ver) = version {
-			mm.dbx()
-				.fetch_all(
-					sqlx::query_as::<_, MeddraTerm>(&sql)
-						.bind(&search_pattern)
-						.bind(ver)
-						.bind(limit),
-				)
-				.await?
-		} else {
-			mm.dbx()
-				.fetch_all(
-					sqlx::query_as::<_, MeddraTerm>(&sql)
-						.bind(&search_pattern)
-						.bind(limit),
+		let version = match version {
+			Some(version) => Some(version.to_string()),
+			None => {
+				TerminologyVersionBmc::active_version(
+					ctx,
+					mm,
+					TerminologyDictionary::Meddra,
 				)
 				.await?
+			}
 		};
+		let sql = format!(
+			"SELECT * FROM {} WHERE term ILIKE $1 AND {} AND active = true
+			 ORDER BY term LIMIT $3",
+			Self::TABLE,
+			default_version_condition(TerminologyDictionary::Meddra, "$2")
+		);
+
+		let terms = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, MeddraTerm>(&sql)
+					.bind(format!("%{query}%"))
+					.bind(version)
+					.bind(limit),
+			)
+			.await?;
 
 		Ok(terms)
 	}
+
+	/// Same as `search`, with the primary PT -> SOC path of each LLT/PT hit.
+	pub async fn search_with_hierarchy(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		query: &str,
+		version: Option<&str>,
+		limit: i64,
+	) -> Result<Vec<MeddraSearchHit>> {
+		let terms = Self::search(ctx, mm, query, version, limit).await?;
+
+		let mut pts_by_release: Vec<((String, String), Vec<String>)> = Vec::new();
+		for term in terms.iter().filter(|t| is_llt_or_pt(t)) {
+			let key = (term.version.clone(), term.language.clone());
+			let pt_code = term.pt_code.clone().unwrap_or_else(|| term.code.clone());
+			match pts_by_release.iter_mut().find(|(k, _)| *k == key) {
+				Some((_, pts)) => pts.push(pt_code),
+				None => pts_by_release.push((key, vec![pt_code])),
+			}
+		}
+		let mut paths = Vec::new();
+		for ((version, language), pt_codes) in &pts_by_release {
+			paths.extend(
+				MeddraHierarchyBmc::paths_for_pts(
+					ctx, mm, version, language, pt_codes, true,
+				)
+				.await?,
+			);
+		}
+
+		Ok(terms
+			.into_iter()
+			.map(|term| {
+				let primary_path = is_llt_or_pt(&term)
+					.then(|| term.pt_code.as_deref().unwrap_or(&term.code))
+					.and_then(|pt_code| {
+						paths.iter().find(|p| {
+							p.pt_code == pt_code
+								&& p.version == term.version
+								&& p.language == term.language
+						})
+					})
+					.cloned();
+				MeddraSearchHit { term, primary_path }
+			})
+			.collect())
+	}
+}
+
+fn is_llt_or_pt(term: &MeddraTerm) -> bool {
+	term.level == LEVEL_LLT || term.level == LEVEL_PT
 }
 
 pub struct WhodrugProductBmc;
@@ -171,29 +288,129 @@ impl DbBmc for WhodrugProductBmc {
 }
 
 impl WhodrugProductBmc {
+	/// Products matching `query` in the organization's active WHODrug version.
 	pub async fn search(
-		_ctx: &Ctx,
+		ctx: &Ctx,
 		mm: &ModelManager,
 		query: &str,
 		limit: i64,
 	) -> Result<Vec<WhodrugProduct>> {
+		let version = TerminologyVersionBmc::active_version(
+			ctx,
+			mm,
+			TerminologyDictionary::Whodrug,
+		)
+		.await?;
 		let sql = format!(
-			"SELECT * FROM {} WHERE drug_name ILIKE $1 AND active = true ORDER BY drug_name LIMIT $2",
-			Self::TABLE
+			"SELECT * FROM {} WHERE drug_name ILIKE $1 AND {} AND active = true
+			 ORDER BY drug_name LIMIT $3",
+			Self::TABLE,
+			default_version_condition(TerminologyDictionary::Whodrug, "$2")
 		);
 
-		let search_pattern = format!("%{query}%");
 		let products = mm
 			.dbx()
 			.fetch_all(
 				sqlx::query_as::<_, WhodrugProduct>(&sql)
-					.bind(&search_pattern)
+					.bind(format!("%{query}%"))
+					.bind(version)
 					.bind(limit),
 			)
 			.await?;
 
 		Ok(products)
 	}
+
+	/// Products matching `query` in `version` (by default the organization's
+	/// active WHODrug version), with the ingredient breakdown and ATC
+	/// classification of each.
+	pub async fn search_with_ingredients(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		query: &str,
+		version: Option<&str>,
+		limit: i64,
+	) -> Result<Vec<WhodrugSearchHit>> {
+		let version = match version {
+			Some(version) => Some(version.to_string()),
+			None => {
+				TerminologyVersionBmc::active_version(
+					ctx,
+					mm,
+					TerminologyDictionary::Whodrug,
+				)
+				.await?
+			}
+		};
+		let sql = format!(
+			"SELECT * FROM {} WHERE drug_name ILIKE $1 AND active = true AND {}
+			 ORDER BY drug_name, version DESC LIMIT $3",
+			Self::TABLE,
+			default_version_condition(TerminologyDictionary::Whodrug, "$2")
+		);
+		let products = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, WhodrugProduct>(&sql)
+					.bind(format!("%{query}%"))
+					.bind(version)
+					.bind(limit),
+			)
+			.await?;
+
+		let mut codes_by_release: Vec<((String, String), Vec<String>)> = Vec::new();
+		for product in &products {
+			let key = (product.version.clone(), product.language.clone());
+			match codes_by_release.iter_mut().find(|(k, _)| *k == key) {
+				Some((_, codes)) => codes.push(product.code.clone()),
+				None => codes_by_release.push((key, vec![product.code.clone()])),
+			}
+		}
+		let mut ingredients = Vec::new();
+		let mut atc = Vec::new();
+		for ((version, language), codes) in &codes_by_release {
+			for ingredient in WhodrugIngredientBmc::list_for_products(
+				ctx, mm, version, language, codes,
+			)
+			.await?
+			{
+				ingredients.push((version.clone(), language.clone(), ingredient));
+			}
+			for (code, product_atc) in WhodrugIngredientBmc::atc_for_products(
+				ctx, mm, version, language, codes,
+			)
+			.await?
+			{
+				atc.push((version.clone(), language.clone(), code, product_atc));
+			}
+		}
+
+		Ok(products
+			.into_iter()
+			.map(|product| {
+				let same_release = |version: &String, language: &String| {
+					*version == product.version && *language == product.language
+				};
+				WhodrugSearchHit {
+					ingredients: ingredients
+						.iter()
+						.filter(|(v, l, i)| {
+							same_release(v, l) && i.product_code == product.code
+						})
+						.map(|(_, _, i)| i.clone())
+						.collect(),
+					atc: atc
+						.iter()
+						.filter(|(v, l, code, _)| {
+							same_release(v, l) && *code == product.code
+						})
+						.map(|(_, _, _, a)| a.clone())
+						.collect(),
+					product,
+				}
+			})
+			.collect())
+	}
 }
 
 pub struct IsoCountryBmc;
@@ -202,14 +419,22 @@ impl DbBmc for IsoCountryBmc {
 }
 
 impl IsoCountryBmc {
-	pub async fn list_all(_ctx: &Ctx, mm: &ModelManager) -> Result<Vec<IsoCountry>> {
+	/// Active countries of the organization's active country list version.
+	pub async fn list_all(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<IsoCountry>> {
+		let version = TerminologyVersionBmc::active_version(
+			ctx,
+			mm,
+			TerminologyDictionary::IsoCountries,
+		)
+		.await?;
 		let sql = format!(
-			"SELECT * FROM {} WHERE active = true ORDER BY name",
-			Self::TABLE
+			"SELECT * FROM {} WHERE active = true AND {} ORDER BY name",
+			Self::TABLE,
+			default_version_condition(TerminologyDictionary::IsoCountries, "$1")
 		);
 		let countries = mm
 			.dbx()
-			.fetch_all(sqlx::query_as::<_, IsoCountry>(&sql))
+			.fetch_all(sqlx::query_as::<_, IsoCountry>(&sql).bind(version))
 			.await?;
 		Ok(countries)
 	}
@@ -221,19 +446,53 @@ impl DbBmc for E2bCodeListBmc {
 }
 
 impl E2bCodeListBmc {
+	/// Active codes of a list in the organization's active code list version.
 	pub async fn get_by_list_name(
-		_ctx: &Ctx,
+		ctx: &Ctx,
 		mm: &ModelManager,
 		list_name: &str,
 	) -> Result<Vec<E2bCodeList>> {
+		let version = TerminologyVersionBmc::active_version(
+			ctx,
+			mm,
+			TerminologyDictionary::E2bCodeLists,
+		)
+		.await?;
 		let sql = format!(
-			"SELECT * FROM {} WHERE list_name = $1 AND active = true ORDER BY sort_order, code",
-			Self::TABLE
+			"SELECT * FROM {} WHERE list_name = $1 AND active = true AND {}
+			 ORDER BY sort_order, code",
+			Self::TABLE,
+			default_version_condition(TerminologyDictionary::E2bCodeLists, "$2")
 		);
 		let codes = mm
 			.dbx()
-			.fetch_all(sqlx::query_as::<_, E2bCodeList>(&sql).bind(list_name))
+			.fetch_all(
+				sqlx::query_as::<_, E2bCodeList>(&sql)
+					.bind(list_name)
+					.bind(version),
+			)
 			.await?;
 		Ok(codes)
 	}
+
+	/// Active codes of all lists in the organization's active code list
+	/// version.
+	pub async fn catalog(ctx: &Ctx, mm: &ModelManager) -> Result<CodeListCatalog> {
+		let version = TerminologyVersionBmc::active_version(
+			ctx,
+			mm,
+			TerminologyDictionary::E2bCodeLists,
+		)
+		.await?;
+		let sql = format!(
+			"SELECT list_name, code FROM {} WHERE active = true AND {}",
+			Self::TABLE,
+			default_version_condition(TerminologyDictionary::E2bCodeLists, "$1")
+		);
+		let codes: Vec<(String, String)> = mm
+			.dbx()
+			.fetch_all(sqlx::query_as(&sql).bind(version))
+			.await?;
+		Ok(CodeListCatalog::from_codes(codes))
+	}
 }
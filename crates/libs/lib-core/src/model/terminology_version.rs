@@ -0,0 +1,738 @@
+// Terminology releases: bulk import as inactive versions, per-org activation
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::meddra::{
+	parse_meddra_ascii, MeddraHierarchyBmc, LEVEL_HLGT, LEVEL_HLT, LEVEL_LLT,
+	LEVEL_PT, LEVEL_SOC,
+};
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::whodrug::{parse_whodrug_b3, WhodrugIngredientBmc};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use sqlx::types::Uuid;
+use std::collections::{BTreeMap, HashSet};
+
+pub const COUNTRIES_FILE: &str = "countries.txt";
+pub const CODE_LISTS_FILE: &str = "code_lists.txt";
+
+// -- Types
+
+/// A terminology table family that is loaded and activated as a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminologyDictionary {
+	/// MedDRA ASCII distribution (`llt.asc`, `pt.asc`, ...)
+	Meddra,
+	/// WHODrug Global B3 (`DD.txt`, `ING.txt`, `BNA.txt`, ...)
+	Whodrug,
+	/// `countries.txt`: `code<TAB>name`
+	IsoCountries,
+	/// `code_lists.txt`: `list_name<TAB>code<TAB>display_name` followed by the
+	/// optional `description`, `sort_order`, `oid` and `profile` columns
+	E2bCodeLists,
+}
+
+impl TerminologyDictionary {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Meddra => "meddra",
+			Self::Whodrug => "whodrug",
+			Self::IsoCountries => "iso_countries",
+			Self::E2bCodeLists => "e2b_code_lists",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		match value {
+			"meddra" => Some(Self::Meddra),
+			"whodrug" => Some(Self::Whodrug),
+			"iso_countries" => Some(Self::IsoCountries),
+			"e2b_code_lists" => Some(Self::E2bCodeLists),
+			_ => None,
+		}
+	}
+}
+
+/// An uploaded terminology package: the dictionary, its version and files.
+#[derive(Debug, Clone)]
+pub struct TerminologyPackage {
+	pub dictionary: TerminologyDictionary,
+	/// Required except for MedDRA, where `meddra_release.asc` supplies it
+	pub version: Option<String>,
+	pub language: Option<String>,
+	pub files: Vec<(String, Vec<u8>)>,
+}
+
+/// What a package would load, from parsing it without writing.
+#[derive(Debug, Clone, Serialize)]
+pub struct TerminologyValidation {
+	pub dictionary: TerminologyDictionary,
+	pub version: Option<String>,
+	pub language: Option<String>,
+	/// Records per kind, e.g. `llt`, `product`, `country`
+	pub counts: BTreeMap<String, usize>,
+	pub file_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TerminologyVersion {
+	pub id: Uuid,
+	pub dictionary: String,
+	pub version: String,
+	pub language: String,
+	pub summary: JsonValue,
+	pub file_names: Vec<String>,
+	pub created_at: OffsetDateTime,
+	pub created_by: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TerminologyActivationForCreate {
+	pub dictionary: TerminologyDictionary,
+	pub version: String,
+	pub language: Option<String>,
+	/// Admins may activate for another organization; defaults to their own
+	pub organization_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TerminologyActivation {
+	pub id: Uuid,
+	pub organization_id: Uuid,
+	pub terminology_version_id: Uuid,
+	pub dictionary: String,
+	pub version: String,
+	pub language: String,
+	pub previous_version: Option<String>,
+	pub activated_at: OffsetDateTime,
+	pub created_by: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsoCountryRecord {
+	pub code: String,
+	pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeListRecord {
+	pub list_name: String,
+	pub code: String,
+	pub display_name: String,
+	pub description: Option<String>,
+	pub sort_order: Option<i32>,
+	pub oid: Option<String>,
+	pub profile: String,
+}
+
+// -- Package parsing
+
+fn package_file<'a>(
+	files: &'a [(String, Vec<u8>)],
+	name: &str,
+) -> Result<(String, std::borrow::Cow<'a, str>)> {
+	files
+		.iter()
+		.find(|(path, _)| {
+			path.rsplit(['/', '\\'])
+				.next()
+				.unwrap_or(path)
+				.eq_ignore_ascii_case(name)
+		})
+		.map(|(_, content)| (name.to_string(), String::from_utf8_lossy(content)))
+		.ok_or_else(|| Error::TerminologyLoad {
+			file: name.to_string(),
+			message: "file missing from the package".to_string(),
+		})
+}
+
+/// Tab-separated rows with their 1-based line number; blank lines and a
+/// header row starting with `header` are skipped.
+fn tsv_rows<'a>(
+	content: &'a str,
+	header: &'a str,
+) -> impl Iterator<Item = (usize, Vec<&'a str>)> + 'a {
+	content
+		.lines()
+		.enumerate()
+		.map(|(idx, line)| (idx + 1, line.trim_start_matches('\u{feff}')))
+		.filter(|(_, line)| !line.trim().is_empty())
+		.map(|(line, row)| {
+			(line, row.split('\t').map(str::trim).collect::<Vec<_>>())
+		})
+		.filter(move |(line, fields)| {
+			!(*line == 1 && fields[0].eq_ignore_ascii_case(header))
+		})
+}
+
+fn row_error(file: &str, line: usize, message: impl std::fmt::Display) -> Error {
+	Error::TerminologyLoad {
+		file: file.to_string(),
+		message: format!("line {line}: {message}"),
+	}
+}
+
+/// Parses `countries.txt`: ISO 3166-1 alpha-2 code and country name.
+pub fn parse_iso_countries(
+	files: &[(String, Vec<u8>)],
+) -> Result<Vec<IsoCountryRecord>> {
+	let (file, content) = package_file(files, COUNTRIES_FILE)?;
+	let mut seen = HashSet::new();
+	let mut countries = Vec::new();
+	for (line, fields) in tsv_rows(&content, "code") {
+		let (code, name) = match fields.as_slice() {
+			[code, name, ..] if !name.is_empty() => (*code, *name),
+			_ => return Err(row_error(&file, line, "expected code and name")),
+		};
+		if code.len() != 2 || !code.bytes().all(|b| b.is_ascii_uppercase()) {
+			return Err(row_error(
+				&file,
+				line,
+				format!("'{code}' is not an ISO 3166-1 alpha-2 code"),
+			));
+		}
+		if !seen.insert(code) {
+			return Err(row_error(&file, line, format!("duplicate code {code}")));
+		}
+		countries.push(IsoCountryRecord {
+			code: code.to_string(),
+			name: name.to_string(),
+		});
+	}
+	Ok(countries)
+}
+
+/// Parses `code_lists.txt`: list name, code, display name and the optional
+/// description, sort order, code system OID and profile (`ich` by default).
+pub fn parse_code_lists(files: &[(String, Vec<u8>)]) -> Result<Vec<CodeListRecord>> {
+	let (file, content) = package_file(files, CODE_LISTS_FILE)?;
+	let mut seen = HashSet::new();
+	let mut codes = Vec::new();
+	for (line, fields) in tsv_rows(&content, "list_name") {
+		let [list_name, code, display_name] = match fields.as_slice() {
+			[list_name, code, display_name, ..]
+				if !list_name.is_empty()
+					&& !code.is_empty()
+					&& !display_name.is_empty() =>
+			{
+				[*list_name, *code, *display_name]
+			}
+			_ => {
+				return Err(row_error(
+					&file,
+					line,
+					"expected list_name, code and display_name",
+				))
+			}
+		};
+		if list_name.len() > 100 || code.len() > 10 {
+			return Err(row_error(
+				&file,
+				line,
+				"list_name is limited to 100 and code to 10 characters",
+			));
+		}
+		let sort_order = match fields.get(4).filter(|v| !v.is_empty()) {
+			Some(value) => Some(value.parse::<i32>().map_err(|_| {
+				row_error(
+					&file,
+					line,
+					format!("sort_order '{value}' is not a number"),
+				)
+			})?),
+			None => None,
+		};
+		let profile = match fields.get(6).copied().filter(|v| !v.is_empty()) {
+			Some(value @ ("ich" | "fda" | "mfds" | "ema" | "pmda")) => value.to_string(),
+			Some(value) => {
+				return Err(row_error(
+					&file,
+					line,
+					format!("profile '{value}' is not ich, fda, mfds, ema or pmda"),
+				))
+			}
+			None => "ich".to_string(),
+		};
+		if !seen.insert((list_name, code)) {
+			return Err(row_error(
+				&file,
+				line,
+				format!("duplicate code {code} in list {list_name}"),
+			));
+		}
+		codes.push(CodeListRecord {
+			list_name: list_name.to_string(),
+			code: code.to_string(),
+			display_name: display_name.to_string(),
+			description: fields
+				.get(3)
+				.filter(|v| !v.is_empty())
+				.map(|v| v.to_string()),
+			sort_order,
+			oid: fields
+				.get(5)
+				.filter(|v| !v.is_empty())
+				.map(|v| v.to_string()),
+			profile,
+		});
+	}
+	Ok(codes)
+}
+
+/// Parses a package without writing anything, reporting what it holds.
+pub fn validate_terminology_package(
+	package: &TerminologyPackage,
+) -> Result<TerminologyValidation> {
+	let mut version = package.version.clone();
+	let mut language = package.language.clone();
+	let counts: Vec<(&str, usize)> = match package.dictionary {
+		TerminologyDictionary::Meddra => {
+			let release = parse_meddra_ascii(&package.files)?;
+			version = version.or(release.version.clone());
+			language = language.or(release.language.clone());
+			vec![
+				("llt", release.count(LEVEL_LLT)),
+				("pt", release.count(LEVEL_PT)),
+				("hlt", release.count(LEVEL_HLT)),
+				("hlgt", release.count(LEVEL_HLGT)),
+				("soc", release.count(LEVEL_SOC)),
+				("hierarchy", release.hierarchy.len()),
+			]
+		}
+		TerminologyDictionary::Whodrug => {
+			let release = parse_whodrug_b3(&package.files)?;
+			vec![
+				("product", release.records.len()),
+				("ingredient", release.ingredients.len()),
+				("product_atc", release.product_atc.len()),
+				("atc_class", release.atc_classes.len()),
+			]
+		}
+		TerminologyDictionary::IsoCountries => {
+			vec![("country", parse_iso_countries(&package.files)?.len())]
+		}
+		TerminologyDictionary::E2bCodeLists => {
+			vec![("code", parse_code_lists(&package.files)?.len())]
+		}
+	};
+	Ok(TerminologyValidation {
+		dictionary: package.dictionary,
+		version,
+		language,
+		counts: counts
+			.into_iter()
+			.map(|(kind, count)| (kind.to_string(), count))
+			.collect(),
+		file_names: package.files.iter().map(|(name, _)| name.clone()).collect(),
+	})
+}
+
+// -- Default version filtering
+
+/// SQL condition on the `version` column of a terminology table for searches
+/// without an explicit version: the organization's active version when it
+/// has one (bound as `param`), otherwise only rows that were never loaded
+/// through the import endpoints (e.g. seeded by `docs/dev_initial`).
+pub(crate) fn default_version_condition(
+	dictionary: TerminologyDictionary,
+	param: &str,
+) -> String {
+	format!(
+		"(({param}::text IS NOT NULL AND version = {param})
+		  OR ({param}::text IS NULL AND version NOT IN (
+		      SELECT tv.version FROM terminology_versions tv
+		      WHERE tv.dictionary = '{}')))",
+		dictionary.as_str()
+	)
+}
+
+// -- Bmc
+
+pub struct TerminologyVersionBmc;
+impl DbBmc for TerminologyVersionBmc {
+	const TABLE: &'static str = "terminology_versions";
+}
+
+impl TerminologyVersionBmc {
+	/// Validates and loads a package as a new version, inactive until an
+	/// organization activates it. Loading and registering the version run in
+	/// one transaction; a version already registered is rejected.
+	pub async fn import(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		package: TerminologyPackage,
+	) -> Result<TerminologyVersion> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::import_in_txn(ctx, &mm, &package).await {
+			Ok(version) => {
+				dbx.commit_txn().await?;
+				Ok(version)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	async fn import_in_txn(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		package: &TerminologyPackage,
+	) -> Result<TerminologyVersion> {
+		let validation = validate_terminology_package(package)?;
+		let dictionary = package.dictionary;
+		let version =
+			validation
+				.version
+				.clone()
+				.ok_or_else(|| Error::TerminologyLoad {
+					file: String::new(),
+					message: format!("{} version not given", dictionary.as_str()),
+				})?;
+		let language = validation
+			.language
+			.clone()
+			.unwrap_or_else(|| "en".to_string());
+		if Self::find(mm, dictionary, &version, &language)
+			.await?
+			.is_some()
+		{
+			return Err(Error::TerminologyVersionExists {
+				dictionary: dictionary.as_str().to_string(),
+				version,
+				language,
+			});
+		}
+
+		let files = &package.files;
+		let summary = match dictionary {
+			TerminologyDictionary::Meddra => serde_json::to_value(
+				MeddraHierarchyBmc::load_ascii_in_txn(
+					mm,
+					Some(&version),
+					Some(&language),
+					files,
+				)
+				.await?,
+			),
+			TerminologyDictionary::Whodrug => serde_json::to_value(
+				WhodrugIngredientBmc::load_b3_in_txn(
+					mm,
+					&version,
+					Some(&language),
+					files,
+				)
+				.await?,
+			),
+			TerminologyDictionary::IsoCountries => {
+				Self::write_countries(mm, &version, &parse_iso_countries(files)?)
+					.await?;
+				serde_json::to_value(&validation.counts)
+			}
+			TerminologyDictionary::E2bCodeLists => {
+				Self::write_code_lists(mm, &version, &parse_code_lists(files)?)
+					.await?;
+				serde_json::to_value(&validation.counts)
+			}
+		}
+		.map_err(|err| Error::TerminologyLoad {
+			file: String::new(),
+			message: format!("load summary: {err}"),
+		})?;
+
+		let sql = format!(
+			"INSERT INTO {} (dictionary, version, language, summary, file_names, created_by)
+			 VALUES ($1, $2, $3, $4, $5, $6)
+			 RETURNING id, dictionary, version, language, summary, file_names,
+			           created_at, created_by",
+			Self::TABLE
+		);
+		let registered = mm
+			.dbx()
+			.fetch_one(
+				sqlx::query_as::<_, TerminologyVersion>(&sql)
+					.bind(dictionary.as_str())
+					.bind(&version)
+					.bind(&language)
+					.bind(summary)
+					.bind(&validation.file_names)
+					.bind(ctx.user_id()),
+			)
+			.await?;
+		Ok(registered)
+	}
+
+	async fn write_countries(
+		mm: &ModelManager,
+		version: &str,
+		countries: &[IsoCountryRecord],
+	) -> Result<()> {
+		let dbx = mm.dbx();
+		dbx.execute(
+			sqlx::query("DELETE FROM iso_countries WHERE version = $1")
+				.bind(version),
+		)
+		.await?;
+		let (codes, names): (Vec<&str>, Vec<&str>) = countries
+			.iter()
+			.map(|c| (c.code.as_str(), c.name.as_str()))
+			.unzip();
+		dbx.execute(
+			sqlx::query(
+				"INSERT INTO iso_countries (code, name, version)
+				 SELECT code, name, $3
+				 FROM unnest($1::text[], $2::text[]) AS t(code, name)",
+			)
+			.bind(codes)
+			.bind(names)
+			.bind(version),
+		)
+		.await?;
+		Ok(())
+	}
+
+	async fn write_code_lists(
+		mm: &ModelManager,
+		version: &str,
+		codes: &[CodeListRecord],
+	) -> Result<()> {
+		let dbx = mm.dbx();
+		dbx.execute(
+			sqlx::query("DELETE FROM e2b_code_lists WHERE version = $1")
+				.bind(version),
+		)
+		.await?;
+		dbx.execute(
+			sqlx::query(
+				"INSERT INTO e2b_code_lists
+				     (list_name, code, display_name, description, sort_order, oid,
+				      profile, version)
+				 SELECT list_name, code, display_name, description, sort_order, oid,
+				        profile, $8
+				 FROM unnest($1::text[], $2::text[], $3::text[], $4::text[], $5::int4[],
+				             $6::text[], $7::text[])
+				     AS t(list_name, code, display_name, description, sort_order, oid,
+				          profile)",
+			)
+			.bind(
+				codes
+					.iter()
+					.map(|c| c.list_name.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(codes.iter().map(|c| c.code.as_str()).collect::<Vec<_>>())
+			.bind(
+				codes
+					.iter()
+					.map(|c| c.display_name.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				codes
+					.iter()
+					.map(|c| c.description.as_deref())
+					.collect::<Vec<_>>(),
+			)
+			.bind(codes.iter().map(|c| c.sort_order).collect::<Vec<_>>())
+			.bind(codes.iter().map(|c| c.oid.as_deref()).collect::<Vec<_>>())
+			.bind(codes.iter().map(|c| c.profile.as_str()).collect::<Vec<_>>())
+			.bind(version),
+		)
+		.await?;
+		Ok(())
+	}
+
+	async fn find(
+		mm: &ModelManager,
+		dictionary: TerminologyDictionary,
+		version: &str,
+		language: &str,
+	) -> Result<Option<TerminologyVersion>> {
+		let sql = format!(
+			"SELECT id, dictionary, version, language, summary, file_names,
+			        created_at, created_by
+			 FROM {}
+			 WHERE dictionary = $1 AND version = $2 AND language = $3",
+			Self::TABLE
+		);
+		let found = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, TerminologyVersion>(&sql)
+					.bind(dictionary.as_str())
+					.bind(version)
+					.bind(language),
+			)
+			.await?;
+		Ok(found)
+	}
+
+	/// Registered versions, newest first.
+	pub async fn list(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		dictionary: Option<TerminologyDictionary>,
+	) -> Result<Vec<TerminologyVersion>> {
+		let sql = format!(
+			"SELECT id, dictionary, version, language, summary, file_names,
+			        created_at, created_by
+			 FROM {}
+			 WHERE ($1::text IS NULL OR dictionary = $1)
+			 ORDER BY created_at DESC",
+			Self::TABLE
+		);
+		let versions = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, TerminologyVersion>(&sql)
+					.bind(dictionary.map(TerminologyDictionary::as_str)),
+			)
+			.await?;
+		Ok(versions)
+	}
+
+	/// Makes a registered version the one served by default to the
+	/// organization, keeping the previous activation in the history.
+	pub async fn activate(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		activation: TerminologyActivationForCreate,
+	) -> Result<TerminologyActivation> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::activate_in_txn(ctx, &mm, &activation).await {
+			Ok(activated) => {
+				dbx.commit_txn().await?;
+				Ok(activated)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	async fn activate_in_txn(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		activation: &TerminologyActivationForCreate,
+	) -> Result<TerminologyActivation> {
+		let dictionary = activation.dictionary;
+		let language = activation.language.as_deref().unwrap_or("en");
+		let organization_id =
+			activation.organization_id.unwrap_or(ctx.organization_id());
+		let Some(version) =
+			Self::find(mm, dictionary, &activation.version, language).await?
+		else {
+			return Err(Error::TerminologyVersionNotFound {
+				dictionary: dictionary.as_str().to_string(),
+				version: activation.version.clone(),
+				language: language.to_string(),
+			});
+		};
+		let previous = Self::active_for(mm, organization_id, dictionary).await?;
+
+		let activated = mm
+			.dbx()
+			.fetch_one(
+				sqlx::query_as::<_, TerminologyActivation>(
+					"INSERT INTO terminology_activations
+					     (organization_id, terminology_version_id, dictionary, version,
+					      language, previous_version, created_by)
+					 VALUES ($1, $2, $3, $4, $5, $6, $7)
+					 RETURNING id, organization_id, terminology_version_id, dictionary,
+					           version, language, previous_version, activated_at,
+					           created_by",
+				)
+				.bind(organization_id)
+				.bind(version.id)
+				.bind(dictionary.as_str())
+				.bind(&version.version)
+				.bind(&version.language)
+				.bind(previous.map(|p| p.version))
+				.bind(ctx.user_id()),
+			)
+			.await?;
+		Ok(activated)
+	}
+
+	/// Activation history of the organization, most recent first.
+	pub async fn list_activations(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		dictionary: Option<TerminologyDictionary>,
+	) -> Result<Vec<TerminologyActivation>> {
+		let activations = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, TerminologyActivation>(
+					"SELECT id, organization_id, terminology_version_id, dictionary,
+					        version, language, previous_version, activated_at, created_by
+					 FROM terminology_activations
+					 WHERE organization_id = $1 AND ($2::text IS NULL OR dictionary = $2)
+					 ORDER BY activated_at DESC, created_at DESC",
+				)
+				.bind(ctx.organization_id())
+				.bind(dictionary.map(TerminologyDictionary::as_str)),
+			)
+			.await?;
+		Ok(activations)
+	}
+
+	/// Version the organization currently serves by default, if any.
+	pub async fn active_version(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		dictionary: TerminologyDictionary,
+	) -> Result<Option<String>> {
+		Ok(Self::active_for(mm, ctx.organization_id(), dictionary)
+			.await?
+			.map(|activation| activation.version))
+	}
+
+	async fn active_for(
+		mm: &ModelManager,
+		organization_id: Uuid,
+		dictionary: TerminologyDictionary,
+	) -> Result<Option<TerminologyActivation>> {
+		let active = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, TerminologyActivation>(
+					"SELECT id, organization_id, terminology_version_id, dictionary,
+					        version, language, previous_version, activated_at, created_by
+					 FROM terminology_activations
+					 WHERE organization_id = $1 AND dictionary = $2
+					 ORDER BY activated_at DESC, created_at DESC
+					 LIMIT 1",
+				)
+				.bind(organization_id)
+				.bind(dictionary.as_str()),
+			)
+			.await?;
+		Ok(active)
+	}
+}
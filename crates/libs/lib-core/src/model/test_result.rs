@@ -3,11 +3,12 @@
 use crate::ctx::Ctx;
 use crate::model::base::DbBmc;
 use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::partial_date::PartialDate;
 use crate::model::ModelManager;
 use crate::model::Result;
 use modql::field::Fields;
 use serde::{Deserialize, Serialize};
-use sqlx::types::time::{Date, OffsetDateTime};
+use sqlx::types::time::OffsetDateTime;
 use sqlx::types::Uuid;
 use sqlx::FromRow;
 
@@ -20,7 +21,7 @@ pub struct TestResult {
 	pub sequence_number: i32,
 
 	// F.r.1 - Test Date
-	pub test_date: Option<Date>,
+	pub test_date: Option<PartialDate>,
 
 	// F.r.2 - Test Name
 	pub test_name: String,
@@ -65,10 +66,10 @@ pub struct TestResultForCreate {
 	pub test_name: String,
 }
 
-#[derive(Fields, Deserialize)]
+#[derive(Fields, Deserialize, Default)]
 pub struct TestResultForUpdate {
 	pub test_name: Option<String>,
-	pub test_date: Option<Date>,
+	pub test_date: Option<PartialDate>,
 	pub test_meddra_version: Option<String>,
 	pub test_meddra_code: Option<String>,
 	pub test_result_code: Option<String>,
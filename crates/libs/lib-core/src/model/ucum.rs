@@ -0,0 +1,591 @@
+// UCUM units: parsing, validation, synonym normalization and conversion of the
+// unit expressions E2B(R3) requires for doses, test results, ages and durations
+
+use crate::model::{Error, Result};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+/// Exponents of the UCUM base units: m, g, s, rad, K, C, cd, plus one slot
+/// for international units, which only convert among themselves.
+type Dimension = [i8; 8];
+
+const NONE: Dimension = [0; 8];
+const LENGTH: Dimension = [1, 0, 0, 0, 0, 0, 0, 0];
+const MASS: Dimension = [0, 1, 0, 0, 0, 0, 0, 0];
+const TIME: Dimension = [0, 0, 1, 0, 0, 0, 0, 0];
+const ANGLE: Dimension = [0, 0, 0, 1, 0, 0, 0, 0];
+const TEMPERATURE: Dimension = [0, 0, 0, 0, 1, 0, 0, 0];
+const CHARGE: Dimension = [0, 0, 0, 0, 0, 1, 0, 0];
+const LUMINOSITY: Dimension = [0, 0, 0, 0, 0, 0, 1, 0];
+const INTERNATIONAL_UNIT: Dimension = [0, 0, 0, 0, 0, 0, 0, 1];
+const VOLUME: Dimension = [3, 0, 0, 0, 0, 0, 0, 0];
+const FREQUENCY: Dimension = [0, 0, -1, 0, 0, 0, 0, 0];
+const FORCE: Dimension = [1, 1, -2, 0, 0, 0, 0, 0];
+const PRESSURE: Dimension = [-1, 1, -2, 0, 0, 0, 0, 0];
+const ENERGY: Dimension = [2, 1, -2, 0, 0, 0, 0, 0];
+const POWER: Dimension = [2, 1, -3, 0, 0, 0, 0, 0];
+const CURRENT: Dimension = [0, 0, -1, 0, 0, 1, 0, 0];
+const VOLTAGE: Dimension = [2, 1, -2, 0, 0, -1, 0, 0];
+const RESISTANCE: Dimension = [2, 1, -1, 0, 0, -2, 0, 0];
+const DOSE: Dimension = [2, 0, -2, 0, 0, 0, 0, 0];
+const SOLID_ANGLE: Dimension = [0, 0, 0, 2, 0, 0, 0, 0];
+
+const AVOGADRO: f64 = 6.0221367e23;
+
+struct Atom {
+	code: &'static str,
+	/// UCUM case-insensitive code, used for all-uppercase input (`MG/DL`).
+	ci: &'static str,
+	/// Magnitude in base units (gram, not kilogram).
+	factor: f64,
+	dimension: Dimension,
+	metric: bool,
+	/// Kelvin offset of the interval scale, for `Cel` and `[degF]`.
+	offset: Option<f64>,
+}
+
+const fn atom(
+	code: &'static str,
+	ci: &'static str,
+	factor: f64,
+	dimension: Dimension,
+	metric: bool,
+) -> Atom {
+	Atom {
+		code,
+		ci,
+		factor,
+		dimension,
+		metric,
+		offset: None,
+	}
+}
+
+const ATOMS: &[Atom] = &[
+	atom("m", "M", 1.0, LENGTH, true),
+	atom("s", "S", 1.0, TIME, true),
+	atom("g", "G", 1.0, MASS, true),
+	atom("rad", "RAD", 1.0, ANGLE, true),
+	atom("K", "K", 1.0, TEMPERATURE, true),
+	atom("C", "C", 1.0, CHARGE, true),
+	atom("cd", "CD", 1.0, LUMINOSITY, true),
+	atom("mol", "MOL", AVOGADRO, NONE, true),
+	atom("eq", "EQ", AVOGADRO, NONE, true),
+	atom("osm", "OSM", AVOGADRO, NONE, true),
+	atom("kat", "KAT", AVOGADRO, FREQUENCY, true),
+	atom("U", "U", AVOGADRO * 1e-6 / 60.0, FREQUENCY, true),
+	atom("L", "L", 1e-3, VOLUME, true),
+	atom("sr", "SR", 1.0, SOLID_ANGLE, true),
+	atom("Hz", "HZ", 1.0, FREQUENCY, true),
+	atom("N", "N", 1e3, FORCE, true),
+	atom("Pa", "PAL", 1e3, PRESSURE, true),
+	atom("J", "J", 1e3, ENERGY, true),
+	atom("W", "W", 1e3, POWER, true),
+	atom("A", "A", 1.0, CURRENT, true),
+	atom("V", "V", 1e3, VOLTAGE, true),
+	atom("Ohm", "OHM", 1e3, RESISTANCE, true),
+	atom("Bq", "BQ", 1.0, FREQUENCY, true),
+	atom("Gy", "GY", 1.0, DOSE, true),
+	atom("Sv", "SV", 1.0, DOSE, true),
+	atom("t", "TNE", 1e6, MASS, true),
+	atom("bar", "BAR", 1e8, PRESSURE, true),
+	atom("cal", "CAL", 4.184e3, ENERGY, true),
+	atom("m[Hg]", "M[HG]", 1.33322e8, PRESSURE, true),
+	atom("[iU]", "[IU]", 1.0, INTERNATIONAL_UNIT, true),
+	atom("min", "MIN", 60.0, TIME, false),
+	atom("h", "HR", 3600.0, TIME, false),
+	atom("d", "D", 86400.0, TIME, false),
+	atom("wk", "WK", 604800.0, TIME, false),
+	atom("mo", "MO", 2629800.0, TIME, false),
+	atom("a", "ANN", 31557600.0, TIME, false),
+	atom("atm", "ATM", 1.01325e8, PRESSURE, false),
+	atom("[Cal]", "[CAL]", 4.184e6, ENERGY, false),
+	atom("[lb_av]", "[LB_AV]", 453.59237, MASS, false),
+	atom("[oz_av]", "[OZ_AV]", 28.349523125, MASS, false),
+	atom("[in_i]", "[IN_I]", 0.0254, LENGTH, false),
+	atom("[ft_i]", "[FT_I]", 0.3048, LENGTH, false),
+	atom("[drp]", "[DRP]", 5e-8, VOLUME, false),
+	atom("%", "%", 1e-2, NONE, false),
+	atom("[ppth]", "[PPTH]", 1e-3, NONE, false),
+	atom("[ppm]", "[PPM]", 1e-6, NONE, false),
+	atom("10*", "10*", 10.0, NONE, false),
+	atom("10^", "10^", 10.0, NONE, false),
+	Atom {
+		code: "Cel",
+		ci: "CEL",
+		factor: 1.0,
+		dimension: TEMPERATURE,
+		metric: true,
+		offset: Some(273.15),
+	},
+	Atom {
+		code: "[degF]",
+		ci: "[DEGF]",
+		factor: 5.0 / 9.0,
+		dimension: TEMPERATURE,
+		metric: false,
+		offset: Some(459.67),
+	},
+];
+
+/// Valid UCUM spellings that normalize to another code of the same unit.
+const ALIASES: &[(&str, &str)] = &[("l", "L"), ("[IU]", "[iU]")];
+
+/// Common non-UCUM spellings found in source systems (matched lowercase).
+const SYNONYMS: &[(&str, &str)] = &[
+	("mcg", "ug"),
+	("cc", "mL"),
+	("iu", "[iU]"),
+	("hr", "h"),
+	("hrs", "h"),
+	("hour", "h"),
+	("hours", "h"),
+	("sec", "s"),
+	("secs", "s"),
+	("day", "d"),
+	("days", "d"),
+	("week", "wk"),
+	("weeks", "wk"),
+	("month", "mo"),
+	("months", "mo"),
+	("yr", "a"),
+	("yrs", "a"),
+	("year", "a"),
+	("years", "a"),
+	("decade", "{decade}"),
+	("decades", "{decade}"),
+	("mmhg", "mm[Hg]"),
+	("degc", "Cel"),
+	("degf", "[degF]"),
+	("lb", "[lb_av]"),
+	("lbs", "[lb_av]"),
+	("oz", "[oz_av]"),
+	("inch", "[in_i]"),
+	("inches", "[in_i]"),
+	("ft", "[ft_i]"),
+	("drop", "[drp]"),
+	("drops", "[drp]"),
+	("gtt", "[drp]"),
+];
+
+/// Two-letter prefixes come first so `da` wins over `d`.
+const PREFIXES: &[(&str, &str, f64)] = &[
+	("da", "DA", 1e1),
+	("Y", "YA", 1e24),
+	("Z", "ZA", 1e21),
+	("E", "EX", 1e18),
+	("P", "PT", 1e15),
+	("T", "TR", 1e12),
+	("G", "GA", 1e9),
+	("M", "MA", 1e6),
+	("k", "K", 1e3),
+	("h", "H", 1e2),
+	("d", "D", 1e-1),
+	("c", "C", 1e-2),
+	("m", "M", 1e-3),
+	("u", "U", 1e-6),
+	("n", "N", 1e-9),
+	("p", "P", 1e-12),
+	("f", "F", 1e-15),
+	("a", "A", 1e-18),
+	("z", "ZO", 1e-21),
+	("y", "YO", 1e-24),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+	/// Case-sensitive UCUM only.
+	Strict,
+	/// Case-insensitive UCUM first, for input without lowercase letters.
+	Uppercase,
+	/// Strict, then synonyms, then case-insensitive UCUM per symbol.
+	Lenient,
+}
+
+/// A parsed UCUM expression with its canonical code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UcumUnit {
+	code: String,
+	factor: f64,
+	dimension: Dimension,
+	/// Kelvin offset when the whole expression is `Cel` or `[degF]`.
+	offset: Option<f64>,
+	/// An offset unit used inside a larger expression; valid, not convertible.
+	has_nested_offset: bool,
+}
+
+impl UcumUnit {
+	/// Parses a case-sensitive UCUM expression (`mg/dL`, `10*3/uL`,
+	/// `mL/min/{1.73_m2}`).
+	pub fn parse(expr: &str) -> Result<Self> {
+		parse_with_mode(expr, Mode::Strict)
+	}
+
+	/// Canonical code: aliases resolved (`l` -> `L`), exponents unsigned.
+	pub fn code(&self) -> &str {
+		&self.code
+	}
+
+	pub fn is_commensurable(&self, other: &UcumUnit) -> bool {
+		self.dimension == other.dimension
+	}
+
+	/// Whether the unit measures time. E2B(R3) also codes decades as the
+	/// annotation `{decade}`.
+	pub fn is_time(&self) -> bool {
+		self.dimension == TIME || self.code == "{decade}"
+	}
+
+	pub fn convert(&self, value: Decimal, to: &UcumUnit) -> Result<Decimal> {
+		let not_convertible = || Error::UcumNotConvertible {
+			from: self.code.clone(),
+			to: to.code.clone(),
+		};
+		if !self.is_commensurable(to)
+			|| self.has_nested_offset
+			|| to.has_nested_offset
+		{
+			return Err(not_convertible());
+		}
+		if self.code == to.code {
+			return Ok(value);
+		}
+		let converted = match (self.offset, to.offset) {
+			(None, None) => {
+				let ratio = Decimal::from_f64(self.factor / to.factor)
+					.and_then(|r| r.round_sf(12))
+					.ok_or_else(not_convertible)?;
+				value.checked_mul(ratio)
+			}
+			_ => {
+				let v = value.to_f64().ok_or_else(not_convertible)?;
+				let kelvin = (v + self.offset.unwrap_or(0.0)) * self.factor;
+				Decimal::from_f64(kelvin / to.factor - to.offset.unwrap_or(0.0))
+					.and_then(|r| r.round_sf(12))
+			}
+		};
+		converted.map(|v| v.normalize()).ok_or_else(not_convertible)
+	}
+}
+
+pub fn is_valid_ucum(expr: &str) -> bool {
+	UcumUnit::parse(expr).is_ok()
+}
+
+/// Normalizes a unit to its canonical UCUM code: case-insensitive UCUM
+/// (`MG/DL`), aliases (`mg/dl`) and common synonyms (`mcg`, `IU`, `hrs`).
+pub fn normalize_ucum(expr: &str) -> Result<String> {
+	let cleaned: String = expr
+		.trim()
+		.chars()
+		.filter(|c| !c.is_whitespace())
+		.map(|c| match c {
+			'\u{00b5}' | '\u{03bc}' => 'u',
+			other => other,
+		})
+		.collect();
+	let uppercase = !cleaned.chars().any(|c| c.is_ascii_lowercase());
+	let first = if uppercase {
+		Mode::Uppercase
+	} else {
+		Mode::Strict
+	};
+	parse_with_mode(&cleaned, first)
+		.or_else(|_| parse_with_mode(&cleaned, Mode::Lenient))
+		.map(|unit| unit.code)
+		.map_err(|_| invalid(expr, "not a UCUM unit expression"))
+}
+
+/// Converts `value` between two commensurable UCUM units, e.g. a test result
+/// in `mg/dL` to the `g/L` of its normal range.
+pub fn convert_ucum(value: Decimal, from: &str, to: &str) -> Result<Decimal> {
+	UcumUnit::parse(from)?.convert(value, &UcumUnit::parse(to)?)
+}
+
+// -- Parser
+
+fn invalid(unit: &str, message: impl Into<String>) -> Error {
+	Error::UcumInvalidUnit {
+		unit: unit.to_string(),
+		message: message.into(),
+	}
+}
+
+fn parse_with_mode(expr: &str, mode: Mode) -> Result<UcumUnit> {
+	if expr.is_empty() {
+		return Err(invalid(expr, "empty unit"));
+	}
+	let mut parser = Parser {
+		chars: expr.chars().collect(),
+		pos: 0,
+		mode,
+	};
+	let unit = parser
+		.main_term()
+		.map_err(|message| invalid(expr, message))?;
+	if parser.pos < parser.chars.len() {
+		return Err(invalid(
+			expr,
+			format!("unexpected '{}'", parser.chars[parser.pos]),
+		));
+	}
+	Ok(unit)
+}
+
+fn unity(code: String) -> UcumUnit {
+	UcumUnit {
+		code,
+		factor: 1.0,
+		dimension: NONE,
+		offset: None,
+		has_nested_offset: false,
+	}
+}
+
+fn combine(left: UcumUnit, op: char, right: UcumUnit) -> UcumUnit {
+	let sign = if op == '/' { -1 } else { 1 };
+	let mut dimension = left.dimension;
+	for (d, r) in dimension.iter_mut().zip(right.dimension) {
+		*d += sign * r;
+	}
+	UcumUnit {
+		code: format!("{}{op}{}", left.code, right.code),
+		factor: if op == '/' {
+			left.factor / right.factor
+		} else {
+			left.factor * right.factor
+		},
+		dimension,
+		offset: None,
+		has_nested_offset: left.has_nested_offset
+			|| right.has_nested_offset
+			|| left.offset.is_some()
+			|| right.offset.is_some(),
+	}
+}
+
+struct Parser {
+	chars: Vec<char>,
+	pos: usize,
+	mode: Mode,
+}
+
+type ParseResult<T> = core::result::Result<T, String>;
+
+impl Parser {
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn main_term(&mut self) -> ParseResult<UcumUnit> {
+		if self.peek() == Some('/') {
+			self.pos += 1;
+			let term = self.term()?;
+			return Ok(combine(unity(String::new()), '/', term));
+		}
+		self.term()
+	}
+
+	fn term(&mut self) -> ParseResult<UcumUnit> {
+		let mut unit = self.component()?;
+		while let Some(op @ ('.' | '/')) = self.peek() {
+			self.pos += 1;
+			let right = self.component()?;
+			unit = combine(unit, op, right);
+		}
+		Ok(unit)
+	}
+
+	fn component(&mut self) -> ParseResult<UcumUnit> {
+		match self.peek() {
+			Some('(') => {
+				self.pos += 1;
+				let inner = self.term()?;
+				if self.peek() != Some(')') {
+					return Err("missing ')'".to_string());
+				}
+				self.pos += 1;
+				Ok(UcumUnit {
+					code: format!("({})", inner.code),
+					has_nested_offset: inner.has_nested_offset
+						|| inner.offset.is_some(),
+					offset: None,
+					..inner
+				})
+			}
+			Some('{') => Ok(unity(self.annotation()?)),
+			Some(_) => {
+				let mut unit = self.annotatable()?;
+				if self.peek() == Some('{') {
+					unit.code.push_str(&self.annotation()?);
+				}
+				Ok(unit)
+			}
+			None => Err("missing unit".to_string()),
+		}
+	}
+
+	fn annotation(&mut self) -> ParseResult<String> {
+		let start = self.pos;
+		self.pos += 1;
+		while let Some(c) = self.peek() {
+			self.pos += 1;
+			match c {
+				'}' => return Ok(self.chars[start..self.pos].iter().collect()),
+				'{' => return Err("nested '{' in annotation".to_string()),
+				c if !c.is_ascii() || c.is_ascii_control() => {
+					return Err(format!("'{c}' is not allowed in annotations"))
+				}
+				_ => {}
+			}
+		}
+		Err("missing '}'".to_string())
+	}
+
+	/// Prefixed atom with an optional exponent (`mg`, `m2`, `s-1`, `10*3`),
+	/// or an integer factor (`1000`).
+	fn annotatable(&mut self) -> ParseResult<UcumUnit> {
+		let start = self.pos;
+		while let Some(c) = self.peek() {
+			match c {
+				'.' | '/' | '(' | ')' | '{' | '}' => break,
+				'[' => {
+					while self.peek().is_some_and(|c| c != ']') {
+						self.pos += 1;
+					}
+					if self.peek().is_none() {
+						return Err("missing ']'".to_string());
+					}
+					self.pos += 1;
+				}
+				_ => self.pos += 1,
+			}
+		}
+		let run: String = self.chars[start..self.pos].iter().collect();
+		if run.is_empty() {
+			return Err("missing unit".to_string());
+		}
+		if run.chars().all(|c| c.is_ascii_digit()) {
+			let factor = run
+				.parse::<f64>()
+				.map_err(|_| format!("invalid factor '{run}'"))?;
+			return Ok(UcumUnit {
+				factor,
+				..unity(run.trim_start_matches('0').to_string())
+			});
+		}
+
+		let digits =
+			run.len() - run.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+		let mut symbol_end = run.len() - digits;
+		let negative = digits > 0 && run[..symbol_end].ends_with('-');
+		if digits > 0 && (negative || run[..symbol_end].ends_with('+')) {
+			symbol_end -= 1;
+		}
+		let symbol = &run[..symbol_end];
+		let exponent: i8 = if digits > 0 {
+			let value: i8 = run[run.len() - digits..]
+				.parse()
+				.map_err(|_| format!("invalid exponent in '{run}'"))?;
+			if negative {
+				-value
+			} else {
+				value
+			}
+		} else {
+			1
+		};
+
+		let (code, factor, dimension, offset) = self
+			.resolve(symbol)
+			.ok_or_else(|| format!("unknown unit '{symbol}'"))?;
+		let mut unit = UcumUnit {
+			code,
+			factor: factor.powi(i32::from(exponent)),
+			dimension: dimension.map(|d| d * exponent),
+			offset: None,
+			has_nested_offset: false,
+		};
+		if digits > 0 {
+			unit.code.push_str(&exponent.to_string());
+		}
+		match offset {
+			Some(_) if digits > 0 => unit.has_nested_offset = true,
+			Some(offset) => unit.offset = Some(offset),
+			None => {}
+		}
+		Ok(unit)
+	}
+
+	/// Canonical code, factor, dimension and offset of a (prefixed) atom.
+	fn resolve(
+		&self,
+		symbol: &str,
+	) -> Option<(String, f64, Dimension, Option<f64>)> {
+		match self.mode {
+			Mode::Strict => resolve_case_sensitive(symbol),
+			Mode::Uppercase => resolve_case_insensitive(symbol)
+				.or_else(|| resolve_case_sensitive(symbol)),
+			Mode::Lenient => resolve_case_sensitive(symbol)
+				.or_else(|| resolve_synonym(symbol))
+				.or_else(|| resolve_case_insensitive(&symbol.to_ascii_uppercase())),
+		}
+	}
+}
+
+fn resolved(
+	prefix: Option<(&str, f64)>,
+	atom: &Atom,
+) -> (String, f64, Dimension, Option<f64>) {
+	let (prefix_code, prefix_factor) = prefix.unwrap_or(("", 1.0));
+	(
+		format!("{prefix_code}{}", atom.code),
+		prefix_factor * atom.factor,
+		atom.dimension,
+		atom.offset.map(|offset| offset / prefix_factor),
+	)
+}
+
+fn find_atom(code: &str) -> Option<&'static Atom> {
+	let code = ALIASES
+		.iter()
+		.find(|(alias, _)| *alias == code)
+		.map(|(_, canonical)| *canonical)
+		.unwrap_or(code);
+	ATOMS.iter().find(|atom| atom.code == code)
+}
+
+fn resolve_case_sensitive(
+	symbol: &str,
+) -> Option<(String, f64, Dimension, Option<f64>)> {
+	if let Some(atom) = find_atom(symbol) {
+		return Some(resolved(None, atom));
+	}
+	PREFIXES.iter().find_map(|(prefix, _, factor)| {
+		let atom = find_atom(symbol.strip_prefix(prefix)?)?;
+		atom.metric.then(|| resolved(Some((prefix, *factor)), atom))
+	})
+}
+
+fn resolve_case_insensitive(
+	symbol: &str,
+) -> Option<(String, f64, Dimension, Option<f64>)> {
+	if let Some(atom) = ATOMS.iter().find(|atom| atom.ci == symbol) {
+		return Some(resolved(None, atom));
+	}
+	PREFIXES.iter().find_map(|(prefix, ci, factor)| {
+		let rest = symbol.strip_prefix(ci)?;
+		let atom = ATOMS.iter().find(|atom| atom.ci == rest)?;
+		atom.metric.then(|| resolved(Some((prefix, *factor)), atom))
+	})
+}
+
+/// Synonyms may carry a prefix: `mcg` is matched whole, `mmcg` is not.
+fn resolve_synonym(symbol: &str) -> Option<(String, f64, Dimension, Option<f64>)> {
+	let lower = symbol.to_ascii_lowercase();
+	let (_, canonical) = SYNONYMS.iter().find(|(synonym, _)| *synonym == lower)?;
+	if canonical.starts_with('{') {
+		return Some((canonical.to_string(), 1.0, NONE, None));
+	}
+	resolve_case_sensitive(canonical)
+}
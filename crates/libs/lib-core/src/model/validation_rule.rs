@@ -0,0 +1,333 @@
+// Stored validation rule definitions and per-organization severity overrides
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use sqlx::types::Uuid;
+
+// -- Types
+
+/// A rule definition as stored; codes without a row use the compiled-in
+/// catalog entry.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ValidationRule {
+	pub code: String,
+	pub profile: String,
+	pub section: String,
+	pub blocking: bool,
+	pub message: String,
+	pub xpath: Option<String>,
+	/// Condition expression, e.g. `fda_has_pre_anda && fda_study_type_is_1_2_3`
+	pub condition: String,
+	pub active: bool,
+	pub revision: i64,
+	pub updated_at: OffsetDateTime,
+	pub updated_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidationRuleForSave {
+	pub profile: String,
+	pub section: String,
+	pub blocking: bool,
+	pub message: String,
+	pub xpath: Option<String>,
+	/// Defaults to `always`
+	pub condition: Option<String>,
+	/// Defaults to true; inactive rules raise no issues
+	pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ValidationRuleOverride {
+	pub id: Uuid,
+	pub organization_id: Uuid,
+	pub rule_code: String,
+	pub blocking: bool,
+	pub revision: i64,
+	pub created_at: OffsetDateTime,
+	pub created_by: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidationRuleOverrideForSet {
+	pub blocking: bool,
+	/// Admins may override for another organization; defaults to their own
+	pub organization_id: Option<Uuid>,
+}
+
+// -- Rules
+
+pub struct ValidationRuleBmc;
+impl DbBmc for ValidationRuleBmc {
+	const TABLE: &'static str = "validation_rules";
+}
+
+const RULE_COLUMNS: &str = "code, profile, section, blocking, message, xpath, \
+	condition, active, revision, updated_at, updated_by";
+
+impl ValidationRuleBmc {
+	pub async fn list(_ctx: &Ctx, mm: &ModelManager) -> Result<Vec<ValidationRule>> {
+		let sql =
+			format!("SELECT {RULE_COLUMNS} FROM {} ORDER BY code", Self::TABLE);
+		let rules = mm
+			.dbx()
+			.fetch_all(sqlx::query_as::<_, ValidationRule>(&sql))
+			.await?;
+		Ok(rules)
+	}
+
+	/// Creates or replaces the stored definition of a rule under a new
+	/// revision.
+	pub async fn save(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		code: &str,
+		rule: ValidationRuleForSave,
+	) -> Result<ValidationRule> {
+		let sql = format!(
+			"INSERT INTO {table} (code, profile, section, blocking, message, xpath,
+			                     condition, active, created_by, updated_by)
+			 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9)
+			 ON CONFLICT (code) DO UPDATE SET
+			     profile = EXCLUDED.profile,
+			     section = EXCLUDED.section,
+			     blocking = EXCLUDED.blocking,
+			     message = EXCLUDED.message,
+			     xpath = EXCLUDED.xpath,
+			     condition = EXCLUDED.condition,
+			     active = EXCLUDED.active,
+			     revision = nextval('validation_rule_revision_seq'),
+			     updated_by = EXCLUDED.updated_by
+			 RETURNING {RULE_COLUMNS}",
+			table = Self::TABLE
+		);
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		let saved = dbx
+			.fetch_one(
+				sqlx::query_as::<_, ValidationRule>(&sql)
+					.bind(code)
+					.bind(&rule.profile)
+					.bind(&rule.section)
+					.bind(rule.blocking)
+					.bind(&rule.message)
+					.bind(&rule.xpath)
+					.bind(rule.condition.as_deref().unwrap_or("always"))
+					.bind(rule.active.unwrap_or(true))
+					.bind(ctx.user_id()),
+			)
+			.await;
+		match saved {
+			Ok(saved) => {
+				dbx.commit_txn().await?;
+				Ok(saved)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err.into())
+			}
+		}
+	}
+
+	/// Stores the given definitions for codes that have no row yet; existing
+	/// rows are left as edited. Returns how many were inserted.
+	pub async fn insert_missing(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		rules: Vec<(String, ValidationRuleForSave)>,
+	) -> Result<u64> {
+		let mut codes = Vec::with_capacity(rules.len());
+		let mut profiles = Vec::with_capacity(rules.len());
+		let mut sections = Vec::with_capacity(rules.len());
+		let mut blocking = Vec::with_capacity(rules.len());
+		let mut messages = Vec::with_capacity(rules.len());
+		let mut xpaths = Vec::with_capacity(rules.len());
+		let mut conditions = Vec::with_capacity(rules.len());
+		for (code, rule) in rules {
+			codes.push(code);
+			profiles.push(rule.profile);
+			sections.push(rule.section);
+			blocking.push(rule.blocking);
+			messages.push(rule.message);
+			xpaths.push(rule.xpath);
+			conditions.push(rule.condition.unwrap_or_else(|| "always".to_string()));
+		}
+		let sql = format!(
+			"INSERT INTO {} (code, profile, section, blocking, message, xpath,
+			                 condition, created_by, updated_by)
+			 SELECT *, $8::uuid, $8::uuid
+			 FROM UNNEST($1::text[], $2::text[], $3::text[], $4::bool[],
+			             $5::text[], $6::text[], $7::text[])
+			 ON CONFLICT (code) DO NOTHING",
+			Self::TABLE
+		);
+
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		let inserted = dbx
+			.execute(
+				sqlx::query(&sql)
+					.bind(codes)
+					.bind(profiles)
+					.bind(sections)
+					.bind(blocking)
+					.bind(messages)
+					.bind(xpaths)
+					.bind(conditions)
+					.bind(ctx.user_id()),
+			)
+			.await;
+		match inserted {
+			Ok(count) => {
+				dbx.commit_txn().await?;
+				Ok(count)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err.into())
+			}
+		}
+	}
+}
+
+// -- Organization overrides
+
+pub struct ValidationRuleOverrideBmc;
+impl DbBmc for ValidationRuleOverrideBmc {
+	const TABLE: &'static str = "validation_rule_overrides";
+}
+
+const OVERRIDE_COLUMNS: &str =
+	"id, organization_id, rule_code, blocking, revision, created_at, created_by";
+
+impl ValidationRuleOverrideBmc {
+	pub async fn list_for_org(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		organization_id: Uuid,
+	) -> Result<Vec<ValidationRuleOverride>> {
+		let sql = format!(
+			"SELECT {OVERRIDE_COLUMNS} FROM {} WHERE organization_id = $1
+			 ORDER BY rule_code",
+			Self::TABLE
+		);
+		let overrides = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, ValidationRuleOverride>(&sql)
+					.bind(organization_id),
+			)
+			.await?;
+		Ok(overrides)
+	}
+
+	/// Sets whether the rule blocks submission for the organization.
+	pub async fn set(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		rule_code: &str,
+		data: ValidationRuleOverrideForSet,
+	) -> Result<ValidationRuleOverride> {
+		let organization_id = data.organization_id.unwrap_or(ctx.organization_id());
+		let sql = format!(
+			"INSERT INTO {} (organization_id, rule_code, blocking, created_by,
+			                 updated_by)
+			 VALUES ($1, $2, $3, $4, $4)
+			 ON CONFLICT (organization_id, rule_code) DO UPDATE SET
+			     blocking = EXCLUDED.blocking,
+			     revision = nextval('validation_rule_revision_seq'),
+			     updated_by = EXCLUDED.updated_by
+			 RETURNING {OVERRIDE_COLUMNS}",
+			Self::TABLE
+		);
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		let saved = dbx
+			.fetch_one(
+				sqlx::query_as::<_, ValidationRuleOverride>(&sql)
+					.bind(organization_id)
+					.bind(rule_code)
+					.bind(data.blocking)
+					.bind(ctx.user_id()),
+			)
+			.await;
+		match saved {
+			Ok(saved) => {
+				dbx.commit_txn().await?;
+				Ok(saved)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err.into())
+			}
+		}
+	}
+
+	/// Returns the rule to its stored (or compiled-in) severity for the
+	/// organization. Returns whether an override was removed.
+	pub async fn clear(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		rule_code: &str,
+		organization_id: Option<Uuid>,
+	) -> Result<bool> {
+		let organization_id = organization_id.unwrap_or(ctx.organization_id());
+		let sql = format!(
+			"DELETE FROM {} WHERE organization_id = $1 AND rule_code = $2",
+			Self::TABLE
+		);
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		let deleted = dbx
+			.execute(sqlx::query(&sql).bind(organization_id).bind(rule_code))
+			.await;
+		match deleted {
+			Ok(count) => {
+				dbx.commit_txn().await?;
+				Ok(count > 0)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err.into())
+			}
+		}
+	}
+}
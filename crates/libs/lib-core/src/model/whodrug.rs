@@ -0,0 +1,653 @@
+// WHODrug Global B3 dictionary loader: drug records, ingredients and ATC classes
+
+use crate::ctx::Ctx;
+use crate::model::base::DbBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use std::collections::{HashMap, HashSet};
+
+// -- Types
+
+/// A drug record of the B3 drug dictionary (`DD.txt`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhodrugB3Record {
+	pub drug_record_number: String,
+	pub sequence_number_1: String,
+	pub sequence_number_2: String,
+	pub drug_name: String,
+}
+
+impl WhodrugB3Record {
+	/// Product code: drug record number + sequence number 1 + sequence number 2.
+	pub fn code(&self) -> String {
+		format!(
+			"{}{}{}",
+			self.drug_record_number, self.sequence_number_1, self.sequence_number_2
+		)
+	}
+}
+
+/// An active ingredient of a drug record (`ING.txt` with its `BNA.txt` name).
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize)]
+pub struct WhodrugIngredient {
+	pub product_code: String,
+	pub sequence_number: i32,
+	pub substance_number: String,
+	pub substance_name: String,
+	pub cas_number: Option<String>,
+}
+
+/// An ATC code assigned to a drug record (`DDA.txt`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhodrugB3Atc {
+	pub product_code: String,
+	pub atc_code: String,
+	pub official: bool,
+}
+
+/// One level of the ATC classification (`INA.txt`).
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize)]
+pub struct WhodrugAtcClass {
+	pub atc_code: String,
+	pub level: i16,
+	pub text: String,
+}
+
+/// An ATC code of a product with its level 1-5 classification, broadest first.
+#[derive(Debug, Clone, Serialize)]
+pub struct WhodrugProductAtc {
+	pub atc_code: String,
+	pub official: bool,
+	pub path: Vec<WhodrugAtcClass>,
+}
+
+/// A WHODrug release parsed from its B3 distribution files.
+#[derive(Debug, Clone, Default)]
+pub struct WhodrugRelease {
+	pub records: Vec<WhodrugB3Record>,
+	pub ingredients: Vec<WhodrugIngredient>,
+	pub product_atc: Vec<WhodrugB3Atc>,
+	pub atc_classes: Vec<WhodrugAtcClass>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WhodrugLoadResult {
+	pub version: String,
+	pub language: String,
+	pub product_count: usize,
+	pub ingredient_count: usize,
+	pub product_atc_count: usize,
+	pub atc_class_count: usize,
+}
+
+// -- B3 parsing
+
+// Fixed-width B3 layouts: (first, last) 1-based inclusive character columns.
+// DD.txt: drug record number, sequence number 1, sequence number 2, ..., drug name
+const DD_DRUG_RECORD: (usize, usize) = (1, 6);
+const DD_SEQ_1: (usize, usize) = (7, 8);
+const DD_SEQ_2: (usize, usize) = (9, 11);
+const DD_DRUG_NAME: (usize, usize) = (32, 1531);
+// ING.txt: create date, substance number, drug record number, sequence number 1
+const ING_SUBSTANCE: (usize, usize) = (9, 18);
+const ING_DRUG_RECORD: (usize, usize) = (19, 24);
+const ING_SEQ_1: (usize, usize) = (25, 26);
+// BNA.txt: substance number, CAS number, language code, substance name
+const BNA_SUBSTANCE: (usize, usize) = (1, 10);
+const BNA_CAS: (usize, usize) = (11, 20);
+const BNA_NAME: (usize, usize) = (31, 280);
+// DDA.txt: drug record number, sequence numbers, check digit, ATC code, year
+// quarter, official ATC code flag
+const DDA_DRUG_RECORD: (usize, usize) = (1, 6);
+const DDA_SEQ_1: (usize, usize) = (7, 8);
+const DDA_SEQ_2: (usize, usize) = (9, 11);
+const DDA_ATC: (usize, usize) = (13, 19);
+const DDA_OFFICIAL: (usize, usize) = (23, 23);
+// INA.txt: ATC code, level, text
+const INA_ATC: (usize, usize) = (1, 7);
+const INA_LEVEL: (usize, usize) = (8, 8);
+const INA_TEXT: (usize, usize) = (9, 118);
+
+/// Generic-name record of a drug record number; trade names (sequence
+/// number 2 > 001) and salts inherit its ingredients and ATC codes.
+const BASE_SEQ_1: &str = "01";
+const BASE_SEQ_2: &str = "001";
+
+/// Parses the WHODrug Global B3 distribution: `DD.txt`, `ING.txt` and
+/// `BNA.txt`, plus `DDA.txt` and `INA.txt` for the ATC classification when
+/// present. Files are matched by name, case insensitively and ignoring
+/// directories; other files are skipped.
+pub fn parse_whodrug_b3(files: &[(String, Vec<u8>)]) -> Result<WhodrugRelease> {
+	let mut by_name: HashMap<String, &[u8]> = HashMap::new();
+	for (name, content) in files {
+		let base = name
+			.rsplit(['/', '\\'])
+			.next()
+			.unwrap_or(name)
+			.to_lowercase();
+		by_name.insert(base, content.as_slice());
+	}
+	let file = |name: &str| {
+		by_name
+			.get(&name.to_lowercase())
+			.map(|content| B3File::new(name, content))
+	};
+	let required = |name: &str| {
+		file(name).ok_or_else(|| Error::TerminologyLoad {
+			file: name.to_string(),
+			message: "file missing from the WHODrug B3 distribution".to_string(),
+		})
+	};
+
+	let mut release = WhodrugRelease::default();
+	let dd = required("DD.txt")?;
+	for (line, row) in dd.numbered_rows() {
+		let [drug_record_number, sequence_number_1, sequence_number_2, drug_name] =
+			dd.columns(
+				row,
+				line,
+				[DD_DRUG_RECORD, DD_SEQ_1, DD_SEQ_2, DD_DRUG_NAME],
+			)?;
+		release.records.push(WhodrugB3Record {
+			drug_record_number,
+			sequence_number_1,
+			sequence_number_2,
+			drug_name,
+		});
+	}
+
+	let bna = required("BNA.txt")?;
+	let mut substances: HashMap<String, (String, Option<String>)> = HashMap::new();
+	for (line, row) in bna.numbered_rows() {
+		let [number, name] = bna.columns(row, line, [BNA_SUBSTANCE, BNA_NAME])?;
+		let cas = Some(column(row, BNA_CAS)).filter(|c| !c.is_empty());
+		substances.insert(number, (name, cas));
+	}
+
+	let ing = required("ING.txt")?;
+	let mut ingredients_of: HashMap<(String, String), Vec<String>> = HashMap::new();
+	for (line, row) in ing.numbered_rows() {
+		let [substance, drug_record, seq_1] =
+			ing.columns(row, line, [ING_SUBSTANCE, ING_DRUG_RECORD, ING_SEQ_1])?;
+		if !substances.contains_key(&substance) {
+			return Err(Error::TerminologyLoad {
+				file: ing.name.clone(),
+				message: format!(
+					"line {line}: substance {substance} not found in BNA.txt"
+				),
+			});
+		}
+		let list = ingredients_of.entry((drug_record, seq_1)).or_default();
+		if !list.contains(&substance) {
+			list.push(substance);
+		}
+	}
+
+	let mut atc_of: HashMap<(String, String, String), Vec<(String, bool)>> =
+		HashMap::new();
+	if let Some(dda) = file("DDA.txt") {
+		for (line, row) in dda.numbered_rows() {
+			let [drug_record, seq_1, seq_2, atc_code] = dda.columns(
+				row,
+				line,
+				[DDA_DRUG_RECORD, DDA_SEQ_1, DDA_SEQ_2, DDA_ATC],
+			)?;
+			let official = column(row, DDA_OFFICIAL).eq_ignore_ascii_case("Y");
+			atc_of
+				.entry((drug_record, seq_1, seq_2))
+				.or_default()
+				.push((atc_code, official));
+		}
+	}
+	if let Some(ina) = file("INA.txt") {
+		for (line, row) in ina.numbered_rows() {
+			let [atc_code, level, text] =
+				ina.columns(row, line, [INA_ATC, INA_LEVEL, INA_TEXT])?;
+			let level = level.parse().map_err(|_| Error::TerminologyLoad {
+				file: ina.name.clone(),
+				message: format!("line {line}: ATC level {level} is not a number"),
+			})?;
+			release.atc_classes.push(WhodrugAtcClass {
+				atc_code,
+				level,
+				text,
+			});
+		}
+	}
+
+	for record in &release.records {
+		let code = record.code();
+		let drn = &record.drug_record_number;
+		let substances_of_record = ingredients_of
+			.get(&(drn.clone(), record.sequence_number_1.clone()))
+			.or_else(|| ingredients_of.get(&(drn.clone(), BASE_SEQ_1.to_string())));
+		for (idx, number) in substances_of_record.into_iter().flatten().enumerate() {
+			let (name, cas) = &substances[number];
+			release.ingredients.push(WhodrugIngredient {
+				product_code: code.clone(),
+				sequence_number: idx as i32 + 1,
+				substance_number: number.clone(),
+				substance_name: name.clone(),
+				cas_number: cas.clone(),
+			});
+		}
+
+		let atc_of_record = atc_of
+			.get(&(
+				drn.clone(),
+				record.sequence_number_1.clone(),
+				record.sequence_number_2.clone(),
+			))
+			.or_else(|| {
+				atc_of.get(&(
+					drn.clone(),
+					BASE_SEQ_1.to_string(),
+					BASE_SEQ_2.to_string(),
+				))
+			});
+		for (atc_code, official) in atc_of_record.into_iter().flatten() {
+			release.product_atc.push(WhodrugB3Atc {
+				product_code: code.clone(),
+				atc_code: atc_code.clone(),
+				official: *official,
+			});
+		}
+	}
+
+	Ok(release)
+}
+
+/// A fixed-width B3 text file.
+struct B3File<'a> {
+	name: String,
+	content: std::borrow::Cow<'a, str>,
+}
+
+impl<'a> B3File<'a> {
+	fn new(name: &str, content: &'a [u8]) -> Self {
+		Self {
+			name: name.to_string(),
+			content: String::from_utf8_lossy(content),
+		}
+	}
+
+	/// Non-blank rows with their 1-based line number.
+	fn numbered_rows(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+		self.content
+			.lines()
+			.enumerate()
+			.map(|(idx, line)| (idx + 1, line.trim_start_matches('\u{feff}')))
+			.filter(|(_, line)| !line.trim().is_empty())
+	}
+
+	/// Required columns of a row; an empty one is a load error.
+	fn columns<const N: usize>(
+		&self,
+		row: &str,
+		line: usize,
+		cols: [(usize, usize); N],
+	) -> Result<[String; N]> {
+		let mut out: [String; N] = std::array::from_fn(|_| String::new());
+		for (slot, col) in out.iter_mut().zip(cols) {
+			*slot = column(row, col);
+			if slot.is_empty() {
+				return Err(Error::TerminologyLoad {
+					file: self.name.clone(),
+					message: format!(
+						"line {line}: columns {}-{} are empty",
+						col.0, col.1
+					),
+				});
+			}
+		}
+		Ok(out)
+	}
+}
+
+/// Trimmed text of 1-based inclusive character columns; short rows yield "".
+fn column(row: &str, (first, last): (usize, usize)) -> String {
+	row.chars()
+		.skip(first - 1)
+		.take(last + 1 - first)
+		.collect::<String>()
+		.trim()
+		.to_string()
+}
+
+/// ATC prefixes of each classification level: N, N02, N02B, N02BE, N02BE01.
+fn atc_levels(atc_code: &str) -> Vec<&str> {
+	[1, 3, 4, 5, 7]
+		.into_iter()
+		.filter(|len| *len <= atc_code.len())
+		.map(|len| &atc_code[..len])
+		.collect()
+}
+
+// -- Bmc
+
+pub struct WhodrugIngredientBmc;
+impl DbBmc for WhodrugIngredientBmc {
+	const TABLE: &'static str = "whodrug_ingredients";
+}
+
+impl WhodrugIngredientBmc {
+	/// Loads a WHODrug B3 distribution as `version`/`language` (default
+	/// "en"), replacing any products, ingredients and ATC data already
+	/// loaded for that version and language.
+	pub async fn load_b3(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		version: &str,
+		language: Option<&str>,
+		files: &[(String, Vec<u8>)],
+	) -> Result<WhodrugLoadResult> {
+		let mm = mm.new_with_txn()?;
+		let dbx = mm.dbx();
+		dbx.begin_txn().await?;
+		set_full_context_dbx_or_rollback(
+			dbx,
+			ctx.user_id(),
+			ctx.organization_id(),
+			ctx.role(),
+		)
+		.await?;
+		match Self::load_b3_in_txn(&mm, version, language, files).await {
+			Ok(result) => {
+				dbx.commit_txn().await?;
+				Ok(result)
+			}
+			Err(err) => {
+				let _ = dbx.rollback_txn().await;
+				Err(err)
+			}
+		}
+	}
+
+	/// `load_b3` within the caller's transaction.
+	pub(crate) async fn load_b3_in_txn(
+		mm: &ModelManager,
+		version: &str,
+		language: Option<&str>,
+		files: &[(String, Vec<u8>)],
+	) -> Result<WhodrugLoadResult> {
+		let release = parse_whodrug_b3(files)?;
+		let language = language.unwrap_or("en").to_string();
+
+		Self::write_release(mm, version, &language, &release).await?;
+
+		Ok(WhodrugLoadResult {
+			version: version.to_string(),
+			language,
+			product_count: release.records.len(),
+			ingredient_count: release.ingredients.len(),
+			product_atc_count: release.product_atc.len(),
+			atc_class_count: release.atc_classes.len(),
+		})
+	}
+
+	async fn write_release(
+		mm: &ModelManager,
+		version: &str,
+		language: &str,
+		release: &WhodrugRelease,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+		for table in [
+			Self::TABLE,
+			"whodrug_product_atc",
+			"whodrug_atc_classes",
+			"whodrug_products",
+		] {
+			dbx.execute(
+				sqlx::query(&format!(
+					"DELETE FROM {table} WHERE version = $1 AND language = $2"
+				))
+				.bind(version)
+				.bind(language),
+			)
+			.await?;
+		}
+
+		// The first official ATC code is the product's primary one.
+		let mut primary_atc: HashMap<&str, &str> = HashMap::new();
+		let (official, other): (Vec<_>, Vec<_>) =
+			release.product_atc.iter().partition(|a| a.official);
+		for atc in official.into_iter().chain(other) {
+			primary_atc
+				.entry(atc.product_code.as_str())
+				.or_insert(atc.atc_code.as_str());
+		}
+
+		let mut seen = HashSet::new();
+		let records: Vec<_> = release
+			.records
+			.iter()
+			.map(|r| (r.code(), r))
+			.filter(|(code, _)| seen.insert(code.clone()))
+			.collect();
+		dbx.execute(
+			sqlx::query(
+				"INSERT INTO whodrug_products
+				     (code, drug_record_number, sequence_number_1, sequence_number_2,
+				      drug_name, atc_code, version, language, active)
+				 SELECT u.code, u.drn, u.seq_1, u.seq_2, u.drug_name, u.atc_code, $7, $8, true
+				 FROM unnest($1::text[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[])
+				      AS u(code, drn, seq_1, seq_2, drug_name, atc_code)",
+			)
+			.bind(records.iter().map(|(c, _)| c.as_str()).collect::<Vec<_>>())
+			.bind(
+				records
+					.iter()
+					.map(|(_, r)| r.drug_record_number.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				records
+					.iter()
+					.map(|(_, r)| r.sequence_number_1.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				records
+					.iter()
+					.map(|(_, r)| r.sequence_number_2.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				records
+					.iter()
+					.map(|(_, r)| r.drug_name.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				records
+					.iter()
+					.map(|(c, _)| primary_atc.get(c.as_str()).copied())
+					.collect::<Vec<_>>(),
+			)
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+
+		let ingredients = &release.ingredients;
+		dbx.execute(
+			sqlx::query(&format!(
+				"INSERT INTO {}
+				     (product_code, sequence_number, substance_number, substance_name,
+				      cas_number, version, language)
+				 SELECT DISTINCT ON (u.product_code, u.substance_number)
+				        u.product_code, u.sequence_number, u.substance_number,
+				        u.substance_name, u.cas_number, $6, $7
+				 FROM unnest($1::text[], $2::int[], $3::text[], $4::text[], $5::text[])
+				      AS u(product_code, sequence_number, substance_number, substance_name, cas_number)
+				 ORDER BY u.product_code, u.substance_number, u.sequence_number",
+				Self::TABLE
+			))
+			.bind(
+				ingredients
+					.iter()
+					.map(|i| i.product_code.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(ingredients.iter().map(|i| i.sequence_number).collect::<Vec<_>>())
+			.bind(
+				ingredients
+					.iter()
+					.map(|i| i.substance_number.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				ingredients
+					.iter()
+					.map(|i| i.substance_name.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(
+				ingredients
+					.iter()
+					.map(|i| i.cas_number.as_deref())
+					.collect::<Vec<_>>(),
+			)
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+
+		let atc = &release.product_atc;
+		dbx.execute(
+			sqlx::query(
+				"INSERT INTO whodrug_product_atc
+				     (product_code, atc_code, official, version, language)
+				 SELECT u.product_code, u.atc_code, bool_or(u.official), $4, $5
+				 FROM unnest($1::text[], $2::text[], $3::bool[])
+				      AS u(product_code, atc_code, official)
+				 GROUP BY u.product_code, u.atc_code",
+			)
+			.bind(
+				atc.iter()
+					.map(|a| a.product_code.as_str())
+					.collect::<Vec<_>>(),
+			)
+			.bind(atc.iter().map(|a| a.atc_code.as_str()).collect::<Vec<_>>())
+			.bind(atc.iter().map(|a| a.official).collect::<Vec<_>>())
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+
+		let classes = &release.atc_classes;
+		dbx.execute(
+			sqlx::query(
+				"INSERT INTO whodrug_atc_classes (atc_code, level, text, version, language)
+				 SELECT DISTINCT ON (u.atc_code) u.atc_code, u.level, u.text, $4, $5
+				 FROM unnest($1::text[], $2::int2[], $3::text[]) AS u(atc_code, level, text)
+				 ORDER BY u.atc_code",
+			)
+			.bind(classes.iter().map(|c| c.atc_code.as_str()).collect::<Vec<_>>())
+			.bind(classes.iter().map(|c| c.level).collect::<Vec<_>>())
+			.bind(classes.iter().map(|c| c.text.as_str()).collect::<Vec<_>>())
+			.bind(version)
+			.bind(language),
+		)
+		.await?;
+		Ok(())
+	}
+
+	/// Ingredients of the given products of a release, in ingredient order.
+	pub async fn list_for_products(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		version: &str,
+		language: &str,
+		product_codes: &[String],
+	) -> Result<Vec<WhodrugIngredient>> {
+		let sql = format!(
+			"SELECT product_code, sequence_number, substance_number, substance_name, cas_number
+			 FROM {}
+			 WHERE version = $1 AND language = $2 AND product_code = ANY($3)
+			 ORDER BY product_code, sequence_number",
+			Self::TABLE
+		);
+		let ingredients = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, WhodrugIngredient>(&sql)
+					.bind(version)
+					.bind(language)
+					.bind(product_codes),
+			)
+			.await?;
+		Ok(ingredients)
+	}
+
+	/// ATC codes of the given products with their classification path,
+	/// official codes first.
+	pub async fn atc_for_products(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		version: &str,
+		language: &str,
+		product_codes: &[String],
+	) -> Result<Vec<(String, WhodrugProductAtc)>> {
+		let assigned: Vec<(String, String, bool)> = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as(
+					"SELECT product_code, atc_code, official
+					 FROM whodrug_product_atc
+					 WHERE version = $1 AND language = $2 AND product_code = ANY($3)
+					 ORDER BY product_code, official DESC, atc_code",
+				)
+				.bind(version)
+				.bind(language)
+				.bind(product_codes),
+			)
+			.await?;
+
+		let prefixes: Vec<String> = assigned
+			.iter()
+			.flat_map(|(_, atc_code, _)| atc_levels(atc_code))
+			.map(str::to_string)
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect();
+		let classes: HashMap<String, WhodrugAtcClass> = mm
+			.dbx()
+			.fetch_all(
+				sqlx::query_as::<_, WhodrugAtcClass>(
+					"SELECT atc_code, level, text
+					 FROM whodrug_atc_classes
+					 WHERE version = $1 AND language = $2 AND atc_code = ANY($3)",
+				)
+				.bind(version)
+				.bind(language)
+				.bind(&prefixes),
+			)
+			.await?
+			.into_iter()
+			.map(|class| (class.atc_code.clone(), class))
+			.collect();
+
+		Ok(assigned
+			.into_iter()
+			.map(|(product_code, atc_code, official)| {
+				let path = atc_levels(&atc_code)
+					.into_iter()
+					.filter_map(|prefix| classes.get(prefix).cloned())
+					.collect();
+				(
+					product_code,
+					WhodrugProductAtc {
+						atc_code,
+						official,
+						path,
+					},
+				)
+			})
+			.collect())
+	}
+}
@@ -0,0 +1,669 @@
+use crate::ctx::Ctx;
+use crate::model;
+use crate::model::acknowledgment::{
+	CaseAcknowledgmentBmc, CaseAcknowledgmentForCreate,
+};
+use crate::model::case::CaseBmc;
+use crate::model::case_workflow::{
+	find_transition, CaseWorkflowBmc, TransitionTrigger,
+};
+use crate::model::message_header::MessageHeaderBmc;
+use crate::model::ModelManager;
+use crate::xml::error::Error;
+use crate::xml::types::{
+	XmlAckGenerationRequest, XmlAckImportResult, XmlAckMessageResult,
+	XmlBatchImportResult, XmlValidationError, XmlValidationReport,
+};
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::tree::Node;
+use libxml::xpath::Context;
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+const ACK_ROOT: &str = "MCCI_IN200101UV01";
+
+/// Batch-level part of an MCCI_IN200101UV01 acknowledgment.
+#[derive(Debug, Clone, Default)]
+pub struct AckBatch {
+	/// ACK.M.1 Acknowledgement Batch Number.
+	pub ack_batch_number: Option<String>,
+	/// ACK.M.2 Acknowledgement Batch Sender Identifier.
+	pub sender_identifier: Option<String>,
+	/// ACK.M.3 Acknowledgement Batch Receiver Identifier.
+	pub receiver_identifier: Option<String>,
+	/// ACK.M.4 Acknowledgement Date of Batch Transmission.
+	pub ack_date: Option<String>,
+	/// ACK.A.1 ICSR Batch Number being acknowledged.
+	pub batch_number: Option<String>,
+	/// ACK.A.3 Date of ICSR Batch Transmission.
+	pub transmission_date: Option<String>,
+	/// ACK.A.4 Transmission Acknowledgement Code (AA, AE, AR).
+	pub batch_ack_code: Option<String>,
+	/// ACK.A.5 Batch Validation Error.
+	pub batch_error_text: Option<String>,
+	pub messages: Vec<AckMessage>,
+}
+
+/// One MCCI_IN000002UV01 ICSR acknowledgment inside the batch.
+#[derive(Debug, Clone, Default)]
+pub struct AckMessage {
+	/// ACK.B.r.1 ICSR Message Number as sent back by the receiver.
+	pub message_number: Option<String>,
+	/// ACK.B.r.2 Local Report Number.
+	pub local_report_number: Option<String>,
+	/// ACK.B.r.3 ICSR Message ACK Receiver.
+	pub receiver_identifier: Option<String>,
+	/// ACK.B.r.4 ICSR Message ACK Sender.
+	pub sender_identifier: Option<String>,
+	/// ACK.B.r.5 Date of ICSR Message Creation.
+	pub message_date: Option<String>,
+	/// ACK.B.r.6 Acknowledgement Code for an ICSR Message (CA, CR).
+	pub ack_code: Option<String>,
+	/// ACK.B.r.7 Error / Warning Message or Comment.
+	pub error_text: Option<String>,
+}
+
+impl AckMessage {
+	/// Tokens of ACK.B.r.1; gateways send the message number, the safety
+	/// report id, or both separated by whitespace.
+	pub fn identifiers(&self) -> Vec<String> {
+		self.message_number
+			.as_deref()
+			.unwrap_or_default()
+			.split_whitespace()
+			.map(str::to_string)
+			.collect()
+	}
+}
+
+/// Maps an ICSR (CA/CR) or batch (AA/AE/AR) acknowledgment code to the case
+/// status it implies.
+///
+/// A batch AE means the receiver took the batch and flagged some reports, so
+/// without per-report codes the cases count as acknowledged (the error text
+/// stays on the acknowledgment). A batch AR refused the whole batch, so it
+/// rejects every case that has no ICSR code of its own.
+pub fn case_status_for_ack_code(code: &str) -> Option<&'static str> {
+	match code.trim().to_ascii_uppercase().as_str() {
+		"CA" | "AA" | "AE" => Some("acknowledged"),
+		"CR" | "AR" => Some("rejected"),
+		_ => None,
+	}
+}
+
+/// Parses an MCCI_IN200101UV01 acknowledgment document.
+pub fn parse_ack_xml(xml: &[u8]) -> Result<AckBatch> {
+	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
+		message: format!("XML not valid UTF-8: {err}"),
+		line: None,
+		column: None,
+	})?;
+	let doc =
+		Parser::default()
+			.parse_string(xml_str)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("XML parse error: {err}"),
+				line: None,
+				column: None,
+			})?;
+	let root = doc.get_root_element().ok_or(Error::MissingRootElement)?;
+	if root.get_name() != ACK_ROOT {
+		return Err(Error::UnsupportedRoot {
+			found: root.get_name(),
+		});
+	}
+
+	let mut xpath = Context::new(&doc).map_err(|_| Error::InvalidXml {
+		message: "Failed to initialize XPath context".to_string(),
+		line: None,
+		column: None,
+	})?;
+	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+
+	let mut batch = AckBatch {
+		ack_batch_number: first_value(&mut xpath, &root, "hl7:id/@extension"),
+		sender_identifier: first_value(
+			&mut xpath,
+			&root,
+			"hl7:sender/hl7:device/hl7:id/@extension",
+		),
+		receiver_identifier: first_value(
+			&mut xpath,
+			&root,
+			"hl7:receiver/hl7:device/hl7:id/@extension",
+		),
+		ack_date: first_value(&mut xpath, &root, "hl7:creationTime/@value"),
+		batch_number: first_value(
+			&mut xpath,
+			&root,
+			"hl7:acknowledgement/hl7:targetBatch/hl7:id/@extension",
+		),
+		transmission_date: first_value(
+			&mut xpath,
+			&root,
+			"hl7:attentionLine[hl7:keyWordText/@code='3']/hl7:value/@value",
+		),
+		batch_ack_code: first_value(
+			&mut xpath,
+			&root,
+			"hl7:acknowledgement/@typeCode",
+		),
+		batch_error_text: first_value(
+			&mut xpath,
+			&root,
+			"hl7:acknowledgement/hl7:acknowledgementDetail/hl7:text",
+		),
+		messages: Vec::new(),
+	};
+
+	let messages = xpath
+		.findnodes("hl7:MCCI_IN000002UV01", Some(&root))
+		.unwrap_or_default();
+	for node in messages {
+		batch.messages.push(AckMessage {
+			message_number: first_value(
+				&mut xpath,
+				&node,
+				"hl7:acknowledgement/hl7:targetMessage/hl7:id/@extension",
+			),
+			local_report_number: first_value(
+				&mut xpath,
+				&node,
+				"hl7:id/@extension",
+			),
+			receiver_identifier: first_value(
+				&mut xpath,
+				&node,
+				"hl7:receiver/hl7:device/hl7:id/@extension",
+			),
+			sender_identifier: first_value(
+				&mut xpath,
+				&node,
+				"hl7:sender/hl7:device/hl7:id/@extension",
+			),
+			message_date: first_value(
+				&mut xpath,
+				&node,
+				"hl7:attentionLine[hl7:keyWordText/@code='1']/hl7:value/@value",
+			),
+			ack_code: first_value(
+				&mut xpath,
+				&node,
+				"hl7:acknowledgement/@typeCode",
+			),
+			error_text: first_value(
+				&mut xpath,
+				&node,
+				"hl7:acknowledgement/hl7:acknowledgementDetail/hl7:text",
+			),
+		});
+	}
+
+	Ok(batch)
+}
+
+/// Records an inbound acknowledgment against the submitted cases.
+///
+/// Each ICSR acknowledgment is matched by message number / C.1.1 and stored
+/// with its code and error text; the case status moves to `acknowledged` or
+/// `rejected`. A batch-only acknowledgment (no ICSR entries) is recorded on
+/// every case exported under the acknowledged batch number, and its batch
+/// code moves them (see [`case_status_for_ack_code`]).
+pub async fn import_ack_xml(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	xml: &[u8],
+) -> Result<XmlAckImportResult> {
+	let batch = parse_ack_xml(xml)?;
+	let mut messages = Vec::new();
+
+	if batch.messages.is_empty() {
+		if let Some(batch_number) = batch.batch_number.as_deref() {
+			let case_ids =
+				CaseAcknowledgmentBmc::find_cases_for_batch(ctx, mm, batch_number)
+					.await
+					.map_err(Error::from)?;
+			for (index, case_id) in case_ids.into_iter().enumerate() {
+				let message = AckMessage {
+					ack_code: None,
+					error_text: batch.batch_error_text.clone(),
+					..Default::default()
+				};
+				messages.push(
+					record_ack(ctx, mm, &batch, index, &message, Some(case_id))
+						.await?,
+				);
+			}
+		}
+	} else {
+		for (index, message) in batch.messages.iter().enumerate() {
+			let case_id = CaseAcknowledgmentBmc::find_case_for_message(
+				ctx,
+				mm,
+				&message.identifiers(),
+				batch.batch_number.as_deref(),
+			)
+			.await
+			.map_err(Error::from)?;
+			messages.push(record_ack(ctx, mm, &batch, index, message, case_id).await?);
+		}
+	}
+
+	let matched = messages.iter().filter(|m| m.case_id.is_some()).count();
+	Ok(XmlAckImportResult {
+		ack_batch_number: batch.ack_batch_number,
+		batch_number: batch.batch_number,
+		batch_ack_code: batch.batch_ack_code,
+		total: messages.len(),
+		matched,
+		unmatched: messages.len() - matched,
+		messages,
+	})
+}
+
+async fn record_ack(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	batch: &AckBatch,
+	index: usize,
+	message: &AckMessage,
+	case_id: Option<Uuid>,
+) -> Result<XmlAckMessageResult> {
+	let ack_code = message
+		.ack_code
+		.clone()
+		.or_else(|| batch.batch_ack_code.clone());
+	let mut result = XmlAckMessageResult {
+		index,
+		message_number: message.message_number.clone(),
+		ack_code: ack_code.clone(),
+		error_text: message.error_text.clone(),
+		case_id: None,
+		case_status: None,
+		acknowledgment_id: None,
+		error: None,
+	};
+	let Some(case_id) = case_id else {
+		result.error = Some("No submitted case matches this acknowledgment".to_string());
+		return Ok(result);
+	};
+
+	let mm = mm.new_with_txn().map_err(Error::from)?;
+	mm.dbx().begin_txn().await.map_err(model::Error::from)?;
+	match apply_ack(ctx, &mm, batch, message, case_id, ack_code.as_deref()).await {
+		Ok((ack_id, status)) => {
+			mm.dbx().commit_txn().await.map_err(model::Error::from)?;
+			result.case_id = Some(case_id);
+			result.case_status = Some(status);
+			result.acknowledgment_id = Some(ack_id);
+		}
+		Err(err) => {
+			let _ = mm.dbx().rollback_txn().await;
+			result.case_id = Some(case_id);
+			result.error = Some(err.to_string());
+		}
+	}
+	Ok(result)
+}
+
+async fn apply_ack(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	batch: &AckBatch,
+	message: &AckMessage,
+	case_id: Uuid,
+	ack_code: Option<&str>,
+) -> Result<(Uuid, String)> {
+	let ack_id = CaseAcknowledgmentBmc::create(
+		ctx,
+		mm,
+		CaseAcknowledgmentForCreate {
+			case_id,
+			ack_batch_number: batch.ack_batch_number.clone(),
+			batch_number: batch.batch_number.clone(),
+			batch_ack_code: batch.batch_ack_code.clone(),
+			message_number: message.message_number.clone(),
+			local_report_number: message.local_report_number.clone(),
+			ack_code: message.ack_code.clone(),
+			error_text: message.error_text.clone(),
+			ack_date: batch.ack_date.clone(),
+		},
+	)
+	.await?;
+
+	let case = CaseBmc::get(ctx, mm, case_id).await?;
+	let next_status = ack_code
+		.and_then(case_status_for_ack_code)
+		.filter(|next| {
+			find_transition(&case.status, next, TransitionTrigger::Acknowledgment)
+				.is_some()
+		});
+	let status = match next_status {
+		Some(next) => {
+			CaseWorkflowBmc::transition_in_txn(
+				ctx,
+				mm,
+				case_id,
+				next,
+				TransitionTrigger::Acknowledgment,
+				message.error_text.clone(),
+			)
+			.await?
+			.status
+		}
+		None => case.status,
+	};
+	Ok((ack_id, status))
+}
+
+fn first_value(xpath: &mut Context, node: &Node, expr: &str) -> Option<String> {
+	xpath
+		.findvalues(expr, Some(node))
+		.ok()?
+		.into_iter()
+		.map(|v| v.trim().to_string())
+		.find(|v| !v.is_empty())
+}
+
+/// Transmission acknowledgment code (ACK.A.4) for a received batch: AR when
+/// file validation failed or nothing could be imported, AE when only some
+/// ICSRs were imported, AA otherwise.
+pub fn batch_ack_code_for_import(
+	result: &XmlBatchImportResult,
+	validation: Option<&XmlValidationReport>,
+) -> &'static str {
+	if validation.is_some_and(|report| !report.ok) {
+		return "AR";
+	}
+	if result.total > 0 && result.imported == 0 {
+		return "AR";
+	}
+	if result.failed > 0 {
+		"AE"
+	} else {
+		"AA"
+	}
+}
+
+/// Builds the MCCI_IN200101UV01 acknowledgment for a received ICSR file.
+///
+/// ACK.B.r.1 echoes the N.2.r.1 message number read from the received file.
+/// Batch routing (sender, receiver, batch number, transmission date) comes
+/// from the message headers stored for the imported cases.
+pub async fn build_import_ack_xml(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	req: &XmlAckGenerationRequest,
+) -> Result<String> {
+	let result = &req.import_result;
+	let mut batch = AckBatch {
+		ack_batch_number: Some(Uuid::new_v4().to_string()),
+		ack_date: Some(fmt_ack_date(OffsetDateTime::now_utc())),
+		batch_number: result.batch_number.clone(),
+		batch_ack_code: Some(
+			batch_ack_code_for_import(result, req.validation.as_ref()).to_string(),
+		),
+		batch_error_text: req
+			.validation
+			.as_ref()
+			.filter(|report| !report.ok)
+			.map(|report| join_errors(&report.errors))
+			.or_else(|| {
+				(result.failed > 0).then(|| {
+					format!(
+						"{} of {} ICSR message(s) were rejected",
+						result.failed, result.total
+					)
+				})
+			}),
+		..Default::default()
+	};
+
+	for report in &result.reports {
+		let header = match report
+			.case_id
+			.as_deref()
+			.and_then(|id| Uuid::parse_str(id).ok())
+		{
+			Some(case_id) => MessageHeaderBmc::get_by_case(ctx, mm, case_id)
+				.await
+				.ok(),
+			None => None,
+		};
+
+		if let Some(header) = header.as_ref() {
+			// Our side of the acknowledgment is the receiver of the original.
+			if batch.sender_identifier.is_none() {
+				batch.sender_identifier = header
+					.batch_receiver_identifier
+					.clone()
+					.or_else(|| Some(header.message_receiver_identifier.clone()));
+				batch.receiver_identifier = header
+					.batch_sender_identifier
+					.clone()
+					.or_else(|| Some(header.message_sender_identifier.clone()));
+			}
+			if batch.batch_number.is_none() {
+				batch.batch_number = header.batch_number.clone();
+			}
+			if batch.transmission_date.is_none() {
+				batch.transmission_date =
+					header.batch_transmission_date.map(fmt_ack_date);
+			}
+		}
+
+		let (ack_code, error_text) = match report.error.as_ref() {
+			None => ("CA", None),
+			Some(error) => {
+				let mut text = error.message.clone();
+				if !error.errors.is_empty() {
+					text = format!("{text}: {}", join_errors(&error.errors));
+				}
+				("CR", Some(text))
+			}
+		};
+		batch.messages.push(AckMessage {
+			// Echo the sender's N.2.r.1; the stored header carries the
+			// case-suffixed number import generates.
+			message_number: report
+				.message_number
+				.clone()
+				.or_else(|| report.safety_report_id.clone()),
+			local_report_number: report
+				.case_id
+				.clone()
+				.or_else(|| report.safety_report_id.clone()),
+			receiver_identifier: header
+				.as_ref()
+				.map(|h| h.message_sender_identifier.clone()),
+			sender_identifier: header
+				.as_ref()
+				.map(|h| h.message_receiver_identifier.clone()),
+			message_date: header.as_ref().map(|h| h.message_date.clone()),
+			ack_code: Some(ack_code.to_string()),
+			error_text,
+		});
+	}
+
+	Ok(render_ack_xml(&batch))
+}
+
+/// Serializes an acknowledgment as an MCCI_IN200101UV01 document.
+pub fn render_ack_xml(batch: &AckBatch) -> String {
+	let mut xml = String::from(
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<MCCI_IN200101UV01 xmlns=\"urn:hl7-org:v3\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ITSVersion=\"XML_1.0\">\n",
+	);
+	xml.push_str(&id_element(
+		"\t",
+		batch.ack_batch_number.as_deref(),
+		"2.16.840.1.113883.3.989.2.1.3.20",
+	));
+	xml.push_str(&ts_element("\t", "creationTime", batch.ack_date.as_deref()));
+	xml.push_str("\t<responseModeCode code=\"D\"/>\n");
+	xml.push_str(
+		"\t<interactionId extension=\"MCCI_IN200101UV01\" root=\"2.16.840.1.113883.1.6\"/>\n",
+	);
+
+	for message in &batch.messages {
+		xml.push_str("\t<MCCI_IN000002UV01>\n");
+		xml.push_str(&id_element(
+			"\t\t",
+			message.local_report_number.as_deref(),
+			"2.16.840.1.113883.3.989.2.1.3.19",
+		));
+		xml.push_str(&ts_element("\t\t", "creationTime", batch.ack_date.as_deref()));
+		xml.push_str(
+			"\t\t<interactionId extension=\"MCCI_IN000002UV01\" root=\"2.16.840.1.113883.1.6\"/>\n\
+\t\t<processingCode code=\"P\"/>\n\
+\t\t<processingModeCode code=\"T\"/>\n\
+\t\t<acceptAckCode code=\"NE\"/>\n",
+		);
+		xml.push_str(&device_element(
+			"\t\t",
+			"receiver",
+			"RCV",
+			message.receiver_identifier.as_deref(),
+			"2.16.840.1.113883.3.989.2.1.3.16",
+		));
+		xml.push_str(&device_element(
+			"\t\t",
+			"sender",
+			"SND",
+			message.sender_identifier.as_deref(),
+			"2.16.840.1.113883.3.989.2.1.3.15",
+		));
+		if let Some(date) = message.message_date.as_deref() {
+			xml.push_str(&format!(
+				"\t\t<attentionLine>\n\
+\t\t\t<keyWordText code=\"1\" codeSystem=\"2.16.840.1.113883.3.989.2.1.1.24\" codeSystemVersion=\"1.0\" displayName=\"dateOfIcsrMessageCreation\"/>\n\
+\t\t\t<value value=\"{}\" xsi:type=\"TS\"/>\n\
+\t\t</attentionLine>\n",
+				xml_escape(date)
+			));
+		}
+		xml.push_str(&format!(
+			"\t\t<acknowledgement typeCode=\"{}\">\n\t\t\t<targetMessage>\n",
+			xml_escape(message.ack_code.as_deref().unwrap_or("CA"))
+		));
+		xml.push_str(&id_element(
+			"\t\t\t\t",
+			message.message_number.as_deref(),
+			"2.16.840.1.113883.3.989.2.1.3.1",
+		));
+		xml.push_str("\t\t\t</targetMessage>\n");
+		xml.push_str(&detail_element("\t\t\t", message.error_text.as_deref()));
+		xml.push_str("\t\t</acknowledgement>\n\t</MCCI_IN000002UV01>\n");
+	}
+
+	xml.push_str(&device_element(
+		"\t",
+		"receiver",
+		"RCV",
+		batch.receiver_identifier.as_deref(),
+		"2.16.840.1.113883.3.989.2.1.3.18",
+	));
+	xml.push_str(&device_element(
+		"\t",
+		"sender",
+		"SND",
+		batch.sender_identifier.as_deref(),
+		"2.16.840.1.113883.3.989.2.1.3.17",
+	));
+	if let Some(date) = batch.transmission_date.as_deref() {
+		xml.push_str(&format!(
+			"\t<attentionLine>\n\
+\t\t<keyWordText code=\"3\" codeSystem=\"2.16.840.1.113883.3.989.2.1.1.24\" codeSystemVersion=\"1.0\" displayName=\"dateOfIcsrBatchTransmission\"/>\n\
+\t\t<value value=\"{}\" xsi:type=\"TS\"/>\n\
+\t</attentionLine>\n",
+			xml_escape(date)
+		));
+	}
+	xml.push_str(&format!(
+		"\t<acknowledgement typeCode=\"{}\">\n\t\t<targetBatch>\n",
+		xml_escape(batch.batch_ack_code.as_deref().unwrap_or("AA"))
+	));
+	xml.push_str(&id_element(
+		"\t\t\t",
+		batch.batch_number.as_deref(),
+		"2.16.840.1.113883.3.989.2.1.3.22",
+	));
+	xml.push_str("\t\t</targetBatch>\n");
+	xml.push_str(&detail_element("\t\t", batch.batch_error_text.as_deref()));
+	xml.push_str("\t</acknowledgement>\n</MCCI_IN200101UV01>\n");
+	xml
+}
+
+fn id_element(indent: &str, extension: Option<&str>, root: &str) -> String {
+	match extension {
+		Some(ext) => format!(
+			"{indent}<id extension=\"{}\" root=\"{root}\"/>\n",
+			xml_escape(ext)
+		),
+		None => format!("{indent}<id nullFlavor=\"UNK\" root=\"{root}\"/>\n"),
+	}
+}
+
+fn ts_element(indent: &str, name: &str, value: Option<&str>) -> String {
+	match value {
+		Some(value) => format!("{indent}<{name} value=\"{}\"/>\n", xml_escape(value)),
+		None => format!("{indent}<{name} nullFlavor=\"UNK\"/>\n"),
+	}
+}
+
+fn device_element(
+	indent: &str,
+	name: &str,
+	type_code: &str,
+	identifier: Option<&str>,
+	root: &str,
+) -> String {
+	format!(
+		"{indent}<{name} typeCode=\"{type_code}\">\n\
+{indent}\t<device classCode=\"DEV\" determinerCode=\"INSTANCE\">\n\
+{}\
+{indent}\t</device>\n\
+{indent}</{name}>\n",
+		id_element(&format!("{indent}\t\t"), identifier, root)
+	)
+}
+
+fn detail_element(indent: &str, text: Option<&str>) -> String {
+	match text {
+		Some(text) if !text.trim().is_empty() => format!(
+			"{indent}<acknowledgementDetail>\n{indent}\t<text>{}</text>\n{indent}</acknowledgementDetail>\n",
+			xml_escape(text)
+		),
+		_ => String::new(),
+	}
+}
+
+fn join_errors(errors: &[XmlValidationError]) -> String {
+	errors
+		.iter()
+		.map(|err| err.message.as_str())
+		.collect::<Vec<_>>()
+		.join(";")
+}
+
+fn fmt_ack_date(dt: OffsetDateTime) -> String {
+	format!(
+		"{:04}{:02}{:02}{:02}{:02}{:02}",
+		dt.year(),
+		u8::from(dt.month()),
+		dt.day(),
+		dt.hour(),
+		dt.minute(),
+		dt.second()
+	)
+}
+
+fn xml_escape(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
@@ -0,0 +1,249 @@
+use crate::xml::error::Error;
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::tree::{Document, Node};
+use libxml::xpath::Context;
+
+const BATCH_REPORTS_XPATH: &str = "/hl7:MCCI_IN200100UV01/hl7:PORR_IN049016UV";
+
+/// One ICSR message (PORR_IN049016UV) cut out of an MCCI_IN200100UV01 batch.
+///
+/// `xml` is a standalone batch document that keeps the N.1 batch wrapper and
+/// contains only this message, so it can go through the single-report
+/// validation/import pipeline unchanged.
+#[derive(Debug, Clone)]
+pub struct BatchReportSlice {
+	/// Zero-based position of the message inside the batch.
+	pub index: usize,
+	/// XPath of the message inside the original batch document.
+	pub xpath: String,
+	/// N.2.r.1 Message Identifier.
+	pub message_number: Option<String>,
+	/// C.1.1 Sender's (case) Safety Report Unique Identifier.
+	pub safety_report_id: Option<String>,
+	pub xml: Vec<u8>,
+}
+
+/// Splits an MCCI_IN200100UV01 batch into one document per ICSR message.
+///
+/// Documents with zero or one message are returned as a single slice holding
+/// the original bytes, so single-report imports keep the exact source XML.
+pub fn split_batch_reports(xml: &[u8]) -> Result<Vec<BatchReportSlice>> {
+	let doc = parse_document(xml)?;
+	let mut xpath = new_xpath(&doc)?;
+	let reports = batch_reports(&mut xpath)?;
+
+	if reports.len() <= 1 {
+		let (message_number, safety_report_id) = reports
+			.first()
+			.map(|node| report_identifiers(&mut xpath, node))
+			.unwrap_or((None, None));
+		return Ok(vec![BatchReportSlice {
+			index: 0,
+			xpath: format!("{BATCH_REPORTS_XPATH}[1]"),
+			message_number,
+			safety_report_id,
+			xml: xml.to_vec(),
+		}]);
+	}
+
+	let identifiers: Vec<_> = reports
+		.iter()
+		.map(|node| report_identifiers(&mut xpath, node))
+		.collect();
+	let documents = single_report_documents(&doc, reports)?;
+
+	Ok(identifiers
+		.into_iter()
+		.zip(documents)
+		.enumerate()
+		.map(
+			|(index, ((message_number, safety_report_id), xml))| BatchReportSlice {
+				index,
+				xpath: format!("{BATCH_REPORTS_XPATH}[{}]", index + 1),
+				message_number,
+				safety_report_id,
+				xml: xml.into_bytes(),
+			},
+		)
+		.collect())
+}
+
+/// Serializes the batch wrapper once per message, each time holding only
+/// that message, so the batch is parsed a single time.
+fn single_report_documents(
+	doc: &Document,
+	mut reports: Vec<Node>,
+) -> Result<Vec<String>> {
+	let insert_error = |err: String| Error::InvalidXml {
+		message: format!("Failed to isolate batch message: {err}"),
+		line: None,
+		column: None,
+	};
+	let mut parent = reports
+		.first()
+		.and_then(Node::get_parent)
+		.ok_or(Error::MissingRootElement)?;
+	// Messages go back where they were: before whatever followed the last one.
+	let mut anchor = reports.last().and_then(Node::get_next_sibling);
+	for node in reports.iter_mut() {
+		node.unlink_node();
+	}
+
+	let mut documents = Vec::with_capacity(reports.len());
+	for node in reports.iter_mut() {
+		match anchor.as_mut() {
+			Some(anchor) => anchor
+				.add_prev_sibling(node)
+				.map_err(|err| insert_error(err.to_string()))?,
+			None => parent.add_child(node).map_err(insert_error)?,
+		}
+		documents.push(doc.to_string());
+		node.unlink_node();
+	}
+	Ok(documents)
+}
+
+fn report_identifiers(
+	xpath: &mut Context,
+	node: &Node,
+) -> (Option<String>, Option<String>) {
+	let mut first_value = |expr: &str| -> Option<String> {
+		xpath
+			.findvalues(expr, Some(node))
+			.ok()?
+			.into_iter()
+			.map(|v| v.trim().to_string())
+			.find(|v| !v.is_empty())
+	};
+	(
+		first_value("hl7:id/@extension"),
+		first_value(
+			".//hl7:investigationEvent/hl7:id[@root='2.16.840.1.113883.3.989.2.1.3.1']/@extension",
+		),
+	)
+}
+
+fn parse_document(xml: &[u8]) -> Result<Document> {
+	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
+		message: format!("XML not valid UTF-8: {err}"),
+		line: None,
+		column: None,
+	})?;
+	Parser::default()
+		.parse_string(xml_str)
+		.map_err(|err| Error::InvalidXml {
+			message: format!("XML parse error: {err}"),
+			line: None,
+			column: None,
+		})
+}
+
+fn new_xpath(doc: &Document) -> Result<Context> {
+	let xpath = Context::new(doc).map_err(|_| Error::InvalidXml {
+		message: "Failed to initialize XPath context".to_string(),
+		line: None,
+		column: None,
+	})?;
+	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+	Ok(xpath)
+}
+
+/// N.1 batch header values written onto an assembled batch document.
+#[derive(Debug, Clone)]
+pub struct BatchHeader {
+	/// N.1.2 Batch Number.
+	pub batch_number: String,
+	/// N.1.3 Batch Sender Identifier.
+	pub sender_identifier: String,
+	/// N.1.4 Batch Receiver Identifier.
+	pub receiver_identifier: String,
+	/// N.1.5 Date of Batch Transmission (HL7 TS, e.g. `20240131120000`).
+	pub transmission_date: String,
+}
+
+/// Merges single-report documents into one MCCI_IN200100UV01 batch.
+///
+/// The first document provides the batch wrapper; the PORR_IN049016UV
+/// messages of every document are appended in order and the N.1 header is
+/// overwritten with `header`.
+pub fn assemble_batch_reports(
+	documents: &[String],
+	header: &BatchHeader,
+) -> Result<String> {
+	let Some((first, rest)) = documents.split_first() else {
+		return Err(Error::InvalidXml {
+			message: "Batch export requires at least one report".to_string(),
+			line: None,
+			column: None,
+		});
+	};
+
+	let mut doc = parse_document(first.as_bytes())?;
+	let mut xpath = new_xpath(&doc)?;
+	let mut last = batch_reports(&mut xpath)?
+		.pop()
+		.ok_or(Error::MissingRootElement)?;
+
+	for source in rest {
+		let source_doc = parse_document(source.as_bytes())?;
+		let mut source_xpath = new_xpath(&source_doc)?;
+		for mut report in batch_reports(&mut source_xpath)? {
+			report.unlink_node();
+			let mut imported =
+				doc.import_node(&mut report).map_err(|_| Error::InvalidXml {
+					message: "Failed to copy message into batch".to_string(),
+					line: None,
+					column: None,
+				})?;
+			last.add_next_sibling(&mut imported)
+				.map_err(|err| Error::InvalidXml {
+					message: format!("Failed to append message to batch: {err}"),
+					line: None,
+					column: None,
+				})?;
+			last = imported;
+		}
+	}
+
+	set_batch_attr(&mut xpath, "hl7:id", "extension", &header.batch_number);
+	set_batch_attr(
+		&mut xpath,
+		"hl7:creationTime",
+		"value",
+		&header.transmission_date,
+	);
+	set_batch_attr(
+		&mut xpath,
+		"hl7:sender/hl7:device/hl7:id",
+		"extension",
+		&header.sender_identifier,
+	);
+	set_batch_attr(
+		&mut xpath,
+		"hl7:receiver/hl7:device/hl7:id",
+		"extension",
+		&header.receiver_identifier,
+	);
+
+	Ok(doc.to_string())
+}
+
+fn batch_reports(xpath: &mut Context) -> Result<Vec<Node>> {
+	xpath
+		.findnodes(BATCH_REPORTS_XPATH, None)
+		.map_err(|_| Error::InvalidXml {
+			message: "Failed to query batch messages".to_string(),
+			line: None,
+			column: None,
+		})
+}
+
+fn set_batch_attr(xpath: &mut Context, path: &str, attr: &str, value: &str) {
+	let path = format!("/hl7:MCCI_IN200100UV01/{path}");
+	if let Ok(nodes) = xpath.findnodes(&path, None) {
+		if let Some(mut node) = nodes.into_iter().next() {
+			let _ = node.set_attribute(attr, value);
+		}
+	}
+}
@@ -0,0 +1,307 @@
+// Structured diff between the exported XML of two case versions
+
+use crate::ctx::Ctx;
+use crate::model;
+use crate::model::case::{Case, CaseBmc};
+use crate::model::case_diff::{
+	diff_documents, CaseDiff, CaseDiffParams, CaseDiffSide, CaseDocument,
+};
+use crate::model::ModelManager;
+use crate::xml::error::Error;
+use crate::xml::export::export_case_xml_from_db;
+use crate::xml::import_sections::c_safety_report::parse_c_safety_report;
+use crate::xml::import_sections::d_patient::parse_d_patient;
+use crate::xml::import_sections::e_reaction::parse_e_reactions;
+use crate::xml::import_sections::f_test_result::parse_f_test_results;
+use crate::xml::import_sections::g_drug::parse_g_drugs;
+use crate::xml::import_sections::h_narrative::parse_h_narrative;
+use crate::xml::Result;
+use sqlx::types::Uuid;
+
+/// Diffs the E2B XML of two versions of the safety report `case_id` belongs
+/// to, each rendered from its stored state (the import base with edited
+/// sections patched in), so follow-up and nullification versions and edits
+/// made after import are compared as they would be exported.
+///
+/// Only what the section importers read is compared (C.1, D, E.i, F.r, G.k
+/// with its substances, dosages and indications, and H).
+pub async fn diff_case_version_snapshots(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+	params: CaseDiffParams,
+) -> Result<CaseDiff> {
+	let case = CaseBmc::get(ctx, mm, case_id).await.map_err(Error::from)?;
+	let to_version = params.to.unwrap_or(case.version);
+	let from_version = params.from.unwrap_or(to_version - 1);
+
+	let (from_case_id, old) = version_document(ctx, mm, &case, from_version).await?;
+	let (to_case_id, new) = version_document(ctx, mm, &case, to_version).await?;
+
+	Ok(CaseDiff {
+		safety_report_id: case.safety_report_id,
+		from: CaseDiffSide {
+			case_id: from_case_id,
+			version: from_version,
+		},
+		to: CaseDiffSide {
+			case_id: to_case_id,
+			version: to_version,
+		},
+		sections: diff_documents(&old, &new),
+	})
+}
+
+/// Renders `version` of the safety report of `case` into a diff document.
+async fn version_document(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case: &Case,
+	version: i32,
+) -> Result<(Uuid, CaseDocument)> {
+	let version_case_id: Option<(Uuid,)> = mm
+		.dbx()
+		.fetch_optional(
+			sqlx::query_as(
+				"SELECT id FROM cases
+				 WHERE organization_id = $1 AND safety_report_id = $2
+				   AND version = $3",
+			)
+			.bind(case.organization_id)
+			.bind(&case.safety_report_id)
+			.bind(version),
+		)
+		.await
+		.map_err(model::Error::from)?;
+	let (version_case_id,) =
+		version_case_id.ok_or(model::Error::EntityNotFound {
+			entity: "case_versions",
+			id: version as i64,
+		})?;
+	let xml = export_case_xml_from_db(ctx, mm, version_case_id).await?;
+	Ok((version_case_id, parse_case_document(xml.as_bytes())?))
+}
+
+/// Reads an ICSR into the document compared by `diff_documents`, using the
+/// same field names as the section tables. Repeating items are numbered in
+/// document order.
+pub fn parse_case_document(xml: &[u8]) -> Result<CaseDocument> {
+	let mut document = CaseDocument::default();
+
+	if let Some(c) = parse_c_safety_report(xml)? {
+		document.push(
+			"C.1",
+			None,
+			None,
+			[
+				("transmission_date", text(Some(c.transmission_date))),
+				("report_type", Some(c.report_type)),
+				(
+					"date_first_received_from_source",
+					text(Some(c.date_first_received_from_source)),
+				),
+				(
+					"date_of_most_recent_information",
+					text(Some(c.date_of_most_recent_information)),
+				),
+				("fulfil_expedited_criteria", text(Some(c.fulfil_expedited_criteria))),
+				("local_criteria_report_type", c.local_criteria_report_type),
+				(
+					"combination_product_report_indicator",
+					c.combination_product_report_indicator,
+				),
+				("worldwide_unique_id", c.worldwide_unique_id),
+				("nullification_code", c.nullification_code),
+				("nullification_reason", c.nullification_reason),
+			],
+		);
+	}
+
+	if let Some(d) = parse_d_patient(xml)? {
+		document.push(
+			"D",
+			None,
+			None,
+			[
+				("patient_initials", d.patient_initials),
+				("patient_given_name", d.patient_given_name),
+				("patient_family_name", d.patient_family_name),
+				("birth_date", text(d.birth_date)),
+				("sex", d.sex),
+				("age_at_time_of_onset", text(d.age_at_time_of_onset)),
+				("age_unit", d.age_unit),
+				("gestation_period", text(d.gestation_period)),
+				("gestation_period_unit", d.gestation_period_unit),
+				("age_group", d.age_group),
+				("weight_kg", text(d.weight_kg)),
+				("height_cm", text(d.height_cm)),
+				("race_code", d.race_code),
+				("ethnicity_code", d.ethnicity_code),
+				("last_menstrual_period_date", text(d.last_menstrual_period_date)),
+				("medical_history_text", d.medical_history_text),
+				("concomitant_therapy", text(d.concomitant_therapy)),
+			],
+		);
+	}
+
+	for (index, e) in parse_e_reactions(xml)?.into_iter().enumerate() {
+		document.push(
+			"E.i",
+			None,
+			Some(index as i32 + 1),
+			[
+				("primary_source_reaction", Some(e.primary_source_reaction)),
+				("reaction_language", e.reaction_language),
+				("reaction_meddra_version", e.reaction_meddra_version),
+				("reaction_meddra_code", e.reaction_meddra_code),
+				("term_highlighted", text(e.term_highlighted)),
+				("serious", text(e.serious)),
+				("criteria_death", text(e.criteria_death)),
+				("criteria_life_threatening", text(e.criteria_life_threatening)),
+				("criteria_hospitalization", text(e.criteria_hospitalization)),
+				("criteria_disabling", text(e.criteria_disabling)),
+				("criteria_congenital_anomaly", text(e.criteria_congenital_anomaly)),
+				(
+					"criteria_other_medically_important",
+					text(e.criteria_other_medically_important),
+				),
+				("required_intervention", e.required_intervention),
+				("start_date", text(e.start_date)),
+				("end_date", text(e.end_date)),
+				("duration_value", text(e.duration_value)),
+				("duration_unit", e.duration_unit),
+				("outcome", e.outcome),
+				("medical_confirmation", text(e.medical_confirmation)),
+				("country_code", e.country_code),
+			],
+		);
+	}
+
+	for (index, f) in parse_f_test_results(xml)?.into_iter().enumerate() {
+		document.push(
+			"F.r",
+			None,
+			Some(index as i32 + 1),
+			[
+				("test_date", text(f.test_date)),
+				("test_name", Some(f.test_name)),
+				("test_meddra_version", f.test_meddra_version),
+				("test_meddra_code", f.test_meddra_code),
+				("test_result_code", f.test_result_code),
+				("test_result_value", f.test_result_value),
+				("test_result_unit", f.test_result_unit),
+				("result_unstructured", f.result_unstructured),
+				("normal_low_value", f.normal_low_value),
+				("normal_high_value", f.normal_high_value),
+				("comments", f.comments),
+				("more_info_available", text(f.more_info_available)),
+			],
+		);
+	}
+
+	for g in parse_g_drugs(xml)? {
+		let drug = document.push(
+			"G.k",
+			None,
+			Some(g.sequence_number),
+			[
+				("drug_characterization", Some(g.drug_characterization)),
+				("medicinal_product", Some(g.medicinal_product)),
+				("brand_name", g.brand_name),
+				("mpid", g.mpid),
+				("mpid_version", g.mpid_version),
+				(
+					"investigational_product_blinded",
+					text(g.investigational_product_blinded),
+				),
+				("obtain_drug_country", g.obtain_drug_country),
+				("manufacturer_name", g.manufacturer_name),
+				("manufacturer_country", g.manufacturer_country),
+				("batch_lot_number", g.batch_lot_number),
+				("dosage_text", g.dosage_text),
+				("action_taken", g.action_taken),
+				("rechallenge", g.rechallenge),
+				("parent_route", g.parent_route),
+				("parent_route_termid", g.parent_route_termid),
+				("parent_route_termid_version", g.parent_route_termid_version),
+				("parent_dosage_text", g.parent_dosage_text),
+				("fda_additional_info_coded", g.fda_additional_info_coded),
+				("kr_product_code_version", g.kr_product_code_version),
+				("kr_product_code", g.kr_product_code),
+			],
+		);
+		for (index, s) in g.substances.into_iter().enumerate() {
+			document.push(
+				"G.k.2.3.r",
+				Some(&drug),
+				Some(index as i32 + 1),
+				[
+					("substance_name", s.substance_name),
+					("substance_termid", s.substance_termid),
+					("substance_termid_version", s.substance_termid_version),
+					("strength_value", text(s.strength_value)),
+					("strength_unit", s.strength_unit),
+					("kr_ingredient_code_version", s.kr_ingredient_code_version),
+					("kr_ingredient_code", s.kr_ingredient_code),
+				],
+			);
+		}
+		for (index, d) in g.dosages.into_iter().enumerate() {
+			document.push(
+				"G.k.4.r",
+				Some(&drug),
+				Some(index as i32 + 1),
+				[
+					("dose_value", text(d.dose_value)),
+					("dose_unit", d.dose_unit),
+					("frequency_value", text(d.frequency_value)),
+					("frequency_unit", d.frequency_unit),
+					("first_administration_date", text(d.start_date)),
+					("last_administration_date", text(d.end_date)),
+					("duration_value", text(d.duration_value)),
+					("duration_unit", d.duration_unit),
+					("batch_lot_number", d.batch_lot),
+					("dosage_text", d.dosage_text),
+					("dose_form", d.dose_form),
+					("dose_form_termid", d.dose_form_termid),
+					("dose_form_termid_version", d.dose_form_termid_version),
+					("route_of_administration", d.route),
+					("parent_route", d.parent_route),
+					("parent_route_termid", d.parent_route_termid),
+					("parent_route_termid_version", d.parent_route_termid_version),
+				],
+			);
+		}
+		for (index, i) in g.indications.into_iter().enumerate() {
+			document.push(
+				"G.k.7.r",
+				Some(&drug),
+				Some(index as i32 + 1),
+				[
+					("indication_text", i.text),
+					("indication_meddra_version", i.version),
+					("indication_meddra_code", i.code),
+				],
+			);
+		}
+	}
+
+	if let Some(h) = parse_h_narrative(xml)? {
+		document.push(
+			"H",
+			None,
+			None,
+			[
+				("case_narrative", Some(h.case_narrative)),
+				("reporter_comments", h.reporter_comments),
+				("sender_comments", h.sender_comments),
+			],
+		);
+	}
+
+	Ok(document)
+}
+
+fn text(value: Option<impl ToString>) -> Option<String> {
+	value.map(|value| value.to_string())
+}
@@ -0,0 +1,2 @@
+// EMA-specific business rules (EudraVigilance profile on top of ICH core).
+pub mod validation;
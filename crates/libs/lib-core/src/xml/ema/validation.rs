@@ -0,0 +1,231 @@
+use crate::ctx::Ctx;
+use crate::model::message_header::MessageHeader;
+use crate::model::safety_report::{
+	LiteratureReference, SafetyReportIdentification, StudyInformation,
+};
+use crate::model::{ModelManager, Result};
+use crate::xml::validate::{
+	build_report, has_text, load_rule_set, push_issue_if_condition_violated,
+	push_issue_if_conditioned_value_invalid, CaseValidationReport, RuleFacts,
+	ValidationIssue, ValidationProfile,
+};
+use sqlx::types::Uuid;
+
+async fn get_safety_report_optional(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Option<SafetyReportIdentification>> {
+	let sql = "SELECT * FROM safety_report_identification WHERE case_id = $1";
+	mm.dbx()
+		.fetch_optional(
+			sqlx::query_as::<_, SafetyReportIdentification>(sql).bind(case_id),
+		)
+		.await
+		.map_err(Into::into)
+}
+
+async fn get_message_header_optional(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Option<MessageHeader>> {
+	let sql = "SELECT * FROM message_headers WHERE case_id = $1";
+	mm.dbx()
+		.fetch_optional(sqlx::query_as::<_, MessageHeader>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+async fn get_study_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Option<StudyInformation>> {
+	let sql = "SELECT * FROM study_information WHERE case_id = $1 ORDER BY created_at LIMIT 1";
+	mm.dbx()
+		.fetch_optional(sqlx::query_as::<_, StudyInformation>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+async fn list_literature_references_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<LiteratureReference>> {
+	let sql = "SELECT * FROM literature_references WHERE case_id = $1 ORDER BY sequence_number";
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, LiteratureReference>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+fn push_ema_value_issue(
+	issues: &mut Vec<ValidationIssue>,
+	code: &str,
+	path: impl Into<String>,
+	value: Option<&str>,
+	condition_facts: RuleFacts,
+) {
+	let _ = push_issue_if_conditioned_value_invalid(
+		issues,
+		code,
+		code,
+		code,
+		path,
+		value,
+		None,
+		condition_facts,
+		RuleFacts::default(),
+	);
+}
+
+fn is_code(value: Option<&str>, code: &str) -> bool {
+	value.map(str::trim) == Some(code)
+}
+
+/// EVCTMPROD takes clinical trial reports (C.1.3=2 with C.5.4=1), EVHUMAN
+/// everything else; EVTEST accepts both.
+fn is_receiver_routing_mismatch(receiver: &str, clinical_trial: bool) -> bool {
+	let receiver = receiver.trim().to_ascii_uppercase();
+	match receiver.as_str() {
+		"EVCTMPROD" => !clinical_trial,
+		"EVHUMAN" => clinical_trial,
+		_ => false,
+	}
+}
+
+pub async fn validate_case(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<CaseValidationReport> {
+	let ich_report =
+		crate::xml::ich::validation::validate_case(ctx, mm, case_id).await?;
+	let rules = load_rule_set(ctx, mm).await?;
+	let report = get_safety_report_optional(mm, case_id).await?;
+	let header = get_message_header_optional(mm, case_id).await?;
+	let study = get_study_by_case(mm, case_id).await?;
+	let literature = list_literature_references_by_case(mm, case_id).await?;
+
+	let mut issues: Vec<ValidationIssue> = ich_report.issues;
+
+	let clinical_trial = report
+		.as_ref()
+		.is_some_and(|r| is_code(Some(r.report_type.as_str()), "2"))
+		&& study
+			.as_ref()
+			.is_some_and(|s| is_code(s.study_type_reaction.as_deref(), "1"));
+
+	if let Some(header) = header.as_ref() {
+		let message_receiver = header.message_receiver_identifier.as_str();
+		// Export falls back to the message receiver when N.1.4 is empty.
+		let batch_receiver = header
+			.batch_receiver_identifier
+			.as_deref()
+			.filter(|value| has_text(Some(value)))
+			.unwrap_or(message_receiver);
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.N.1.4.RECEIVER.INVALID",
+			"messageHeader.batchReceiverIdentifier",
+			Some(batch_receiver),
+			RuleFacts::default(),
+		);
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.N.2.r.3.RECEIVER.INVALID",
+			"messageHeader.messageReceiverIdentifier",
+			Some(message_receiver),
+			RuleFacts::default(),
+		);
+		let _ = push_issue_if_condition_violated(
+			&mut issues,
+			"EMA.N.2.r.3.RECEIVER.MISMATCH",
+			"messageHeader.messageReceiverIdentifier",
+			RuleFacts {
+				ema_receiver_routing_mismatch: Some(is_receiver_routing_mismatch(
+					message_receiver,
+					clinical_trial,
+				)),
+				..RuleFacts::default()
+			},
+		);
+	}
+
+	if let Some(report) = report.as_ref() {
+		let literature_facts = RuleFacts {
+			ema_literature_reference_present: Some(!literature.is_empty()),
+			..RuleFacts::default()
+		};
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.C.1.3.LITERATURE.INVALID",
+			"safetyReportIdentification.reportType",
+			Some(report.report_type.trim()),
+			literature_facts,
+		);
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.C.1.8.1.REQUIRED",
+			"safetyReportIdentification.worldwideUniqueId",
+			report.worldwide_unique_id.as_deref(),
+			RuleFacts::default(),
+		);
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.C.1.8.1.FORMAT",
+			"safetyReportIdentification.worldwideUniqueId",
+			report.worldwide_unique_id.as_deref(),
+			RuleFacts::default(),
+		);
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.C.1.11.2.REQUIRED",
+			"safetyReportIdentification.nullificationReason",
+			report.nullification_reason.as_deref(),
+			RuleFacts {
+				ema_nullification_code_present: Some(has_text(
+					report.nullification_code.as_deref(),
+				)),
+				..RuleFacts::default()
+			},
+		);
+	}
+
+	literature.iter().enumerate().for_each(|(idx, reference)| {
+		push_ema_value_issue(
+			&mut issues,
+			"EMA.C.4.r.2.RECOMMENDED",
+			format!("literatureReferences.{idx}.documentBase64"),
+			reference.document_base64.as_deref(),
+			RuleFacts {
+				ema_literature_reference_present: Some(true),
+				..RuleFacts::default()
+			},
+		);
+	});
+
+	Ok(build_report(
+		ValidationProfile::Ema,
+		case_id,
+		rules.apply(issues),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn receiver_routing_follows_clinical_trial_flag() {
+		assert!(is_receiver_routing_mismatch("EVHUMAN", true));
+		assert!(!is_receiver_routing_mismatch("EVHUMAN", false));
+		assert!(is_receiver_routing_mismatch("EVCTMPROD", false));
+		assert!(!is_receiver_routing_mismatch("EVCTMPROD", true));
+		// Case and surrounding blanks are ignored.
+		assert!(is_receiver_routing_mismatch(" evctmprod ", false));
+		// EVTEST takes both; unknown receivers are left to RECEIVER.INVALID.
+		assert!(!is_receiver_routing_mismatch("EVTEST", true));
+		assert!(!is_receiver_routing_mismatch("EVTEST", false));
+		assert!(!is_receiver_routing_mismatch("ZZFDA", true));
+		assert!(!is_receiver_routing_mismatch("", false));
+	}
+}
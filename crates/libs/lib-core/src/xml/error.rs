@@ -1,5 +1,6 @@
 use crate::model;
 use crate::xml::types::XmlValidationError;
+use crate::xml::validate::ValidationIssue;
 use derive_more::From;
 use serde::Serialize;
 use serde_with::{serde_as, DisplayFromStr};
@@ -15,6 +16,9 @@ pub enum Error {
 	XsdValidationFailed {
 		errors: Vec<XmlValidationError>,
 	},
+	InvalidCodes {
+		issues: Vec<ValidationIssue>,
+	},
 	MissingRootElement,
 	UnsupportedRoot {
 		found: String,
@@ -1,19 +1,26 @@
 use crate::ctx::Ctx;
 use crate::model;
-use crate::model::case::CaseBmc;
+use crate::model::case::{Case, CaseBmc};
+use crate::model::case_workflow::STATUS_VALIDATED;
 use crate::model::drug::{
 	DosageInformation, DrugActiveSubstance, DrugDeviceCharacteristic,
 	DrugIndication, DrugInformation,
 };
-use crate::model::message_header::MessageHeader;
+use crate::model::message_header::{
+	MessageHeader, MessageHeaderBmc, MessageHeaderForUpdate,
+};
 use crate::model::narrative::NarrativeInformationBmc;
 use crate::model::patient::PatientInformationBmc;
 use crate::model::reaction::Reaction;
 use crate::model::safety_report::{StudyInformation, StudyRegistrationNumber};
 use crate::model::safety_report::SafetyReportIdentificationBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
 use crate::model::test_result::TestResult;
 use crate::model::ModelManager;
+use crate::xml::batch::{assemble_batch_reports, BatchHeader};
 use crate::xml::error::Error;
+use crate::xml::mfds::export::apply_kr_elements_from_db;
+use crate::xml::pmda::export::apply_jp_elements_from_db;
 use crate::xml::export_postprocess::postprocess_export_doc;
 use crate::xml::export_sections::c_safety_report::export_c_safety_report_patch;
 use crate::xml::export_sections::c_safety_report::export_c_safety_report_xml;
@@ -26,10 +33,15 @@ use crate::xml::export_sections::h_narrative::export_h_narrative_xml;
 use crate::xml::raw::patch::{
 	patch_e_reactions, patch_f_test_results, patch_g_drugs, patch_h_narrative,
 };
+use crate::xml::types::{XmlBatchExportRequest, XmlBatchExportResult};
+use crate::xml::validate::{ValidationProfile, CODE_LIST_INVALID_RULE};
+use crate::xml::xml_validation::{should_skip_xml_validation, validate_e2b_xml};
 use crate::xml::Result;
 use libxml::parser::Parser;
 use libxml::tree::Document;
 use libxml::xpath::Context;
+use std::collections::HashSet;
+use time::OffsetDateTime;
 
 pub async fn export_case_xml(
 	ctx: &Ctx,
@@ -55,6 +67,7 @@ pub async fn export_case_xml(
 			column: None,
 		});
 	}
+	ensure_codes_in_lists(ctx, mm, &case).await?;
 
 	if let Some(raw_xml) = case.raw_xml.as_deref() {
 		let only_c_dirty = case.dirty_c
@@ -398,7 +411,212 @@ pub async fn export_case_xml(
 	export_case_xml_from_db(ctx, mm, case_id).await
 }
 
-async fn export_case_xml_from_db(
+/// Re-checks coded values against the active `e2b_code_lists` rows, so codes
+/// retired after the case was validated are not sent.
+async fn ensure_codes_in_lists(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case: &Case,
+) -> Result<()> {
+	let profile = case
+		.validation_profile
+		.as_deref()
+		.and_then(ValidationProfile::parse)
+		.unwrap_or(ValidationProfile::Fda);
+	let report = match profile {
+		ValidationProfile::Ich => {
+			crate::xml::ich::validation::validate_case(ctx, mm, case.id).await
+		}
+		ValidationProfile::Fda => {
+			crate::xml::fda::validation::validate_case(ctx, mm, case.id).await
+		}
+		ValidationProfile::Mfds => {
+			crate::xml::mfds::validation::validate_case(ctx, mm, case.id).await
+		}
+		ValidationProfile::Ema => {
+			crate::xml::ema::validation::validate_case(ctx, mm, case.id).await
+		}
+		ValidationProfile::Pmda => {
+			crate::xml::pmda::validation::validate_case(ctx, mm, case.id).await
+		}
+	}
+	.map_err(Error::from)?;
+	let issues: Vec<_> = report
+		.issues
+		.into_iter()
+		.filter(|issue| issue.code == CODE_LIST_INVALID_RULE)
+		.collect();
+	if issues.is_empty() {
+		Ok(())
+	} else {
+		Err(Error::InvalidCodes { issues })
+	}
+}
+
+/// Exports several validated cases as one MCCI_IN200100UV01 batch.
+///
+/// All cases must target the same receiver and validation profile. The
+/// assembled batch is validated and, once accepted, its N.1 header is recorded
+/// on every case's message header.
+pub async fn export_cases_batch_xml(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	req: XmlBatchExportRequest,
+) -> Result<XmlBatchExportResult> {
+	if req.case_ids.is_empty() {
+		return Err(batch_export_error("case_ids must not be empty"));
+	}
+	let mut seen = HashSet::new();
+	if let Some(dup) = req.case_ids.iter().find(|id| !seen.insert(**id)) {
+		return Err(batch_export_error(&format!(
+			"case {dup} is listed more than once"
+		)));
+	}
+
+	let mut receiver: Option<String> = None;
+	let mut profile: Option<String> = None;
+	let mut default_sender: Option<String> = None;
+	for case_id in &req.case_ids {
+		let case = CaseBmc::get(ctx, mm, *case_id).await.map_err(Error::from)?;
+		if case.status != STATUS_VALIDATED {
+			return Err(batch_export_error(&format!(
+				"case {case_id} has status '{}'; only validated cases can be batched",
+				case.status
+			)));
+		}
+		let header = MessageHeaderBmc::get_by_case(ctx, mm, *case_id)
+			.await
+			.map_err(Error::from)?;
+		let case_receiver = non_empty(header.batch_receiver_identifier.as_deref())
+			.unwrap_or(&header.message_receiver_identifier)
+			.to_string();
+		let case_profile = case
+			.validation_profile
+			.as_deref()
+			.map(|p| p.trim().to_ascii_lowercase())
+			.unwrap_or_else(|| "fda".to_string());
+
+		match &receiver {
+			Some(expected) if *expected != case_receiver => {
+				return Err(batch_export_error(&format!(
+					"case {case_id} targets receiver '{case_receiver}', expected '{expected}'"
+				)));
+			}
+			Some(_) => {}
+			None => receiver = Some(case_receiver),
+		}
+		match &profile {
+			Some(expected) if *expected != case_profile => {
+				return Err(batch_export_error(&format!(
+					"case {case_id} uses validation profile '{case_profile}', expected '{expected}'"
+				)));
+			}
+			Some(_) => {}
+			None => profile = Some(case_profile),
+		}
+		if default_sender.is_none() {
+			default_sender = Some(
+				non_empty(header.batch_sender_identifier.as_deref())
+					.unwrap_or(&header.message_sender_identifier)
+					.to_string(),
+			);
+		}
+	}
+
+	let mut documents = Vec::with_capacity(req.case_ids.len());
+	for case_id in &req.case_ids {
+		documents.push(export_case_xml(ctx, mm, *case_id).await?);
+	}
+
+	let transmitted_at = OffsetDateTime::now_utc();
+	let header = BatchHeader {
+		batch_number: non_empty(req.batch_number.as_deref())
+			.map(str::to_string)
+			.unwrap_or_else(|| format!("BATCH-{}", sqlx::types::Uuid::new_v4())),
+		sender_identifier: non_empty(req.batch_sender_identifier.as_deref())
+			.map(str::to_string)
+			.or(default_sender)
+			.unwrap_or_default(),
+		receiver_identifier: receiver.unwrap_or_default(),
+		transmission_date: fmt_datetime(transmitted_at),
+	};
+	let xml =
+		normalize_namespace_artifacts(assemble_batch_reports(&documents, &header)?);
+
+	let validation = validate_e2b_xml(xml.as_bytes(), None)?;
+	if !validation.ok && !should_skip_xml_validation() {
+		return Err(Error::XsdValidationFailed {
+			errors: validation.errors,
+		});
+	}
+
+	record_batch_header(ctx, mm, &req.case_ids, &header, transmitted_at).await?;
+
+	Ok(XmlBatchExportResult {
+		batch_number: header.batch_number,
+		batch_sender_identifier: header.sender_identifier,
+		batch_receiver_identifier: header.receiver_identifier,
+		validation_profile: profile.unwrap_or_default(),
+		case_ids: req.case_ids,
+		validation,
+		xml,
+	})
+}
+
+/// Writes the batch header onto every exported case in one transaction.
+async fn record_batch_header(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_ids: &[sqlx::types::Uuid],
+	header: &BatchHeader,
+	transmitted_at: OffsetDateTime,
+) -> Result<()> {
+	let mm = mm.new_with_txn().map_err(Error::from)?;
+	mm.dbx().begin_txn().await.map_err(model::Error::from)?;
+	set_full_context_dbx_or_rollback(
+		mm.dbx(),
+		ctx.user_id(),
+		ctx.organization_id(),
+		ctx.role(),
+	)
+	.await?;
+	for case_id in case_ids {
+		let update = MessageHeaderForUpdate {
+			batch_number: Some(header.batch_number.clone()),
+			batch_sender_identifier: Some(header.sender_identifier.clone()),
+			batch_receiver_identifier: Some(header.receiver_identifier.clone()),
+			batch_transmission_date: Some(transmitted_at),
+			message_number: None,
+			message_sender_identifier: None,
+			message_receiver_identifier: None,
+		};
+		if let Err(err) =
+			MessageHeaderBmc::update_by_case(ctx, &mm, *case_id, update).await
+		{
+			let _ = mm.dbx().rollback_txn().await;
+			return Err(err.into());
+		}
+	}
+	mm.dbx().commit_txn().await.map_err(model::Error::from)?;
+	Ok(())
+}
+
+fn batch_export_error(message: &str) -> Error {
+	Error::InvalidXml {
+		message: format!("Batch export: {message}"),
+		line: None,
+		column: None,
+	}
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+	value.filter(|v| !v.trim().is_empty())
+}
+
+/// Renders the stored state of a case: the import base with every dirty
+/// section patched from its tables. Unlike [`export_case_xml`] it does not
+/// require the case to be validated.
+pub(crate) async fn export_case_xml_from_db(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	case_id: sqlx::types::Uuid,
@@ -556,7 +774,19 @@ async fn export_case_xml_from_db(
 		xpath.register_namespace("xsi", "http://www.w3.org/2001/XMLSchema-instance");
 	apply_section_n(&mut doc, &parser, mm, case_id, &mut xpath).await?;
 	apply_study_section(&mut doc, &parser, mm, case_id, &mut xpath).await?;
-	postprocess_export_doc(&mut doc, &mut xpath);
+	let profile = case
+		.validation_profile
+		.as_deref()
+		.and_then(ValidationProfile::parse);
+	if profile == Some(ValidationProfile::Mfds) {
+		apply_kr_elements_from_db(&mut doc, &parser, mm, case_id, &mut xpath)
+			.await?;
+	}
+	if profile == Some(ValidationProfile::Pmda) {
+		apply_jp_elements_from_db(&mut doc, &parser, mm, case_id, &mut xpath)
+			.await?;
+	}
+	postprocess_export_doc(&mut doc, &mut xpath, profile);
 
 	Ok(normalize_namespace_artifacts(doc.to_string()))
 }
@@ -4,12 +4,16 @@ use crate::xml::validate::{
 	export_attribute_strip_spec_for_rule, export_normalization_spec_for_rule,
 	export_xpath_for_rule, export_xpaths_for_rule,
 	has_export_directive, is_rule_condition_satisfied, ExportDirective,
-	ExportNormalizeKind, RuleFacts,
+	ExportNormalizeKind, RuleFacts, ValidationProfile,
 };
 
-pub(crate) fn postprocess_export_doc(doc: &mut Document, xpath: &mut Context) {
+pub(crate) fn postprocess_export_doc(
+	doc: &mut Document,
+	xpath: &mut Context,
+	profile: Option<ValidationProfile>,
+) {
 	normalize_export_values(xpath);
-	prune_optional_nodes(doc, xpath);
+	prune_optional_nodes(doc, xpath, profile);
 }
 
 fn normalize_export_values(xpath: &mut Context) {
@@ -64,7 +68,11 @@ fn matches_normalization_kind(value: &str, kind: ExportNormalizeKind) -> bool {
 	}
 }
 
-fn prune_optional_nodes(_doc: &mut Document, xpath: &mut Context) {
+fn prune_optional_nodes(
+	_doc: &mut Document,
+	xpath: &mut Context,
+	profile: Option<ValidationProfile>,
+) {
 	if has_export_directive(
 		"ICH.XML.OPTIONAL.PATH.EMPTY.PRUNE",
 		ExportDirective::RemoveOptionalPathEmptyNodes,
@@ -98,7 +106,7 @@ fn prune_optional_nodes(_doc: &mut Document, xpath: &mut Context) {
 		}
 	}
 
-	prune_placeholder_nodes(xpath);
+	prune_placeholder_nodes(xpath, profile);
 	if has_export_directive(
 		"ICH.XML.STRUCTURAL.EMPTY.PRUNE",
 		ExportDirective::RemoveEmptyStructuralNodes,
@@ -107,7 +115,10 @@ fn prune_optional_nodes(_doc: &mut Document, xpath: &mut Context) {
 	}
 }
 
-fn prune_placeholder_nodes(xpath: &mut Context) {
+fn prune_placeholder_nodes(
+	xpath: &mut Context,
+	profile: Option<ValidationProfile>,
+) {
 	if has_export_directive(
 		"ICH.XML.PLACEHOLDER.VALUE.PRUNE",
 		ExportDirective::RemovePlaceholderValueNodes,
@@ -165,10 +176,13 @@ fn prune_placeholder_nodes(xpath: &mut Context) {
 		}
 	}
 
-	if has_export_directive(
-		"ICH.XML.SUMMARY.LANGUAGE.JA.FORBIDDEN",
-		ExportDirective::RemoveSummaryLanguageJa,
-	) {
+	// PMDA takes Japanese summaries; every other receiver gets them untagged.
+	if profile != Some(ValidationProfile::Pmda)
+		&& has_export_directive(
+			"ICH.XML.SUMMARY.LANGUAGE.JA.FORBIDDEN",
+			ExportDirective::RemoveSummaryLanguageJa,
+		)
+	{
 		if let Some(spec) =
 			export_attribute_strip_spec_for_rule("ICH.XML.SUMMARY.LANGUAGE.JA.FORBIDDEN")
 		{
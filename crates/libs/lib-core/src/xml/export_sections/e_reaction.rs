@@ -4,7 +4,6 @@ use crate::xml::validate::{
 	should_emit_required_intervention_null_flavor_ni,
 };
 use crate::xml::Result;
-use sqlx::types::time::Date;
 
 pub fn export_e_reactions_xml(reactions: &[Reaction]) -> Result<String> {
 	let mut reactions_xml = String::new();
@@ -36,12 +35,12 @@ pub(crate) fn reaction_fragment(reaction: &Reaction) -> String {
 		}
 		if let Some(start) = reaction.start_date {
 			out.push_str("<low value=\"");
-			out.push_str(&fmt_date(start));
+			out.push_str(&start.to_string());
 			out.push_str("\"/>");
 		}
 		if let Some(end) = reaction.end_date {
 			out.push_str("<high value=\"");
-			out.push_str(&fmt_date(end));
+			out.push_str(&end.to_string());
 			out.push_str("\"/>");
 		}
 		if let Some(width) = reaction.duration_value.as_ref() {
@@ -178,15 +177,6 @@ fn term_highlight_code(
 	}
 }
 
-fn fmt_date(date: Date) -> String {
-	format!(
-		"{:04}{:02}{:02}",
-		date.year(),
-		u8::from(date.month()),
-		date.day()
-	)
-}
-
 fn xml_escape(value: &str) -> String {
 	value
 		.replace('&', "&amp;")
@@ -1,6 +1,5 @@
 use crate::model::test_result::TestResult;
 use crate::xml::Result;
-use sqlx::types::time::Date;
 
 pub fn export_f_test_results_xml(results: &[TestResult]) -> Result<String> {
 	let mut items_xml = String::new();
@@ -39,7 +38,7 @@ pub(crate) fn test_result_fragment(result: &TestResult) -> String {
 
 	if let Some(date) = result.test_date {
 		out.push_str("<effectiveTime value=\"");
-		out.push_str(&fmt_date(date));
+		out.push_str(&date.to_string());
 		out.push_str("\"/>");
 	}
 
@@ -104,15 +103,6 @@ pub(crate) fn test_result_fragment(result: &TestResult) -> String {
 	out
 }
 
-fn fmt_date(date: Date) -> String {
-	format!(
-		"{:04}{:02}{:02}",
-		date.year(),
-		u8::from(date.month()),
-		date.day()
-	)
-}
-
 fn xml_escape(value: &str) -> String {
 	value
 		.replace('&', "&amp;")
@@ -3,7 +3,7 @@ use crate::model::drug::{
 	DrugIndication, DrugInformation,
 };
 use crate::xml::Result;
-use sqlx::types::time::{Date, Time};
+use sqlx::types::time::Time;
 use std::collections::HashMap;
 
 pub fn export_g_drugs_xml(
@@ -303,12 +303,12 @@ pub(crate) fn drug_fragment(
 			out.push_str("<effectiveTime>");
 			if let Some(start) = dose.first_administration_date {
 				out.push_str("<comp operator=\"A\"><low value=\"");
-				out.push_str(&fmt_date(start));
+				out.push_str(&start.to_string());
 				out.push_str("\"/></comp>");
 			}
 			if let Some(end) = dose.last_administration_date {
 				out.push_str("<comp operator=\"A\"><high value=\"");
-				out.push_str(&fmt_date(end));
+				out.push_str(&end.to_string());
 				out.push_str("\"/></comp>");
 			}
 			if let Some(width) = dose.duration_value.as_ref() {
@@ -418,15 +418,6 @@ pub(crate) fn drug_fragment(
 	out
 }
 
-fn fmt_date(date: Date) -> String {
-	format!(
-		"{:04}{:02}{:02}",
-		date.year(),
-		u8::from(date.month()),
-		date.day()
-	)
-}
-
 #[allow(dead_code)]
 fn fmt_time(time: Time) -> String {
 	format!("{:02}{:02}{:02}", time.hour(), time.minute(), time.second())
@@ -1,3 +1,4 @@
 // FDA-specific code lists and business rules (ICH/MDFS later).
 pub mod codes;
+pub mod rule_input;
 pub mod validation;
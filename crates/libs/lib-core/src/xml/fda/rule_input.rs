@@ -0,0 +1,530 @@
+// Builds the FDA rejection/warning rule view from an ICSR batch.
+
+use crate::xml::mapping::fda::g_drug::GDrugPaths;
+use crate::xml::validate::{
+	FdaRuleAssessment, FdaRuleBatch, FdaRuleCause, FdaRuleDevice, FdaRuleDocument,
+	FdaRuleDosage, FdaRuleDrug, FdaRulePair, FdaRuleParent, FdaRulePastDrug,
+	FdaRulePatient, FdaRulePrimarySource, FdaRuleReaction, FdaRuleReport,
+	FdaRuleTest, FdaRuleValue,
+};
+use libxml::tree::Node;
+use libxml::xpath::Context;
+
+const BATCH_SENDER: &str =
+	"/hl7:MCCI_IN200100UV01/hl7:sender/hl7:device/hl7:id/@extension";
+const BATCH_RECEIVER: &str =
+	"/hl7:MCCI_IN200100UV01/hl7:receiver/hl7:device/hl7:id/@extension";
+const REPORT_NODE: &str = "/hl7:MCCI_IN200100UV01/hl7:PORR_IN049016UV";
+const MESSAGE_SENDER: &str = "hl7:sender/hl7:device/hl7:id/@extension";
+const MESSAGE_RECEIVER: &str = "hl7:receiver/hl7:device/hl7:id/@extension";
+const INVESTIGATION_EVENT: &str =
+	"hl7:controlActProcess/hl7:subject/hl7:investigationEvent";
+
+// Relative to the investigation event
+const REPORT_TYPE: &str = "hl7:subjectOf2/hl7:investigationCharacteristic[hl7:code[@code='1' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.23']]/hl7:value/@code";
+const OTHER_IDS_AVAILABLE: &str = "hl7:subjectOf2/hl7:investigationCharacteristic[hl7:code[@code='2' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.23']]/hl7:value";
+const NULLIFICATION_CODE: &str = "hl7:subjectOf2/hl7:investigationCharacteristic[hl7:code[@code='3' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.23']]/hl7:value/@code";
+const DOCUMENTS_AVAILABLE: &str = "hl7:component/hl7:observationEvent[hl7:code[@code='1' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const DOCUMENT_NODE: &str = "hl7:reference/hl7:document[hl7:code[@code='1' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.27']]";
+const EXPEDITED: &str = "hl7:component/hl7:observationEvent[hl7:code[@code='23' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const LOCAL_CRITERIA: &str = "hl7:component/hl7:observationEvent[hl7:code[@code='C54588' and @codeSystem='2.16.840.1.113883.3.26.1.1']]/hl7:value/@code";
+const COMBINATION_PRODUCT: &str = "hl7:component/hl7:observationEvent[hl7:code[@code='C156384' and @codeSystem='2.16.840.1.113883.3.26.1.1']]/hl7:value";
+const OTHER_ID_NODE: &str = "hl7:subjectOf1/hl7:controlActEvent/hl7:id[@root='2.16.840.1.113883.3.989.2.1.3.3']";
+const LINKED_REPORT_NODE: &str = "hl7:outboundRelationship[@typeCode='SPRT']/hl7:relatedInvestigation[hl7:code[@nullFlavor='NA']]/hl7:subjectOf2/hl7:controlActEvent/hl7:id[@root='2.16.840.1.113883.3.989.2.1.3.2']";
+const PRIMARY_SOURCE_NODE: &str = "hl7:outboundRelationship[@typeCode='SPRT'][hl7:relatedInvestigation/hl7:code[@code='2' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.22']]";
+const PRIMARY_SOURCE_REGULATORY: &str = "hl7:priorityNumber/@value";
+const PRIMARY_SOURCE_QUALIFICATION: &str = "hl7:relatedInvestigation/hl7:subjectOf2/hl7:controlActEvent/hl7:author/hl7:assignedEntity/hl7:assignedPerson/hl7:asQualifiedEntity/hl7:code/@code";
+const PRIMARY_SOURCE_COUNTRY: &str = "hl7:relatedInvestigation/hl7:subjectOf2/hl7:controlActEvent/hl7:author/hl7:assignedEntity/hl7:assignedPerson/hl7:asLocatedEntity/hl7:location/hl7:code/@code";
+const SENDER_TYPE: &str =
+	"hl7:subjectOf1/hl7:controlActEvent/hl7:author/hl7:assignedEntity/hl7:code/@code";
+const SENDER_ORGANIZATION: &str = "hl7:subjectOf1/hl7:controlActEvent/hl7:author/hl7:assignedEntity/hl7:representedOrganization/hl7:assignedEntity/hl7:representedOrganization/hl7:name";
+const CAUSALITY_NODE: &str =
+	"hl7:component/hl7:adverseEventAssessment/hl7:component/hl7:causalityAssessment";
+const DIAGNOSIS_VALUE: &str = "hl7:component/hl7:adverseEventAssessment/hl7:component1/hl7:observationEvent[hl7:code[@code='15' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const CASE_SUMMARY_VALUE: &str = "hl7:component/hl7:observationEvent[hl7:code[@code='36' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const PRIMARY_ROLE: &str =
+	"hl7:component/hl7:adverseEventAssessment/hl7:subject1/hl7:primaryRole";
+
+// Relative to the patient role
+const STUDY_TYPE: &str = "hl7:subjectOf1/hl7:researchStudy/hl7:code/@code";
+const IND_NUMBER: &str = "hl7:subjectOf1/hl7:researchStudy/hl7:authorization/hl7:studyRegistration/hl7:id[@root='2.16.840.1.113883.3.989.5.1.2.2.1.2.1']/@extension";
+const PRE_ANDA_NUMBER: &str = "hl7:subjectOf1/hl7:researchStudy/hl7:authorization/hl7:studyRegistration/hl7:id[@root='2.16.840.1.113883.3.989.5.1.2.2.1.2.2']/@extension";
+const CROSS_REPORTED_IND_NODE: &str = "hl7:subjectOf1/hl7:researchStudy/hl7:authorization/hl7:studyRegistration/hl7:id[@root='2.16.840.1.113883.3.989.5.1.2.2.1.2.3']";
+const PATIENT_NAME: &str = "hl7:player1/hl7:name";
+const AGE_VALUE: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='3' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const GESTATION_VALUE: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='16' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const MEDICAL_HISTORY_ORGANIZER: &str = "hl7:subjectOf2/hl7:organizer[hl7:code[@code='1' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.20']]";
+const MEDICAL_HISTORY_EPISODE_CODE: &str = "hl7:component/hl7:observation[not(hl7:code[@codeSystem='2.16.840.1.113883.3.989.2.1.1.19'])]/hl7:code";
+const MEDICAL_HISTORY_TEXT: &str = "hl7:component/hl7:observation[hl7:code[@code='18' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const PAST_DRUG_NODE: &str = "hl7:subjectOf2/hl7:organizer[hl7:code[@code='2' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.20']]/hl7:component/hl7:substanceAdministration";
+const PAST_DRUG_NAME: &str =
+	"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:name";
+const PAST_DRUG_OTHER_DATA: &str = "hl7:effectiveTime | hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:code";
+const PAST_DRUG_INDICATION: &str = "hl7:outboundRelationship2/hl7:observation[hl7:code[@code='19' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const PAST_DRUG_REACTION: &str = "hl7:outboundRelationship2/hl7:observation[hl7:code[@code='29' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const DEATH_DATE: &str = "hl7:player1/hl7:deceasedTime";
+const REPORTED_CAUSE_VALUE: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='32' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const AUTOPSY_OBSERVATION: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='5' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]";
+const AUTOPSY_CAUSE_VALUE: &str = "hl7:outboundRelationship2/hl7:observation[hl7:code[@code='8' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const PARENT_ROLE: &str = "hl7:player1/hl7:role[hl7:code[@code='PRN']]";
+const PARENT_SEX: &str = "hl7:associatedPerson/hl7:administrativeGenderCode";
+const RACE_VALUE: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='C17049' and @codeSystem='2.16.840.1.113883.3.26.1.1']]/hl7:value";
+const ETHNICITY_VALUE: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='C16564' and @codeSystem='2.16.840.1.113883.3.26.1.1']]/hl7:value";
+const REACTION_NODE: &str = "hl7:subjectOf2/hl7:observation[hl7:code[@code='29' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]";
+const TEST_NODE: &str = "hl7:subjectOf2/hl7:organizer[hl7:code[@code='3' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.20']]/hl7:component/hl7:observation";
+const DRUG_NODE: &str = "hl7:subjectOf2/hl7:organizer[hl7:code[@code='4' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.20']]/hl7:component/hl7:substanceAdministration";
+
+// Relative to a reaction
+const REACTION_TERM: &str = "hl7:value/hl7:originalText";
+const REACTION_LANGUAGE: &str = "hl7:value/hl7:originalText/@language";
+const REACTION_MEDDRA_CODE: &str = "hl7:value/@code";
+const REACTION_DEATH: &str = "hl7:outboundRelationship2/hl7:observation[hl7:code[@code='34' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value/@value";
+const REACTION_DURATION: &str =
+	"hl7:effectiveTime/hl7:comp[@operator='A']/hl7:width";
+
+// Relative to a test
+const TEST_DATE: &str = "hl7:effectiveTime/@value";
+const TEST_NAME: &str = "hl7:code/hl7:originalText";
+const TEST_MEDDRA_VERSION: &str = "hl7:code/@codeSystemVersion";
+const TEST_MEDDRA_CODE: &str = "hl7:code/@code";
+const TEST_RESULT_CODE: &str = "hl7:interpretationCode/@code";
+const TEST_RESULT_VALUE: &str = "hl7:value/@value | hl7:value/hl7:center/@value | hl7:value/hl7:low/@value | hl7:value/hl7:high/@value";
+const TEST_RESULT_UNIT: &str = "hl7:value/@unit | hl7:value/hl7:center/@unit | hl7:value/hl7:low/@unit | hl7:value/hl7:high/@unit";
+const TEST_RESULT_UNSTRUCTURED: &str = "hl7:value[@xsi:type='ED']";
+
+// Relative to a drug
+const CHARACTERIZATION_CODE_SYSTEM: &str = "2.16.840.1.113883.3.989.2.1.1.13";
+const OTHER_CHARACTERIZATION_CODE_SYSTEM: &str =
+	"2.16.840.1.113883.3.989.5.1.2.1.1.8";
+const AUTHORISATION_NUMBER: &str = "hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:asManufacturedProduct/hl7:subjectOf/hl7:approval/hl7:id/@extension";
+const AUTHORISATION_COUNTRY: &str = "hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:asManufacturedProduct/hl7:subjectOf/hl7:approval/hl7:author/hl7:territorialAuthority/hl7:territory/hl7:code/@code";
+const CUMULATIVE_DOSE: &str = "hl7:outboundRelationship2/hl7:observation[hl7:code[@code='14' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const GESTATION_AT_EXPOSURE: &str = "hl7:outboundRelationship2/hl7:observation[hl7:code[@code='16' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value";
+const START_INTERVAL: &str =
+	"hl7:outboundRelationship1[@typeCode='SAS']/hl7:pauseQuantity";
+const LAST_DOSE_INTERVAL: &str =
+	"hl7:outboundRelationship1[@typeCode='SAE']/hl7:pauseQuantity";
+const ADDITIONAL_INFO_VALUE: &str = "hl7:outboundRelationship2[@typeCode='REFR']/hl7:observation[hl7:code[@code='9']]/hl7:value";
+const DEVICE_NODE: &str =
+	"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:part/hl7:partProduct";
+const DEVICE_CHARACTERISTIC: &str =
+	"hl7:asManufacturedProduct/hl7:subjectOf/hl7:characteristic";
+
+pub(crate) fn collect_fda_rule_batch(xpath: &mut Context) -> FdaRuleBatch {
+	let reports = nodes(xpath, None, REPORT_NODE)
+		.iter()
+		.map(|node| collect_report(xpath, node))
+		.collect();
+	FdaRuleBatch {
+		batch_sender: value(xpath, None, BATCH_SENDER),
+		batch_receiver: value(xpath, None, BATCH_RECEIVER),
+		reports,
+	}
+}
+
+fn collect_report(xpath: &mut Context, message: &Node) -> FdaRuleReport {
+	let mut report = FdaRuleReport {
+		message_sender: value(xpath, Some(message), MESSAGE_SENDER),
+		message_receiver: value(xpath, Some(message), MESSAGE_RECEIVER),
+		..FdaRuleReport::default()
+	};
+	let Some(event) = nodes(xpath, Some(message), INVESTIGATION_EVENT)
+		.into_iter()
+		.next()
+	else {
+		return report;
+	};
+	let event = Some(&event);
+
+	report.report_type = value(xpath, event, REPORT_TYPE);
+	report.documents_available =
+		rule_value(xpath, event, DOCUMENTS_AVAILABLE, Some("value"));
+	report.documents = nodes(xpath, event, DOCUMENT_NODE)
+		.iter()
+		.map(|node| FdaRuleDocument {
+			title: text(xpath, Some(node), "hl7:title"),
+			representation: value(xpath, Some(node), "hl7:text/@representation"),
+			compression: value(xpath, Some(node), "hl7:text/@compression"),
+		})
+		.collect();
+	report.expedited = rule_value(xpath, event, EXPEDITED, Some("value"));
+	report.local_criteria_report_type = value(xpath, event, LOCAL_CRITERIA);
+	report.combination_product =
+		rule_value(xpath, event, COMBINATION_PRODUCT, Some("value"));
+	report.other_identifiers_available =
+		rule_value(xpath, event, OTHER_IDS_AVAILABLE, Some("value"));
+	report.other_identifiers = nodes(xpath, event, OTHER_ID_NODE)
+		.iter()
+		.map(|node| pair(xpath, node, "@assigningAuthorityName", "@extension"))
+		.collect();
+	report.linked_reports = nodes(xpath, event, LINKED_REPORT_NODE).len();
+	report.nullification_code = value(xpath, event, NULLIFICATION_CODE);
+	report.primary_sources = nodes(xpath, event, PRIMARY_SOURCE_NODE)
+		.iter()
+		.map(|node| FdaRulePrimarySource {
+			country: value(xpath, Some(node), PRIMARY_SOURCE_COUNTRY),
+			qualification: value(xpath, Some(node), PRIMARY_SOURCE_QUALIFICATION),
+			regulatory: value(xpath, Some(node), PRIMARY_SOURCE_REGULATORY),
+		})
+		.collect();
+	report.sender_type = value(xpath, event, SENDER_TYPE);
+	report.sender_organization = text(xpath, event, SENDER_ORGANIZATION);
+	report.diagnoses = nodes(xpath, event, DIAGNOSIS_VALUE)
+		.iter()
+		.map(|node| pair(xpath, node, "@codeSystemVersion", "@code"))
+		.collect();
+	report.summaries = nodes(xpath, event, CASE_SUMMARY_VALUE)
+		.iter()
+		.map(|node| {
+			FdaRulePair::new(
+				text(xpath, Some(node), "."),
+				value(xpath, Some(node), "@language"),
+			)
+		})
+		.collect();
+	let causality = nodes(xpath, event, CAUSALITY_NODE);
+
+	if let Some(role) = nodes(xpath, event, PRIMARY_ROLE).into_iter().next() {
+		let role = Some(&role);
+		report.study_type = value(xpath, role, STUDY_TYPE);
+		report.ind_number = value(xpath, role, IND_NUMBER);
+		report.pre_anda_number = value(xpath, role, PRE_ANDA_NUMBER);
+		report.cross_reported_inds = nodes(xpath, role, CROSS_REPORTED_IND_NODE)
+			.iter()
+			.map(|node| rule_value(xpath, Some(node), ".", Some("extension")))
+			.collect();
+		report.patient = collect_patient(xpath, role);
+		report.reactions = nodes(xpath, role, REACTION_NODE)
+			.iter()
+			.map(|node| collect_reaction(xpath, node))
+			.collect();
+		report.tests = nodes(xpath, role, TEST_NODE)
+			.iter()
+			.map(|node| collect_test(xpath, node))
+			.collect();
+		report.drugs = nodes(xpath, role, DRUG_NODE)
+			.iter()
+			.map(|node| collect_drug(xpath, node, &causality))
+			.collect();
+	}
+	report
+}
+
+/// Section D
+fn collect_patient(xpath: &mut Context, role: Option<&Node>) -> FdaRulePatient {
+	let history = nodes(xpath, role, MEDICAL_HISTORY_ORGANIZER);
+	let autopsy = nodes(xpath, role, AUTOPSY_OBSERVATION);
+	FdaRulePatient {
+		name: rule_value(xpath, role, PATIENT_NAME, None),
+		age: value_pair(xpath, role, AGE_VALUE),
+		gestation: value_pair(xpath, role, GESTATION_VALUE),
+		medical_history_provided: !history.is_empty(),
+		medical_history: medical_history(xpath, role),
+		medical_history_text: history
+			.first()
+			.and_then(|node| text(xpath, Some(node), MEDICAL_HISTORY_TEXT)),
+		past_drugs: past_drugs(xpath, role),
+		death_date: rule_value(xpath, role, DEATH_DATE, Some("value")),
+		reported_causes: nodes(xpath, role, REPORTED_CAUSE_VALUE)
+			.iter()
+			.map(|node| collect_cause(xpath, node))
+			.collect(),
+		autopsy: autopsy
+			.first()
+			.map(|node| rule_value(xpath, Some(node), "hl7:value", Some("value")))
+			.unwrap_or_default(),
+		autopsy_causes: autopsy
+			.iter()
+			.flat_map(|node| nodes(xpath, Some(node), AUTOPSY_CAUSE_VALUE))
+			.collect::<Vec<_>>()
+			.iter()
+			.map(|node| collect_cause(xpath, node))
+			.collect(),
+		parent: nodes(xpath, role, PARENT_ROLE).first().map(|node| {
+			let parent = Some(node);
+			FdaRuleParent {
+				age: value_pair(xpath, parent, AGE_VALUE),
+				sex: rule_value(xpath, parent, PARENT_SEX, Some("code")),
+				medical_history: medical_history(xpath, parent),
+				past_drugs: past_drugs(xpath, parent),
+			}
+		}),
+		race: nodes(xpath, role, RACE_VALUE)
+			.iter()
+			.map(|node| rule_value(xpath, Some(node), ".", Some("code")))
+			.collect(),
+		ethnicity: rule_value(xpath, role, ETHNICITY_VALUE, Some("code")),
+	}
+}
+
+fn medical_history(xpath: &mut Context, role: Option<&Node>) -> Vec<FdaRulePair> {
+	nodes(xpath, role, MEDICAL_HISTORY_ORGANIZER)
+		.iter()
+		.flat_map(|node| nodes(xpath, Some(node), MEDICAL_HISTORY_EPISODE_CODE))
+		.collect::<Vec<_>>()
+		.iter()
+		.map(|node| pair(xpath, node, "@codeSystemVersion", "@code"))
+		.collect()
+}
+
+fn past_drugs(xpath: &mut Context, role: Option<&Node>) -> Vec<FdaRulePastDrug> {
+	nodes(xpath, role, PAST_DRUG_NODE)
+		.iter()
+		.map(|node| {
+			let drug = Some(node);
+			FdaRulePastDrug {
+				name: text(xpath, drug, PAST_DRUG_NAME),
+				other_data: !nodes(xpath, drug, PAST_DRUG_OTHER_DATA).is_empty(),
+				indication: meddra_pair(xpath, drug, PAST_DRUG_INDICATION),
+				reaction: meddra_pair(xpath, drug, PAST_DRUG_REACTION),
+			}
+		})
+		.collect()
+}
+
+fn collect_cause(xpath: &mut Context, node: &Node) -> FdaRuleCause {
+	FdaRuleCause {
+		meddra: pair(xpath, node, "@codeSystemVersion", "@code"),
+		text: text(xpath, Some(node), "hl7:originalText"),
+	}
+}
+
+/// Section E
+fn collect_reaction(xpath: &mut Context, node: &Node) -> FdaRuleReaction {
+	let reaction = Some(node);
+	FdaRuleReaction {
+		term: text(xpath, reaction, REACTION_TERM),
+		language: value(xpath, reaction, REACTION_LANGUAGE),
+		meddra_code: value(xpath, reaction, REACTION_MEDDRA_CODE),
+		death: value(xpath, reaction, REACTION_DEATH)
+			.is_some_and(|death| death.trim().eq_ignore_ascii_case("true")),
+		duration: value_pair(xpath, reaction, REACTION_DURATION),
+	}
+}
+
+/// Section F
+fn collect_test(xpath: &mut Context, node: &Node) -> FdaRuleTest {
+	let test = Some(node);
+	FdaRuleTest {
+		date: value(xpath, test, TEST_DATE),
+		name: text(xpath, test, TEST_NAME),
+		meddra: FdaRulePair::new(
+			value(xpath, test, TEST_MEDDRA_VERSION),
+			value(xpath, test, TEST_MEDDRA_CODE),
+		),
+		result_code: value(xpath, test, TEST_RESULT_CODE),
+		result_value: value(xpath, test, TEST_RESULT_VALUE),
+		result_unit: value(xpath, test, TEST_RESULT_UNIT),
+		result_unstructured: text(xpath, test, TEST_RESULT_UNSTRUCTURED),
+	}
+}
+
+/// Section G; G.k.1 and G.k.9.i.2.r sit on causality assessments that point
+/// back at the drug by its id.
+fn collect_drug(
+	xpath: &mut Context,
+	node: &Node,
+	causality: &[Node],
+) -> FdaRuleDrug {
+	let drug = Some(node);
+	let drug_id = value(xpath, drug, GDrugPaths::XML_ID_ROOT);
+	let mut characterization = None;
+	let mut other_characterization = None;
+	let mut assessments = Vec::new();
+	for assessment in causality {
+		let assessment = Some(assessment);
+		let target = value(
+			xpath,
+			assessment,
+			"hl7:subject2/hl7:productUseReference/hl7:id/@root",
+		);
+		if drug_id.is_none() || target != drug_id {
+			continue;
+		}
+		match value(xpath, assessment, "hl7:code/@code").as_deref() {
+			Some("20") => {
+				let code = value(xpath, assessment, "hl7:value/@code");
+				match value(xpath, assessment, "hl7:value/@codeSystem").as_deref() {
+					Some(CHARACTERIZATION_CODE_SYSTEM) => characterization = code,
+					Some(OTHER_CHARACTERIZATION_CODE_SYSTEM) => {
+						other_characterization = code
+					}
+					_ => {}
+				}
+			}
+			Some("39") => assessments.push(FdaRuleAssessment {
+				source: text(
+					xpath,
+					assessment,
+					"hl7:author/hl7:assignedEntity/hl7:code/hl7:originalText",
+				),
+				method: text(xpath, assessment, "hl7:methodCode/hl7:originalText"),
+				result: text(xpath, assessment, "hl7:value"),
+			}),
+			_ => {}
+		}
+	}
+
+	FdaRuleDrug {
+		characterization,
+		other_characterization,
+		substance_strengths: nodes(xpath, drug, GDrugPaths::SUBSTANCE_NODE)
+			.iter()
+			.map(|node| {
+				FdaRulePair::new(
+					value(xpath, Some(node), GDrugPaths::SUBSTANCE_STRENGTH_VALUE),
+					value(xpath, Some(node), GDrugPaths::SUBSTANCE_STRENGTH_UNIT),
+				)
+			})
+			.collect(),
+		authorisation_number: value(xpath, drug, AUTHORISATION_NUMBER),
+		authorisation_country: value(xpath, drug, AUTHORISATION_COUNTRY),
+		dosages: nodes(xpath, drug, GDrugPaths::DOSAGE_NODE)
+			.iter()
+			.map(|node| {
+				let dosage = Some(node);
+				FdaRuleDosage {
+					dose: FdaRulePair::new(
+						value(xpath, dosage, GDrugPaths::DOSE_VALUE),
+						value(xpath, dosage, GDrugPaths::DOSE_UNIT),
+					),
+					frequency: FdaRulePair::new(
+						value(xpath, dosage, GDrugPaths::DOSAGE_FREQUENCY_VALUE),
+						value(xpath, dosage, GDrugPaths::DOSAGE_FREQUENCY_UNIT),
+					),
+					duration: FdaRulePair::new(
+						value(xpath, dosage, GDrugPaths::DOSAGE_DURATION_VALUE),
+						value(xpath, dosage, GDrugPaths::DOSAGE_DURATION_UNIT),
+					),
+				}
+			})
+			.collect(),
+		cumulative_dose: value_pair(xpath, drug, CUMULATIVE_DOSE),
+		gestation: value_pair(xpath, drug, GESTATION_AT_EXPOSURE),
+		indications: nodes(xpath, drug, GDrugPaths::INDICATION_NODE)
+			.iter()
+			.map(|node| {
+				pair(
+					xpath,
+					node,
+					GDrugPaths::INDICATION_VERSION,
+					GDrugPaths::INDICATION_CODE,
+				)
+			})
+			.collect(),
+		assessments,
+		start_intervals: nodes(xpath, drug, START_INTERVAL)
+			.iter()
+			.map(|node| pair(xpath, node, "@value", "@unit"))
+			.collect(),
+		last_dose_intervals: nodes(xpath, drug, LAST_DOSE_INTERVAL)
+			.iter()
+			.map(|node| pair(xpath, node, "@value", "@unit"))
+			.collect(),
+		additional_info: nodes(xpath, drug, ADDITIONAL_INFO_VALUE)
+			.iter()
+			.map(|node| rule_value(xpath, Some(node), ".", Some("code")))
+			.collect(),
+		devices: nodes(xpath, drug, DEVICE_NODE)
+			.iter()
+			.map(|node| collect_device(xpath, node))
+			.collect(),
+	}
+}
+
+/// FDA.G.k.12.r
+fn collect_device(xpath: &mut Context, node: &Node) -> FdaRuleDevice {
+	let device = Some(node);
+	let characteristic = |code: &str| {
+		format!("{DEVICE_CHARACTERISTIC}[hl7:code[@code='{code}']]/hl7:value")
+	};
+	FdaRuleDevice {
+		malfunction: rule_value(
+			xpath,
+			device,
+			&characteristic("C54026"),
+			Some("value"),
+		),
+		problem_codes: values(
+			xpath,
+			device,
+			&format!("{}/@code", characteristic("C54451")),
+		),
+		brand_name: rule_value(xpath, device, "hl7:name[1]", None),
+		common_name: rule_value(xpath, device, "hl7:name[2]", None),
+		product_code: value(xpath, device, "hl7:code/@code"),
+		remedial_actions: nodes(xpath, device, &characteristic("C54594")).len(),
+	}
+}
+
+// -- XPath helpers
+
+fn nodes(xpath: &mut Context, node: Option<&Node>, expr: &str) -> Vec<Node> {
+	xpath.findnodes(expr, node).unwrap_or_default()
+}
+
+fn values(xpath: &mut Context, node: Option<&Node>, expr: &str) -> Vec<String> {
+	xpath
+		.findvalues(expr, node)
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|v| !v.trim().is_empty())
+		.collect()
+}
+
+fn value(xpath: &mut Context, node: Option<&Node>, expr: &str) -> Option<String> {
+	values(xpath, node, expr).into_iter().next()
+}
+
+fn text(xpath: &mut Context, node: Option<&Node>, expr: &str) -> Option<String> {
+	nodes(xpath, node, expr)
+		.into_iter()
+		.map(|n| n.get_content())
+		.find(|content| !content.trim().is_empty())
+}
+
+/// `attr` of `element`, or its text when `attr` is `None`, with the
+/// element's nullFlavor.
+fn rule_value(
+	xpath: &mut Context,
+	node: Option<&Node>,
+	element: &str,
+	attr: Option<&str>,
+) -> FdaRuleValue {
+	let value = match attr {
+		Some(attr) => value(xpath, node, &format!("{element}/@{attr}")),
+		None => text(xpath, node, element),
+	};
+	FdaRuleValue {
+		value,
+		null_flavor: self::value(xpath, node, &format!("{element}/@nullFlavor")),
+	}
+}
+
+fn pair(xpath: &mut Context, node: &Node, a: &str, b: &str) -> FdaRulePair {
+	FdaRulePair::new(value(xpath, Some(node), a), value(xpath, Some(node), b))
+}
+
+/// `@value` and `@unit` of a PQ element
+fn value_pair(
+	xpath: &mut Context,
+	node: Option<&Node>,
+	element: &str,
+) -> FdaRulePair {
+	FdaRulePair::new(
+		value(xpath, node, &format!("{element}/@value")),
+		value(xpath, node, &format!("{element}/@unit")),
+	)
+}
+
+/// `@codeSystemVersion` and `@code` of a MedDRA coded element
+fn meddra_pair(
+	xpath: &mut Context,
+	node: Option<&Node>,
+	element: &str,
+) -> FdaRulePair {
+	FdaRulePair::new(
+		value(xpath, node, &format!("{element}/@codeSystemVersion")),
+		value(xpath, node, &format!("{element}/@code")),
+	)
+}
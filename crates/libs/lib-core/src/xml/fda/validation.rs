@@ -1,15 +1,47 @@
 use crate::ctx::Ctx;
-use crate::model::patient::PatientInformation;
+use crate::model::case::CaseBmc;
+use crate::model::case_identifiers::{LinkedReportNumber, OtherCaseIdentifier};
+use crate::model::drug::{
+	DosageInformation, DrugActiveSubstance, DrugDeviceCharacteristic,
+	DrugIndication, DrugInformation, DrugInformationBmc,
+};
+use crate::model::drug_reaction_assessment::{
+	DrugReactionAssessment, RelatednessAssessment,
+};
+use crate::model::message_header::MessageHeaderBmc;
+use crate::model::narrative::{
+	CaseSummaryInformation, NarrativeInformationBmc, SenderDiagnosis,
+};
+use crate::model::parent_history::{ParentMedicalHistory, ParentPastDrugHistory};
+use crate::model::patient::{
+	AutopsyCauseOfDeath, MedicalHistoryEpisode, ParentInformation, PastDrugHistory,
+	PatientDeathInformation, PatientInformation, ReportedCauseOfDeath,
+};
 use crate::model::reaction::Reaction;
-use crate::model::safety_report::{PrimarySource, SafetyReportIdentification};
+use crate::model::safety_report::{
+	DocumentsHeldBySender, PrimarySource, SafetyReportIdentification,
+	SenderInformation, StudyInformation,
+};
+use crate::model::terminology::E2bCodeListBmc;
+use crate::model::test_result::TestResultBmc;
 use crate::model::{ModelManager, Result};
 use crate::xml::validate::{
-	build_report, CaseValidationReport, ValidationIssue, ValidationProfile,
+	build_report, check_fda_rules, has_text, load_rule_set, CaseValidationReport,
+	FdaRuleAssessment, FdaRuleBatch, FdaRuleCause, FdaRuleDevice,
+	FdaRuleDocument, FdaRuleDosage, FdaRuleDrug, FdaRulePair, FdaRuleParent,
+	FdaRulePastDrug, FdaRulePatient, FdaRulePrimarySource, FdaRuleReaction,
+	FdaRuleReport, FdaRuleScope, FdaRuleTest, FdaRuleValue, ValidationIssue,
+	ValidationProfile,
 	has_any_primary_source_content,
 	should_case_validator_require_required_intervention,
 	push_issue_if_conditioned_value_invalid, RuleFacts,
+	push_issue_if_code_not_in_list, FDA_ETHNICITY, FDA_RACE,
+	LOCAL_CRITERIA_REPORT_TYPE,
 };
+use sqlx::postgres::PgRow;
 use sqlx::types::Uuid;
+use sqlx::FromRow;
+use std::fmt::Display;
 
 async fn get_safety_report_optional(
 	mm: &ModelManager,
@@ -47,18 +79,540 @@ async fn get_patient_optional(
 		.map_err(Into::into)
 }
 
+async fn rows<T>(mm: &ModelManager, sql: &str, id: Uuid) -> Result<Vec<T>>
+where
+	T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, T>(sql).bind(id))
+		.await
+		.map_err(Into::into)
+}
+
+async fn rows_any<T>(mm: &ModelManager, sql: &str, ids: &[Uuid]) -> Result<Vec<T>>
+where
+	T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+	if ids.is_empty() {
+		return Ok(Vec::new());
+	}
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, T>(sql).bind(ids.to_vec()))
+		.await
+		.map_err(Into::into)
+}
+
+fn text_of<T: Display>(value: Option<T>) -> Option<String> {
+	value.map(|value| value.to_string())
+}
+
+/// Builds the FDA rejection/warning rule view of one stored case.
+async fn load_fda_rule_batch(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+	report: Option<&SafetyReportIdentification>,
+	patient: Option<&PatientInformation>,
+	primary_sources: &[PrimarySource],
+	reactions: &[Reaction],
+) -> Result<FdaRuleBatch> {
+	let case = CaseBmc::get(ctx, mm, case_id).await?;
+	let header = MessageHeaderBmc::get_by_case(ctx, mm, case_id).await.ok();
+	let sender: Option<SenderInformation> = rows(
+		mm,
+		"SELECT * FROM sender_information WHERE case_id = $1 LIMIT 1",
+		case_id,
+	)
+	.await?
+	.into_iter()
+	.next();
+	let study: Option<StudyInformation> = rows(
+		mm,
+		"SELECT * FROM study_information WHERE case_id = $1 LIMIT 1",
+		case_id,
+	)
+	.await?
+	.into_iter()
+	.next();
+	let documents: Vec<DocumentsHeldBySender> = rows(
+		mm,
+		"SELECT * FROM documents_held_by_sender WHERE case_id = $1 ORDER BY sequence_number",
+		case_id,
+	)
+	.await?;
+	let other_identifiers: Vec<OtherCaseIdentifier> = rows(
+		mm,
+		"SELECT * FROM other_case_identifiers WHERE case_id = $1 ORDER BY sequence_number",
+		case_id,
+	)
+	.await?;
+	let linked_reports: Vec<LinkedReportNumber> = rows(
+		mm,
+		"SELECT * FROM linked_report_numbers WHERE case_id = $1 ORDER BY sequence_number",
+		case_id,
+	)
+	.await?;
+	let narrative = NarrativeInformationBmc::get_by_case(ctx, mm, case_id)
+		.await
+		.ok();
+	let narrative_ids: Vec<Uuid> = narrative.iter().map(|n| n.id).collect();
+	let diagnoses: Vec<SenderDiagnosis> = rows_any(
+		mm,
+		"SELECT * FROM sender_diagnoses WHERE narrative_id = ANY($1) ORDER BY sequence_number",
+		&narrative_ids,
+	)
+	.await?;
+	let summaries: Vec<CaseSummaryInformation> = rows_any(
+		mm,
+		"SELECT * FROM case_summary_information WHERE narrative_id = ANY($1) ORDER BY sequence_number",
+		&narrative_ids,
+	)
+	.await?;
+
+	let mut rule_report = FdaRuleReport {
+		message_sender: header
+			.as_ref()
+			.map(|header| header.message_sender_identifier.clone()),
+		message_receiver: header
+			.as_ref()
+			.map(|header| header.message_receiver_identifier.clone()),
+		initial: Some(case.version == 1),
+		documents: documents
+			.iter()
+			.map(|document| FdaRuleDocument {
+				title: document.title.clone(),
+				representation: document.representation.clone(),
+				compression: document.compression.clone(),
+			})
+			.collect(),
+		other_identifiers: other_identifiers
+			.iter()
+			.map(|identifier| {
+				FdaRulePair::new(
+					Some(identifier.source_of_identifier.clone()),
+					Some(identifier.case_identifier.clone()),
+				)
+			})
+			.collect(),
+		linked_reports: linked_reports.len(),
+		primary_sources: primary_sources
+			.iter()
+			.map(|source| FdaRulePrimarySource {
+				country: source.country_code.clone(),
+				qualification: source.qualification.clone(),
+				regulatory: source.primary_source_regulatory.clone(),
+			})
+			.collect(),
+		sender_type: sender.as_ref().map(|sender| sender.sender_type.clone()),
+		sender_organization: sender
+			.as_ref()
+			.map(|sender| sender.organization_name.clone()),
+		study_type: study.and_then(|study| study.study_type_reaction),
+		patient: load_fda_rule_patient(mm, patient).await?,
+		reactions: reactions
+			.iter()
+			.map(|reaction| FdaRuleReaction {
+				// Import stores UNKNOWN when E.i.1.1a is absent.
+				term: Some(reaction.primary_source_reaction.clone())
+					.filter(|term| term.trim() != "UNKNOWN"),
+				language: reaction.reaction_language.clone(),
+				meddra_code: reaction.reaction_meddra_code.clone(),
+				death: reaction.criteria_death,
+				duration: FdaRulePair::new(
+					text_of(reaction.duration_value),
+					reaction.duration_unit.clone(),
+				),
+			})
+			.collect(),
+		tests: TestResultBmc::list_by_case(ctx, mm, case_id)
+			.await?
+			.into_iter()
+			.map(|test| FdaRuleTest {
+				date: text_of(test.test_date),
+				name: Some(test.test_name),
+				meddra: FdaRulePair::new(
+					test.test_meddra_version,
+					test.test_meddra_code,
+				),
+				result_code: test.test_result_code,
+				result_value: test.test_result_value,
+				result_unit: test.test_result_unit,
+				result_unstructured: test.result_unstructured,
+			})
+			.collect(),
+		drugs: load_fda_rule_drugs(ctx, mm, case_id).await?,
+		diagnoses: diagnoses
+			.into_iter()
+			.map(|diagnosis| {
+				FdaRulePair::new(
+					diagnosis.diagnosis_meddra_version,
+					diagnosis.diagnosis_meddra_code,
+				)
+			})
+			.collect(),
+		summaries: summaries
+			.into_iter()
+			.map(|summary| {
+				FdaRulePair::new(summary.summary_text, summary.language_code)
+			})
+			.collect(),
+		..FdaRuleReport::default()
+	};
+	if let Some(report) = report {
+		rule_report.report_type = Some(report.report_type.clone());
+		rule_report.expedited =
+			FdaRuleValue::new(Some(report.fulfil_expedited_criteria.to_string()));
+		rule_report.local_criteria_report_type =
+			report.local_criteria_report_type.clone();
+		rule_report.combination_product =
+			FdaRuleValue::new(report.combination_product_report_indicator.clone());
+		rule_report.nullification_code = report.nullification_code.clone();
+	}
+
+	Ok(FdaRuleBatch {
+		batch_sender: header
+			.as_ref()
+			.and_then(|header| header.batch_sender_identifier.clone()),
+		batch_receiver: header
+			.as_ref()
+			.and_then(|header| header.batch_receiver_identifier.clone()),
+		reports: vec![rule_report],
+	})
+}
+
+async fn load_fda_rule_patient(
+	mm: &ModelManager,
+	patient: Option<&PatientInformation>,
+) -> Result<FdaRulePatient> {
+	let Some(patient) = patient else {
+		return Ok(FdaRulePatient::default());
+	};
+	let episodes: Vec<MedicalHistoryEpisode> = rows(
+		mm,
+		"SELECT * FROM medical_history_episodes WHERE patient_id = $1 ORDER BY sequence_number",
+		patient.id,
+	)
+	.await?;
+	let past_drugs: Vec<PastDrugHistory> = rows(
+		mm,
+		"SELECT * FROM past_drug_history WHERE patient_id = $1 ORDER BY sequence_number",
+		patient.id,
+	)
+	.await?;
+	let death: Option<PatientDeathInformation> = rows(
+		mm,
+		"SELECT * FROM patient_death_information WHERE patient_id = $1",
+		patient.id,
+	)
+	.await?
+	.into_iter()
+	.next();
+	let death_ids: Vec<Uuid> = death.iter().map(|d| d.id).collect();
+	let reported_causes: Vec<ReportedCauseOfDeath> = rows_any(
+		mm,
+		"SELECT * FROM reported_causes_of_death WHERE death_info_id = ANY($1) ORDER BY sequence_number",
+		&death_ids,
+	)
+	.await?;
+	let autopsy_causes: Vec<AutopsyCauseOfDeath> = rows_any(
+		mm,
+		"SELECT * FROM autopsy_causes_of_death WHERE death_info_id = ANY($1) ORDER BY sequence_number",
+		&death_ids,
+	)
+	.await?;
+	let parent: Option<ParentInformation> = rows(
+		mm,
+		"SELECT * FROM parent_information WHERE patient_id = $1",
+		patient.id,
+	)
+	.await?
+	.into_iter()
+	.next();
+	let parent = match parent {
+		Some(parent) => {
+			let history: Vec<ParentMedicalHistory> = rows(
+				mm,
+				"SELECT * FROM parent_medical_history WHERE parent_id = $1 ORDER BY sequence_number",
+				parent.id,
+			)
+			.await?;
+			let past_drugs: Vec<ParentPastDrugHistory> = rows(
+				mm,
+				"SELECT * FROM parent_past_drug_history WHERE parent_id = $1 ORDER BY sequence_number",
+				parent.id,
+			)
+			.await?;
+			Some(FdaRuleParent {
+				age: FdaRulePair::new(
+					text_of(parent.parent_age),
+					parent.parent_age_unit,
+				),
+				sex: FdaRuleValue::new(parent.sex),
+				medical_history: history
+					.into_iter()
+					.map(|episode| {
+						FdaRulePair::new(episode.meddra_version, episode.meddra_code)
+					})
+					.collect(),
+				past_drugs: past_drugs
+					.into_iter()
+					.map(|drug| FdaRulePastDrug {
+						other_data: drug.mpid.is_some()
+							|| drug.phpid.is_some()
+							|| drug.start_date.is_some()
+							|| drug.end_date.is_some(),
+						name: drug.drug_name,
+						indication: FdaRulePair::new(
+							drug.indication_meddra_version,
+							drug.indication_meddra_code,
+						),
+						reaction: FdaRulePair::new(
+							drug.reaction_meddra_version,
+							drug.reaction_meddra_code,
+						),
+					})
+					.collect(),
+			})
+		}
+		None => None,
+	};
+
+	Ok(FdaRulePatient {
+		name: FdaRuleValue::new(patient.patient_initials.clone()),
+		age: FdaRulePair::new(
+			text_of(patient.age_at_time_of_onset),
+			patient.age_unit.clone(),
+		),
+		gestation: FdaRulePair::new(
+			text_of(patient.gestation_period),
+			patient.gestation_period_unit.clone(),
+		),
+		medical_history_provided: patient.concomitant_therapy.is_some()
+			|| !episodes.is_empty()
+			|| has_text(patient.medical_history_text.as_deref()),
+		medical_history: episodes
+			.into_iter()
+			.map(|episode| {
+				FdaRulePair::new(episode.meddra_version, episode.meddra_code)
+			})
+			.collect(),
+		medical_history_text: patient.medical_history_text.clone(),
+		past_drugs: past_drugs
+			.into_iter()
+			.map(|drug| FdaRulePastDrug {
+				other_data: drug.mpid.is_some()
+					|| drug.phpid.is_some()
+					|| drug.start_date.is_some()
+					|| drug.end_date.is_some(),
+				name: drug.drug_name,
+				indication: FdaRulePair::new(
+					drug.indication_meddra_version,
+					drug.indication_meddra_code,
+				),
+				reaction: FdaRulePair::new(
+					drug.reaction_meddra_version,
+					drug.reaction_meddra_code,
+				),
+			})
+			.collect(),
+		death_date: FdaRuleValue::new(
+			death
+				.as_ref()
+				.and_then(|death| text_of(death.date_of_death.as_ref())),
+		),
+		reported_causes: reported_causes
+			.into_iter()
+			.map(|cause| FdaRuleCause {
+				meddra: FdaRulePair::new(cause.meddra_version, cause.meddra_code),
+				text: None,
+			})
+			.collect(),
+		autopsy: FdaRuleValue::new(
+			death
+				.as_ref()
+				.and_then(|death| text_of(death.autopsy_performed)),
+		),
+		autopsy_causes: autopsy_causes
+			.into_iter()
+			.map(|cause| FdaRuleCause {
+				meddra: FdaRulePair::new(cause.meddra_version, cause.meddra_code),
+				text: None,
+			})
+			.collect(),
+		parent,
+		race: patient
+			.race_code
+			.iter()
+			.map(|race| FdaRuleValue::new(Some(race.clone())))
+			.collect(),
+		ethnicity: FdaRuleValue::new(patient.ethnicity_code.clone()),
+	})
+}
+
+async fn load_fda_rule_drugs(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<FdaRuleDrug>> {
+	let drugs: Vec<DrugInformation> =
+		DrugInformationBmc::list_by_case(ctx, mm, case_id).await?;
+	let drug_ids: Vec<Uuid> = drugs.iter().map(|d| d.id).collect();
+	let substances: Vec<DrugActiveSubstance> = rows_any(
+		mm,
+		"SELECT * FROM drug_active_substances WHERE drug_id = ANY($1) ORDER BY sequence_number",
+		&drug_ids,
+	)
+	.await?;
+	let dosages: Vec<DosageInformation> = rows_any(
+		mm,
+		"SELECT * FROM dosage_information WHERE drug_id = ANY($1) ORDER BY sequence_number",
+		&drug_ids,
+	)
+	.await?;
+	let indications: Vec<DrugIndication> = rows_any(
+		mm,
+		"SELECT * FROM drug_indications WHERE drug_id = ANY($1) ORDER BY sequence_number",
+		&drug_ids,
+	)
+	.await?;
+	let assessments: Vec<DrugReactionAssessment> = rows_any(
+		mm,
+		"SELECT * FROM drug_reaction_assessments WHERE drug_id = ANY($1)",
+		&drug_ids,
+	)
+	.await?;
+	let assessment_ids: Vec<Uuid> = assessments.iter().map(|a| a.id).collect();
+	let relatedness: Vec<RelatednessAssessment> = rows_any(
+		mm,
+		"SELECT * FROM relatedness_assessments WHERE drug_reaction_assessment_id = ANY($1) ORDER BY sequence_number",
+		&assessment_ids,
+	)
+	.await?;
+	let characteristics: Vec<DrugDeviceCharacteristic> = rows_any(
+		mm,
+		"SELECT * FROM drug_device_characteristics WHERE drug_id = ANY($1) ORDER BY sequence_number",
+		&drug_ids,
+	)
+	.await?;
+
+	Ok(drugs
+		.iter()
+		.map(|drug| {
+			let drug_assessments: Vec<&DrugReactionAssessment> = assessments
+				.iter()
+				.filter(|assessment| assessment.drug_id == drug.id)
+				.collect();
+			let device: Vec<&DrugDeviceCharacteristic> = characteristics
+				.iter()
+				.filter(|characteristic| characteristic.drug_id == drug.id)
+				.collect();
+			let with_code = |code: &'static str| {
+				device
+					.iter()
+					.filter(move |c| c.code.as_deref() == Some(code))
+					.copied()
+			};
+			FdaRuleDrug {
+				characterization: Some(drug.drug_characterization.clone()),
+				substance_strengths: substances
+					.iter()
+					.filter(|substance| substance.drug_id == drug.id)
+					.map(|substance| {
+						FdaRulePair::new(
+							text_of(substance.strength_value),
+							substance.strength_unit.clone(),
+						)
+					})
+					.collect(),
+				dosages: dosages
+					.iter()
+					.filter(|dosage| dosage.drug_id == drug.id)
+					.map(|dosage| FdaRuleDosage {
+						dose: FdaRulePair::new(
+							text_of(dosage.dose_value),
+							dosage.dose_unit.clone(),
+						),
+						frequency: FdaRulePair::new(
+							text_of(dosage.frequency_value),
+							dosage.frequency_unit.clone(),
+						),
+						duration: FdaRulePair::new(
+							text_of(dosage.duration_value),
+							dosage.duration_unit.clone(),
+						),
+					})
+					.collect(),
+				indications: indications
+					.iter()
+					.filter(|indication| indication.drug_id == drug.id)
+					.map(|indication| {
+						FdaRulePair::new(
+							indication.indication_meddra_version.clone(),
+							indication.indication_meddra_code.clone(),
+						)
+					})
+					.collect(),
+				assessments: relatedness
+					.iter()
+					.filter(|related| {
+						drug_assessments
+							.iter()
+							.any(|a| a.id == related.drug_reaction_assessment_id)
+					})
+					.map(|related| FdaRuleAssessment {
+						source: related.source_of_assessment.clone(),
+						method: related.method_of_assessment.clone(),
+						result: related.result_of_assessment.clone(),
+					})
+					.collect(),
+				start_intervals: drug_assessments
+					.iter()
+					.map(|assessment| {
+						FdaRulePair::new(
+							text_of(assessment.time_interval_value),
+							assessment.time_interval_unit.clone(),
+						)
+					})
+					.collect(),
+				additional_info: drug
+					.fda_additional_info_coded
+					.iter()
+					.map(|info| FdaRuleValue::new(Some(info.clone())))
+					.collect(),
+				devices: if device.is_empty() {
+					Vec::new()
+				} else {
+					vec![FdaRuleDevice {
+						malfunction: FdaRuleValue::new(
+							with_code("C54026").find_map(|c| c.value_value.clone()),
+						),
+						problem_codes: with_code("C54451")
+							.filter_map(|c| c.value_code.clone())
+							.collect(),
+						remedial_actions: with_code("C54594").count(),
+						..FdaRuleDevice::default()
+					}]
+				},
+				..FdaRuleDrug::default()
+			}
+		})
+		.collect())
+}
+
 pub async fn validate_case(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	case_id: Uuid,
 ) -> Result<CaseValidationReport> {
 	let ich_report = crate::xml::ich::validation::validate_case(ctx, mm, case_id).await?;
+	let rules = load_rule_set(ctx, mm).await?;
 
 	let report = get_safety_report_optional(mm, case_id).await?;
 	let patient = get_patient_optional(mm, case_id).await?;
 	let primary_sources = list_primary_sources(mm, case_id).await?;
 	let reactions: Vec<Reaction> =
 		crate::model::reaction::ReactionBmc::list_by_case(ctx, mm, case_id).await?;
+	let codes = E2bCodeListBmc::catalog(ctx, mm).await?;
 	let mut issues: Vec<ValidationIssue> = ich_report.issues;
 
 	if let Some(report) = report.as_ref() {
@@ -98,6 +652,13 @@ pub async fn validate_case(
 			RuleFacts::default(),
 			RuleFacts::default(),
 		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			LOCAL_CRITERIA_REPORT_TYPE,
+			"safetyReportIdentification.localCriteriaReportType",
+			report.local_criteria_report_type.as_deref(),
+		);
 	}
 
 	primary_sources
@@ -153,6 +714,20 @@ pub async fn validate_case(
 			},
 			RuleFacts::default(),
 		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			FDA_RACE,
+			"patientInformation.raceCode",
+			patient.race_code.as_deref(),
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			FDA_ETHNICITY,
+			"patientInformation.ethnicityCode",
+			patient.ethnicity_code.as_deref(),
+		);
 	}
 
 	if should_case_validator_require_required_intervention() {
@@ -176,5 +751,25 @@ pub async fn validate_case(
 		});
 	}
 
-	Ok(build_report(ValidationProfile::Fda, case_id, issues))
+	let batch = load_fda_rule_batch(
+		ctx,
+		mm,
+		case_id,
+		report.as_ref(),
+		patient.as_ref(),
+		&primary_sources,
+		&reactions,
+	)
+	.await?;
+	issues.extend(check_fda_rules(&batch, FdaRuleScope::Case).into_iter().map(
+		|hit| ValidationIssue {
+			code: hit.rule.code.to_string(),
+			message: hit.message(),
+			path: hit.path,
+			section: hit.rule.section().to_string(),
+			blocking: hit.rule.blocking(),
+		},
+	));
+
+	Ok(build_report(ValidationProfile::Fda, case_id, rules.apply(issues)))
 }
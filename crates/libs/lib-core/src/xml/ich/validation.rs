@@ -1,18 +1,23 @@
 use crate::ctx::Ctx;
 use crate::model::case::{Case, CaseBmc};
-use crate::model::drug::DrugInformation;
+use crate::model::drug::{DosageInformation, DrugInformation};
 use crate::model::message_header::MessageHeader;
 use crate::model::narrative::NarrativeInformation;
 use crate::model::patient::PatientInformation;
 use crate::model::reaction::Reaction;
 use crate::model::safety_report::{PrimarySource, SafetyReportIdentification};
+use crate::model::terminology::E2bCodeListBmc;
+use crate::model::test_result::{TestResult, TestResultBmc};
 use crate::model::{ModelManager, Result};
 use crate::xml::validate::{
-	build_report, push_issue_by_code, CaseValidationReport,
+	build_report, load_rule_set, push_issue_by_code, CaseValidationReport,
 	ValidationIssue, ValidationProfile, has_patient_initials,
 	has_any_primary_source_content,
 	should_require_patient_initials, should_require_case_narrative,
-	push_issue_if_rule_invalid, RuleFacts,
+	push_issue_if_rule_invalid, RuleFacts, push_issue_if_code_not_in_list,
+	AGE_GROUP, DRUG_ACTION, DRUG_CHARACTERIZATION, NULLIFICATION_CODE,
+	QUALIFICATION, REACTION_OUTCOME, RECHALLENGE, REPORT_TYPE, SEX,
+	push_issue_if_unit_invalid, UnitKind,
 };
 use sqlx::types::Uuid;
 
@@ -74,12 +79,26 @@ async fn list_primary_sources(
 		.map_err(Into::into)
 }
 
+async fn list_dosages(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<DosageInformation>> {
+	let sql = "SELECT di.* FROM dosage_information di \
+		JOIN drug_information d ON d.id = di.drug_id \
+		WHERE d.case_id = $1 ORDER BY d.sequence_number, di.sequence_number";
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, DosageInformation>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
 pub async fn validate_case(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	case_id: Uuid,
 ) -> Result<CaseValidationReport> {
 	let _case: Case = CaseBmc::get(ctx, mm, case_id).await?;
+	let rules = load_rule_set(ctx, mm).await?;
 
 	let report = get_safety_report_optional(mm, case_id).await?;
 	let header = get_message_header_optional(mm, case_id).await?;
@@ -91,6 +110,10 @@ pub async fn validate_case(
 	let drugs: Vec<DrugInformation> =
 		crate::model::drug::DrugInformationBmc::list_by_case(ctx, mm, case_id)
 			.await?;
+	let dosages = list_dosages(mm, case_id).await?;
+	let tests: Vec<TestResult> =
+		TestResultBmc::list_by_case(ctx, mm, case_id).await?;
+	let codes = E2bCodeListBmc::catalog(ctx, mm).await?;
 
 	let mut issues: Vec<ValidationIssue> = Vec::new();
 
@@ -111,6 +134,20 @@ pub async fn validate_case(
 			None,
 			RuleFacts::default(),
 		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			REPORT_TYPE,
+			"safetyReportIdentification.reportType",
+			Some(report.report_type.as_str()),
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			NULLIFICATION_CODE,
+			"safetyReportIdentification.nullificationCode",
+			report.nullification_code.as_deref(),
+		);
 	}
 
 	primary_sources
@@ -128,9 +165,42 @@ pub async fn validate_case(
 				None,
 				RuleFacts::default(),
 			);
+			let _ = push_issue_if_code_not_in_list(
+				&mut issues,
+				&codes,
+				QUALIFICATION,
+				format!("primarySources.{idx}.qualification"),
+				source.qualification.as_deref(),
+			);
 		});
 
 	if let Some(patient) = patient.as_ref() {
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			SEX,
+			"patientInformation.sex",
+			patient.sex.as_deref(),
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			AGE_GROUP,
+			"patientInformation.ageGroup",
+			patient.age_group.as_deref(),
+		);
+		let _ = push_issue_if_unit_invalid(
+			&mut issues,
+			"patientInformation.ageUnit",
+			patient.age_unit.as_deref(),
+			UnitKind::Time,
+		);
+		let _ = push_issue_if_unit_invalid(
+			&mut issues,
+			"patientInformation.gestationPeriodUnit",
+			patient.gestation_period_unit.as_deref(),
+			UnitKind::Time,
+		);
 		if should_require_patient_initials(patient) && !has_patient_initials(patient)
 		{
 			push_issue_by_code(
@@ -158,6 +228,28 @@ pub async fn validate_case(
 			None,
 			RuleFacts::default(),
 		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			REACTION_OUTCOME,
+			format!("reactions.{idx}.reactionOutcome"),
+			reaction.outcome.as_deref(),
+		);
+		let _ = push_issue_if_unit_invalid(
+			&mut issues,
+			format!("reactions.{idx}.durationUnit"),
+			reaction.duration_unit.as_deref(),
+			UnitKind::Time,
+		);
+	});
+
+	tests.iter().enumerate().for_each(|(idx, test)| {
+		let _ = push_issue_if_unit_invalid(
+			&mut issues,
+			format!("testResults.{idx}.testResultUnit"),
+			test.test_result_unit.as_deref(),
+			UnitKind::Any,
+		);
 	});
 
 	drugs.iter().enumerate().for_each(|(idx, drug)| {
@@ -177,6 +269,52 @@ pub async fn validate_case(
 			None,
 			RuleFacts::default(),
 		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			DRUG_CHARACTERIZATION,
+			format!("drugs.{idx}.drugCharacterization"),
+			Some(drug.drug_characterization.as_str()),
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			DRUG_ACTION,
+			format!("drugs.{idx}.actionTaken"),
+			drug.action_taken.as_deref(),
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			RECHALLENGE,
+			format!("drugs.{idx}.rechallenge"),
+			drug.rechallenge.as_deref(),
+		);
+		dosages
+			.iter()
+			.filter(|dosage| dosage.drug_id == drug.id)
+			.enumerate()
+			.for_each(|(dose_idx, dosage)| {
+				let path = format!("drugs.{idx}.dosages.{dose_idx}");
+				let _ = push_issue_if_unit_invalid(
+					&mut issues,
+					format!("{path}.doseUnit"),
+					dosage.dose_unit.as_deref(),
+					UnitKind::Any,
+				);
+				let _ = push_issue_if_unit_invalid(
+					&mut issues,
+					format!("{path}.frequencyUnit"),
+					dosage.frequency_unit.as_deref(),
+					UnitKind::Time,
+				);
+				let _ = push_issue_if_unit_invalid(
+					&mut issues,
+					format!("{path}.durationUnit"),
+					dosage.duration_unit.as_deref(),
+					UnitKind::Time,
+				);
+			});
 	});
 
 	if let Some(narrative) = narrative.as_ref() {
@@ -193,5 +331,5 @@ pub async fn validate_case(
 		}
 	}
 
-	Ok(build_report(ValidationProfile::Ich, case_id, issues))
+	Ok(build_report(ValidationProfile::Ich, case_id, rules.apply(issues)))
 }
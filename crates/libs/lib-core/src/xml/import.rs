@@ -24,10 +24,15 @@ use crate::model::drug_recurrence::{
 use crate::model::message_header::{
 	MessageHeaderBmc, MessageHeaderForCreate, MessageHeaderForUpdate,
 };
+use crate::model::jp_element::{
+	CaseJpElementBmc, CaseJpElementForCreate, CaseJpElementForUpdate,
+};
 use crate::model::narrative::{
-	NarrativeInformationBmc, NarrativeInformationForCreate,
-	NarrativeInformationForUpdate,
+	CaseSummaryInformationBmc, CaseSummaryInformationForCreate,
+	CaseSummaryInformationForUpdate, NarrativeInformationBmc,
+	NarrativeInformationForCreate, NarrativeInformationForUpdate,
 };
+use crate::model::partial_date::PartialDate;
 use crate::model::parent_history::{
 	ParentMedicalHistoryBmc, ParentMedicalHistoryForCreate,
 	ParentMedicalHistoryForUpdate, ParentPastDrugHistoryBmc,
@@ -51,6 +56,7 @@ use crate::model::receiver::{
 	ReceiverInformationBmc, ReceiverInformationForCreate,
 	ReceiverInformationForUpdate,
 };
+use crate::model::reporting_deadline::CaseReportingDeadlineBmc;
 use crate::model::safety_report::{
 	DocumentsHeldBySenderBmc, DocumentsHeldBySenderForCreate,
 	DocumentsHeldBySenderForUpdate, LiteratureReferenceBmc,
@@ -63,12 +69,27 @@ use crate::model::safety_report::{
 	StudyRegistrationNumberForUpdate,
 };
 use crate::model::store::set_full_context_dbx;
+use crate::model::terminology::{CodeListCatalog, E2bCodeListBmc};
 use crate::model::test_result::{
 	TestResultBmc, TestResultForCreate, TestResultForUpdate,
 };
+use crate::model::ucum::normalize_ucum;
 use crate::model::{self, ModelManager};
+use crate::xml::ack::build_import_ack_xml;
+use crate::xml::batch::{split_batch_reports, BatchReportSlice};
 use crate::xml::error::Error;
-use crate::xml::types::XmlImportResult;
+use crate::xml::mapping::mfds::c_safety_report::CMfdsSafetyReportPaths;
+use crate::xml::mapping::mfds::d_patient::DMfdsPatientPaths;
+use crate::xml::mapping::mfds::g_drug::GMfdsDrugPaths;
+use crate::xml::types::{
+	XmlAckGenerationRequest, XmlBatchImportResult, XmlImportReportError,
+	XmlImportReportResult, XmlImportResult, XmlValidationError, XmlValidationReport,
+};
+use crate::xml::validate::{
+	AGE_GROUP, DRUG_ACTION, LOCAL_CRITERIA_REPORT_TYPE, NULLIFICATION_CODE,
+	PRIMARY_SOURCE_REGULATORY, QUALIFICATION, RECHALLENGE, REPORT_TYPE, SENDER_TYPE,
+	ValidationProfile,
+};
 use crate::xml::xml_validation::{should_skip_xml_validation, validate_e2b_xml};
 use crate::xml::{parse_e2b_xml, Result};
 use libxml::parser::Parser;
@@ -89,6 +110,8 @@ struct DrugSubstanceImport {
 	substance_termid_version: Option<String>,
 	strength_value: Option<Decimal>,
 	strength_unit: Option<String>,
+	kr_ingredient_code_version: Option<String>,
+	kr_ingredient_code: Option<String>,
 }
 
 #[derive(Debug)]
@@ -96,8 +119,8 @@ struct DrugDosageImport {
 	dosage_text: Option<String>,
 	frequency_value: Option<Decimal>,
 	frequency_unit: Option<String>,
-	start_date: Option<Date>,
-	end_date: Option<Date>,
+	start_date: Option<PartialDate>,
+	end_date: Option<PartialDate>,
 	duration_value: Option<Decimal>,
 	duration_unit: Option<String>,
 	dose_value: Option<Decimal>,
@@ -153,6 +176,8 @@ struct DrugImport {
 	parent_route_termid_version: Option<String>,
 	parent_dosage_text: Option<String>,
 	fda_additional_info_coded: Option<String>,
+	kr_product_code_version: Option<String>,
+	kr_product_code: Option<String>,
 	substances: Vec<DrugSubstanceImport>,
 	dosages: Vec<DrugDosageImport>,
 	indications: Vec<DrugIndicationImport>,
@@ -186,6 +211,9 @@ struct RelatednessImport {
 	source_of_assessment: Option<String>,
 	method_of_assessment: Option<String>,
 	result_of_assessment: Option<String>,
+	kr_method_of_assessment: Option<String>,
+	kr_result_who_umc: Option<String>,
+	kr_result_korean: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -221,14 +249,208 @@ pub struct XmlImportRequest {
 	pub filename: Option<String>,
 }
 
+/// Imports a single-report E2B(R3) document as one case.
+///
+/// All inserts run in one transaction, so a failing section leaves no
+/// partially imported case behind.
 pub async fn import_e2b_xml(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	req: XmlImportRequest,
+) -> Result<XmlImportResult> {
+	import_report_in_txn(ctx, mm, &req.xml).await
+}
+
+/// Imports every ICSR message of an MCCI_IN200100UV01 batch.
+///
+/// Each message is validated and imported in its own transaction; a failing
+/// message is reported with its batch XPath and does not roll back the others.
+///
+/// Committing per report is intentional: `Dbx` has no savepoints (nested
+/// `begin_txn` only counts, and a failed statement rolls back the whole
+/// transaction), so one outer transaction could not keep the good reports
+/// once a bad one hits a constraint. A batch is therefore not atomic.
+pub async fn import_e2b_xml_batch(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	req: XmlImportRequest,
+) -> Result<XmlBatchImportResult> {
+	let slices = split_batch_reports(&req.xml)?;
+	Ok(import_batch_slices(ctx, mm, &req.xml, slices).await)
+}
+
+/// Imports a received batch like [`import_e2b_xml_batch`] and attaches the
+/// MCCI_IN200101UV01 acknowledgment to send back, built from the same
+/// outcomes: CA/CR per ICSR and AA, AE or AR for the batch.
+///
+/// A file that cannot be read as a batch earns AR from its validation report
+/// and imports nothing; otherwise AR means every ICSR failed, so nothing
+/// rejected is ever left imported.
+pub async fn import_e2b_xml_batch_with_ack(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	req: XmlImportRequest,
+) -> Result<XmlBatchImportResult> {
+	let (mut result, validation) = match split_batch_reports(&req.xml) {
+		Ok(slices) => (import_batch_slices(ctx, mm, &req.xml, slices).await, None),
+		Err(err) => (
+			XmlBatchImportResult::default(),
+			Some(unreadable_batch_report(err)),
+		),
+	};
+	let ack = build_import_ack_xml(
+		ctx,
+		mm,
+		&XmlAckGenerationRequest {
+			import_result: result.clone(),
+			validation,
+		},
+	)
+	.await?;
+	result.ack_xml = Some(ack);
+	Ok(result)
+}
+
+async fn import_batch_slices(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	xml: &[u8],
+	slices: Vec<BatchReportSlice>,
+) -> XmlBatchImportResult {
+	let batch_number = extract_message_header(xml)
+		.ok()
+		.and_then(|h| h.batch_number);
+
+	let mut reports = Vec::with_capacity(slices.len());
+	for slice in slices {
+		let outcome = import_report_in_txn(ctx, mm, &slice.xml).await;
+		let mut report = XmlImportReportResult {
+			index: slice.index,
+			message_number: slice.message_number,
+			safety_report_id: slice.safety_report_id,
+			case_id: None,
+			case_version: None,
+			parsed_json_id: None,
+			error: None,
+		};
+		match outcome {
+			Ok(result) => {
+				report.case_id = result.case_id;
+				report.case_version = result.case_version;
+				report.parsed_json_id = result.parsed_json_id;
+			}
+			Err(err) => {
+				tracing::warn!(
+					index = slice.index,
+					xpath = %slice.xpath,
+					error = %err,
+					"batch report import failed"
+				);
+				report.error = Some(import_report_error(err, slice.xpath));
+			}
+		}
+		reports.push(report);
+	}
+
+	let failed = reports.iter().filter(|r| r.error.is_some()).count();
+	XmlBatchImportResult {
+		batch_number,
+		total: reports.len(),
+		imported: reports.len() - failed,
+		failed,
+		reports,
+		ack_xml: None,
+	}
+}
+
+fn unreadable_batch_report(err: Error) -> XmlValidationReport {
+	let error = match err {
+		Error::InvalidXml {
+			message,
+			line,
+			column,
+		} => XmlValidationError {
+			message,
+			line,
+			column,
+			xpath: None,
+		},
+		other => XmlValidationError {
+			message: other.to_string(),
+			line: None,
+			column: None,
+			xpath: None,
+		},
+	};
+	XmlValidationReport {
+		ok: false,
+		errors: vec![error],
+		warnings: Vec::new(),
+		root_element: None,
+	}
+}
+
+async fn import_report_in_txn(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	xml: &[u8],
 ) -> Result<XmlImportResult> {
 	let mm = mm.new_with_txn()?;
+	let dbx = mm.dbx();
+	dbx.begin_txn().await.map_err(model::Error::from)?;
+	match import_report(ctx, &mm, xml).await {
+		Ok(result) => {
+			dbx.commit_txn().await.map_err(model::Error::from)?;
+			Ok(result)
+		}
+		Err(err) => {
+			let _ = dbx.rollback_txn().await;
+			Err(err)
+		}
+	}
+}
+
+fn import_report_error(err: Error, xpath: String) -> XmlImportReportError {
+	match err {
+		Error::XsdValidationFailed { errors } => XmlImportReportError {
+			message: "XML validation failed".to_string(),
+			xpath,
+			errors,
+		},
+		Error::InvalidXml {
+			message,
+			line,
+			column,
+		} => XmlImportReportError {
+			message: message.clone(),
+			xpath,
+			errors: vec![XmlValidationError {
+				message,
+				line,
+				column,
+				xpath: None,
+			}],
+		},
+		Error::Model(err) => XmlImportReportError {
+			message: err.to_string(),
+			xpath,
+			errors: Vec::new(),
+		},
+		other => XmlImportReportError {
+			message: other.to_string(),
+			xpath,
+			errors: Vec::new(),
+		},
+	}
+}
+
+async fn import_report(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	xml: &[u8],
+) -> Result<XmlImportResult> {
 	if !should_skip_xml_validation() {
-		let report = validate_e2b_xml(&req.xml, None)?;
+		let report = validate_e2b_xml(xml, None)?;
 		if !report.ok {
 			return Err(Error::XsdValidationFailed {
 				errors: report.errors,
@@ -236,13 +458,14 @@ pub async fn import_e2b_xml(
 		}
 	}
 
-	let parsed = parse_e2b_xml(&req.xml)?;
-	let safety_report_id_raw = extract_safety_report_id(&req.xml)?;
+	let parsed = parse_e2b_xml(xml)?;
+	let safety_report_id_raw = extract_safety_report_id(xml)?;
 	let safety_report_id =
 		clamp_str(Some(safety_report_id_raw), 100, "cases.safety_report_id")
 			.unwrap_or_else(|| "UNKNOWN".to_string());
-	let header_extract = extract_message_header(&req.xml).ok();
+	let header_extract = extract_message_header(xml).ok();
 	let inferred_validation_profile = infer_validation_profile(header_extract.as_ref());
+	let codes = E2bCodeListBmc::catalog(ctx, mm).await?;
 
 	let next_version = {
 		let dbx = mm.dbx();
@@ -269,7 +492,7 @@ pub async fn import_e2b_xml(
 
 	let case_id = CaseBmc::create(
 		ctx,
-		&mm,
+		mm,
 		CaseForCreate {
 			organization_id: ctx.organization_id(),
 			safety_report_id: safety_report_id.clone(),
@@ -308,13 +531,13 @@ pub async fn import_e2b_xml(
 		if let (Some(message_sender), Some(message_receiver), Some(message_date)) =
 			(msg_sender, msg_receiver, msg_date)
 		{
-				let has_header = MessageHeaderBmc::get_by_case(ctx, &mm, case_id)
+				let has_header = MessageHeaderBmc::get_by_case(ctx, mm, case_id)
 					.await
 					.is_ok();
 				if !has_header {
 					MessageHeaderBmc::create(
 						ctx,
-						&mm,
+						mm,
 						MessageHeaderForCreate {
 						case_id,
 						message_number,
@@ -327,7 +550,7 @@ pub async fn import_e2b_xml(
 				}
 				MessageHeaderBmc::update_by_case(
 					ctx,
-					&mm,
+					mm,
 					case_id,
 				MessageHeaderForUpdate {
 					batch_number: header.batch_number.clone(),
@@ -350,40 +573,49 @@ pub async fn import_e2b_xml(
 		}
 	}
 
-	import_safety_report(ctx, &mm, &req.xml, case_id, header_extract.as_ref())
-		.await?;
-	import_sender_information(ctx, &mm, &req.xml, case_id, header_extract.as_ref())
+	import_safety_report(ctx, mm, xml, &codes, case_id, header_extract.as_ref())
 		.await?;
-	import_primary_sources(ctx, &mm, &req.xml, case_id).await?;
-	import_case_identifiers(ctx, &mm, &req.xml, case_id).await?;
-	import_documents_held_by_sender(ctx, &mm, &req.xml, case_id).await?;
-	import_literature_references(ctx, &mm, &req.xml, case_id).await?;
-	import_study_information(ctx, &mm, &req.xml, case_id).await?;
-	import_receiver_information(ctx, &mm, &req.xml, case_id).await?;
-	let patient_id = import_patient_information(ctx, &mm, &req.xml, case_id).await?;
+	import_sender_information(
+		ctx,
+		mm,
+		xml,
+		&codes,
+		case_id,
+		header_extract.as_ref(),
+	)
+	.await?;
+	import_primary_sources(ctx, mm, xml, &codes, case_id).await?;
+	import_case_identifiers(ctx, mm, xml, case_id).await?;
+	import_documents_held_by_sender(ctx, mm, xml, case_id).await?;
+	import_literature_references(ctx, mm, xml, case_id).await?;
+	import_study_information(ctx, mm, xml, case_id).await?;
+	import_receiver_information(ctx, mm, xml, case_id).await?;
+	let patient_id =
+		import_patient_information(ctx, mm, xml, &codes, case_id).await?;
 	if let Some(patient_id) = patient_id {
-		import_patient_identifiers(ctx, &mm, &req.xml, patient_id).await?;
-		import_medical_history(ctx, &mm, &req.xml, patient_id).await?;
-		import_past_drug_history(ctx, &mm, &req.xml, patient_id).await?;
-		import_patient_death(ctx, &mm, &req.xml, patient_id).await?;
-		import_parent_information(ctx, &mm, &req.xml, patient_id).await?;
+		import_patient_identifiers(ctx, mm, xml, patient_id).await?;
+		import_medical_history(ctx, mm, xml, patient_id).await?;
+		import_past_drug_history(ctx, mm, xml, patient_id).await?;
+		import_patient_death(ctx, mm, xml, patient_id).await?;
+		import_parent_information(ctx, mm, xml, patient_id).await?;
 	}
-	import_narrative(ctx, &mm, &req.xml, case_id).await?;
+	import_narrative(ctx, mm, xml, case_id).await?;
+	import_jp_elements(ctx, mm, xml, case_id).await?;
 	let snapshot = json!({
 		"parsed": parsed.json,
-		"raw_xml": String::from_utf8_lossy(&req.xml),
+		"raw_xml": String::from_utf8_lossy(xml),
 	});
 
-	let reaction_map = import_reactions(ctx, &mm, &req.xml, case_id).await?;
-	import_test_results(ctx, &mm, &req.xml, case_id).await?;
-	let drug_map = import_drugs(ctx, &mm, &req.xml, case_id).await?;
-	import_drug_recurrences(ctx, &mm, &req.xml, &drug_map).await?;
-	import_drug_reaction_assessments(ctx, &mm, &req.xml, &drug_map, &reaction_map)
+	let reaction_map = import_reactions(ctx, mm, xml, case_id).await?;
+	import_test_results(ctx, mm, xml, case_id).await?;
+	let drug_map = import_drugs(ctx, mm, xml, &codes, case_id).await?;
+	import_drug_recurrences(ctx, mm, xml, &drug_map).await?;
+	import_drug_reaction_assessments(ctx, mm, xml, &drug_map, &reaction_map)
 		.await?;
 
 	let version_id = match CaseVersionBmc::create(
 		ctx,
-		&mm,
+		mm,
 		CaseVersionForCreate {
 			case_id,
 			version: next_version,
@@ -401,10 +633,10 @@ pub async fn import_e2b_xml(
 	// section inserts/updates (DB triggers may mark sections dirty during import).
 	CaseBmc::update(
 		ctx,
-		&mm,
+		mm,
 		case_id,
 		CaseForUpdate {
-			raw_xml: Some(req.xml.to_vec()),
+			raw_xml: Some(xml.to_vec()),
 			safety_report_id: None,
 			dg_prd_key: None,
 			status: None,
@@ -421,6 +653,8 @@ pub async fn import_e2b_xml(
 	)
 	.await?;
 
+	CaseReportingDeadlineBmc::refresh(ctx, mm, case_id).await?;
+
 	Ok(XmlImportResult {
 		case_id: Some(case_id.to_string()),
 		case_version: Some(i64::from(next_version)),
@@ -495,6 +729,7 @@ async fn import_safety_report(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	case_id: Uuid,
 	header: Option<&MessageHeaderExtract>,
 ) -> Result<()> {
@@ -519,7 +754,7 @@ async fn import_safety_report(
 					.and_then(|h| h.message_receiver.clone()),
 			})
 	} else {
-		parse_safety_report_identification(xml, header)?
+		parse_safety_report_identification(xml, codes, header)?
 	}) else {
 		return Ok(());
 	};
@@ -567,10 +802,11 @@ async fn import_sender_information(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	case_id: Uuid,
 	header: Option<&MessageHeaderExtract>,
 ) -> Result<()> {
-	let Some(sender) = parse_sender_information(xml, header)? else {
+	let Some(sender) = parse_sender_information(xml, codes, header)? else {
 		return Ok(());
 	};
 
@@ -605,6 +841,7 @@ async fn import_sender_information(
 		sender_id,
 		SenderInformationForUpdate {
 			sender_type: Some(sender.sender_type),
+			kr_health_professional_type: sender.kr_health_professional_type,
 			organization_name: Some(sender.organization_name),
 			department: sender.department,
 			street_address: sender.street_address,
@@ -630,9 +867,10 @@ async fn import_primary_sources(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	case_id: Uuid,
 ) -> Result<()> {
-	let Some(primary) = parse_primary_source(xml)? else {
+	let Some(primary) = parse_primary_source(xml, codes)? else {
 		return Ok(());
 	};
 
@@ -680,6 +918,7 @@ async fn import_primary_sources(
 			country_code: primary.country_code,
 			email: primary.email,
 			qualification: primary.qualification,
+			kr_other_health_professional: primary.kr_other_health_professional,
 			primary_source_regulatory: primary.primary_source_regulatory,
 		},
 	)
@@ -932,6 +1171,7 @@ async fn import_study_information(
 			study_name: study.study_name,
 			sponsor_study_number: study.sponsor_study_number,
 			study_type_reaction: study.study_type_reaction,
+			kr_other_study_type: study.kr_other_study_type,
 		},
 	)
 	.await;
@@ -1197,6 +1437,8 @@ async fn import_past_drug_history(
 					mpid_version: entry.mpid_version,
 					phpid: entry.phpid,
 					phpid_version: entry.phpid_version,
+					kr_product_code_version: entry.kr_product_code_version,
+					kr_product_code: entry.kr_product_code,
 					start_date: entry.start_date,
 					end_date: entry.end_date,
 					indication_meddra_version: entry.indication_meddra_version,
@@ -1218,6 +1460,8 @@ async fn import_past_drug_history(
 					mpid_version: entry.mpid_version,
 					phpid: entry.phpid,
 					phpid_version: entry.phpid_version,
+					kr_product_code_version: entry.kr_product_code_version,
+					kr_product_code: entry.kr_product_code,
 					start_date: entry.start_date,
 					end_date: entry.end_date,
 					indication_meddra_version: entry.indication_meddra_version,
@@ -1515,6 +1759,8 @@ async fn import_parent_information(
 					mpid_version: entry.mpid_version,
 					phpid: entry.phpid,
 					phpid_version: entry.phpid_version,
+					kr_product_code_version: entry.kr_product_code_version,
+					kr_product_code: entry.kr_product_code,
 					start_date: entry.start_date,
 					end_date: entry.end_date,
 					indication_meddra_version: entry.indication_meddra_version,
@@ -1546,6 +1792,8 @@ async fn import_parent_information(
 					mpid_version: entry.mpid_version,
 					phpid: entry.phpid,
 					phpid_version: entry.phpid_version,
+					kr_product_code_version: entry.kr_product_code_version,
+					kr_product_code: entry.kr_product_code,
 					start_date: entry.start_date,
 					end_date: entry.end_date,
 					indication_meddra_version: entry.indication_meddra_version,
@@ -1664,6 +1912,7 @@ async fn import_patient_information(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	case_id: Uuid,
 ) -> Result<Option<Uuid>> {
 	let use_v2 = std::env::var("XML_V2_IMPORT_D").unwrap_or_default() == "1";
@@ -1690,7 +1939,7 @@ async fn import_patient_information(
 			},
 		)
 	} else {
-		parse_patient_information(xml)?
+		parse_patient_information(xml, codes)?
 	}) else {
 		return Ok(None);
 	};
@@ -1814,6 +2063,118 @@ async fn import_narrative(
 	Ok(())
 }
 
+/// H.5.r case summaries (any profile) and PMDA J.2 - J.20 elements.
+async fn import_jp_elements(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	xml: &[u8],
+	case_id: Uuid,
+) -> Result<()> {
+	let jp = crate::xml::pmda::import::parse_jp_elements(xml)?;
+
+	if let Ok(narrative) = NarrativeInformationBmc::get_by_case(ctx, mm, case_id).await
+	{
+		for (idx, entry) in jp.summaries.into_iter().enumerate() {
+			let seq = (idx + 1) as i32;
+			let existing: Option<Uuid> = mm
+				.dbx()
+				.fetch_optional(
+					sqlx::query_as::<_, (Uuid,)>(
+						"SELECT id FROM case_summary_information WHERE narrative_id = $1 AND sequence_number = $2 LIMIT 1",
+					)
+					.bind(narrative.id)
+					.bind(seq),
+				)
+				.await
+				.map_err(model::Error::from)?
+				.map(|v| v.0);
+			let id = match existing {
+				Some(id) => id,
+				None => {
+					CaseSummaryInformationBmc::create(
+						ctx,
+						mm,
+						CaseSummaryInformationForCreate {
+							narrative_id: narrative.id,
+							sequence_number: seq,
+							summary_text: entry.summary_text.clone(),
+						},
+					)
+					.await?
+				}
+			};
+			let _ = CaseSummaryInformationBmc::update(
+				ctx,
+				mm,
+				id,
+				CaseSummaryInformationForUpdate {
+					summary_type: clamp_str(
+						entry.summary_type,
+						2,
+						"case_summary_information.summary_type",
+					),
+					language_code: clamp_str(
+						entry.language_code,
+						3,
+						"case_summary_information.language_code",
+					),
+					summary_text: entry.summary_text,
+				},
+			)
+			.await;
+		}
+	}
+
+	let mut sequences: HashMap<String, i32> = HashMap::new();
+	for entry in jp.elements {
+		let Some(element_id) =
+			clamp_str(Some(entry.element_id), 10, "case_jp_elements.element_id")
+		else {
+			continue;
+		};
+		let seq = sequences
+			.entry(element_id.clone())
+			.and_modify(|seq| *seq += 1)
+			.or_insert(1);
+		let existing: Option<Uuid> = mm
+			.dbx()
+			.fetch_optional(
+				sqlx::query_as::<_, (Uuid,)>(
+					"SELECT id FROM case_jp_elements WHERE case_id = $1 AND element_id = $2 AND sequence_number = $3 LIMIT 1",
+				)
+				.bind(case_id)
+				.bind(&element_id)
+				.bind(*seq),
+			)
+			.await
+			.map_err(model::Error::from)?
+			.map(|v| v.0);
+		if let Some(id) = existing {
+			let _ = CaseJpElementBmc::update(
+				ctx,
+				mm,
+				id,
+				CaseJpElementForUpdate { value: entry.value },
+			)
+			.await;
+		} else {
+			let _ = CaseJpElementBmc::create(
+				ctx,
+				mm,
+				CaseJpElementForCreate {
+					case_id,
+					element_id,
+					sequence_number: *seq,
+					value: entry.value,
+				},
+			)
+			.await?;
+		}
+	}
+
+	Ok(())
+}
+
 struct SafetyReportImport {
 	transmission_date: Date,
 	report_type: String,
@@ -1830,6 +2191,7 @@ struct SafetyReportImport {
 
 struct SenderImport {
 	sender_type: String,
+	kr_health_professional_type: Option<String>,
 	organization_name: String,
 	department: Option<String>,
 	street_address: Option<String>,
@@ -1861,6 +2223,7 @@ struct PrimarySourceImport {
 	country_code: Option<String>,
 	email: Option<String>,
 	qualification: Option<String>,
+	kr_other_health_professional: Option<String>,
 	primary_source_regulatory: Option<String>,
 }
 
@@ -1868,7 +2231,7 @@ struct PatientImport {
 	patient_initials: Option<String>,
 	patient_given_name: Option<String>,
 	patient_family_name: Option<String>,
-	birth_date: Option<Date>,
+	birth_date: Option<PartialDate>,
 	sex: Option<String>,
 	age_at_time_of_onset: Option<Decimal>,
 	age_unit: Option<String>,
@@ -1879,7 +2242,7 @@ struct PatientImport {
 	height_cm: Option<Decimal>,
 	race_code: Option<String>,
 	ethnicity_code: Option<String>,
-	last_menstrual_period_date: Option<Date>,
+	last_menstrual_period_date: Option<PartialDate>,
 	medical_history_text: Option<String>,
 	concomitant_therapy: Option<bool>,
 }
@@ -1924,6 +2287,7 @@ struct StudyImport {
 	study_name: Option<String>,
 	sponsor_study_number: Option<String>,
 	study_type_reaction: Option<String>,
+	kr_other_study_type: Option<String>,
 	registrations: Vec<StudyRegistrationImport>,
 }
 
@@ -1943,9 +2307,9 @@ struct PatientIdentifierImport {
 struct MedicalHistoryImport {
 	meddra_version: Option<String>,
 	meddra_code: Option<String>,
-	start_date: Option<Date>,
+	start_date: Option<PartialDate>,
 	continuing: Option<bool>,
-	end_date: Option<Date>,
+	end_date: Option<PartialDate>,
 	comments: Option<String>,
 	family_history: Option<bool>,
 }
@@ -1957,8 +2321,10 @@ struct PastDrugHistoryImport {
 	mpid_version: Option<String>,
 	phpid: Option<String>,
 	phpid_version: Option<String>,
-	start_date: Option<Date>,
-	end_date: Option<Date>,
+	kr_product_code_version: Option<String>,
+	kr_product_code: Option<String>,
+	start_date: Option<PartialDate>,
+	end_date: Option<PartialDate>,
 	indication_meddra_version: Option<String>,
 	indication_meddra_code: Option<String>,
 	reaction_meddra_version: Option<String>,
@@ -1967,7 +2333,7 @@ struct PastDrugHistoryImport {
 
 #[derive(Debug)]
 struct DeathImport {
-	date_of_death: Option<Date>,
+	date_of_death: Option<PartialDate>,
 	autopsy_performed: Option<bool>,
 	reported_causes: Vec<DeathCauseImport>,
 	autopsy_causes: Vec<DeathCauseImport>,
@@ -1982,10 +2348,10 @@ struct DeathCauseImport {
 #[derive(Debug)]
 struct ParentImport {
 	parent_identification: Option<String>,
-	parent_birth_date: Option<Date>,
+	parent_birth_date: Option<PartialDate>,
 	parent_age: Option<Decimal>,
 	parent_age_unit: Option<String>,
-	last_menstrual_period_date: Option<Date>,
+	last_menstrual_period_date: Option<PartialDate>,
 	weight_kg: Option<Decimal>,
 	height_cm: Option<Decimal>,
 	sex: Option<String>,
@@ -1997,7 +2363,7 @@ struct ParentImport {
 #[derive(Debug)]
 struct TestResultImport {
 	test_name: String,
-	test_date: Option<Date>,
+	test_date: Option<PartialDate>,
 	test_meddra_version: Option<String>,
 	test_meddra_code: Option<String>,
 	test_result_code: Option<String>,
@@ -2012,6 +2378,7 @@ struct TestResultImport {
 
 fn parse_safety_report_identification(
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	header: Option<&MessageHeaderExtract>,
 ) -> Result<Option<SafetyReportImport>> {
 	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
@@ -2053,7 +2420,8 @@ fn parse_safety_report_identification(
 			&mut xpath,
 			"//hl7:investigationEvent/hl7:subjectOf2/hl7:investigationCharacteristic[hl7:code[@code='1' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.23']]/hl7:value/@code",
 		),
-		&["1", "2", "3", "4"],
+		codes,
+		REPORT_TYPE,
 		"safety_report_identification.report_type",
 	)
 	.unwrap_or_else(|| "1".to_string());
@@ -2092,7 +2460,8 @@ fn parse_safety_report_identification(
 			&mut xpath,
 			"//hl7:investigationEvent/hl7:subjectOf2/hl7:investigationCharacteristic[hl7:code[@code='2' and @codeSystem='2.16.840.1.113883.3.989.2.1.1.19']]/hl7:value/@code",
 		),
-		&["1", "2", "3", "4", "5"],
+		codes,
+		LOCAL_CRITERIA_REPORT_TYPE,
 		"safety_report_identification.local_criteria_report_type",
 	);
 
@@ -2110,7 +2479,8 @@ fn parse_safety_report_identification(
 			&mut xpath,
 			"//hl7:investigationEvent/hl7:subjectOf2/hl7:investigationCharacteristic[hl7:code[@code='3' or @displayName='nullificationAmendmentCode']]/hl7:value/@code",
 		),
-		&["1", "2", "3", "4"],
+		codes,
+		NULLIFICATION_CODE,
 		"safety_report_identification.nullification_code",
 	);
 
@@ -2142,6 +2512,7 @@ fn parse_safety_report_identification(
 
 fn parse_sender_information(
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	header: Option<&MessageHeaderExtract>,
 ) -> Result<Option<SenderImport>> {
 	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
@@ -2170,7 +2541,8 @@ fn parse_sender_information(
 			"//hl7:sender/hl7:device/hl7:asAgent/hl7:representedOrganization/hl7:code/@code",
 		)
 		.or_else(|| first_value_root(&mut xpath, "//hl7:assignedEntity/hl7:code/@code")),
-		&["1", "2", "3", "4", "5", "6"],
+		codes,
+		SENDER_TYPE,
 		"sender_information.sender_type",
 	)
 	.unwrap_or_else(|| "1".to_string());
@@ -2188,8 +2560,22 @@ fn parse_sender_information(
 	.or_else(|| header.and_then(|h| h.message_sender.clone()))
 	.unwrap_or_else(|| "Unknown Sender".to_string());
 
+	let kr_health_professional_type = clamp_str(
+		first_value_root(
+			&mut xpath,
+			&format!(
+				"{}/{}/@code",
+				CMfdsSafetyReportPaths::SENDER,
+				CMfdsSafetyReportPaths::HEALTH_PROFESSIONAL_TYPE
+			),
+		),
+		10,
+		"sender_information.kr_health_professional_type",
+	);
+
 	Ok(Some(SenderImport {
 		sender_type,
+		kr_health_professional_type,
 		organization_name,
 		department: first_text_root(
 			&mut xpath,
@@ -2237,7 +2623,10 @@ fn parse_sender_information(
 	}))
 }
 
-fn parse_primary_source(xml: &[u8]) -> Result<Option<PrimarySourceImport>> {
+fn parse_primary_source(
+	xml: &[u8],
+	codes: &CodeListCatalog,
+) -> Result<Option<PrimarySourceImport>> {
 	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
 		message: format!("XML not valid UTF-8: {err}"),
 		line: None,
@@ -2326,7 +2715,8 @@ fn parse_primary_source(xml: &[u8]) -> Result<Option<PrimarySourceImport>> {
 
 	let qualification = normalize_code(
 		first_value_root(&mut xpath, "//hl7:assignedEntity/hl7:code/@code"),
-		&["1", "2", "3", "4", "5"],
+		codes,
+		QUALIFICATION,
 		"primary_sources.qualification",
 	)
 	.or(Some("1".to_string()));
@@ -2336,11 +2726,25 @@ fn parse_primary_source(xml: &[u8]) -> Result<Option<PrimarySourceImport>> {
 			&mut xpath,
 			"//hl7:primaryRole//hl7:subjectOf2/hl7:observation[hl7:code[@code='1']]/hl7:value/@code",
 		),
-		&["1", "2", "3"],
+		codes,
+		PRIMARY_SOURCE_REGULATORY,
 		"primary_sources.primary_source_regulatory",
 	)
 	.or(Some("1".to_string()));
 
+	let kr_other_health_professional = clamp_str(
+		first_value_root(
+			&mut xpath,
+			&format!(
+				"{}/{}/@code",
+				CMfdsSafetyReportPaths::PRIMARY_SOURCE,
+				CMfdsSafetyReportPaths::OTHER_HEALTH_PROFESSIONAL
+			),
+		),
+		10,
+		"primary_sources.kr_other_health_professional",
+	);
+
 	if reporter_given_name.is_none()
 		&& reporter_family_name.is_none()
 		&& organization.is_none()
@@ -2363,6 +2767,7 @@ fn parse_primary_source(xml: &[u8]) -> Result<Option<PrimarySourceImport>> {
 		country_code,
 		email,
 		qualification,
+		kr_other_health_professional,
 		primary_source_regulatory,
 	}))
 }
@@ -2610,6 +3015,16 @@ fn parse_study_information(xml: &[u8]) -> Result<Option<StudyImport>> {
 	let study_name = first_text(&mut xpath, node, "hl7:title");
 	let sponsor_study_number = first_attr(&mut xpath, node, "hl7:id", "extension");
 	let study_type_reaction = first_attr(&mut xpath, node, "hl7:code", "code");
+	let kr_other_study_type = clamp_str(
+		first_attr(
+			&mut xpath,
+			node,
+			CMfdsSafetyReportPaths::OTHER_STUDY_TYPE,
+			"code",
+		),
+		10,
+		"study_information.kr_other_study_type",
+	);
 
 	let reg_nodes = xpath
 		.findnodes("hl7:authorization/hl7:studyRegistration", Some(node))
@@ -2644,6 +3059,7 @@ fn parse_study_information(xml: &[u8]) -> Result<Option<StudyImport>> {
 		study_name,
 		sponsor_study_number,
 		study_type_reaction,
+		kr_other_study_type,
 		registrations,
 	}))
 }
@@ -2787,10 +3203,10 @@ fn parse_medical_history(xml: &[u8]) -> Result<Vec<MedicalHistoryImport>> {
 		);
 		let start_date =
 			first_attr(&mut xpath, &node, "hl7:effectiveTime/hl7:low", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let end_date =
 			first_attr(&mut xpath, &node, "hl7:effectiveTime/hl7:high", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let continuing = parse_bool_attr(
 			&mut xpath,
 			&node,
@@ -2876,12 +3292,28 @@ fn parse_past_drug_history(xml: &[u8]) -> Result<Vec<PastDrugHistoryImport>> {
 			10,
 			"past_drug_history.mpid_version",
 		);
+		let kr_product_code = first_attr(
+			&mut xpath,
+			&node,
+			DMfdsPatientPaths::PAST_DRUG_PRODUCT_CODE,
+			"code",
+		);
+		let kr_product_code_version = clamp_str(
+			first_attr(
+				&mut xpath,
+				&node,
+				DMfdsPatientPaths::PAST_DRUG_PRODUCT_CODE,
+				"codeSystemVersion",
+			),
+			10,
+			"past_drug_history.kr_product_code_version",
+		);
 		let start_date =
 			first_attr(&mut xpath, &node, "hl7:effectiveTime/hl7:low", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let end_date =
 			first_attr(&mut xpath, &node, "hl7:effectiveTime/hl7:high", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let indication_meddra_code = first_attr(
 			&mut xpath,
 			&node,
@@ -2920,6 +3352,8 @@ fn parse_past_drug_history(xml: &[u8]) -> Result<Vec<PastDrugHistoryImport>> {
 			mpid_version,
 			phpid: None,
 			phpid_version: None,
+			kr_product_code_version,
+			kr_product_code,
 			start_date,
 			end_date,
 			indication_meddra_version,
@@ -2953,7 +3387,7 @@ fn parse_patient_death(xml: &[u8]) -> Result<Option<DeathImport>> {
 	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
 
 	let date_of_death = first_value_root(&mut xpath, "//hl7:deceasedTime/@value")
-		.and_then(parse_date);
+		.and_then(parse_partial_date);
 	let autopsy_performed = parse_bool_value(first_value_root(
 		&mut xpath,
 		"//hl7:observation[hl7:code[@code='5']]/hl7:value/@value",
@@ -3060,7 +3494,7 @@ fn parse_parent_information(xml: &[u8]) -> Result<Option<ParentImport>> {
 		"hl7:associatedPerson/hl7:birthTime",
 		"value",
 	)
-	.and_then(parse_date);
+	.and_then(parse_partial_date);
 	let sex = normalize_sex_code(first_attr(
 		&mut xpath,
 		node,
@@ -3074,7 +3508,7 @@ fn parse_parent_information(xml: &[u8]) -> Result<Option<ParentImport>> {
 		"value",
 	)
 	.and_then(|v| v.parse::<Decimal>().ok());
-	let parent_age_unit = normalize_code3(
+	let parent_age_unit = normalize_time_unit(
 		first_attr(
 			&mut xpath,
 			node,
@@ -3089,7 +3523,7 @@ fn parse_parent_information(xml: &[u8]) -> Result<Option<ParentImport>> {
 		"hl7:subjectOf2/hl7:observation[hl7:code[@code='22']]/hl7:value",
 		"value",
 	)
-	.and_then(parse_date);
+	.and_then(parse_partial_date);
 	let weight_kg = first_attr(
 		&mut xpath,
 		node,
@@ -3134,10 +3568,10 @@ fn parse_parent_information(xml: &[u8]) -> Result<Option<ParentImport>> {
 		);
 		let start_date =
 			first_attr(&mut xpath, &obs, "hl7:effectiveTime/hl7:low", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let end_date =
 			first_attr(&mut xpath, &obs, "hl7:effectiveTime/hl7:high", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let continuing = parse_bool_attr(
 			&mut xpath,
 			&obs,
@@ -3199,12 +3633,28 @@ fn parse_parent_information(xml: &[u8]) -> Result<Option<ParentImport>> {
 			10,
 			"parent_past_drug.mpid_version",
 		);
+		let kr_product_code = first_attr(
+			&mut xpath,
+			&obs,
+			DMfdsPatientPaths::PARENT_PAST_DRUG_PRODUCT_CODE,
+			"code",
+		);
+		let kr_product_code_version = clamp_str(
+			first_attr(
+				&mut xpath,
+				&obs,
+				DMfdsPatientPaths::PARENT_PAST_DRUG_PRODUCT_CODE,
+				"codeSystemVersion",
+			),
+			10,
+			"parent_past_drug.kr_product_code_version",
+		);
 		let start_date =
 			first_attr(&mut xpath, &obs, "hl7:effectiveTime/hl7:low", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let end_date =
 			first_attr(&mut xpath, &obs, "hl7:effectiveTime/hl7:high", "value")
-				.and_then(parse_date);
+				.and_then(parse_partial_date);
 		let indication_meddra_code = first_attr(
 			&mut xpath,
 			&obs,
@@ -3243,6 +3693,8 @@ fn parse_parent_information(xml: &[u8]) -> Result<Option<ParentImport>> {
 			mpid_version,
 			phpid: None,
 			phpid_version: None,
+			kr_product_code_version,
+			kr_product_code,
 			start_date,
 			end_date,
 			indication_meddra_version,
@@ -3311,15 +3763,17 @@ fn parse_test_results(xml: &[u8]) -> Result<Vec<TestResultImport>> {
 			"test_results.test_meddra_version",
 		);
 		let test_date = first_attr(&mut xpath, &node, "hl7:effectiveTime", "value")
-			.and_then(parse_date);
+			.and_then(parse_partial_date);
 		let test_result_code =
 			first_attr(&mut xpath, &node, "hl7:interpretationCode", "code");
 		let test_result_value =
 			first_attr(&mut xpath, &node, "hl7:value/hl7:center", "value")
 				.or_else(|| first_attr(&mut xpath, &node, "hl7:value", "value"));
-		let test_result_unit =
+		let test_result_unit = normalize_unit(
 			first_attr(&mut xpath, &node, "hl7:value/hl7:center", "unit")
-				.or_else(|| first_attr(&mut xpath, &node, "hl7:value", "unit"));
+				.or_else(|| first_attr(&mut xpath, &node, "hl7:value", "unit")),
+			"test_results.test_result_unit",
+		);
 		let result_unstructured = first_text(&mut xpath, &node, "hl7:value");
 		let normal_low_value = first_attr(
 			&mut xpath,
@@ -3363,7 +3817,10 @@ fn parse_test_results(xml: &[u8]) -> Result<Vec<TestResultImport>> {
 	Ok(items)
 }
 
-fn parse_patient_information(xml: &[u8]) -> Result<Option<PatientImport>> {
+fn parse_patient_information(
+	xml: &[u8],
+	codes: &CodeListCatalog,
+) -> Result<Option<PatientImport>> {
 	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
 		message: format!("XML not valid UTF-8: {err}"),
 		line: None,
@@ -3413,7 +3870,7 @@ fn parse_patient_information(xml: &[u8]) -> Result<Option<PatientImport>> {
 		"//hl7:administrativeGenderCode/@code",
 	));
 	let birth_date =
-		first_value_root(&mut xpath, "//hl7:birthTime/@value").and_then(parse_date);
+		first_value_root(&mut xpath, "//hl7:birthTime/@value").and_then(parse_partial_date);
 	let age_at_time_of_onset = first_attr(
 		&mut xpath,
 		&root,
@@ -3421,7 +3878,7 @@ fn parse_patient_information(xml: &[u8]) -> Result<Option<PatientImport>> {
 		"value",
 	)
 	.and_then(|v| v.parse::<Decimal>().ok());
-	let age_unit = normalize_code3(
+	let age_unit = normalize_time_unit(
 		first_attr(
 			&mut xpath,
 			&root,
@@ -3437,7 +3894,7 @@ fn parse_patient_information(xml: &[u8]) -> Result<Option<PatientImport>> {
 		"value",
 	)
 	.and_then(|v| v.parse::<Decimal>().ok());
-	let gestation_period_unit = normalize_code3(
+	let gestation_period_unit = normalize_time_unit(
 		first_attr(
 			&mut xpath,
 			&root,
@@ -3453,7 +3910,8 @@ fn parse_patient_information(xml: &[u8]) -> Result<Option<PatientImport>> {
 			"//hl7:subjectOf2/hl7:observation[hl7:code[@code='4']]/hl7:value",
 			"code",
 		),
-		&["1", "2", "3", "4", "5", "6"],
+		codes,
+		AGE_GROUP,
 		"patient_information.age_group",
 	);
 	let weight_kg = first_attr(
@@ -3476,7 +3934,7 @@ fn parse_patient_information(xml: &[u8]) -> Result<Option<PatientImport>> {
 		"//hl7:subjectOf2/hl7:observation[hl7:code[@code='22']]/hl7:value",
 		"value",
 	)
-	.and_then(parse_date);
+	.and_then(parse_partial_date);
 	let race_code = first_attr(
 		&mut xpath,
 		&root,
@@ -3764,7 +4222,7 @@ fn parse_reactions(xml: &[u8], case_id: Uuid) -> Result<Vec<ReactionImport>> {
 					"value",
 				)
 			})
-			.and_then(parse_date),
+			.and_then(parse_partial_date),
 			end_date: first_attr(
 				&mut xpath,
 				&node,
@@ -3779,7 +4237,7 @@ fn parse_reactions(xml: &[u8], case_id: Uuid) -> Result<Vec<ReactionImport>> {
 					"value",
 				)
 			})
-			.and_then(parse_date),
+			.and_then(parse_partial_date),
 			duration_value: first_attr(
 				&mut xpath,
 				&node,
@@ -3787,7 +4245,7 @@ fn parse_reactions(xml: &[u8], case_id: Uuid) -> Result<Vec<ReactionImport>> {
 				"value",
 			)
 			.and_then(|v| v.parse::<Decimal>().ok()),
-			duration_unit: normalize_code3(
+			duration_unit: normalize_time_unit(
 				first_attr(
 					&mut xpath,
 					&node,
@@ -3917,21 +4375,32 @@ fn parse_uuid_opt(value: Option<String>) -> Option<Uuid> {
 	Uuid::parse_str(&value).ok()
 }
 
+/// Keeps `value` when it is an active code of `list_name` in `e2b_code_lists`.
+/// Lists missing from the catalog are not checked.
 fn normalize_code(
 	value: Option<String>,
-	allowed: &[&str],
+	codes: &CodeListCatalog,
+	list_name: &str,
 	field: &str,
 ) -> Option<String> {
 	match value {
 		Some(v) => {
 			let trimmed = v.trim();
-			if allowed.contains(&trimmed) {
+			if !codes.has_list(list_name) {
+				tracing::warn!(
+					field,
+					list_name,
+					"code list not loaded; keeping value unchecked"
+				);
+				return Some(trimmed.to_string()).filter(|v| !v.is_empty());
+			}
+			if codes.contains(list_name, trimmed) == Some(true) {
 				return Some(trimmed.to_string());
 			}
 			let digit = trimmed.chars().next().filter(|c| c.is_ascii_digit());
 			if let Some(d) = digit {
 				let s = d.to_string();
-				if allowed.contains(&s.as_str()) {
+				if codes.contains(list_name, &s) == Some(true) {
 					eprintln!(
 						"[import_e2b_xml] coercing {field} value={trimmed} -> {s}"
 					);
@@ -3980,6 +4449,27 @@ fn normalize_code3(value: Option<String>, field: &str) -> Option<String> {
 	}
 }
 
+/// Canonical UCUM code (`MG/DL` -> `mg/dL`, `mcg` -> `ug`). Units that are not
+/// UCUM are kept as sent so case validation can flag them.
+fn normalize_unit(value: Option<String>, field: &str) -> Option<String> {
+	let v = value?.trim().to_string();
+	if v.is_empty() {
+		return None;
+	}
+	match normalize_ucum(&v) {
+		Ok(unit) => Some(unit),
+		Err(_) => {
+			tracing::warn!(field, value = %v, "keeping non-UCUM unit");
+			Some(v)
+		}
+	}
+}
+
+/// Age, duration and frequency units live in 3-char columns (`a`, `wk`, `min`).
+fn normalize_time_unit(value: Option<String>, field: &str) -> Option<String> {
+	normalize_code3(normalize_unit(value, field), field)
+}
+
 fn normalize_sex_code(value: Option<String>) -> Option<String> {
 	let v = value?.trim().to_ascii_uppercase();
 	match v.as_str() {
@@ -4052,6 +4542,10 @@ fn telecom_first(xpath: &mut Context, prefix: &str) -> Option<String> {
 	None
 }
 
+fn parse_partial_date(value: String) -> Option<PartialDate> {
+	PartialDate::parse(&value)
+}
+
 fn parse_date(value: String) -> Option<Date> {
 	let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
 	if digits.len() < 8 {
@@ -4071,17 +4565,11 @@ fn infer_validation_profile(header: Option<&MessageHeaderExtract>) -> String {
 				.as_deref()
 				.or(h.message_receiver.as_deref())
 		})
-		.unwrap_or_default()
-		.trim()
-		.to_ascii_uppercase();
-	if receiver.contains("MFDS") {
-		"mfds".to_string()
-	} else {
-		"fda".to_string()
-	}
+		.unwrap_or_default();
+	ValidationProfile::for_receiver(receiver).as_str().to_string()
 }
 
-fn normalize_message_date(value: String) -> Option<String> {
+pub(crate) fn normalize_message_date(value: String) -> Option<String> {
 	let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
 	if digits.len() < 14 {
 		return None;
@@ -4089,7 +4577,7 @@ fn normalize_message_date(value: String) -> Option<String> {
 	Some(digits[0..14].to_string())
 }
 
-fn make_import_message_number(base: &str, case_id: Uuid) -> String {
+pub(crate) fn make_import_message_number(base: &str, case_id: Uuid) -> String {
 	let suffix = case_id.to_string();
 	let max_base = 100usize.saturating_sub(1 + suffix.len());
 	let truncated = if base.len() > max_base {
@@ -4184,19 +4672,23 @@ fn extract_safety_report_id(xml: &[u8]) -> Result<String> {
 	})?;
 	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
 
-	let candidates = xpath
-		.findvalues(
-			"//hl7:id[@root='2.16.840.1.113883.3.989.2.1.3.1']/@extension",
-			None,
-		)
-		.map_err(|_| Error::InvalidXml {
-			message: "Failed to query safety_report_id".to_string(),
-			line: None,
-			column: None,
-		})?;
-	for value in candidates {
-		if !value.trim().is_empty() {
-			return Ok(value);
+	// N.2.r.1 shares the C.1.1 OID, so prefer the investigationEvent id.
+	for expr in [
+		"//hl7:investigationEvent/hl7:id[@root='2.16.840.1.113883.3.989.2.1.3.1']/@extension",
+		"//hl7:id[@root='2.16.840.1.113883.3.989.2.1.3.1']/@extension",
+	] {
+		let candidates =
+			xpath
+				.findvalues(expr, None)
+				.map_err(|_| Error::InvalidXml {
+					message: "Failed to query safety_report_id".to_string(),
+					line: None,
+					column: None,
+				})?;
+		for value in candidates {
+			if !value.trim().is_empty() {
+				return Ok(value);
+			}
 		}
 	}
 
@@ -4211,6 +4703,7 @@ async fn import_drugs(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	xml: &[u8],
+	codes: &CodeListCatalog,
 	case_id: Uuid,
 ) -> Result<ImportIdMap> {
 	let use_v2 = std::env::var("XML_V2_IMPORT_G").unwrap_or_default() == "1";
@@ -4240,6 +4733,8 @@ async fn import_drugs(
 				parent_route_termid_version: entry.parent_route_termid_version,
 				parent_dosage_text: entry.parent_dosage_text,
 				fda_additional_info_coded: entry.fda_additional_info_coded,
+				kr_product_code_version: entry.kr_product_code_version,
+				kr_product_code: entry.kr_product_code,
 				substances: entry
 					.substances
 					.into_iter()
@@ -4249,6 +4744,8 @@ async fn import_drugs(
 						substance_termid_version: sub.substance_termid_version,
 						strength_value: sub.strength_value,
 						strength_unit: sub.strength_unit,
+						kr_ingredient_code_version: sub.kr_ingredient_code_version,
+						kr_ingredient_code: sub.kr_ingredient_code,
 					})
 					.collect(),
 				dosages: entry
@@ -4301,7 +4798,7 @@ async fn import_drugs(
 			})
 			.collect::<Vec<_>>()
 	} else {
-		parse_drugs(xml)?
+		parse_drugs(xml, codes)?
 	};
 	let mut map = ImportIdMap::default();
 
@@ -4342,6 +4839,8 @@ async fn import_drugs(
 				parent_route_termid_version: drug.parent_route_termid_version,
 				parent_dosage_text: drug.parent_dosage_text,
 				fda_additional_info_coded: drug.fda_additional_info_coded,
+				kr_product_code_version: drug.kr_product_code_version,
+				kr_product_code: drug.kr_product_code,
 			},
 		)
 		.await?;
@@ -4358,6 +4857,8 @@ async fn import_drugs(
 					substance_termid_version: sub.substance_termid_version,
 					strength_value: sub.strength_value,
 					strength_unit: sub.strength_unit,
+					kr_ingredient_code_version: sub.kr_ingredient_code_version,
+					kr_ingredient_code: sub.kr_ingredient_code,
 				},
 			)
 			.await?;
@@ -4632,6 +5133,9 @@ async fn import_drug_reaction_assessments(
 					source_of_assessment: rel.source_of_assessment,
 					method_of_assessment: rel.method_of_assessment,
 					result_of_assessment: rel.result_of_assessment,
+					kr_method_of_assessment: rel.kr_method_of_assessment,
+					kr_result_who_umc: rel.kr_result_who_umc,
+					kr_result_korean: rel.kr_result_korean,
 				},
 			)
 			.await;
@@ -4653,6 +5157,9 @@ async fn import_drug_reaction_assessments(
 					source_of_assessment: rel.source_of_assessment,
 					method_of_assessment: rel.method_of_assessment,
 					result_of_assessment: rel.result_of_assessment,
+					kr_method_of_assessment: rel.kr_method_of_assessment,
+					kr_result_who_umc: rel.kr_result_who_umc,
+					kr_result_korean: rel.kr_result_korean,
 				},
 			)
 			.await;
@@ -4662,7 +5169,7 @@ async fn import_drug_reaction_assessments(
 	Ok(())
 }
 
-fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
+fn parse_drugs(xml: &[u8], codes: &CodeListCatalog) -> Result<Vec<DrugImport>> {
 	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
 		message: format!("XML not valid UTF-8: {err}"),
 		line: None,
@@ -4768,7 +5275,8 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 				"hl7:inboundRelationship[@typeCode='CAUS']/hl7:act/hl7:code",
 				"code",
 			),
-			&["1", "2", "3", "4", "5", "6"],
+			codes,
+			DRUG_ACTION,
 			"drug_information.action_taken",
 		)
 		.or_else(|| Some("5".to_string()));
@@ -4782,7 +5290,8 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 				"hl7:outboundRelationship2/hl7:observation[hl7:code[@code='31']]/hl7:value",
 				"code",
 			),
-			&["1", "2", "3", "4"],
+			codes,
+			RECHALLENGE,
 			"drug_information.rechallenge",
 		);
 		let dosage_text = first_text(&mut xpath, &node, "hl7:text");
@@ -4801,6 +5310,18 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 			10,
 			"drug_information.fda_additional_info_coded",
 		);
+		let kr_product_code =
+			first_attr(&mut xpath, &node, GMfdsDrugPaths::PRODUCT_CODE, "code");
+		let kr_product_code_version = clamp_str(
+			first_attr(
+				&mut xpath,
+				&node,
+				GMfdsDrugPaths::PRODUCT_CODE,
+				"codeSystemVersion",
+			),
+			10,
+			"drug_information.kr_product_code_version",
+		);
 		let parent_route_termid_version = clamp_str(
 			first_attr(
 				&mut xpath,
@@ -4859,12 +5380,30 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 					.and_then(|v| v.parse::<Decimal>().ok());
 			let strength_unit =
 				first_attr(&mut xpath, &sub, "hl7:quantity/hl7:numerator", "unit");
+			let kr_ingredient_code_path = format!(
+				"hl7:ingredientSubstance/{}",
+				GMfdsDrugPaths::INGREDIENT_CODE
+			);
+			let kr_ingredient_code =
+				first_attr(&mut xpath, &sub, &kr_ingredient_code_path, "code");
+			let kr_ingredient_code_version = clamp_str(
+				first_attr(
+					&mut xpath,
+					&sub,
+					&kr_ingredient_code_path,
+					"codeSystemVersion",
+				),
+				10,
+				"drug_active_substances.kr_ingredient_code_version",
+			);
 			substances.push(DrugSubstanceImport {
 				substance_name: sub_name,
 				substance_termid: termid,
 				substance_termid_version: termid_version,
 				strength_value,
 				strength_unit,
+				kr_ingredient_code_version,
+				kr_ingredient_code,
 			});
 		}
 
@@ -4884,7 +5423,7 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 				"value",
 			)
 			.and_then(|v| v.parse::<Decimal>().ok());
-			let frequency_unit = normalize_code3(
+			let frequency_unit = normalize_time_unit(
 				first_attr(
 					&mut xpath,
 					&dose,
@@ -4899,14 +5438,14 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 				"hl7:effectiveTime/hl7:comp[@operator='A']/hl7:low",
 				"value",
 			)
-			.and_then(parse_date);
+			.and_then(parse_partial_date);
 			let end_date = first_attr(
 				&mut xpath,
 				&dose,
 				"hl7:effectiveTime/hl7:comp[@operator='A']/hl7:high",
 				"value",
 			)
-			.and_then(parse_date);
+			.and_then(parse_partial_date);
 			let duration_value = first_attr(
 				&mut xpath,
 				&dose,
@@ -4914,7 +5453,7 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 				"value",
 			)
 			.and_then(|v| v.parse::<Decimal>().ok());
-			let duration_unit = normalize_code3(
+			let duration_unit = normalize_time_unit(
 				first_attr(
 					&mut xpath,
 					&dose,
@@ -4926,8 +5465,10 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 			let dose_value =
 				first_attr(&mut xpath, &dose, "hl7:doseQuantity", "value")
 					.and_then(|v| v.parse::<Decimal>().ok());
-			let dose_unit =
-				first_attr(&mut xpath, &dose, "hl7:doseQuantity", "unit");
+			let dose_unit = normalize_unit(
+				first_attr(&mut xpath, &dose, "hl7:doseQuantity", "unit"),
+				"dosage_information.dose_unit",
+			);
 			let route = normalize_code3(
 				first_attr(&mut xpath, &dose, "hl7:routeCode", "code"),
 				"dosage_information.route_of_administration",
@@ -5080,6 +5621,8 @@ fn parse_drugs(xml: &[u8]) -> Result<Vec<DrugImport>> {
 			parent_route_termid_version,
 			parent_dosage_text,
 			fda_additional_info_coded,
+			kr_product_code_version,
+			kr_product_code,
 			substances,
 			dosages: dosage_list,
 			indications,
@@ -5275,6 +5818,37 @@ fn parse_relatedness_assessments(xml: &[u8]) -> Result<Vec<RelatednessImport>> {
 		);
 		let result_of_assessment =
 			first_text(&mut xpath, &node, "hl7:causalityAssessment/hl7:value");
+		let kr_path = |path: &str| format!("hl7:causalityAssessment/{path}");
+		let kr_method_of_assessment = clamp_str(
+			first_attr(
+				&mut xpath,
+				&node,
+				&kr_path(GMfdsDrugPaths::METHOD_OF_ASSESSMENT),
+				"code",
+			),
+			10,
+			"relatedness_assessments.kr_method_of_assessment",
+		);
+		let kr_result_who_umc = clamp_str(
+			first_attr(
+				&mut xpath,
+				&node,
+				&kr_path(GMfdsDrugPaths::RESULT_WHO_UMC),
+				"code",
+			),
+			10,
+			"relatedness_assessments.kr_result_who_umc",
+		);
+		let kr_result_korean = clamp_str(
+			first_attr(
+				&mut xpath,
+				&node,
+				&kr_path(GMfdsDrugPaths::RESULT_KOREAN),
+				"code",
+			),
+			10,
+			"relatedness_assessments.kr_result_korean",
+		);
 		let reaction_xml_id = parse_uuid_opt(first_attr(
 			&mut xpath,
 			&node,
@@ -5294,6 +5868,9 @@ fn parse_relatedness_assessments(xml: &[u8]) -> Result<Vec<RelatednessImport>> {
 			source_of_assessment,
 			method_of_assessment,
 			result_of_assessment,
+			kr_method_of_assessment,
+			kr_result_who_umc,
+			kr_result_korean,
 		});
 	}
 
@@ -1,20 +1,19 @@
 // Section D importer (Patient) - FDA mapping.
 
+use crate::model::partial_date::PartialDate;
 use crate::xml::error::Error;
 use crate::xml::mapping::fda::d_patient::DPatientPaths;
 use crate::xml::Result;
 use libxml::parser::Parser;
 use libxml::xpath::Context;
 use rust_decimal::Decimal;
-use sqlx::types::time::Date;
-use time::Month;
 
 #[derive(Debug)]
 pub struct DPatientImport {
 	pub patient_initials: Option<String>,
 	pub patient_given_name: Option<String>,
 	pub patient_family_name: Option<String>,
-	pub birth_date: Option<Date>,
+	pub birth_date: Option<PartialDate>,
 	pub sex: Option<String>,
 	pub age_at_time_of_onset: Option<Decimal>,
 	pub age_unit: Option<String>,
@@ -25,7 +24,7 @@ pub struct DPatientImport {
 	pub height_cm: Option<Decimal>,
 	pub race_code: Option<String>,
 	pub ethnicity_code: Option<String>,
-	pub last_menstrual_period_date: Option<Date>,
+	pub last_menstrual_period_date: Option<PartialDate>,
 	pub medical_history_text: Option<String>,
 	pub concomitant_therapy: Option<bool>,
 }
@@ -205,14 +204,6 @@ fn parse_bool_value(value: Option<String>) -> Option<bool> {
 	})
 }
 
-fn parse_date(value: String) -> Option<Date> {
-	let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
-	if digits.len() < 8 {
-		return None;
-	}
-	let y: i32 = digits[0..4].parse().ok()?;
-	let m: u8 = digits[4..6].parse().ok()?;
-	let d: u8 = digits[6..8].parse().ok()?;
-	let month = Month::try_from(m).ok()?;
-	Date::from_calendar_date(y, month, d).ok()
+fn parse_date(value: String) -> Option<PartialDate> {
+	PartialDate::parse(&value)
 }
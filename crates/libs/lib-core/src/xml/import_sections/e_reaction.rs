@@ -1,5 +1,6 @@
 // Section E importer (Reaction/Event) - FDA mapping.
 
+use crate::model::partial_date::PartialDate;
 use crate::xml::error::Error;
 use crate::xml::mapping::fda::e_reaction::EReactionPaths;
 use crate::xml::Result;
@@ -7,9 +8,7 @@ use libxml::parser::Parser;
 use libxml::tree::Node;
 use libxml::xpath::Context;
 use rust_decimal::Decimal;
-use sqlx::types::time::Date;
 use sqlx::types::Uuid;
-use time::Month;
 
 #[derive(Debug)]
 pub struct EReactionImport {
@@ -27,8 +26,8 @@ pub struct EReactionImport {
 	pub criteria_congenital_anomaly: Option<bool>,
 	pub criteria_other_medically_important: Option<bool>,
 	pub required_intervention: Option<String>,
-	pub start_date: Option<Date>,
-	pub end_date: Option<Date>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub duration_value: Option<Decimal>,
 	pub duration_unit: Option<String>,
 	pub outcome: Option<String>,
@@ -285,14 +284,6 @@ fn normalize_code3(value: Option<String>, _field: &str) -> Option<String> {
 	}
 }
 
-fn parse_date(value: String) -> Option<Date> {
-	let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
-	if digits.len() < 8 {
-		return None;
-	}
-	let y: i32 = digits[0..4].parse().ok()?;
-	let m: u8 = digits[4..6].parse().ok()?;
-	let d: u8 = digits[6..8].parse().ok()?;
-	let month = Month::try_from(m).ok()?;
-	Date::from_calendar_date(y, month, d).ok()
+fn parse_date(value: String) -> Option<PartialDate> {
+	PartialDate::parse(&value)
 }
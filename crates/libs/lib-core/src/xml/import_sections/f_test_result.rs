@@ -1,18 +1,17 @@
 // Section F importer (Tests and Procedures) - FDA mapping.
 
+use crate::model::partial_date::PartialDate;
 use crate::xml::error::Error;
 use crate::xml::mapping::fda::f_test_result::FTestResultPaths;
 use crate::xml::Result;
 use libxml::parser::Parser;
 use libxml::tree::Node;
 use libxml::xpath::Context;
-use sqlx::types::time::Date;
-use time::Month;
 
 #[derive(Debug)]
 pub struct FTestResultImport {
 	pub test_name: String,
-	pub test_date: Option<Date>,
+	pub test_date: Option<PartialDate>,
 	pub test_meddra_version: Option<String>,
 	pub test_meddra_code: Option<String>,
 	pub test_result_code: Option<String>,
@@ -155,14 +154,6 @@ fn clamp_str(value: Option<String>, max: usize) -> Option<String> {
 	}
 }
 
-fn parse_date(value: String) -> Option<Date> {
-	let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
-	if digits.len() < 8 {
-		return None;
-	}
-	let y: i32 = digits[0..4].parse().ok()?;
-	let m: u8 = digits[4..6].parse().ok()?;
-	let d: u8 = digits[6..8].parse().ok()?;
-	let month = Month::try_from(m).ok()?;
-	Date::from_calendar_date(y, month, d).ok()
+fn parse_date(value: String) -> Option<PartialDate> {
+	PartialDate::parse(&value)
 }
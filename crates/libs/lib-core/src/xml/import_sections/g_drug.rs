@@ -1,7 +1,9 @@
 // Section G importer (Drug/Biological) - FDA mapping.
 
+use crate::model::partial_date::PartialDate;
 use crate::xml::error::Error;
 use crate::xml::mapping::fda::g_drug::GDrugPaths;
+use crate::xml::mapping::mfds::g_drug::GMfdsDrugPaths;
 use crate::xml::Result;
 use libxml::parser::Parser;
 use libxml::tree::Node;
@@ -31,6 +33,8 @@ pub struct GDrugImport {
 	pub parent_route_termid_version: Option<String>,
 	pub parent_dosage_text: Option<String>,
 	pub fda_additional_info_coded: Option<String>,
+	pub kr_product_code_version: Option<String>,
+	pub kr_product_code: Option<String>,
 	pub substances: Vec<GDrugSubstanceImport>,
 	pub dosages: Vec<GDrugDosageImport>,
 	pub indications: Vec<GDrugIndicationImport>,
@@ -44,6 +48,8 @@ pub struct GDrugSubstanceImport {
 	pub substance_termid_version: Option<String>,
 	pub strength_value: Option<Decimal>,
 	pub strength_unit: Option<String>,
+	pub kr_ingredient_code_version: Option<String>,
+	pub kr_ingredient_code: Option<String>,
 }
 
 #[derive(Debug)]
@@ -51,8 +57,8 @@ pub struct GDrugDosageImport {
 	pub dosage_text: Option<String>,
 	pub frequency_value: Option<Decimal>,
 	pub frequency_unit: Option<String>,
-	pub start_date: Option<sqlx::types::time::Date>,
-	pub end_date: Option<sqlx::types::time::Date>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
 	pub duration_value: Option<Decimal>,
 	pub duration_unit: Option<String>,
 	pub dose_value: Option<Decimal>,
@@ -159,6 +165,19 @@ pub fn parse_g_drugs(xml: &[u8]) -> Result<Vec<GDrugImport>> {
 			first_attr(&mut xpath, &node, GDrugPaths::FDA_ADDITIONAL_INFO),
 			10,
 		);
+		let kr_product_code = first_attr(
+			&mut xpath,
+			&node,
+			&format!("{}/@code", GMfdsDrugPaths::PRODUCT_CODE),
+		);
+		let kr_product_code_version = clamp_str(
+			first_attr(
+				&mut xpath,
+				&node,
+				&format!("{}/@codeSystemVersion", GMfdsDrugPaths::PRODUCT_CODE),
+			),
+			10,
+		);
 		let parent_route_termid_version = clamp_str(
 			first_attr(&mut xpath, &node, GDrugPaths::PARENT_ROUTE_TERMID_VERSION),
 			10,
@@ -186,12 +205,33 @@ pub fn parse_g_drugs(xml: &[u8]) -> Result<Vec<GDrugImport>> {
 					.and_then(|v| v.parse::<Decimal>().ok());
 			let strength_unit =
 				first_attr(&mut xpath, &sub, GDrugPaths::SUBSTANCE_STRENGTH_UNIT);
+			let kr_ingredient_code = first_attr(
+				&mut xpath,
+				&sub,
+				&format!(
+					"hl7:ingredientSubstance/{}/@code",
+					GMfdsDrugPaths::INGREDIENT_CODE
+				),
+			);
+			let kr_ingredient_code_version = clamp_str(
+				first_attr(
+					&mut xpath,
+					&sub,
+					&format!(
+						"hl7:ingredientSubstance/{}/@codeSystemVersion",
+						GMfdsDrugPaths::INGREDIENT_CODE
+					),
+				),
+				10,
+			);
 			substances.push(GDrugSubstanceImport {
 				substance_name: sub_name,
 				substance_termid: termid,
 				substance_termid_version: termid_version,
 				strength_value,
 				strength_unit,
+				kr_ingredient_code_version,
+				kr_ingredient_code,
 			});
 		}
 
@@ -362,6 +402,8 @@ pub fn parse_g_drugs(xml: &[u8]) -> Result<Vec<GDrugImport>> {
 			parent_route_termid_version,
 			parent_dosage_text,
 			fda_additional_info_coded,
+			kr_product_code_version,
+			kr_product_code,
 			substances,
 			dosages: dosage_list,
 			indications,
@@ -444,14 +486,6 @@ fn normalize_code3(value: Option<String>) -> Option<String> {
 	}
 }
 
-fn parse_date(value: String) -> Option<sqlx::types::time::Date> {
-	let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
-	if digits.len() < 8 {
-		return None;
-	}
-	let y: i32 = digits[0..4].parse().ok()?;
-	let m: u8 = digits[4..6].parse().ok()?;
-	let d: u8 = digits[6..8].parse().ok()?;
-	let month = time::Month::try_from(m).ok()?;
-	sqlx::types::time::Date::from_calendar_date(y, month, d).ok()
+fn parse_date(value: String) -> Option<PartialDate> {
+	PartialDate::parse(&value)
 }
@@ -1,6 +1,8 @@
 // MFDS mapping for Section C (Safety Report, Sender/Reporter).
 //
 // Keep ICH core paths in shared mappings and place KR-only paths here.
+// Each KR element travels as a `subjectOf/observation` coded with its element
+// ID under the node that owns it (see docs/MFDS_E2B_MAPPING.md).
 
 pub struct CMfdsSafetyReportPaths;
 
@@ -8,4 +10,27 @@ impl CMfdsSafetyReportPaths {
 	// Regional field IDs implemented in this section.
 	pub const KR_FIELDS: &'static [&'static str] =
 		&["C.2.r.4.KR.1", "C.3.1.KR.1", "C.5.4.KR.1"];
+
+	// C.2.r Primary source (owner of C.2.r.4.KR.1), one node per repeat
+	pub const PRIMARY_SOURCE: &'static str =
+		"//hl7:investigationEvent/hl7:outboundRelationship/hl7:relatedInvestigation[hl7:code[@code='2']]/hl7:subjectOf2/hl7:controlActEvent/hl7:author/hl7:assignedEntity";
+
+	// C.2.r.4.KR.1 Other Health Professional (relative to PRIMARY_SOURCE)
+	pub const OTHER_HEALTH_PROFESSIONAL: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='C.2.r.4.KR.1']]/hl7:value";
+
+	// C.3 Sender (owner of C.3.1.KR.1)
+	pub const SENDER: &'static str =
+		"//hl7:investigationEvent/hl7:subjectOf1/hl7:controlActEvent/hl7:author/hl7:assignedEntity";
+
+	// C.3.1.KR.1 Health Professional Type (relative to SENDER)
+	pub const HEALTH_PROFESSIONAL_TYPE: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='C.3.1.KR.1']]/hl7:value";
+
+	// C.5 Study (owner of C.5.4.KR.1)
+	pub const RESEARCH_STUDY: &'static str = "//hl7:researchStudy";
+
+	// C.5.4.KR.1 Other Studies Type (relative to RESEARCH_STUDY)
+	pub const OTHER_STUDY_TYPE: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='C.5.4.KR.1']]/hl7:value";
 }
@@ -1,6 +1,8 @@
 // MFDS mapping for Section D (Patient).
 //
 // Regional KR fields should be declared here; ICH core remains shared.
+// The KR product code pair is one CE value: `code` carries KR.1b and
+// `codeSystemVersion` carries KR.1a.
 
 pub struct DMfdsPatientPaths;
 
@@ -11,4 +13,24 @@ impl DMfdsPatientPaths {
 		"D.10.8.r.1.KR.1a",
 		"D.10.8.r.1.KR.1b",
 	];
+
+	// D.8.r Past drug (owner of D.8.r.1.KR.1a/b), one node per repeat
+	pub const PAST_DRUG: &'static str =
+		"//hl7:primaryRole/hl7:subjectOf2/hl7:organizer[hl7:code[@code='2']]/hl7:component/hl7:substanceAdministration";
+
+	// D.10.8.r Parent past drug (owner of D.10.8.r.1.KR.1a/b)
+	pub const PARENT_PAST_DRUG: &'static str =
+		"//hl7:primaryRole/hl7:role[hl7:code[@code='PRN']]/hl7:subjectOf2/hl7:organizer[hl7:code[@code='2']]/hl7:component/hl7:substanceAdministration";
+
+	// Product node of a past drug (relative to PAST_DRUG / PARENT_PAST_DRUG)
+	pub const PAST_DRUG_PRODUCT: &'static str =
+		"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct";
+
+	// D.8.r.1.KR.1a/b MFDS Product Code (relative to PAST_DRUG)
+	pub const PAST_DRUG_PRODUCT_CODE: &'static str =
+		"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:subjectOf/hl7:observation[hl7:code[@code='D.8.r.1.KR.1b']]/hl7:value";
+
+	// D.10.8.r.1.KR.1a/b MFDS Product Code (relative to PARENT_PAST_DRUG)
+	pub const PARENT_PAST_DRUG_PRODUCT_CODE: &'static str =
+		"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:subjectOf/hl7:observation[hl7:code[@code='D.10.8.r.1.KR.1b']]/hl7:value";
 }
@@ -1,4 +1,7 @@
 // MFDS mapping for Section G (Drug/Biologic).
+//
+// KR code pairs are one CE value: `code` carries KR.1b and
+// `codeSystemVersion` carries KR.1a.
 
 pub struct GMfdsDrugPaths;
 
@@ -12,4 +15,40 @@ impl GMfdsDrugPaths {
 		"G.k.9.i.2.r.3.KR.1",
 		"G.k.9.i.2.r.3.KR.2",
 	];
+
+	// G.k Drug (owner of the product and ingredient codes), one node per repeat
+	pub const DRUG: &'static str =
+		"//hl7:organizer[hl7:code[@code='4']]/hl7:component/hl7:substanceAdministration";
+
+	// Product node of a drug (relative to DRUG)
+	pub const PRODUCT: &'static str =
+		"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct";
+
+	// G.k.2.1.KR.1a/b MFDS Product Code (relative to DRUG)
+	pub const PRODUCT_CODE: &'static str =
+		"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:subjectOf/hl7:observation[hl7:code[@code='G.k.2.1.KR.1b']]/hl7:value";
+
+	// G.k.2.3.r Active substance (relative to DRUG)
+	pub const INGREDIENT: &'static str =
+		"hl7:consumable/hl7:instanceOfKind/hl7:kindOfProduct/hl7:ingredient/hl7:ingredientSubstance";
+
+	// G.k.2.3.r.1.KR.1a/b MFDS Ingredient Code (relative to INGREDIENT)
+	pub const INGREDIENT_CODE: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='G.k.2.3.r.1.KR.1b']]/hl7:value";
+
+	// G.k.9.i.2.r Relatedness assessment (owner of the KR method/results)
+	pub const CAUSALITY_ASSESSMENT: &'static str =
+		"//hl7:causalityAssessment[hl7:code[@code='39']]";
+
+	// G.k.9.i.2.r.2.KR.1 KR Method of Assessment (relative to CAUSALITY_ASSESSMENT)
+	pub const METHOD_OF_ASSESSMENT: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='G.k.9.i.2.r.2.KR.1']]/hl7:value";
+
+	// G.k.9.i.2.r.3.KR.1 KR Result of Assessment, WHO-UMC (relative to CAUSALITY_ASSESSMENT)
+	pub const RESULT_WHO_UMC: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='G.k.9.i.2.r.3.KR.1']]/hl7:value";
+
+	// G.k.9.i.2.r.3.KR.2 KR Result of Assessment, Korean algorithm (relative to CAUSALITY_ASSESSMENT)
+	pub const RESULT_KOREAN: &'static str =
+		"hl7:subjectOf/hl7:observation[hl7:code[@code='G.k.9.i.2.r.3.KR.2']]/hl7:value";
 }
@@ -1,3 +1,4 @@
 // Mapping spec and helpers (FDA-focused for now).
 pub mod fda;
 pub mod mfds;
+pub mod pmda;
@@ -0,0 +1,37 @@
+// PMDA mapping for the J regional elements and Japanese case summaries.
+//
+// Keep ICH core paths in shared mappings and place J-only paths here.
+// Each J element travels as a `component/observationEvent` coded with its
+// element ID under the investigation event, next to the H.5.r case summaries
+// (see docs/PMDA_E2B_MAPPING.md).
+
+pub struct JPmdaPaths;
+
+impl JPmdaPaths {
+	// Investigation event (owner of the J elements and H.5.r summaries)
+	pub const INVESTIGATION_EVENT: &'static str = "//hl7:investigationEvent";
+
+	// Components of the investigation event, in document order
+	pub const COMPONENT: &'static str = "//hl7:investigationEvent/hl7:component";
+
+	// J.2 - J.20 components
+	pub const J_ELEMENT: &'static str =
+		"//hl7:investigationEvent/hl7:component[hl7:observationEvent/hl7:code[starts-with(@code,'J.')]]";
+
+	// J element ID (relative to J_ELEMENT)
+	pub const J_ELEMENT_ID: &'static str = "hl7:observationEvent/hl7:code/@code";
+
+	// J element value (relative to J_ELEMENT): @code when coded, text otherwise
+	pub const J_ELEMENT_VALUE: &'static str = "hl7:observationEvent/hl7:value";
+
+	// H.5.r Case Summary and Reporter's Comments components
+	pub const CASE_SUMMARY: &'static str =
+		"//hl7:investigationEvent/hl7:component[hl7:observationEvent/hl7:code[@code='36']]";
+
+	// H.5.r.1a Text / H.5.r.1b Language (relative to CASE_SUMMARY)
+	pub const CASE_SUMMARY_VALUE: &'static str = "hl7:observationEvent/hl7:value";
+
+	// Summary author type (relative to CASE_SUMMARY)
+	pub const CASE_SUMMARY_AUTHOR: &'static str =
+		"hl7:observationEvent/hl7:author/hl7:assignedEntity/hl7:code/@code";
+}
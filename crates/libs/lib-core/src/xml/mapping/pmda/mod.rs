@@ -0,0 +1 @@
+pub mod j_elements;
@@ -1,6 +1,8 @@
-// MFDS regional business-rule scaffolding.
+// MFDS regional business rules.
 //
-// Goal: keep regional rule definitions separate from FDA and ICH core rules.
+// Keeps regional rule definitions separate from FDA and ICH core rules. Each
+// id is a catalog code in `xml::validate`, so the validator, rule store and
+// this table refer to the same rule.
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuleSeverity {
@@ -17,43 +19,122 @@ pub struct MfdsRuleSpec {
 	pub note: &'static str,
 }
 
-// Initial MFDS rule catalog distilled from implementation references.
-// Replace notes with official MFDS guide citations when parsed from source files.
 pub const MFDS_RULES: &[MfdsRuleSpec] = &[
 	MfdsRuleSpec {
-		id: "MFDS.C.5.4.KR.1.REQ",
+		id: "MFDS.C.2.r.4.KR.1.REQUIRED",
+		title: "C.2.r.4.KR.1 required for other health professionals",
+		severity: RuleSeverity::Reject,
+		note: "If C.2.r.4=3 (Other health professional), C.2.r.4.KR.1 must be populated.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.C.2.r.4.KR.1.FORBIDDEN",
+		title: "C.2.r.4.KR.1 not accepted for other qualifications",
+		severity: RuleSeverity::Reject,
+		note: "C.2.r.4.KR.1 is only accepted when C.2.r.4=3.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.C.3.1.KR.1.REQUIRED",
+		title: "C.3.1.KR.1 required when sender is a health professional",
+		severity: RuleSeverity::Reject,
+		note: "If C.3.1=3 (Health professional), C.3.1.KR.1 must be populated.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.C.5.4.KR.1.REQUIRED",
 		title: "C.5.4.KR.1 required when C.5.4 is Other studies",
 		severity: RuleSeverity::Reject,
 		note: "If C.5.4=3 (Other studies), C.5.4.KR.1 must be populated.",
 	},
 	MfdsRuleSpec {
-		id: "MFDS.C.2.R.4.KR.1.COND",
-		title: "C.2.r.4.KR.1 conditional on reporter qualification",
+		id: "MFDS.D.8.r.1.KR.1a.REQUIRED",
+		title: "D.8.r.1.KR.1a required with a past drug KR product code",
+		severity: RuleSeverity::Reject,
+		note: "A D.8.r.1.KR.1b code needs its code version in D.8.r.1.KR.1a.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.D.10.8.r.1.KR.1a.REQUIRED",
+		title: "D.10.8.r.1.KR.1a required with a parent past drug KR product code",
+		severity: RuleSeverity::Reject,
+		note: "A D.10.8.r.1.KR.1b code needs its code version in D.10.8.r.1.KR.1a.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.KR.DOMESTIC.PRODUCTCODE.REQUIRED",
+		title: "G.k.2.1.KR.1b required for domestic products",
+		severity: RuleSeverity::Reject,
+		note: "Drugs obtained in Korea (G.k.2.4=KR) must carry the MFDS product code.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.KR.DOMESTIC.INGREDIENTCODE.REQUIRED",
+		title: "G.k.2.3.r.1.KR.1b required for domestic products",
+		severity: RuleSeverity::Reject,
+		note: "Each active substance of a domestic drug must carry the MFDS ingredient code.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.KR.FOREIGN.WHOMPID.RECOMMENDED",
+		title: "WHO MPID recommended for foreign-use products",
+		severity: RuleSeverity::Warning,
+		note: "For drugs obtained outside Korea the WHO MPID pathway (G.k.2.1.1b) applies.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.G.k.2.1.KR.1a.REQUIRED",
+		title: "G.k.2.1.KR.1a required with a KR product code",
+		severity: RuleSeverity::Reject,
+		note: "A G.k.2.1.KR.1b code needs its code version in G.k.2.1.KR.1a.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.G.k.2.3.r.1.KR.1a.REQUIRED",
+		title: "G.k.2.3.r.1.KR.1a required with a KR ingredient code",
+		severity: RuleSeverity::Reject,
+		note: "A G.k.2.3.r.1.KR.1b code needs its code version in G.k.2.3.r.1.KR.1a.",
+	},
+	MfdsRuleSpec {
+		id: "MFDS.G.k.9.i.2.r.2.KR.1.REQUIRED",
+		title: "KR method of assessment required with a source",
 		severity: RuleSeverity::Reject,
-		note: "Required when C.2.r.4 indicates Other health professional; otherwise not accepted.",
+		note: "Each assessment with a source (G.k.9.i.2.r.1) needs G.k.9.i.2.r.2.KR.1.",
 	},
 	MfdsRuleSpec {
-		id: "MFDS.C.3.1.KR.1.COND",
-		title: "C.3.1.KR.1 conditional for sender type",
+		id: "MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED",
+		title: "WHO-UMC result required for WHO-UMC method",
 		severity: RuleSeverity::Reject,
-		note: "Expected when C.3.1 sender type is health professional.",
+		note: "If G.k.9.i.2.r.2.KR.1=1, G.k.9.i.2.r.3.KR.1 must be populated.",
 	},
 	MfdsRuleSpec {
-		id: "MFDS.KR.DOMESTIC.PRODUCTCODE.COND",
-		title: "KR product/ingredient domestic code conditional",
+		id: "MFDS.G.k.9.i.2.r.3.KR.2.REQUIRED",
+		title: "Korean algorithm result required for Korean method",
 		severity: RuleSeverity::Reject,
-		note: "For domestic post-marketed cases, KR product and ingredient codes become conditionally required.",
+		note: "If G.k.9.i.2.r.2.KR.1=2, G.k.9.i.2.r.3.KR.2 must be populated.",
 	},
 	MfdsRuleSpec {
-		id: "MFDS.KR.FOREIGN.WHOMPID.COND",
-		title: "KR product/ingredient WHO-MPID conditional",
+		id: "MFDS.G.k.9.i.2.r.3.KR.METHOD.MISMATCH",
+		title: "KR causality result constrained by method",
 		severity: RuleSeverity::Reject,
-		note: "For foreign-use products, WHO MPID pathway applies for KR product/ingredient fields.",
+		note: "G.k.9.i.2.r.3.KR.1 is only accepted with method 1 and KR.2 only with method 2.",
 	},
 	MfdsRuleSpec {
-		id: "MFDS.G.K.9.I.2.R.3.KR.SOURCEMETHOD",
-		title: "KR causality method/result values constrained by source",
+		id: "MFDS.G.k.9.i.2.r.1.REQUIRED",
+		title: "Source of assessment required with KR causality values",
 		severity: RuleSeverity::Reject,
-		note: "Allowed values for KR.1/KR.2 depend on source-of-assessment categories.",
+		note: "KR method/result values need the source of assessment (G.k.9.i.2.r.1).",
 	},
 ];
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::xml::validate::{find_canonical_rule, ValidationProfile};
+
+	#[test]
+	fn mfds_rules_match_catalog() {
+		for spec in MFDS_RULES {
+			let rule = find_canonical_rule(spec.id)
+				.unwrap_or_else(|| panic!("{} missing from catalog", spec.id));
+			assert_eq!(rule.profile, ValidationProfile::Mfds, "{}", spec.id);
+			assert_eq!(
+				rule.blocking,
+				spec.severity == RuleSeverity::Reject,
+				"{}",
+				spec.id
+			);
+		}
+	}
+}
@@ -0,0 +1,717 @@
+// MFDS KR regional elements on export.
+//
+// KR values are stored in their own columns and written back after the ICH
+// sections have been patched: each one becomes a `subjectOf/observation`
+// child of the node that owns it (paths in xml/mapping/mfds). Owner nodes are
+// matched to rows by document order, the same order the importer uses.
+
+use crate::model;
+use crate::model::ModelManager;
+use crate::xml::error::Error;
+use crate::xml::mapping::fda::e_reaction::EReactionPaths;
+use crate::xml::mapping::fda::g_drug::GDrugPaths;
+use crate::xml::mapping::mfds::c_safety_report::CMfdsSafetyReportPaths;
+use crate::xml::mapping::mfds::d_patient::DMfdsPatientPaths;
+use crate::xml::mapping::mfds::g_drug::GMfdsDrugPaths;
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::tree::{Document, Node, NodeType};
+use libxml::xpath::Context;
+use sqlx::types::Uuid;
+use std::collections::HashMap;
+
+/// Every KR observation written by this module, whatever its owner.
+const KR_OBSERVATIONS: &str =
+	"//hl7:subjectOf[hl7:observation/hl7:code[contains(@code,'.KR.')]]";
+
+/// KR.1a/KR.1b code pair (MFDS product or ingredient code).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KrCode {
+	pub version: Option<String>,
+	pub code: Option<String>,
+}
+
+/// KR method/results of one G.k.9.i.2.r assessment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KrAssessment {
+	/// 0-based drug position (G.k)
+	pub drug_index: usize,
+	/// 0-based reaction position (E.i)
+	pub reaction_index: usize,
+	/// 0-based position among the assessments of this drug/reaction pair
+	pub assessment_index: usize,
+	pub method: Option<String>,
+	pub result_who_umc: Option<String>,
+	pub result_korean: Option<String>,
+}
+
+/// KR values of one case, in document order.
+#[derive(Debug, Clone, Default)]
+pub struct KrElements {
+	pub sender_health_professional_type: Option<String>,
+	pub primary_sources: Vec<Option<String>>,
+	pub other_study_type: Option<String>,
+	pub past_drugs: Vec<KrCode>,
+	pub parent_past_drugs: Vec<KrCode>,
+	pub drugs: Vec<KrCode>,
+	/// Ingredient codes per drug, aligned with `drugs`
+	pub ingredients: Vec<Vec<KrCode>>,
+	pub assessments: Vec<KrAssessment>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct KrRelatednessRow {
+	drug_id: Uuid,
+	reaction_id: Uuid,
+	kr_method_of_assessment: Option<String>,
+	kr_result_who_umc: Option<String>,
+	kr_result_korean: Option<String>,
+}
+
+/// Loads the case's KR values and writes them into the export document.
+pub async fn apply_kr_elements_from_db(
+	doc: &mut Document,
+	parser: &Parser,
+	mm: &ModelManager,
+	case_id: Uuid,
+	xpath: &mut Context,
+) -> Result<()> {
+	let kr = fetch_kr_elements(mm, case_id).await?;
+	apply_kr_elements(doc, parser, xpath, &kr)
+}
+
+pub async fn fetch_kr_elements(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<KrElements> {
+	let sender: Option<(Option<String>,)> = mm
+		.dbx()
+		.fetch_optional(
+			sqlx::query_as(
+				"SELECT kr_health_professional_type FROM sender_information WHERE case_id = $1 LIMIT 1",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let primary_sources: Vec<(Option<String>,)> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT kr_other_health_professional FROM primary_sources WHERE case_id = $1 ORDER BY sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let study: Option<(Option<String>,)> = mm
+		.dbx()
+		.fetch_optional(
+			sqlx::query_as(
+				"SELECT kr_other_study_type FROM study_information WHERE case_id = $1 ORDER BY created_at ASC LIMIT 1",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let past_drugs: Vec<(Option<String>, Option<String>)> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT pdh.kr_product_code_version, pdh.kr_product_code
+				 FROM past_drug_history pdh
+				 JOIN patient_information p ON p.id = pdh.patient_id
+				 WHERE p.case_id = $1
+				 ORDER BY pdh.sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let parent_past_drugs: Vec<(Option<String>, Option<String>)> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT ppdh.kr_product_code_version, ppdh.kr_product_code
+				 FROM parent_past_drug_history ppdh
+				 JOIN parent_information pi ON pi.id = ppdh.parent_id
+				 JOIN patient_information p ON p.id = pi.patient_id
+				 WHERE p.case_id = $1
+				 ORDER BY ppdh.sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let drugs: Vec<(Uuid, Option<String>, Option<String>)> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT id, kr_product_code_version, kr_product_code FROM drug_information WHERE case_id = $1 ORDER BY sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let substances: Vec<(Uuid, Option<String>, Option<String>)> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT s.drug_id, s.kr_ingredient_code_version, s.kr_ingredient_code
+				 FROM drug_active_substances s
+				 JOIN drug_information d ON d.id = s.drug_id
+				 WHERE d.case_id = $1
+				 ORDER BY s.sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let reactions: Vec<(Uuid,)> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT id FROM reactions WHERE case_id = $1 ORDER BY sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let relatedness: Vec<KrRelatednessRow> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT dra.drug_id, dra.reaction_id, ra.kr_method_of_assessment,
+				        ra.kr_result_who_umc, ra.kr_result_korean
+				 FROM relatedness_assessments ra
+				 JOIN drug_reaction_assessments dra ON dra.id = ra.drug_reaction_assessment_id
+				 JOIN drug_information d ON d.id = dra.drug_id
+				 WHERE d.case_id = $1
+				 ORDER BY dra.drug_id, dra.reaction_id, ra.sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+
+	let drug_index: HashMap<Uuid, usize> = drugs
+		.iter()
+		.enumerate()
+		.map(|(idx, d)| (d.0, idx))
+		.collect();
+	let reaction_index: HashMap<Uuid, usize> = reactions
+		.iter()
+		.enumerate()
+		.map(|(idx, r)| (r.0, idx))
+		.collect();
+
+	let mut ingredients = vec![Vec::new(); drugs.len()];
+	for (drug_id, version, code) in substances {
+		if let Some(idx) = drug_index.get(&drug_id) {
+			ingredients[*idx].push(KrCode { version, code });
+		}
+	}
+
+	let mut pair_counts: HashMap<(usize, usize), usize> = HashMap::new();
+	let mut assessments = Vec::new();
+	for row in relatedness {
+		let (Some(d), Some(r)) = (
+			drug_index.get(&row.drug_id),
+			reaction_index.get(&row.reaction_id),
+		) else {
+			continue;
+		};
+		let count = pair_counts.entry((*d, *r)).or_insert(0);
+		assessments.push(KrAssessment {
+			drug_index: *d,
+			reaction_index: *r,
+			assessment_index: *count,
+			method: row.kr_method_of_assessment,
+			result_who_umc: row.kr_result_who_umc,
+			result_korean: row.kr_result_korean,
+		});
+		*count += 1;
+	}
+
+	Ok(KrElements {
+		sender_health_professional_type: sender.and_then(|v| v.0),
+		primary_sources: primary_sources.into_iter().map(|v| v.0).collect(),
+		other_study_type: study.and_then(|v| v.0),
+		past_drugs: past_drugs
+			.into_iter()
+			.map(|(version, code)| KrCode { version, code })
+			.collect(),
+		parent_past_drugs: parent_past_drugs
+			.into_iter()
+			.map(|(version, code)| KrCode { version, code })
+			.collect(),
+		drugs: drugs
+			.into_iter()
+			.map(|(_, version, code)| KrCode { version, code })
+			.collect(),
+		ingredients,
+		assessments,
+	})
+}
+
+/// Replaces the KR observations in `doc` with the given values.
+pub fn apply_kr_elements(
+	doc: &mut Document,
+	parser: &Parser,
+	xpath: &mut Context,
+	kr: &KrElements,
+) -> Result<()> {
+	if let Ok(nodes) = xpath.findnodes(KR_OBSERVATIONS, None) {
+		for mut node in nodes {
+			node.unlink_node();
+		}
+	}
+
+	if let Some(sender) = find_nodes(xpath, CMfdsSafetyReportPaths::SENDER, None)
+		.into_iter()
+		.next()
+	{
+		let fragment = single_fragment(
+			"C.3.1.KR.1",
+			kr.sender_health_professional_type.as_deref(),
+		);
+		append_fragment(doc, parser, sender, fragment)?;
+	}
+	let sources = find_nodes(xpath, CMfdsSafetyReportPaths::PRIMARY_SOURCE, None);
+	for (node, value) in sources.into_iter().zip(&kr.primary_sources) {
+		let fragment = single_fragment("C.2.r.4.KR.1", value.as_deref());
+		append_fragment(doc, parser, node, fragment)?;
+	}
+	if let Some(study) =
+		find_nodes(xpath, CMfdsSafetyReportPaths::RESEARCH_STUDY, None)
+			.into_iter()
+			.next()
+	{
+		let fragment = single_fragment("C.5.4.KR.1", kr.other_study_type.as_deref());
+		append_fragment(doc, parser, study, fragment)?;
+	}
+
+	let past_drugs = find_nodes(xpath, DMfdsPatientPaths::PAST_DRUG, None);
+	for (node, code) in past_drugs.into_iter().zip(&kr.past_drugs) {
+		if let Some(product) =
+			find_nodes(xpath, DMfdsPatientPaths::PAST_DRUG_PRODUCT, Some(&node))
+				.into_iter()
+				.next()
+		{
+			let fragment = code_fragment("D.8.r.1.KR.1b", code);
+			append_fragment(doc, parser, product, fragment)?;
+		}
+	}
+	let parent_past_drugs =
+		find_nodes(xpath, DMfdsPatientPaths::PARENT_PAST_DRUG, None);
+	for (node, code) in parent_past_drugs.into_iter().zip(&kr.parent_past_drugs) {
+		if let Some(product) =
+			find_nodes(xpath, DMfdsPatientPaths::PAST_DRUG_PRODUCT, Some(&node))
+				.into_iter()
+				.next()
+		{
+			let fragment = code_fragment("D.10.8.r.1.KR.1b", code);
+			append_fragment(doc, parser, product, fragment)?;
+		}
+	}
+
+	let drugs = find_nodes(xpath, GMfdsDrugPaths::DRUG, None);
+	let mut drug_ids: HashMap<String, usize> = HashMap::new();
+	for (idx, node) in drugs.iter().enumerate() {
+		if let Some(id) = first_value(xpath, GDrugPaths::XML_ID_ROOT, node) {
+			drug_ids.entry(id).or_insert(idx);
+		}
+		if let Some(code) = kr.drugs.get(idx) {
+			if let Some(product) =
+				find_nodes(xpath, GMfdsDrugPaths::PRODUCT, Some(node))
+					.into_iter()
+					.next()
+			{
+				let fragment = code_fragment("G.k.2.1.KR.1b", code);
+				append_fragment(doc, parser, product, fragment)?;
+			}
+		}
+		let ingredients = kr.ingredients.get(idx).map(Vec::as_slice).unwrap_or(&[]);
+		let nodes = find_nodes(xpath, GMfdsDrugPaths::INGREDIENT, Some(node));
+		for (ingredient, code) in nodes.into_iter().zip(ingredients) {
+			let fragment = code_fragment("G.k.2.3.r.1.KR.1b", code);
+			append_fragment(doc, parser, ingredient, fragment)?;
+		}
+	}
+
+	let mut reaction_ids: HashMap<String, usize> = HashMap::new();
+	for (idx, node) in find_nodes(xpath, EReactionPaths::REACTION_NODE, None)
+		.iter()
+		.enumerate()
+	{
+		if let Some(id) = first_value(xpath, EReactionPaths::XML_ID_ROOT, node) {
+			reaction_ids.entry(id).or_insert(idx);
+		}
+	}
+
+	let mut pair_counts: HashMap<(usize, usize), usize> = HashMap::new();
+	for node in find_nodes(xpath, GMfdsDrugPaths::CAUSALITY_ASSESSMENT, None) {
+		let drug = first_value(
+			xpath,
+			"hl7:subject2/hl7:productUseReference/hl7:id/@root",
+			&node,
+		)
+		.and_then(|id| drug_ids.get(&id).copied());
+		let reaction = first_value(
+			xpath,
+			"hl7:subject1/hl7:adverseEffectReference/hl7:id/@root",
+			&node,
+		)
+		.and_then(|id| reaction_ids.get(&id).copied());
+		let (Some(drug), Some(reaction)) = (drug, reaction) else {
+			continue;
+		};
+		let count = pair_counts.entry((drug, reaction)).or_insert(0);
+		let position = *count;
+		*count += 1;
+		let Some(assessment) = kr.assessments.iter().find(|a| {
+			a.drug_index == drug
+				&& a.reaction_index == reaction
+				&& a.assessment_index == position
+		}) else {
+			continue;
+		};
+		let fragment = [
+			single_fragment("G.k.9.i.2.r.2.KR.1", assessment.method.as_deref()),
+			single_fragment(
+				"G.k.9.i.2.r.3.KR.1",
+				assessment.result_who_umc.as_deref(),
+			),
+			single_fragment(
+				"G.k.9.i.2.r.3.KR.2",
+				assessment.result_korean.as_deref(),
+			),
+		]
+		.into_iter()
+		.flatten()
+		.collect::<String>();
+		append_fragment(doc, parser, node, Some(fragment))?;
+	}
+
+	Ok(())
+}
+
+fn single_fragment(element: &str, value: Option<&str>) -> Option<String> {
+	let value = non_empty(value)?;
+	Some(observation_fragment(
+		element,
+		&format!("code=\"{}\"", xml_escape(value)),
+	))
+}
+
+fn code_fragment(element: &str, code: &KrCode) -> Option<String> {
+	let value = non_empty(code.code.as_deref())?;
+	let mut attrs = format!("code=\"{}\"", xml_escape(value));
+	if let Some(version) = non_empty(code.version.as_deref()) {
+		attrs.push_str(&format!(" codeSystemVersion=\"{}\"", xml_escape(version)));
+	}
+	Some(observation_fragment(element, &attrs))
+}
+
+fn observation_fragment(element: &str, value_attrs: &str) -> String {
+	format!(
+		"<subjectOf typeCode=\"SBJ\"><observation classCode=\"OBS\" moodCode=\"EVN\"><code code=\"{element}\"/><value xsi:type=\"CE\" {value_attrs}/></observation></subjectOf>"
+	)
+}
+
+fn append_fragment(
+	doc: &mut Document,
+	parser: &Parser,
+	mut owner: Node,
+	fragment: Option<String>,
+) -> Result<()> {
+	let Some(fragment) = fragment.filter(|f| !f.is_empty()) else {
+		return Ok(());
+	};
+	let wrapped = format!(
+		"<wrapper xmlns=\"urn:hl7-org:v3\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">{fragment}</wrapper>"
+	);
+	let frag_doc =
+		parser
+			.parse_string(&wrapped)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("XML parse error: {err}"),
+				line: None,
+				column: None,
+			})?;
+	let root = frag_doc.get_root_element().ok_or(Error::InvalidXml {
+		message: "Failed to get fragment root".to_string(),
+		line: None,
+		column: None,
+	})?;
+	for mut child in root
+		.get_child_nodes()
+		.into_iter()
+		.filter(|n| n.get_type() == Some(NodeType::ElementNode))
+	{
+		child.unlink_node();
+		let mut node =
+			doc.import_node(&mut child).map_err(|_| Error::InvalidXml {
+				message: "Failed to import cloned node".to_string(),
+				line: None,
+				column: None,
+			})?;
+		owner
+			.add_child(&mut node)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("Failed to append fragment: {err}"),
+				line: None,
+				column: None,
+			})?;
+	}
+	Ok(())
+}
+
+fn find_nodes(xpath: &mut Context, path: &str, node: Option<&Node>) -> Vec<Node> {
+	xpath.findnodes(path, node).unwrap_or_default()
+}
+
+fn first_value(xpath: &mut Context, path: &str, node: &Node) -> Option<String> {
+	xpath
+		.findvalues(path, Some(node))
+		.ok()
+		.and_then(|values| values.into_iter().next())
+		.filter(|v| !v.trim().is_empty())
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+	value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn xml_escape(input: &str) -> String {
+	input
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn skeleton() -> (Parser, Document) {
+		let parser = Parser::default();
+		let doc = parser
+			.parse_string(include_str!(
+				"../../../../../../docs/refs/instances/FAERS2022Scenario1.xml"
+			))
+			.expect("skeleton parses");
+		(parser, doc)
+	}
+
+	fn context(doc: &Document) -> Context {
+		let xpath = Context::new(doc).expect("xpath context");
+		let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+		let _ = xpath
+			.register_namespace("xsi", "http://www.w3.org/2001/XMLSchema-instance");
+		xpath
+	}
+
+	fn values(xpath: &mut Context, path: &str) -> Vec<String> {
+		xpath.findvalues(path, None).unwrap_or_default()
+	}
+
+	fn code(version: &str, code: &str) -> KrCode {
+		KrCode {
+			version: Some(version.to_string()),
+			code: Some(code.to_string()),
+		}
+	}
+
+	#[test]
+	fn kr_elements_written_under_owner_nodes() {
+		let (parser, mut doc) = skeleton();
+		let mut xpath = context(&doc);
+		let kr = KrElements {
+			sender_health_professional_type: Some("1".to_string()),
+			past_drugs: vec![code("1", "KR-PAST-1")],
+			drugs: vec![code("2", "KR-DRUG-1")],
+			ingredients: vec![vec![code("3", "KR-ING-1")]],
+			..Default::default()
+		};
+
+		apply_kr_elements(&mut doc, &parser, &mut xpath, &kr).unwrap();
+
+		let mut xpath = context(&doc);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!(
+					"{}/{}/@code",
+					CMfdsSafetyReportPaths::SENDER,
+					CMfdsSafetyReportPaths::HEALTH_PROFESSIONAL_TYPE
+				)
+			),
+			vec!["1"]
+		);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!(
+					"{}/{}/@code",
+					DMfdsPatientPaths::PAST_DRUG,
+					DMfdsPatientPaths::PAST_DRUG_PRODUCT_CODE
+				)
+			),
+			vec!["KR-PAST-1"]
+		);
+		let drug_code = format!(
+			"({})[1]/{}",
+			GMfdsDrugPaths::DRUG,
+			GMfdsDrugPaths::PRODUCT_CODE
+		);
+		assert_eq!(
+			values(&mut xpath, &format!("{drug_code}/@code")),
+			vec!["KR-DRUG-1"]
+		);
+		assert_eq!(
+			values(&mut xpath, &format!("{drug_code}/@codeSystemVersion")),
+			vec!["2"]
+		);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!(
+					"({})[1]/{}/{}/@code",
+					GMfdsDrugPaths::DRUG,
+					GMfdsDrugPaths::INGREDIENT,
+					GMfdsDrugPaths::INGREDIENT_CODE
+				)
+			),
+			vec!["KR-ING-1"]
+		);
+	}
+
+	#[test]
+	fn kr_elements_replace_previous_values() {
+		let (parser, mut doc) = skeleton();
+		let mut xpath = context(&doc);
+		let first = KrElements {
+			drugs: vec![code("1", "OLD")],
+			..Default::default()
+		};
+		apply_kr_elements(&mut doc, &parser, &mut xpath, &first).unwrap();
+		let second = KrElements {
+			drugs: vec![code("1", "NEW")],
+			..Default::default()
+		};
+		let mut xpath = context(&doc);
+		apply_kr_elements(&mut doc, &parser, &mut xpath, &second).unwrap();
+
+		let mut xpath = context(&doc);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!(
+					"{}/{}/@code",
+					GMfdsDrugPaths::DRUG,
+					GMfdsDrugPaths::PRODUCT_CODE
+				)
+			),
+			vec!["NEW"]
+		);
+	}
+
+	#[test]
+	fn kr_assessment_follows_drug_and_reaction_references() {
+		let (parser, mut doc) = skeleton();
+		let mut xpath = context(&doc);
+		let drug_ids = values(
+			&mut xpath,
+			&format!("{}/{}", GMfdsDrugPaths::DRUG, GDrugPaths::XML_ID_ROOT),
+		);
+		let reaction_ids = values(
+			&mut xpath,
+			&format!(
+				"{}/{}",
+				EReactionPaths::REACTION_NODE,
+				EReactionPaths::XML_ID_ROOT
+			),
+		);
+		let target =
+			find_nodes(&mut xpath, GMfdsDrugPaths::CAUSALITY_ASSESSMENT, None)
+				.iter()
+				.find_map(|node| {
+					let drug = first_value(
+						&mut xpath,
+						"hl7:subject2/hl7:productUseReference/hl7:id/@root",
+						node,
+					)?;
+					let reaction = first_value(
+						&mut xpath,
+						"hl7:subject1/hl7:adverseEffectReference/hl7:id/@root",
+						node,
+					)?;
+					Some((
+						drug_ids.iter().position(|id| *id == drug)?,
+						reaction_ids.iter().position(|id| *id == reaction)?,
+						drug,
+						reaction,
+					))
+				})
+				.expect("skeleton has a linked causality assessment");
+
+		let kr = KrElements {
+			assessments: vec![KrAssessment {
+				drug_index: target.0,
+				reaction_index: target.1,
+				assessment_index: 0,
+				method: Some("1".to_string()),
+				result_who_umc: Some("2".to_string()),
+				result_korean: None,
+			}],
+			..Default::default()
+		};
+		apply_kr_elements(&mut doc, &parser, &mut xpath, &kr).unwrap();
+
+		let mut xpath = context(&doc);
+		let assessment = format!(
+			"({}[hl7:subject2/hl7:productUseReference/hl7:id/@root='{}' and hl7:subject1/hl7:adverseEffectReference/hl7:id/@root='{}'])[1]",
+			GMfdsDrugPaths::CAUSALITY_ASSESSMENT,
+			target.2,
+			target.3
+		);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!(
+					"{assessment}/{}/@code",
+					GMfdsDrugPaths::METHOD_OF_ASSESSMENT
+				)
+			),
+			vec!["1"]
+		);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!("{assessment}/{}/@code", GMfdsDrugPaths::RESULT_WHO_UMC)
+			),
+			vec!["2"]
+		);
+		assert!(values(
+			&mut xpath,
+			&format!(
+				"{}/{}",
+				GMfdsDrugPaths::CAUSALITY_ASSESSMENT,
+				GMfdsDrugPaths::RESULT_KOREAN
+			)
+		)
+		.is_empty());
+	}
+}
@@ -1,4 +1,5 @@
 // MFDS-specific code lists and business rules (Korea profile on top of ICH core).
 pub mod business_rules;
 pub mod codes;
+pub mod export;
 pub mod validation;
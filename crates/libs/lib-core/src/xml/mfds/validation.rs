@@ -1,11 +1,17 @@
 use crate::ctx::Ctx;
 use crate::model::drug::{DrugActiveSubstance, DrugInformation};
-use crate::model::safety_report::SenderInformation;
+use crate::model::parent_history::ParentPastDrugHistory;
+use crate::model::patient::PastDrugHistory;
+use crate::model::safety_report::{PrimarySource, SenderInformation, StudyInformation};
+use crate::model::terminology::E2bCodeListBmc;
 use crate::model::{ModelManager, Result};
 use crate::xml::validate::{
-	build_report, has_text, CaseValidationReport,
+	build_report, has_text, load_rule_set, CaseValidationReport,
 	ValidationIssue, ValidationProfile, RuleFacts,
 	push_issue_if_conditioned_value_invalid, push_issue_if_condition_violated,
+	push_issue_if_code_not_in_list, KR_ASSESSMENT_METHOD,
+	KR_ASSESSMENT_RESULT_KOREAN, KR_ASSESSMENT_RESULT_WHO_UMC,
+	KR_HEALTH_PROFESSIONAL_TYPE, KR_OTHER_HEALTH_PROFESSIONAL, KR_OTHER_STUDY_TYPE,
 };
 use sqlx::types::Uuid;
 
@@ -31,8 +37,9 @@ struct RelatednessWithDrug {
 	pub drug_id: Uuid,
 	pub relatedness_sequence_number: i32,
 	pub source_of_assessment: Option<String>,
-	pub method_of_assessment: Option<String>,
-	pub result_of_assessment: Option<String>,
+	pub kr_method_of_assessment: Option<String>,
+	pub kr_result_who_umc: Option<String>,
+	pub kr_result_korean: Option<String>,
 }
 
 async fn list_relatedness_by_case(
@@ -43,8 +50,9 @@ async fn list_relatedness_by_case(
 SELECT di.id as drug_id
      , ra.sequence_number as relatedness_sequence_number
      , ra.source_of_assessment
-     , ra.method_of_assessment
-     , ra.result_of_assessment
+     , ra.kr_method_of_assessment
+     , ra.kr_result_who_umc
+     , ra.kr_result_korean
 FROM relatedness_assessments ra
 JOIN drug_reaction_assessments dra ON dra.id = ra.drug_reaction_assessment_id
 JOIN drug_information di ON di.id = dra.drug_id
@@ -68,6 +76,64 @@ async fn list_senders_by_case(
 		.map_err(Into::into)
 }
 
+async fn list_primary_sources_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<PrimarySource>> {
+	let sql =
+		"SELECT * FROM primary_sources WHERE case_id = $1 ORDER BY sequence_number";
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, PrimarySource>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+async fn get_study_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Option<StudyInformation>> {
+	let sql = "SELECT * FROM study_information WHERE case_id = $1 ORDER BY created_at LIMIT 1";
+	mm.dbx()
+		.fetch_optional(sqlx::query_as::<_, StudyInformation>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+async fn list_past_drugs_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<PastDrugHistory>> {
+	let sql = r#"
+SELECT pdh.*
+FROM past_drug_history pdh
+JOIN patient_information pi ON pi.id = pdh.patient_id
+WHERE pi.case_id = $1
+ORDER BY pdh.sequence_number
+"#;
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, PastDrugHistory>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+async fn list_parent_past_drugs_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<ParentPastDrugHistory>> {
+	let sql = r#"
+SELECT ppdh.*
+FROM parent_past_drug_history ppdh
+JOIN parent_information par ON par.id = ppdh.parent_id
+JOIN patient_information pi ON pi.id = par.patient_id
+WHERE pi.case_id = $1
+ORDER BY ppdh.sequence_number
+"#;
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, ParentPastDrugHistory>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
 fn push_mfds_required_issue(
 	issues: &mut Vec<ValidationIssue>,
 	code: &str,
@@ -88,32 +154,151 @@ fn push_mfds_required_issue(
 	);
 }
 
+/// KR.1a code version is required once the KR.1b code is given.
+fn push_mfds_code_version_issue(
+	issues: &mut Vec<ValidationIssue>,
+	code: &str,
+	path: String,
+	version: Option<&str>,
+	kr_code: Option<&str>,
+) {
+	push_mfds_required_issue(
+		issues,
+		code,
+		path,
+		version,
+		RuleFacts {
+			mfds_kr_code_present: Some(has_text(kr_code)),
+			..RuleFacts::default()
+		},
+	);
+}
+
+fn is_code(value: Option<&str>, code: &str) -> bool {
+	value.map(str::trim) == Some(code)
+}
+
 pub async fn validate_case(
 	ctx: &Ctx,
 	mm: &ModelManager,
 	case_id: Uuid,
 ) -> Result<CaseValidationReport> {
 	let ich_report = crate::xml::ich::validation::validate_case(ctx, mm, case_id).await?;
+	let rules = load_rule_set(ctx, mm).await?;
 	let drugs: Vec<DrugInformation> =
 		crate::model::drug::DrugInformationBmc::list_by_case(ctx, mm, case_id)
 			.await?;
 	let senders = list_senders_by_case(mm, case_id).await?;
+	let primary_sources = list_primary_sources_by_case(mm, case_id).await?;
+	let study = get_study_by_case(mm, case_id).await?;
+	let past_drugs = list_past_drugs_by_case(mm, case_id).await?;
+	let parent_past_drugs = list_parent_past_drugs_by_case(mm, case_id).await?;
 	let active_substances = list_active_substances_by_case(mm, case_id).await?;
 	let relatedness = list_relatedness_by_case(mm, case_id).await?;
+	let codes = E2bCodeListBmc::catalog(ctx, mm).await?;
 
 	let mut issues: Vec<ValidationIssue> = ich_report.issues;
 
-	// MFDS-specific checks (KR profile): only rules backed by persisted fields.
+	// MFDS-specific checks (KR profile).
 	senders.iter().enumerate().for_each(|(idx, sender)| {
-		let _ = push_issue_if_condition_violated(
+		let path = format!("senderInformation.{idx}.krHealthProfessionalType");
+		push_mfds_required_issue(
 			&mut issues,
 			"MFDS.C.3.1.KR.1.REQUIRED",
-			format!("senderInformation.{idx}.senderType"),
+			path.clone(),
+			sender.kr_health_professional_type.as_deref(),
+			RuleFacts {
+				mfds_sender_health_professional: Some(is_code(
+					Some(&sender.sender_type),
+					"3",
+				)),
+				..RuleFacts::default()
+			},
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			KR_HEALTH_PROFESSIONAL_TYPE,
+			path,
+			sender.kr_health_professional_type.as_deref(),
+		);
+	});
+
+	primary_sources.iter().enumerate().for_each(|(idx, source)| {
+		let path = format!("primarySources.{idx}.krOtherHealthProfessional");
+		let value = source.kr_other_health_professional.as_deref();
+		let facts = RuleFacts {
+			mfds_reporter_other_health_professional: Some(is_code(
+				source.qualification.as_deref(),
+				"3",
+			)),
+			..RuleFacts::default()
+		};
+		push_mfds_required_issue(
+			&mut issues,
+			"MFDS.C.2.r.4.KR.1.REQUIRED",
+			path.clone(),
+			value,
+			facts,
+		);
+		push_mfds_required_issue(
+			&mut issues,
+			"MFDS.C.2.r.4.KR.1.FORBIDDEN",
+			path.clone(),
+			value,
+			facts,
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			KR_OTHER_HEALTH_PROFESSIONAL,
+			path,
+			value,
+		);
+	});
+
+	if let Some(study) = &study {
+		let path = "studyInformation.krOtherStudyType".to_string();
+		push_mfds_required_issue(
+			&mut issues,
+			"MFDS.C.5.4.KR.1.REQUIRED",
+			path.clone(),
+			study.kr_other_study_type.as_deref(),
 			RuleFacts {
-				mfds_sender_type_disallowed: Some(sender.sender_type.trim() == "3"),
+				mfds_study_type_other: Some(is_code(
+					study.study_type_reaction.as_deref(),
+					"3",
+				)),
 				..RuleFacts::default()
 			},
 		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			KR_OTHER_STUDY_TYPE,
+			path,
+			study.kr_other_study_type.as_deref(),
+		);
+	}
+
+	past_drugs.iter().enumerate().for_each(|(idx, drug)| {
+		push_mfds_code_version_issue(
+			&mut issues,
+			"MFDS.D.8.r.1.KR.1a.REQUIRED",
+			format!("patientInformation.pastDrugHistory.{idx}.krProductCodeVersion"),
+			drug.kr_product_code_version.as_deref(),
+			drug.kr_product_code.as_deref(),
+		);
+	});
+
+	parent_past_drugs.iter().enumerate().for_each(|(idx, drug)| {
+		push_mfds_code_version_issue(
+			&mut issues,
+			"MFDS.D.10.8.r.1.KR.1a.REQUIRED",
+			format!("parentInformation.pastDrugHistory.{idx}.krProductCodeVersion"),
+			drug.kr_product_code_version.as_deref(),
+			drug.kr_product_code.as_deref(),
+		);
 	});
 
 	let mut domestic_drug_ids = std::collections::HashSet::new();
@@ -130,8 +315,8 @@ pub async fn validate_case(
 				push_mfds_required_issue(
 					&mut issues,
 					"MFDS.KR.DOMESTIC.PRODUCTCODE.REQUIRED",
-					format!("drugs.{idx}.mpid"),
-					drug.mpid.as_deref(),
+					format!("drugs.{idx}.krProductCode"),
+					drug.kr_product_code.as_deref(),
 					RuleFacts {
 						mfds_drug_domestic_kr: Some(is_domestic_kr),
 						..RuleFacts::default()
@@ -152,6 +337,13 @@ pub async fn validate_case(
 			}
 			_ => {}
 		}
+		push_mfds_code_version_issue(
+			&mut issues,
+			"MFDS.G.k.2.1.KR.1a.REQUIRED",
+			format!("drugs.{idx}.krProductCodeVersion"),
+			drug.kr_product_code_version.as_deref(),
+			drug.kr_product_code.as_deref(),
+		);
 	});
 
 	active_substances.iter().for_each(|substance| {
@@ -160,17 +352,17 @@ pub async fn validate_case(
 			.sequence_number
 			.checked_sub(1)
 			.and_then(|v| usize::try_from(v).ok());
-		let path = match (drug_index, substance_index) {
+		let path_for = |field: &str| match (drug_index, substance_index) {
 			(Some(d_idx), Some(s_idx)) => {
-				format!("drugs.{d_idx}.activeSubstances.{s_idx}.substanceTermId")
+				format!("drugs.{d_idx}.activeSubstances.{s_idx}.{field}")
 			}
 			_ => "drugs".to_string(),
 		};
 		push_mfds_required_issue(
 			&mut issues,
 			"MFDS.KR.DOMESTIC.INGREDIENTCODE.REQUIRED",
-			path,
-			substance.substance_termid.as_deref(),
+			path_for("krIngredientCode"),
+			substance.kr_ingredient_code.as_deref(),
 			RuleFacts {
 				mfds_drug_domestic_kr: Some(
 					domestic_drug_ids.contains(&substance.drug_id),
@@ -178,12 +370,23 @@ pub async fn validate_case(
 				..RuleFacts::default()
 			},
 		);
+		push_mfds_code_version_issue(
+			&mut issues,
+			"MFDS.G.k.2.3.r.1.KR.1a.REQUIRED",
+			path_for("krIngredientCodeVersion"),
+			substance.kr_ingredient_code_version.as_deref(),
+			substance.kr_ingredient_code.as_deref(),
+		);
 	});
 
 	relatedness.iter().for_each(|r| {
+		let method = r.kr_method_of_assessment.as_deref();
 		let has_source = has_text(r.source_of_assessment.as_deref());
-		let has_method = has_text(r.method_of_assessment.as_deref());
-		let has_result = has_text(r.result_of_assessment.as_deref());
+		let has_method = has_text(method);
+		let has_who_umc = has_text(r.kr_result_who_umc.as_deref());
+		let has_korean = has_text(r.kr_result_korean.as_deref());
+		let method_who_umc = is_code(method, "1");
+		let method_korean = is_code(method, "2");
 		let drug_index = drug_index_by_id.get(&r.drug_id).copied();
 		let assess_index = r
 			.relatedness_sequence_number
@@ -199,8 +402,8 @@ pub async fn validate_case(
 		push_mfds_required_issue(
 			&mut issues,
 			"MFDS.G.k.9.i.2.r.2.KR.1.REQUIRED",
-			path_for("methodOfAssessment"),
-			r.method_of_assessment.as_deref(),
+			path_for("krMethodOfAssessment"),
+			method,
 			RuleFacts {
 				mfds_relatedness_source_present: Some(has_source),
 				..RuleFacts::default()
@@ -209,13 +412,56 @@ pub async fn validate_case(
 		push_mfds_required_issue(
 			&mut issues,
 			"MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED",
-			path_for("resultOfAssessment"),
-			r.result_of_assessment.as_deref(),
+			path_for("krResultWhoUmc"),
+			r.kr_result_who_umc.as_deref(),
 			RuleFacts {
-				mfds_relatedness_source_present: Some(has_source),
+				mfds_kr_method_who_umc: Some(method_who_umc),
+				..RuleFacts::default()
+			},
+		);
+		push_mfds_required_issue(
+			&mut issues,
+			"MFDS.G.k.9.i.2.r.3.KR.2.REQUIRED",
+			path_for("krResultKorean"),
+			r.kr_result_korean.as_deref(),
+			RuleFacts {
+				mfds_kr_method_korean: Some(method_korean),
 				..RuleFacts::default()
 			},
 		);
+		// WHO-UMC results go with method 1 and Korean results with method 2.
+		let _ = push_issue_if_condition_violated(
+			&mut issues,
+			"MFDS.G.k.9.i.2.r.3.KR.METHOD.MISMATCH",
+			path_for("krMethodOfAssessment"),
+			RuleFacts {
+				mfds_kr_result_method_mismatch: Some(
+					(has_who_umc && !method_who_umc) || (has_korean && !method_korean),
+				),
+				..RuleFacts::default()
+			},
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			KR_ASSESSMENT_METHOD,
+			path_for("krMethodOfAssessment"),
+			method,
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			KR_ASSESSMENT_RESULT_WHO_UMC,
+			path_for("krResultWhoUmc"),
+			r.kr_result_who_umc.as_deref(),
+		);
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			KR_ASSESSMENT_RESULT_KOREAN,
+			path_for("krResultKorean"),
+			r.kr_result_korean.as_deref(),
+		);
 		if !has_source
 		{
 			push_mfds_required_issue(
@@ -225,12 +471,12 @@ pub async fn validate_case(
 				r.source_of_assessment.as_deref(),
 				RuleFacts {
 					mfds_relatedness_method_present: Some(has_method),
-					mfds_relatedness_result_present: Some(has_result),
+					mfds_relatedness_result_present: Some(has_who_umc || has_korean),
 					..RuleFacts::default()
 				},
 			);
 		}
 	});
 
-	Ok(build_report(ValidationProfile::Mfds, case_id, issues))
+	Ok(build_report(ValidationProfile::Mfds, case_id, rules.apply(issues)))
 }
@@ -1,3 +1,7 @@
+pub mod ack;
+pub mod batch;
+pub mod diff;
+pub mod ema;
 pub mod export;
 mod export_postprocess;
 pub mod export_sections;
@@ -8,6 +12,9 @@ pub mod import_sections;
 pub mod mapping;
 pub mod mfds;
 pub mod model;
+pub mod nullification;
+pub mod pmda;
+pub mod r2;
 pub mod raw;
 pub mod validate;
 
@@ -17,13 +24,31 @@ pub mod types;
 pub mod xml_validation;
 mod xml_validation_fda;
 mod xml_validation_ich;
+pub mod xml_validation_rules;
 
 pub use error::Error;
 pub type Result<T> = core::result::Result<T, Error>;
 
-pub use export::export_case_xml;
-pub use import::{import_e2b_xml, XmlImportRequest};
+pub use ack::{build_import_ack_xml, import_ack_xml, parse_ack_xml, render_ack_xml};
+pub use diff::{diff_case_version_snapshots, parse_case_document};
+pub use export::{export_case_xml, export_cases_batch_xml};
+pub use import::{
+	import_e2b_xml, import_e2b_xml_batch, import_e2b_xml_batch_with_ack,
+	XmlImportRequest,
+};
+pub use nullification::nullify_case_xml;
 pub use parser::parse_e2b_xml;
+pub use r2::{export_case_r2_xml, import_r2_xml, parse_r2_xml};
 pub use types::ParsedE2b;
-pub use types::{XmlImportResult, XmlValidationError, XmlValidationReport};
-pub use xml_validation::{validate_e2b_xml, XmlValidatorConfig};
+pub use types::{
+	XmlAckGenerationRequest, XmlAckImportResult, XmlAckMessageResult,
+	XmlBatchExportRequest, XmlBatchExportResult, XmlBatchImportResult,
+	XmlImportReportError, XmlImportReportResult,
+	XmlImportResult, XmlNullificationResult, XmlR2ExportResult,
+	XmlR2ImportReportResult, XmlR2ImportResult, XmlValidationError,
+	XmlValidationReport,
+};
+pub use xml_validation::{
+	init_default_xml_rules, validate_e2b_xml, XmlValidatorConfig,
+};
+pub use xml_validation_rules::{load_xml_rule_files, parse_xml_rules, XmlRuleSet};
@@ -0,0 +1,127 @@
+// C.1.11 nullification reports
+
+use crate::ctx::Ctx;
+use crate::model;
+use crate::model::case_workflow::{
+	CaseWorkflowBmc, TransitionTrigger, STATUS_VALIDATED,
+};
+use crate::model::follow_up::CaseFollowUpBmc;
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::ModelManager;
+use crate::xml::error::Error;
+use crate::xml::export::export_case_xml;
+use crate::xml::types::XmlNullificationResult;
+use crate::xml::xml_validation::{should_skip_xml_validation, validate_e2b_xml};
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::xpath::Context;
+
+/// Children of the investigation event a nullification report carries: C.1.1
+/// and C.1.8.1 (`id`), C.1.4/C.1.5, the C.3 sender (`subjectOf1`) and the C.1.3
+/// report type and C.1.11.1/C.1.11.2 characteristics. Everything else (patient,
+/// reactions, drugs, narrative, linked reports) is dropped, with the comments
+/// describing it.
+const DROPPED_INVESTIGATION_CHILDREN_XPATH: &str = "//hl7:investigationEvent/*[not(self::hl7:id or self::hl7:code or self::hl7:statusCode or self::hl7:effectiveTime or self::hl7:availabilityTime or self::hl7:subjectOf1 or self::hl7:subjectOf2[hl7:investigationCharacteristic/hl7:code[@code='1' or @code='3' or @code='4']])] | //hl7:investigationEvent/comment()";
+
+/// Nullifies a submitted case and exports the nullification ICSR.
+///
+/// The new version (C.1.11.1 = 1, C.1.11.2 = `reason`) is created, the
+/// original case is marked nullified and the report is exported and validated
+/// in one transaction, so a report that fails validation leaves nothing behind.
+/// The exported report keeps the N batch/message wrapper and only the C.1 and
+/// C.3 elements a nullification needs (see [`minimal_nullification_xml`]).
+pub async fn nullify_case_xml(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: sqlx::types::Uuid,
+	reason: String,
+) -> Result<XmlNullificationResult> {
+	let mm = mm.new_with_txn().map_err(Error::from)?;
+	mm.dbx().begin_txn().await.map_err(model::Error::from)?;
+	set_full_context_dbx_or_rollback(
+		mm.dbx(),
+		ctx.user_id(),
+		ctx.organization_id(),
+		ctx.role(),
+	)
+	.await?;
+	match nullify_and_export(ctx, &mm, case_id, reason).await {
+		Ok(result) => {
+			mm.dbx().commit_txn().await.map_err(model::Error::from)?;
+			Ok(result)
+		}
+		Err(err) => {
+			let _ = mm.dbx().rollback_txn().await;
+			Err(err)
+		}
+	}
+}
+
+async fn nullify_and_export(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: sqlx::types::Uuid,
+	reason: String,
+) -> Result<XmlNullificationResult> {
+	let nullification_case_id = CaseFollowUpBmc::nullify(ctx, mm, case_id, reason)
+		.await
+		.map_err(Error::from)?;
+	let xml = export_case_xml(ctx, mm, nullification_case_id).await?;
+	let xml = minimal_nullification_xml(&xml)?;
+
+	let validation = validate_e2b_xml(xml.as_bytes(), None)?;
+	if !validation.ok && !should_skip_xml_validation() {
+		return Err(Error::XsdValidationFailed {
+			errors: validation.errors,
+		});
+	}
+	let case = CaseWorkflowBmc::transition_in_txn(
+		ctx,
+		mm,
+		nullification_case_id,
+		STATUS_VALIDATED,
+		TransitionTrigger::Validator,
+		None,
+	)
+	.await
+	.map_err(Error::from)?;
+
+	Ok(XmlNullificationResult {
+		case_id,
+		nullification_case_id,
+		version: case.version,
+		validation,
+		xml,
+	})
+}
+
+/// Reduces an exported ICSR to a nullification report: the N wrapper, C.1.1,
+/// C.1.8.1, C.1.11.1/C.1.11.2 and the sender/receiver stay, the case content
+/// goes.
+pub fn minimal_nullification_xml(xml: &str) -> Result<String> {
+	let doc =
+		Parser::default()
+			.parse_string(xml)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("XML parse error: {err}"),
+				line: None,
+				column: None,
+			})?;
+	let mut xpath = Context::new(&doc).map_err(|_| Error::InvalidXml {
+		message: "Failed to initialize XPath context".to_string(),
+		line: None,
+		column: None,
+	})?;
+	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+	let dropped = xpath
+		.findnodes(DROPPED_INVESTIGATION_CHILDREN_XPATH, None)
+		.map_err(|_| Error::InvalidXml {
+			message: "Failed to query investigation event".to_string(),
+			line: None,
+			column: None,
+		})?;
+	for mut node in dropped {
+		node.unlink_node();
+	}
+	Ok(doc.to_string())
+}
@@ -0,0 +1,446 @@
+// PMDA J regional elements and Japanese case summaries on export.
+//
+// J values and H.5.r summaries are stored in their own tables and written
+// back after the ICH sections have been patched: every one becomes a
+// `component/observationEvent` of the investigation event (paths in
+// xml/mapping/pmda). Summaries keep the language they were stored with, so
+// Japanese text goes out with `language="ja"` instead of being stripped.
+
+use crate::model;
+use crate::model::jp_element::{is_coded_jp_element, CaseJpElement};
+use crate::model::narrative::CaseSummaryInformation;
+use crate::model::ModelManager;
+use crate::xml::error::Error;
+use crate::xml::mapping::pmda::j_elements::JPmdaPaths;
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::tree::{Document, Node, NodeType};
+use libxml::xpath::Context;
+use sqlx::types::Uuid;
+
+/// H.5.r Case Summary and Reporter's Comments observation code.
+const CASE_SUMMARY_CODE: &str = "36";
+const CASE_SUMMARY_CODE_SYSTEM: &str = "2.16.840.1.113883.3.989.2.1.1.19";
+const AUTHOR_CODE_SYSTEM: &str = "2.16.840.1.113883.3.989.2.1.1.21";
+
+/// One H.5.r case summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JpSummary {
+	/// H.5.r.1 author type (summary_type)
+	pub summary_type: Option<String>,
+	/// H.5.r.1b language (`ja`, `jpn`, `en`, ...)
+	pub language_code: Option<String>,
+	/// H.5.r.1a text
+	pub summary_text: Option<String>,
+}
+
+/// One J element occurrence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JpElementValue {
+	/// `J.2` ... `J.20`
+	pub element_id: String,
+	/// Code for J.2/J.4, text otherwise
+	pub value: Option<String>,
+}
+
+/// Everything PMDA adds to an ICH message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JpElements {
+	pub summaries: Vec<JpSummary>,
+	pub elements: Vec<JpElementValue>,
+}
+
+/// Whether an H.5.r.1b language code names Japanese (ISO 639-1 or 639-2).
+pub fn is_japanese_language(code: Option<&str>) -> bool {
+	code.map(str::trim).is_some_and(|code| {
+		code.eq_ignore_ascii_case("ja") || code.eq_ignore_ascii_case("jpn")
+	})
+}
+
+/// Loads the case's J elements and summaries and writes them into the export
+/// document.
+pub async fn apply_jp_elements_from_db(
+	doc: &mut Document,
+	parser: &Parser,
+	mm: &ModelManager,
+	case_id: Uuid,
+	xpath: &mut Context,
+) -> Result<()> {
+	let jp = fetch_jp_elements(mm, case_id).await?;
+	apply_jp_elements(doc, parser, xpath, &jp)
+}
+
+pub async fn fetch_jp_elements(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<JpElements> {
+	let summaries: Vec<CaseSummaryInformation> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT cs.*
+				 FROM case_summary_information cs
+				 JOIN narrative_information n ON n.id = cs.narrative_id
+				 WHERE n.case_id = $1
+				 ORDER BY cs.sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+	let elements: Vec<CaseJpElement> = mm
+		.dbx()
+		.fetch_all(
+			sqlx::query_as(
+				"SELECT * FROM case_jp_elements WHERE case_id = $1
+				 ORDER BY length(element_id), element_id, sequence_number",
+			)
+			.bind(case_id),
+		)
+		.await
+		.map_err(model::Error::from)
+		.map_err(Error::from)?;
+
+	Ok(JpElements {
+		summaries: summaries
+			.into_iter()
+			.map(|summary| JpSummary {
+				summary_type: summary.summary_type,
+				language_code: summary.language_code,
+				summary_text: summary.summary_text,
+			})
+			.collect(),
+		elements: elements
+			.into_iter()
+			.map(|element| JpElementValue {
+				element_id: element.element_id,
+				value: element.value,
+			})
+			.collect(),
+	})
+}
+
+/// Replaces the H.5.r summaries and J elements in `doc` with the given values.
+/// They are written after the remaining investigation event components, where
+/// the ICH schema puts H.5.r.
+pub fn apply_jp_elements(
+	doc: &mut Document,
+	parser: &Parser,
+	xpath: &mut Context,
+	jp: &JpElements,
+) -> Result<()> {
+	for path in [JPmdaPaths::CASE_SUMMARY, JPmdaPaths::J_ELEMENT] {
+		for mut node in find_nodes(xpath, path, None) {
+			node.unlink_node();
+		}
+	}
+
+	let fragment: String = jp
+		.summaries
+		.iter()
+		.filter_map(summary_fragment)
+		.chain(jp.elements.iter().filter_map(element_fragment))
+		.collect();
+	if fragment.is_empty() {
+		return Ok(());
+	}
+
+	if let Some(anchor) = find_nodes(xpath, JPmdaPaths::COMPONENT, None).pop() {
+		insert_fragment_after(doc, parser, anchor, &fragment)
+	} else if let Some(owner) =
+		find_nodes(xpath, JPmdaPaths::INVESTIGATION_EVENT, None)
+			.into_iter()
+			.next()
+	{
+		append_fragment(doc, parser, owner, &fragment)
+	} else {
+		Ok(())
+	}
+}
+
+fn summary_fragment(summary: &JpSummary) -> Option<String> {
+	let text = non_empty(summary.summary_text.as_deref())?;
+	let language = non_empty(summary.language_code.as_deref())
+		.map(|lang| format!(" language=\"{}\"", xml_escape(lang)))
+		.unwrap_or_default();
+	let author = non_empty(summary.summary_type.as_deref())
+		.map(|code| {
+			format!(
+				"<author typeCode=\"AUT\"><assignedEntity classCode=\"ASSIGNED\"><code code=\"{}\" codeSystem=\"{AUTHOR_CODE_SYSTEM}\"/></assignedEntity></author>",
+				xml_escape(code)
+			)
+		})
+		.unwrap_or_default();
+	Some(format!(
+		"<component typeCode=\"COMP\"><observationEvent classCode=\"OBS\" moodCode=\"EVN\"><code code=\"{CASE_SUMMARY_CODE}\" codeSystem=\"{CASE_SUMMARY_CODE_SYSTEM}\" displayName=\"summaryAndComment\"/><value xsi:type=\"ED\"{language}>{}</value>{author}</observationEvent></component>",
+		xml_escape(text)
+	))
+}
+
+fn element_fragment(element: &JpElementValue) -> Option<String> {
+	let value = non_empty(element.value.as_deref())?;
+	let value = if is_coded_jp_element(&element.element_id) {
+		format!("<value xsi:type=\"CE\" code=\"{}\"/>", xml_escape(value))
+	} else {
+		format!("<value xsi:type=\"ST\">{}</value>", xml_escape(value))
+	};
+	Some(format!(
+		"<component typeCode=\"COMP\"><observationEvent classCode=\"OBS\" moodCode=\"EVN\"><code code=\"{}\"/>{value}</observationEvent></component>",
+		xml_escape(element.element_id.trim())
+	))
+}
+
+fn fragment_nodes(
+	doc: &mut Document,
+	parser: &Parser,
+	fragment: &str,
+) -> Result<Vec<Node>> {
+	let wrapped = format!(
+		"<wrapper xmlns=\"urn:hl7-org:v3\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">{fragment}</wrapper>"
+	);
+	let frag_doc =
+		parser
+			.parse_string(&wrapped)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("XML parse error: {err}"),
+				line: None,
+				column: None,
+			})?;
+	let root = frag_doc.get_root_element().ok_or(Error::InvalidXml {
+		message: "Failed to get fragment root".to_string(),
+		line: None,
+		column: None,
+	})?;
+	let mut nodes = Vec::new();
+	for mut child in root
+		.get_child_nodes()
+		.into_iter()
+		.filter(|n| n.get_type() == Some(NodeType::ElementNode))
+	{
+		child.unlink_node();
+		let node = doc.import_node(&mut child).map_err(|_| Error::InvalidXml {
+			message: "Failed to import cloned node".to_string(),
+			line: None,
+			column: None,
+		})?;
+		nodes.push(node);
+	}
+	Ok(nodes)
+}
+
+fn insert_fragment_after(
+	doc: &mut Document,
+	parser: &Parser,
+	mut anchor: Node,
+	fragment: &str,
+) -> Result<()> {
+	for mut node in fragment_nodes(doc, parser, fragment)? {
+		anchor
+			.add_next_sibling(&mut node)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("Failed to insert fragment: {err}"),
+				line: None,
+				column: None,
+			})?;
+		anchor = node;
+	}
+	Ok(())
+}
+
+fn append_fragment(
+	doc: &mut Document,
+	parser: &Parser,
+	mut owner: Node,
+	fragment: &str,
+) -> Result<()> {
+	for mut node in fragment_nodes(doc, parser, fragment)? {
+		owner
+			.add_child(&mut node)
+			.map_err(|err| Error::InvalidXml {
+				message: format!("Failed to append fragment: {err}"),
+				line: None,
+				column: None,
+			})?;
+	}
+	Ok(())
+}
+
+fn find_nodes(xpath: &mut Context, path: &str, node: Option<&Node>) -> Vec<Node> {
+	xpath.findnodes(path, node).unwrap_or_default()
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+	value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn xml_escape(input: &str) -> String {
+	input
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::xml::export_postprocess::postprocess_export_doc;
+	use crate::xml::pmda::import::parse_jp_elements;
+	use crate::xml::validate::ValidationProfile;
+
+	fn skeleton() -> (Parser, Document) {
+		let parser = Parser::default();
+		let doc = parser
+			.parse_string(include_str!(
+				"../../../../../../docs/refs/instances/FAERS2022Scenario1.xml"
+			))
+			.expect("skeleton parses");
+		(parser, doc)
+	}
+
+	fn context(doc: &Document) -> Context {
+		let xpath = Context::new(doc).expect("xpath context");
+		let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+		let _ = xpath
+			.register_namespace("xsi", "http://www.w3.org/2001/XMLSchema-instance");
+		xpath
+	}
+
+	fn reparse(doc: &Document) -> (Parser, Document) {
+		let parser = Parser::default();
+		let doc = parser.parse_string(doc.to_string()).expect("output parses");
+		(parser, doc)
+	}
+
+	fn values(xpath: &mut Context, path: &str) -> Vec<String> {
+		xpath.findvalues(path, None).unwrap_or_default()
+	}
+
+	fn summary(language: &str, text: &str) -> JpSummary {
+		JpSummary {
+			summary_type: Some("1".to_string()),
+			language_code: Some(language.to_string()),
+			summary_text: Some(text.to_string()),
+		}
+	}
+
+	fn element(element_id: &str, value: &str) -> JpElementValue {
+		JpElementValue {
+			element_id: element_id.to_string(),
+			value: Some(value.to_string()),
+		}
+	}
+
+	#[test]
+	fn jp_elements_round_trip_through_import() {
+		let (parser, mut doc) = skeleton();
+		let mut xpath = context(&doc);
+		let jp = JpElements {
+			summaries: vec![
+				summary("ja", "副作用により入院。"),
+				summary("en", "Hospitalised."),
+			],
+			elements: vec![
+				element("J.2", "1"),
+				element("J.4", "1"),
+				element("J.12", "医療機関からの報告"),
+			],
+		};
+
+		apply_jp_elements(&mut doc, &parser, &mut xpath, &jp).unwrap();
+
+		let parsed = parse_jp_elements(doc.to_string().as_bytes()).unwrap();
+		assert_eq!(parsed, jp);
+	}
+
+	#[test]
+	fn jp_elements_written_after_investigation_components() {
+		let (parser, mut doc) = skeleton();
+		let mut xpath = context(&doc);
+		let jp = JpElements {
+			summaries: vec![summary("ja", "概要")],
+			elements: vec![element("J.2", "1")],
+		};
+
+		apply_jp_elements(&mut doc, &parser, &mut xpath, &jp).unwrap();
+
+		let mut xpath = context(&doc);
+		assert_eq!(
+			values(
+				&mut xpath,
+				"//hl7:investigationEvent/hl7:component/hl7:observationEvent/hl7:code[@code='36' or starts-with(@code,'J.')]/@code"
+			),
+			vec!["36", "J.2"]
+		);
+		assert!(values(
+			&mut xpath,
+			"//hl7:investigationEvent/hl7:component[hl7:observationEvent/hl7:code[@code='J.2']]/following-sibling::hl7:component/@typeCode"
+		)
+		.is_empty());
+		// Fragments lose the xsi prefix on import; export postprocessing
+		// restores it as for every other patched section.
+		postprocess_export_doc(&mut doc, &mut xpath, Some(ValidationProfile::Pmda));
+		let (_, doc) = reparse(&doc);
+		let mut xpath = context(&doc);
+		assert_eq!(
+			values(
+				&mut xpath,
+				&format!(
+					"{}/{}/@xsi:type",
+					JPmdaPaths::J_ELEMENT,
+					JPmdaPaths::J_ELEMENT_VALUE
+				)
+			),
+			vec!["CE"]
+		);
+	}
+
+	#[test]
+	fn jp_elements_replace_previous_values() {
+		let (parser, mut doc) = skeleton();
+		let mut xpath = context(&doc);
+		let first = JpElements {
+			summaries: vec![summary("ja", "旧")],
+			elements: vec![element("J.2", "1")],
+		};
+		apply_jp_elements(&mut doc, &parser, &mut xpath, &first).unwrap();
+		let second = JpElements {
+			summaries: vec![summary("ja", "新")],
+			elements: vec![element("J.2", "2")],
+		};
+		let mut xpath = context(&doc);
+		apply_jp_elements(&mut doc, &parser, &mut xpath, &second).unwrap();
+
+		let parsed = parse_jp_elements(doc.to_string().as_bytes()).unwrap();
+		assert_eq!(parsed, second);
+	}
+
+	#[test]
+	fn japanese_summary_language_kept_for_pmda_only() {
+		let jp = JpElements {
+			summaries: vec![summary("JA", "概要")],
+			..Default::default()
+		};
+		let language = |profile: Option<ValidationProfile>| {
+			let (parser, mut doc) = skeleton();
+			let mut xpath = context(&doc);
+			apply_jp_elements(&mut doc, &parser, &mut xpath, &jp).unwrap();
+			let mut xpath = context(&doc);
+			postprocess_export_doc(&mut doc, &mut xpath, profile);
+			let mut xpath = context(&doc);
+			values(
+				&mut xpath,
+				&format!(
+					"{}/{}/@language",
+					JPmdaPaths::CASE_SUMMARY,
+					JPmdaPaths::CASE_SUMMARY_VALUE
+				),
+			)
+		};
+
+		assert_eq!(language(Some(ValidationProfile::Pmda)), vec!["JA"]);
+		assert!(language(Some(ValidationProfile::Fda)).is_empty());
+	}
+}
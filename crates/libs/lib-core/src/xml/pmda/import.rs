@@ -0,0 +1,107 @@
+// PMDA J regional elements and H.5.r case summaries on import.
+//
+// Reads back what xml/pmda/export writes. Summaries are read for every
+// message, whatever the receiver, so their text and language survive a
+// re-export; J elements only appear in messages for PMDA.
+
+use crate::xml::error::Error;
+use crate::xml::mapping::pmda::j_elements::JPmdaPaths;
+use crate::xml::pmda::export::{JpElementValue, JpElements, JpSummary};
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::tree::Node;
+use libxml::xpath::Context;
+
+/// H.5.r summaries and J elements of a message, in document order.
+pub fn parse_jp_elements(xml: &[u8]) -> Result<JpElements> {
+	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
+		message: format!("XML not valid UTF-8: {err}"),
+		line: None,
+		column: None,
+	})?;
+	let parser = Parser::default();
+	let doc = parser
+		.parse_string(xml_str)
+		.map_err(|err| Error::InvalidXml {
+			message: format!("XML parse error: {err}"),
+			line: None,
+			column: None,
+		})?;
+	let mut xpath = Context::new(&doc).map_err(|_| Error::InvalidXml {
+		message: "Failed to initialize XPath context".to_string(),
+		line: None,
+		column: None,
+	})?;
+	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+
+	let mut summaries = Vec::new();
+	for node in find_nodes(&mut xpath, JPmdaPaths::CASE_SUMMARY) {
+		let value = first_node(&mut xpath, &node, JPmdaPaths::CASE_SUMMARY_VALUE);
+		let summary_text = value
+			.as_ref()
+			.map(Node::get_content)
+			.filter(|text| !text.trim().is_empty());
+		// ED carries @language; some senders write @languageCode instead.
+		let language_code = value.as_ref().and_then(|value| {
+			value
+				.get_attribute("language")
+				.or_else(|| value.get_attribute("languageCode"))
+				.map(|lang| lang.trim().to_string())
+				.filter(|lang| !lang.is_empty())
+		});
+		let summary_type =
+			first_value(&mut xpath, &node, JPmdaPaths::CASE_SUMMARY_AUTHOR);
+		summaries.push(JpSummary {
+			summary_type,
+			language_code,
+			summary_text,
+		});
+	}
+
+	let mut elements = Vec::new();
+	for node in find_nodes(&mut xpath, JPmdaPaths::J_ELEMENT) {
+		let Some(element_id) =
+			first_value(&mut xpath, &node, JPmdaPaths::J_ELEMENT_ID)
+		else {
+			continue;
+		};
+		let value = first_node(&mut xpath, &node, JPmdaPaths::J_ELEMENT_VALUE)
+			.map(|value| {
+				value
+					.get_attribute("code")
+					.unwrap_or_else(|| value.get_content())
+					.trim()
+					.to_string()
+			})
+			.filter(|value| !value.is_empty());
+		elements.push(JpElementValue {
+			element_id: element_id.trim().to_string(),
+			value,
+		});
+	}
+
+	Ok(JpElements {
+		summaries,
+		elements,
+	})
+}
+
+fn find_nodes(xpath: &mut Context, path: &str) -> Vec<Node> {
+	xpath.findnodes(path, None).unwrap_or_default()
+}
+
+fn first_node(xpath: &mut Context, node: &Node, path: &str) -> Option<Node> {
+	xpath
+		.findnodes(path, Some(node))
+		.ok()
+		.and_then(|nodes| nodes.into_iter().next())
+}
+
+fn first_value(xpath: &mut Context, node: &Node, path: &str) -> Option<String> {
+	xpath
+		.findvalues(path, Some(node))
+		.ok()
+		.and_then(|values| values.into_iter().next())
+		.map(|value| value.trim().to_string())
+		.filter(|value| !value.is_empty())
+}
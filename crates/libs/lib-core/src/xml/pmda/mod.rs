@@ -0,0 +1,4 @@
+// PMDA-specific J elements and business rules (Japan profile on top of ICH core).
+pub mod export;
+pub mod import;
+pub mod validation;
@@ -0,0 +1,168 @@
+use crate::ctx::Ctx;
+use crate::model::jp_element::{
+	CaseJpElement, CaseJpElementBmc, JP_REPORTING_CATEGORY_ELEMENT,
+	JP_REPORTING_DEADLINE_ELEMENT,
+};
+use crate::model::narrative::CaseSummaryInformation;
+use crate::model::safety_report::SafetyReportIdentification;
+use crate::model::terminology::E2bCodeListBmc;
+use crate::model::{ModelManager, Result};
+use crate::xml::pmda::export::is_japanese_language;
+use crate::xml::validate::{
+	build_report, has_text, load_rule_set, push_issue_if_code_not_in_list,
+	push_issue_if_condition_violated, push_issue_if_conditioned_value_invalid,
+	CaseValidationReport, RuleFacts, ValidationIssue, ValidationProfile,
+	JP_REPORTING_CATEGORY, JP_REPORTING_DEADLINE,
+};
+use sqlx::types::Uuid;
+
+async fn get_safety_report_optional(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Option<SafetyReportIdentification>> {
+	let sql = "SELECT * FROM safety_report_identification WHERE case_id = $1";
+	mm.dbx()
+		.fetch_optional(
+			sqlx::query_as::<_, SafetyReportIdentification>(sql).bind(case_id),
+		)
+		.await
+		.map_err(Into::into)
+}
+
+async fn list_case_summaries_by_case(
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<Vec<CaseSummaryInformation>> {
+	let sql = r#"
+SELECT cs.*
+FROM case_summary_information cs
+JOIN narrative_information n ON n.id = cs.narrative_id
+WHERE n.case_id = $1
+ORDER BY cs.sequence_number
+"#;
+	mm.dbx()
+		.fetch_all(sqlx::query_as::<_, CaseSummaryInformation>(sql).bind(case_id))
+		.await
+		.map_err(Into::into)
+}
+
+fn push_pmda_value_issue(
+	issues: &mut Vec<ValidationIssue>,
+	code: &str,
+	path: impl Into<String>,
+	value: Option<&str>,
+	condition_facts: RuleFacts,
+) {
+	let _ = push_issue_if_conditioned_value_invalid(
+		issues,
+		code,
+		code,
+		code,
+		path,
+		value,
+		None,
+		condition_facts,
+		RuleFacts::default(),
+	);
+}
+
+fn first_value<'a>(
+	elements: &'a [CaseJpElement],
+	element_id: &str,
+) -> Option<&'a str> {
+	elements
+		.iter()
+		.filter(|element| element.element_id.trim() == element_id)
+		.find_map(|element| element.value.as_deref().filter(|v| has_text(Some(v))))
+}
+
+pub async fn validate_case(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<CaseValidationReport> {
+	let ich_report =
+		crate::xml::ich::validation::validate_case(ctx, mm, case_id).await?;
+	let rules = load_rule_set(ctx, mm).await?;
+	let codes = E2bCodeListBmc::catalog(ctx, mm).await?;
+	let report = get_safety_report_optional(mm, case_id).await?;
+	let elements = CaseJpElementBmc::list_by_case(ctx, mm, case_id).await?;
+	let summaries = list_case_summaries_by_case(mm, case_id).await?;
+
+	let mut issues: Vec<ValidationIssue> = ich_report.issues;
+
+	push_pmda_value_issue(
+		&mut issues,
+		"PMDA.J.2.REQUIRED",
+		"jpElements.J.2",
+		first_value(&elements, JP_REPORTING_CATEGORY_ELEMENT),
+		RuleFacts::default(),
+	);
+	push_pmda_value_issue(
+		&mut issues,
+		"PMDA.J.4.REQUIRED",
+		"jpElements.J.4",
+		first_value(&elements, JP_REPORTING_DEADLINE_ELEMENT),
+		RuleFacts {
+			pmda_expedited_criteria_true: Some(
+				report.as_ref().is_some_and(|r| r.fulfil_expedited_criteria),
+			),
+			..RuleFacts::default()
+		},
+	);
+
+	elements.iter().enumerate().for_each(|(idx, element)| {
+		push_pmda_value_issue(
+			&mut issues,
+			"PMDA.J.ELEMENT.UNKNOWN",
+			format!("jpElements.{idx}.elementId"),
+			Some(element.element_id.as_str()),
+			RuleFacts::default(),
+		);
+		let list_name = match element.element_id.trim() {
+			JP_REPORTING_CATEGORY_ELEMENT => JP_REPORTING_CATEGORY,
+			JP_REPORTING_DEADLINE_ELEMENT => JP_REPORTING_DEADLINE,
+			_ => return,
+		};
+		let _ = push_issue_if_code_not_in_list(
+			&mut issues,
+			&codes,
+			list_name,
+			format!("jpElements.{idx}.value"),
+			element.value.as_deref(),
+		);
+	});
+
+	let _ = push_issue_if_condition_violated(
+		&mut issues,
+		"PMDA.H.5.r.JA.REQUIRED",
+		"narrative.summaries",
+		RuleFacts {
+			pmda_japanese_summary_missing: Some(!summaries.iter().any(|summary| {
+				is_japanese_language(summary.language_code.as_deref())
+					&& has_text(summary.summary_text.as_deref())
+			})),
+			..RuleFacts::default()
+		},
+	);
+	summaries.iter().enumerate().for_each(|(idx, summary)| {
+		push_pmda_value_issue(
+			&mut issues,
+			"PMDA.H.5.r.1b.REQUIRED",
+			format!("narrative.summaries.{idx}.languageCode"),
+			summary.language_code.as_deref(),
+			RuleFacts {
+				pmda_summary_text_present: Some(has_text(
+					summary.summary_text.as_deref(),
+				)),
+				..RuleFacts::default()
+			},
+		);
+	});
+
+	Ok(build_report(
+		ValidationProfile::Pmda,
+		case_id,
+		rules.apply(issues),
+	))
+}
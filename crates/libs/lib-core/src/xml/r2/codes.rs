@@ -0,0 +1,187 @@
+// E2B(R2) <-> E2B(R3) code and date conversions (ICH BFC, Appendix I)
+
+use crate::model::partial_date::{DatePrecision, PartialDate};
+
+/// R2 dose units (B.4.k.5.2, B.4.k.5.7) and their UCUM equivalent.
+const DOSE_UNITS: &[(&str, &str)] = &[
+	("001", "kg"),
+	("002", "g"),
+	("003", "mg"),
+	("004", "ug"),
+	("005", "ng"),
+	("006", "pg"),
+	("007", "mg/kg"),
+	("008", "ug/kg"),
+	("009", "mg/m2"),
+	("010", "ug/m2"),
+	("011", "L"),
+	("012", "mL"),
+	("013", "uL"),
+	("014", "Bq"),
+	("015", "GBq"),
+	("016", "MBq"),
+	("017", "kBq"),
+	("018", "Ci"),
+	("019", "MCi"),
+	("020", "mCi"),
+	("021", "uCi"),
+	("022", "nCi"),
+	("023", "mol"),
+	("024", "mmol"),
+	("025", "umol"),
+	("026", "[iU]"),
+	("027", "k[iU]"),
+	("028", "M[iU]"),
+	("029", "[iU]/kg"),
+	("030", "meq"),
+	("031", "%"),
+	("032", "[drp]"),
+	("033", "{DF}"),
+];
+
+/// R2 age / duration / interval units (800-813) and their UCUM equivalent.
+const TIME_UNITS: &[(&str, &str)] = &[
+	("800", "{decade}"),
+	("801", "a"),
+	("802", "mo"),
+	("803", "wk"),
+	("804", "d"),
+	("805", "h"),
+	("806", "min"),
+	("807", "s"),
+	("810", "{cyclical}"),
+	("811", "{trimester}"),
+	("812", "{asnecessary}"),
+	("813", "{total}"),
+];
+
+fn lookup<'a>(table: &'a [(&'a str, &'a str)], key: &str) -> Option<&'a str> {
+	table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+fn reverse<'a>(table: &'a [(&'a str, &'a str)], value: &str) -> Option<&'a str> {
+	table.iter().find(|(_, v)| *v == value).map(|(k, _)| *k)
+}
+
+pub fn dose_unit_to_ucum(code: &str) -> Option<&'static str> {
+	lookup(DOSE_UNITS, code.trim())
+}
+
+/// Accepts a UCUM unit or a value already stored as an R2 code.
+pub fn dose_unit_from_ucum(unit: &str) -> Option<&'static str> {
+	let unit = unit.trim();
+	reverse(DOSE_UNITS, unit)
+		.or_else(|| DOSE_UNITS.iter().find(|(k, _)| *k == unit).map(|(k, _)| *k))
+}
+
+pub fn time_unit_to_ucum(code: &str) -> Option<&'static str> {
+	lookup(TIME_UNITS, code.trim())
+}
+
+/// Accepts a UCUM unit or a value already stored as an R2 code.
+pub fn time_unit_from_ucum(unit: &str) -> Option<&'static str> {
+	let unit = unit.trim();
+	reverse(TIME_UNITS, unit)
+		.or_else(|| TIME_UNITS.iter().find(|(k, _)| *k == unit).map(|(k, _)| *k))
+}
+
+/// R2 yes/no flags: 1 = yes, 2 = no; anything else (3 = unknown) is absent.
+pub fn r2_bool(value: &str) -> Option<bool> {
+	match value.trim() {
+		"1" => Some(true),
+		"2" => Some(false),
+		_ => None,
+	}
+}
+
+pub fn r2_flag(value: bool) -> &'static str {
+	if value {
+		"1"
+	} else {
+		"2"
+	}
+}
+
+/// B.2.i.8 reaction outcome; R2 6 (unknown) is R3 0.
+pub fn outcome_to_r3(code: &str) -> Option<&'static str> {
+	match code.trim() {
+		"1" => Some("1"),
+		"2" => Some("2"),
+		"3" => Some("3"),
+		"4" => Some("4"),
+		"5" => Some("5"),
+		"6" => Some("0"),
+		_ => None,
+	}
+}
+
+pub fn outcome_to_r2(code: &str) -> Option<&'static str> {
+	match code.trim() {
+		"0" => Some("6"),
+		"1" => Some("1"),
+		"2" => Some("2"),
+		"3" => Some("3"),
+		"4" => Some("4"),
+		"5" => Some("5"),
+		_ => None,
+	}
+}
+
+/// B.4.k.16 action taken. Stored with the R2 list (1-6); R3 0 (unknown) and
+/// 9 (not applicable) are accepted on export.
+pub fn action_to_r2(code: &str) -> Option<&'static str> {
+	match code.trim() {
+		"1" => Some("1"),
+		"2" => Some("2"),
+		"3" => Some("3"),
+		"4" => Some("4"),
+		"5" | "0" => Some("5"),
+		"6" | "9" => Some("6"),
+		_ => None,
+	}
+}
+
+/// B.2.i.3 term highlighted: 1/3 = highlighted, 2/4 = not highlighted (the
+/// seriousness half of the code is carried by A.1.5).
+pub fn term_highlighted_to_r3(code: &str) -> Option<bool> {
+	match code.trim() {
+		"1" | "3" => Some(true),
+		"2" | "4" => Some(false),
+		_ => None,
+	}
+}
+
+pub fn term_highlighted_to_r2(highlighted: bool, serious: bool) -> &'static str {
+	match (highlighted, serious) {
+		(true, false) => "1",
+		(false, false) => "2",
+		(true, true) => "3",
+		(false, true) => "4",
+	}
+}
+
+/// Reads an R2 date; the format code (102, 610, 602, 203, 204) only restates
+/// the precision the value already carries.
+pub fn parse_r2_date(value: &str) -> Option<PartialDate> {
+	PartialDate::parse(value.trim())
+}
+
+/// R2 date format code and value for a date, without timezone offset.
+pub fn r2_date(date: &PartialDate) -> (&'static str, String) {
+	let precision = date.precision();
+	let format = match precision {
+		DatePrecision::Year => "602",
+		DatePrecision::Month => "610",
+		DatePrecision::Day => "102",
+		DatePrecision::Minute => "203",
+		DatePrecision::Second => "204",
+	};
+	let value = date.to_string().chars().take(precision.digits()).collect();
+	(format, value)
+}
+
+/// Whether a MedDRA field holds a code (R2 also allowed the term text).
+pub fn is_meddra_code(value: &str) -> bool {
+	let value = value.trim();
+	value.len() == 8 && value.chars().all(|c| c.is_ascii_digit())
+}
@@ -0,0 +1,1336 @@
+use crate::ctx::Ctx;
+use crate::model::case::{Case, CaseBmc};
+use crate::model::case_identifiers::{LinkedReportNumber, OtherCaseIdentifier};
+use crate::model::drug::{
+	DosageInformation, DrugActiveSubstance, DrugIndication, DrugInformation,
+	DrugInformationBmc,
+};
+use crate::model::drug_reaction_assessment::{
+	DrugReactionAssessment, RelatednessAssessment,
+};
+use crate::model::message_header::{MessageHeader, MessageHeaderBmc};
+use crate::model::narrative::{
+	NarrativeInformation, NarrativeInformationBmc, SenderDiagnosis,
+};
+use crate::model::partial_date::PartialDate;
+use crate::model::patient::{
+	AutopsyCauseOfDeath, MedicalHistoryEpisode, PastDrugHistory,
+	PatientDeathInformation, PatientIdentifier, PatientInformation,
+	PatientInformationBmc, ReportedCauseOfDeath,
+};
+use crate::model::reaction::{Reaction, ReactionBmc};
+use crate::model::receiver::{ReceiverInformation, ReceiverInformationBmc};
+use crate::model::safety_report::{
+	DocumentsHeldBySender, LiteratureReference, PrimarySource,
+	SafetyReportIdentification, SafetyReportIdentificationBmc, SenderInformation,
+	StudyInformation,
+};
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::test_result::{TestResult, TestResultBmc};
+use crate::model::{self, ModelManager};
+use crate::xml::error::Error;
+use crate::xml::r2::codes::{
+	action_to_r2, dose_unit_from_ucum, outcome_to_r2, r2_date, r2_flag,
+	term_highlighted_to_r2, time_unit_from_ucum,
+};
+use crate::xml::r2::{loss_note, xml_escape};
+use crate::xml::types::XmlR2ExportResult;
+use crate::xml::validate::ValidationIssue;
+use crate::xml::Result;
+use sqlx::postgres::PgRow;
+use sqlx::types::Uuid;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Everything an ichicsr `safetyreport` is built from.
+struct CaseData {
+	case: Case,
+	header: Option<MessageHeader>,
+	report: Option<SafetyReportIdentification>,
+	sender: Option<SenderInformation>,
+	receiver: Option<ReceiverInformation>,
+	primary_sources: Vec<PrimarySource>,
+	literature: Vec<LiteratureReference>,
+	study: Option<StudyInformation>,
+	documents: Vec<DocumentsHeldBySender>,
+	other_identifiers: Vec<OtherCaseIdentifier>,
+	linked_reports: Vec<LinkedReportNumber>,
+	patient: Option<PatientInformation>,
+	patient_identifiers: Vec<PatientIdentifier>,
+	medical_history: Vec<MedicalHistoryEpisode>,
+	past_drugs: Vec<PastDrugHistory>,
+	death: Option<PatientDeathInformation>,
+	reported_causes: Vec<ReportedCauseOfDeath>,
+	autopsy_causes: Vec<AutopsyCauseOfDeath>,
+	reactions: Vec<Reaction>,
+	tests: Vec<TestResult>,
+	drugs: Vec<DrugInformation>,
+	substances: Vec<DrugActiveSubstance>,
+	dosages: Vec<DosageInformation>,
+	indications: Vec<DrugIndication>,
+	assessments: Vec<DrugReactionAssessment>,
+	relatedness: Vec<RelatednessAssessment>,
+	narrative: Option<NarrativeInformation>,
+	diagnoses: Vec<SenderDiagnosis>,
+}
+
+/// Renders a case as an E2B(R2) `ichicsr` message following the ICH BFC.
+///
+/// Case content that R2 cannot carry is reported as non-blocking issues.
+/// Each dosage regimen is sent as its own `drug` element, as R2 holds a
+/// single regimen per drug.
+pub async fn export_case_r2_xml(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+) -> Result<XmlR2ExportResult> {
+	let mm = mm.new_with_txn().map_err(Error::from)?;
+	mm.dbx().begin_txn().await.map_err(model::Error::from)?;
+	set_full_context_dbx_or_rollback(
+		mm.dbx(),
+		ctx.user_id(),
+		ctx.organization_id(),
+		ctx.role(),
+	)
+	.await?;
+	let data = match load_case(ctx, &mm, case_id).await {
+		Ok(data) => {
+			mm.dbx().commit_txn().await.map_err(model::Error::from)?;
+			data
+		}
+		Err(err) => {
+			let _ = mm.dbx().rollback_txn().await;
+			return Err(err);
+		}
+	};
+
+	let mut issues = Vec::new();
+	let xml = render_r2_xml(&data, &mut issues);
+	Ok(XmlR2ExportResult {
+		case_id,
+		xml,
+		issues,
+	})
+}
+
+async fn load_case(ctx: &Ctx, mm: &ModelManager, case_id: Uuid) -> Result<CaseData> {
+	let case = CaseBmc::get(ctx, mm, case_id).await?;
+	let header = MessageHeaderBmc::get_by_case(ctx, mm, case_id).await.ok();
+	let report = SafetyReportIdentificationBmc::get_by_case(ctx, mm, case_id)
+		.await
+		.ok();
+	let receiver =
+		ReceiverInformationBmc::get_by_case_optional(ctx, mm, case_id).await?;
+	let patient = PatientInformationBmc::get_by_case(ctx, mm, case_id)
+		.await
+		.ok();
+	let narrative = NarrativeInformationBmc::get_by_case(ctx, mm, case_id)
+		.await
+		.ok();
+
+	let patient_ids: Vec<Uuid> = patient.iter().map(|p| p.id).collect();
+	let death: Option<PatientDeathInformation> = rows_any(
+		mm,
+		"SELECT * FROM patient_death_information WHERE patient_id = ANY($1)",
+		&patient_ids,
+	)
+	.await?
+	.into_iter()
+	.next();
+	let death_ids: Vec<Uuid> = death.iter().map(|d| d.id).collect();
+
+	let drugs = DrugInformationBmc::list_by_case(ctx, mm, case_id).await?;
+	let drug_ids: Vec<Uuid> = drugs.iter().map(|d| d.id).collect();
+	let assessments: Vec<DrugReactionAssessment> = rows_any(
+		mm,
+		"SELECT * FROM drug_reaction_assessments WHERE drug_id = ANY($1)",
+		&drug_ids,
+	)
+	.await?;
+	let assessment_ids: Vec<Uuid> = assessments.iter().map(|a| a.id).collect();
+	let narrative_ids: Vec<Uuid> = narrative.iter().map(|n| n.id).collect();
+
+	Ok(CaseData {
+		header,
+		report,
+		sender: rows(
+			mm,
+			"SELECT * FROM sender_information WHERE case_id = $1 LIMIT 1",
+			case_id,
+		)
+		.await?
+		.into_iter()
+		.next(),
+		receiver,
+		primary_sources: rows(
+			mm,
+			"SELECT * FROM primary_sources WHERE case_id = $1 ORDER BY sequence_number",
+			case_id,
+		)
+		.await?,
+		literature: rows(
+			mm,
+			"SELECT * FROM literature_references WHERE case_id = $1 ORDER BY sequence_number",
+			case_id,
+		)
+		.await?,
+		study: rows(
+			mm,
+			"SELECT * FROM study_information WHERE case_id = $1 LIMIT 1",
+			case_id,
+		)
+		.await?
+		.into_iter()
+		.next(),
+		documents: rows(
+			mm,
+			"SELECT * FROM documents_held_by_sender WHERE case_id = $1 ORDER BY sequence_number",
+			case_id,
+		)
+		.await?,
+		other_identifiers: rows(
+			mm,
+			"SELECT * FROM other_case_identifiers WHERE case_id = $1 ORDER BY sequence_number",
+			case_id,
+		)
+		.await?,
+		linked_reports: rows(
+			mm,
+			"SELECT * FROM linked_report_numbers WHERE case_id = $1 ORDER BY sequence_number",
+			case_id,
+		)
+		.await?,
+		patient_identifiers: rows_any(
+			mm,
+			"SELECT * FROM patient_identifiers WHERE patient_id = ANY($1) ORDER BY sequence_number",
+			&patient_ids,
+		)
+		.await?,
+		medical_history: rows_any(
+			mm,
+			"SELECT * FROM medical_history_episodes WHERE patient_id = ANY($1) ORDER BY sequence_number",
+			&patient_ids,
+		)
+		.await?,
+		past_drugs: rows_any(
+			mm,
+			"SELECT * FROM past_drug_history WHERE patient_id = ANY($1) ORDER BY sequence_number",
+			&patient_ids,
+		)
+		.await?,
+		reported_causes: rows_any(
+			mm,
+			"SELECT * FROM reported_causes_of_death WHERE death_info_id = ANY($1) ORDER BY sequence_number",
+			&death_ids,
+		)
+		.await?,
+		autopsy_causes: rows_any(
+			mm,
+			"SELECT * FROM autopsy_causes_of_death WHERE death_info_id = ANY($1) ORDER BY sequence_number",
+			&death_ids,
+		)
+		.await?,
+		death,
+		patient,
+		reactions: ReactionBmc::list_by_case(ctx, mm, case_id).await?,
+		tests: TestResultBmc::list_by_case(ctx, mm, case_id).await?,
+		substances: rows_any(
+			mm,
+			"SELECT * FROM drug_active_substances WHERE drug_id = ANY($1) ORDER BY sequence_number",
+			&drug_ids,
+		)
+		.await?,
+		dosages: rows_any(
+			mm,
+			"SELECT * FROM dosage_information WHERE drug_id = ANY($1) ORDER BY sequence_number",
+			&drug_ids,
+		)
+		.await?,
+		indications: rows_any(
+			mm,
+			"SELECT * FROM drug_indications WHERE drug_id = ANY($1) ORDER BY sequence_number",
+			&drug_ids,
+		)
+		.await?,
+		relatedness: rows_any(
+			mm,
+			"SELECT * FROM relatedness_assessments WHERE drug_reaction_assessment_id = ANY($1) ORDER BY sequence_number",
+			&assessment_ids,
+		)
+		.await?,
+		diagnoses: rows_any(
+			mm,
+			"SELECT * FROM sender_diagnoses WHERE narrative_id = ANY($1) ORDER BY sequence_number",
+			&narrative_ids,
+		)
+		.await?,
+		assessments,
+		drugs,
+		narrative,
+		case,
+	})
+}
+
+async fn rows<T>(mm: &ModelManager, sql: &str, id: Uuid) -> Result<Vec<T>>
+where
+	T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+	let rows = mm
+		.dbx()
+		.fetch_all(sqlx::query_as::<_, T>(sql).bind(id))
+		.await
+		.map_err(model::Error::from)?;
+	Ok(rows)
+}
+
+async fn rows_any<T>(mm: &ModelManager, sql: &str, ids: &[Uuid]) -> Result<Vec<T>>
+where
+	T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+	if ids.is_empty() {
+		return Ok(Vec::new());
+	}
+	let rows = mm
+		.dbx()
+		.fetch_all(sqlx::query_as::<_, T>(sql).bind(ids.to_vec()))
+		.await
+		.map_err(model::Error::from)?;
+	Ok(rows)
+}
+
+/// Indented element writer; empty values are skipped, as R2 has no nulls.
+struct Writer {
+	out: String,
+	depth: usize,
+}
+
+impl Writer {
+	fn open(&mut self, name: &str) {
+		self.indent();
+		self.out.push_str(&format!("<{name}>\n"));
+		self.depth += 1;
+	}
+
+	fn close(&mut self, name: &str) {
+		self.depth -= 1;
+		self.indent();
+		self.out.push_str(&format!("</{name}>\n"));
+	}
+
+	fn field(&mut self, name: &str, value: Option<&str>) {
+		let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+			return;
+		};
+		self.indent();
+		self.out
+			.push_str(&format!("<{name}>{}</{name}>\n", xml_escape(value)));
+	}
+
+	fn value(&mut self, name: &str, value: Option<impl ToString>) {
+		self.field(name, value.map(|v| v.to_string()).as_deref());
+	}
+
+	fn flag(&mut self, name: &str, value: Option<bool>) {
+		self.field(name, value.map(r2_flag));
+	}
+
+	fn date(&mut self, format_name: &str, name: &str, value: Option<&PartialDate>) {
+		if let Some(date) = value {
+			let (format, value) = r2_date(date);
+			self.field(format_name, Some(format));
+			self.field(name, Some(&value));
+		}
+	}
+
+	fn indent(&mut self) {
+		for _ in 0..self.depth {
+			self.out.push('\t');
+		}
+	}
+}
+
+fn render_r2_xml(data: &CaseData, issues: &mut Vec<ValidationIssue>) -> String {
+	let mut w = Writer {
+		out: String::from(
+			"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE ichicsr SYSTEM \"http://eudravigilance.ema.europa.eu/dtd/icsr21xml.dtd\">\n<ichicsr lang=\"en\">\n",
+		),
+		depth: 1,
+	};
+	let now = OffsetDateTime::now_utc();
+	let message_date = format!(
+		"{:04}{:02}{:02}{:02}{:02}{:02}",
+		now.year(),
+		u8::from(now.month()),
+		now.day(),
+		now.hour(),
+		now.minute(),
+		now.second()
+	);
+
+	w.open("ichicsrmessageheader");
+	w.field("messagetype", Some("ichicsr"));
+	w.field("messageformatversion", Some("2.1"));
+	w.field("messageformatrelease", Some("2.0"));
+	w.field(
+		"messagenumb",
+		Some(
+			data.header
+				.as_ref()
+				.map(|h| h.message_number.as_str())
+				.unwrap_or(&data.case.safety_report_id),
+		),
+	);
+	w.field(
+		"messagesenderidentifier",
+		data.header
+			.as_ref()
+			.map(|h| h.message_sender_identifier.as_str()),
+	);
+	w.field(
+		"messagereceiveridentifier",
+		data.header
+			.as_ref()
+			.map(|h| h.message_receiver_identifier.as_str()),
+	);
+	w.field("messagedateformat", Some("204"));
+	w.field("messagedate", Some(&message_date));
+	w.close("ichicsrmessageheader");
+
+	w.open("safetyreport");
+	render_report(&mut w, data, issues);
+	w.close("safetyreport");
+	w.out.push_str("</ichicsr>\n");
+	w.out
+}
+
+fn render_report(
+	w: &mut Writer,
+	data: &CaseData,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	let report = data.report.as_ref();
+	w.value("safetyreportversion", Some(data.case.version));
+	w.field("safetyreportid", Some(&data.case.safety_report_id));
+	w.field(
+		"primarysourcecountry",
+		data.primary_sources
+			.iter()
+			.find_map(|s| s.country_code.as_deref()),
+	);
+
+	let countries: Vec<&str> = data
+		.reactions
+		.iter()
+		.filter_map(|r| r.country_code.as_deref())
+		.collect();
+	if countries.iter().any(|c| *c != countries[0]) {
+		loss_note(
+			issues,
+			"BFC.E.i.9.MERGED",
+			"Reactions occurred in different countries; A.1.2 carries the first one.",
+			"reactions",
+			"reactions",
+		);
+	}
+	w.field("occurcountry", countries.first().copied());
+	w.date(
+		"transmissiondateformat",
+		"transmissiondate",
+		report
+			.map(|r| PartialDate::from(r.transmission_date))
+			.as_ref(),
+	);
+	w.field("reporttype", report.map(|r| r.report_type.as_str()));
+
+	// A.1.5 is report-level: any serious reaction makes the report serious and
+	// the criteria are the union over all reactions.
+	if !data.reactions.is_empty() {
+		let criteria = |f: fn(&Reaction) -> bool| data.reactions.iter().any(f);
+		let serious = data.reactions.iter().any(|r| r.serious == Some(true));
+		w.flag("serious", Some(serious));
+		if serious {
+			w.flag("seriousnessdeath", Some(criteria(|r| r.criteria_death)));
+			w.flag(
+				"seriousnesslifethreatening",
+				Some(criteria(|r| r.criteria_life_threatening)),
+			);
+			w.flag(
+				"seriousnesshospitalization",
+				Some(criteria(|r| r.criteria_hospitalization)),
+			);
+			w.flag(
+				"seriousnessdisabling",
+				Some(criteria(|r| r.criteria_disabling)),
+			);
+			w.flag(
+				"seriousnesscongenitalanomali",
+				Some(criteria(|r| r.criteria_congenital_anomaly)),
+			);
+			w.flag(
+				"seriousnessother",
+				Some(criteria(|r| r.criteria_other_medically_important)),
+			);
+		}
+		let mixed = data.reactions.iter().any(|r| {
+			r.serious != data.reactions[0].serious
+				|| r.criteria_death != data.reactions[0].criteria_death
+				|| r.criteria_life_threatening
+					!= data.reactions[0].criteria_life_threatening
+				|| r.criteria_hospitalization
+					!= data.reactions[0].criteria_hospitalization
+				|| r.criteria_disabling != data.reactions[0].criteria_disabling
+				|| r.criteria_congenital_anomaly
+					!= data.reactions[0].criteria_congenital_anomaly
+				|| r.criteria_other_medically_important
+					!= data.reactions[0].criteria_other_medically_important
+		});
+		if mixed {
+			loss_note(
+				issues,
+				"BFC.E.i.3.2.MERGED",
+				"Per-reaction seriousness differs; A.1.5 carries the combined criteria.",
+				"reactions",
+				"reactions",
+			);
+		}
+	}
+
+	w.date(
+		"receivedateformat",
+		"receivedate",
+		report
+			.map(|r| PartialDate::from(r.date_first_received_from_source))
+			.as_ref(),
+	);
+	w.date(
+		"receiptdateformat",
+		"receiptdate",
+		report
+			.map(|r| PartialDate::from(r.date_of_most_recent_information))
+			.as_ref(),
+	);
+	if !data.documents.is_empty() {
+		w.flag("additionaldocument", Some(true));
+		let titles: Vec<&str> = data
+			.documents
+			.iter()
+			.filter_map(|d| d.title.as_deref())
+			.collect();
+		w.field("documentlist", Some(&titles.join(", ")));
+		if data.documents.iter().any(|d| d.document_base64.is_some()) {
+			loss_note(
+				issues,
+				"BFC.C.1.6.1.r.2.DROPPED",
+				"Attached documents cannot be sent in R2; only their titles are listed.",
+				"documentsHeldBySender",
+				"case-identification",
+			);
+		}
+	}
+	w.flag(
+		"fulfillexpeditecriteria",
+		report.map(|r| r.fulfil_expedited_criteria),
+	);
+	let from_authority = data.sender.as_ref().is_some_and(|s| s.sender_type == "2");
+	let unique_id = report.and_then(|r| r.worldwide_unique_id.as_deref());
+	if from_authority {
+		w.field("authoritynumb", unique_id);
+	} else {
+		w.field("companynumb", unique_id);
+	}
+	if !data.other_identifiers.is_empty() {
+		w.flag("duplicate", Some(true));
+	}
+	match report.and_then(|r| r.nullification_code.as_deref()) {
+		Some("1") => {
+			w.flag("casenullification", Some(true));
+			w.field(
+				"nullificationreason",
+				report.and_then(|r| r.nullification_reason.as_deref()),
+			);
+		}
+		Some(code) => loss_note(
+			issues,
+			"BFC.C.1.11.1.DROPPED",
+			&format!("Nullification/amendment code '{code}' has no R2 equivalent."),
+			"safetyReportIdentification.nullificationCode",
+			"case-identification",
+		),
+		None => {}
+	}
+	let confirmed: Vec<bool> = data
+		.reactions
+		.iter()
+		.filter_map(|r| r.medical_confirmation)
+		.collect();
+	if !confirmed.is_empty() {
+		w.flag("medicallyconfirm", Some(confirmed.iter().any(|c| *c)));
+	}
+	for duplicate in &data.other_identifiers {
+		w.open("reportduplicate");
+		w.field("duplicatesource", Some(&duplicate.source_of_identifier));
+		w.field("duplicatenumb", Some(&duplicate.case_identifier));
+		w.close("reportduplicate");
+	}
+	for linked in &data.linked_reports {
+		w.open("linkedreport");
+		w.field("linkreportnumb", Some(&linked.linked_report_number));
+		w.close("linkedreport");
+	}
+
+	render_primary_sources(w, data, issues);
+	render_sender(w, data);
+
+	w.open("patient");
+	render_patient(w, data, issues);
+	for (idx, reaction) in data.reactions.iter().enumerate() {
+		render_reaction(w, reaction, idx, issues);
+	}
+	for (idx, test) in data.tests.iter().enumerate() {
+		if is_free_text_test(test) {
+			continue;
+		}
+		render_test(w, test, idx, issues);
+	}
+	for (idx, drug) in data.drugs.iter().enumerate() {
+		render_drug(w, data, drug, idx, issues);
+	}
+	render_summary(w, data, issues);
+	w.close("patient");
+}
+
+fn render_primary_sources(
+	w: &mut Writer,
+	data: &CaseData,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	if data.literature.len() > 1
+		&& data.primary_sources.len() < data.literature.len()
+	{
+		loss_note(
+			issues,
+			"BFC.C.4.r.MERGED",
+			"R2 carries one literature reference per primary source; extra references were joined.",
+			"literatureReferences",
+			"case-identification",
+		);
+	}
+	let count = data.primary_sources.len().max(usize::from(
+		!data.literature.is_empty() || data.study.is_some(),
+	));
+	for idx in 0..count {
+		let source = data.primary_sources.get(idx);
+		w.open("primarysource");
+		if let Some(source) = source {
+			w.field("reportertitle", source.reporter_title.as_deref());
+			w.field("reportergivename", source.reporter_given_name.as_deref());
+			w.field("reportermiddlename", source.reporter_middle_name.as_deref());
+			w.field("reporterfamilyname", source.reporter_family_name.as_deref());
+			w.field("reporterorganization", source.organization.as_deref());
+			w.field("reporterdepartment", source.department.as_deref());
+			w.field("reporterstreet", source.street.as_deref());
+			w.field("reportercity", source.city.as_deref());
+			w.field("reporterstate", source.state.as_deref());
+			w.field("reporterpostcode", source.postcode.as_deref());
+			w.field("reportercountry", source.country_code.as_deref());
+			w.field("qualification", source.qualification.as_deref());
+			if source.telephone.is_some() || source.email.is_some() {
+				loss_note(
+					issues,
+					"BFC.C.2.r.2.DROPPED",
+					"Reporter telephone and e-mail have no R2 element and were dropped.",
+					format!("primarySources.{idx}"),
+					"reporter",
+				);
+			}
+		}
+		// The last primary source takes any literature references left over.
+		let references: Vec<&str> = if idx + 1 == count {
+			data.literature
+				.iter()
+				.skip(idx)
+				.map(|l| l.reference_text.as_str())
+				.collect()
+		} else {
+			data.literature
+				.get(idx)
+				.map(|l| vec![l.reference_text.as_str()])
+				.unwrap_or_default()
+		};
+		if !references.is_empty() {
+			w.field("literaturereference", Some(&references.join("; ")));
+		}
+		if idx == 0 {
+			if let Some(study) = data.study.as_ref() {
+				w.field("studyname", study.study_name.as_deref());
+				w.field("sponsorstudynumb", study.sponsor_study_number.as_deref());
+				w.field("observestudytype", study.study_type_reaction.as_deref());
+			}
+		}
+		w.close("primarysource");
+	}
+}
+
+fn render_sender(w: &mut Writer, data: &CaseData) {
+	if let Some(sender) = data.sender.as_ref() {
+		w.open("sender");
+		w.field("sendertype", Some(&sender.sender_type));
+		w.field("senderorganization", Some(&sender.organization_name));
+		w.field("senderdepartment", sender.department.as_deref());
+		w.field("sendertitle", sender.person_title.as_deref());
+		w.field("sendergivename", sender.person_given_name.as_deref());
+		w.field("sendermiddlename", sender.person_middle_name.as_deref());
+		w.field("senderfamilyname", sender.person_family_name.as_deref());
+		w.field("senderstreetaddress", sender.street_address.as_deref());
+		w.field("sendercity", sender.city.as_deref());
+		w.field("senderstate", sender.state.as_deref());
+		w.field("senderpostcode", sender.postcode.as_deref());
+		w.field("sendercountrycode", sender.country_code.as_deref());
+		w.field("sendertel", sender.telephone.as_deref());
+		w.field("senderfax", sender.fax.as_deref());
+		w.field("senderemailaddress", sender.email.as_deref());
+		w.close("sender");
+	}
+	if let Some(receiver) = data.receiver.as_ref() {
+		w.open("receiver");
+		w.field("receivertype", receiver.receiver_type.as_deref());
+		w.field(
+			"receiverorganization",
+			receiver.organization_name.as_deref(),
+		);
+		w.field("receiverdepartment", receiver.department.as_deref());
+		w.field("receiverstreetaddress", receiver.street_address.as_deref());
+		w.field("receivercity", receiver.city.as_deref());
+		w.field("receiverstate", receiver.state_province.as_deref());
+		w.field("receiverpostcode", receiver.postcode.as_deref());
+		w.field("receivercountrycode", receiver.country_code.as_deref());
+		w.field("receivertel", receiver.telephone.as_deref());
+		w.field("receiverfax", receiver.fax.as_deref());
+		w.field("receiveremailaddress", receiver.email.as_deref());
+		w.close("receiver");
+	}
+}
+
+fn render_patient(
+	w: &mut Writer,
+	data: &CaseData,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	if let Some(patient) = data.patient.as_ref() {
+		w.field("patientinitial", patient.patient_initials.as_deref());
+		if patient.patient_given_name.is_some()
+			|| patient.patient_family_name.is_some()
+		{
+			loss_note(
+				issues,
+				"BFC.D.1.DROPPED",
+				"Patient name has no R2 element; only the initials were sent.",
+				"patientInformation.patientName",
+				"patient",
+			);
+		}
+	}
+	for (type_code, element) in [
+		("1", "patientgpmedicalrecordnumb"),
+		("2", "patientspecialistrecordnumb"),
+		("3", "patienthospitalrecordnumb"),
+		("4", "patientinvestigationnumb"),
+	] {
+		w.field(
+			element,
+			data.patient_identifiers
+				.iter()
+				.find(|i| i.identifier_type_code == type_code)
+				.map(|i| i.identifier_value.as_str()),
+		);
+	}
+	if let Some(patient) = data.patient.as_ref() {
+		w.date(
+			"patientbirthdateformat",
+			"patientbirthdate",
+			patient.birth_date.as_ref(),
+		);
+		w.value("patientonsetage", patient.age_at_time_of_onset);
+		w.field(
+			"patientonsetageunit",
+			unit_to_r2(
+				patient.age_unit.as_deref(),
+				"patientInformation.ageUnit",
+				issues,
+			),
+		);
+		w.value("gestationperiod", patient.gestation_period);
+		w.field(
+			"gestationperiodunit",
+			unit_to_r2(
+				patient.gestation_period_unit.as_deref(),
+				"patientInformation.gestationPeriodUnit",
+				issues,
+			),
+		);
+		match patient.age_group.as_deref() {
+			Some("0") => loss_note(
+				issues,
+				"BFC.D.2.3.DROPPED",
+				"Age group 'foetus' has no R2 code and was dropped.",
+				"patientInformation.ageGroup",
+				"patient",
+			),
+			group => w.field("patientagegroup", group),
+		}
+		w.value("patientweight", patient.weight_kg);
+		w.value("patientheight", patient.height_cm);
+		w.field("patientsex", patient.sex.as_deref());
+		w.date(
+			"lastmenstrualdateformat",
+			"patientlastmenstrualdate",
+			patient.last_menstrual_period_date.as_ref(),
+		);
+		w.field(
+			"patientmedicalhistorytext",
+			patient.medical_history_text.as_deref(),
+		);
+		if patient.race_code.is_some() || patient.ethnicity_code.is_some() {
+			loss_note(
+				issues,
+				"BFC.FDA.D.11.DROPPED",
+				"Race and ethnicity have no R2 element and were dropped.",
+				"patientInformation.raceCode",
+				"patient",
+			);
+		}
+	}
+	let free_text: Vec<&str> = data
+		.tests
+		.iter()
+		.filter(|t| is_free_text_test(t))
+		.filter_map(|t| t.result_unstructured.as_deref())
+		.collect();
+	if !free_text.is_empty() {
+		w.field("resultstestsprocedures", Some(&free_text.join("\n")));
+	}
+
+	for episode in &data.medical_history {
+		w.open("medicalhistoryepisode");
+		w.field(
+			"patientepisodenamemeddraversion",
+			episode.meddra_version.as_deref(),
+		);
+		w.field("patientepisodename", episode.meddra_code.as_deref());
+		w.date(
+			"patientmedicalstartdateformat",
+			"patientmedicalstartdate",
+			episode.start_date.as_ref(),
+		);
+		w.flag("patientmedicalcontinue", episode.continuing);
+		w.date(
+			"patientmedicalenddateformat",
+			"patientmedicalenddate",
+			episode.end_date.as_ref(),
+		);
+		w.field("patientmedicalcomment", episode.comments.as_deref());
+		w.close("medicalhistoryepisode");
+		if episode.family_history == Some(true) {
+			loss_note(
+				issues,
+				"BFC.D.7.1.r.6.DROPPED",
+				"Family history flag has no R2 element and was dropped.",
+				format!("medicalHistory.{}", episode.sequence_number - 1),
+				"patient",
+			);
+		}
+	}
+	for therapy in &data.past_drugs {
+		w.open("patientpastdrugtherapy");
+		w.field("patientdrugname", therapy.drug_name.as_deref());
+		w.date(
+			"patientdrugstartdateformat",
+			"patientdrugstartdate",
+			therapy.start_date.as_ref(),
+		);
+		w.date(
+			"patientdrugenddateformat",
+			"patientdrugenddate",
+			therapy.end_date.as_ref(),
+		);
+		w.field(
+			"patientindicationmeddraversion",
+			therapy.indication_meddra_version.as_deref(),
+		);
+		w.field(
+			"patientdrugindication",
+			therapy.indication_meddra_code.as_deref(),
+		);
+		w.field(
+			"patientdrgreactionmeddraversion",
+			therapy.reaction_meddra_version.as_deref(),
+		);
+		w.field(
+			"patientdrugreaction",
+			therapy.reaction_meddra_code.as_deref(),
+		);
+		w.close("patientpastdrugtherapy");
+	}
+	if let Some(death) = data.death.as_ref() {
+		w.open("patientdeath");
+		w.date(
+			"patientdeathdateformat",
+			"patientdeathdate",
+			death.date_of_death.as_ref(),
+		);
+		w.field(
+			"patientautopsyyesno",
+			Some(match death.autopsy_performed {
+				Some(performed) => r2_flag(performed),
+				None => "3",
+			}),
+		);
+		for cause in &data.reported_causes {
+			w.open("patientdeathcause");
+			w.field(
+				"patientdeathreportmeddraversion",
+				cause.meddra_version.as_deref(),
+			);
+			w.field("patientdeathreport", cause.meddra_code.as_deref());
+			w.close("patientdeathcause");
+		}
+		for cause in &data.autopsy_causes {
+			w.open("patientautopsy");
+			w.field(
+				"patientdetermautopsmeddraversion",
+				cause.meddra_version.as_deref(),
+			);
+			w.field("patientdetermineautopsy", cause.meddra_code.as_deref());
+			w.close("patientautopsy");
+		}
+		w.close("patientdeath");
+	}
+}
+
+fn render_reaction(
+	w: &mut Writer,
+	reaction: &Reaction,
+	idx: usize,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	w.open("reaction");
+	w.field(
+		"primarysourcereaction",
+		Some(&reaction.primary_source_reaction),
+	);
+	w.field(
+		"reactionmeddraversionllt",
+		reaction.reaction_meddra_version.as_deref(),
+	);
+	w.field(
+		"reactionmeddrallt",
+		reaction.reaction_meddra_code.as_deref(),
+	);
+	if let Some(highlighted) = reaction.term_highlighted {
+		w.field(
+			"termhighlighted",
+			Some(term_highlighted_to_r2(
+				highlighted,
+				reaction.serious == Some(true),
+			)),
+		);
+	}
+	w.date(
+		"reactionstartdateformat",
+		"reactionstartdate",
+		reaction.start_date.as_ref(),
+	);
+	w.date(
+		"reactionenddateformat",
+		"reactionenddate",
+		reaction.end_date.as_ref(),
+	);
+	w.value("reactionduration", reaction.duration_value);
+	w.field(
+		"reactiondurationunit",
+		unit_to_r2(
+			reaction.duration_unit.as_deref(),
+			&format!("reactions.{idx}.durationUnit"),
+			issues,
+		),
+	);
+	w.field(
+		"reactionoutcome",
+		reaction.outcome.as_deref().and_then(outcome_to_r2),
+	);
+	w.close("reaction");
+
+	if reaction.reaction_language.is_some() {
+		loss_note(
+			issues,
+			"BFC.E.i.1.1b.DROPPED",
+			"Reaction language has no R2 element and was dropped.",
+			format!("reactions.{idx}.reactionLanguage"),
+			"reactions",
+		);
+	}
+	if reaction.required_intervention.is_some() {
+		loss_note(
+			issues,
+			"BFC.FDA.E.i.3.2h.DROPPED",
+			"Required intervention has no R2 element and was dropped.",
+			format!("reactions.{idx}.requiredIntervention"),
+			"reactions",
+		);
+	}
+}
+
+/// Tests that only hold free text go to B.3.2 instead of a `test` element.
+fn is_free_text_test(test: &TestResult) -> bool {
+	test.result_unstructured.is_some()
+		&& test.test_result_value.is_none()
+		&& test.test_result_code.is_none()
+		&& test.test_date.is_none()
+		&& test.test_meddra_code.is_none()
+}
+
+fn render_test(
+	w: &mut Writer,
+	test: &TestResult,
+	idx: usize,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	w.open("test");
+	w.date("testdateformat", "testdate", test.test_date.as_ref());
+	w.field("testname", Some(&test.test_name));
+	w.field(
+		"testresult",
+		test.test_result_value
+			.as_deref()
+			.or(test.result_unstructured.as_deref())
+			.or(test.test_result_code.as_deref()),
+	);
+	w.field("testunit", test.test_result_unit.as_deref());
+	w.field("lowtestrange", test.normal_low_value.as_deref());
+	w.field("hightestrange", test.normal_high_value.as_deref());
+	w.flag("moreinformation", test.more_info_available);
+	w.close("test");
+
+	if test.test_meddra_code.is_some() {
+		loss_note(
+			issues,
+			"BFC.F.r.2.2b.DROPPED",
+			"R2 test names are free text; the MedDRA code was dropped.",
+			format!("testResults.{idx}.testMeddraCode"),
+			"tests",
+		);
+	}
+	if test.comments.is_some() {
+		loss_note(
+			issues,
+			"BFC.F.r.6.DROPPED",
+			"Test comments have no R2 element and were dropped.",
+			format!("testResults.{idx}.comments"),
+			"tests",
+		);
+	}
+}
+
+fn render_drug(
+	w: &mut Writer,
+	data: &CaseData,
+	drug: &DrugInformation,
+	idx: usize,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	let path = format!("drugs.{idx}");
+	let dosages: Vec<&DosageInformation> = data
+		.dosages
+		.iter()
+		.filter(|d| d.drug_id == drug.id)
+		.collect();
+	let indications: Vec<&DrugIndication> = data
+		.indications
+		.iter()
+		.filter(|i| i.drug_id == drug.id)
+		.collect();
+	let substances: Vec<&DrugActiveSubstance> = data
+		.substances
+		.iter()
+		.filter(|s| s.drug_id == drug.id)
+		.collect();
+	let assessments: Vec<&DrugReactionAssessment> = data
+		.assessments
+		.iter()
+		.filter(|a| a.drug_id == drug.id)
+		.collect();
+
+	if indications.len() > 1 {
+		loss_note(
+			issues,
+			"BFC.G.k.7.r.DROPPED",
+			"R2 carries one indication per drug; only the first was sent.",
+			format!("{path}.indications"),
+			"drugs",
+		);
+	}
+	if drug.mpid.is_some() || drug.phpid.is_some() {
+		loss_note(
+			issues,
+			"BFC.G.k.2.1.DROPPED",
+			"MPID/PhPID have no R2 element and were dropped.",
+			format!("{path}.mpid"),
+			"drugs",
+		);
+	}
+	if drug.investigational_product_blinded.is_some() {
+		loss_note(
+			issues,
+			"BFC.G.k.2.5.DROPPED",
+			"Investigational product blinded has no R2 element and was dropped.",
+			format!("{path}.investigationalProductBlinded"),
+			"drugs",
+		);
+	}
+	if substances
+		.iter()
+		.any(|s| s.substance_termid.is_some() || s.strength_value.is_some())
+	{
+		loss_note(
+			issues,
+			"BFC.G.k.2.3.r.DROPPED",
+			"Substance TermIDs and strengths have no R2 element and were dropped.",
+			format!("{path}.substances"),
+			"drugs",
+		);
+	}
+	if drug.rechallenge.as_deref() == Some("4") {
+		loss_note(
+			issues,
+			"BFC.G.k.9.i.4.DROPPED",
+			"'No rechallenge' has no R2 code; B.4.k.17.1 was left empty.",
+			format!("{path}.rechallenge"),
+			"drugs",
+		);
+	}
+	let start_period = assessments.iter().find_map(|a| {
+		Some((a.time_interval_value?, a.time_interval_unit.as_deref()?))
+	});
+
+	let regimens: Vec<Option<&DosageInformation>> = if dosages.is_empty() {
+		vec![None]
+	} else {
+		dosages.into_iter().map(Some).collect()
+	};
+	for dosage in regimens {
+		w.open("drug");
+		w.field("drugcharacterization", Some(&drug.drug_characterization));
+		w.field("medicinalproduct", Some(&drug.medicinal_product));
+		w.field("obtaindrugcountry", drug.obtain_drug_country.as_deref());
+		w.field(
+			"drugbatchnumb",
+			dosage
+				.and_then(|d| d.batch_lot_number.as_deref())
+				.or(drug.batch_lot_number.as_deref()),
+		);
+		w.field(
+			"drugauthorizationcountry",
+			drug.manufacturer_country.as_deref(),
+		);
+		w.field("drugauthorizationholder", drug.manufacturer_name.as_deref());
+		if let Some(dosage) = dosage {
+			w.value("drugstructuredosagenumb", dosage.dose_value);
+			let unit = dosage.dose_unit.as_deref();
+			let r2_unit = unit.and_then(dose_unit_from_ucum);
+			if let Some(unit) = unit.filter(|_| r2_unit.is_none()) {
+				loss_note(
+					issues,
+					"BFC.G.k.4.r.1b.UNMAPPED",
+					&format!("Dose unit '{unit}' has no R2 code and was dropped."),
+					format!(
+						"{path}.dosages.{}.doseUnit",
+						dosage.sequence_number - 1
+					),
+					"drugs",
+				);
+			}
+			w.field("drugstructuredosageunit", r2_unit);
+			w.value("drugseparatedosagenumb", dosage.number_of_units);
+			w.value("drugintervaldosageunitnumb", dosage.frequency_value);
+			w.field(
+				"drugintervaldosagedefinition",
+				dosage
+					.frequency_unit
+					.as_deref()
+					.and_then(time_unit_from_ucum),
+			);
+		}
+		w.field(
+			"drugdosagetext",
+			dosage
+				.and_then(|d| d.dosage_text.as_deref())
+				.or(drug.dosage_text.as_deref()),
+		);
+		if let Some(dosage) = dosage {
+			w.field("drugdosageform", dosage.dose_form.as_deref());
+			w.field(
+				"drugadministrationroute",
+				dosage.route_of_administration.as_deref(),
+			);
+			w.field(
+				"drugparadministration",
+				dosage
+					.parent_route
+					.as_deref()
+					.or(drug.parent_route.as_deref()),
+			);
+		}
+		if let Some(indication) = indications.first() {
+			w.field(
+				"drugindicationmeddraversion",
+				indication.indication_meddra_version.as_deref(),
+			);
+			w.field(
+				"drugindication",
+				indication
+					.indication_meddra_code
+					.as_deref()
+					.or(indication.indication_text.as_deref()),
+			);
+		}
+		if let Some(dosage) = dosage {
+			w.date(
+				"drugstartdateformat",
+				"drugstartdate",
+				dosage.first_administration_date.as_ref(),
+			);
+		}
+		if let Some((value, unit)) = start_period {
+			w.value("drugstartperiod", Some(value));
+			w.field("drugstartperiodunit", time_unit_from_ucum(unit));
+		}
+		if let Some(dosage) = dosage {
+			w.date(
+				"drugenddateformat",
+				"drugenddate",
+				dosage.last_administration_date.as_ref(),
+			);
+			w.value("drugtreatmentduration", dosage.duration_value);
+			w.field(
+				"drugtreatmentdurationunit",
+				unit_to_r2(
+					dosage.duration_unit.as_deref(),
+					&format!(
+						"{path}.dosages.{}.durationUnit",
+						dosage.sequence_number - 1
+					),
+					issues,
+				),
+			);
+		}
+		w.field(
+			"actiondrug",
+			drug.action_taken.as_deref().and_then(action_to_r2),
+		);
+		w.field(
+			"drugrecurreadministration",
+			drug.rechallenge
+				.as_deref()
+				.filter(|r| matches!(*r, "1" | "2" | "3")),
+		);
+		for substance in &substances {
+			w.open("activesubstance");
+			w.field("activesubstancename", substance.substance_name.as_deref());
+			w.close("activesubstance");
+		}
+		for assessment in &assessments {
+			let Some(reaction) = data
+				.reactions
+				.iter()
+				.find(|r| r.id == assessment.reaction_id)
+			else {
+				continue;
+			};
+			for relatedness in data
+				.relatedness
+				.iter()
+				.filter(|r| r.drug_reaction_assessment_id == assessment.id)
+			{
+				w.open("drugreactionrelatedness");
+				w.field(
+					"drugreactionassesmeddraversion",
+					reaction.reaction_meddra_version.as_deref(),
+				);
+				w.field(
+					"drugreactionasses",
+					reaction
+						.reaction_meddra_code
+						.as_deref()
+						.or(Some(&reaction.primary_source_reaction)),
+				);
+				w.field(
+					"drugassessmentsource",
+					relatedness.source_of_assessment.as_deref(),
+				);
+				w.field(
+					"drugassessmentmethod",
+					relatedness.method_of_assessment.as_deref(),
+				);
+				w.field("drugresult", relatedness.result_of_assessment.as_deref());
+				w.close("drugreactionrelatedness");
+			}
+		}
+		w.close("drug");
+	}
+}
+
+fn render_summary(
+	w: &mut Writer,
+	data: &CaseData,
+	issues: &mut Vec<ValidationIssue>,
+) {
+	let Some(narrative) = data.narrative.as_ref() else {
+		return;
+	};
+	w.open("summary");
+	w.field("narrativeincludeclinical", Some(&narrative.case_narrative));
+	w.field("reportercomment", narrative.reporter_comments.as_deref());
+	if let Some(diagnosis) = data.diagnoses.first() {
+		w.field(
+			"senderdiagnosismeddraversion",
+			diagnosis.diagnosis_meddra_version.as_deref(),
+		);
+		w.field(
+			"senderdiagnosis",
+			diagnosis.diagnosis_meddra_code.as_deref(),
+		);
+	}
+	w.field("sendercomment", narrative.sender_comments.as_deref());
+	w.close("summary");
+	if data.diagnoses.len() > 1 {
+		loss_note(
+			issues,
+			"BFC.H.3.r.DROPPED",
+			"R2 carries one sender's diagnosis; only the first was sent.",
+			"narrative.senderDiagnoses",
+			"narrative",
+		);
+	}
+}
+
+/// Age, duration and gestation units back to the R2 800-series codes.
+fn unit_to_r2<'a>(
+	unit: Option<&'a str>,
+	path: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> Option<&'a str> {
+	let unit = unit?;
+	match time_unit_from_ucum(unit) {
+		Some(code) => Some(code),
+		None => {
+			loss_note(
+				issues,
+				"BFC.UNIT.UNMAPPED",
+				&format!("Unit '{unit}' has no R2 code and was dropped."),
+				path,
+				"patient",
+			);
+			None
+		}
+	}
+}
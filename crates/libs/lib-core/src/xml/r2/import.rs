@@ -0,0 +1,1046 @@
+use crate::ctx::Ctx;
+use crate::model::case::{CaseBmc, CaseForCreate};
+use crate::model::case_identifiers::{
+	LinkedReportNumberBmc, LinkedReportNumberForCreate, OtherCaseIdentifierBmc,
+	OtherCaseIdentifierForCreate,
+};
+use crate::model::drug::{
+	DosageInformationBmc, DosageInformationForCreate, DrugActiveSubstanceBmc,
+	DrugActiveSubstanceForCreate, DrugIndicationBmc, DrugIndicationForCreate,
+	DrugInformationBmc, DrugInformationForCreate, DrugInformationForUpdate,
+};
+use crate::model::drug_reaction_assessment::{
+	DrugReactionAssessmentBmc, DrugReactionAssessmentForCreate,
+	DrugReactionAssessmentForUpdate, RelatednessAssessmentBmc,
+	RelatednessAssessmentForCreate, RelatednessAssessmentForUpdate,
+};
+use crate::model::message_header::{MessageHeaderBmc, MessageHeaderForCreate};
+use crate::model::narrative::{
+	NarrativeInformationBmc, NarrativeInformationForCreate,
+	NarrativeInformationForUpdate, SenderDiagnosisBmc, SenderDiagnosisForCreate,
+	SenderDiagnosisForUpdate,
+};
+use crate::model::patient::{
+	AutopsyCauseOfDeathBmc, AutopsyCauseOfDeathForCreate,
+	AutopsyCauseOfDeathForUpdate, MedicalHistoryEpisodeBmc,
+	MedicalHistoryEpisodeForCreate, MedicalHistoryEpisodeForUpdate,
+	PastDrugHistoryBmc, PastDrugHistoryForCreate, PatientDeathInformationBmc,
+	PatientDeathInformationForCreate, PatientIdentifierBmc,
+	PatientIdentifierForCreate, PatientInformationBmc, PatientInformationForCreate,
+	PatientInformationForUpdate, ReportedCauseOfDeathBmc,
+	ReportedCauseOfDeathForCreate, ReportedCauseOfDeathForUpdate,
+};
+use crate::model::reaction::{ReactionBmc, ReactionForCreate, ReactionForUpdate};
+use crate::model::receiver::{
+	ReceiverInformationBmc, ReceiverInformationForCreate,
+	ReceiverInformationForUpdate,
+};
+use crate::model::reporting_deadline::CaseReportingDeadlineBmc;
+use crate::model::safety_report::{
+	DocumentsHeldBySenderBmc, DocumentsHeldBySenderForCreate,
+	LiteratureReferenceBmc, LiteratureReferenceForCreate, PrimarySourceBmc,
+	PrimarySourceForCreate, PrimarySourceForUpdate, SafetyReportIdentificationBmc,
+	SafetyReportIdentificationForCreate, SafetyReportIdentificationForUpdate,
+	SenderInformationBmc, SenderInformationForCreate, SenderInformationForUpdate,
+	StudyInformationBmc, StudyInformationForCreate, StudyInformationForUpdate,
+};
+use crate::model::store::set_full_context_dbx_or_rollback;
+use crate::model::test_result::{
+	TestResultBmc, TestResultForCreate, TestResultForUpdate,
+};
+use crate::model::{self, ModelManager};
+use crate::xml::import::{make_import_message_number, normalize_message_date};
+use crate::xml::r2::loss_note;
+use crate::xml::r2::parse::{
+	full_date, parse_r2_xml, R2MessageHeader, R2Patient, R2SafetyReport,
+};
+use crate::xml::types::{XmlR2ImportReportResult, XmlR2ImportResult};
+use crate::xml::validate::ValidationIssue;
+use crate::xml::Result;
+use sqlx::types::Uuid;
+use time::{Date, OffsetDateTime};
+
+/// Imports every `safetyreport` of an E2B(R2) `ichicsr` message as a case.
+///
+/// Each report is created in its own transaction as a draft case built from
+/// the model (no raw R3 XML is kept); a failing report does not roll back
+/// the others. BFC loss notes are returned per report.
+pub async fn import_r2_xml(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	xml: &[u8],
+) -> Result<XmlR2ImportResult> {
+	let message = parse_r2_xml(xml)?;
+
+	let mut reports = Vec::with_capacity(message.reports.len());
+	for report in message.reports {
+		let mut result = XmlR2ImportReportResult {
+			index: report.index,
+			safety_report_id: report.safety_report_id.clone(),
+			case_id: None,
+			case_version: None,
+			error: None,
+			issues: Vec::new(),
+		};
+		match import_report_in_txn(ctx, mm, &message.header, report).await {
+			Ok((case_id, version, issues)) => {
+				result.case_id = Some(case_id);
+				result.case_version = Some(version);
+				result.issues = issues;
+			}
+			Err(err) => {
+				tracing::warn!(
+					index = result.index,
+					error = %err,
+					"R2 report import failed"
+				);
+				result.error = Some(err.to_string());
+			}
+		}
+		reports.push(result);
+	}
+
+	let failed = reports.iter().filter(|r| r.error.is_some()).count();
+	Ok(XmlR2ImportResult {
+		message_number: message.header.message_number,
+		total: reports.len(),
+		imported: reports.len() - failed,
+		failed,
+		reports,
+	})
+}
+
+async fn import_report_in_txn(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	header: &R2MessageHeader,
+	report: R2SafetyReport,
+) -> Result<(Uuid, i32, Vec<ValidationIssue>)> {
+	let mm = mm.new_with_txn()?;
+	let dbx = mm.dbx();
+	dbx.begin_txn().await.map_err(model::Error::from)?;
+	set_full_context_dbx_or_rollback(
+		dbx,
+		ctx.user_id(),
+		ctx.organization_id(),
+		ctx.role(),
+	)
+	.await?;
+	match import_report(ctx, &mm, header, report).await {
+		Ok(result) => {
+			dbx.commit_txn().await.map_err(model::Error::from)?;
+			Ok(result)
+		}
+		Err(err) => {
+			let _ = dbx.rollback_txn().await;
+			Err(err)
+		}
+	}
+}
+
+async fn import_report(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	header: &R2MessageHeader,
+	mut report: R2SafetyReport,
+) -> Result<(Uuid, i32, Vec<ValidationIssue>)> {
+	let mut issues = std::mem::take(&mut report.issues);
+	let base = format!("safetyreport[{}]", report.index + 1);
+	let safety_report_id = report
+		.safety_report_id
+		.clone()
+		.map(|id| id.chars().take(100).collect::<String>())
+		.unwrap_or_else(|| "UNKNOWN".to_string());
+
+	let sql = "select max(version) from cases where safety_report_id = $1";
+	let max_version: (Option<i32>,) = mm
+		.dbx()
+		.fetch_one(sqlx::query_as(sql).bind(&safety_report_id))
+		.await
+		.map_err(model::Error::from)?;
+	let version = max_version.0.unwrap_or(0) + 1;
+
+	let case_id = CaseBmc::create(
+		ctx,
+		mm,
+		CaseForCreate {
+			organization_id: ctx.organization_id(),
+			safety_report_id: safety_report_id.clone(),
+			dg_prd_key: None,
+			status: Some("draft".to_string()),
+			validation_profile: Some("ich".to_string()),
+			version: Some(version),
+		},
+	)
+	.await?;
+
+	if let (Some(sender), Some(receiver), Some(date)) = (
+		header.sender_identifier.clone(),
+		header.receiver_identifier.clone(),
+		header.message_date.clone().and_then(normalize_message_date),
+	) {
+		let base_number = header
+			.message_number
+			.clone()
+			.unwrap_or_else(|| safety_report_id.clone());
+		MessageHeaderBmc::create(
+			ctx,
+			mm,
+			MessageHeaderForCreate {
+				case_id,
+				message_number: make_import_message_number(&base_number, case_id),
+				message_sender_identifier: sender,
+				message_receiver_identifier: receiver,
+				message_date: date,
+			},
+		)
+		.await?;
+	}
+
+	// -- C.1 Identification of the case safety report
+	let today = OffsetDateTime::now_utc().date();
+	let transmission_date = c1_date(
+		report.transmission_date,
+		today,
+		"A.1.3",
+		&format!("{base}/transmissiondate"),
+		&mut issues,
+	);
+	let receive_date = c1_date(
+		report.receive_date.or(report.receipt_date),
+		transmission_date,
+		"A.1.6",
+		&format!("{base}/receivedate"),
+		&mut issues,
+	);
+	let receipt_date = c1_date(
+		report.receipt_date,
+		receive_date,
+		"A.1.7",
+		&format!("{base}/receiptdate"),
+		&mut issues,
+	);
+	let report_type = report.report_type.clone().unwrap_or_else(|| {
+		loss_note(
+			&mut issues,
+			"BFC.A.1.4.DEFAULTED",
+			"Type of report was missing; C.1.3 was set to 4 (not available to sender).",
+			format!("{base}/reporttype"),
+			"case-identification",
+		);
+		"4".to_string()
+	});
+	SafetyReportIdentificationBmc::create(
+		ctx,
+		mm,
+		SafetyReportIdentificationForCreate {
+			case_id,
+			transmission_date,
+			report_type: report_type.clone(),
+			date_first_received_from_source: receive_date,
+			date_of_most_recent_information: receipt_date,
+			fulfil_expedited_criteria: report
+				.fulfil_expedited_criteria
+				.unwrap_or(false),
+		},
+	)
+	.await?;
+	SafetyReportIdentificationBmc::update_by_case(
+		ctx,
+		mm,
+		case_id,
+		SafetyReportIdentificationForUpdate {
+			transmission_date: None,
+			report_type: None,
+			local_criteria_report_type: None,
+			combination_product_report_indicator: None,
+			worldwide_unique_id: report.worldwide_unique_id.clone(),
+			nullification_code: report.nullification_code.clone(),
+			nullification_reason: report.nullification_reason.clone(),
+			receiver_organization: report
+				.receiver
+				.as_ref()
+				.and_then(|r| r.organization.clone()),
+		},
+	)
+	.await?;
+
+	for (idx, title) in report.documents.iter().enumerate() {
+		DocumentsHeldBySenderBmc::create(
+			ctx,
+			mm,
+			DocumentsHeldBySenderForCreate {
+				case_id,
+				title: Some(title.clone()),
+				document_base64: None,
+				media_type: None,
+				representation: None,
+				compression: None,
+				sequence_number: idx as i32 + 1,
+			},
+		)
+		.await?;
+	}
+	for (idx, (source, number)) in report.other_identifiers.iter().enumerate() {
+		OtherCaseIdentifierBmc::create(
+			ctx,
+			mm,
+			OtherCaseIdentifierForCreate {
+				case_id,
+				sequence_number: idx as i32 + 1,
+				source_of_identifier: source.clone(),
+				case_identifier: number.clone(),
+			},
+		)
+		.await?;
+	}
+	for (idx, number) in report.linked_reports.iter().enumerate() {
+		LinkedReportNumberBmc::create(
+			ctx,
+			mm,
+			LinkedReportNumberForCreate {
+				case_id,
+				sequence_number: idx as i32 + 1,
+				linked_report_number: number.clone(),
+			},
+		)
+		.await?;
+	}
+
+	// -- C.2 Primary sources, C.4 literature, C.5 study
+	for (idx, source) in report.primary_sources.iter().enumerate() {
+		let id = PrimarySourceBmc::create(
+			ctx,
+			mm,
+			PrimarySourceForCreate {
+				case_id,
+				sequence_number: idx as i32 + 1,
+				qualification: source.qualification.clone(),
+			},
+		)
+		.await?;
+		PrimarySourceBmc::update(
+			ctx,
+			mm,
+			id,
+			PrimarySourceForUpdate {
+				reporter_title: source.title.clone(),
+				reporter_given_name: source.given_name.clone(),
+				reporter_middle_name: source.middle_name.clone(),
+				reporter_family_name: source.family_name.clone(),
+				organization: source.organization.clone(),
+				department: source.department.clone(),
+				street: source.street.clone(),
+				city: source.city.clone(),
+				state: source.state.clone(),
+				postcode: source.postcode.clone(),
+				telephone: None,
+				country_code: source.country_code.clone(),
+				email: None,
+				qualification: source.qualification.clone(),
+				// BFC: the first R2 primary source is the one for regulatory purposes.
+				primary_source_regulatory: (idx == 0).then(|| "1".to_string()),
+				kr_other_health_professional: None,
+			},
+		)
+		.await?;
+	}
+	for (idx, reference) in report.literature.iter().enumerate() {
+		LiteratureReferenceBmc::create(
+			ctx,
+			mm,
+			LiteratureReferenceForCreate {
+				case_id,
+				reference_text: reference.clone(),
+				sequence_number: idx as i32 + 1,
+				document_base64: None,
+				media_type: None,
+				representation: None,
+				compression: None,
+			},
+		)
+		.await?;
+	}
+	if let Some(study) = report.study.as_ref() {
+		let id = StudyInformationBmc::create(
+			ctx,
+			mm,
+			StudyInformationForCreate {
+				case_id,
+				study_name: study.study_name.clone(),
+				sponsor_study_number: study.sponsor_study_number.clone(),
+			},
+		)
+		.await?;
+		StudyInformationBmc::update(
+			ctx,
+			mm,
+			id,
+			StudyInformationForUpdate {
+				study_name: None,
+				sponsor_study_number: None,
+				study_type_reaction: study.study_type.clone(),
+				kr_other_study_type: None,
+			},
+		)
+		.await?;
+	}
+
+	// -- C.3 Sender and receiver
+	if let Some(sender) = report.sender.as_ref() {
+		let sender_type = sender
+			.sender_type
+			.clone()
+			.unwrap_or_else(|| "6".to_string());
+		let organization_name = sender.organization.clone().unwrap_or_default();
+		let id = SenderInformationBmc::create(
+			ctx,
+			mm,
+			SenderInformationForCreate {
+				case_id,
+				sender_type: sender_type.clone(),
+				organization_name: organization_name.clone(),
+			},
+		)
+		.await?;
+		SenderInformationBmc::update(
+			ctx,
+			mm,
+			id,
+			SenderInformationForUpdate {
+				sender_type: Some(sender_type),
+				organization_name: Some(organization_name),
+				department: sender.department.clone(),
+				street_address: sender.street.clone(),
+				city: sender.city.clone(),
+				state: sender.state.clone(),
+				postcode: sender.postcode.clone(),
+				country_code: sender.country_code.clone(),
+				person_title: sender.title.clone(),
+				person_given_name: sender.given_name.clone(),
+				person_middle_name: sender.middle_name.clone(),
+				person_family_name: sender.family_name.clone(),
+				telephone: sender.telephone.clone(),
+				fax: sender.fax.clone(),
+				email: sender.email.clone(),
+				kr_health_professional_type: None,
+			},
+		)
+		.await?;
+	}
+	if let Some(receiver) = report.receiver.as_ref() {
+		ReceiverInformationBmc::create(
+			ctx,
+			mm,
+			ReceiverInformationForCreate {
+				case_id,
+				receiver_type: receiver.receiver_type.clone(),
+				organization_name: receiver.organization.clone(),
+			},
+		)
+		.await?;
+		ReceiverInformationBmc::update_by_case(
+			ctx,
+			mm,
+			case_id,
+			ReceiverInformationForUpdate {
+				receiver_type: None,
+				organization_name: None,
+				department: receiver.department.clone(),
+				street_address: receiver.street.clone(),
+				city: receiver.city.clone(),
+				state_province: receiver.state.clone(),
+				postcode: receiver.postcode.clone(),
+				country_code: receiver.country_code.clone(),
+				telephone: receiver.telephone.clone(),
+				fax: receiver.fax.clone(),
+				email: receiver.email.clone(),
+			},
+		)
+		.await?;
+	}
+
+	// -- D Patient
+	if let Some(patient) = report.patient.as_ref() {
+		import_patient(ctx, mm, case_id, patient).await?;
+	}
+
+	// -- E Reactions, F tests
+	let mut reaction_ids = Vec::with_capacity(report.reactions.len());
+	for (idx, reaction) in report.reactions.iter().enumerate() {
+		let primary_source_reaction = reaction
+			.primary_source_reaction
+			.clone()
+			.or_else(|| reaction.meddra_code.clone())
+			.unwrap_or_default();
+		let id = ReactionBmc::create(
+			ctx,
+			mm,
+			ReactionForCreate {
+				case_id,
+				sequence_number: idx as i32 + 1,
+				primary_source_reaction: primary_source_reaction.clone(),
+			},
+		)
+		.await?;
+		ReactionBmc::update(
+			ctx,
+			mm,
+			id,
+			ReactionForUpdate {
+				primary_source_reaction: Some(primary_source_reaction),
+				reaction_language: None,
+				reaction_meddra_version: reaction.meddra_version.clone(),
+				reaction_meddra_code: reaction.meddra_code.clone(),
+				term_highlighted: reaction.term_highlighted,
+				serious: reaction.serious,
+				criteria_death: Some(reaction.criteria_death),
+				criteria_life_threatening: Some(reaction.criteria_life_threatening),
+				criteria_hospitalization: Some(reaction.criteria_hospitalization),
+				criteria_disabling: Some(reaction.criteria_disabling),
+				criteria_congenital_anomaly: Some(
+					reaction.criteria_congenital_anomaly,
+				),
+				criteria_other_medically_important: Some(
+					reaction.criteria_other_medically_important,
+				),
+				required_intervention: None,
+				start_date: reaction.start_date,
+				end_date: reaction.end_date,
+				duration_value: reaction.duration_value,
+				duration_unit: reaction.duration_unit.clone(),
+				outcome: reaction.outcome.clone(),
+				medical_confirmation: reaction.medical_confirmation,
+				country_code: reaction.country_code.clone(),
+			},
+		)
+		.await?;
+		reaction_ids.push(id);
+	}
+	for (idx, test) in report.tests.iter().enumerate() {
+		let test_name = test.test_name.clone().unwrap_or_default();
+		let id = TestResultBmc::create(
+			ctx,
+			mm,
+			TestResultForCreate {
+				case_id,
+				sequence_number: idx as i32 + 1,
+				test_name: test_name.clone(),
+			},
+		)
+		.await?;
+		TestResultBmc::update(
+			ctx,
+			mm,
+			id,
+			TestResultForUpdate {
+				test_name: Some(test_name),
+				test_date: test.test_date,
+				test_meddra_version: None,
+				test_meddra_code: None,
+				test_result_code: None,
+				test_result_value: test.result_value.clone(),
+				test_result_unit: test.result_unit.clone(),
+				result_unstructured: test.result_unstructured.clone(),
+				normal_low_value: test.normal_low.clone(),
+				normal_high_value: test.normal_high.clone(),
+				comments: None,
+				more_info_available: test.more_info_available,
+			},
+		)
+		.await?;
+	}
+
+	// -- G Drugs
+	for (idx, drug) in report.drugs.iter().enumerate() {
+		let path = format!("{base}/patient/drug[{}]", idx + 1);
+		let characterization = drug
+			.characterization
+			.clone()
+			.filter(|c| matches!(c.as_str(), "1" | "2" | "3"))
+			.unwrap_or_else(|| {
+				loss_note(
+					&mut issues,
+					"BFC.B.4.k.1.DEFAULTED",
+					"Drug characterisation was missing or invalid; G.k.1 was set to 1 (suspect).",
+					format!("{path}/drugcharacterization"),
+					"drugs",
+				);
+				"1".to_string()
+			});
+		let medicinal_product = drug
+			.medicinal_product
+			.clone()
+			.or_else(|| drug.substances.first().cloned())
+			.unwrap_or_default();
+		let drug_id = DrugInformationBmc::create(
+			ctx,
+			mm,
+			DrugInformationForCreate {
+				case_id,
+				sequence_number: idx as i32 + 1,
+				drug_characterization: characterization,
+				medicinal_product: medicinal_product.clone(),
+			},
+		)
+		.await?;
+		DrugInformationBmc::update(
+			ctx,
+			mm,
+			drug_id,
+			DrugInformationForUpdate {
+				medicinal_product: None,
+				drug_characterization: None,
+				brand_name: None,
+				manufacturer_name: drug.authorization_holder.clone(),
+				manufacturer_country: drug.authorization_country.clone(),
+				batch_lot_number: drug.batch_lot_number.clone(),
+				dosage_text: None,
+				action_taken: drug.action_taken.clone(),
+				rechallenge: drug.rechallenge.clone(),
+				investigational_product_blinded: None,
+				mpid: None,
+				mpid_version: None,
+				obtain_drug_country: drug.obtain_drug_country.clone(),
+				parent_route: None,
+				parent_route_termid: None,
+				parent_route_termid_version: None,
+				parent_dosage_text: None,
+				fda_additional_info_coded: None,
+				kr_product_code_version: None,
+				kr_product_code: None,
+			},
+		)
+		.await?;
+
+		for (seq, name) in drug.substances.iter().enumerate() {
+			DrugActiveSubstanceBmc::create(
+				ctx,
+				mm,
+				DrugActiveSubstanceForCreate {
+					drug_id,
+					sequence_number: seq as i32 + 1,
+					substance_name: Some(name.clone()),
+					substance_termid: None,
+					substance_termid_version: None,
+					strength_value: None,
+					strength_unit: None,
+					kr_ingredient_code_version: None,
+					kr_ingredient_code: None,
+				},
+			)
+			.await?;
+		}
+
+		let dosage = &drug.dosage;
+		if !dosage.is_empty() {
+			DosageInformationBmc::create(
+				ctx,
+				mm,
+				DosageInformationForCreate {
+					drug_id,
+					sequence_number: 1,
+					dose_value: dosage.dose_value,
+					dose_unit: dosage.dose_unit.clone(),
+					number_of_units: dosage.number_of_units,
+					frequency_value: dosage.frequency_value,
+					frequency_unit: dosage.frequency_unit.clone(),
+					first_administration_date: dosage.start_date,
+					first_administration_time: None,
+					last_administration_date: dosage.end_date,
+					last_administration_time: None,
+					duration_value: dosage.duration_value,
+					duration_unit: dosage.duration_unit.clone(),
+					batch_lot_number: None,
+					dosage_text: dosage.dosage_text.clone(),
+					dose_form: dosage.dose_form.clone(),
+					dose_form_termid: None,
+					dose_form_termid_version: None,
+					route_of_administration: dosage.route.clone(),
+					parent_route: dosage.parent_route.clone(),
+					parent_route_termid: None,
+					parent_route_termid_version: None,
+					first_administration_date_null_flavor: None,
+					last_administration_date_null_flavor: None,
+				},
+			)
+			.await?;
+		}
+
+		if let Some(indication) = drug.indication.as_ref() {
+			DrugIndicationBmc::create(
+				ctx,
+				mm,
+				DrugIndicationForCreate {
+					drug_id,
+					sequence_number: 1,
+					indication_text: indication.text.clone(),
+					indication_meddra_version: indication.meddra_version.clone(),
+					indication_meddra_code: indication.meddra_code.clone(),
+				},
+			)
+			.await?;
+		}
+
+		// B.4.k.18 names the reaction by MedDRA code or term; match it against
+		// the reactions imported above.
+		let mut assessed = Vec::new();
+		for (seq, relatedness) in drug.relatedness.iter().enumerate() {
+			let term = relatedness.reaction.trim();
+			let matched = report.reactions.iter().position(|r| {
+				r.meddra_code.as_deref() == Some(term)
+					|| r.primary_source_reaction
+						.as_deref()
+						.is_some_and(|p| p.eq_ignore_ascii_case(term))
+			});
+			let Some(reaction_pos) = matched else {
+				loss_note(
+					&mut issues,
+					"BFC.B.4.k.18.UNMAPPED",
+					&format!("Relatedness for '{term}' matches no reaction and was dropped."),
+					format!("{path}/drugreactionrelatedness[{}]", seq + 1),
+					"drugs",
+				);
+				continue;
+			};
+			let reaction_id = reaction_ids[reaction_pos];
+			let assessment_id =
+				match assessed.iter().find(|(r, _)| *r == reaction_id) {
+					Some((_, id)) => *id,
+					None => {
+						let id = DrugReactionAssessmentBmc::create(
+							ctx,
+							mm,
+							DrugReactionAssessmentForCreate {
+								drug_id,
+								reaction_id,
+							},
+						)
+						.await?;
+						assessed.push((reaction_id, id));
+						id
+					}
+				};
+			let relatedness_id = RelatednessAssessmentBmc::create(
+				ctx,
+				mm,
+				RelatednessAssessmentForCreate {
+					drug_reaction_assessment_id: assessment_id,
+					sequence_number: seq as i32 + 1,
+				},
+			)
+			.await?;
+			RelatednessAssessmentBmc::update(
+				ctx,
+				mm,
+				relatedness_id,
+				RelatednessAssessmentForUpdate {
+					source_of_assessment: relatedness.source.clone(),
+					method_of_assessment: relatedness.method.clone(),
+					result_of_assessment: relatedness.result.clone(),
+					kr_method_of_assessment: None,
+					kr_result_who_umc: None,
+					kr_result_korean: None,
+				},
+			)
+			.await?;
+		}
+
+		if let Some((value, unit)) = drug.start_period.as_ref() {
+			if assessed.is_empty() && reaction_ids.len() == 1 {
+				let id = DrugReactionAssessmentBmc::create(
+					ctx,
+					mm,
+					DrugReactionAssessmentForCreate {
+						drug_id,
+						reaction_id: reaction_ids[0],
+					},
+				)
+				.await?;
+				assessed.push((reaction_ids[0], id));
+			}
+			if assessed.is_empty() {
+				loss_note(
+					&mut issues,
+					"BFC.B.4.k.13.1.DROPPED",
+					"Time interval to reaction onset could not be tied to a reaction and was dropped.",
+					format!("{path}/drugstartperiod"),
+					"drugs",
+				);
+			}
+			for (_, assessment_id) in &assessed {
+				DrugReactionAssessmentBmc::update(
+					ctx,
+					mm,
+					*assessment_id,
+					DrugReactionAssessmentForUpdate {
+						time_interval_value: Some(*value),
+						time_interval_unit: Some(unit.clone()),
+						recurrence_action: None,
+						recurrence_meddra_version: None,
+						recurrence_meddra_code: None,
+						reaction_recurred: None,
+					},
+				)
+				.await?;
+			}
+		}
+	}
+
+	// -- H Narrative
+	if let Some(narrative) = report.narrative.as_ref() {
+		let case_narrative = narrative.case_narrative.clone().unwrap_or_default();
+		let narrative_id = NarrativeInformationBmc::create(
+			ctx,
+			mm,
+			NarrativeInformationForCreate {
+				case_id,
+				case_narrative: case_narrative.clone(),
+			},
+		)
+		.await?;
+		NarrativeInformationBmc::update_by_case(
+			ctx,
+			mm,
+			case_id,
+			NarrativeInformationForUpdate {
+				case_narrative: Some(case_narrative),
+				reporter_comments: narrative.reporter_comments.clone(),
+				sender_comments: narrative.sender_comments.clone(),
+			},
+		)
+		.await?;
+		if let Some(code) = narrative.diagnosis_meddra_code.clone() {
+			let id = SenderDiagnosisBmc::create(
+				ctx,
+				mm,
+				SenderDiagnosisForCreate {
+					narrative_id,
+					sequence_number: 1,
+					diagnosis_meddra_code: Some(code),
+				},
+			)
+			.await?;
+			SenderDiagnosisBmc::update(
+				ctx,
+				mm,
+				id,
+				SenderDiagnosisForUpdate {
+					diagnosis_meddra_version: narrative
+						.diagnosis_meddra_version
+						.clone(),
+					diagnosis_meddra_code: None,
+				},
+			)
+			.await?;
+		}
+	}
+
+	CaseReportingDeadlineBmc::refresh(ctx, mm, case_id).await?;
+
+	Ok((case_id, version, issues))
+}
+
+async fn import_patient(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+	patient: &R2Patient,
+) -> Result<()> {
+	let patient_id = PatientInformationBmc::create(
+		ctx,
+		mm,
+		PatientInformationForCreate {
+			case_id,
+			patient_initials: patient.initials.clone(),
+			sex: patient.sex.clone(),
+			concomitant_therapy: None,
+		},
+	)
+	.await?;
+	PatientInformationBmc::update(
+		ctx,
+		mm,
+		patient_id,
+		PatientInformationForUpdate {
+			patient_initials: None,
+			patient_given_name: None,
+			patient_family_name: None,
+			birth_date: patient.birth_date,
+			age_at_time_of_onset: patient.onset_age,
+			age_unit: patient.onset_age_unit.clone(),
+			gestation_period: patient.gestation_period,
+			gestation_period_unit: patient.gestation_period_unit.clone(),
+			age_group: patient.age_group.clone(),
+			weight_kg: patient.weight_kg,
+			height_cm: patient.height_cm,
+			sex: None,
+			race_code: None,
+			ethnicity_code: None,
+			last_menstrual_period_date: patient.last_menstrual_date,
+			medical_history_text: patient.medical_history_text.clone(),
+			concomitant_therapy: None,
+		},
+	)
+	.await?;
+
+	for (idx, (type_code, value)) in patient.identifiers.iter().enumerate() {
+		PatientIdentifierBmc::create(
+			ctx,
+			mm,
+			PatientIdentifierForCreate {
+				patient_id,
+				sequence_number: idx as i32 + 1,
+				identifier_type_code: type_code.clone(),
+				identifier_value: value.clone(),
+			},
+		)
+		.await?;
+	}
+
+	for (idx, episode) in patient.medical_history.iter().enumerate() {
+		let id = MedicalHistoryEpisodeBmc::create(
+			ctx,
+			mm,
+			MedicalHistoryEpisodeForCreate {
+				patient_id,
+				sequence_number: idx as i32 + 1,
+				meddra_code: episode.meddra_code.clone(),
+			},
+		)
+		.await?;
+		MedicalHistoryEpisodeBmc::update(
+			ctx,
+			mm,
+			id,
+			MedicalHistoryEpisodeForUpdate {
+				meddra_version: episode.meddra_version.clone(),
+				meddra_code: None,
+				start_date: episode.start_date,
+				continuing: episode.continuing,
+				end_date: episode.end_date,
+				comments: episode.comments.clone(),
+				family_history: None,
+			},
+		)
+		.await?;
+	}
+
+	for (idx, therapy) in patient.past_drugs.iter().enumerate() {
+		PastDrugHistoryBmc::create(
+			ctx,
+			mm,
+			PastDrugHistoryForCreate {
+				patient_id,
+				sequence_number: idx as i32 + 1,
+				drug_name: therapy.drug_name.clone(),
+				mpid: None,
+				mpid_version: None,
+				phpid: None,
+				phpid_version: None,
+				start_date: therapy.start_date,
+				end_date: therapy.end_date,
+				indication_meddra_version: therapy.indication_meddra_version.clone(),
+				indication_meddra_code: therapy.indication_meddra_code.clone(),
+				reaction_meddra_version: therapy.reaction_meddra_version.clone(),
+				reaction_meddra_code: therapy.reaction_meddra_code.clone(),
+				kr_product_code_version: None,
+				kr_product_code: None,
+			},
+		)
+		.await?;
+	}
+
+	if let Some(death) = patient.death.as_ref() {
+		let death_id = PatientDeathInformationBmc::create(
+			ctx,
+			mm,
+			PatientDeathInformationForCreate {
+				patient_id,
+				date_of_death: death.date_of_death,
+				autopsy_performed: death.autopsy_performed,
+			},
+		)
+		.await?;
+		for (idx, (version, code)) in death.reported_causes.iter().enumerate() {
+			let id = ReportedCauseOfDeathBmc::create(
+				ctx,
+				mm,
+				ReportedCauseOfDeathForCreate {
+					death_info_id: death_id,
+					sequence_number: idx as i32 + 1,
+					meddra_code: Some(code.clone()),
+				},
+			)
+			.await?;
+			ReportedCauseOfDeathBmc::update(
+				ctx,
+				mm,
+				id,
+				ReportedCauseOfDeathForUpdate {
+					meddra_version: version.clone(),
+					meddra_code: None,
+				},
+			)
+			.await?;
+		}
+		for (idx, (version, code)) in death.autopsy_causes.iter().enumerate() {
+			let id = AutopsyCauseOfDeathBmc::create(
+				ctx,
+				mm,
+				AutopsyCauseOfDeathForCreate {
+					death_info_id: death_id,
+					sequence_number: idx as i32 + 1,
+					meddra_code: Some(code.clone()),
+				},
+			)
+			.await?;
+			AutopsyCauseOfDeathBmc::update(
+				ctx,
+				mm,
+				id,
+				AutopsyCauseOfDeathForUpdate {
+					meddra_version: version.clone(),
+					meddra_code: None,
+				},
+			)
+			.await?;
+		}
+	}
+
+	Ok(())
+}
+
+/// C.1.2/C.1.4/C.1.5 are full dates: lower precision is padded and a missing
+/// value takes `fallback`, both with a loss note.
+fn c1_date(
+	value: Option<crate::model::partial_date::PartialDate>,
+	fallback: Date,
+	element: &str,
+	path: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> Date {
+	match full_date(value) {
+		Some((date, false)) => date,
+		Some((date, true)) => {
+			loss_note(
+				issues,
+				&format!("BFC.{element}.PADDED"),
+				"Date is less precise than a day; it was padded to the first day of the period.",
+				path,
+				"case-identification",
+			);
+			date
+		}
+		None => {
+			loss_note(
+				issues,
+				&format!("BFC.{element}.DEFAULTED"),
+				&format!("Date was missing; {fallback} was used."),
+				path,
+				"case-identification",
+			);
+			fallback
+		}
+	}
+}
@@ -0,0 +1,36 @@
+// E2B(R2) <-> E2B(R3) conversion (ICH ICSR backwards/forwards compatibility)
+//
+// R2 `ichicsr` messages are parsed onto the R3 case model and cases are
+// rendered back as `ichicsr`. Wherever the conversion cannot carry a value as
+// sent, a non-blocking `ValidationIssue` with a `BFC.*` code is returned
+// alongside the result.
+
+pub mod codes;
+mod export;
+mod import;
+pub mod parse;
+
+pub use export::export_case_r2_xml;
+pub use import::import_r2_xml;
+pub use parse::parse_r2_xml;
+
+use crate::xml::validate::{push_issue, ValidationIssue};
+
+fn loss_note(
+	issues: &mut Vec<ValidationIssue>,
+	code: &str,
+	message: &str,
+	path: impl Into<String>,
+	section: &str,
+) {
+	push_issue(issues, code, message, path, section, false);
+}
+
+fn xml_escape(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
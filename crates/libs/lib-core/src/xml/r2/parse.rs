@@ -0,0 +1,1137 @@
+// E2B(R2) ichicsr parsing, mapped onto R3 values
+
+use crate::model::partial_date::{DatePrecision, PartialDate};
+use crate::model::ucum::normalize_ucum;
+use crate::xml::error::Error;
+use crate::xml::r2::codes::{
+	dose_unit_to_ucum, is_meddra_code, outcome_to_r3, parse_r2_date, r2_bool,
+	term_highlighted_to_r3, time_unit_to_ucum,
+};
+use crate::xml::r2::loss_note;
+use crate::xml::validate::ValidationIssue;
+use crate::xml::Result;
+use libxml::parser::Parser;
+use libxml::tree::Node;
+use libxml::xpath::Context;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+const R2_ROOT: &str = "ichicsr";
+
+/// M.1 header of an ichicsr message.
+#[derive(Debug, Clone, Default)]
+pub struct R2MessageHeader {
+	/// M.1.4 Message Number.
+	pub message_number: Option<String>,
+	/// M.1.5 Message Sender Identifier.
+	pub sender_identifier: Option<String>,
+	/// M.1.6 Message Receiver Identifier.
+	pub receiver_identifier: Option<String>,
+	/// M.1.7 Message Date.
+	pub message_date: Option<String>,
+}
+
+/// Parsed ichicsr message: the header and one entry per `safetyreport`.
+#[derive(Debug, Clone, Default)]
+pub struct R2Message {
+	pub header: R2MessageHeader,
+	pub reports: Vec<R2SafetyReport>,
+}
+
+/// One `safetyreport` with its values already converted to the R3 lists.
+#[derive(Debug, Clone, Default)]
+pub struct R2SafetyReport {
+	/// Position of the report inside the message.
+	pub index: usize,
+	/// A.1.0.1 Sender's (case) Safety Report Unique Identifier (C.1.1).
+	pub safety_report_id: Option<String>,
+	/// A.1.3 Date of This Transmission (C.1.2).
+	pub transmission_date: Option<PartialDate>,
+	/// A.1.4 Type of Report (C.1.3).
+	pub report_type: Option<String>,
+	/// A.1.6 Date Report Was First Received from Source (C.1.4).
+	pub receive_date: Option<PartialDate>,
+	/// A.1.7 Date of Receipt of the Most Recent Information (C.1.5).
+	pub receipt_date: Option<PartialDate>,
+	/// A.1.8.2 List of Documents Held by Sender (C.1.6.1.r.1).
+	pub documents: Vec<String>,
+	/// A.1.9 Fulfils Local Criteria for an Expedited Report (C.1.7).
+	pub fulfil_expedited_criteria: Option<bool>,
+	/// A.1.10.1 / A.1.10.2 Authority's or company's case number (C.1.8.1).
+	pub worldwide_unique_id: Option<String>,
+	/// A.1.11.1 / A.1.11.2 Other case identifiers as (source, number) (C.1.9.1.r).
+	pub other_identifiers: Vec<(String, String)>,
+	/// A.1.12 Identification Number of the Report Linked (C.1.10.r).
+	pub linked_reports: Vec<String>,
+	/// A.1.13 Report Nullification (C.1.11.1).
+	pub nullification_code: Option<String>,
+	/// A.1.13.1 Reason for Nullification (C.1.11.2).
+	pub nullification_reason: Option<String>,
+	/// A.2.1 Primary sources (C.2.r).
+	pub primary_sources: Vec<R2PrimarySource>,
+	/// A.2.2 Literature references (C.4.r).
+	pub literature: Vec<String>,
+	/// A.2.3 Study identification (C.5).
+	pub study: Option<R2Study>,
+	/// A.3.1 Sender (C.3).
+	pub sender: Option<R2Sender>,
+	/// A.3.2 Receiver.
+	pub receiver: Option<R2Receiver>,
+	/// B.1 Patient (D).
+	pub patient: Option<R2Patient>,
+	/// B.2.i Reactions (E.i).
+	pub reactions: Vec<R2Reaction>,
+	/// B.3.1 Tests and procedures (F.r).
+	pub tests: Vec<R2Test>,
+	/// B.4.k Drugs (G.k).
+	pub drugs: Vec<R2Drug>,
+	/// B.5 Narrative (H).
+	pub narrative: Option<R2Narrative>,
+	/// Non-blocking notes for R2 content the R3 model cannot hold as sent.
+	pub issues: Vec<ValidationIssue>,
+}
+
+/// A.2.1 Primary source(s) of information.
+#[derive(Debug, Clone, Default)]
+pub struct R2PrimarySource {
+	pub title: Option<String>,
+	pub given_name: Option<String>,
+	pub middle_name: Option<String>,
+	pub family_name: Option<String>,
+	pub organization: Option<String>,
+	pub department: Option<String>,
+	pub street: Option<String>,
+	pub city: Option<String>,
+	pub state: Option<String>,
+	pub postcode: Option<String>,
+	/// A.2.1.3 Country, or A.1.1 when the reporter has none.
+	pub country_code: Option<String>,
+	/// A.2.1.4 Qualification.
+	pub qualification: Option<String>,
+}
+
+/// A.2.3 Study identification.
+#[derive(Debug, Clone, Default)]
+pub struct R2Study {
+	pub study_name: Option<String>,
+	pub sponsor_study_number: Option<String>,
+	/// A.2.3.3 Study type in which the reaction(s) were observed.
+	pub study_type: Option<String>,
+}
+
+/// A.3.1 Information on the sender of the case report.
+#[derive(Debug, Clone, Default)]
+pub struct R2Sender {
+	pub sender_type: Option<String>,
+	pub organization: Option<String>,
+	pub department: Option<String>,
+	pub title: Option<String>,
+	pub given_name: Option<String>,
+	pub middle_name: Option<String>,
+	pub family_name: Option<String>,
+	pub street: Option<String>,
+	pub city: Option<String>,
+	pub state: Option<String>,
+	pub postcode: Option<String>,
+	pub country_code: Option<String>,
+	pub telephone: Option<String>,
+	pub fax: Option<String>,
+	pub email: Option<String>,
+}
+
+/// A.3.2 Information on the receiver of the case report.
+#[derive(Debug, Clone, Default)]
+pub struct R2Receiver {
+	pub receiver_type: Option<String>,
+	pub organization: Option<String>,
+	pub department: Option<String>,
+	pub street: Option<String>,
+	pub city: Option<String>,
+	pub state: Option<String>,
+	pub postcode: Option<String>,
+	pub country_code: Option<String>,
+	pub telephone: Option<String>,
+	pub fax: Option<String>,
+	pub email: Option<String>,
+}
+
+/// B.1 Patient characteristics.
+#[derive(Debug, Clone, Default)]
+pub struct R2Patient {
+	/// B.1.1 Patient (name or initials).
+	pub initials: Option<String>,
+	/// B.1.1.1a-d Record numbers as (D.1.1.x type code, value).
+	pub identifiers: Vec<(String, String)>,
+	pub birth_date: Option<PartialDate>,
+	/// B.1.2.2 Age at time of onset of reaction.
+	pub onset_age: Option<Decimal>,
+	/// B.1.2.2b Age unit (UCUM).
+	pub onset_age_unit: Option<String>,
+	pub gestation_period: Option<Decimal>,
+	pub gestation_period_unit: Option<String>,
+	pub age_group: Option<String>,
+	pub weight_kg: Option<Decimal>,
+	pub height_cm: Option<Decimal>,
+	pub sex: Option<String>,
+	pub last_menstrual_date: Option<PartialDate>,
+	/// B.1.7.2 Text for relevant medical history.
+	pub medical_history_text: Option<String>,
+	pub medical_history: Vec<R2MedicalHistory>,
+	pub past_drugs: Vec<R2PastDrug>,
+	pub death: Option<R2Death>,
+}
+
+/// B.1.7.1 Structured medical history episode.
+#[derive(Debug, Clone, Default)]
+pub struct R2MedicalHistory {
+	pub meddra_version: Option<String>,
+	pub meddra_code: Option<String>,
+	pub start_date: Option<PartialDate>,
+	pub continuing: Option<bool>,
+	pub end_date: Option<PartialDate>,
+	pub comments: Option<String>,
+}
+
+/// B.1.8 Relevant past drug history.
+#[derive(Debug, Clone, Default)]
+pub struct R2PastDrug {
+	pub drug_name: Option<String>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
+	pub indication_meddra_version: Option<String>,
+	pub indication_meddra_code: Option<String>,
+	pub reaction_meddra_version: Option<String>,
+	pub reaction_meddra_code: Option<String>,
+}
+
+/// B.1.9 In case of death.
+#[derive(Debug, Clone, Default)]
+pub struct R2Death {
+	pub date_of_death: Option<PartialDate>,
+	pub autopsy_performed: Option<bool>,
+	/// B.1.9.2 Reported cause(s) of death as (MedDRA version, code).
+	pub reported_causes: Vec<(Option<String>, String)>,
+	/// B.1.9.4 Autopsy-determined cause(s) of death as (MedDRA version, code).
+	pub autopsy_causes: Vec<(Option<String>, String)>,
+}
+
+/// B.2.i Reaction(s)/event(s).
+#[derive(Debug, Clone, Default)]
+pub struct R2Reaction {
+	/// B.2.i.0 Reaction/event as reported by the primary source.
+	pub primary_source_reaction: Option<String>,
+	pub meddra_version: Option<String>,
+	/// B.2.i.1 MedDRA LLT code (B.2.i.2 PT when no LLT is given).
+	pub meddra_code: Option<String>,
+	pub term_highlighted: Option<bool>,
+	/// A.1.5.1 Serious, copied from the report level.
+	pub serious: Option<bool>,
+	pub criteria_death: bool,
+	pub criteria_life_threatening: bool,
+	pub criteria_hospitalization: bool,
+	pub criteria_disabling: bool,
+	pub criteria_congenital_anomaly: bool,
+	pub criteria_other_medically_important: bool,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
+	pub duration_value: Option<Decimal>,
+	pub duration_unit: Option<String>,
+	/// B.2.i.8 Outcome (R3 list).
+	pub outcome: Option<String>,
+	/// A.1.14 Medically confirmed, copied from the report level.
+	pub medical_confirmation: Option<bool>,
+	/// A.1.2 Country of occurrence, copied from the report level.
+	pub country_code: Option<String>,
+}
+
+/// B.3.1 Structured test results.
+#[derive(Debug, Clone, Default)]
+pub struct R2Test {
+	pub test_date: Option<PartialDate>,
+	pub test_name: Option<String>,
+	/// B.3.1d Test result when numeric.
+	pub result_value: Option<String>,
+	pub result_unit: Option<String>,
+	/// B.3.1d Test result when not numeric, or B.3.2 free text.
+	pub result_unstructured: Option<String>,
+	pub normal_low: Option<String>,
+	pub normal_high: Option<String>,
+	pub more_info_available: Option<bool>,
+}
+
+/// B.4.k Drug information. An R2 drug carries a single dosage regimen.
+#[derive(Debug, Clone, Default)]
+pub struct R2Drug {
+	pub characterization: Option<String>,
+	pub medicinal_product: Option<String>,
+	pub substances: Vec<String>,
+	pub obtain_drug_country: Option<String>,
+	pub batch_lot_number: Option<String>,
+	/// B.4.k.4.2 Country of authorisation (G.k.3.2).
+	pub authorization_country: Option<String>,
+	/// B.4.k.4.3 Name of holder/applicant (G.k.3.3).
+	pub authorization_holder: Option<String>,
+	pub dosage: R2Dosage,
+	pub indication: Option<R2Indication>,
+	/// B.4.k.13.1 Time interval between beginning of drug administration and
+	/// start of reaction (G.k.9.i.3.1), as (value, UCUM unit).
+	pub start_period: Option<(Decimal, String)>,
+	/// B.4.k.16 Action taken with drug (stored with the R2 list).
+	pub action_taken: Option<String>,
+	/// B.4.k.17.1 Did reaction recur on readministration (G.k.9.i.4).
+	pub rechallenge: Option<String>,
+	pub relatedness: Vec<R2Relatedness>,
+}
+
+/// B.4.k.5-15 Dosage regimen of an R2 drug.
+#[derive(Debug, Clone, Default)]
+pub struct R2Dosage {
+	pub dose_value: Option<Decimal>,
+	pub dose_unit: Option<String>,
+	pub number_of_units: Option<i32>,
+	pub frequency_value: Option<Decimal>,
+	pub frequency_unit: Option<String>,
+	pub dosage_text: Option<String>,
+	pub dose_form: Option<String>,
+	pub route: Option<String>,
+	pub parent_route: Option<String>,
+	pub start_date: Option<PartialDate>,
+	pub end_date: Option<PartialDate>,
+	pub duration_value: Option<Decimal>,
+	pub duration_unit: Option<String>,
+}
+
+impl R2Dosage {
+	pub fn is_empty(&self) -> bool {
+		self.dose_value.is_none()
+			&& self.dose_unit.is_none()
+			&& self.number_of_units.is_none()
+			&& self.frequency_value.is_none()
+			&& self.frequency_unit.is_none()
+			&& self.dosage_text.is_none()
+			&& self.dose_form.is_none()
+			&& self.route.is_none()
+			&& self.parent_route.is_none()
+			&& self.start_date.is_none()
+			&& self.end_date.is_none()
+			&& self.duration_value.is_none()
+			&& self.duration_unit.is_none()
+	}
+}
+
+/// B.4.k.11 Indication for use in the case.
+#[derive(Debug, Clone, Default)]
+pub struct R2Indication {
+	pub meddra_version: Option<String>,
+	pub meddra_code: Option<String>,
+	/// Indication sent as text instead of a MedDRA code.
+	pub text: Option<String>,
+}
+
+/// B.4.k.18 Relatedness of drug to reaction(s)/event(s).
+#[derive(Debug, Clone, Default)]
+pub struct R2Relatedness {
+	/// B.4.k.18.1b Reaction assessed, as MedDRA code or term.
+	pub reaction: String,
+	pub source: Option<String>,
+	pub method: Option<String>,
+	pub result: Option<String>,
+}
+
+/// B.5 Narrative case summary and further information.
+#[derive(Debug, Clone, Default)]
+pub struct R2Narrative {
+	pub case_narrative: Option<String>,
+	pub reporter_comments: Option<String>,
+	pub sender_comments: Option<String>,
+	pub diagnosis_meddra_version: Option<String>,
+	pub diagnosis_meddra_code: Option<String>,
+}
+
+/// Parses an E2B(R2) `ichicsr` message.
+///
+/// Values are converted to the R3 code lists following the ICH BFC; R2
+/// content with no R3 home, or that R3 holds differently, is reported as a
+/// non-blocking issue on the report it belongs to.
+pub fn parse_r2_xml(xml: &[u8]) -> Result<R2Message> {
+	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
+		message: format!("XML not valid UTF-8: {err}"),
+		line: None,
+		column: None,
+	})?;
+	let doc = Parser::default().parse_string(xml_str).map_err(|err| {
+		Error::InvalidXml {
+			message: format!("XML parse error: {err}"),
+			line: None,
+			column: None,
+		}
+	})?;
+	let root = doc.get_root_element().ok_or(Error::MissingRootElement)?;
+	if root.get_name() != R2_ROOT {
+		return Err(Error::UnsupportedRoot {
+			found: root.get_name(),
+		});
+	}
+	let mut xpath = Context::new(&doc).map_err(|_| Error::InvalidXml {
+		message: "Failed to initialize XPath context".to_string(),
+		line: None,
+		column: None,
+	})?;
+
+	let header = R2MessageHeader {
+		message_number: text(&mut xpath, &root, "ichicsrmessageheader/messagenumb"),
+		sender_identifier: text(
+			&mut xpath,
+			&root,
+			"ichicsrmessageheader/messagesenderidentifier",
+		),
+		receiver_identifier: text(
+			&mut xpath,
+			&root,
+			"ichicsrmessageheader/messagereceiveridentifier",
+		),
+		message_date: text(&mut xpath, &root, "ichicsrmessageheader/messagedate"),
+	};
+
+	let nodes = xpath
+		.findnodes("safetyreport", Some(&root))
+		.unwrap_or_default();
+	let reports = nodes
+		.iter()
+		.enumerate()
+		.map(|(index, node)| parse_safety_report(&mut xpath, node, index))
+		.collect();
+
+	Ok(R2Message { header, reports })
+}
+
+fn parse_safety_report(
+	xpath: &mut Context,
+	node: &Node,
+	index: usize,
+) -> R2SafetyReport {
+	let base = format!("safetyreport[{}]", index + 1);
+	let mut issues = Vec::new();
+	let x = xpath;
+
+	let mut report = R2SafetyReport {
+		index,
+		safety_report_id: text(x, node, "safetyreportid"),
+		transmission_date: date(x, node, "transmissiondate"),
+		report_type: text(x, node, "reporttype"),
+		receive_date: date(x, node, "receivedate"),
+		receipt_date: date(x, node, "receiptdate"),
+		fulfil_expedited_criteria: flag(x, node, "fulfillexpeditecriteria"),
+		worldwide_unique_id: text(x, node, "authoritynumb")
+			.or_else(|| text(x, node, "companynumb")),
+		nullification_code: flag(x, node, "casenullification")
+			.filter(|n| *n)
+			.map(|_| "1".to_string()),
+		nullification_reason: text(x, node, "nullificationreason"),
+		..Default::default()
+	};
+
+	if let Some(version) = text(x, node, "safetyreportversion") {
+		loss_note(
+			&mut issues,
+			"BFC.A.1.0.2.DROPPED",
+			&format!(
+				"Safety report version '{version}' has no R3 element; the case version is assigned on import."
+			),
+			format!("{base}/safetyreportversion"),
+			"case-identification",
+		);
+	}
+	if text(x, node, "authoritynumb").is_some()
+		&& text(x, node, "companynumb").is_some()
+	{
+		loss_note(
+			&mut issues,
+			"BFC.A.1.10.2.DROPPED",
+			"Both authority and company numbers were sent; C.1.8.1 keeps the authority number.",
+			format!("{base}/companynumb"),
+			"case-identification",
+		);
+	}
+	if let Some(documents) = text(x, node, "documentlist") {
+		report.documents.push(documents);
+	}
+	for dup in children(x, node, "reportduplicate") {
+		if let (Some(source), Some(number)) = (
+			text(x, &dup, "duplicatesource"),
+			text(x, &dup, "duplicatenumb"),
+		) {
+			report.other_identifiers.push((source, number));
+		}
+	}
+	for linked in children(x, node, "linkedreport") {
+		if let Some(number) = text(x, &linked, "linkreportnumb") {
+			report.linked_reports.push(number);
+		}
+	}
+
+	let report_country = text(x, node, "primarysourcecountry");
+	for (idx, source) in children(x, node, "primarysource").iter().enumerate() {
+		let path = format!("{base}/primarysource[{}]", idx + 1);
+		report.primary_sources.push(R2PrimarySource {
+			title: text(x, source, "reportertitle"),
+			given_name: text(x, source, "reportergivename"),
+			middle_name: text(x, source, "reportermiddlename"),
+			family_name: text(x, source, "reporterfamilyname"),
+			organization: text(x, source, "reporterorganization"),
+			department: text(x, source, "reporterdepartment"),
+			street: text(x, source, "reporterstreet"),
+			city: text(x, source, "reportercity"),
+			state: text(x, source, "reporterstate"),
+			postcode: text(x, source, "reporterpostcode"),
+			country_code: text(x, source, "reportercountry")
+				.or_else(|| report_country.clone()),
+			qualification: text(x, source, "qualification"),
+		});
+		if let Some(reference) = text(x, source, "literaturereference") {
+			report.literature.push(reference);
+		}
+		let study = R2Study {
+			study_name: text(x, source, "studyname"),
+			sponsor_study_number: text(x, source, "sponsorstudynumb"),
+			study_type: text(x, source, "observestudytype"),
+		};
+		if study.study_name.is_some()
+			|| study.sponsor_study_number.is_some()
+			|| study.study_type.is_some()
+		{
+			if report.study.is_some() {
+				loss_note(
+					&mut issues,
+					"BFC.A.2.3.DROPPED",
+					"Only the first study identification is kept (C.5 is not repeatable).",
+					format!("{path}/studyname"),
+					"case-identification",
+				);
+			} else {
+				report.study = Some(study);
+			}
+		}
+	}
+
+	report.sender = children(x, node, "sender").first().map(|s| R2Sender {
+		sender_type: text(x, s, "sendertype"),
+		organization: text(x, s, "senderorganization"),
+		department: text(x, s, "senderdepartment"),
+		title: text(x, s, "sendertitle"),
+		given_name: text(x, s, "sendergivename"),
+		middle_name: text(x, s, "sendermiddlename"),
+		family_name: text(x, s, "senderfamilyname"),
+		street: text(x, s, "senderstreetaddress"),
+		city: text(x, s, "sendercity"),
+		state: text(x, s, "senderstate"),
+		postcode: text(x, s, "senderpostcode"),
+		country_code: text(x, s, "sendercountrycode"),
+		telephone: phone(x, s, "sender"),
+		fax: fax(x, s, "sender"),
+		email: text(x, s, "senderemailaddress"),
+	});
+	report.receiver = children(x, node, "receiver").first().map(|r| R2Receiver {
+		receiver_type: text(x, r, "receivertype"),
+		organization: text(x, r, "receiverorganization"),
+		department: text(x, r, "receiverdepartment"),
+		street: text(x, r, "receiverstreetaddress"),
+		city: text(x, r, "receivercity"),
+		state: text(x, r, "receiverstate"),
+		postcode: text(x, r, "receiverpostcode"),
+		country_code: text(x, r, "receivercountrycode"),
+		telephone: phone(x, r, "receiver"),
+		fax: fax(x, r, "receiver"),
+		email: text(x, r, "receiveremailaddress"),
+	});
+
+	if let Some(patient) = children(x, node, "patient").first() {
+		let path = format!("{base}/patient");
+		report.patient = Some(parse_patient(x, patient, &path, &mut issues));
+
+		// A.1.5 and A.1.14 are report-level in R2 and per reaction in R3.
+		let serious = flag(x, node, "serious");
+		let criteria = [
+			flag(x, node, "seriousnessdeath").unwrap_or(false),
+			flag(x, node, "seriousnesslifethreatening").unwrap_or(false),
+			flag(x, node, "seriousnesshospitalization").unwrap_or(false),
+			flag(x, node, "seriousnessdisabling").unwrap_or(false),
+			flag(x, node, "seriousnesscongenitalanomali").unwrap_or(false),
+			flag(x, node, "seriousnessother").unwrap_or(false),
+		];
+		let medically_confirmed = flag(x, node, "medicallyconfirm");
+		let occur_country = text(x, node, "occurcountry");
+		for (idx, reaction) in children(x, patient, "reaction").iter().enumerate() {
+			let mut reaction = parse_reaction(
+				x,
+				reaction,
+				&format!("{path}/reaction[{}]", idx + 1),
+				&mut issues,
+			);
+			reaction.serious = serious;
+			reaction.criteria_death = criteria[0];
+			reaction.criteria_life_threatening = criteria[1];
+			reaction.criteria_hospitalization = criteria[2];
+			reaction.criteria_disabling = criteria[3];
+			reaction.criteria_congenital_anomaly = criteria[4];
+			reaction.criteria_other_medically_important = criteria[5];
+			reaction.medical_confirmation = medically_confirmed;
+			reaction.country_code = occur_country.clone();
+			report.reactions.push(reaction);
+		}
+		if serious == Some(true) && report.reactions.len() > 1 {
+			loss_note(
+				&mut issues,
+				"BFC.A.1.5.2.COPIED",
+				"Report-level seriousness criteria were applied to every reaction; review them per reaction.",
+				format!("{base}/serious"),
+				"reactions",
+			);
+		}
+
+		for (idx, test) in children(x, patient, "test").iter().enumerate() {
+			report.tests.push(parse_test(
+				x,
+				test,
+				&format!("{path}/test[{}]", idx + 1),
+				&mut issues,
+			));
+		}
+		if let Some(results) = text(x, patient, "resultstestsprocedures") {
+			report.tests.push(R2Test {
+				test_name: Some("Results of tests and procedures".to_string()),
+				result_unstructured: Some(results),
+				..Default::default()
+			});
+		}
+
+		for (idx, drug) in children(x, patient, "drug").iter().enumerate() {
+			report.drugs.push(parse_drug(
+				x,
+				drug,
+				&format!("{path}/drug[{}]", idx + 1),
+				&mut issues,
+			));
+		}
+
+		report.narrative = children(x, patient, "summary").first().map(|s| {
+			let diagnosis = text(x, s, "senderdiagnosis");
+			let diagnosis_code = diagnosis.clone().filter(|d| is_meddra_code(d));
+			if diagnosis.is_some() && diagnosis_code.is_none() {
+				loss_note(
+					&mut issues,
+					"BFC.B.5.3.UNMAPPED",
+					"Sender's diagnosis is not a MedDRA code and was dropped.",
+					format!("{path}/summary/senderdiagnosis"),
+					"narrative",
+				);
+			}
+			R2Narrative {
+				case_narrative: text(x, s, "narrativeincludeclinical"),
+				reporter_comments: text(x, s, "reportercomment"),
+				sender_comments: text(x, s, "sendercomment"),
+				diagnosis_meddra_version: text(x, s, "senderdiagnosismeddraversion"),
+				diagnosis_meddra_code: diagnosis_code,
+			}
+		});
+	}
+
+	report.issues = issues;
+	report
+}
+
+fn parse_patient(
+	x: &mut Context,
+	node: &Node,
+	path: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> R2Patient {
+	let mut patient = R2Patient {
+		initials: text(x, node, "patientinitial"),
+		birth_date: date(x, node, "patientbirthdate"),
+		onset_age: decimal(x, node, "patientonsetage"),
+		onset_age_unit: time_unit(
+			x,
+			node,
+			"patientonsetageunit",
+			path,
+			"patient",
+			issues,
+		),
+		gestation_period: decimal(x, node, "gestationperiod"),
+		gestation_period_unit: time_unit(
+			x,
+			node,
+			"gestationperiodunit",
+			path,
+			"patient",
+			issues,
+		),
+		age_group: text(x, node, "patientagegroup"),
+		weight_kg: decimal(x, node, "patientweight"),
+		height_cm: decimal(x, node, "patientheight"),
+		sex: text(x, node, "patientsex"),
+		last_menstrual_date: date(x, node, "patientlastmenstrualdate"),
+		medical_history_text: text(x, node, "patientmedicalhistorytext"),
+		..Default::default()
+	};
+	for (code, element) in [
+		("1", "patientgpmedicalrecordnumb"),
+		("2", "patientspecialistrecordnumb"),
+		("3", "patienthospitalrecordnumb"),
+		("4", "patientinvestigationnumb"),
+	] {
+		if let Some(value) = text(x, node, element) {
+			patient.identifiers.push((code.to_string(), value));
+		}
+	}
+
+	for (idx, episode) in children(x, node, "medicalhistoryepisode")
+		.iter()
+		.enumerate()
+	{
+		let episode_path = format!("{path}/medicalhistoryepisode[{}]", idx + 1);
+		let (meddra_code, unmapped) = meddra(x, episode, "patientepisodename");
+		let mut comments = text(x, episode, "patientmedicalcomment");
+		if let Some(term) = unmapped {
+			loss_note(
+				issues,
+				"BFC.B.1.7.1a.UNMAPPED",
+				"Medical history term is not a MedDRA code; it was kept in the comments.",
+				format!("{episode_path}/patientepisodename"),
+				"patient",
+			);
+			comments = Some(match comments {
+				Some(comment) => format!("{term}: {comment}"),
+				None => term,
+			});
+		}
+		patient.medical_history.push(R2MedicalHistory {
+			meddra_version: text(x, episode, "patientepisodenamemeddraversion"),
+			meddra_code,
+			start_date: date(x, episode, "patientmedicalstartdate"),
+			continuing: flag(x, episode, "patientmedicalcontinue"),
+			end_date: date(x, episode, "patientmedicalenddate"),
+			comments,
+		});
+	}
+
+	for (idx, therapy) in children(x, node, "patientpastdrugtherapy")
+		.iter()
+		.enumerate()
+	{
+		let therapy_path = format!("{path}/patientpastdrugtherapy[{}]", idx + 1);
+		let (indication, unmapped_indication) =
+			meddra(x, therapy, "patientdrugindication");
+		let (reaction, unmapped_reaction) =
+			meddra(x, therapy, "patientdrugreaction");
+		for (unmapped, element) in [
+			(unmapped_indication, "patientdrugindication"),
+			(unmapped_reaction, "patientdrugreaction"),
+		] {
+			if unmapped.is_some() {
+				loss_note(
+					issues,
+					"BFC.B.1.8.UNMAPPED",
+					"Past drug term is not a MedDRA code and was dropped.",
+					format!("{therapy_path}/{element}"),
+					"patient",
+				);
+			}
+		}
+		patient.past_drugs.push(R2PastDrug {
+			drug_name: text(x, therapy, "patientdrugname"),
+			start_date: date(x, therapy, "patientdrugstartdate"),
+			end_date: date(x, therapy, "patientdrugenddate"),
+			indication_meddra_version: text(
+				x,
+				therapy,
+				"patientindicationmeddraversion",
+			),
+			indication_meddra_code: indication,
+			reaction_meddra_version: text(
+				x,
+				therapy,
+				"patientdrgreactionmeddraversion",
+			),
+			reaction_meddra_code: reaction,
+		});
+	}
+
+	if let Some(death) = children(x, node, "patientdeath").first() {
+		let death_path = format!("{path}/patientdeath");
+		let mut info = R2Death {
+			date_of_death: date(x, death, "patientdeathdate"),
+			autopsy_performed: flag(x, death, "patientautopsyyesno"),
+			..Default::default()
+		};
+		for cause in children(x, death, "patientdeathcause") {
+			match meddra(x, &cause, "patientdeathreport") {
+				(Some(code), _) => info.reported_causes.push((
+					text(x, &cause, "patientdeathreportmeddraversion"),
+					code,
+				)),
+				(None, Some(_)) => loss_note(
+					issues,
+					"BFC.B.1.9.2.UNMAPPED",
+					"Reported cause of death is not a MedDRA code and was dropped.",
+					format!("{death_path}/patientdeathcause"),
+					"patient",
+				),
+				(None, None) => {}
+			}
+		}
+		for cause in children(x, death, "patientautopsy") {
+			match meddra(x, &cause, "patientdetermineautopsy") {
+				(Some(code), _) => info.autopsy_causes.push((
+					text(x, &cause, "patientdetermautopsmeddraversion"),
+					code,
+				)),
+				(None, Some(_)) => loss_note(
+					issues,
+					"BFC.B.1.9.4.UNMAPPED",
+					"Autopsy-determined cause of death is not a MedDRA code and was dropped.",
+					format!("{death_path}/patientautopsy"),
+					"patient",
+				),
+				(None, None) => {}
+			}
+		}
+		patient.death = Some(info);
+	}
+
+	if !children(x, node, "parent").is_empty() {
+		loss_note(
+			issues,
+			"BFC.B.1.10.DROPPED",
+			"Parent information (B.1.10) is not converted; enter it on the case.",
+			format!("{path}/parent"),
+			"patient",
+		);
+	}
+
+	patient
+}
+
+fn parse_reaction(
+	x: &mut Context,
+	node: &Node,
+	path: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> R2Reaction {
+	let (llt, unmapped_llt) = meddra(x, node, "reactionmeddrallt");
+	let (pt, _) = meddra(x, node, "reactionmeddrapt");
+	let meddra_version = if llt.is_some() {
+		text(x, node, "reactionmeddraversionllt")
+	} else {
+		text(x, node, "reactionmeddraversionpt")
+	};
+	if let Some(term) = unmapped_llt.as_ref().filter(|_| pt.is_none()) {
+		loss_note(
+			issues,
+			"BFC.B.2.i.1.UNMAPPED",
+			&format!("Reaction term '{term}' is not a MedDRA code; E.i.2.1 is left empty."),
+			format!("{path}/reactionmeddrallt"),
+			"reactions",
+		);
+	}
+	for element in ["reactionfirsttime", "reactionlasttime"] {
+		if text(x, node, element).is_some() {
+			loss_note(
+				issues,
+				"BFC.B.2.i.7.DROPPED",
+				"Time intervals from drug to reaction are held per drug in R3 (G.k.9.i.3) and were dropped.",
+				format!("{path}/{element}"),
+				"reactions",
+			);
+		}
+	}
+	let outcome = text(x, node, "reactionoutcome");
+	R2Reaction {
+		primary_source_reaction: text(x, node, "primarysourcereaction")
+			.or_else(|| unmapped_llt.clone()),
+		meddra_version,
+		meddra_code: llt.or(pt),
+		term_highlighted: text(x, node, "termhighlighted")
+			.and_then(|v| term_highlighted_to_r3(&v)),
+		start_date: date(x, node, "reactionstartdate"),
+		end_date: date(x, node, "reactionenddate"),
+		duration_value: decimal(x, node, "reactionduration"),
+		duration_unit: time_unit(
+			x,
+			node,
+			"reactiondurationunit",
+			path,
+			"reactions",
+			issues,
+		),
+		outcome: outcome.and_then(|v| outcome_to_r3(&v)).map(str::to_string),
+		..Default::default()
+	}
+}
+
+fn parse_test(
+	x: &mut Context,
+	node: &Node,
+	path: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> R2Test {
+	let result = text(x, node, "testresult");
+	let numeric = result
+		.as_deref()
+		.is_some_and(|v| Decimal::from_str(v).is_ok());
+	if result.is_some() && !numeric && text(x, node, "testunit").is_some() {
+		loss_note(
+			issues,
+			"BFC.B.3.1d.MERGED",
+			"Non-numeric test result was stored as unstructured data with its unit dropped.",
+			format!("{path}/testresult"),
+			"tests",
+		);
+	}
+	R2Test {
+		test_date: date(x, node, "testdate"),
+		test_name: text(x, node, "testname"),
+		result_value: result.clone().filter(|_| numeric),
+		result_unit: text(x, node, "testunit")
+			.filter(|_| numeric)
+			.map(|unit| normalize_ucum(&unit).unwrap_or(unit)),
+		result_unstructured: result.filter(|_| !numeric),
+		normal_low: text(x, node, "lowtestrange"),
+		normal_high: text(x, node, "hightestrange"),
+		more_info_available: flag(x, node, "moreinformation"),
+	}
+}
+
+fn parse_drug(
+	x: &mut Context,
+	node: &Node,
+	path: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> R2Drug {
+	let dose_unit = text(x, node, "drugstructuredosageunit");
+	let dose_unit_ucum = dose_unit.as_deref().and_then(dose_unit_to_ucum);
+	if let Some(unit) = dose_unit.as_ref().filter(|_| dose_unit_ucum.is_none()) {
+		loss_note(
+			issues,
+			"BFC.B.4.k.5.2.UNMAPPED",
+			&format!("Dose unit '{unit}' is not an R2 unit code and was dropped."),
+			format!("{path}/drugstructuredosageunit"),
+			"drugs",
+		);
+	}
+	let dosage = R2Dosage {
+		dose_value: decimal(x, node, "drugstructuredosagenumb"),
+		dose_unit: dose_unit_ucum.map(str::to_string),
+		number_of_units: text(x, node, "drugseparatedosagenumb")
+			.and_then(|v| v.parse().ok()),
+		frequency_value: decimal(x, node, "drugintervaldosageunitnumb"),
+		frequency_unit: text(x, node, "drugintervaldosagedefinition")
+			.and_then(|v| time_unit_to_ucum(&v))
+			.map(str::to_string),
+		dosage_text: text(x, node, "drugdosagetext"),
+		dose_form: text(x, node, "drugdosageform"),
+		route: text(x, node, "drugadministrationroute"),
+		parent_route: text(x, node, "drugparadministration"),
+		start_date: date(x, node, "drugstartdate"),
+		end_date: date(x, node, "drugenddate"),
+		duration_value: decimal(x, node, "drugtreatmentduration"),
+		duration_unit: time_unit(
+			x,
+			node,
+			"drugtreatmentdurationunit",
+			path,
+			"drugs",
+			issues,
+		),
+	};
+
+	let (indication_code, indication_text) = meddra(x, node, "drugindication");
+	let indication =
+		(indication_code.is_some() || indication_text.is_some()).then(|| {
+			R2Indication {
+				meddra_version: text(x, node, "drugindicationmeddraversion"),
+				meddra_code: indication_code,
+				text: indication_text,
+			}
+		});
+
+	for (element, code, message) in [
+		(
+			"drugauthorizationnumb",
+			"BFC.B.4.k.4.1.DROPPED",
+			"Authorisation/application number has no field on the drug and was dropped.",
+		),
+		(
+			"drugcumulativedosagenumb",
+			"BFC.B.4.k.5.6.DROPPED",
+			"Cumulative dose to first reaction has no field on the drug and was dropped.",
+		),
+		(
+			"reactiongestationperiod",
+			"BFC.B.4.k.10.DROPPED",
+			"Gestation period at time of exposure was dropped.",
+		),
+		(
+			"druglastperiod",
+			"BFC.B.4.k.13.2.DROPPED",
+			"Time interval from last dose to reaction was dropped.",
+		),
+		(
+			"drugadditional",
+			"BFC.B.4.k.19.DROPPED",
+			"Additional information on drug has no field on the drug and was dropped.",
+		),
+	] {
+		if text(x, node, element).is_some() {
+			loss_note(issues, code, message, format!("{path}/{element}"), "drugs");
+		}
+	}
+	if !children(x, node, "drugrecurrence").is_empty() {
+		loss_note(
+			issues,
+			"BFC.B.4.k.17.2.DROPPED",
+			"Reactions that recurred on readministration (B.4.k.17.2) were dropped.",
+			format!("{path}/drugrecurrence"),
+			"drugs",
+		);
+	}
+
+	let start_period = decimal(x, node, "drugstartperiod").and_then(|value| {
+		text(x, node, "drugstartperiodunit")
+			.and_then(|v| time_unit_to_ucum(&v))
+			.map(|unit| (value, unit.to_string()))
+	});
+
+	let relatedness = children(x, node, "drugreactionrelatedness")
+		.iter()
+		.filter_map(|r| {
+			Some(R2Relatedness {
+				reaction: text(x, r, "drugreactionasses")?,
+				source: text(x, r, "drugassessmentsource"),
+				method: text(x, r, "drugassessmentmethod"),
+				result: text(x, r, "drugresult"),
+			})
+		})
+		.collect();
+
+	R2Drug {
+		characterization: text(x, node, "drugcharacterization"),
+		medicinal_product: text(x, node, "medicinalproduct"),
+		substances: children(x, node, "activesubstance")
+			.iter()
+			.filter_map(|s| text(x, s, "activesubstancename"))
+			.collect(),
+		obtain_drug_country: text(x, node, "obtaindrugcountry"),
+		batch_lot_number: text(x, node, "drugbatchnumb"),
+		authorization_country: text(x, node, "drugauthorizationcountry"),
+		authorization_holder: text(x, node, "drugauthorizationholder"),
+		dosage,
+		indication,
+		start_period,
+		action_taken: text(x, node, "actiondrug"),
+		rechallenge: text(x, node, "drugrecurreadministration")
+			.filter(|v| matches!(v.as_str(), "1" | "2" | "3")),
+		relatedness,
+	}
+}
+
+fn children(x: &mut Context, node: &Node, name: &str) -> Vec<Node> {
+	x.findnodes(name, Some(node)).unwrap_or_default()
+}
+
+fn text(x: &mut Context, node: &Node, expr: &str) -> Option<String> {
+	x.findvalues(expr, Some(node))
+		.ok()?
+		.into_iter()
+		.map(|v| v.trim().to_string())
+		.find(|v| !v.is_empty())
+}
+
+fn date(x: &mut Context, node: &Node, element: &str) -> Option<PartialDate> {
+	text(x, node, element).and_then(|v| parse_r2_date(&v))
+}
+
+fn decimal(x: &mut Context, node: &Node, element: &str) -> Option<Decimal> {
+	text(x, node, element).and_then(|v| Decimal::from_str(&v).ok())
+}
+
+fn flag(x: &mut Context, node: &Node, element: &str) -> Option<bool> {
+	text(x, node, element).and_then(|v| r2_bool(&v))
+}
+
+/// A MedDRA element as (code, text): R2 allowed the term itself.
+fn meddra(
+	x: &mut Context,
+	node: &Node,
+	element: &str,
+) -> (Option<String>, Option<String>) {
+	match text(x, node, element) {
+		Some(v) if is_meddra_code(&v) => (Some(v), None),
+		Some(v) => (None, Some(v)),
+		None => (None, None),
+	}
+}
+
+/// Age, duration and gestation units are stored as UCUM in three characters.
+fn time_unit(
+	x: &mut Context,
+	node: &Node,
+	element: &str,
+	path: &str,
+	section: &str,
+	issues: &mut Vec<ValidationIssue>,
+) -> Option<String> {
+	let code = text(x, node, element)?;
+	match time_unit_to_ucum(&code).filter(|unit| unit.len() <= 3) {
+		Some(unit) => Some(unit.to_string()),
+		None => {
+			loss_note(
+				issues,
+				"BFC.UNIT.UNMAPPED",
+				&format!("Unit '{code}' has no R3 equivalent for this element and was dropped."),
+				format!("{path}/{element}"),
+				section,
+			);
+			None
+		}
+	}
+}
+
+/// R2 splits phone numbers into number, extension and country code.
+fn phone(x: &mut Context, node: &Node, prefix: &str) -> Option<String> {
+	join_phone(
+		text(x, node, &format!("{prefix}telcountrycode")),
+		text(x, node, &format!("{prefix}tel")),
+		text(x, node, &format!("{prefix}telextension")),
+	)
+}
+
+fn fax(x: &mut Context, node: &Node, prefix: &str) -> Option<String> {
+	join_phone(
+		text(x, node, &format!("{prefix}faxcountrycode")),
+		text(x, node, &format!("{prefix}fax")),
+		text(x, node, &format!("{prefix}faxextension")),
+	)
+}
+
+fn join_phone(
+	country: Option<String>,
+	number: Option<String>,
+	extension: Option<String>,
+) -> Option<String> {
+	let number = number?;
+	let mut value = match country {
+		Some(country) => format!("+{} {number}", country.trim_start_matches('+')),
+		None => number,
+	};
+	if let Some(extension) = extension {
+		value.push_str(&format!(" ext {extension}"));
+	}
+	Some(value)
+}
+
+/// Full dates for C.1 fields; lower precision is padded to its first day.
+pub(crate) fn full_date(date: Option<PartialDate>) -> Option<(time::Date, bool)> {
+	date.map(|d| (d.earliest(), !d.meets(DatePrecision::Day)))
+}
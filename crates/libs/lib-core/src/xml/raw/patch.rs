@@ -3,6 +3,7 @@ use crate::model::drug::{
 	DrugIndication, DrugInformation,
 };
 use crate::model::narrative::NarrativeInformation;
+use crate::model::partial_date::PartialDate;
 use crate::model::reaction::Reaction;
 use crate::model::test_result::TestResult;
 use crate::xml::error::Error;
@@ -37,7 +38,7 @@ pub struct CSafetyReportPatch<'a> {
 pub struct DPatientPatch<'a> {
 	pub patient_name: Option<&'a str>,
 	pub sex: Option<&'a str>,
-	pub birth_date: Option<Date>,
+	pub birth_date: Option<PartialDate>,
 	pub age_value: Option<&'a str>,
 	pub age_unit: Option<&'a str>,
 	pub weight_kg: Option<&'a str>,
@@ -288,7 +289,7 @@ pub fn patch_d_patient(raw_xml: &[u8], patch: &DPatientPatch) -> Result<String>
 			&mut xpath,
 			"//hl7:primaryRole/hl7:player1/hl7:birthTime",
 			"value",
-			&fmt_date(birth_date),
+			&birth_date.to_string(),
 		);
 	}
 
@@ -1,3 +1,4 @@
+use crate::xml::validate::ValidationIssue;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5,12 +6,17 @@ pub struct XmlValidationError {
 	pub message: String,
 	pub line: Option<usize>,
 	pub column: Option<usize>,
+	/// Location of the offending node, for XML business rules.
+	pub xpath: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct XmlValidationReport {
 	pub ok: bool,
 	pub errors: Vec<XmlValidationError>,
+	/// Non-blocking findings; they do not affect `ok`.
+	#[serde(default)]
+	pub warnings: Vec<XmlValidationError>,
 	pub root_element: Option<String>,
 }
 
@@ -27,3 +33,128 @@ pub struct XmlImportResult {
 	pub xml_key: Option<String>,
 	pub parsed_json_id: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlImportReportError {
+	pub message: String,
+	/// Location of the failing message inside the submitted batch.
+	pub xpath: String,
+	pub errors: Vec<XmlValidationError>,
+}
+
+/// Outcome of importing one ICSR message from a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlImportReportResult {
+	pub index: usize,
+	pub message_number: Option<String>,
+	pub safety_report_id: Option<String>,
+	pub case_id: Option<String>,
+	pub case_version: Option<i64>,
+	pub parsed_json_id: Option<String>,
+	pub error: Option<XmlImportReportError>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct XmlBatchImportResult {
+	pub batch_number: Option<String>,
+	pub total: usize,
+	pub imported: usize,
+	pub failed: usize,
+	pub reports: Vec<XmlImportReportResult>,
+	/// MCCI_IN200101UV01 acknowledgment for the sender, when one was built.
+	pub ack_xml: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlBatchExportRequest {
+	pub case_ids: Vec<sqlx::types::Uuid>,
+	/// N.1.2 Batch Number; generated when omitted.
+	pub batch_number: Option<String>,
+	/// N.1.3 Batch Sender Identifier; defaults to the first case's sender.
+	pub batch_sender_identifier: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlBatchExportResult {
+	pub batch_number: String,
+	pub batch_sender_identifier: String,
+	pub batch_receiver_identifier: String,
+	pub validation_profile: String,
+	pub case_ids: Vec<sqlx::types::Uuid>,
+	pub validation: XmlValidationReport,
+	pub xml: String,
+}
+
+/// A nullification report created for a submitted case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlNullificationResult {
+	/// The nullified case.
+	pub case_id: sqlx::types::Uuid,
+	/// The new version carrying C.1.11.1 = 1.
+	pub nullification_case_id: sqlx::types::Uuid,
+	pub version: i32,
+	pub validation: XmlValidationReport,
+	pub xml: String,
+}
+
+/// Outcome of recording one ICSR acknowledgment against a case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlAckMessageResult {
+	pub index: usize,
+	pub message_number: Option<String>,
+	pub ack_code: Option<String>,
+	pub error_text: Option<String>,
+	pub case_id: Option<sqlx::types::Uuid>,
+	pub case_status: Option<String>,
+	pub acknowledgment_id: Option<sqlx::types::Uuid>,
+	pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlAckImportResult {
+	pub ack_batch_number: Option<String>,
+	pub batch_number: Option<String>,
+	pub batch_ack_code: Option<String>,
+	pub total: usize,
+	pub matched: usize,
+	pub unmatched: usize,
+	pub messages: Vec<XmlAckMessageResult>,
+}
+
+/// Input for building the MCCI_IN200101UV01 acknowledgment of a received file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlAckGenerationRequest {
+	pub import_result: XmlBatchImportResult,
+	/// Whole-file validation report; a failed report rejects the batch (AR).
+	pub validation: Option<XmlValidationReport>,
+}
+
+/// Outcome of importing one `safetyreport` of an E2B(R2) message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlR2ImportReportResult {
+	pub index: usize,
+	pub safety_report_id: Option<String>,
+	pub case_id: Option<sqlx::types::Uuid>,
+	pub case_version: Option<i32>,
+	pub error: Option<String>,
+	/// Non-blocking BFC notes for R2 content not carried over as sent.
+	pub issues: Vec<ValidationIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlR2ImportResult {
+	pub message_number: Option<String>,
+	pub total: usize,
+	pub imported: usize,
+	pub failed: usize,
+	pub reports: Vec<XmlR2ImportReportResult>,
+}
+
+/// A case rendered as an E2B(R2) `ichicsr` message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XmlR2ExportResult {
+	pub case_id: sqlx::types::Uuid,
+	pub xml: String,
+	/// Non-blocking BFC notes for case content R2 cannot carry.
+	pub issues: Vec<ValidationIssue>,
+}
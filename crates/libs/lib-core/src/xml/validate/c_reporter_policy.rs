@@ -46,6 +46,7 @@ mod tests {
 			email: None,
 			qualification: None,
 			primary_source_regulatory: None,
+			kr_other_health_professional: None,
 			created_at: OffsetDateTime::now_utc(),
 			updated_at: OffsetDateTime::now_utc(),
 			created_by: Uuid::nil(),
@@ -75,6 +76,7 @@ mod tests {
 			email: None,
 			qualification: None,
 			primary_source_regulatory: None,
+			kr_other_health_professional: None,
 			created_at: OffsetDateTime::now_utc(),
 			updated_at: OffsetDateTime::now_utc(),
 			created_by: Uuid::nil(),
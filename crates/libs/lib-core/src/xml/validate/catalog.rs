@@ -1,4 +1,9 @@
+use super::fda_rule_table::{
+	find_fda_rule_by_code, FdaRuleDefinition, FDA_REJECTION_WARNING_RULES,
+};
+use super::rule_set::{stored_condition_satisfied, ValidationRuleSet};
 use super::ValidationProfile;
+use crate::model::jp_element::is_jp_element_id;
 use serde::Serialize;
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -82,6 +87,20 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 		blocking: false,
 		message: "[H.1] This Element is required.",
 	},
+	ValidationRuleMetadata {
+		code: "ICH.CODE_LIST.INVALID",
+		profile: ValidationProfile::Ich,
+		section: "code-lists",
+		blocking: true,
+		message: "Coded value is not an active code of its E2B code list.",
+	},
+	ValidationRuleMetadata {
+		code: "ICH.UCUM.INVALID",
+		profile: ValidationProfile::Ich,
+		section: "units",
+		blocking: true,
+		message: "Unit is not a valid UCUM expression of the expected kind.",
+	},
 	// FDA profile overlays
 	ValidationRuleMetadata {
 		code: "FDA.C.1.7.1.REQUIRED",
@@ -113,12 +132,51 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 			"FDA requires [E.i.3.2h] when other medically important condition is selected.",
 	},
 	// MFDS profile overlays
+	ValidationRuleMetadata {
+		code: "MFDS.C.2.r.4.KR.1.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "reporter",
+		blocking: true,
+		message:
+			"MFDS requires [C.2.r.4.KR.1] when the reporter is another health professional.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.C.2.r.4.KR.1.FORBIDDEN",
+		profile: ValidationProfile::Mfds,
+		section: "reporter",
+		blocking: true,
+		message:
+			"MFDS does not accept [C.2.r.4.KR.1] unless the reporter is another health professional.",
+	},
 	ValidationRuleMetadata {
 		code: "MFDS.C.3.1.KR.1.REQUIRED",
 		profile: ValidationProfile::Mfds,
 		section: "case-identification",
 		blocking: true,
-		message: "MFDS KR profile does not allow sender type 3.",
+		message: "MFDS requires [C.3.1.KR.1] when sender type is health professional.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.C.5.4.KR.1.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "study",
+		blocking: true,
+		message: "MFDS requires [C.5.4.KR.1] when the study type is other studies.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.D.8.r.1.KR.1a.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "patient",
+		blocking: true,
+		message:
+			"MFDS requires [D.8.r.1.KR.1a] code version when a KR product code is given.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.D.10.8.r.1.KR.1a.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "patient",
+		blocking: true,
+		message:
+			"MFDS requires [D.10.8.r.1.KR.1a] code version when a KR product code is given.",
 	},
 	ValidationRuleMetadata {
 		code: "MFDS.KR.DOMESTIC.PRODUCTCODE.REQUIRED",
@@ -132,8 +190,7 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 		profile: ValidationProfile::Mfds,
 		section: "drugs",
 		blocking: false,
-		message:
-			"MFDS foreign-use products should provide WHO MPID/KR product coding.",
+		message: "MFDS foreign-use products should provide WHO MPID/KR product coding.",
 	},
 	ValidationRuleMetadata {
 		code: "MFDS.KR.DOMESTIC.INGREDIENTCODE.REQUIRED",
@@ -143,6 +200,22 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 		message:
 			"MFDS domestic cases require KR ingredient coding for each active substance.",
 	},
+	ValidationRuleMetadata {
+		code: "MFDS.G.k.2.1.KR.1a.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "drugs",
+		blocking: true,
+		message:
+			"MFDS requires [G.k.2.1.KR.1a] code version when a KR product code is given.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.G.k.2.3.r.1.KR.1a.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "drugs",
+		blocking: true,
+		message:
+			"MFDS requires [G.k.2.3.r.1.KR.1a] code version when a KR ingredient code is given.",
+	},
 	ValidationRuleMetadata {
 		code: "MFDS.G.k.9.i.2.r.2.KR.1.REQUIRED",
 		profile: ValidationProfile::Mfds,
@@ -157,7 +230,23 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 		section: "drugs",
 		blocking: true,
 		message:
-			"MFDS requires KR result of assessment when source of assessment is present.",
+			"MFDS requires [G.k.9.i.2.r.3.KR.1] when the KR method of assessment is WHO-UMC.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.G.k.9.i.2.r.3.KR.2.REQUIRED",
+		profile: ValidationProfile::Mfds,
+		section: "drugs",
+		blocking: true,
+		message:
+			"MFDS requires [G.k.9.i.2.r.3.KR.2] when the KR method of assessment is the Korean algorithm.",
+	},
+	ValidationRuleMetadata {
+		code: "MFDS.G.k.9.i.2.r.3.KR.METHOD.MISMATCH",
+		profile: ValidationProfile::Mfds,
+		section: "drugs",
+		blocking: true,
+		message:
+			"MFDS KR result of assessment does not match the KR method of assessment.",
 	},
 	ValidationRuleMetadata {
 		code: "MFDS.G.k.9.i.2.r.1.REQUIRED",
@@ -167,6 +256,106 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 		message:
 			"MFDS requires source of assessment when KR method/result values are provided.",
 	},
+	// EMA (EudraVigilance) profile overlays
+	ValidationRuleMetadata {
+		code: "EMA.N.1.4.RECEIVER.INVALID",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"EMA requires batch receiver [N.1.4] EVHUMAN, EVCTMPROD or EVTEST.",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.N.2.r.3.RECEIVER.INVALID",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"EMA requires message receiver [N.2.r.3] EVHUMAN, EVCTMPROD or EVTEST.",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.N.2.r.3.RECEIVER.MISMATCH",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"EMA routes clinical trial reports (C.5.4=1) to EVCTMPROD and other reports to EVHUMAN.",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.C.1.3.LITERATURE.INVALID",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"EMA requires [C.1.3] spontaneous (1) or report from study (2) for literature reports.",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.C.4.r.2.RECOMMENDED",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: false,
+		message: "EMA expects a copy of the article [C.4.r.2] for literature references.",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.C.1.8.1.REQUIRED",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message: "EMA requires the worldwide unique case identifier [C.1.8.1].",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.C.1.8.1.FORMAT",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"EMA requires [C.1.8.1] as country code-organisation-case number (e.g. DE-ACME-12345).",
+	},
+	ValidationRuleMetadata {
+		code: "EMA.C.1.11.2.REQUIRED",
+		profile: ValidationProfile::Ema,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"EMA requires the nullification/amendment reason [C.1.11.2] when [C.1.11.1] is given.",
+	},
+	// PMDA (Japan) profile overlays
+	ValidationRuleMetadata {
+		code: "PMDA.J.2.REQUIRED",
+		profile: ValidationProfile::Pmda,
+		section: "case-identification",
+		blocking: true,
+		message: "PMDA requires the reporting category [J.2].",
+	},
+	ValidationRuleMetadata {
+		code: "PMDA.J.4.REQUIRED",
+		profile: ValidationProfile::Pmda,
+		section: "case-identification",
+		blocking: true,
+		message:
+			"PMDA requires the reporting deadline [J.4] when expedited criteria [C.1.7] are met.",
+	},
+	ValidationRuleMetadata {
+		code: "PMDA.J.ELEMENT.UNKNOWN",
+		profile: ValidationProfile::Pmda,
+		section: "case-identification",
+		blocking: true,
+		message: "PMDA regional elements are numbered J.2 to J.20.",
+	},
+	ValidationRuleMetadata {
+		code: "PMDA.H.5.r.JA.REQUIRED",
+		profile: ValidationProfile::Pmda,
+		section: "narrative",
+		blocking: true,
+		message: "PMDA requires a case summary [H.5.r] in Japanese (language ja).",
+	},
+	ValidationRuleMetadata {
+		code: "PMDA.H.5.r.1b.REQUIRED",
+		profile: ValidationProfile::Pmda,
+		section: "narrative",
+		blocking: true,
+		message: "PMDA requires the language [H.5.r.1b] of each case summary.",
+	},
 	// XML-level coded checks
 	ValidationRuleMetadata {
 		code: "FDA.N.1.4.REQUIRED",
@@ -450,6 +639,41 @@ pub const VALIDATION_RULES: &[ValidationRuleMetadata] = &[
 		blocking: true,
 		message: "Unsupported test result xsi:type.",
 	},
+	ValidationRuleMetadata {
+		code: "ICH.N.1.5.PRECISION",
+		profile: ValidationProfile::Ich,
+		section: "xml",
+		blocking: true,
+		message: "N.1.5 does not contain full precision date.",
+	},
+	ValidationRuleMetadata {
+		code: "ICH.N.2.r.4.PRECISION",
+		profile: ValidationProfile::Ich,
+		section: "xml",
+		blocking: true,
+		message: "N.2.r.4 does not contain full precision date.",
+	},
+	ValidationRuleMetadata {
+		code: "ICH.C.1.2.PRECISION",
+		profile: ValidationProfile::Ich,
+		section: "xml",
+		blocking: true,
+		message: "C.1.2 does not contain full precision date.",
+	},
+	ValidationRuleMetadata {
+		code: "ICH.C.1.4.PRECISION",
+		profile: ValidationProfile::Ich,
+		section: "xml",
+		blocking: true,
+		message: "C.1.4 does not contain minimum precision of \"CCYYMMDD\".",
+	},
+	ValidationRuleMetadata {
+		code: "ICH.C.1.5.PRECISION",
+		profile: ValidationProfile::Ich,
+		section: "xml",
+		blocking: true,
+		message: "C.1.5 does not contain minimum precision of \"CCYYMMDD\".",
+	},
 	ValidationRuleMetadata {
 		code: "ICH.D.5.SEX.CONDITIONAL",
 		profile: ValidationProfile::Ich,
@@ -908,10 +1132,55 @@ pub enum RuleCondition {
 	MfdsRelatednessMethodOrResultPresent,
 	MfdsDrugDomesticKr,
 	MfdsDrugForeignNonKr,
-	MfdsSenderTypeDisallowed,
+	MfdsSenderHealthProfessional,
+	MfdsReporterOtherHealthProfessional,
+	MfdsReporterNotOtherHealthProfessional,
+	MfdsStudyTypeOther,
+	MfdsKrCodePresent,
+	MfdsKrMethodWhoUmc,
+	MfdsKrMethodKorean,
+	MfdsKrResultMethodMismatch,
+	EmaReceiverRoutingMismatch,
+	EmaLiteratureReferencePresent,
+	EmaNullificationCodePresent,
+	PmdaExpeditedCriteriaTrue,
+	PmdaJapaneseSummaryMissing,
+	PmdaSummaryTextPresent,
 }
 
 impl RuleCondition {
+	pub const ALL: &'static [RuleCondition] = &[
+		Self::Always,
+		Self::IchCaseHistoryTrueMissingPriorIds,
+		Self::IchMedicalHistoryMissingD72Text,
+		Self::FdaFulfilExpeditedCriteriaTrue,
+		Self::FdaReactionOtherMedicallyImportantTrue,
+		Self::FdaPrimarySourcePresent,
+		Self::FdaPatientPayloadPresent,
+		Self::FdaPreAndaRequired,
+		Self::FdaPreAndaForbidden,
+		Self::FdaGk10aRequired,
+		Self::FdaPremarketReportTypeMustBeTwo,
+		Self::MfdsRelatednessSourcePresent,
+		Self::MfdsRelatednessMethodOrResultPresent,
+		Self::MfdsDrugDomesticKr,
+		Self::MfdsDrugForeignNonKr,
+		Self::MfdsSenderHealthProfessional,
+		Self::MfdsReporterOtherHealthProfessional,
+		Self::MfdsReporterNotOtherHealthProfessional,
+		Self::MfdsStudyTypeOther,
+		Self::MfdsKrCodePresent,
+		Self::MfdsKrMethodWhoUmc,
+		Self::MfdsKrMethodKorean,
+		Self::MfdsKrResultMethodMismatch,
+		Self::EmaReceiverRoutingMismatch,
+		Self::EmaLiteratureReferencePresent,
+		Self::EmaNullificationCodePresent,
+		Self::PmdaExpeditedCriteriaTrue,
+		Self::PmdaJapaneseSummaryMissing,
+		Self::PmdaSummaryTextPresent,
+	];
+
 	pub fn as_str(self) -> &'static str {
 		match self {
 			Self::Always => "always",
@@ -943,7 +1212,133 @@ impl RuleCondition {
 			}
 			Self::MfdsDrugDomesticKr => "mfds_drug_domestic_kr",
 			Self::MfdsDrugForeignNonKr => "mfds_drug_foreign_non_kr",
-			Self::MfdsSenderTypeDisallowed => "mfds_sender_type_disallowed",
+			Self::MfdsSenderHealthProfessional => {
+				"mfds_sender_health_professional"
+			}
+			Self::MfdsReporterOtherHealthProfessional => {
+				"mfds_reporter_other_health_professional"
+			}
+			Self::MfdsReporterNotOtherHealthProfessional => {
+				"mfds_reporter_not_other_health_professional"
+			}
+			Self::MfdsStudyTypeOther => "mfds_study_type_other",
+			Self::MfdsKrCodePresent => "mfds_kr_code_present",
+			Self::MfdsKrMethodWhoUmc => "mfds_kr_method_who_umc",
+			Self::MfdsKrMethodKorean => "mfds_kr_method_korean",
+			Self::MfdsKrResultMethodMismatch => {
+				"mfds_kr_result_method_mismatch"
+			}
+			Self::EmaReceiverRoutingMismatch => "ema_receiver_routing_mismatch",
+			Self::EmaLiteratureReferencePresent => {
+				"ema_literature_reference_present"
+			}
+			Self::EmaNullificationCodePresent => "ema_nullification_code_present",
+			Self::PmdaExpeditedCriteriaTrue => "pmda_expedited_criteria_true",
+			Self::PmdaJapaneseSummaryMissing => "pmda_japanese_summary_missing",
+			Self::PmdaSummaryTextPresent => "pmda_summary_text_present",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		Self::ALL
+			.iter()
+			.copied()
+			.find(|condition| condition.as_str() == value)
+	}
+
+	pub fn is_satisfied(self, facts: RuleFacts) -> bool {
+		match self {
+			Self::Always => true,
+			Self::IchCaseHistoryTrueMissingPriorIds => facts
+				.ich_case_history_true_missing_prior_ids
+				.unwrap_or(false),
+			Self::IchMedicalHistoryMissingD72Text => facts
+				.ich_medical_history_missing_d72_text
+				.unwrap_or(false),
+			Self::FdaFulfilExpeditedCriteriaTrue => {
+				facts.fda_fulfil_expedited_criteria.unwrap_or(false)
+			}
+			Self::FdaReactionOtherMedicallyImportantTrue => {
+				facts.fda_reaction_other_medically_important.unwrap_or(false)
+			}
+			Self::FdaPrimarySourcePresent => {
+				facts.fda_primary_source_present.unwrap_or(false)
+			}
+			Self::FdaPatientPayloadPresent => {
+				facts.fda_patient_payload_present.unwrap_or(false)
+			}
+			Self::FdaPreAndaRequired => {
+				facts.fda_type_of_report_is_two.unwrap_or(false)
+					&& facts
+						.fda_msg_receiver_is_cder_ind_exempt_ba_be
+						.unwrap_or(false)
+					&& !facts.fda_has_pre_anda.unwrap_or(false)
+			}
+			Self::FdaPreAndaForbidden => {
+				facts.fda_has_pre_anda.unwrap_or(false)
+					&& facts.fda_batch_receiver_is_zzfda.unwrap_or(false)
+					&& facts.fda_msg_receiver_is_cder_or_cber.unwrap_or(false)
+			}
+			Self::FdaGk10aRequired => facts.fda_has_pre_anda.unwrap_or(false),
+			Self::FdaPremarketReportTypeMustBeTwo => {
+				facts.fda_batch_receiver_is_zzfda_premarket.unwrap_or(false)
+					&& facts.fda_msg_receiver_is_premarket.unwrap_or(false)
+					&& facts.fda_has_pre_anda.unwrap_or(false)
+					&& facts.fda_study_type_is_1_2_3.unwrap_or(false)
+			}
+			Self::MfdsRelatednessSourcePresent => {
+				facts.mfds_relatedness_source_present.unwrap_or(false)
+			}
+			Self::MfdsRelatednessMethodOrResultPresent => {
+				facts.mfds_relatedness_method_present.unwrap_or(false)
+					|| facts.mfds_relatedness_result_present.unwrap_or(false)
+			}
+			Self::MfdsDrugDomesticKr => {
+				facts.mfds_drug_domestic_kr.unwrap_or(false)
+			}
+			Self::MfdsDrugForeignNonKr => {
+				facts.mfds_drug_foreign_non_kr.unwrap_or(false)
+			}
+			Self::MfdsSenderHealthProfessional => {
+				facts.mfds_sender_health_professional.unwrap_or(false)
+			}
+			Self::MfdsReporterOtherHealthProfessional => facts
+				.mfds_reporter_other_health_professional
+				.unwrap_or(false),
+			Self::MfdsReporterNotOtherHealthProfessional => {
+				facts.mfds_reporter_other_health_professional == Some(false)
+			}
+			Self::MfdsStudyTypeOther => {
+				facts.mfds_study_type_other.unwrap_or(false)
+			}
+			Self::MfdsKrCodePresent => facts.mfds_kr_code_present.unwrap_or(false),
+			Self::MfdsKrMethodWhoUmc => {
+				facts.mfds_kr_method_who_umc.unwrap_or(false)
+			}
+			Self::MfdsKrMethodKorean => {
+				facts.mfds_kr_method_korean.unwrap_or(false)
+			}
+			Self::MfdsKrResultMethodMismatch => {
+				facts.mfds_kr_result_method_mismatch.unwrap_or(false)
+			}
+			Self::EmaReceiverRoutingMismatch => {
+				facts.ema_receiver_routing_mismatch.unwrap_or(false)
+			}
+			Self::EmaLiteratureReferencePresent => {
+				facts.ema_literature_reference_present.unwrap_or(false)
+			}
+			Self::EmaNullificationCodePresent => {
+				facts.ema_nullification_code_present.unwrap_or(false)
+			}
+			Self::PmdaExpeditedCriteriaTrue => {
+				facts.pmda_expedited_criteria_true.unwrap_or(false)
+			}
+			Self::PmdaJapaneseSummaryMissing => {
+				facts.pmda_japanese_summary_missing.unwrap_or(false)
+			}
+			Self::PmdaSummaryTextPresent => {
+				facts.pmda_summary_text_present.unwrap_or(false)
+			}
 		}
 	}
 }
@@ -970,7 +1365,63 @@ pub struct RuleFacts {
 	pub mfds_relatedness_result_present: Option<bool>,
 	pub mfds_drug_domestic_kr: Option<bool>,
 	pub mfds_drug_foreign_non_kr: Option<bool>,
-	pub mfds_sender_type_disallowed: Option<bool>,
+	pub mfds_sender_health_professional: Option<bool>,
+	pub mfds_reporter_other_health_professional: Option<bool>,
+	pub mfds_study_type_other: Option<bool>,
+	pub mfds_kr_code_present: Option<bool>,
+	pub mfds_kr_method_who_umc: Option<bool>,
+	pub mfds_kr_method_korean: Option<bool>,
+	pub mfds_kr_result_method_mismatch: Option<bool>,
+	pub ema_receiver_routing_mismatch: Option<bool>,
+	pub ema_literature_reference_present: Option<bool>,
+	pub ema_nullification_code_present: Option<bool>,
+	pub pmda_expedited_criteria_true: Option<bool>,
+	pub pmda_japanese_summary_missing: Option<bool>,
+	pub pmda_summary_text_present: Option<bool>,
+}
+
+impl RuleFacts {
+	/// Value of a fact by field name, for stored condition expressions;
+	/// `None` when no fact has that name.
+	pub fn fact(&self, name: &str) -> Option<Option<bool>> {
+		let value = match name {
+			"ich_case_history_true_missing_prior_ids" => self.ich_case_history_true_missing_prior_ids,
+			"ich_medical_history_missing_d72_text" => self.ich_medical_history_missing_d72_text,
+			"fda_fulfil_expedited_criteria" => self.fda_fulfil_expedited_criteria,
+			"fda_reaction_other_medically_important" => self.fda_reaction_other_medically_important,
+			"fda_combination_product_true" => self.fda_combination_product_true,
+			"fda_primary_source_present" => self.fda_primary_source_present,
+			"fda_patient_payload_present" => self.fda_patient_payload_present,
+			"fda_type_of_report_is_two" => self.fda_type_of_report_is_two,
+			"fda_msg_receiver_is_cder_ind_exempt_ba_be" => self.fda_msg_receiver_is_cder_ind_exempt_ba_be,
+			"fda_has_pre_anda" => self.fda_has_pre_anda,
+			"fda_batch_receiver_is_zzfda" => self.fda_batch_receiver_is_zzfda,
+			"fda_msg_receiver_is_cder_or_cber" => self.fda_msg_receiver_is_cder_or_cber,
+			"fda_batch_receiver_is_zzfda_premarket" => self.fda_batch_receiver_is_zzfda_premarket,
+			"fda_msg_receiver_is_premarket" => self.fda_msg_receiver_is_premarket,
+			"fda_study_type_is_1_2_3" => self.fda_study_type_is_1_2_3,
+			"mfds_relatedness_source_present" => self.mfds_relatedness_source_present,
+			"mfds_relatedness_method_present" => self.mfds_relatedness_method_present,
+			"mfds_relatedness_result_present" => self.mfds_relatedness_result_present,
+			"mfds_drug_domestic_kr" => self.mfds_drug_domestic_kr,
+			"mfds_drug_foreign_non_kr" => self.mfds_drug_foreign_non_kr,
+			"mfds_sender_health_professional" => self.mfds_sender_health_professional,
+			"mfds_reporter_other_health_professional" => self.mfds_reporter_other_health_professional,
+			"mfds_study_type_other" => self.mfds_study_type_other,
+			"mfds_kr_code_present" => self.mfds_kr_code_present,
+			"mfds_kr_method_who_umc" => self.mfds_kr_method_who_umc,
+			"mfds_kr_method_korean" => self.mfds_kr_method_korean,
+			"mfds_kr_result_method_mismatch" => self.mfds_kr_result_method_mismatch,
+			"ema_receiver_routing_mismatch" => self.ema_receiver_routing_mismatch,
+			"ema_literature_reference_present" => self.ema_literature_reference_present,
+			"ema_nullification_code_present" => self.ema_nullification_code_present,
+			"pmda_expedited_criteria_true" => self.pmda_expedited_criteria_true,
+			"pmda_japanese_summary_missing" => self.pmda_japanese_summary_missing,
+			"pmda_summary_text_present" => self.pmda_summary_text_present,
+			_ => return None,
+		};
+		Some(value)
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1046,11 +1497,26 @@ fn condition_for_code(code: &str) -> RuleCondition {
 		"FDA.E.i.3.2h.REQUIRED" => {
 			RuleCondition::FdaReactionOtherMedicallyImportantTrue
 		}
-		"MFDS.C.3.1.KR.1.REQUIRED" => RuleCondition::MfdsSenderTypeDisallowed,
-		"MFDS.G.k.9.i.2.r.2.KR.1.REQUIRED"
-		| "MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED" => {
+		"MFDS.C.2.r.4.KR.1.REQUIRED" => {
+			RuleCondition::MfdsReporterOtherHealthProfessional
+		}
+		"MFDS.C.2.r.4.KR.1.FORBIDDEN" => {
+			RuleCondition::MfdsReporterNotOtherHealthProfessional
+		}
+		"MFDS.C.3.1.KR.1.REQUIRED" => RuleCondition::MfdsSenderHealthProfessional,
+		"MFDS.C.5.4.KR.1.REQUIRED" => RuleCondition::MfdsStudyTypeOther,
+		"MFDS.D.8.r.1.KR.1a.REQUIRED"
+		| "MFDS.D.10.8.r.1.KR.1a.REQUIRED"
+		| "MFDS.G.k.2.1.KR.1a.REQUIRED"
+		| "MFDS.G.k.2.3.r.1.KR.1a.REQUIRED" => RuleCondition::MfdsKrCodePresent,
+		"MFDS.G.k.9.i.2.r.2.KR.1.REQUIRED" => {
 			RuleCondition::MfdsRelatednessSourcePresent
 		}
+		"MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED" => RuleCondition::MfdsKrMethodWhoUmc,
+		"MFDS.G.k.9.i.2.r.3.KR.2.REQUIRED" => RuleCondition::MfdsKrMethodKorean,
+		"MFDS.G.k.9.i.2.r.3.KR.METHOD.MISMATCH" => {
+			RuleCondition::MfdsKrResultMethodMismatch
+		}
 		"MFDS.G.k.9.i.2.r.1.REQUIRED" => {
 			RuleCondition::MfdsRelatednessMethodOrResultPresent
 		}
@@ -1061,6 +1527,14 @@ fn condition_for_code(code: &str) -> RuleCondition {
 		"MFDS.KR.FOREIGN.WHOMPID.RECOMMENDED" => {
 			RuleCondition::MfdsDrugForeignNonKr
 		}
+		"EMA.N.2.r.3.RECEIVER.MISMATCH" => RuleCondition::EmaReceiverRoutingMismatch,
+		"EMA.C.1.3.LITERATURE.INVALID" | "EMA.C.4.r.2.RECOMMENDED" => {
+			RuleCondition::EmaLiteratureReferencePresent
+		}
+		"EMA.C.1.11.2.REQUIRED" => RuleCondition::EmaNullificationCodePresent,
+		"PMDA.J.4.REQUIRED" => RuleCondition::PmdaExpeditedCriteriaTrue,
+		"PMDA.H.5.r.JA.REQUIRED" => RuleCondition::PmdaJapaneseSummaryMissing,
+		"PMDA.H.5.r.1b.REQUIRED" => RuleCondition::PmdaSummaryTextPresent,
 		_ => RuleCondition::Always,
 	}
 }
@@ -1077,11 +1551,28 @@ fn to_canonical_rule<'a>(rule: &'a ValidationRuleMetadata) -> CanonicalRule<'a>
 	}
 }
 
+/// FDA rejection/warning rules are always evaluated; their severity follows
+/// the published table.
+fn fda_rule_to_canonical_rule(
+	rule: &'static FdaRuleDefinition,
+) -> CanonicalRule<'static> {
+	CanonicalRule {
+		code: rule.code,
+		profile: ValidationProfile::Fda,
+		section: rule.section(),
+		blocking: rule.blocking(),
+		message: rule.description,
+		condition: RuleCondition::Always,
+		export_directive: None,
+	}
+}
+
 pub fn find_canonical_rule(code: &str) -> Option<CanonicalRule<'static>> {
 	CANONICAL_RULES
 		.iter()
 		.find(|rule| rule.code == code)
 		.map(to_canonical_rule)
+		.or_else(|| find_fda_rule_by_code(code).map(fda_rule_to_canonical_rule))
 }
 
 pub fn canonical_rules_for_profile(
@@ -1094,14 +1585,26 @@ pub fn canonical_rules_for_profile(
 				|| rule.profile == profile
 		})
 		.map(to_canonical_rule)
+		.chain(
+			FDA_REJECTION_WARNING_RULES
+				.iter()
+				.filter(|_| profile == ValidationProfile::Fda)
+				.map(fda_rule_to_canonical_rule),
+		)
 		.collect()
 }
 
 pub fn canonical_rules_all() -> Vec<CanonicalRule<'static>> {
-	CANONICAL_RULES.iter().map(to_canonical_rule).collect()
+	CANONICAL_RULES
+		.iter()
+		.map(to_canonical_rule)
+		.chain(FDA_REJECTION_WARNING_RULES.iter().map(fda_rule_to_canonical_rule))
+		.collect()
 }
 
-fn fnv1a_update(mut hash: u64, bytes: &[u8]) -> u64 {
+pub(super) const FNV_OFFSET_BASIS: u64 = 14695981039346656037;
+
+pub(super) fn fnv1a_update(mut hash: u64, bytes: &[u8]) -> u64 {
 	const FNV_PRIME: u64 = 1099511628211;
 	for b in bytes {
 		hash ^= *b as u64;
@@ -1110,96 +1613,20 @@ fn fnv1a_update(mut hash: u64, bytes: &[u8]) -> u64 {
 	hash
 }
 
+/// Version of the compiled-in catalog; a loaded [`ValidationRuleSet`] also
+/// reflects stored definitions, overrides and their revision.
 pub fn canonical_rules_version(profile: Option<ValidationProfile>) -> String {
-	let rules = if let Some(profile) = profile {
-		canonical_rules_for_profile(profile)
-	} else {
-		canonical_rules_all()
-	};
-
-	let mut hash: u64 = 14695981039346656037;
-	for rule in rules {
-		hash = fnv1a_update(hash, rule.code.as_bytes());
-		hash = fnv1a_update(hash, b"|");
-		hash = fnv1a_update(hash, rule.profile.as_str().as_bytes());
-		hash = fnv1a_update(hash, b"|");
-		hash = fnv1a_update(hash, rule.section.as_bytes());
-		hash = fnv1a_update(hash, b"|");
-		hash = fnv1a_update(hash, if rule.blocking { b"1" } else { b"0" });
-		hash = fnv1a_update(hash, b"|");
-		hash = fnv1a_update(hash, rule.message.as_bytes());
-		hash = fnv1a_update(hash, b"|");
-		hash = fnv1a_update(hash, rule.condition.as_str().as_bytes());
-		hash = fnv1a_update(hash, b"|");
-		if let Some(d) = rule.export_directive {
-			hash = fnv1a_update(hash, d.as_str().as_bytes());
-		}
-		hash = fnv1a_update(hash, b";");
-	}
-
-	format!("{hash:016x}")
+	ValidationRuleSet::compiled().version(profile)
 }
 
 pub fn is_rule_condition_satisfied(code: &str, facts: RuleFacts) -> bool {
+	if let Some(satisfied) = stored_condition_satisfied(code, facts) {
+		return satisfied;
+	}
 	let Some(rule) = find_canonical_rule(code) else {
 		return true;
 	};
-	match rule.condition {
-		RuleCondition::Always => true,
-		RuleCondition::IchCaseHistoryTrueMissingPriorIds => facts
-			.ich_case_history_true_missing_prior_ids
-			.unwrap_or(false),
-		RuleCondition::IchMedicalHistoryMissingD72Text => facts
-			.ich_medical_history_missing_d72_text
-			.unwrap_or(false),
-		RuleCondition::FdaFulfilExpeditedCriteriaTrue => {
-			facts.fda_fulfil_expedited_criteria.unwrap_or(false)
-		}
-		RuleCondition::FdaReactionOtherMedicallyImportantTrue => {
-			facts.fda_reaction_other_medically_important.unwrap_or(false)
-		}
-		RuleCondition::FdaPrimarySourcePresent => {
-			facts.fda_primary_source_present.unwrap_or(false)
-		}
-		RuleCondition::FdaPatientPayloadPresent => {
-			facts.fda_patient_payload_present.unwrap_or(false)
-		}
-		RuleCondition::FdaPreAndaRequired => {
-			facts.fda_type_of_report_is_two.unwrap_or(false)
-				&& facts
-					.fda_msg_receiver_is_cder_ind_exempt_ba_be
-					.unwrap_or(false)
-				&& !facts.fda_has_pre_anda.unwrap_or(false)
-		}
-		RuleCondition::FdaPreAndaForbidden => {
-			facts.fda_has_pre_anda.unwrap_or(false)
-				&& facts.fda_batch_receiver_is_zzfda.unwrap_or(false)
-				&& facts.fda_msg_receiver_is_cder_or_cber.unwrap_or(false)
-		}
-		RuleCondition::FdaGk10aRequired => facts.fda_has_pre_anda.unwrap_or(false),
-		RuleCondition::FdaPremarketReportTypeMustBeTwo => {
-			facts.fda_batch_receiver_is_zzfda_premarket.unwrap_or(false)
-				&& facts.fda_msg_receiver_is_premarket.unwrap_or(false)
-				&& facts.fda_has_pre_anda.unwrap_or(false)
-				&& facts.fda_study_type_is_1_2_3.unwrap_or(false)
-		}
-		RuleCondition::MfdsRelatednessSourcePresent => {
-			facts.mfds_relatedness_source_present.unwrap_or(false)
-		}
-		RuleCondition::MfdsRelatednessMethodOrResultPresent => {
-			facts.mfds_relatedness_method_present.unwrap_or(false)
-				|| facts.mfds_relatedness_result_present.unwrap_or(false)
-		}
-		RuleCondition::MfdsDrugDomesticKr => {
-			facts.mfds_drug_domestic_kr.unwrap_or(false)
-		}
-		RuleCondition::MfdsDrugForeignNonKr => {
-			facts.mfds_drug_foreign_non_kr.unwrap_or(false)
-		}
-		RuleCondition::MfdsSenderTypeDisallowed => {
-			facts.mfds_sender_type_disallowed.unwrap_or(false)
-		}
-	}
+	rule.condition.is_satisfied(facts)
 }
 
 pub fn is_rule_value_valid(
@@ -1254,18 +1681,71 @@ pub fn is_rule_value_valid(
 			}
 			value_code.map(|v| v == "2").unwrap_or(false)
 		}
-		"MFDS.KR.DOMESTIC.PRODUCTCODE.REQUIRED"
+		"MFDS.C.2.r.4.KR.1.REQUIRED"
+		| "MFDS.C.3.1.KR.1.REQUIRED"
+		| "MFDS.C.5.4.KR.1.REQUIRED"
+		| "MFDS.D.8.r.1.KR.1a.REQUIRED"
+		| "MFDS.D.10.8.r.1.KR.1a.REQUIRED"
+		| "MFDS.KR.DOMESTIC.PRODUCTCODE.REQUIRED"
 		| "MFDS.KR.FOREIGN.WHOMPID.RECOMMENDED"
 		| "MFDS.KR.DOMESTIC.INGREDIENTCODE.REQUIRED"
+		| "MFDS.G.k.2.1.KR.1a.REQUIRED"
+		| "MFDS.G.k.2.3.r.1.KR.1a.REQUIRED"
 		| "MFDS.G.k.9.i.2.r.1.REQUIRED"
 		| "MFDS.G.k.9.i.2.r.2.KR.1.REQUIRED"
-		| "MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED" => {
+		| "MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED"
+		| "MFDS.G.k.9.i.2.r.3.KR.2.REQUIRED" => {
+			value_code.map(|v| !v.trim().is_empty()).unwrap_or(false)
+		}
+		"MFDS.C.2.r.4.KR.1.FORBIDDEN" => {
+			value_code.map(|v| v.trim().is_empty()).unwrap_or(true)
+		}
+		"EMA.C.4.r.2.RECOMMENDED"
+		| "EMA.C.1.8.1.REQUIRED"
+		| "EMA.C.1.11.2.REQUIRED" => {
+			value_code.map(|v| !v.trim().is_empty()).unwrap_or(false)
+		}
+		"EMA.N.1.4.RECEIVER.INVALID" | "EMA.N.2.r.3.RECEIVER.INVALID" => value_code
+			.map(|v| is_ema_receiver(v.trim()))
+			.unwrap_or(false),
+		"EMA.C.1.3.LITERATURE.INVALID" => {
+			value_code.map(|v| v == "1" || v == "2").unwrap_or(false)
+		}
+		// An absent id is reported by EMA.C.1.8.1.REQUIRED.
+		"EMA.C.1.8.1.FORMAT" => value_code
+			.map(|v| v.trim().is_empty() || is_worldwide_unique_id_format(v.trim()))
+			.unwrap_or(true),
+		"PMDA.J.2.REQUIRED" | "PMDA.J.4.REQUIRED" | "PMDA.H.5.r.1b.REQUIRED" => {
 			value_code.map(|v| !v.trim().is_empty()).unwrap_or(false)
 		}
+		"PMDA.J.ELEMENT.UNKNOWN" => value_code.map(is_jp_element_id).unwrap_or(false),
 		_ => true,
 	}
 }
 
+/// EudraVigilance receiver identifiers (N.1.4 / N.2.r.3): post-authorisation,
+/// clinical trial and test environment.
+pub const EMA_RECEIVERS: &[&str] = &["EVHUMAN", "EVCTMPROD", "EVTEST"];
+
+fn is_ema_receiver(value: &str) -> bool {
+	EMA_RECEIVERS
+		.iter()
+		.any(|receiver| receiver.eq_ignore_ascii_case(value))
+}
+
+/// `CC-organisation-number`: an ISO country code, then the sender and its
+/// case number, both non-empty.
+fn is_worldwide_unique_id_format(value: &str) -> bool {
+	let mut parts = value.splitn(3, '-');
+	let country = parts.next().unwrap_or_default();
+	let organisation = parts.next().unwrap_or_default();
+	let number = parts.next().unwrap_or_default();
+	country.len() == 2
+		&& country.bytes().all(|b| b.is_ascii_uppercase())
+		&& !organisation.trim().is_empty()
+		&& !number.trim().is_empty()
+}
+
 pub fn is_rule_presence_valid(
 	code: &str,
 	present: bool,
@@ -1370,6 +1850,7 @@ pub fn export_attribute_strip_spec_for_rule(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use std::collections::BTreeSet;
 
 	#[test]
 	fn canonical_lookup_covers_validation_rules() {
@@ -1526,7 +2007,33 @@ mod tests {
 		assert!(is_rule_condition_satisfied(
 			"MFDS.C.3.1.KR.1.REQUIRED",
 			RuleFacts {
-				mfds_sender_type_disallowed: Some(true),
+				mfds_sender_health_professional: Some(true),
+				..RuleFacts::default()
+			}
+		));
+		assert!(is_rule_condition_satisfied(
+			"MFDS.C.2.r.4.KR.1.FORBIDDEN",
+			RuleFacts {
+				mfds_reporter_other_health_professional: Some(false),
+				..RuleFacts::default()
+			}
+		));
+		assert!(!is_rule_condition_satisfied(
+			"MFDS.C.2.r.4.KR.1.FORBIDDEN",
+			RuleFacts::default()
+		));
+		assert!(is_rule_condition_satisfied(
+			"MFDS.G.k.9.i.2.r.3.KR.2.REQUIRED",
+			RuleFacts {
+				mfds_kr_method_korean: Some(true),
+				..RuleFacts::default()
+			}
+		));
+		assert!(!is_rule_condition_satisfied(
+			"MFDS.G.k.9.i.2.r.3.KR.1.REQUIRED",
+			RuleFacts {
+				mfds_relatedness_source_present: Some(true),
+				mfds_kr_method_korean: Some(true),
 				..RuleFacts::default()
 			}
 		));
@@ -1665,6 +2172,233 @@ mod tests {
 		));
 	}
 
+	#[test]
+	fn ema_rules_are_evaluated_from_catalog() {
+		let ema_rules = canonical_rules_for_profile(ValidationProfile::Ema);
+		assert!(ema_rules.iter().any(|rule| rule.code == "ICH.C.1.3.REQUIRED"));
+		assert!(ema_rules.iter().any(|rule| rule.code == "EMA.C.1.8.1.FORMAT"));
+		assert!(!ema_rules.iter().any(|rule| rule.code.starts_with("FDA.")));
+
+		assert!(is_rule_value_valid(
+			"EMA.N.2.r.3.RECEIVER.INVALID",
+			Some("EVCTMPROD"),
+			None,
+			RuleFacts::default()
+		));
+		assert!(!is_rule_value_valid(
+			"EMA.N.1.4.RECEIVER.INVALID",
+			Some("ZZFDA"),
+			None,
+			RuleFacts::default()
+		));
+		assert!(is_rule_value_valid(
+			"EMA.C.1.8.1.FORMAT",
+			Some("DE-ACME-2024-00012"),
+			None,
+			RuleFacts::default()
+		));
+		assert!(!is_rule_value_valid(
+			"EMA.C.1.8.1.FORMAT",
+			Some("ACME-00012"),
+			None,
+			RuleFacts::default()
+		));
+		assert!(!is_rule_value_valid(
+			"EMA.C.1.3.LITERATURE.INVALID",
+			Some("3"),
+			None,
+			RuleFacts::default()
+		));
+		assert!(is_rule_condition_satisfied(
+			"EMA.C.1.11.2.REQUIRED",
+			RuleFacts {
+				ema_nullification_code_present: Some(true),
+				..RuleFacts::default()
+			}
+		));
+		assert!(!is_rule_condition_satisfied(
+			"EMA.C.4.r.2.RECOMMENDED",
+			RuleFacts {
+				ema_literature_reference_present: Some(false),
+				..RuleFacts::default()
+			}
+		));
+	}
+
+	#[test]
+	fn worldwide_unique_id_needs_country_sender_and_number() {
+		for (value, valid) in [
+			("DE-ACME-12345", true),
+			("FR-ACME-2024-00012", true),
+			("DE-ACME-", false),
+			("DE--12345", false),
+			("de-ACME-12345", false),
+			("DEU-ACME-12345", false),
+			("D1-ACME-12345", false),
+			("ACME-12345", false),
+			("DE-ACME", false),
+			("", false),
+		] {
+			assert_eq!(is_worldwide_unique_id_format(value), valid, "{value}");
+		}
+	}
+
+	/// One rule-violating and one compliant input per EMA catalog rule,
+	/// evaluated the way `ema::validation` raises them: condition-only rules
+	/// through `push_issue_if_condition_violated`, the others through
+	/// `push_issue_if_conditioned_value_invalid`.
+	#[test]
+	fn every_ema_rule_fires_only_on_violation() {
+		let literature = RuleFacts {
+			ema_literature_reference_present: Some(true),
+			..RuleFacts::default()
+		};
+		let nullified = RuleFacts {
+			ema_nullification_code_present: Some(true),
+			..RuleFacts::default()
+		};
+		let mismatch = |value| RuleFacts {
+			ema_receiver_routing_mismatch: Some(value),
+			..RuleFacts::default()
+		};
+		let condition_only = ["EMA.N.2.r.3.RECEIVER.MISMATCH"];
+		let fires = |code: &str, value: Option<&str>, facts: RuleFacts| {
+			is_rule_condition_satisfied(code, facts)
+				&& (condition_only.contains(&code)
+					|| !is_rule_value_valid(code, value, None, RuleFacts::default()))
+		};
+
+		let cases = &[
+			(
+				"EMA.N.1.4.RECEIVER.INVALID",
+				Some("ZZFDA"),
+				RuleFacts::default(),
+				Some("EVHUMAN"),
+				RuleFacts::default(),
+			),
+			(
+				"EMA.N.2.r.3.RECEIVER.INVALID",
+				Some("CDER"),
+				RuleFacts::default(),
+				Some("evtest"),
+				RuleFacts::default(),
+			),
+			(
+				"EMA.N.2.r.3.RECEIVER.MISMATCH",
+				Some("EVHUMAN"),
+				mismatch(true),
+				Some("EVHUMAN"),
+				mismatch(false),
+			),
+			(
+				"EMA.C.1.3.LITERATURE.INVALID",
+				Some("3"),
+				literature,
+				Some("3"),
+				RuleFacts::default(),
+			),
+			(
+				"EMA.C.4.r.2.RECOMMENDED",
+				None,
+				literature,
+				Some("JVBERi0="),
+				literature,
+			),
+			(
+				"EMA.C.1.8.1.REQUIRED",
+				Some(" "),
+				RuleFacts::default(),
+				Some("DE-ACME-12345"),
+				RuleFacts::default(),
+			),
+			(
+				"EMA.C.1.8.1.FORMAT",
+				Some("ACME-12345"),
+				RuleFacts::default(),
+				None,
+				RuleFacts::default(),
+			),
+			(
+				"EMA.C.1.11.2.REQUIRED",
+				None,
+				nullified,
+				None,
+				RuleFacts::default(),
+			),
+		];
+
+		let catalog: BTreeSet<&str> =
+			canonical_rules_for_profile(ValidationProfile::Ema)
+				.iter()
+				.map(|rule| rule.code)
+				.filter(|code| code.starts_with("EMA."))
+				.collect();
+		let tested: BTreeSet<&str> = cases.iter().map(|case| case.0).collect();
+		assert_eq!(tested, catalog, "every EMA rule needs a case");
+
+		for (code, bad_value, bad_facts, good_value, good_facts) in cases {
+			assert!(fires(code, *bad_value, *bad_facts), "{code} should fire");
+			assert!(!fires(code, *good_value, *good_facts), "{code} should pass");
+		}
+	}
+
+	#[test]
+	fn pmda_rules_are_evaluated_from_catalog() {
+		let pmda_rules = canonical_rules_for_profile(ValidationProfile::Pmda);
+		assert!(pmda_rules.iter().any(|rule| rule.code == "ICH.C.1.3.REQUIRED"));
+		assert!(pmda_rules.iter().any(|rule| rule.code == "PMDA.J.2.REQUIRED"));
+		assert!(!pmda_rules.iter().any(|rule| rule.code.starts_with("EMA.")));
+
+		for (element_id, valid) in [
+			("J.2", true),
+			("J.20", true),
+			("J.1", false),
+			("J.21", false),
+			("KR.1", false),
+		] {
+			assert_eq!(
+				is_rule_value_valid(
+					"PMDA.J.ELEMENT.UNKNOWN",
+					Some(element_id),
+					None,
+					RuleFacts::default()
+				),
+				valid,
+				"{element_id}"
+			);
+		}
+		assert!(!is_rule_value_valid(
+			"PMDA.J.2.REQUIRED",
+			None,
+			None,
+			RuleFacts::default()
+		));
+		assert!(!is_rule_condition_satisfied(
+			"PMDA.J.4.REQUIRED",
+			RuleFacts {
+				pmda_expedited_criteria_true: Some(false),
+				..RuleFacts::default()
+			}
+		));
+		assert!(is_rule_condition_satisfied(
+			"PMDA.H.5.r.JA.REQUIRED",
+			RuleFacts {
+				pmda_japanese_summary_missing: Some(true),
+				..RuleFacts::default()
+			}
+		));
+	}
+
+	#[test]
+	fn receiver_identifier_selects_profile() {
+		assert_eq!(ValidationProfile::for_receiver("evhuman"), ValidationProfile::Ema);
+		assert_eq!(ValidationProfile::for_receiver("EVCTMPROD"), ValidationProfile::Ema);
+		assert_eq!(ValidationProfile::for_receiver("MFDS-KR"), ValidationProfile::Mfds);
+		assert_eq!(ValidationProfile::for_receiver("PMDA"), ValidationProfile::Pmda);
+		assert_eq!(ValidationProfile::for_receiver("ZZFDA"), ValidationProfile::Fda);
+		assert_eq!(ValidationProfile::for_receiver(""), ValidationProfile::Fda);
+	}
+
 	#[test]
 	fn fda_presence_rules_are_evaluated_from_catalog() {
 		assert!(is_rule_presence_valid(
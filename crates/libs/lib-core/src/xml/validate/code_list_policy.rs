@@ -0,0 +1,106 @@
+// Shared code list policy used by importer + exporter + case validators.
+use super::{find_canonical_rule, has_text, ValidationIssue};
+use crate::model::terminology::CodeListCatalog;
+
+pub const CODE_LIST_INVALID_RULE: &str = "ICH.CODE_LIST.INVALID";
+
+pub const REPORT_TYPE: &str = "report_type";
+pub const NULLIFICATION_CODE: &str = "nullification_code";
+pub const SENDER_TYPE: &str = "sender_type";
+pub const QUALIFICATION: &str = "qualification";
+pub const PRIMARY_SOURCE_REGULATORY: &str = "primary_source_regulatory";
+pub const SEX: &str = "sex";
+pub const AGE_GROUP: &str = "age_group";
+pub const REACTION_OUTCOME: &str = "reaction_outcome";
+pub const TEST_RESULT_ASSESSMENT: &str = "test_result_assessment";
+pub const DRUG_CHARACTERIZATION: &str = "drug_characterization";
+pub const DRUG_ACTION: &str = "drug_action";
+pub const RECHALLENGE: &str = "rechallenge";
+pub const ROUTE_OF_ADMINISTRATION: &str = "route_of_administration";
+pub const LOCAL_CRITERIA_REPORT_TYPE: &str = "local_criteria_report_type";
+pub const FDA_RACE: &str = "fda_race";
+pub const FDA_ETHNICITY: &str = "fda_ethnicity";
+pub const KR_OTHER_HEALTH_PROFESSIONAL: &str = "kr_other_health_professional";
+pub const KR_HEALTH_PROFESSIONAL_TYPE: &str = "kr_health_professional_type";
+pub const KR_OTHER_STUDY_TYPE: &str = "kr_other_study_type";
+pub const KR_ASSESSMENT_METHOD: &str = "kr_assessment_method";
+pub const KR_ASSESSMENT_RESULT_WHO_UMC: &str = "kr_assessment_result_who_umc";
+pub const KR_ASSESSMENT_RESULT_KOREAN: &str = "kr_assessment_result_korean";
+pub const JP_REPORTING_CATEGORY: &str = "jp_reporting_category";
+pub const JP_REPORTING_DEADLINE: &str = "jp_reporting_deadline";
+
+/// Whether a coded value is outside its list. Empty values and lists that are
+/// not loaded are left to the presence rules.
+pub fn is_code_not_in_list(
+	catalog: &CodeListCatalog,
+	list_name: &str,
+	value: Option<&str>,
+) -> bool {
+	if !has_text(value) {
+		return false;
+	}
+	let code = value.unwrap_or_default().trim();
+	catalog.contains(list_name, code) == Some(false)
+}
+
+pub fn push_issue_if_code_not_in_list(
+	issues: &mut Vec<ValidationIssue>,
+	catalog: &CodeListCatalog,
+	list_name: &str,
+	path: impl Into<String>,
+	value: Option<&str>,
+) -> bool {
+	if !is_code_not_in_list(catalog, list_name, value) {
+		return false;
+	}
+	let Some(rule) = find_canonical_rule(CODE_LIST_INVALID_RULE) else {
+		return false;
+	};
+	issues.push(ValidationIssue {
+		code: rule.code.to_string(),
+		message: format!(
+			"[{list_name}] '{}' is not an active code of the list.",
+			value.unwrap_or_default().trim()
+		),
+		path: path.into(),
+		section: rule.section.to_string(),
+		blocking: rule.blocking,
+	});
+	true
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn catalog() -> CodeListCatalog {
+		CodeListCatalog::from_codes([(SEX, "1"), (SEX, "2"), (SEX, "0")])
+	}
+
+	#[test]
+	fn code_outside_loaded_list_is_flagged() {
+		let mut issues = Vec::new();
+		assert!(push_issue_if_code_not_in_list(
+			&mut issues,
+			&catalog(),
+			SEX,
+			"patient.sex",
+			Some(" 7 ")
+		));
+		assert_eq!(issues[0].code, CODE_LIST_INVALID_RULE);
+		assert_eq!(
+			issues[0].message,
+			"[sex] '7' is not an active code of the list."
+		);
+		assert!(issues[0].blocking);
+	}
+
+	#[test]
+	fn empty_values_and_unloaded_lists_pass() {
+		let catalog = catalog();
+		assert!(!is_code_not_in_list(&catalog, SEX, Some("2")));
+		assert!(!is_code_not_in_list(&catalog, SEX, Some(" ")));
+		assert!(!is_code_not_in_list(&catalog, SEX, None));
+		assert!(!is_code_not_in_list(&catalog, FDA_RACE, Some("X")));
+	}
+}
@@ -0,0 +1,185 @@
+// Stored rule conditions: boolean expressions over named conditions and facts.
+use super::{RuleCondition, RuleFacts};
+
+/// A parsed condition such as `fda_has_pre_anda && !fda_batch_receiver_is_zzfda`.
+/// Identifiers are [`RuleCondition`] names or [`RuleFacts`] field names; `&&`,
+/// `||`, `!` (or `and`, `or`, `not`), parentheses, `true`, `false` and
+/// `always` are supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionExpr {
+	Const(bool),
+	Condition(RuleCondition),
+	Fact(String),
+	Not(Box<ConditionExpr>),
+	And(Box<ConditionExpr>, Box<ConditionExpr>),
+	Or(Box<ConditionExpr>, Box<ConditionExpr>),
+}
+
+impl ConditionExpr {
+	pub fn parse(source: &str) -> Result<Self, String> {
+		let tokens = tokenize(source)?;
+		if tokens.is_empty() {
+			return Err("empty condition".to_string());
+		}
+		let mut parser = Parser { tokens, pos: 0 };
+		let expr = parser.or()?;
+		match parser.tokens.get(parser.pos) {
+			Some(token) => Err(format!("unexpected '{token}'")),
+			None => Ok(expr),
+		}
+	}
+
+	/// Missing facts count as false, like the compiled conditions.
+	pub fn evaluate(&self, facts: &RuleFacts) -> bool {
+		match self {
+			Self::Const(value) => *value,
+			Self::Condition(condition) => condition.is_satisfied(*facts),
+			Self::Fact(name) => facts.fact(name).flatten().unwrap_or(false),
+			Self::Not(inner) => !inner.evaluate(facts),
+			Self::And(left, right) => left.evaluate(facts) && right.evaluate(facts),
+			Self::Or(left, right) => left.evaluate(facts) || right.evaluate(facts),
+		}
+	}
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+	let mut tokens = Vec::new();
+	let mut chars = source.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			c if c.is_whitespace() => {}
+			'(' | ')' | '!' => tokens.push(c.to_string()),
+			'&' | '|' => {
+				if chars.next() != Some(c) {
+					return Err(format!("expected '{c}{c}'"));
+				}
+				tokens.push(format!("{c}{c}"));
+			}
+			c if c.is_ascii_alphanumeric() || c == '_' => {
+				let mut ident = c.to_string();
+				while let Some(&next) = chars.peek() {
+					if !(next.is_ascii_alphanumeric() || next == '_') {
+						break;
+					}
+					ident.push(next);
+					chars.next();
+				}
+				tokens.push(ident);
+			}
+			other => return Err(format!("unexpected '{other}'")),
+		}
+	}
+	Ok(tokens)
+}
+
+struct Parser {
+	tokens: Vec<String>,
+	pos: usize,
+}
+
+impl Parser {
+	fn accept(&mut self, alternatives: &[&str]) -> bool {
+		let matched = self
+			.tokens
+			.get(self.pos)
+			.is_some_and(|token| alternatives.contains(&token.as_str()));
+		if matched {
+			self.pos += 1;
+		}
+		matched
+	}
+
+	fn or(&mut self) -> Result<ConditionExpr, String> {
+		let mut expr = self.and()?;
+		while self.accept(&["||", "or"]) {
+			expr = ConditionExpr::Or(Box::new(expr), Box::new(self.and()?));
+		}
+		Ok(expr)
+	}
+
+	fn and(&mut self) -> Result<ConditionExpr, String> {
+		let mut expr = self.unary()?;
+		while self.accept(&["&&", "and"]) {
+			expr = ConditionExpr::And(Box::new(expr), Box::new(self.unary()?));
+		}
+		Ok(expr)
+	}
+
+	fn unary(&mut self) -> Result<ConditionExpr, String> {
+		if self.accept(&["!", "not"]) {
+			return Ok(ConditionExpr::Not(Box::new(self.unary()?)));
+		}
+		self.atom()
+	}
+
+	fn atom(&mut self) -> Result<ConditionExpr, String> {
+		if self.accept(&["("]) {
+			let expr = self.or()?;
+			if !self.accept(&[")"]) {
+				return Err("missing ')'".to_string());
+			}
+			return Ok(expr);
+		}
+		let Some(token) = self.tokens.get(self.pos).cloned() else {
+			return Err("unexpected end of condition".to_string());
+		};
+		self.pos += 1;
+		match token.as_str() {
+			"true" => Ok(ConditionExpr::Const(true)),
+			"false" => Ok(ConditionExpr::Const(false)),
+			name => {
+				if let Some(condition) = RuleCondition::parse(name) {
+					Ok(ConditionExpr::Condition(condition))
+				} else if RuleFacts::default().fact(name).is_some() {
+					Ok(ConditionExpr::Fact(name.to_string()))
+				} else {
+					Err(format!("unknown condition or fact '{name}'"))
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn named_conditions_and_facts_combine() {
+		let expr = ConditionExpr::parse(
+			"fda_has_pre_anda && !(fda_batch_receiver_is_zzfda)",
+		)
+		.expect("parse");
+		let facts = RuleFacts {
+			fda_has_pre_anda: Some(true),
+			..RuleFacts::default()
+		};
+		assert!(expr.evaluate(&facts));
+		let facts = RuleFacts {
+			fda_batch_receiver_is_zzfda: Some(true),
+			..facts
+		};
+		assert!(!expr.evaluate(&facts));
+
+		let named = ConditionExpr::parse("always").expect("parse");
+		assert_eq!(named, ConditionExpr::Condition(RuleCondition::Always));
+		let either = ConditionExpr::parse(
+			"mfds_drug_domestic_kr or mfds_drug_foreign_non_kr",
+		)
+		.expect("parse");
+		assert!(!either.evaluate(&RuleFacts::default()));
+	}
+
+	#[test]
+	fn malformed_conditions_are_rejected() {
+		for source in [
+			"",
+			"no_such_fact",
+			"fda_has_pre_anda &",
+			"(true",
+			"true false",
+		] {
+			assert!(ConditionExpr::parse(source).is_err(), "{source}");
+		}
+	}
+}
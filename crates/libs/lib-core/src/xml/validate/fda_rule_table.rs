@@ -0,0 +1,914 @@
+// FDA rejection/warning rules as published in the FDA regional business
+// rules (docs/refs/rejection_warning_rules.csv), keyed by error ID.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FdaRuleSeverity {
+	Rejection,
+	Warning,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FdaRuleDefinition {
+	/// Official error ID (R#### rejects the ICSR, W#### is a warning)
+	pub id: &'static str,
+	/// Catalog code, the error ID prefixed with `FDA.`
+	pub code: &'static str,
+	/// Data element the rule is listed under
+	pub element: &'static str,
+	pub severity: FdaRuleSeverity,
+	pub description: &'static str,
+}
+
+impl FdaRuleDefinition {
+	pub fn blocking(&self) -> bool {
+		self.severity == FdaRuleSeverity::Rejection
+	}
+
+	/// Catalog section of the element the rule is listed under.
+	pub fn section(&self) -> &'static str {
+		let element = self.element.trim_start_matches("FDA.");
+		if element.starts_with("C.2.") {
+			"reporter"
+		} else if element.starts_with("C.5.") {
+			"study"
+		} else if element.starts_with('N') || element.starts_with('C') {
+			"case-identification"
+		} else if element.starts_with('D') {
+			"patient"
+		} else if element.starts_with('E') {
+			"reactions"
+		} else if element.starts_with('F') {
+			"test-results"
+		} else if element.starts_with('G') {
+			"drugs"
+		} else {
+			"narrative"
+		}
+	}
+}
+
+pub fn find_fda_rule(id: &str) -> Option<&'static FdaRuleDefinition> {
+	FDA_REJECTION_WARNING_RULES
+		.iter()
+		.find(|rule| rule.id == id)
+}
+
+/// Rule of a catalog code such as `FDA.R0100`.
+pub fn find_fda_rule_by_code(code: &str) -> Option<&'static FdaRuleDefinition> {
+	FDA_REJECTION_WARNING_RULES
+		.iter()
+		.find(|rule| rule.code == code)
+}
+
+pub const FDA_REJECTION_WARNING_RULES: &[FdaRuleDefinition] = &[
+	FdaRuleDefinition {
+		id: "R0004",
+		code: "FDA.R0004",
+		element: "N.1.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "ICSR sent to the postmaket route does not have the value \"ZZFDA\" for N.1.4",
+	},
+	FdaRuleDefinition {
+		id: "R0005",
+		code: "FDA.R0005",
+		element: "N.1.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "ICSR sent to the premaket route does not have the value \"ZZFDA_PREMKT\" for N.1.4",
+	},
+	FdaRuleDefinition {
+		id: "R0100",
+		code: "FDA.R0100",
+		element: "N.2.r.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "N.2.r.2 provided is not same for all reports and does not match with N.1.3",
+	},
+	FdaRuleDefinition {
+		id: "R0006",
+		code: "FDA.R0006",
+		element: "N.2.r.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "N.1.4 = \"ZZFDA\" but N.2.r.3 is not \"CDER\"",
+	},
+	FdaRuleDefinition {
+		id: "R0007",
+		code: "FDA.R0007",
+		element: "N.2.r.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "N.1.4 = \"ZZFDA_PREMKT\" but N.2.r.3 is not \"CDER_IND\" or \"CBER_IND\" or \"CDER_IND_EXEMPT_BA_BE\"",
+	},
+	FdaRuleDefinition {
+		id: "R0008",
+		code: "FDA.R0008",
+		element: "C.1.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.3 must be 2 when N.1.4 = \"ZZFDA_PREMKT\" and N.2.r.3 = 'CDER_IND' or 'CBER_IND' or 'CDER_IND_EXEMPT_BA_BE' and (FDA.C.5.5a or FDA.C.5.5b) is provided and C.5.4 = (1, 2 or 3)",
+	},
+	FdaRuleDefinition {
+		id: "R0110",
+		code: "FDA.R0110",
+		element: "C.1.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.3 must be 1 when N.1.4 = \"ZZFDA_PREMKT\" and N.2.r.3 = ('CDER_IND' or 'CBER_IND') and FDA.C.5.5a is provided and C.5.4 is not provided",
+	},
+	FdaRuleDefinition {
+		id: "R0111",
+		code: "FDA.R0111",
+		element: "C.1.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.3 must be 2 when N.1.4 = \"ZZFDA_PREMKT\" and N.2.r.3 = 'CDER_IND_EXEMPT_BA_BE'",
+	},
+	FdaRuleDefinition {
+		id: "R0112",
+		code: "FDA.R0112",
+		element: "C.1.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.3 must not be 3 or 4 when N.1.4 = \"ZZFDA_PREMKT\" and N.2.r.3 = 'CDER_IND' or 'CBER_IND'",
+	},
+	FdaRuleDefinition {
+		id: "R0009",
+		code: "FDA.R0009",
+		element: "C.1.6.1.r.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.6.1.r.1 must be provided when C.1.6.1 = true",
+	},
+	FdaRuleDefinition {
+		id: "R0010",
+		code: "FDA.R0010",
+		element: "C.1.6.1.r.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "Compression used for US reporting and encoding is not B64.",
+	},
+	FdaRuleDefinition {
+		id: "R0011",
+		code: "FDA.R0011",
+		element: "C.1.7",
+		severity: FdaRuleSeverity::Rejection,
+		description: "nullFlavor 'NI' used for initial report submission",
+	},
+	FdaRuleDefinition {
+		id: "R0012",
+		code: "FDA.R0012",
+		element: "FDA.C.1.7.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.1.7.1 must have Observation Code Value of 1 or 4 when FDA.C.1.12 = true and C.1.7 = true",
+	},
+	FdaRuleDefinition {
+		id: "R0013",
+		code: "FDA.R0013",
+		element: "FDA.C.1.7.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.1.7.1 must have Observation Code Value of 2 or 5 when FDA.C.1.12 = true and C.1.7 = false or nullFlavor \"NI\"",
+	},
+	FdaRuleDefinition {
+		id: "R0014",
+		code: "FDA.R0014",
+		element: "FDA.C.1.7.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.1.7.1 must have Observation Code Value of 1 when FDA.C.1.12 = false or NI and C.1.7 = true",
+	},
+	FdaRuleDefinition {
+		id: "R0015",
+		code: "FDA.R0015",
+		element: "FDA.C.1.7.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.1.7.1 must have Observation Code Value of 2 when FDA.C.1.12 = false or nullFlavor \"NI\" and C.1.7 = false or nullFlavor \"NI\"",
+	},
+	FdaRuleDefinition {
+		id: "R0016",
+		code: "FDA.R0016",
+		element: "FDA.C.1.7.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.1.7.1 must have Observation Code Value of 1 or 6 when C.1.7 = true and C.1.3 = 1 or 2",
+	},
+	FdaRuleDefinition {
+		id: "R0017",
+		code: "FDA.R0017",
+		element: "C.1.9.1.r.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "At least one C.1.9.1.r.1 must be provided when C.1.9.1 = true",
+	},
+	FdaRuleDefinition {
+		id: "R0018",
+		code: "FDA.R0018",
+		element: "C.1.9.1.r.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.9.1.r.2 must be provided for C.1.9.1.r.1 = <value>",
+	},
+	FdaRuleDefinition {
+		id: "W0001",
+		code: "FDA.W0001",
+		element: "C.1.10.r",
+		severity: FdaRuleSeverity::Warning,
+		description: "C.1.10.r should be provided when D.1 = \"AGGREGRATE\"",
+	},
+	FdaRuleDefinition {
+		id: "R0101",
+		code: "FDA.R0101",
+		element: "C.1.11.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.1.11.1 must not have the value (1 or 2) for an initial report",
+	},
+	FdaRuleDefinition {
+		id: "R0019",
+		code: "FDA.R0019",
+		element: "C.2.r.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.2.r.3 must be provided when C.2.r.5 = 1",
+	},
+	FdaRuleDefinition {
+		id: "R0020",
+		code: "FDA.R0020",
+		element: "C.2.r.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.2.r.4 must be provided when C.2.r.5 = 1",
+	},
+	FdaRuleDefinition {
+		id: "R0021",
+		code: "FDA.R0021",
+		element: "C.2.r.5",
+		severity: FdaRuleSeverity::Rejection,
+		description: "At least one reporter must be provided with C.2.r.5 = 1",
+	},
+	FdaRuleDefinition {
+		id: "R0022",
+		code: "FDA.R0022",
+		element: "C.3.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.3.2 must be provided when C.3.1 = 1, 2, 3, 4, 5 or 6",
+	},
+	FdaRuleDefinition {
+		id: "R0023",
+		code: "FDA.R0023",
+		element: "C.5.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.5.4 must be provided when C.1.3 =2",
+	},
+	FdaRuleDefinition {
+		id: "R0102",
+		code: "FDA.R0102",
+		element: "C.5.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.5.4 must be 1, 2 or 3 when C.1.3 = 2 and N.2.r.3 = \"CDER_IND\" or \"CBER_IND\" or \"CDER_IND_EXEMPT_BA_BE\" and N.1.4 = \"ZZFDA_PREMKT\"",
+	},
+	FdaRuleDefinition {
+		id: "R0103",
+		code: "FDA.R0103",
+		element: "C.5.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.5.4 must not be provided when C.1.3 = 1 and N.2.r.3 = \"CDER\"",
+	},
+	FdaRuleDefinition {
+		id: "R0104",
+		code: "FDA.R0104",
+		element: "C.5.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.5.4 must be 1 or 2 or 3 when C.1.3 = 2, N.2.r.3 = \"CDER\" and N.1.4 = 'ZZFDA'",
+	},
+	FdaRuleDefinition {
+		id: "R0113",
+		code: "FDA.R0113",
+		element: "C.5.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "C.5.4 must not be provided when N.1.4 = \"ZZFDA_PREMKT\" and N.2.r.3 = 'CDER_IND' or 'CBER_IND' and C.1.3 =1",
+	},
+	FdaRuleDefinition {
+		id: "W0002",
+		code: "FDA.W0002",
+		element: "C.5.4",
+		severity: FdaRuleSeverity::Warning,
+		description: "C.5.4 should be 1 when D.1 = \"AGGREGRATE\"",
+	},
+	FdaRuleDefinition {
+		id: "R0024",
+		code: "FDA.R0024",
+		element: "FDA.C.5.5a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.5a must be provided when C.1.3 = (1 or 2) and N.2.r.3 = \"CDER_IND\" or \"CBER_IND\"",
+	},
+	FdaRuleDefinition {
+		id: "R0107",
+		code: "FDA.R0107",
+		element: "FDA.C.5.5a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.5a must not be provided when N.2.r.3 = \"CDER\" or \"CBER\" and N.1.4 = \"ZZFDA\"",
+	},
+	FdaRuleDefinition {
+		id: "R0105",
+		code: "FDA.R0105",
+		element: "FDA.C.5.5a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.5a is invalid for the Center specified in N.2.r.3",
+	},
+	FdaRuleDefinition {
+		id: "R0025",
+		code: "FDA.R0025",
+		element: "FDA.C.5.5b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.5b must be provided when C.1.3 = 2 and N.2.r.3 = \"CDER_IND_EXEMPT_BA_BE\"",
+	},
+	FdaRuleDefinition {
+		id: "R0108",
+		code: "FDA.R0108",
+		element: "FDA.C.5.5b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.5b must not be provided when N.2.r.3 = \"CDER\" or \"CBER\" and N.1.4 = \"ZZFDA\"",
+	},
+	FdaRuleDefinition {
+		id: "R0106",
+		code: "FDA.R0106",
+		element: "FDA.C.5.5b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.5b is invalid for the Center specified in N.2.r.3",
+	},
+	FdaRuleDefinition {
+		id: "R0026",
+		code: "FDA.R0026",
+		element: "FDA.C.5.6.r",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.6.r is not provided or nullFlavor is not 'NA' when FDA.C.5.5a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0109",
+		code: "FDA.R0109",
+		element: "FDA.C.5.6.r",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.C.5.6.r must not be provided when N.2.r.3 = \"CDER\" or \"CBER\" and N.1.4 = \"ZZFDA\"",
+	},
+	FdaRuleDefinition {
+		id: "W0012",
+		code: "FDA.W0012",
+		element: "FDA.C.5.6.r",
+		severity: FdaRuleSeverity::Warning,
+		description: "FDA.C.5.6.r is invalid for the Center specified in N.2.r.3",
+	},
+	FdaRuleDefinition {
+		id: "R0027",
+		code: "FDA.R0027",
+		element: "D.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.1 must be nullFlavor 'NA' when FDA.C.1.12 = true and FDA.G.k.12.r.1 = true and E.i.2.1b = 10067482",
+	},
+	FdaRuleDefinition {
+		id: "W0010",
+		code: "FDA.W0010",
+		element: "D.1",
+		severity: FdaRuleSeverity::Warning,
+		description: "D.1 value must be \"AGGREGATE\" when C.1.3=2 and C.1.10.r and FDA.C.5.5a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0029",
+		code: "FDA.R0029",
+		element: "D.2.2a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.2.2a must be provided when D.2.2b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0030",
+		code: "FDA.R0030",
+		element: "D.2.2b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.2.2b must be provided when D.2.2a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0031",
+		code: "FDA.R0031",
+		element: "D.2.2.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.2.2.1a must be provided when D.2.2.1b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0032",
+		code: "FDA.R0032",
+		element: "D.2.2.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.2.2.1b must be provided when D.2.2.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0033",
+		code: "FDA.R0033",
+		element: "D.7.1.r.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.7.1.r.1a must be provided when D.7.1.r.1b is provided for D.7.1.r.1b = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0034",
+		code: "FDA.R0034",
+		element: "D.7.1.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.7.1.r.1b must be provided when D.7.1.r.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0035",
+		code: "FDA.R0035",
+		element: "D.7.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.7.2 must be provided when no information is provided in Section D.7.1",
+	},
+	FdaRuleDefinition {
+		id: "R0036",
+		code: "FDA.R0036",
+		element: "D.8.r.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.8.r.1 must be provided when any other information is provided in Section D.8.r",
+	},
+	FdaRuleDefinition {
+		id: "R0037",
+		code: "FDA.R0037",
+		element: "D.8.r.6a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.8.r.6a must be provided when D.8.r.6b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0038",
+		code: "FDA.R0038",
+		element: "D.8.r.6b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.8.r.6b must be provided when D.8.r.6a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0039",
+		code: "FDA.R0039",
+		element: "D.8.r.7a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.8.r.7a must be provided when D.8.r.7b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0040",
+		code: "FDA.R0040",
+		element: "D.8.r.7b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.8.r.7b must be provided when D.8.r.7a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0041",
+		code: "FDA.R0041",
+		element: "D.9.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.1 must be provided when C.1.3 = 2 and N.2.r.3 = \"CDER_IND\" or \"CBER_IND\" and E.i.3.2a = ture",
+	},
+	FdaRuleDefinition {
+		id: "R0042",
+		code: "FDA.R0042",
+		element: "D.9.2.r.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.2.r.1a must be provided when D.9.2.r.1b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0043",
+		code: "FDA.R0043",
+		element: "D.9.2.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.2.r.1b must be provided when D.9.2.r.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0044",
+		code: "FDA.R0044",
+		element: "D.9.2.r.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.2.r.2 must be provided when D.9.2.r.1a and D.9.2.r.1b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0045",
+		code: "FDA.R0045",
+		element: "D.9.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.3 must be provided when D.9.1 is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0046",
+		code: "FDA.R0046",
+		element: "D.9.4.r.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.4.r.1a must be provided when D.9.4.r.1b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0047",
+		code: "FDA.R0047",
+		element: "D.9.4.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.4.r.1b must be provided when D.9.4.r.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0048",
+		code: "FDA.R0048",
+		element: "D.9.4.r.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.9.4.r.2 must be provided when D.9.4.r.1a and D.9.4.r.1b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0049",
+		code: "FDA.R0049",
+		element: "D.10.2.2a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.2.2a must be provided when D.10.2.2b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0050",
+		code: "FDA.R0050",
+		element: "D.10.2.2b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.2.2b must be provided when D.10.2.2a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0051",
+		code: "FDA.R0051",
+		element: "D.10.6",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.6 must be provided when any information is provided in Section D.10",
+	},
+	FdaRuleDefinition {
+		id: "R0052",
+		code: "FDA.R0052",
+		element: "D.10.7.1.r.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.7.1.r.1a must be provided when D.10.7.1.r.1b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0053",
+		code: "FDA.R0053",
+		element: "D.10.7.1.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.7.1.r.1b must be provided when D.10.7.1.r.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0054",
+		code: "FDA.R0054",
+		element: "D.10.8.r.6a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.8.r.6a must be provided when D.10.8.r.6b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0055",
+		code: "FDA.R0055",
+		element: "D.10.8.r.6b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.8.r.6b must be provided when D.10.8.r.6a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0056",
+		code: "FDA.R0056",
+		element: "D.10.8.r.7a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.8.r.7a must be provided when D.10.8.r.7b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0057",
+		code: "FDA.R0057",
+		element: "D.10.8.r.7b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "D.10.8.r.7b must be provided when D.10.8.r.7a is provided",
+	},
+	FdaRuleDefinition {
+		id: "W0003",
+		code: "FDA.W0003",
+		element: "FDA.D.11.r",
+		severity: FdaRuleSeverity::Warning,
+		description: "FDA.D.11.r should be provided as nullFlavor \"NA\" when D.1 is provided as nullFlavor \"NA\" or \"SUMMARY\" or \"AGGREGRATE\"",
+	},
+	FdaRuleDefinition {
+		id: "W0004",
+		code: "FDA.W0004",
+		element: "FDA.D.12",
+		severity: FdaRuleSeverity::Warning,
+		description: "FDA.D.12 should be provided as nullFlavor \"NA\" when D.1 is provided as nullFlavor \"NA\" or \"SUMMARY\" or \"AGGREGRATE\"",
+	},
+	FdaRuleDefinition {
+		id: "R0058",
+		code: "FDA.R0058",
+		element: "E.i.1.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "E.i.1.1b must be provided when E.i.1.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0059",
+		code: "FDA.R0059",
+		element: "E.i.6a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "E.i.6a must be provided when E.i.6b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0060",
+		code: "FDA.R0060",
+		element: "E.i.6b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "E.i.6b must be provided when E.i.6a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0061",
+		code: "FDA.R0061",
+		element: "F.r.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "F.r.1 must be provided when any of the data element in F.r.2 is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0062",
+		code: "FDA.R0062",
+		element: "F.r.2.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "F.r.2.1 must be provided when F.r.1 is provided and F.r.2.2b is not provided",
+	},
+	FdaRuleDefinition {
+		id: "R0063",
+		code: "FDA.R0063",
+		element: "F.r.2.2a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "F.r.2.2a must be provided when F.r.2.2b is provided with value = <value>",
+	},
+	FdaRuleDefinition {
+		id: "R0064",
+		code: "FDA.R0064",
+		element: "F.r.2.2b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "F.r.2.2b must be provided when F.r.1 is provided and F.r.2.1 is not provided",
+	},
+	FdaRuleDefinition {
+		id: "R0065",
+		code: "FDA.R0065",
+		element: "F.r.3.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "At least one of test results (F.r.3.1, F.r.3.2, F.r.3.4) must contain a value when F.r.2.2b is provided.",
+	},
+	FdaRuleDefinition {
+		id: "R0066",
+		code: "FDA.R0066",
+		element: "F.r.3.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "At least one of test results (F.r.3.1, F.r.3.2, F.r.3.4) must contain a value when F.r.2.2b is provided.",
+	},
+	FdaRuleDefinition {
+		id: "R0067",
+		code: "FDA.R0067",
+		element: "F.r.3.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "F.r.3.3 must be provided when F.r.3.2 is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0068",
+		code: "FDA.R0068",
+		element: "F.r.3.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "At least one of test results (F.r.3.1, F.r.3.2, F.r.3.4) must contain a value when F.r.2.2b is provided.",
+	},
+	FdaRuleDefinition {
+		id: "W0005",
+		code: "FDA.W0005",
+		element: "G.k.1",
+		severity: FdaRuleSeverity::Warning,
+		description: "The first product reported does not have G.k.1 with Observation Code Value of 1, 3 or 4.",
+	},
+	FdaRuleDefinition {
+		id: "R0069",
+		code: "FDA.R0069",
+		element: "G.k.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.1 must be provided with Observation Code Value of 1, 3 or 4 when N.2.r.3 = \"CDER\"",
+	},
+	FdaRuleDefinition {
+		id: "R0070",
+		code: "FDA.R0070",
+		element: "G.k.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.1 must be provided with Observation Code Value of 1 or 3 when C.1.3 = 2 and N.2.r.3 = \"CDER_IND\" or \"CBER_IND\"",
+	},
+	FdaRuleDefinition {
+		id: "R0071",
+		code: "FDA.R0071",
+		element: "G.k.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.1 must be provided with Observation Code Value of 1, 3 or 4 when C.1.3 = 2 and N.2.r.3 = \"CDER_EXEMPT_BA_BE\"",
+	},
+	FdaRuleDefinition {
+		id: "R0072",
+		code: "FDA.R0072",
+		element: "FDA.G.k.1.a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.1.a must be provided when FDA.C.1.12 = true and FDA.G.k.12.r. = true' and G.k.1 = 4 is provided",
+	},
+	FdaRuleDefinition {
+		id: "W0009",
+		code: "FDA.W0009",
+		element: "G.k.2.2",
+		severity: FdaRuleSeverity::Warning,
+		description: "G.k.2.2 does not match the registered product name for application# reported in FDA.C.5.5a/FDA.C.5.5b. Ignore this message for comparator study product.",
+	},
+	FdaRuleDefinition {
+		id: "R0073",
+		code: "FDA.R0073",
+		element: "G.k.2.3.r.3b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.2.3.r.3b must be provided when G.k.2.3.r.3a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0114",
+		code: "FDA.R0114",
+		element: "G.k.3.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.K.3.1 should not contain “IND” or “PANDA” number when N.1.4 = “ZZFDA” and N.2.r.3 = \"CDER\" and C.1.3=2 and C.5.4=2 or 3",
+	},
+	FdaRuleDefinition {
+		id: "R0074",
+		code: "FDA.R0074",
+		element: "G.k.3.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.3.2 must be provided when G.k.3.1 is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0075",
+		code: "FDA.R0075",
+		element: "G.k.4.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.4.r.1b must be provided when G.k.4.r.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0076",
+		code: "FDA.R0076",
+		element: "G.k.4.r.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.4.r.3 must be provided when G.k.4.r.2 is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0077",
+		code: "FDA.R0077",
+		element: "G.k.4.r.6a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.4.r.6a must be provided when G.k.4.r.6b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0078",
+		code: "FDA.R0078",
+		element: "G.k.4.r.6b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.4.r.6b must be provided when G.k.4.r.6a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0079",
+		code: "FDA.R0079",
+		element: "G.k.5a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.5a must be provided when G.k.5b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0080",
+		code: "FDA.R0080",
+		element: "G.k.5b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.5b must be provided when G.k.5a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0081",
+		code: "FDA.R0081",
+		element: "G.k.6a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.6a must be provided when G.k.6b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0082",
+		code: "FDA.R0082",
+		element: "G.k.6b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.6b must be provided when G.k.6a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0083",
+		code: "FDA.R0083",
+		element: "G.k.7.r.2a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.7.r.2a must be provided when G.k.7.r.2b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0084",
+		code: "FDA.R0084",
+		element: "G.k.7.r.2b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.7.r.2b must be provided when G.k.7.r.2a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0085",
+		code: "FDA.R0085",
+		element: "G.k.9.i.2.r.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.2.r.1 must be provided when C.1.3 = 2 and FDA.C.5.5a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0086",
+		code: "FDA.R0086",
+		element: "G.k.9.i.2.r.2",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.2.r.2 must be provided when C.1.3 = 2 and FDA.C.5.5a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0087",
+		code: "FDA.R0087",
+		element: "G.k.9.i.2.r.3",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.2.r.3 must be provided when C.1.3 = 2 and FDA.C.5.5a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0088",
+		code: "FDA.R0088",
+		element: "G.k.9.i.3.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.3.1a must be provided when G.k.9.i.3.1b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0089",
+		code: "FDA.R0089",
+		element: "G.k.9.i.3.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.3.1b must be provided when G.k.9.i.3.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0090",
+		code: "FDA.R0090",
+		element: "G.k.9.i.3.2a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.3.2a must be provided when G.k.9.i.3.2b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0091",
+		code: "FDA.R0091",
+		element: "G.k.9.i.3.2b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "G.k.9.i.3.2b must be provided when G.k.9.i.3.2a is provided",
+	},
+	FdaRuleDefinition {
+		id: "W0006",
+		code: "FDA.W0006",
+		element: "FDA.G.k.10a",
+		severity: FdaRuleSeverity::Warning,
+		description: "FDA.G.k.10a should be provided with Observation Code value of 1, 2 or nullFlavor \"NA\" when FDA.C.5.5b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0092",
+		code: "FDA.R0092",
+		element: "FDA.G.k.12.r.1",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.G.k.12.r.1 must be 'true' for at least one suspect product when FDA.C.1.7.1 = 5",
+	},
+	FdaRuleDefinition {
+		id: "R0093",
+		code: "FDA.R0093",
+		element: "FDA.G.k.12.r.3.r",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.G.k.12.r.3.r must be provided when FDA.G.k.12.r.1 = true",
+	},
+	FdaRuleDefinition {
+		id: "W0008",
+		code: "FDA.W0008",
+		element: "FDA.G.k.12.r.3.r",
+		severity: FdaRuleSeverity::Warning,
+		description: "FDA.G.k.12.r.3.r contain invalid value",
+	},
+	FdaRuleDefinition {
+		id: "R0094",
+		code: "FDA.R0094",
+		element: "FDA.G.k.12.r.4",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.G.k.12.r.4 and/or FDA.G.k.12.r.5 must be provided when FDA.C.1.12 = true",
+	},
+	FdaRuleDefinition {
+		id: "R0095",
+		code: "FDA.R0095",
+		element: "FDA.G.k.12.r.5",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.G.k.12.r.4 and/or FDA.G.k.12.r.5 must be provided when FDA.C.1.12 = true",
+	},
+	FdaRuleDefinition {
+		id: "R0096",
+		code: "FDA.R0096",
+		element: "FDA.G.k.12.r.6",
+		severity: FdaRuleSeverity::Rejection,
+		description: "FDA.G.k.12.r.6 must be provided when FDA.G.k.12.r.4 and FDA.G.k.12.r.5 are not provided",
+	},
+	FdaRuleDefinition {
+		id: "W0007",
+		code: "FDA.W0007",
+		element: "FDA.G.k.12.r.11.r",
+		severity: FdaRuleSeverity::Warning,
+		description: "FDA.G.k.12.r.11.r should be provided when FDA.G.k.12.r.1 = true and FDA.C.1.7.1 = 4 (5-Day)",
+	},
+	FdaRuleDefinition {
+		id: "R0097",
+		code: "FDA.R0097",
+		element: "H.3.r.1a",
+		severity: FdaRuleSeverity::Rejection,
+		description: "H.3.r.1a must be provided when H.3.r.1b is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0098",
+		code: "FDA.R0098",
+		element: "H.3.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "H.3.r.1b must be provided when H.3.r.1a is provided",
+	},
+	FdaRuleDefinition {
+		id: "R0099",
+		code: "FDA.R0099",
+		element: "H.5.r.1b",
+		severity: FdaRuleSeverity::Rejection,
+		description: "H.5.r.1b must be provided when H.5.r.1a is provided",
+	},
+];
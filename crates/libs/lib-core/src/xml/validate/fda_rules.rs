@@ -0,0 +1,1503 @@
+// FDA rejection/warning rules evaluated over a batch view; the XML and case
+// validators each build the view from their own source.
+
+use super::fda_rule_table::{
+	find_fda_rule, FdaRuleDefinition, FdaRuleSeverity, FDA_REJECTION_WARNING_RULES,
+};
+use super::has_text;
+use serde::Serialize;
+
+/// Source a batch view was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdaRuleScope {
+	Xml,
+	Case,
+}
+
+/// Rules that need the FDA application registry (registered product names and
+/// the center an IND or pre-ANDA number belongs to).
+pub const FDA_RULES_NOT_EVALUATED: &[&str] = &["R0105", "R0106", "W0009", "W0012"];
+
+/// Rules over data the case tables do not store: the C.1.6.1 and C.1.9.1
+/// flags, C.5.5a/b and C.5.6.r, nullFlavors of D.1, D.11 and D.12, free-text
+/// causes of death, G.k.1.a, G.k.3, G.k.5, G.k.6, G.k.9.i.3.2 and device names
+/// and product codes.
+pub const FDA_RULES_XML_ONLY: &[&str] = &[
+	"R0008", "R0009", "R0017", "R0024", "R0025", "R0026", "R0027", "R0044", "R0048",
+	"R0072", "R0074", "R0079", "R0080", "R0081", "R0082", "R0085", "R0086", "R0087",
+	"R0090", "R0091", "R0094", "R0095", "R0096", "R0107", "R0108", "R0109", "R0110",
+	"R0114", "W0003", "W0004", "W0006", "W0010",
+];
+
+/// Rules that need to know whether the report is the initial one, which the
+/// ICSR itself does not state.
+pub const FDA_RULES_CASE_ONLY: &[&str] = &["R0011", "R0101"];
+
+const POSTMARKET_RECEIVERS: &[&str] = &["CDER", "CBER"];
+const IND_RECEIVERS: &[&str] = &["CDER_IND", "CBER_IND"];
+const PREMARKET_RECEIVERS: &[&str] =
+	&["CDER_IND", "CBER_IND", "CDER_IND_EXEMPT_BA_BE"];
+const EXEMPT_BA_BE_RECEIVER: &str = "CDER_IND_EXEMPT_BA_BE";
+/// E.i.2.1b of a device malfunction without an adverse event
+const NO_ADVERSE_EVENT_MEDDRA_CODE: &str = "10067482";
+
+pub fn is_fda_rule_evaluated(id: &str, scope: FdaRuleScope) -> bool {
+	if FDA_RULES_NOT_EVALUATED.contains(&id) {
+		return false;
+	}
+	match scope {
+		FdaRuleScope::Xml => !FDA_RULES_CASE_ONLY.contains(&id),
+		FdaRuleScope::Case => !FDA_RULES_XML_ONLY.contains(&id),
+	}
+}
+
+// -- Batch view
+
+/// A value that may be sent as a nullFlavor instead.
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleValue {
+	pub value: Option<String>,
+	pub null_flavor: Option<String>,
+}
+
+impl FdaRuleValue {
+	pub fn new(value: Option<String>) -> Self {
+		Self {
+			value,
+			null_flavor: None,
+		}
+	}
+
+	fn get(&self) -> Option<&str> {
+		self.value
+			.as_deref()
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+	}
+
+	fn has_value(&self) -> bool {
+		self.get().is_some()
+	}
+
+	/// Value or nullFlavor present
+	fn provided(&self) -> bool {
+		self.has_value() || has_text(self.null_flavor.as_deref())
+	}
+
+	fn is_null_flavor(&self, null_flavor: &str) -> bool {
+		self.null_flavor.as_deref().map(str::trim) == Some(null_flavor)
+	}
+
+	fn is_true(&self) -> bool {
+		self.get()
+			.is_some_and(|value| value.eq_ignore_ascii_case("true") || value == "1")
+	}
+
+	fn is_false_or_ni(&self) -> bool {
+		self.get()
+			.is_some_and(|value| value.eq_ignore_ascii_case("false") || value == "0")
+			|| self.is_null_flavor("NI")
+	}
+}
+
+/// Number/unit or MedDRA version/code; `a` and `b` follow the element suffixes.
+#[derive(Debug, Clone, Default)]
+pub struct FdaRulePair {
+	pub a: Option<String>,
+	pub b: Option<String>,
+}
+
+impl FdaRulePair {
+	pub fn new(a: Option<String>, b: Option<String>) -> Self {
+		Self { a, b }
+	}
+
+	fn has_a(&self) -> bool {
+		has_text(self.a.as_deref())
+	}
+
+	fn has_b(&self) -> bool {
+		has_text(self.b.as_deref())
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleBatch {
+	/// N.1.3
+	pub batch_sender: Option<String>,
+	/// N.1.4
+	pub batch_receiver: Option<String>,
+	pub reports: Vec<FdaRuleReport>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleReport {
+	/// N.2.r.2
+	pub message_sender: Option<String>,
+	/// N.2.r.3
+	pub message_receiver: Option<String>,
+	/// Whether this is the initial report of the case, if known
+	pub initial: Option<bool>,
+	/// C.1.3
+	pub report_type: Option<String>,
+	/// C.1.6.1
+	pub documents_available: FdaRuleValue,
+	/// C.1.6.1.r
+	pub documents: Vec<FdaRuleDocument>,
+	/// C.1.7
+	pub expedited: FdaRuleValue,
+	/// FDA.C.1.7.1
+	pub local_criteria_report_type: Option<String>,
+	/// FDA.C.1.12
+	pub combination_product: FdaRuleValue,
+	/// C.1.9.1
+	pub other_identifiers_available: FdaRuleValue,
+	/// C.1.9.1.r.1 (a) and C.1.9.1.r.2 (b)
+	pub other_identifiers: Vec<FdaRulePair>,
+	/// Number of C.1.10.r
+	pub linked_reports: usize,
+	/// C.1.11.1
+	pub nullification_code: Option<String>,
+	pub primary_sources: Vec<FdaRulePrimarySource>,
+	/// C.3.1
+	pub sender_type: Option<String>,
+	/// C.3.2
+	pub sender_organization: Option<String>,
+	/// C.5.4
+	pub study_type: Option<String>,
+	/// FDA.C.5.5a
+	pub ind_number: Option<String>,
+	/// FDA.C.5.5b
+	pub pre_anda_number: Option<String>,
+	/// FDA.C.5.6.r
+	pub cross_reported_inds: Vec<FdaRuleValue>,
+	pub patient: FdaRulePatient,
+	pub reactions: Vec<FdaRuleReaction>,
+	pub tests: Vec<FdaRuleTest>,
+	pub drugs: Vec<FdaRuleDrug>,
+	/// H.3.r.1a (a) and H.3.r.1b (b)
+	pub diagnoses: Vec<FdaRulePair>,
+	/// H.5.r.1a (a) and H.5.r.1b (b)
+	pub summaries: Vec<FdaRulePair>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleDocument {
+	pub title: Option<String>,
+	pub representation: Option<String>,
+	pub compression: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRulePrimarySource {
+	/// C.2.r.3
+	pub country: Option<String>,
+	/// C.2.r.4
+	pub qualification: Option<String>,
+	/// C.2.r.5
+	pub regulatory: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRulePatient {
+	/// D.1
+	pub name: FdaRuleValue,
+	/// D.2.2a/b
+	pub age: FdaRulePair,
+	/// D.2.2.1a/b
+	pub gestation: FdaRulePair,
+	/// Whether Section D.7 is present at all
+	pub medical_history_provided: bool,
+	/// D.7.1.r.1a/b
+	pub medical_history: Vec<FdaRulePair>,
+	/// D.7.2
+	pub medical_history_text: Option<String>,
+	/// D.8.r
+	pub past_drugs: Vec<FdaRulePastDrug>,
+	/// D.9.1
+	pub death_date: FdaRuleValue,
+	/// D.9.2.r
+	pub reported_causes: Vec<FdaRuleCause>,
+	/// D.9.3
+	pub autopsy: FdaRuleValue,
+	/// D.9.4.r
+	pub autopsy_causes: Vec<FdaRuleCause>,
+	/// D.10
+	pub parent: Option<FdaRuleParent>,
+	/// FDA.D.11.r
+	pub race: Vec<FdaRuleValue>,
+	/// FDA.D.12
+	pub ethnicity: FdaRuleValue,
+}
+
+impl FdaRulePatient {
+	fn name_is(&self, value: &str) -> bool {
+		self.name
+			.get()
+			.is_some_and(|name| name.eq_ignore_ascii_case(value))
+	}
+
+	fn is_aggregate(&self) -> bool {
+		self.name_is("AGGREGATE")
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRulePastDrug {
+	/// D.8.r.1 / D.10.8.r.1
+	pub name: Option<String>,
+	/// Any other element of the past drug
+	pub other_data: bool,
+	/// .r.6a/b
+	pub indication: FdaRulePair,
+	/// .r.7a/b
+	pub reaction: FdaRulePair,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleCause {
+	/// .r.1a/b
+	pub meddra: FdaRulePair,
+	/// .r.2
+	pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleParent {
+	/// D.10.2.2a/b
+	pub age: FdaRulePair,
+	/// D.10.6
+	pub sex: FdaRuleValue,
+	/// D.10.7.1.r.1a/b
+	pub medical_history: Vec<FdaRulePair>,
+	/// D.10.8.r
+	pub past_drugs: Vec<FdaRulePastDrug>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleReaction {
+	/// E.i.1.1a
+	pub term: Option<String>,
+	/// E.i.1.1b
+	pub language: Option<String>,
+	/// E.i.2.1b
+	pub meddra_code: Option<String>,
+	/// E.i.3.2a
+	pub death: bool,
+	/// E.i.6a/b
+	pub duration: FdaRulePair,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleTest {
+	/// F.r.1
+	pub date: Option<String>,
+	/// F.r.2.1
+	pub name: Option<String>,
+	/// F.r.2.2a/b
+	pub meddra: FdaRulePair,
+	/// F.r.3.1
+	pub result_code: Option<String>,
+	/// F.r.3.2
+	pub result_value: Option<String>,
+	/// F.r.3.3
+	pub result_unit: Option<String>,
+	/// F.r.3.4
+	pub result_unstructured: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleDrug {
+	/// G.k.1
+	pub characterization: Option<String>,
+	/// FDA.G.k.1.a
+	pub other_characterization: Option<String>,
+	/// G.k.2.3.r.3a/b
+	pub substance_strengths: Vec<FdaRulePair>,
+	/// G.k.3.1
+	pub authorisation_number: Option<String>,
+	/// G.k.3.2
+	pub authorisation_country: Option<String>,
+	pub dosages: Vec<FdaRuleDosage>,
+	/// G.k.5a/b
+	pub cumulative_dose: FdaRulePair,
+	/// G.k.6a/b
+	pub gestation: FdaRulePair,
+	/// G.k.7.r.2a/b
+	pub indications: Vec<FdaRulePair>,
+	/// G.k.9.i.2.r
+	pub assessments: Vec<FdaRuleAssessment>,
+	/// G.k.9.i.3.1a/b
+	pub start_intervals: Vec<FdaRulePair>,
+	/// G.k.9.i.3.2a/b
+	pub last_dose_intervals: Vec<FdaRulePair>,
+	/// FDA.G.k.10a
+	pub additional_info: Vec<FdaRuleValue>,
+	/// FDA.G.k.12.r
+	pub devices: Vec<FdaRuleDevice>,
+}
+
+impl FdaRuleDrug {
+	fn characterization_in(&self, codes: &[&str]) -> bool {
+		self.characterization
+			.as_deref()
+			.is_some_and(|code| codes.contains(&code.trim()))
+	}
+
+	fn has_malfunction(&self) -> bool {
+		self.devices
+			.iter()
+			.any(|device| device.malfunction.is_true())
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleDosage {
+	/// G.k.4.r.1a/b
+	pub dose: FdaRulePair,
+	/// G.k.4.r.2 (a) and G.k.4.r.3 (b)
+	pub frequency: FdaRulePair,
+	/// G.k.4.r.6a/b
+	pub duration: FdaRulePair,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleAssessment {
+	/// G.k.9.i.2.r.1
+	pub source: Option<String>,
+	/// G.k.9.i.2.r.2
+	pub method: Option<String>,
+	/// G.k.9.i.2.r.3
+	pub result: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FdaRuleDevice {
+	/// FDA.G.k.12.r.1
+	pub malfunction: FdaRuleValue,
+	/// FDA.G.k.12.r.3.r
+	pub problem_codes: Vec<String>,
+	/// FDA.G.k.12.r.4
+	pub brand_name: FdaRuleValue,
+	/// FDA.G.k.12.r.5
+	pub common_name: FdaRuleValue,
+	/// FDA.G.k.12.r.6
+	pub product_code: Option<String>,
+	/// Number of FDA.G.k.12.r.11.r
+	pub remedial_actions: usize,
+}
+
+// -- Results
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FdaRuleHit {
+	pub rule: &'static FdaRuleDefinition,
+	/// Index of the report in the batch
+	pub report: usize,
+	pub path: String,
+	/// Offending value, shown in place of `<value>` in the description
+	pub value: Option<String>,
+}
+
+impl FdaRuleHit {
+	pub fn message(&self) -> String {
+		match self.value.as_deref() {
+			Some(value) if self.rule.description.contains("<value>") => {
+				self.rule.description.replace("<value>", value)
+			}
+			_ => self.rule.description.to_string(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FdaRuleCoverageEntry {
+	pub id: &'static str,
+	pub element: &'static str,
+	pub severity: FdaRuleSeverity,
+	pub description: &'static str,
+	/// Evaluated when validating ICSR XML
+	pub xml: bool,
+	/// Evaluated when validating a stored case
+	pub case: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FdaRuleCoverage {
+	pub total: usize,
+	pub implemented: usize,
+	/// Error IDs that no validator evaluates
+	pub missing: Vec<&'static str>,
+	pub rules: Vec<FdaRuleCoverageEntry>,
+}
+
+pub fn fda_rule_coverage() -> FdaRuleCoverage {
+	let rules: Vec<FdaRuleCoverageEntry> = FDA_REJECTION_WARNING_RULES
+		.iter()
+		.map(|rule| FdaRuleCoverageEntry {
+			id: rule.id,
+			element: rule.element,
+			severity: rule.severity,
+			description: rule.description,
+			xml: is_fda_rule_evaluated(rule.id, FdaRuleScope::Xml),
+			case: is_fda_rule_evaluated(rule.id, FdaRuleScope::Case),
+		})
+		.collect();
+	let missing: Vec<&'static str> = rules
+		.iter()
+		.filter(|rule| !rule.xml && !rule.case)
+		.map(|rule| rule.id)
+		.collect();
+	FdaRuleCoverage {
+		total: rules.len(),
+		implemented: rules.len() - missing.len(),
+		missing,
+		rules,
+	}
+}
+
+// -- Evaluation
+
+struct RuleHits {
+	scope: FdaRuleScope,
+	report: usize,
+	hits: Vec<FdaRuleHit>,
+}
+
+impl RuleHits {
+	fn fire(&mut self, id: &str, path: impl Into<String>, value: Option<&str>) {
+		if !is_fda_rule_evaluated(id, self.scope) {
+			return;
+		}
+		let Some(rule) = find_fda_rule(id) else {
+			debug_assert!(false, "unknown FDA rule {id}");
+			return;
+		};
+		self.hits.push(FdaRuleHit {
+			rule,
+			report: self.report,
+			path: path.into(),
+			value: value.map(|value| value.trim().to_string()),
+		});
+	}
+
+	fn fire_if(
+		&mut self,
+		violated: bool,
+		id: &str,
+		path: impl Into<String>,
+		value: Option<&str>,
+	) {
+		if violated {
+			self.fire(id, path, value);
+		}
+	}
+
+	/// `id_a` when only `b` is given, `id_b` when only `a` is given.
+	fn pair(
+		&mut self,
+		pair: &FdaRulePair,
+		(id_a, path_a): (&str, String),
+		(id_b, path_b): (&str, String),
+	) {
+		self.fire_if(
+			pair.has_b() && !pair.has_a(),
+			id_a,
+			path_a,
+			pair.b.as_deref(),
+		);
+		self.fire_if(
+			pair.has_a() && !pair.has_b(),
+			id_b,
+			path_b,
+			pair.a.as_deref(),
+		);
+	}
+}
+
+fn trimmed(value: Option<&str>) -> &str {
+	value.map(str::trim).unwrap_or("")
+}
+
+fn is_one_of(value: Option<&str>, codes: &[&str]) -> bool {
+	codes.contains(&trimmed(value))
+}
+
+/// FDA.G.k.12.r.3.r: FDA device problem codes, up to 7 alphanumerics.
+fn is_device_problem_code(code: &str) -> bool {
+	let code = code.trim();
+	!code.is_empty()
+		&& code.len() <= 7
+		&& code.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Evaluates the rules of `scope` over every report of the batch.
+pub fn check_fda_rules(
+	batch: &FdaRuleBatch,
+	scope: FdaRuleScope,
+) -> Vec<FdaRuleHit> {
+	let mut hits = RuleHits {
+		scope,
+		report: 0,
+		hits: Vec::new(),
+	};
+	let first_sender = batch
+		.reports
+		.first()
+		.map(|report| trimmed(report.message_sender.as_deref()));
+	for (idx, report) in batch.reports.iter().enumerate() {
+		hits.report = idx;
+		check_routing(&mut hits, batch, report, first_sender.unwrap_or(""));
+		check_report(&mut hits, batch, report);
+		check_patient(&mut hits, report);
+		check_reactions_and_tests(&mut hits, report);
+		check_drugs(&mut hits, batch, report);
+		check_narrative(&mut hits, report);
+	}
+	hits.hits
+}
+
+/// Section N
+fn check_routing(
+	hits: &mut RuleHits,
+	batch: &FdaRuleBatch,
+	report: &FdaRuleReport,
+	first_sender: &str,
+) {
+	let batch_receiver = trimmed(batch.batch_receiver.as_deref());
+	let receiver = trimmed(report.message_receiver.as_deref());
+	let receiver_path = "messageHeader.messageReceiverIdentifier";
+	let batch_path = "messageHeader.batchReceiverIdentifier";
+
+	hits.fire_if(
+		POSTMARKET_RECEIVERS.contains(&receiver) && batch_receiver != "ZZFDA",
+		"R0004",
+		batch_path,
+		Some(batch_receiver),
+	);
+	hits.fire_if(
+		PREMARKET_RECEIVERS.contains(&receiver) && batch_receiver != "ZZFDA_PREMKT",
+		"R0005",
+		batch_path,
+		Some(batch_receiver),
+	);
+	hits.fire_if(
+		batch_receiver == "ZZFDA" && receiver != "CDER",
+		"R0006",
+		receiver_path,
+		Some(receiver),
+	);
+	hits.fire_if(
+		batch_receiver == "ZZFDA_PREMKT" && !PREMARKET_RECEIVERS.contains(&receiver),
+		"R0007",
+		receiver_path,
+		Some(receiver),
+	);
+
+	let sender = trimmed(report.message_sender.as_deref());
+	hits.fire_if(
+		sender != trimmed(batch.batch_sender.as_deref()) || sender != first_sender,
+		"R0100",
+		"messageHeader.messageSenderIdentifier",
+		Some(sender),
+	);
+}
+
+/// Section C
+fn check_report(hits: &mut RuleHits, batch: &FdaRuleBatch, report: &FdaRuleReport) {
+	let batch_receiver = trimmed(batch.batch_receiver.as_deref());
+	let premarket_batch = batch_receiver == "ZZFDA_PREMKT";
+	let postmarket = batch_receiver == "ZZFDA";
+	let receiver = trimmed(report.message_receiver.as_deref());
+	let ind_receiver = IND_RECEIVERS.contains(&receiver);
+	let premarket_receiver = PREMARKET_RECEIVERS.contains(&receiver);
+	let postmarket_receiver = POSTMARKET_RECEIVERS.contains(&receiver);
+	let report_type = trimmed(report.report_type.as_deref());
+	let study_type = report.study_type.as_deref();
+	let has_study_type = has_text(study_type);
+	let study_type_1_2_3 = is_one_of(study_type, &["1", "2", "3"]);
+	let has_ind = has_text(report.ind_number.as_deref());
+	let has_pre_anda = has_text(report.pre_anda_number.as_deref());
+	let initial = report.initial == Some(true);
+
+	// C.1.3
+	let report_type_path = "safetyReportIdentification.reportType";
+	hits.fire_if(
+		premarket_batch
+			&& premarket_receiver
+			&& (has_ind || has_pre_anda)
+			&& study_type_1_2_3
+			&& report_type != "2",
+		"R0008",
+		report_type_path,
+		Some(report_type),
+	);
+	hits.fire_if(
+		premarket_batch
+			&& ind_receiver
+			&& has_ind
+			&& !has_study_type
+			&& report_type != "1",
+		"R0110",
+		report_type_path,
+		Some(report_type),
+	);
+	hits.fire_if(
+		premarket_batch && receiver == EXEMPT_BA_BE_RECEIVER && report_type != "2",
+		"R0111",
+		report_type_path,
+		Some(report_type),
+	);
+	hits.fire_if(
+		premarket_batch && ind_receiver && matches!(report_type, "3" | "4"),
+		"R0112",
+		report_type_path,
+		Some(report_type),
+	);
+
+	// C.1.6.1.r
+	if report.documents_available.is_true() {
+		hits.fire_if(
+			report.documents.is_empty(),
+			"R0009",
+			"documentsHeldBySender",
+			None,
+		);
+		for (idx, document) in report.documents.iter().enumerate() {
+			hits.fire_if(
+				!has_text(document.title.as_deref()),
+				"R0009",
+				format!("documentsHeldBySender.{idx}.title"),
+				None,
+			);
+		}
+	}
+	for (idx, document) in report.documents.iter().enumerate() {
+		hits.fire_if(
+			has_text(document.compression.as_deref())
+				&& trimmed(document.representation.as_deref()) != "B64",
+			"R0010",
+			format!("documentsHeldBySender.{idx}.representation"),
+			document.representation.as_deref(),
+		);
+	}
+
+	// C.1.7 and FDA.C.1.7.1
+	hits.fire_if(
+		initial && report.expedited.is_null_flavor("NI"),
+		"R0011",
+		"safetyReportIdentification.fulfilExpeditedCriteria",
+		None,
+	);
+	let local_criteria = report.local_criteria_report_type.as_deref();
+	let local_criteria_path = "safetyReportIdentification.localCriteriaReportType";
+	let combination = &report.combination_product;
+	let expedited = &report.expedited;
+	for (violated, id) in [
+		(
+			combination.is_true()
+				&& expedited.is_true()
+				&& !is_one_of(local_criteria, &["1", "4"]),
+			"R0012",
+		),
+		(
+			combination.is_true()
+				&& expedited.is_false_or_ni()
+				&& !is_one_of(local_criteria, &["2", "5"]),
+			"R0013",
+		),
+		(
+			combination.is_false_or_ni()
+				&& expedited.is_true()
+				&& !is_one_of(local_criteria, &["1"]),
+			"R0014",
+		),
+		(
+			combination.is_false_or_ni()
+				&& expedited.is_false_or_ni()
+				&& !is_one_of(local_criteria, &["2"]),
+			"R0015",
+		),
+		(
+			expedited.is_true()
+				&& matches!(report_type, "1" | "2")
+				&& !is_one_of(local_criteria, &["1", "6"]),
+			"R0016",
+		),
+	] {
+		hits.fire_if(violated, id, local_criteria_path, local_criteria);
+	}
+
+	// C.1.9.1.r, C.1.10.r, C.1.11.1
+	hits.fire_if(
+		report.other_identifiers_available.is_true()
+			&& report.other_identifiers.is_empty(),
+		"R0017",
+		"otherCaseIdentifiers",
+		None,
+	);
+	for (idx, identifier) in report.other_identifiers.iter().enumerate() {
+		hits.fire_if(
+			identifier.has_a() && !identifier.has_b(),
+			"R0018",
+			format!("otherCaseIdentifiers.{idx}.caseIdentifier"),
+			identifier.a.as_deref(),
+		);
+	}
+	hits.fire_if(
+		report.patient.is_aggregate() && report.linked_reports == 0,
+		"W0001",
+		"linkedReportNumbers",
+		None,
+	);
+	hits.fire_if(
+		initial && is_one_of(report.nullification_code.as_deref(), &["1", "2"]),
+		"R0101",
+		"safetyReportIdentification.nullificationCode",
+		report.nullification_code.as_deref(),
+	);
+
+	// C.2.r and C.3
+	for (idx, source) in report.primary_sources.iter().enumerate() {
+		if trimmed(source.regulatory.as_deref()) != "1" {
+			continue;
+		}
+		hits.fire_if(
+			!has_text(source.country.as_deref()),
+			"R0019",
+			format!("primarySources.{idx}.countryCode"),
+			None,
+		);
+		hits.fire_if(
+			!has_text(source.qualification.as_deref()),
+			"R0020",
+			format!("primarySources.{idx}.qualification"),
+			None,
+		);
+	}
+	hits.fire_if(
+		!report
+			.primary_sources
+			.iter()
+			.any(|source| trimmed(source.regulatory.as_deref()) == "1"),
+		"R0021",
+		"primarySources",
+		None,
+	);
+	hits.fire_if(
+		is_one_of(
+			report.sender_type.as_deref(),
+			&["1", "2", "3", "4", "5", "6"],
+		) && !has_text(report.sender_organization.as_deref()),
+		"R0022",
+		"senderInformation.organizationName",
+		None,
+	);
+
+	// C.5.4
+	let study_type_path = "studyInformation.studyTypeReaction";
+	hits.fire_if(
+		report_type == "2" && !has_study_type,
+		"R0023",
+		study_type_path,
+		None,
+	);
+	hits.fire_if(
+		report_type == "2"
+			&& premarket_receiver
+			&& premarket_batch
+			&& !study_type_1_2_3,
+		"R0102",
+		study_type_path,
+		study_type,
+	);
+	hits.fire_if(
+		report_type == "1" && receiver == "CDER" && has_study_type,
+		"R0103",
+		study_type_path,
+		study_type,
+	);
+	hits.fire_if(
+		report_type == "2" && receiver == "CDER" && postmarket && !study_type_1_2_3,
+		"R0104",
+		study_type_path,
+		study_type,
+	);
+	hits.fire_if(
+		premarket_batch && ind_receiver && report_type == "1" && has_study_type,
+		"R0113",
+		study_type_path,
+		study_type,
+	);
+	hits.fire_if(
+		report.patient.is_aggregate() && trimmed(study_type) != "1",
+		"W0002",
+		study_type_path,
+		study_type,
+	);
+
+	// FDA.C.5.5a, FDA.C.5.5b and FDA.C.5.6.r
+	let ind_path = "studyInformation.indNumber";
+	let pre_anda_path = "studyInformation.preAndaNumber";
+	let cross_path = "studyInformation.crossReportedIndNumbers";
+	let postmarket_route = postmarket_receiver && postmarket;
+	hits.fire_if(
+		matches!(report_type, "1" | "2") && ind_receiver && !has_ind,
+		"R0024",
+		ind_path,
+		None,
+	);
+	hits.fire_if(
+		postmarket_route && has_ind,
+		"R0107",
+		ind_path,
+		report.ind_number.as_deref(),
+	);
+	hits.fire_if(
+		report_type == "2" && receiver == EXEMPT_BA_BE_RECEIVER && !has_pre_anda,
+		"R0025",
+		pre_anda_path,
+		None,
+	);
+	hits.fire_if(
+		postmarket_route && has_pre_anda,
+		"R0108",
+		pre_anda_path,
+		report.pre_anda_number.as_deref(),
+	);
+	hits.fire_if(
+		has_ind
+			&& (report.cross_reported_inds.is_empty()
+				|| report
+					.cross_reported_inds
+					.iter()
+					.any(|ind| !ind.has_value() && !ind.is_null_flavor("NA"))),
+		"R0026",
+		cross_path,
+		None,
+	);
+	hits.fire_if(
+		postmarket_route && !report.cross_reported_inds.is_empty(),
+		"R0109",
+		cross_path,
+		None,
+	);
+}
+
+/// Section D
+fn check_patient(hits: &mut RuleHits, report: &FdaRuleReport) {
+	let patient = &report.patient;
+	let name_path = "patientInformation.patientInitials";
+
+	// D.1
+	let malfunction = report.drugs.iter().any(FdaRuleDrug::has_malfunction);
+	let no_adverse_event = report.reactions.iter().any(|reaction| {
+		trimmed(reaction.meddra_code.as_deref()) == NO_ADVERSE_EVENT_MEDDRA_CODE
+	});
+	hits.fire_if(
+		report.combination_product.is_true()
+			&& malfunction
+			&& no_adverse_event
+			&& !patient.name.is_null_flavor("NA"),
+		"R0027",
+		name_path,
+		None,
+	);
+	hits.fire_if(
+		trimmed(report.report_type.as_deref()) == "2"
+			&& report.linked_reports > 0
+			&& has_text(report.ind_number.as_deref())
+			&& !patient.is_aggregate(),
+		"W0010",
+		name_path,
+		patient.name.get(),
+	);
+
+	// D.2.2, D.2.2.1
+	hits.pair(
+		&patient.age,
+		("R0029", "patientInformation.ageAtTimeOfOnset".to_string()),
+		("R0030", "patientInformation.ageUnit".to_string()),
+	);
+	hits.pair(
+		&patient.gestation,
+		("R0031", "patientInformation.gestationPeriod".to_string()),
+		(
+			"R0032",
+			"patientInformation.gestationPeriodUnit".to_string(),
+		),
+	);
+
+	// D.7, D.8
+	for (idx, episode) in patient.medical_history.iter().enumerate() {
+		hits.pair(
+			episode,
+			(
+				"R0033",
+				format!("medicalHistoryEpisodes.{idx}.meddraVersion"),
+			),
+			("R0034", format!("medicalHistoryEpisodes.{idx}.meddraCode")),
+		);
+	}
+	hits.fire_if(
+		patient.medical_history_provided
+			&& patient.medical_history.is_empty()
+			&& !has_text(patient.medical_history_text.as_deref()),
+		"R0035",
+		"patientInformation.medicalHistoryText",
+		None,
+	);
+	check_past_drugs(
+		hits,
+		&patient.past_drugs,
+		"pastDrugHistory",
+		["R0036", "R0037", "R0038", "R0039", "R0040"],
+	);
+
+	// D.9
+	let death_path = "patientDeathInformation";
+	hits.fire_if(
+		trimmed(report.report_type.as_deref()) == "2"
+			&& is_one_of(report.message_receiver.as_deref(), IND_RECEIVERS)
+			&& report.reactions.iter().any(|reaction| reaction.death)
+			&& !patient.death_date.provided(),
+		"R0041",
+		format!("{death_path}.dateOfDeath"),
+		None,
+	);
+	check_causes(
+		hits,
+		&patient.reported_causes,
+		"reportedCausesOfDeath",
+		["R0042", "R0043", "R0044"],
+	);
+	hits.fire_if(
+		patient.death_date.provided() && !patient.autopsy.provided(),
+		"R0045",
+		format!("{death_path}.autopsyPerformed"),
+		None,
+	);
+	check_causes(
+		hits,
+		&patient.autopsy_causes,
+		"autopsyCausesOfDeath",
+		["R0046", "R0047", "R0048"],
+	);
+
+	// D.10
+	if let Some(parent) = patient.parent.as_ref() {
+		hits.pair(
+			&parent.age,
+			("R0049", "parentInformation.parentAge".to_string()),
+			("R0050", "parentInformation.parentAgeUnit".to_string()),
+		);
+		hits.fire_if(
+			!parent.sex.provided(),
+			"R0051",
+			"parentInformation.sex",
+			None,
+		);
+		for (idx, episode) in parent.medical_history.iter().enumerate() {
+			hits.pair(
+				episode,
+				("R0052", format!("parentMedicalHistory.{idx}.meddraVersion")),
+				("R0053", format!("parentMedicalHistory.{idx}.meddraCode")),
+			);
+		}
+		check_past_drugs(
+			hits,
+			&parent.past_drugs,
+			"parentPastDrugHistory",
+			["", "R0054", "R0055", "R0056", "R0057"],
+		);
+	}
+
+	// FDA.D.11.r, FDA.D.12
+	if patient.name.is_null_flavor("NA")
+		|| patient.name_is("SUMMARY")
+		|| patient.is_aggregate()
+	{
+		hits.fire_if(
+			patient.race.is_empty()
+				|| patient.race.iter().any(|race| !race.is_null_flavor("NA")),
+			"W0003",
+			"patientInformation.raceCode",
+			None,
+		);
+		hits.fire_if(
+			!patient.ethnicity.is_null_flavor("NA"),
+			"W0004",
+			"patientInformation.ethnicityCode",
+			None,
+		);
+	}
+}
+
+/// D.8.r or D.10.8.r; `ids` are the rules for .r.1, .r.6a, .r.6b, .r.7a and
+/// .r.7b, an empty ID skips the check.
+fn check_past_drugs(
+	hits: &mut RuleHits,
+	past_drugs: &[FdaRulePastDrug],
+	path: &str,
+	[name_id, indication_a, indication_b, reaction_a, reaction_b]: [&str; 5],
+) {
+	for (idx, drug) in past_drugs.iter().enumerate() {
+		if !name_id.is_empty() {
+			let other_data = drug.other_data
+				|| drug.indication.has_a()
+				|| drug.indication.has_b()
+				|| drug.reaction.has_a()
+				|| drug.reaction.has_b();
+			hits.fire_if(
+				other_data && !has_text(drug.name.as_deref()),
+				name_id,
+				format!("{path}.{idx}.drugName"),
+				None,
+			);
+		}
+		hits.pair(
+			&drug.indication,
+			(
+				indication_a,
+				format!("{path}.{idx}.indicationMeddraVersion"),
+			),
+			(indication_b, format!("{path}.{idx}.indicationMeddraCode")),
+		);
+		hits.pair(
+			&drug.reaction,
+			(reaction_a, format!("{path}.{idx}.reactionMeddraVersion")),
+			(reaction_b, format!("{path}.{idx}.reactionMeddraCode")),
+		);
+	}
+}
+
+/// D.9.2.r or D.9.4.r; `ids` are the rules for .r.1a, .r.1b and .r.2.
+fn check_causes(
+	hits: &mut RuleHits,
+	causes: &[FdaRuleCause],
+	path: &str,
+	[version_id, code_id, text_id]: [&str; 3],
+) {
+	for (idx, cause) in causes.iter().enumerate() {
+		hits.pair(
+			&cause.meddra,
+			(version_id, format!("{path}.{idx}.meddraVersion")),
+			(code_id, format!("{path}.{idx}.meddraCode")),
+		);
+		hits.fire_if(
+			cause.meddra.has_a()
+				&& cause.meddra.has_b()
+				&& !has_text(cause.text.as_deref()),
+			text_id,
+			format!("{path}.{idx}.causeText"),
+			None,
+		);
+	}
+}
+
+/// Sections E and F
+fn check_reactions_and_tests(hits: &mut RuleHits, report: &FdaRuleReport) {
+	for (idx, reaction) in report.reactions.iter().enumerate() {
+		hits.fire_if(
+			has_text(reaction.term.as_deref())
+				&& !has_text(reaction.language.as_deref()),
+			"R0058",
+			format!("reactions.{idx}.reactionLanguage"),
+			None,
+		);
+		hits.pair(
+			&reaction.duration,
+			("R0059", format!("reactions.{idx}.durationValue")),
+			("R0060", format!("reactions.{idx}.durationUnit")),
+		);
+	}
+
+	for (idx, test) in report.tests.iter().enumerate() {
+		let path = format!("testResults.{idx}");
+		let has_date = has_text(test.date.as_deref());
+		let has_name = has_text(test.name.as_deref());
+		let has_code = test.meddra.has_b();
+		hits.fire_if(
+			!has_date && (has_name || test.meddra.has_a() || has_code),
+			"R0061",
+			format!("{path}.testDate"),
+			None,
+		);
+		hits.fire_if(
+			has_date && !has_name && !has_code,
+			"R0062",
+			format!("{path}.testName"),
+			None,
+		);
+		hits.fire_if(
+			has_code && !test.meddra.has_a(),
+			"R0063",
+			format!("{path}.testMeddraVersion"),
+			test.meddra.b.as_deref(),
+		);
+		hits.fire_if(
+			has_date && !has_name && !has_code,
+			"R0064",
+			format!("{path}.testMeddraCode"),
+			None,
+		);
+		if has_code
+			&& !has_text(test.result_code.as_deref())
+			&& !has_text(test.result_value.as_deref())
+			&& !has_text(test.result_unstructured.as_deref())
+		{
+			hits.fire("R0065", format!("{path}.testResultCode"), None);
+			hits.fire("R0066", format!("{path}.testResultValue"), None);
+			hits.fire("R0068", format!("{path}.resultUnstructured"), None);
+		}
+		hits.fire_if(
+			has_text(test.result_value.as_deref())
+				&& !has_text(test.result_unit.as_deref()),
+			"R0067",
+			format!("{path}.testResultUnit"),
+			None,
+		);
+	}
+}
+
+/// Section G
+fn check_drugs(hits: &mut RuleHits, batch: &FdaRuleBatch, report: &FdaRuleReport) {
+	let receiver = trimmed(report.message_receiver.as_deref());
+	let report_type = trimmed(report.report_type.as_deref());
+	let local_criteria = trimmed(report.local_criteria_report_type.as_deref());
+	let drugs = &report.drugs;
+
+	// G.k.1, FDA.G.k.1.a
+	if let Some(first) = drugs.first() {
+		hits.fire_if(
+			!first.characterization_in(&["1", "3", "4"]),
+			"W0005",
+			"drugs.0.drugCharacterization",
+			first.characterization.as_deref(),
+		);
+	}
+	let any_characterized =
+		|codes: &[&str]| drugs.iter().any(|drug| drug.characterization_in(codes));
+	hits.fire_if(
+		receiver == "CDER" && !any_characterized(&["1", "3", "4"]),
+		"R0069",
+		"drugs",
+		None,
+	);
+	hits.fire_if(
+		report_type == "2"
+			&& IND_RECEIVERS.contains(&receiver)
+			&& !any_characterized(&["1", "3"]),
+		"R0070",
+		"drugs",
+		None,
+	);
+	hits.fire_if(
+		report_type == "2"
+			&& receiver == EXEMPT_BA_BE_RECEIVER
+			&& !any_characterized(&["1", "3", "4"]),
+		"R0071",
+		"drugs",
+		None,
+	);
+	let malfunction = drugs.iter().any(FdaRuleDrug::has_malfunction);
+	let gk31_restricted = trimmed(batch.batch_receiver.as_deref()) == "ZZFDA"
+		&& receiver == "CDER"
+		&& report_type == "2"
+		&& is_one_of(report.study_type.as_deref(), &["2", "3"]);
+	let assessments_required =
+		report_type == "2" && has_text(report.ind_number.as_deref());
+
+	for (idx, drug) in drugs.iter().enumerate() {
+		let path = format!("drugs.{idx}");
+		hits.fire_if(
+			report.combination_product.is_true()
+				&& malfunction
+				&& drug.characterization_in(&["4"])
+				&& !has_text(drug.other_characterization.as_deref()),
+			"R0072",
+			format!("{path}.fdaOtherCharacterization"),
+			None,
+		);
+
+		// G.k.2.3.r.3, G.k.3
+		for (sub_idx, strength) in drug.substance_strengths.iter().enumerate() {
+			hits.fire_if(
+				strength.has_a() && !strength.has_b(),
+				"R0073",
+				format!("{path}.activeSubstances.{sub_idx}.strengthUnit"),
+				None,
+			);
+		}
+		let authorisation = trimmed(drug.authorisation_number.as_deref());
+		let authorisation_upper = authorisation.to_ascii_uppercase();
+		hits.fire_if(
+			gk31_restricted
+				&& (authorisation_upper.contains("IND")
+					|| authorisation_upper.contains("PANDA")),
+			"R0114",
+			format!("{path}.authorisationNumber"),
+			Some(authorisation),
+		);
+		hits.fire_if(
+			!authorisation.is_empty()
+				&& !has_text(drug.authorisation_country.as_deref()),
+			"R0074",
+			format!("{path}.authorisationCountry"),
+			None,
+		);
+
+		// G.k.4.r to G.k.7.r
+		for (dose_idx, dosage) in drug.dosages.iter().enumerate() {
+			let dosage_path = format!("{path}.dosages.{dose_idx}");
+			hits.fire_if(
+				dosage.dose.has_a() && !dosage.dose.has_b(),
+				"R0075",
+				format!("{dosage_path}.doseUnit"),
+				None,
+			);
+			hits.fire_if(
+				dosage.frequency.has_a() && !dosage.frequency.has_b(),
+				"R0076",
+				format!("{dosage_path}.frequencyUnit"),
+				None,
+			);
+			hits.pair(
+				&dosage.duration,
+				("R0077", format!("{dosage_path}.durationValue")),
+				("R0078", format!("{dosage_path}.durationUnit")),
+			);
+		}
+		hits.pair(
+			&drug.cumulative_dose,
+			("R0079", format!("{path}.cumulativeDoseValue")),
+			("R0080", format!("{path}.cumulativeDoseUnit")),
+		);
+		hits.pair(
+			&drug.gestation,
+			("R0081", format!("{path}.gestationPeriod")),
+			("R0082", format!("{path}.gestationPeriodUnit")),
+		);
+		for (ind_idx, indication) in drug.indications.iter().enumerate() {
+			hits.pair(
+				indication,
+				(
+					"R0083",
+					format!("{path}.indications.{ind_idx}.indicationMeddraVersion"),
+				),
+				(
+					"R0084",
+					format!("{path}.indications.{ind_idx}.indicationMeddraCode"),
+				),
+			);
+		}
+
+		// G.k.9.i
+		if assessments_required && drug.characterization_in(&["1", "3"]) {
+			if drug.assessments.is_empty() {
+				let assessment_path = format!("{path}.relatednessAssessments");
+				hits.fire("R0085", assessment_path.as_str(), None);
+				hits.fire("R0086", assessment_path.as_str(), None);
+				hits.fire("R0087", assessment_path, None);
+			}
+			for (a_idx, assessment) in drug.assessments.iter().enumerate() {
+				let assessment_path =
+					format!("{path}.relatednessAssessments.{a_idx}");
+				hits.fire_if(
+					!has_text(assessment.source.as_deref()),
+					"R0085",
+					format!("{assessment_path}.sourceOfAssessment"),
+					None,
+				);
+				hits.fire_if(
+					!has_text(assessment.method.as_deref()),
+					"R0086",
+					format!("{assessment_path}.methodOfAssessment"),
+					None,
+				);
+				hits.fire_if(
+					!has_text(assessment.result.as_deref()),
+					"R0087",
+					format!("{assessment_path}.resultOfAssessment"),
+					None,
+				);
+			}
+		}
+		for (i_idx, interval) in drug.start_intervals.iter().enumerate() {
+			let interval_path = format!("{path}.reactionAssessments.{i_idx}");
+			hits.pair(
+				interval,
+				("R0088", format!("{interval_path}.timeIntervalValue")),
+				("R0089", format!("{interval_path}.timeIntervalUnit")),
+			);
+		}
+		for (i_idx, interval) in drug.last_dose_intervals.iter().enumerate() {
+			let interval_path = format!("{path}.reactionAssessments.{i_idx}");
+			hits.pair(
+				interval,
+				("R0090", format!("{interval_path}.lastDoseIntervalValue")),
+				("R0091", format!("{interval_path}.lastDoseIntervalUnit")),
+			);
+		}
+
+		// FDA.G.k.10a
+		hits.fire_if(
+			has_text(report.pre_anda_number.as_deref())
+				&& drug.characterization_in(&["1"])
+				&& !drug.additional_info.iter().any(|info| {
+					is_one_of(info.value.as_deref(), &["1", "2"])
+						|| info.is_null_flavor("NA")
+				}),
+			"W0006",
+			format!("{path}.fdaAdditionalInfoCoded"),
+			None,
+		);
+
+		// FDA.G.k.12.r
+		for (d_idx, device) in drug.devices.iter().enumerate() {
+			let device_path = format!("{path}.devices.{d_idx}");
+			let malfunction = device.malfunction.is_true();
+			hits.fire_if(
+				malfunction && device.problem_codes.is_empty(),
+				"R0093",
+				format!("{device_path}.problemCodes"),
+				None,
+			);
+			for code in &device.problem_codes {
+				hits.fire_if(
+					!is_device_problem_code(code),
+					"W0008",
+					format!("{device_path}.problemCodes"),
+					Some(code),
+				);
+			}
+			if report.combination_product.is_true() {
+				hits.fire_if(
+					!device.brand_name.provided(),
+					"R0094",
+					format!("{device_path}.brandName"),
+					None,
+				);
+				hits.fire_if(
+					!device.common_name.provided(),
+					"R0095",
+					format!("{device_path}.commonName"),
+					None,
+				);
+			}
+			hits.fire_if(
+				!device.brand_name.has_value()
+					&& !device.common_name.has_value()
+					&& !has_text(device.product_code.as_deref()),
+				"R0096",
+				format!("{device_path}.productCode"),
+				None,
+			);
+			hits.fire_if(
+				malfunction && local_criteria == "4" && device.remedial_actions == 0,
+				"W0007",
+				format!("{device_path}.remedialActions"),
+				None,
+			);
+		}
+	}
+	hits.fire_if(
+		local_criteria == "5"
+			&& !drugs.iter().any(|drug| {
+				drug.characterization_in(&["1"]) && drug.has_malfunction()
+			}),
+		"R0092",
+		"drugs",
+		None,
+	);
+}
+
+/// Section H
+fn check_narrative(hits: &mut RuleHits, report: &FdaRuleReport) {
+	for (idx, diagnosis) in report.diagnoses.iter().enumerate() {
+		hits.pair(
+			diagnosis,
+			(
+				"R0097",
+				format!("senderDiagnoses.{idx}.diagnosisMeddraVersion"),
+			),
+			(
+				"R0098",
+				format!("senderDiagnoses.{idx}.diagnosisMeddraCode"),
+			),
+		);
+	}
+	for (idx, summary) in report.summaries.iter().enumerate() {
+		hits.fire_if(
+			summary.has_a() && !summary.has_b(),
+			"R0099",
+			format!("caseSummaries.{idx}.languageCode"),
+			None,
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn postmarket_batch() -> FdaRuleBatch {
+		FdaRuleBatch {
+			batch_sender: Some("SENDER".to_string()),
+			batch_receiver: Some("ZZFDA".to_string()),
+			reports: vec![FdaRuleReport {
+				message_sender: Some("SENDER".to_string()),
+				message_receiver: Some("CDER".to_string()),
+				report_type: Some("1".to_string()),
+				expedited: FdaRuleValue::new(Some("true".to_string())),
+				local_criteria_report_type: Some("1".to_string()),
+				combination_product: FdaRuleValue::new(Some("false".to_string())),
+				primary_sources: vec![FdaRulePrimarySource {
+					country: Some("US".to_string()),
+					qualification: Some("1".to_string()),
+					regulatory: Some("1".to_string()),
+				}],
+				drugs: vec![FdaRuleDrug {
+					characterization: Some("1".to_string()),
+					..FdaRuleDrug::default()
+				}],
+				..FdaRuleReport::default()
+			}],
+		}
+	}
+
+	fn fired(batch: &FdaRuleBatch, scope: FdaRuleScope) -> Vec<&'static str> {
+		check_fda_rules(batch, scope)
+			.iter()
+			.map(|hit| hit.rule.id)
+			.collect()
+	}
+
+	#[test]
+	fn scope_skips_rules_over_data_the_source_lacks() {
+		let mut batch = postmarket_batch();
+		batch.reports[0].ind_number = Some("123456".to_string());
+		batch.reports[0].initial = Some(true);
+		batch.reports[0].nullification_code = Some("1".to_string());
+
+		let xml = fired(&batch, FdaRuleScope::Xml);
+		let case = fired(&batch, FdaRuleScope::Case);
+		assert!(xml.contains(&"R0107") && !xml.contains(&"R0101"));
+		assert!(case.contains(&"R0101") && !case.contains(&"R0107"));
+	}
+
+	#[test]
+	fn pair_rules_report_the_missing_half_with_its_value() {
+		let mut batch = postmarket_batch();
+		batch.reports[0].patient.medical_history =
+			vec![FdaRulePair::new(None, Some("10012345".to_string()))];
+		let hits = check_fda_rules(&batch, FdaRuleScope::Case);
+		let hit = hits
+			.iter()
+			.find(|hit| hit.rule.id == "R0033")
+			.expect("R0033");
+		assert_eq!(hit.path, "medicalHistoryEpisodes.0.meddraVersion");
+		assert!(hit.message().ends_with("D.7.1.r.1b = 10012345"));
+		assert!(!hits.iter().any(|hit| hit.rule.id == "R0034"));
+	}
+}
@@ -3,11 +3,17 @@
 mod c_safety_report_policy;
 mod c_reporter_policy;
 mod catalog;
+mod code_list_policy;
+mod condition_expr;
 mod d_patient_policy;
 mod e_reaction_policy;
 mod f_test_result_policy;
+mod fda_rule_table;
+mod fda_rules;
 mod g_drug_policy;
 mod h_narrative_policy;
+mod rule_set;
+mod unit_policy;
 
 use serde::{Deserialize, Serialize};
 use sqlx::types::Uuid;
@@ -26,6 +32,7 @@ pub use c_reporter_policy::has_any_primary_source_content;
 pub use catalog::{
 	canonical_rules_all, canonical_rules_for_profile, canonical_rules_version,
 	find_canonical_rule, CanonicalRule, ExportDirective, RuleCondition,
+	EMA_RECEIVERS,
 	RuleFacts, ValidationRuleMetadata, VALIDATION_RULES, CANONICAL_RULES,
 	export_directive_for_rule, export_normalization_spec_for_rule,
 	export_xpath_for_rule, export_xpaths_for_rule,
@@ -34,6 +41,17 @@ pub use catalog::{
 	is_rule_condition_satisfied, is_rule_presence_valid, is_rule_value_valid,
 	should_clear_null_flavor_on_value,
 };
+pub use code_list_policy::{
+	is_code_not_in_list, push_issue_if_code_not_in_list, AGE_GROUP,
+	CODE_LIST_INVALID_RULE, DRUG_ACTION, DRUG_CHARACTERIZATION, FDA_ETHNICITY,
+	FDA_RACE, KR_ASSESSMENT_METHOD, KR_ASSESSMENT_RESULT_KOREAN,
+	KR_ASSESSMENT_RESULT_WHO_UMC, KR_HEALTH_PROFESSIONAL_TYPE,
+	JP_REPORTING_CATEGORY, JP_REPORTING_DEADLINE, KR_OTHER_HEALTH_PROFESSIONAL,
+	KR_OTHER_STUDY_TYPE, LOCAL_CRITERIA_REPORT_TYPE,
+	NULLIFICATION_CODE, PRIMARY_SOURCE_REGULATORY, QUALIFICATION, REACTION_OUTCOME,
+	RECHALLENGE, REPORT_TYPE, ROUTE_OF_ADMINISTRATION, SENDER_TYPE, SEX,
+	TEST_RESULT_ASSESSMENT,
+};
 pub use d_patient_policy::{
 	has_fda_ethnicity, has_fda_race, has_patient_initials, has_patient_payload,
 	should_require_fda_ethnicity, should_require_fda_race,
@@ -43,10 +61,31 @@ pub use g_drug_policy::{
 	drug_characterization_display_name, has_drug_characterization,
 	has_medicinal_product, normalize_drug_characterization,
 };
+pub use fda_rule_table::{
+	find_fda_rule, find_fda_rule_by_code, FdaRuleDefinition, FdaRuleSeverity,
+	FDA_REJECTION_WARNING_RULES,
+};
+pub use fda_rules::{
+	check_fda_rules, fda_rule_coverage, is_fda_rule_evaluated, FdaRuleAssessment,
+	FdaRuleBatch, FdaRuleCause, FdaRuleCoverage, FdaRuleCoverageEntry,
+	FdaRuleDevice, FdaRuleDocument, FdaRuleDosage, FdaRuleDrug, FdaRuleHit,
+	FdaRulePair, FdaRuleParent, FdaRulePastDrug, FdaRulePatient,
+	FdaRulePrimarySource, FdaRuleReaction, FdaRuleReport, FdaRuleScope,
+	FdaRuleTest, FdaRuleValue, FDA_RULES_CASE_ONLY, FDA_RULES_NOT_EVALUATED,
+	FDA_RULES_XML_ONLY,
+};
 pub use f_test_result_policy::{has_test_name, has_test_payload};
 pub use h_narrative_policy::{
 	has_case_narrative, has_narrative_payload, should_require_case_narrative,
 };
+pub use condition_expr::ConditionExpr;
+pub use rule_set::{
+	load_rule_set, save_validation_rule, seed_validation_rules,
+	set_validation_rule_override, EffectiveRule, RuleSource, ValidationRuleSet,
+};
+pub use unit_policy::{
+	push_issue_if_unit_invalid, unit_problem, UnitKind, UCUM_INVALID_RULE,
+};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -54,6 +93,8 @@ pub enum ValidationProfile {
 	Ich,
 	Fda,
 	Mfds,
+	Ema,
+	Pmda,
 }
 
 impl ValidationProfile {
@@ -62,6 +103,8 @@ impl ValidationProfile {
 			Self::Ich => "ich",
 			Self::Fda => "fda",
 			Self::Mfds => "mfds",
+			Self::Ema => "ema",
+			Self::Pmda => "pmda",
 		}
 	}
 
@@ -70,9 +113,26 @@ impl ValidationProfile {
 			"ich" => Some(Self::Ich),
 			"fda" => Some(Self::Fda),
 			"mfds" => Some(Self::Mfds),
+			"ema" => Some(Self::Ema),
+			"pmda" => Some(Self::Pmda),
 			_ => None,
 		}
 	}
+
+	/// Profile implied by a batch or message receiver identifier (N.1.4 /
+	/// N.2.r.3); FDA when the receiver names no other regulator.
+	pub fn for_receiver(receiver: &str) -> Self {
+		let receiver = receiver.trim().to_ascii_uppercase();
+		if receiver.contains("MFDS") {
+			Self::Mfds
+		} else if receiver.contains("PMDA") {
+			Self::Pmda
+		} else if EMA_RECEIVERS.contains(&receiver.as_str()) {
+			Self::Ema
+		} else {
+			Self::Fda
+		}
+	}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +152,9 @@ pub struct CaseValidationReport {
 	pub blocking_count: usize,
 	pub non_blocking_count: usize,
 	pub issues: Vec<ValidationIssue>,
+	/// FDA rejection/warning error IDs (e.g. `R0021`) raised by the issues
+	#[serde(default)]
+	pub fda_rule_ids: Vec<String>,
 }
 
 pub fn has_text(value: Option<&str>) -> bool {
@@ -197,6 +260,13 @@ pub fn build_report(
 ) -> CaseValidationReport {
 	let blocking_count = issues.iter().filter(|issue| issue.blocking).count();
 	let non_blocking_count = issues.len().saturating_sub(blocking_count);
+	let mut fda_rule_ids: Vec<String> = issues
+		.iter()
+		.filter_map(|issue| find_fda_rule_by_code(&issue.code))
+		.map(|rule| rule.id.to_string())
+		.collect();
+	fda_rule_ids.sort();
+	fda_rule_ids.dedup();
 	CaseValidationReport {
 		profile: profile.as_str().to_string(),
 		case_id,
@@ -204,5 +274,6 @@ pub fn build_report(
 		blocking_count,
 		non_blocking_count,
 		issues,
+		fda_rule_ids,
 	}
 }
@@ -0,0 +1,434 @@
+// Effective validation rules: compiled catalog seed, stored definitions and
+// per-organization severity overrides.
+use super::catalog::{fnv1a_update, FNV_OFFSET_BASIS};
+use super::condition_expr::ConditionExpr;
+use super::{
+	canonical_rules_all, export_xpath_for_rule, ExportDirective, RuleFacts,
+	ValidationIssue, ValidationProfile,
+};
+use crate::ctx::Ctx;
+use crate::model::validation_rule::{
+	ValidationRule, ValidationRuleBmc, ValidationRuleForSave,
+	ValidationRuleOverride, ValidationRuleOverrideBmc, ValidationRuleOverrideForSet,
+};
+use crate::model::{self, ModelManager};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// Stored conditions that differ from the compiled ones. Rule definitions are
+/// shared by all organizations, so the last loaded set applies process-wide.
+static STORED_CONDITIONS: RwLock<BTreeMap<String, ConditionExpr>> =
+	RwLock::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSource {
+	Compiled,
+	Stored,
+}
+
+impl RuleSource {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Compiled => "compiled",
+			Self::Stored => "stored",
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectiveRule {
+	pub code: String,
+	pub profile: ValidationProfile,
+	pub section: String,
+	/// Severity after the organization override, if any
+	pub blocking: bool,
+	pub message: String,
+	pub xpath: Option<String>,
+	/// Condition expression source
+	pub condition: String,
+	pub export_directive: Option<ExportDirective>,
+	pub active: bool,
+	pub source: RuleSource,
+	pub overridden: bool,
+	condition_expr: ConditionExpr,
+	/// Compiled-in message, which validators emit for generic issues
+	default_message: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationRuleSet {
+	rules: Vec<EffectiveRule>,
+	revision: i64,
+}
+
+impl ValidationRuleSet {
+	/// The compiled-in catalog, revision 0.
+	pub fn compiled() -> Self {
+		let rules = canonical_rules_all()
+			.into_iter()
+			.map(|rule| EffectiveRule {
+				code: rule.code.to_string(),
+				profile: rule.profile,
+				section: rule.section.to_string(),
+				blocking: rule.blocking,
+				message: rule.message.to_string(),
+				xpath: export_xpath_for_rule(rule.code).map(str::to_string),
+				condition: rule.condition.as_str().to_string(),
+				export_directive: rule.export_directive,
+				active: true,
+				source: RuleSource::Compiled,
+				overridden: false,
+				condition_expr: ConditionExpr::Condition(rule.condition),
+				default_message: Some(rule.message.to_string()),
+			})
+			.collect();
+		Self { rules, revision: 0 }
+	}
+
+	/// Stored definitions replace compiled ones by code; overrides then set
+	/// the severity for the organization.
+	pub fn from_stored(
+		stored: Vec<ValidationRule>,
+		overrides: Vec<ValidationRuleOverride>,
+	) -> Self {
+		let mut set = Self::compiled();
+		for row in stored {
+			let Some(profile) = ValidationProfile::parse(&row.profile) else {
+				tracing::warn!(code = %row.code, profile = %row.profile, "skipping stored rule with unknown profile");
+				continue;
+			};
+			let compiled = set.rules.iter().position(|rule| rule.code == row.code);
+			let fallback = compiled
+				.map(|idx| set.rules[idx].condition_expr.clone())
+				.unwrap_or(ConditionExpr::Const(true));
+			let condition_expr =
+				ConditionExpr::parse(&row.condition).unwrap_or_else(|err| {
+					tracing::warn!(code = %row.code, condition = %row.condition, %err, "keeping default condition of stored rule");
+					fallback
+				});
+			let rule = EffectiveRule {
+				profile,
+				section: row.section,
+				blocking: row.blocking,
+				message: row.message,
+				xpath: row.xpath,
+				condition: row.condition,
+				export_directive: compiled
+					.and_then(|idx| set.rules[idx].export_directive),
+				active: row.active,
+				source: RuleSource::Stored,
+				overridden: false,
+				condition_expr,
+				default_message: compiled
+					.and_then(|idx| set.rules[idx].default_message.clone()),
+				code: row.code,
+			};
+			match compiled {
+				Some(idx) => set.rules[idx] = rule,
+				None => set.rules.push(rule),
+			}
+			set.revision = set.revision.max(row.revision);
+		}
+		for row in overrides {
+			if let Some(rule) =
+				set.rules.iter_mut().find(|r| r.code == row.rule_code)
+			{
+				rule.blocking = row.blocking;
+				rule.overridden = true;
+			}
+			set.revision = set.revision.max(row.revision);
+		}
+		set
+	}
+
+	/// Highest stored revision of the definitions and overrides in the set.
+	pub fn revision(&self) -> i64 {
+		self.revision
+	}
+
+	pub fn rule(&self, code: &str) -> Option<&EffectiveRule> {
+		self.rules.iter().find(|rule| rule.code == code)
+	}
+
+	/// ICH rules plus the profile's own; all rules without a profile.
+	pub fn rules_for_profile(
+		&self,
+		profile: Option<ValidationProfile>,
+	) -> Vec<&EffectiveRule> {
+		self.rules
+			.iter()
+			.filter(|rule| match profile {
+				Some(profile) => {
+					rule.profile == ValidationProfile::Ich || rule.profile == profile
+				}
+				None => true,
+			})
+			.collect()
+	}
+
+	pub fn version(&self, profile: Option<ValidationProfile>) -> String {
+		let mut hash = FNV_OFFSET_BASIS;
+		if self.revision > 0 {
+			hash = fnv1a_update(hash, self.revision.to_string().as_bytes());
+			hash = fnv1a_update(hash, b";");
+		}
+		for rule in self.rules_for_profile(profile) {
+			hash = fnv1a_update(hash, rule.code.as_bytes());
+			hash = fnv1a_update(hash, b"|");
+			hash = fnv1a_update(hash, rule.profile.as_str().as_bytes());
+			hash = fnv1a_update(hash, b"|");
+			hash = fnv1a_update(hash, rule.section.as_bytes());
+			hash = fnv1a_update(hash, b"|");
+			hash = fnv1a_update(hash, if rule.blocking { b"1" } else { b"0" });
+			hash = fnv1a_update(hash, b"|");
+			hash = fnv1a_update(hash, rule.message.as_bytes());
+			hash = fnv1a_update(hash, b"|");
+			hash = fnv1a_update(hash, rule.condition.as_bytes());
+			hash = fnv1a_update(hash, b"|");
+			if let Some(d) = rule.export_directive {
+				hash = fnv1a_update(hash, d.as_str().as_bytes());
+			}
+			if rule.source == RuleSource::Stored {
+				hash = fnv1a_update(hash, b"|");
+				hash = fnv1a_update(
+					hash,
+					rule.xpath.as_deref().unwrap_or("").as_bytes(),
+				);
+				hash = fnv1a_update(hash, if rule.active { b"1" } else { b"0" });
+			}
+			hash = fnv1a_update(hash, b";");
+		}
+
+		format!("{hash:016x}")
+	}
+
+	/// Drops issues of inactive rules and applies the effective severity,
+	/// section and stored message to the rest.
+	pub fn apply(&self, issues: Vec<ValidationIssue>) -> Vec<ValidationIssue> {
+		issues
+			.into_iter()
+			.filter_map(|mut issue| {
+				let Some(rule) = self.rule(&issue.code) else {
+					return Some(issue);
+				};
+				if !rule.active {
+					return None;
+				}
+				issue.blocking = rule.blocking;
+				issue.section = rule.section.clone();
+				if rule.default_message.as_deref() == Some(issue.message.as_str()) {
+					issue.message = rule.message.clone();
+				}
+				Some(issue)
+			})
+			.collect()
+	}
+
+	fn install_conditions(&self) {
+		let compiled = Self::compiled();
+		let stored = self
+			.rules
+			.iter()
+			.filter(|rule| rule.source == RuleSource::Stored)
+			.filter(|rule| {
+				compiled.rule(&rule.code).is_none_or(|default| {
+					default.condition_expr != rule.condition_expr
+				})
+			})
+			.map(|rule| (rule.code.clone(), rule.condition_expr.clone()))
+			.collect();
+		if let Ok(mut conditions) = STORED_CONDITIONS.write() {
+			*conditions = stored;
+		}
+	}
+}
+
+pub(super) fn stored_condition_satisfied(
+	code: &str,
+	facts: RuleFacts,
+) -> Option<bool> {
+	let conditions = STORED_CONDITIONS.read().ok()?;
+	conditions.get(code).map(|expr| expr.evaluate(&facts))
+}
+
+// -- Loading and administration
+
+/// Effective rules for the organization of `ctx`; also makes stored
+/// conditions visible to `is_rule_condition_satisfied`.
+pub async fn load_rule_set(
+	ctx: &Ctx,
+	mm: &ModelManager,
+) -> model::Result<ValidationRuleSet> {
+	let stored = ValidationRuleBmc::list(ctx, mm).await?;
+	let overrides =
+		ValidationRuleOverrideBmc::list_for_org(ctx, mm, ctx.organization_id())
+			.await?;
+	let rules = ValidationRuleSet::from_stored(stored, overrides);
+	rules.install_conditions();
+	Ok(rules)
+}
+
+/// Stores the compiled-in catalog for codes that have no stored definition.
+pub async fn seed_validation_rules(
+	ctx: &Ctx,
+	mm: &ModelManager,
+) -> model::Result<u64> {
+	let rules = canonical_rules_all()
+		.into_iter()
+		.map(|rule| {
+			(
+				rule.code.to_string(),
+				ValidationRuleForSave {
+					profile: rule.profile.as_str().to_string(),
+					section: rule.section.to_string(),
+					blocking: rule.blocking,
+					message: rule.message.to_string(),
+					xpath: export_xpath_for_rule(rule.code).map(str::to_string),
+					condition: Some(rule.condition.as_str().to_string()),
+					active: Some(true),
+				},
+			)
+		})
+		.collect();
+	ValidationRuleBmc::insert_missing(ctx, mm, rules).await
+}
+
+pub async fn save_validation_rule(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	code: &str,
+	rule: ValidationRuleForSave,
+) -> model::Result<ValidationRule> {
+	let invalid = |message: String| model::Error::ValidationRuleInvalid {
+		code: code.to_string(),
+		message,
+	};
+	if code.trim().is_empty() || code.trim() != code {
+		return Err(invalid(
+			"rule code must be non-empty and trimmed".to_string(),
+		));
+	}
+	let Some(profile) = ValidationProfile::parse(&rule.profile) else {
+		return Err(invalid(format!(
+			"invalid profile '{}' (expected: ich, fda, mfds, ema or pmda)",
+			rule.profile
+		)));
+	};
+	let rule = ValidationRuleForSave {
+		profile: profile.as_str().to_string(),
+		..rule
+	};
+	if rule.section.trim().is_empty() || rule.message.trim().is_empty() {
+		return Err(invalid("section and message are required".to_string()));
+	}
+	if let Some(condition) = rule.condition.as_deref() {
+		ConditionExpr::parse(condition)
+			.map_err(|err| invalid(format!("condition: {err}")))?;
+	}
+	let saved = ValidationRuleBmc::save(ctx, mm, code, rule).await?;
+	load_rule_set(ctx, mm).await?;
+	Ok(saved)
+}
+
+pub async fn set_validation_rule_override(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	code: &str,
+	data: ValidationRuleOverrideForSet,
+) -> model::Result<ValidationRuleOverride> {
+	if load_rule_set(ctx, mm).await?.rule(code).is_none() {
+		return Err(model::Error::ValidationRuleInvalid {
+			code: code.to_string(),
+			message: "unknown rule".to_string(),
+		});
+	}
+	ValidationRuleOverrideBmc::set(ctx, mm, code, data).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sqlx::types::time::OffsetDateTime;
+	use sqlx::types::Uuid;
+
+	fn stored(
+		code: &str,
+		blocking: bool,
+		message: &str,
+		revision: i64,
+	) -> ValidationRule {
+		ValidationRule {
+			code: code.to_string(),
+			profile: "ich".to_string(),
+			section: "patient".to_string(),
+			blocking,
+			message: message.to_string(),
+			xpath: None,
+			condition: "always".to_string(),
+			active: true,
+			revision,
+			updated_at: OffsetDateTime::UNIX_EPOCH,
+			updated_by: None,
+		}
+	}
+
+	fn issue(code: &str, message: &str) -> ValidationIssue {
+		ValidationIssue {
+			code: code.to_string(),
+			message: message.to_string(),
+			path: "patientInformation.patientInitials".to_string(),
+			section: "patient".to_string(),
+			blocking: true,
+		}
+	}
+
+	#[test]
+	fn stored_definitions_and_overrides_change_issues_and_version() {
+		let compiled = ValidationRuleSet::compiled();
+		let default_message = compiled
+			.rule("ICH.D.1.REQUIRED")
+			.expect("rule")
+			.message
+			.clone();
+		let set = ValidationRuleSet::from_stored(
+			vec![stored(
+				"ICH.D.1.REQUIRED",
+				true,
+				"Patient initials are required.",
+				7,
+			)],
+			vec![ValidationRuleOverride {
+				id: Uuid::nil(),
+				organization_id: Uuid::nil(),
+				rule_code: "ICH.D.1.REQUIRED".to_string(),
+				blocking: false,
+				revision: 9,
+				created_at: OffsetDateTime::UNIX_EPOCH,
+				created_by: Uuid::nil(),
+			}],
+		);
+
+		let issues = set.apply(vec![
+			issue("ICH.D.1.REQUIRED", &default_message),
+			issue("ICH.D.1.REQUIRED", "custom detail"),
+		]);
+		assert!(issues.iter().all(|issue| !issue.blocking));
+		assert_eq!(issues[0].message, "Patient initials are required.");
+		assert_eq!(issues[1].message, "custom detail");
+		assert_eq!(set.revision(), 9);
+		assert_ne!(set.version(None), compiled.version(None));
+		assert_eq!(
+			compiled.version(None),
+			super::super::canonical_rules_version(None)
+		);
+	}
+
+	#[test]
+	fn inactive_rules_drop_their_issues() {
+		let mut row = stored("ICH.D.1.REQUIRED", true, "off", 1);
+		row.active = false;
+		let set = ValidationRuleSet::from_stored(vec![row], Vec::new());
+		assert!(set.apply(vec![issue("ICH.D.1.REQUIRED", "x")]).is_empty());
+		assert_eq!(set.apply(vec![issue("NOT.A.RULE", "x")]).len(), 1);
+	}
+}
@@ -0,0 +1,85 @@
+// Shared UCUM unit policy used by case validators.
+use super::{find_canonical_rule, has_text, ValidationIssue};
+use crate::model::ucum::UcumUnit;
+
+pub const UCUM_INVALID_RULE: &str = "ICH.UCUM.INVALID";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+	/// Dose, strength and test result units.
+	Any,
+	/// Age, gestation, duration and frequency units.
+	Time,
+}
+
+/// Why a unit is rejected, if it is. Empty values are left to the presence
+/// rules.
+pub fn unit_problem(value: Option<&str>, kind: UnitKind) -> Option<&'static str> {
+	if !has_text(value) {
+		return None;
+	}
+	match UcumUnit::parse(value.unwrap_or_default().trim()) {
+		Err(_) => Some("is not a valid UCUM unit"),
+		Ok(unit) if kind == UnitKind::Time && !unit.is_time() => {
+			Some("is not a UCUM unit of time")
+		}
+		Ok(_) => None,
+	}
+}
+
+pub fn push_issue_if_unit_invalid(
+	issues: &mut Vec<ValidationIssue>,
+	path: impl Into<String>,
+	value: Option<&str>,
+	kind: UnitKind,
+) -> bool {
+	let Some(problem) = unit_problem(value, kind) else {
+		return false;
+	};
+	let Some(rule) = find_canonical_rule(UCUM_INVALID_RULE) else {
+		return false;
+	};
+	issues.push(ValidationIssue {
+		code: rule.code.to_string(),
+		message: format!("'{}' {problem}.", value.unwrap_or_default().trim()),
+		path: path.into(),
+		section: rule.section.to_string(),
+		blocking: rule.blocking,
+	});
+	true
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn non_ucum_and_non_time_units_are_flagged() {
+		let mut issues = Vec::new();
+		assert!(push_issue_if_unit_invalid(
+			&mut issues,
+			"drugs.0.dosages.0.doseUnit",
+			Some("mcg"),
+			UnitKind::Any
+		));
+		assert!(push_issue_if_unit_invalid(
+			&mut issues,
+			"patientInformation.ageUnit",
+			Some("mg"),
+			UnitKind::Time
+		));
+		assert_eq!(issues[0].code, UCUM_INVALID_RULE);
+		assert_eq!(issues[0].message, "'mcg' is not a valid UCUM unit.");
+		assert_eq!(issues[1].message, "'mg' is not a UCUM unit of time.");
+		assert!(issues[0].blocking);
+	}
+
+	#[test]
+	fn ucum_and_empty_units_pass() {
+		assert_eq!(unit_problem(Some("mg/dL"), UnitKind::Any), None);
+		assert_eq!(unit_problem(Some("10*3/uL"), UnitKind::Any), None);
+		assert_eq!(unit_problem(Some("wk"), UnitKind::Time), None);
+		assert_eq!(unit_problem(Some(" "), UnitKind::Time), None);
+		assert_eq!(unit_problem(None, UnitKind::Any), None);
+	}
+}
@@ -1,3 +1,4 @@
+use crate::model::partial_date::{DatePrecision, PartialDate};
 use crate::xml::error::Error;
 use crate::xml::types::{XmlValidationError, XmlValidationReport};
 use crate::xml::validate::{
@@ -6,8 +7,11 @@ use crate::xml::validate::{
 	RuleFacts,
 };
 use crate::xml::xml_validation_fda::collect_fda_profile_errors;
+use crate::xml::xml_validation_rules::{
+	collect_xml_rule_findings, load_xml_rule_files, XmlRuleSet,
+};
 use crate::xml::xml_validation_ich::{
-	collect_ich_case_history_errors,
+	collect_ich_case_history_errors, collect_ich_date_precision_errors,
 	collect_ich_identity_text_errors, collect_ich_profile_value_presence_errors,
 	collect_ich_structural_value_errors,
 };
@@ -18,6 +22,7 @@ use libxml::xpath::Context;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 #[derive(Debug, Clone)]
 pub struct XmlValidatorConfig {
@@ -26,6 +31,16 @@ pub struct XmlValidatorConfig {
 	pub xsd_path: Option<PathBuf>,
 	pub require_schema_location: bool,
 	pub require_its_version: Option<&'static str>,
+	/// Schematron-style rules, loaded once when the config is built.
+	pub rules: Arc<XmlRuleSet>,
+}
+
+impl XmlValidatorConfig {
+	/// Replaces the rules with those in `paths` (`.sch` files or directories).
+	pub fn with_rule_paths(mut self, paths: &[PathBuf]) -> Result<Self> {
+		self.rules = Arc::new(load_xml_rule_files(paths)?);
+		Ok(self)
+	}
 }
 
 impl Default for XmlValidatorConfig {
@@ -36,6 +51,7 @@ impl Default for XmlValidatorConfig {
 			xsd_path: xsd_path_from_env(),
 			require_schema_location: true,
 			require_its_version: Some("XML_1.0"),
+			rules: default_xml_rules(),
 		}
 	}
 }
@@ -55,7 +71,9 @@ pub fn validate_e2b_xml(
 				),
 				line: None,
 				column: None,
+				xpath: None,
 			}],
+			warnings: Vec::new(),
 			root_element: None,
 		});
 	}
@@ -82,6 +100,7 @@ pub fn validate_e2b_xml(
 					message: format!("XML parse error: {e}"),
 					line: None,
 					column: Some(pos),
+					xpath: None,
 				});
 				break;
 			}
@@ -94,6 +113,7 @@ pub fn validate_e2b_xml(
 			message: "Missing root element".to_string(),
 			line: None,
 			column: None,
+			xpath: None,
 		});
 	}
 
@@ -106,6 +126,7 @@ pub fn validate_e2b_xml(
 				),
 				line: None,
 				column: None,
+				xpath: None,
 			});
 		}
 	}
@@ -129,15 +150,18 @@ pub fn validate_e2b_xml(
 				.to_string(),
 			line: None,
 			column: None,
+			xpath: None,
 		});
 	}
 
-	let mut rule_errors = validate_e2b_xml_rules(xml, &config)?;
+	let mut warnings = Vec::new();
+	let mut rule_errors = validate_e2b_xml_rules(xml, &config, &mut warnings)?;
 	errors.append(&mut rule_errors);
 
 	Ok(XmlValidationReport {
 		ok: errors.is_empty(),
 		errors,
+		warnings,
 		root_element: root,
 	})
 }
@@ -202,6 +226,7 @@ pub fn validate_e2b_xml_xsd(
 						.unwrap_or_else(|| "XSD validation error".to_string()),
 					line: err.line.map(|v| v as usize),
 					column: err.col.map(|v| v as usize),
+					xpath: None,
 				});
 			}
 			Ok(out)
@@ -213,9 +238,40 @@ fn xsd_path_from_env() -> Option<PathBuf> {
 	std::env::var("E2BR3_XSD_PATH").ok().map(PathBuf::from)
 }
 
+fn rule_paths_from_env() -> Vec<PathBuf> {
+	std::env::var_os("E2BR3_XML_RULES_PATH")
+		.map(|paths| std::env::split_paths(&paths).collect())
+		.unwrap_or_default()
+}
+
+static DEFAULT_XML_RULES: OnceLock<Arc<XmlRuleSet>> = OnceLock::new();
+
+/// Loads the `E2BR3_XML_RULES_PATH` rule files used by the default config.
+/// Call at startup so unreadable or malformed rule files fail fast.
+pub fn init_default_xml_rules() -> Result<()> {
+	if DEFAULT_XML_RULES.get().is_none() {
+		let rules = load_xml_rule_files(&rule_paths_from_env())?;
+		let _ = DEFAULT_XML_RULES.set(Arc::new(rules));
+	}
+	Ok(())
+}
+
+fn default_xml_rules() -> Arc<XmlRuleSet> {
+	DEFAULT_XML_RULES
+		.get_or_init(|| {
+			let rules =
+				load_xml_rule_files(&rule_paths_from_env()).unwrap_or_else(|ex| {
+					panic!("FATAL - WHILE LOADING XML RULES - Cause: {ex:?}")
+				});
+			Arc::new(rules)
+		})
+		.clone()
+}
+
 fn validate_e2b_xml_rules(
 	xml: &[u8],
 	config: &XmlValidatorConfig,
+	warnings: &mut Vec<XmlValidationError>,
 ) -> Result<Vec<XmlValidationError>> {
 	let xml_str = std::str::from_utf8(xml).map_err(|err| Error::InvalidXml {
 		message: format!("XML not valid UTF-8: {err}"),
@@ -251,11 +307,13 @@ fn validate_e2b_xml_rules(
 				),
 				line: None,
 				column: None,
+				xpath: None,
 			}),
 			None => errors.push(XmlValidationError {
 				message: "Missing ITSVersion attribute on root".to_string(),
 				line: None,
 				column: None,
+				xpath: None,
 			}),
 		}
 	}
@@ -278,6 +336,7 @@ fn validate_e2b_xml_rules(
 						),
 						line: None,
 						column: None,
+						xpath: None,
 					});
 				}
 			}
@@ -285,6 +344,7 @@ fn validate_e2b_xml_rules(
 				message: "Missing xsi:schemaLocation on root".to_string(),
 				line: None,
 				column: None,
+				xpath: None,
 			}),
 		}
 	}
@@ -293,10 +353,15 @@ fn validate_e2b_xml_rules(
 	collect_ich_profile_value_presence_errors(&mut xpath, &mut errors);
 	collect_ich_structural_value_errors(&mut xpath, &mut errors);
 	collect_ich_case_history_errors(&mut xpath, &mut errors);
+	collect_ich_date_precision_errors(&mut xpath, &mut errors);
 
 	collect_fda_profile_errors(&mut xpath, &mut errors);
 
 	collect_placeholder_errors(&root, &mut errors);
+
+	if !config.rules.rules.is_empty() {
+		collect_xml_rule_findings(&doc, xml, &config.rules, &mut errors, warnings);
+	}
 	Ok(errors)
 }
 
@@ -315,6 +380,7 @@ fn collect_placeholder_errors(
 				),
 				line: None,
 				column: None,
+				xpath: None,
 			});
 		}
 		for (name, val) in root.get_attributes() {
@@ -328,6 +394,7 @@ fn collect_placeholder_errors(
 					),
 					line: None,
 					column: None,
+					xpath: None,
 				});
 			}
 		}
@@ -350,6 +417,7 @@ pub(crate) fn push_rule_error(
 		message,
 		line: None,
 		column: None,
+		xpath: None,
 	});
 }
 
@@ -567,6 +635,39 @@ pub(crate) fn validate_attr_prefix_on_nodes(
 	});
 }
 
+/// Flags TS `value`s that are malformed or less precise than `minimum`.
+/// Absent values are left to the required/nullFlavor rules.
+pub(crate) fn validate_date_precision_on_nodes(
+	xpath: &mut Context,
+	errors: &mut Vec<XmlValidationError>,
+	node_xpath: &str,
+	minimum: DatePrecision,
+	rule_code: &str,
+	value_label: &str,
+) {
+	for_each_xpath_node(xpath, node_xpath, |node| {
+		let Some(value) = node.get_attribute("value") else {
+			return;
+		};
+		if value.trim().is_empty() {
+			return;
+		}
+		let precise_enough = PartialDate::parse(&value)
+			.map(|date| date.meets(minimum))
+			.unwrap_or(false);
+		if !precise_enough {
+			push_rule_error(
+				errors,
+				rule_code,
+				&format!(
+					"{value_label} '{value}' does not contain minimum precision of \"{}\"",
+					minimum.format()
+				),
+			);
+		}
+	});
+}
+
 pub(crate) fn validate_normalized_code_format_on_nodes(
 	xpath: &mut Context,
 	errors: &mut Vec<XmlValidationError>,
@@ -1,5 +1,8 @@
+use crate::xml::fda::rule_input::collect_fda_rule_batch;
 use crate::xml::types::XmlValidationError;
-use crate::xml::validate::{has_text, RuleFacts};
+use crate::xml::validate::{
+	check_fda_rules, has_text, FdaRuleScope, FdaRuleSeverity, RuleFacts,
+};
 use libxml::xpath::Context;
 
 use super::xml_validation::{
@@ -260,6 +263,20 @@ pub(crate) fn collect_fda_profile_errors(
 		},
 		"FDA.C.5.5b must not be provided for postmarket (N.1.4=ZZFDA, N.2.r.3=CDER/CBER)",
 	);
+
+	// Warnings do not block the submission, so only rejections fail the XML.
+	let batch = collect_fda_rule_batch(xpath);
+	for hit in check_fda_rules(&batch, FdaRuleScope::Xml) {
+		if hit.rule.severity != FdaRuleSeverity::Rejection {
+			continue;
+		}
+		errors.push(XmlValidationError {
+			message: format!("[{}] {} ({})", hit.rule.code, hit.message(), hit.path),
+			line: None,
+			column: None,
+			xpath: None,
+		});
+	}
 }
 
 fn collect_fda_xml_facts(xpath: &mut Context) -> FdaXmlFacts {
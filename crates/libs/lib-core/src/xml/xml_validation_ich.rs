@@ -1,3 +1,4 @@
+use crate::model::partial_date::DatePrecision;
 use crate::xml::types::XmlValidationError;
 use crate::xml::validate::{
 	export_normalization_spec_for_rule, RuleFacts,
@@ -14,6 +15,7 @@ use super::xml_validation::{
 	validate_attr_or_text_or_null_required_on_nodes,
 	validate_code_or_codesystem_or_text_required_with_nullflavor_forbidden_on_nodes,
 	validate_code_or_codesystem_or_text_or_null_required_on_nodes,
+	validate_date_precision_on_nodes,
 	validate_required_attrs_on_nodes, validate_required_child_on_nodes,
 	validate_supported_xsi_types_on_nodes,
 	validate_typed_children_attrs_or_nullflavor_on_nodes,
@@ -594,6 +596,56 @@ pub(crate) fn collect_ich_case_history_errors(
 	);
 }
 
+pub(crate) fn collect_ich_date_precision_errors(
+	xpath: &mut Context,
+	errors: &mut Vec<XmlValidationError>,
+) {
+	// Rule: header and C.1 dates carry the precision required by the
+	// rejection rules; D/E/F/G dates may be as coarse as CCYY.
+	let rules = [
+		(
+			"/hl7:MCCI_IN200100UV01/hl7:creationTime",
+			DatePrecision::Second,
+			"ICH.N.1.5.PRECISION",
+			"N.1.5 batch transmission date",
+		),
+		(
+			"//hl7:PORR_IN049016UV/hl7:creationTime",
+			DatePrecision::Second,
+			"ICH.N.2.r.4.PRECISION",
+			"N.2.r.4 message creation date",
+		),
+		(
+			"//hl7:controlActProcess/hl7:effectiveTime",
+			DatePrecision::Second,
+			"ICH.C.1.2.PRECISION",
+			"C.1.2 date of creation",
+		),
+		(
+			"//hl7:investigationEvent/hl7:effectiveTime/hl7:low",
+			DatePrecision::Day,
+			"ICH.C.1.4.PRECISION",
+			"C.1.4 date first received",
+		),
+		(
+			"//hl7:investigationEvent/hl7:availabilityTime",
+			DatePrecision::Day,
+			"ICH.C.1.5.PRECISION",
+			"C.1.5 date of most recent information",
+		),
+	];
+	for (node_xpath, minimum, rule_code, value_label) in rules {
+		validate_date_precision_on_nodes(
+			xpath,
+			errors,
+			node_xpath,
+			minimum,
+			rule_code,
+			value_label,
+		);
+	}
+}
+
 fn reaction_temporal_markers(node: &Node) -> (bool, bool, bool) {
 	let mut has_start = false;
 	let mut has_end = false;
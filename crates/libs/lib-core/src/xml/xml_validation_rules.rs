@@ -0,0 +1,650 @@
+// Declarative XML business rules (Schematron-style).
+//
+// Rules are XPath 1.0 assertions read from `.sch` files, so ICH or regional
+// checks can be added without Rust changes. Supported ISO Schematron subset:
+// `ns`, `pattern`, `rule/@context`, `assert`/`report` with `@test`, `@id`
+// (rule code) and `@role` (severity), and `value-of`/`name` in messages.
+// As in Schematron, a node is checked by the first rule of a pattern whose
+// context matches it; later rules of that pattern skip it.
+
+use crate::xml::error::Error;
+use crate::xml::types::XmlValidationError;
+use crate::xml::Result;
+use libxml::bindings::xmlNodePtr;
+use libxml::parser::Parser;
+use libxml::tree::{Document, Node, NodeType};
+use libxml::xpath::{is_well_formed_xpath, Context};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+const SCHEMATRON_NS: &str = "http://purl.oclc.org/dsdl/schematron";
+const HL7_NS: &str = "urn:hl7-org:v3";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmlRuleSeverity {
+	Error,
+	Warning,
+}
+
+impl XmlRuleSeverity {
+	/// Schematron `@role`; missing or unknown roles block.
+	pub fn from_role(role: Option<&str>) -> Self {
+		match role.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+			Some("warning" | "warn" | "info" | "information") => Self::Warning,
+			_ => Self::Error,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmlAssertionKind {
+	/// `sch:assert`: fires when `test` is false.
+	Assert,
+	/// `sch:report`: fires when `test` is true.
+	Report,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlRuleMessagePart {
+	Text(String),
+	/// XPath evaluated against the context node.
+	ValueOf(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct XmlAssertionRule {
+	/// Index of the enclosing `sch:pattern` within the rule set.
+	pub pattern: usize,
+	/// Index of the enclosing `sch:rule` within the rule set; assertions of
+	/// one rule share its context.
+	pub rule: usize,
+	pub code: String,
+	/// Node selector; relative patterns match anywhere (`//` is prepended).
+	pub context: String,
+	pub test: String,
+	pub kind: XmlAssertionKind,
+	pub severity: XmlRuleSeverity,
+	pub message: Vec<XmlRuleMessagePart>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct XmlRuleSet {
+	/// Prefix/URI pairs from `sch:ns`; `hl7` and `xsi` are always bound.
+	pub namespaces: Vec<(String, String)>,
+	pub rules: Vec<XmlAssertionRule>,
+}
+
+impl XmlRuleSet {
+	/// Appends the patterns of `other` after those already in the set.
+	pub fn extend(&mut self, other: XmlRuleSet) {
+		let (pattern_offset, rule_offset) = self
+			.rules
+			.last()
+			.map_or((0, 0), |last| (last.pattern + 1, last.rule + 1));
+		self.namespaces.extend(other.namespaces);
+		self.rules
+			.extend(other.rules.into_iter().map(|rule| XmlAssertionRule {
+				pattern: rule.pattern + pattern_offset,
+				rule: rule.rule + rule_offset,
+				..rule
+			}));
+	}
+}
+
+/// Loads rules from `.sch` files; directories contribute their `.sch` files
+/// in name order.
+pub fn load_xml_rule_files(paths: &[PathBuf]) -> Result<XmlRuleSet> {
+	let mut rule_set = XmlRuleSet::default();
+	for path in paths {
+		for file in rule_files_in(path)? {
+			let content = std::fs::read_to_string(&file)?;
+			let parsed = parse_xml_rules(&content).map_err(|err| match err {
+				Error::InvalidXml {
+					message,
+					line,
+					column,
+				} => Error::InvalidXml {
+					message: format!("{}: {message}", file.display()),
+					line,
+					column,
+				},
+				other => other,
+			})?;
+			rule_set.extend(parsed);
+		}
+	}
+	Ok(rule_set)
+}
+
+fn rule_files_in(path: &Path) -> Result<Vec<PathBuf>> {
+	if !path.is_dir() {
+		return Ok(vec![path.to_path_buf()]);
+	}
+	let mut files = Vec::new();
+	for entry in std::fs::read_dir(path)? {
+		let file = entry?.path();
+		if file.extension().is_some_and(|ext| ext == "sch") {
+			files.push(file);
+		}
+	}
+	files.sort();
+	Ok(files)
+}
+
+pub fn parse_xml_rules(content: &str) -> Result<XmlRuleSet> {
+	let parser = Parser::default();
+	let doc = parser
+		.parse_string(content)
+		.map_err(|err| rule_error(format!("rule file parse error: {err}")))?;
+	let mut xpath = Context::new(&doc)
+		.map_err(|_| rule_error("Failed to initialize XPath context".to_string()))?;
+	let _ = xpath.register_namespace("sch", SCHEMATRON_NS);
+
+	let is_schema = xpath
+		.findnodes("/sch:schema", None)
+		.map(|nodes| !nodes.is_empty())
+		.unwrap_or(false);
+	if !is_schema {
+		return Err(rule_error(format!(
+			"rule file root must be <schema xmlns=\"{SCHEMATRON_NS}\">"
+		)));
+	}
+
+	let mut rule_set = XmlRuleSet::default();
+	for ns in xpath
+		.findnodes("/sch:schema/sch:ns", None)
+		.unwrap_or_default()
+	{
+		match (ns.get_attribute("prefix"), ns.get_attribute("uri")) {
+			(Some(prefix), Some(uri)) => rule_set.namespaces.push((prefix, uri)),
+			_ => return Err(rule_error("<ns> needs prefix and uri".to_string())),
+		}
+	}
+
+	let mut rules = 0;
+	let patterns = xpath
+		.findnodes("/sch:schema/sch:pattern", None)
+		.unwrap_or_default();
+	for (pattern, pattern_node) in patterns.into_iter().enumerate() {
+		let rule_nodes = xpath
+			.findnodes("sch:rule", Some(&pattern_node))
+			.unwrap_or_default();
+		for rule_node in rule_nodes {
+			let context = required_attribute(&rule_node, "context", "<rule>")?;
+			let assertions = xpath
+				.findnodes("sch:assert | sch:report", Some(&rule_node))
+				.unwrap_or_default();
+			for node in assertions {
+				let kind = if node.get_name() == "report" {
+					XmlAssertionKind::Report
+				} else {
+					XmlAssertionKind::Assert
+				};
+				let element = format!("<{}>", node.get_name());
+				let code = required_attribute(&node, "id", &element)?;
+				let test = required_attribute(&node, "test", &element)?;
+				for expr in [context_xpath(&context), test.clone()] {
+					if !is_well_formed_xpath(&expr) {
+						return Err(rule_error(format!(
+							"[{code}] invalid XPath '{expr}'"
+						)));
+					}
+				}
+				rule_set.rules.push(XmlAssertionRule {
+					pattern,
+					rule: rules,
+					code,
+					context: context.clone(),
+					test,
+					kind,
+					severity: XmlRuleSeverity::from_role(
+						node.get_attribute("role").as_deref(),
+					),
+					message: message_parts(&node),
+				});
+			}
+			rules += 1;
+		}
+	}
+	Ok(rule_set)
+}
+
+fn rule_error(message: String) -> Error {
+	Error::InvalidXml {
+		message,
+		line: None,
+		column: None,
+	}
+}
+
+fn required_attribute(node: &Node, name: &str, element: &str) -> Result<String> {
+	node.get_attribute(name)
+		.map(|value| value.trim().to_string())
+		.filter(|value| !value.is_empty())
+		.ok_or_else(|| rule_error(format!("{element} is missing @{name}")))
+}
+
+fn message_parts(node: &Node) -> Vec<XmlRuleMessagePart> {
+	let mut parts = Vec::new();
+	for child in node.get_child_nodes() {
+		match child.get_type() {
+			Some(NodeType::TextNode | NodeType::CDataSectionNode) => {
+				parts.push(XmlRuleMessagePart::Text(child.get_content()));
+			}
+			Some(NodeType::ElementNode) if child.get_name() == "value-of" => {
+				if let Some(select) = child.get_attribute("select") {
+					parts.push(XmlRuleMessagePart::ValueOf(select));
+				}
+			}
+			Some(NodeType::ElementNode) if child.get_name() == "name" => {
+				let path = child
+					.get_attribute("path")
+					.unwrap_or_else(|| ".".to_string());
+				parts.push(XmlRuleMessagePart::ValueOf(format!("name({path})")));
+			}
+			_ => {}
+		}
+	}
+	parts
+}
+
+/// Schematron contexts are match patterns: `hl7:reaction` matches any
+/// reaction, so each relative alternative is anchored with `//`.
+fn context_xpath(context: &str) -> String {
+	split_union(context)
+		.into_iter()
+		.map(|alt| {
+			let alt = alt.trim();
+			if alt.starts_with('/') {
+				alt.to_string()
+			} else {
+				format!("//{alt}")
+			}
+		})
+		.collect::<Vec<_>>()
+		.join(" | ")
+}
+
+/// Splits on top-level `|`, leaving predicates and string literals intact.
+fn split_union(expr: &str) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let mut depth = 0usize;
+	let mut quote: Option<char> = None;
+	let mut start = 0;
+	for (idx, c) in expr.char_indices() {
+		match (quote, c) {
+			(Some(q), _) if c == q => quote = None,
+			(Some(_), _) => {}
+			(None, '\'' | '"') => quote = Some(c),
+			(None, '[' | '(') => depth += 1,
+			(None, ']' | ')') => depth = depth.saturating_sub(1),
+			(None, '|') if depth == 0 => {
+				parts.push(&expr[start..idx]);
+				start = idx + 1;
+			}
+			_ => {}
+		}
+	}
+	parts.push(&expr[start..]);
+	parts
+}
+
+/// Evaluates `rules` against a parsed message; findings carry the rule code,
+/// the offending node's XPath and its line in `xml`.
+pub(crate) fn collect_xml_rule_findings(
+	doc: &Document,
+	xml: &[u8],
+	rules: &XmlRuleSet,
+	errors: &mut Vec<XmlValidationError>,
+	warnings: &mut Vec<XmlValidationError>,
+) {
+	let Ok(mut xpath) = Context::new(doc) else {
+		return;
+	};
+	let _ = xpath.register_namespace("hl7", HL7_NS);
+	let _ = xpath.register_namespace("xsi", XSI_NS);
+	for (prefix, uri) in &rules.namespaces {
+		let _ = xpath.register_namespace(prefix, uri);
+	}
+	let element_lines = element_lines(doc, xml);
+	// Nodes already checked by an earlier rule of each pattern.
+	let mut matched: HashMap<usize, HashSet<xmlNodePtr>> = HashMap::new();
+
+	for assertions in rules.rules.chunk_by(|a, b| a.rule == b.rule) {
+		let context = &assertions[0].context;
+		let Ok(nodes) = xpath.findnodes(&context_xpath(context), None) else {
+			for rule in assertions {
+				let findings = match rule.severity {
+					XmlRuleSeverity::Error => &mut *errors,
+					XmlRuleSeverity::Warning => &mut *warnings,
+				};
+				findings.push(XmlValidationError {
+					message: format!(
+						"[{}] rule context '{}' could not be evaluated",
+						rule.code, rule.context
+					),
+					line: None,
+					column: None,
+					xpath: None,
+				});
+			}
+			continue;
+		};
+		let seen = matched.entry(assertions[0].pattern).or_default();
+		let nodes: Vec<Node> = nodes
+			.into_iter()
+			.filter(|node| seen.insert(node.node_ptr()))
+			.collect();
+		for rule in assertions {
+			let findings = match rule.severity {
+				XmlRuleSeverity::Error => &mut *errors,
+				XmlRuleSeverity::Warning => &mut *warnings,
+			};
+			for node in &nodes {
+				let outcome = xpath
+					.node_evaluate(&format!("boolean({})", rule.test), node)
+					.map(|value| value.to_string() == "true");
+				let message = match (outcome, rule.kind) {
+					(Ok(true), XmlAssertionKind::Assert)
+					| (Ok(false), XmlAssertionKind::Report) => continue,
+					(Ok(_), _) => render_message(&xpath, node, &rule.message),
+					(Err(()), _) => {
+						format!("rule test '{}' could not be evaluated", rule.test)
+					}
+				};
+				findings.push(XmlValidationError {
+					message: format!("[{}] {message}", rule.code),
+					line: node_line(node, &element_lines),
+					column: None,
+					xpath: Some(node_xpath(node)),
+				});
+			}
+		}
+	}
+}
+
+fn render_message(
+	xpath: &Context,
+	node: &Node,
+	parts: &[XmlRuleMessagePart],
+) -> String {
+	let text: String = parts
+		.iter()
+		.map(|part| match part {
+			XmlRuleMessagePart::Text(text) => text.clone(),
+			XmlRuleMessagePart::ValueOf(select) => xpath
+				.node_evaluate(&format!("string({select})"), node)
+				.map(|value| value.to_string())
+				.unwrap_or_default(),
+		})
+		.collect();
+	text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Start line of every element of `doc`, read from its source `xml` in one
+/// pass so findings don't re-count their position.
+fn element_lines(doc: &Document, xml: &[u8]) -> HashMap<xmlNodePtr, usize> {
+	let mut elements = Vec::new();
+	let mut pending: Vec<Node> = doc.get_root_element().into_iter().collect();
+	while let Some(node) = pending.pop() {
+		pending.extend(node.get_child_elements().into_iter().rev());
+		elements.push(node.node_ptr());
+	}
+	elements
+		.into_iter()
+		.zip(source_element_lines(xml))
+		.collect()
+}
+
+/// Start line of every element of `xml`, in document order.
+fn source_element_lines(xml: &[u8]) -> Vec<usize> {
+	let mut reader = Reader::from_reader(xml);
+	let mut buf = Vec::new();
+	let mut lines = Vec::new();
+	let mut line = 1;
+	let mut scanned = 0;
+	loop {
+		let start = reader.buffer_position();
+		match reader.read_event_into(&mut buf) {
+			Ok(Event::Start(_) | Event::Empty(_)) => {
+				line += xml[scanned..start].iter().filter(|b| **b == b'\n').count();
+				scanned = start;
+				lines.push(line);
+			}
+			Ok(Event::Eof) | Err(_) => break,
+			Ok(_) => {}
+		}
+		buf.clear();
+	}
+	lines
+}
+
+fn node_line(
+	node: &Node,
+	element_lines: &HashMap<xmlNodePtr, usize>,
+) -> Option<usize> {
+	let element = match node.get_type() {
+		Some(NodeType::ElementNode) => node.clone(),
+		_ => node.get_parent()?,
+	};
+	element_lines.get(&element.node_ptr()).copied()
+}
+
+/// Positional XPath of `node`, e.g. `/hl7:MCCI_IN200100UV01/hl7:id[1]`.
+fn node_xpath(node: &Node) -> String {
+	let mut steps = Vec::new();
+	let mut current = Some(node.clone());
+	while let Some(node) = current {
+		let parent = node.get_parent();
+		match node.get_type() {
+			Some(NodeType::AttributeNode) => {
+				steps.push(format!("@{}", node.get_name()));
+			}
+			Some(NodeType::ElementNode) => {
+				let name = qualified_name(&node);
+				let is_root = parent
+					.as_ref()
+					.is_none_or(|p| p.get_type() != Some(NodeType::ElementNode));
+				if is_root {
+					steps.push(name);
+				} else {
+					steps.push(format!("{name}[{}]", sibling_position(&node)));
+				}
+			}
+			_ => {}
+		}
+		current = parent;
+	}
+	steps.reverse();
+	format!("/{}", steps.join("/"))
+}
+
+fn qualified_name(node: &Node) -> String {
+	match node.get_namespace() {
+		Some(ns) if ns.get_href() == HL7_NS => format!("hl7:{}", node.get_name()),
+		Some(ns) if !ns.get_prefix().is_empty() => {
+			format!("{}:{}", ns.get_prefix(), node.get_name())
+		}
+		_ => node.get_name(),
+	}
+}
+
+fn sibling_position(node: &Node) -> usize {
+	let name = node.get_name();
+	let href = node.get_namespace().map(|ns| ns.get_href());
+	let mut position = 1;
+	let mut sibling = node.get_prev_element_sibling();
+	while let Some(prev) = sibling {
+		if prev.get_name() == name
+			&& prev.get_namespace().map(|ns| ns.get_href()) == href
+		{
+			position += 1;
+		}
+		sibling = prev.get_prev_element_sibling();
+	}
+	position
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const RULES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<schema xmlns="http://purl.oclc.org/dsdl/schematron" queryBinding="xslt">
+	<ns prefix="hl7" uri="urn:hl7-org:v3"/>
+	<pattern id="reactions">
+		<rule context="hl7:observation[hl7:code/@code='29']">
+			<assert id="TEST.E.i.1.1a.REQUIRED" role="warning"
+				test="normalize-space(hl7:value/hl7:originalText) != ''">
+				Reaction <value-of select="hl7:value/@code"/> has no reported term.
+			</assert>
+			<report id="TEST.E.i.1.1b.REQUIRED" test="hl7:value/hl7:originalText and not(hl7:value/hl7:originalText/@language)">
+				<name/> reported term has no language.
+			</report>
+		</rule>
+	</pattern>
+</schema>"#;
+
+	const XML: &str = r#"<MCCI_IN200100UV01 xmlns="urn:hl7-org:v3">
+	<observation>
+		<code code="29"/>
+		<value code="10022617"/>
+	</observation>
+	<observation>
+		<code code="29"/>
+		<value code="10019211">
+			<originalText>headache</originalText>
+		</value>
+	</observation>
+</MCCI_IN200100UV01>"#;
+
+	fn findings(
+		rules: &str,
+		xml: &str,
+	) -> (Vec<XmlValidationError>, Vec<XmlValidationError>) {
+		let rules = parse_xml_rules(rules).expect("rules");
+		let doc = Parser::default().parse_string(xml).expect("xml");
+		let mut errors = Vec::new();
+		let mut warnings = Vec::new();
+		collect_xml_rule_findings(
+			&doc,
+			xml.as_bytes(),
+			&rules,
+			&mut errors,
+			&mut warnings,
+		);
+		(errors, warnings)
+	}
+
+	#[test]
+	fn rule_file_assertions_and_reports_are_parsed() {
+		let rules = parse_xml_rules(RULES).expect("rules");
+		assert_eq!(rules.rules.len(), 2);
+		assert_eq!(rules.rules[0].kind, XmlAssertionKind::Assert);
+		assert_eq!(rules.rules[0].severity, XmlRuleSeverity::Warning);
+		assert_eq!(rules.rules[1].kind, XmlAssertionKind::Report);
+		assert_eq!(rules.rules[1].severity, XmlRuleSeverity::Error);
+		assert_eq!(
+			context_xpath("hl7:a[@x='|'] | /hl7:b"),
+			"//hl7:a[@x='|'] | /hl7:b"
+		);
+	}
+
+	#[test]
+	fn findings_carry_code_severity_xpath_and_line() {
+		let (errors, warnings) = findings(RULES, XML);
+
+		assert_eq!(warnings.len(), 1, "{warnings:?}");
+		assert_eq!(
+			warnings[0].message,
+			"[TEST.E.i.1.1a.REQUIRED] Reaction 10022617 has no reported term."
+		);
+		assert_eq!(
+			warnings[0].xpath.as_deref(),
+			Some("/hl7:MCCI_IN200100UV01/hl7:observation[1]")
+		);
+		assert_eq!(warnings[0].line, Some(2));
+
+		assert_eq!(errors.len(), 1, "{errors:?}");
+		assert_eq!(
+			errors[0].message,
+			"[TEST.E.i.1.1b.REQUIRED] observation reported term has no language."
+		);
+		assert_eq!(
+			errors[0].xpath.as_deref(),
+			Some("/hl7:MCCI_IN200100UV01/hl7:observation[2]")
+		);
+		assert_eq!(errors[0].line, Some(6));
+	}
+
+	#[test]
+	fn nodes_are_checked_by_the_first_matching_rule_of_a_pattern() {
+		let rules = r#"<schema xmlns="http://purl.oclc.org/dsdl/schematron">
+	<pattern>
+		<rule context="hl7:observation[hl7:value/hl7:originalText]">
+			<assert id="TEST.TERM" test="true()">term</assert>
+		</rule>
+		<rule context="hl7:observation">
+			<assert id="TEST.NO.TERM" test="false()">no term</assert>
+		</rule>
+	</pattern>
+	<pattern>
+		<rule context="hl7:observation">
+			<report id="TEST.ANY" test="true()">any</report>
+		</rule>
+	</pattern>
+</schema>"#;
+		let (errors, _) = findings(rules, XML);
+		let found: Vec<(&str, Option<&str>)> = errors
+			.iter()
+			.map(|e| (e.message.as_str(), e.xpath.as_deref()))
+			.collect();
+		assert_eq!(
+			found,
+			vec![
+				(
+					"[TEST.NO.TERM] no term",
+					Some("/hl7:MCCI_IN200100UV01/hl7:observation[1]")
+				),
+				(
+					"[TEST.ANY] any",
+					Some("/hl7:MCCI_IN200100UV01/hl7:observation[1]")
+				),
+				(
+					"[TEST.ANY] any",
+					Some("/hl7:MCCI_IN200100UV01/hl7:observation[2]")
+				),
+			]
+		);
+
+		// Patterns of merged rule files stay apart.
+		let mut merged = parse_xml_rules(rules).expect("rules");
+		merged.extend(parse_xml_rules(rules).expect("rules"));
+		let patterns: Vec<(usize, usize)> =
+			merged.rules.iter().map(|r| (r.pattern, r.rule)).collect();
+		assert_eq!(
+			patterns,
+			vec![(0, 0), (0, 1), (1, 2), (2, 3), (2, 4), (3, 5)]
+		);
+	}
+
+	#[test]
+	fn invalid_rule_files_are_rejected() {
+		let missing_id = RULES.replace("id=\"TEST.E.i.1.1a.REQUIRED\"", "");
+		assert!(parse_xml_rules(&missing_id).is_err());
+		let bad_test = RULES.replace("normalize-space(", "normalize-space((");
+		assert!(parse_xml_rules(&bad_test).is_err());
+		assert!(parse_xml_rules("<rules/>").is_err());
+	}
+
+	#[test]
+	fn bundled_ich_rule_file_loads() {
+		let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+			.join("../../../docs/refs/schematron");
+		let rules = load_xml_rule_files(&[path]).expect("bundled rules");
+		assert!(!rules.rules.is_empty());
+	}
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx, demo_org_id,
+	demo_user_id, init_test_mm, Result,
+};
+use lib_core::model::audit_export::{
+	field_changes, sha256_hex, AuditExportBmc, AuditExportParams,
+};
+use lib_core::model::reaction::{ReactionBmc, ReactionForCreate};
+use serde_json::json;
+use serial_test::serial;
+use time::{Date, Duration, OffsetDateTime};
+
+#[test]
+fn update_changes_keep_only_changed_fields() {
+	let changes = field_changes(
+		"UPDATE",
+		Some(&json!({"outcome": "3", "serious": true, "case_id": "a"})),
+		Some(&json!({"outcome": "1", "serious": true, "case_id": "a"})),
+	);
+	assert_eq!(changes.len(), 1);
+	assert_eq!(changes[0].field, "outcome");
+	assert_eq!(changes[0].old_value.as_deref(), Some("3"));
+	assert_eq!(changes[0].new_value.as_deref(), Some("1"));
+
+	let created = field_changes("CREATE", None, Some(&json!({"a": null, "b": 2})));
+	assert_eq!(created.len(), 2);
+	assert_eq!(created[0].new_value, None);
+	assert_eq!(created[1].new_value.as_deref(), Some("2"));
+}
+
+#[serial]
+#[tokio::test]
+async fn case_export_includes_child_rows_and_manifest_hashes() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let reaction_id = ReactionBmc::create(
+		&ctx,
+		&mm,
+		ReactionForCreate {
+			case_id,
+			sequence_number: 1,
+			primary_source_reaction: "Headache, severe".to_string(),
+		},
+	)
+	.await?;
+	ReactionBmc::delete(&ctx, &mm, reaction_id).await?;
+	// The export reads on its own connection, so the rows must be committed.
+	commit_test_ctx(&mm).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let today = OffsetDateTime::now_utc().date();
+	let package = AuditExportBmc::export(
+		&ctx,
+		&mm,
+		AuditExportParams {
+			case_id: Some(case_id),
+			organization_id: None,
+			from: Some(today - Duration::days(1)),
+			to: Some(today),
+		},
+	)
+	.await?;
+	let outside_range = AuditExportBmc::export(
+		&ctx,
+		&mm,
+		AuditExportParams {
+			case_id: Some(case_id),
+			organization_id: None,
+			from: Some(today + Duration::days(1)),
+			to: None,
+		},
+	)
+	.await?;
+	let open_ended = AuditExportBmc::export(
+		&ctx,
+		&mm,
+		AuditExportParams {
+			case_id: Some(case_id),
+			organization_id: None,
+			from: None,
+			to: Some(Date::MAX),
+		},
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	let document: serde_json::Value = serde_json::from_str(&package.json)?;
+	let entries = document["entries"].as_array().expect("entries");
+	assert!(entries
+		.iter()
+		.any(|e| e["table_name"] == "cases" && e["action"] == "CREATE"));
+	// The reaction is gone but its audit rows are still found through case_id.
+	let reaction_actions: Vec<&str> = entries
+		.iter()
+		.filter(|e| e["record_id"] == reaction_id.to_string())
+		.filter_map(|e| e["action"].as_str())
+		.collect();
+	assert_eq!(reaction_actions, vec!["CREATE", "DELETE"]);
+	assert!(entries.iter().all(|e| e["user_name"].is_string()));
+
+	// Values with commas are quoted.
+	assert!(package
+		.csv
+		.starts_with("audit_id,timestamp,user_id,user_name,"));
+	assert!(package
+		.csv
+		.contains(",primary_source_reaction,,\"Headache, severe\"\r\n"));
+
+	assert_eq!(package.manifest.entry_count, entries.len());
+	assert_eq!(package.manifest.files.len(), 2);
+	assert_eq!(
+		package.manifest.files[0].sha256,
+		sha256_hex(package.csv.as_bytes())
+	);
+	assert_eq!(
+		package.manifest.files[1].sha256,
+		sha256_hex(package.json.as_bytes())
+	);
+	assert_eq!(document["from"], (today - Duration::days(1)).to_string());
+
+	assert_eq!(outside_range.manifest.entry_count, 0);
+	assert_eq!(open_ended.manifest.entry_count, entries.len());
+
+	Ok(())
+}
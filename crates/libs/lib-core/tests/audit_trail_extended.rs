@@ -67,6 +67,8 @@ async fn test_audit_trail_drug_information() -> Result<()> {
 		parent_route_termid_version: None,
 		parent_dosage_text: None,
 		fda_additional_info_coded: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	DrugInformationBmc::update_in_case(&ctx, &mm, case_id, drug_id, drug_u).await?;
 	assert_eq!(
@@ -570,6 +572,8 @@ async fn test_audit_log_captures_all_changed_fields() -> Result<()> {
 		parent_route_termid_version: None,
 		parent_dosage_text: None,
 		fda_additional_info_coded: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	DrugInformationBmc::update_in_case(&ctx, &mm, case_id, drug_id, drug_u).await?;
 
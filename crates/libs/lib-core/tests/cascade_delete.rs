@@ -366,6 +366,8 @@ async fn test_drug_delete_cascades_to_dosage_and_substances() -> Result<()> {
 		substance_termid_version: None,
 		strength_value: None,
 		strength_unit: None,
+		kr_ingredient_code_version: None,
+		kr_ingredient_code: None,
 	};
 	let substance_id =
 		DrugActiveSubstanceBmc::create(&ctx, &mm, substance_c).await?;
@@ -0,0 +1,257 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, import_sample_case, init_test_mm,
+	pmda_sample_xml, sample_xml, Result, SAMPLE_SAFETY_REPORT_ID,
+};
+use lib_core::model::case_diff::{
+	diff_documents, CaseDiffBmc, CaseDiffParams, DiffChange, SectionDiff,
+};
+use lib_core::model::follow_up::{CaseFollowUpBmc, CaseFollowUpForCreate};
+use lib_core::xml::{
+	diff_case_version_snapshots, import_e2b_xml, parse_case_document,
+	XmlImportRequest,
+};
+use serial_test::serial;
+use uuid::Uuid;
+
+fn section<'a>(sections: &'a [SectionDiff], name: &str) -> &'a SectionDiff {
+	sections
+		.iter()
+		.find(|s| s.section == name)
+		.expect("section in diff")
+}
+
+#[test]
+fn xml_documents_diff_by_element_number() -> Result<()> {
+	let old_xml = sample_xml(SAMPLE_SAFETY_REPORT_ID);
+	let new_xml = old_xml.replacen(
+		r#"code="3" displayName="not recovered/not resolved/ongoing""#,
+		r#"code="1" displayName="recovered/resolved""#,
+		1,
+	);
+	let old = parse_case_document(old_xml.as_bytes())?;
+	let new = parse_case_document(new_xml.as_bytes())?;
+
+	let sections = diff_documents(&old, &new);
+	let reactions = section(&sections, "E");
+	assert_eq!(reactions.changes.len(), 1, "{reactions:?}");
+	let change = &reactions.changes[0];
+	assert_eq!(change.change, DiffChange::Changed);
+	assert_eq!(change.path, "E.i[1]");
+	assert_eq!(change.fields.len(), 1);
+	assert_eq!(change.fields[0].element, "E.i.7");
+	assert_eq!(change.fields[0].old.as_deref(), Some("3"));
+	assert_eq!(change.fields[0].new.as_deref(), Some("1"));
+	assert!(sections
+		.iter()
+		.filter(|s| s.section != "E")
+		.all(|s| s.changes.is_empty()));
+
+	let unchanged = diff_documents(&old, &old);
+	assert!(unchanged.iter().all(|s| s.changes.is_empty()));
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn follow_up_diff_reports_changed_and_removed_items() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let source_id =
+		import_sample_case(&ctx, &mm, &format!("DIFF-{}", Uuid::new_v4())).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let follow_up_id = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate::default(),
+	)
+	.await?;
+	let (last_reaction,): (i32,) = mm
+		.dbx()
+		.fetch_one(
+			sqlx::query_as(
+				"SELECT MAX(sequence_number) FROM reactions WHERE case_id = $1",
+			)
+			.bind(follow_up_id),
+		)
+		.await?;
+	assert!(last_reaction > 1, "sample has several reactions");
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE reactions SET outcome = '5' WHERE case_id = $1 AND sequence_number = 1",
+			)
+			.bind(follow_up_id),
+		)
+		.await?;
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"DELETE FROM reactions WHERE case_id = $1 AND sequence_number = $2",
+			)
+			.bind(follow_up_id)
+			.bind(last_reaction),
+		)
+		.await?;
+
+	let diff = CaseDiffBmc::diff_versions(
+		&ctx,
+		&mm,
+		follow_up_id,
+		CaseDiffParams::default(),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(diff.from.case_id, source_id);
+	assert_eq!(diff.to.case_id, follow_up_id);
+	assert_eq!(diff.to.version, diff.from.version + 1);
+
+	let reactions = section(&diff.sections, "E");
+	let changed = reactions
+		.changes
+		.iter()
+		.find(|c| c.path == "E.i[1]")
+		.expect("changed reaction");
+	assert_eq!(changed.change, DiffChange::Changed);
+	let outcome = changed
+		.fields
+		.iter()
+		.find(|f| f.element == "E.i.7")
+		.expect("outcome change");
+	assert_eq!(outcome.new.as_deref(), Some("5"));
+
+	let removed_path = format!("E.i[{last_reaction}]");
+	let removed = reactions
+		.changes
+		.iter()
+		.find(|c| c.path == removed_path)
+		.expect("removed reaction");
+	assert_eq!(removed.change, DiffChange::Removed);
+	assert!(removed.fields.iter().all(|f| f.new.is_none()));
+
+	// C.1.5 moves forward with the follow-up.
+	let report = section(&diff.sections, "C");
+	assert!(report
+		.changes
+		.iter()
+		.any(|c| c.fields.iter().any(|f| f.element == "C.1.5")));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn follow_up_diff_reports_pmda_j_elements() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let xml = pmda_sample_xml(
+		&format!("DIFF-JP-{}", Uuid::new_v4()),
+		&[("J.2", "1"), ("J.12", "医療機関からの報告")],
+	);
+	let imported = import_e2b_xml(
+		&ctx,
+		&mm,
+		XmlImportRequest {
+			xml: xml.into_bytes(),
+			filename: None,
+		},
+	)
+	.await?;
+	let source_id = Uuid::parse_str(imported.case_id.as_deref().expect("case_id"))?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let follow_up_id = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate::default(),
+	)
+	.await?;
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE case_jp_elements SET value = '2' WHERE case_id = $1 AND element_id = 'J.2'",
+			)
+			.bind(follow_up_id),
+		)
+		.await?;
+	let diff = CaseDiffBmc::diff_versions(
+		&ctx,
+		&mm,
+		follow_up_id,
+		CaseDiffParams::default(),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	let jp = section(&diff.sections, "J");
+	assert_eq!(jp.changes.len(), 1, "{jp:?}");
+	assert_eq!(jp.changes[0].path, "J.2[1]");
+	assert_eq!(jp.changes[0].change, DiffChange::Changed);
+	assert_eq!(jp.changes[0].fields[0].old.as_deref(), Some("1"));
+	assert_eq!(jp.changes[0].fields[0].new.as_deref(), Some("2"));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn version_xml_diff_shows_follow_up_edits() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let source_id =
+		import_sample_case(&ctx, &mm, &format!("XDIFF-{}", Uuid::new_v4())).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let follow_up_id = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate::default(),
+	)
+	.await?;
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE reactions SET outcome = '5' WHERE case_id = $1 AND sequence_number = 1",
+			)
+			.bind(follow_up_id),
+		)
+		.await?;
+
+	commit_test_ctx(&mm).await?;
+
+	// libxml documents are not `Send`, so the diff runs on a blocking thread
+	// the same way the REST handler drives it.
+	let diff = {
+		let (ctx, mm) = (ctx.clone(), mm.clone());
+		tokio::task::spawn_blocking(move || {
+			tokio::runtime::Handle::current().block_on(diff_case_version_snapshots(
+				&ctx,
+				&mm,
+				follow_up_id,
+				CaseDiffParams::default(),
+			))
+		})
+		.await
+		.expect("diff task")?
+	};
+
+	assert_eq!(diff.from.case_id, source_id);
+	assert_eq!(diff.to.case_id, follow_up_id);
+	let reactions = section(&diff.sections, "E");
+	let outcome = reactions
+		.changes
+		.iter()
+		.filter(|c| c.path == "E.i[1]")
+		.flat_map(|c| &c.fields)
+		.find(|f| f.element == "E.i.7")
+		.expect("outcome change");
+	assert_eq!(outcome.new.as_deref(), Some("5"));
+
+	Ok(())
+}
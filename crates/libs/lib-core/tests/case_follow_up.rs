@@ -0,0 +1,256 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, import_sample_case, init_test_mm,
+	pmda_sample_xml, Result,
+};
+use lib_core::model::case::{CaseBmc, CaseForUpdate};
+use lib_core::model::case_workflow::{CaseWorkflowBmc, TransitionTrigger};
+use lib_core::model::follow_up::{CaseFollowUpBmc, CaseFollowUpForCreate};
+use lib_core::model::jp_element::{CaseJpElement, CaseJpElementBmc};
+use lib_core::model::message_header::MessageHeaderBmc;
+use lib_core::model::safety_report::SafetyReportIdentificationBmc;
+use lib_core::model::{Error, ModelManager};
+use lib_core::xml::{import_e2b_xml, XmlImportRequest};
+use serial_test::serial;
+use time::macros::date;
+use uuid::Uuid;
+
+/// Row counts of a few nested sections, reached through their parents.
+async fn section_counts(mm: &ModelManager, case_id: Uuid) -> Result<Vec<i64>> {
+	let queries = [
+		"SELECT COUNT(*) FROM reactions WHERE case_id = $1",
+		"SELECT COUNT(*) FROM drug_information WHERE case_id = $1",
+		"SELECT COUNT(*) FROM dosage_information d JOIN drug_information g ON g.id = d.drug_id WHERE g.case_id = $1",
+		"SELECT COUNT(*) FROM drug_active_substances s JOIN drug_information g ON g.id = s.drug_id WHERE g.case_id = $1",
+		"SELECT COUNT(*) FROM drug_reaction_assessments a JOIN drug_information g ON g.id = a.drug_id JOIN reactions r ON r.id = a.reaction_id WHERE g.case_id = $1 AND r.case_id = $1",
+		"SELECT COUNT(*) FROM relatedness_assessments ra JOIN drug_reaction_assessments a ON a.id = ra.drug_reaction_assessment_id JOIN drug_information g ON g.id = a.drug_id WHERE g.case_id = $1",
+		"SELECT COUNT(*) FROM patient_information WHERE case_id = $1",
+		"SELECT COUNT(*) FROM medical_history_episodes m JOIN patient_information p ON p.id = m.patient_id WHERE p.case_id = $1",
+		"SELECT COUNT(*) FROM test_results WHERE case_id = $1",
+		"SELECT COUNT(*) FROM narrative_information WHERE case_id = $1",
+		"SELECT COUNT(*) FROM primary_sources WHERE case_id = $1",
+	];
+	let mut counts = Vec::new();
+	for sql in queries {
+		let (count,): (i64,) = mm
+			.dbx()
+			.fetch_one(sqlx::query_as(sql).bind(case_id))
+			.await?;
+		counts.push(count);
+	}
+	Ok(counts)
+}
+
+/// J elements as `(element_id, sequence_number, value)`, without row ids.
+fn jp_values(elements: &[CaseJpElement]) -> Vec<(String, i32, Option<String>)> {
+	elements
+		.iter()
+		.map(|e| (e.element_id.clone(), e.sequence_number, e.value.clone()))
+		.collect()
+}
+
+fn dirty_c_update() -> CaseForUpdate {
+	CaseForUpdate {
+		safety_report_id: None,
+		dg_prd_key: Some("EDITED".to_string()),
+		status: None,
+		validation_profile: None,
+		submitted_by: None,
+		submitted_at: None,
+		raw_xml: None,
+		dirty_c: None,
+		dirty_d: None,
+		dirty_e: None,
+		dirty_f: None,
+		dirty_g: None,
+		dirty_h: None,
+	}
+}
+
+#[serial]
+#[tokio::test]
+async fn follow_up_copies_every_section_into_next_version() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let safety_report_id = format!("FU-{}", Uuid::new_v4());
+	let source_id = import_sample_case(&ctx, &mm, &safety_report_id).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let follow_up_id = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate {
+			date_of_most_recent_information: Some(date!(2024 - 03 - 15)),
+			amendment: true,
+			amendment_reason: Some("Corrected onset date".to_string()),
+			change_reason: None,
+		},
+	)
+	.await?;
+
+	let source = CaseBmc::get(&ctx, &mm, source_id).await?;
+	let follow_up = CaseBmc::get(&ctx, &mm, follow_up_id).await?;
+	assert_eq!(source.superseded_by, Some(follow_up_id));
+	assert_eq!(follow_up.safety_report_id, safety_report_id);
+	assert_eq!(follow_up.version, source.version + 1);
+	assert_eq!(follow_up.status, "draft");
+	assert!(follow_up.dirty_c);
+
+	let source_counts = section_counts(&mm, source_id).await?;
+	assert!(
+		source_counts[..7].iter().all(|count| *count > 0),
+		"{source_counts:?}"
+	);
+	assert_eq!(section_counts(&mm, follow_up_id).await?, source_counts);
+
+	let report =
+		SafetyReportIdentificationBmc::get_by_case(&ctx, &mm, follow_up_id).await?;
+	assert_eq!(
+		report.date_of_most_recent_information,
+		date!(2024 - 03 - 15)
+	);
+	assert_eq!(report.nullification_code.as_deref(), Some("2"));
+	assert_eq!(
+		report.nullification_reason.as_deref(),
+		Some("Corrected onset date")
+	);
+
+	let source_header = MessageHeaderBmc::get_by_case(&ctx, &mm, source_id).await?;
+	let header = MessageHeaderBmc::get_by_case(&ctx, &mm, follow_up_id).await?;
+	assert_ne!(header.message_number, source_header.message_number);
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn follow_up_keeps_pmda_j_elements() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let xml = pmda_sample_xml(
+		&format!("JP-{}", Uuid::new_v4()),
+		&[("J.2", "1"), ("J.4", "1"), ("J.12", "医療機関からの報告")],
+	);
+	let imported = import_e2b_xml(
+		&ctx,
+		&mm,
+		XmlImportRequest {
+			xml: xml.into_bytes(),
+			filename: None,
+		},
+	)
+	.await?;
+	let source_id = Uuid::parse_str(imported.case_id.as_deref().expect("case_id"))?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let follow_up_id = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate::default(),
+	)
+	.await?;
+	let source = CaseJpElementBmc::list_by_case(&ctx, &mm, source_id).await?;
+	let copied = CaseJpElementBmc::list_by_case(&ctx, &mm, follow_up_id).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(source.len(), 3);
+	assert_eq!(jp_values(&copied), jp_values(&source));
+	assert!(copied.iter().all(|e| e.case_id == follow_up_id));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn superseded_version_is_read_only() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let source_id =
+		import_sample_case(&ctx, &mm, &format!("RO-{}", Uuid::new_v4())).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let follow_up_id = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate::default(),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let edit = CaseBmc::update(&ctx, &mm, source_id, dirty_c_update()).await;
+	commit_test_ctx(&mm).await?;
+	assert!(edit.is_err(), "superseded case data must be read-only");
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let section_edit = mm
+		.dbx()
+		.execute(
+			sqlx::query("UPDATE reactions SET serious = TRUE WHERE case_id = $1")
+				.bind(source_id),
+		)
+		.await;
+	commit_test_ctx(&mm).await?;
+	let err = section_edit.expect_err("superseded case sections must be read-only");
+	assert!(err.to_string().contains("read-only"), "{err}");
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let header_edit = mm
+		.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE message_headers SET batch_number = 'EDITED' WHERE case_id = $1",
+			)
+			.bind(source_id),
+		)
+		.await;
+	commit_test_ctx(&mm).await?;
+	let err = header_edit.expect_err("superseded case headers must be read-only");
+	assert!(err.to_string().contains("read-only"), "{err}");
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let again = CaseFollowUpBmc::create(
+		&ctx,
+		&mm,
+		source_id,
+		CaseFollowUpForCreate::default(),
+	)
+	.await;
+	commit_test_ctx(&mm).await?;
+	assert!(matches!(
+		again,
+		Err(Error::CaseSuperseded { superseded_by, .. }) if superseded_by == follow_up_id
+	));
+
+	// The lifecycle of the old version still moves (e.g. a late ACK).
+	begin_test_ctx(&mm, &ctx).await?;
+	CaseBmc::update(
+		&ctx,
+		&mm,
+		source_id,
+		CaseForUpdate {
+			dg_prd_key: None,
+			status: Some("submitted".to_string()),
+			..dirty_c_update()
+		},
+	)
+	.await?;
+	let case = CaseWorkflowBmc::transition(
+		&ctx,
+		&mm,
+		source_id,
+		"acknowledged",
+		TransitionTrigger::Acknowledgment,
+		None,
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(case.status, "acknowledged");
+
+	Ok(())
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{
+	audit_log_count, begin_test_ctx, commit_test_ctx, demo_ctx, force_case_status,
+	import_sample_case, init_test_mm, sample_xml, Result, SAMPLE_SAFETY_REPORT_ID,
+};
+use lib_core::ctx::Ctx;
+use lib_core::model::case::CaseBmc;
+use lib_core::model::safety_report::SafetyReportIdentificationBmc;
+use lib_core::model::{self, ModelManager};
+use lib_core::xml::nullification::minimal_nullification_xml;
+use lib_core::xml::{nullify_case_xml, XmlNullificationResult};
+use serial_test::serial;
+use uuid::Uuid;
+
+/// libxml documents are not `Send`, so the export runs on a blocking thread
+/// the same way the REST handler drives it.
+async fn nullify(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+	reason: &str,
+) -> lib_core::xml::Result<XmlNullificationResult> {
+	let ctx = ctx.clone();
+	let mm = mm.clone();
+	let reason = reason.to_string();
+	tokio::task::spawn_blocking(move || {
+		tokio::runtime::Handle::current()
+			.block_on(nullify_case_xml(&ctx, &mm, case_id, reason))
+	})
+	.await
+	.expect("nullification task")
+}
+
+#[test]
+fn minimal_nullification_keeps_only_identification() -> Result<()> {
+	let xml = minimal_nullification_xml(&sample_xml(SAMPLE_SAFETY_REPORT_ID))?;
+
+	assert!(xml.contains(SAMPLE_SAFETY_REPORT_ID));
+	assert!(xml.contains("PORR_IN049016UV"));
+	assert!(xml.contains("2.16.840.1.113883.3.989.2.1.3.2"));
+	assert!(xml.contains("ICH ReportType"));
+	assert!(xml.contains("assignedEntity"));
+	assert!(!xml.contains("adverseEventAssessment"));
+	assert!(!xml.contains("relatedInvestigation"));
+	assert!(!xml.contains("otherCaseIds"));
+	assert!(!xml.contains("Case Narrative"));
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn nullify_submitted_case_creates_nullification_version() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let case_id =
+		import_sample_case(&ctx, &mm, &format!("NUL-{}", Uuid::new_v4())).await?;
+	begin_test_ctx(&mm, &ctx).await?;
+	force_case_status(&ctx, &mm, case_id, "submitted").await?;
+	commit_test_ctx(&mm).await?;
+
+	let result =
+		nullify(&ctx, &mm, case_id, "Duplicate of an earlier report").await?;
+	assert_eq!(result.case_id, case_id);
+	assert!(result.xml.contains("Duplicate of an earlier report"));
+	assert!(!result.xml.contains("adverseEventAssessment"));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let original = CaseBmc::get(&ctx, &mm, case_id).await?;
+	let nullification =
+		CaseBmc::get(&ctx, &mm, result.nullification_case_id).await?;
+	assert_eq!(original.status, "nullified");
+	assert_eq!(original.superseded_by, Some(nullification.id));
+	assert_eq!(nullification.status, "validated");
+	assert_eq!(nullification.version, original.version + 1);
+	assert_eq!(result.version, nullification.version);
+
+	let report = SafetyReportIdentificationBmc::get_by_case(
+		&ctx,
+		&mm,
+		result.nullification_case_id,
+	)
+	.await?;
+	assert_eq!(report.nullification_code.as_deref(), Some("1"));
+	assert_eq!(
+		report.nullification_reason.as_deref(),
+		Some("Duplicate of an earlier report")
+	);
+	assert_eq!(audit_log_count(&mm, "cases", case_id, "NULLIFY").await?, 1);
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn nullify_rejects_unsubmitted_case() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let case_id =
+		import_sample_case(&ctx, &mm, &format!("NUL-{}", Uuid::new_v4())).await?;
+	begin_test_ctx(&mm, &ctx).await?;
+	force_case_status(&ctx, &mm, case_id, "draft").await?;
+	commit_test_ctx(&mm).await?;
+
+	let result = nullify(&ctx, &mm, case_id, "Entered in error").await;
+	assert!(matches!(
+		result,
+		Err(lib_core::xml::Error::Model(
+			model::Error::CaseTransitionNotAllowed { .. }
+		))
+	));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let case = CaseBmc::get(&ctx, &mm, case_id).await?;
+	assert_eq!(case.superseded_by, None);
+	assert_eq!(case.status, "draft");
+	let (versions,): (i64,) = mm
+		.dbx()
+		.fetch_one(
+			sqlx::query_as("SELECT COUNT(*) FROM cases WHERE safety_report_id = $1")
+				.bind(&case.safety_report_id),
+		)
+		.await?;
+	assert_eq!(versions, 1);
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
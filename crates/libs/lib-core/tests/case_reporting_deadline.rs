@@ -0,0 +1,271 @@
+mod common;
+
+use common::{
+	audit_log_count, begin_test_ctx, commit_test_ctx, demo_ctx, demo_org_id,
+	import_sample_case, init_test_mm, unique_suffix, Result,
+};
+use lib_core::model::reaction::{ReactionBmc, ReactionForUpdate};
+use lib_core::model::reporting_deadline::{
+	compute_reporting_clock, CaseDeadlineFilter, CaseReportingDeadlineBmc,
+	DeadlineBasis, ReportingClockInput,
+};
+use lib_core::model::safety_report::{
+	SafetyReportIdentificationBmc, SafetyReportIdentificationForUpdate,
+};
+use serial_test::serial;
+use time::{Duration, Month, OffsetDateTime};
+use uuid::Uuid;
+
+fn clock_input() -> ReportingClockInput {
+	ReportingClockInput {
+		validation_profile: None,
+		version: 1,
+		report_type: "1".to_string(),
+		date_first_received_from_source: time::Date::from_calendar_date(
+			2026,
+			Month::March,
+			2,
+		)
+		.unwrap(),
+		date_of_most_recent_information: time::Date::from_calendar_date(
+			2026,
+			Month::April,
+			10,
+		)
+		.unwrap(),
+		fulfil_expedited_criteria: true,
+		serious: true,
+		fatal_or_life_threatening: false,
+	}
+}
+
+#[test]
+fn reporting_clock_applies_7_and_15_day_timelines() {
+	let serious = compute_reporting_clock(&clock_input());
+	assert_eq!(serious.basis, DeadlineBasis::Serious);
+	assert_eq!(serious.validation_profile, "fda");
+	assert_eq!(serious.timeline_days, Some(15));
+	assert_eq!(
+		serious.due_date,
+		time::Date::from_calendar_date(2026, Month::March, 17).ok()
+	);
+
+	// Fatal spontaneous reports stay on the 15-day timeline.
+	let spontaneous_fatal = compute_reporting_clock(&ReportingClockInput {
+		fatal_or_life_threatening: true,
+		..clock_input()
+	});
+	assert_eq!(spontaneous_fatal.timeline_days, Some(15));
+
+	let susar = compute_reporting_clock(&ReportingClockInput {
+		validation_profile: Some("MFDS".to_string()),
+		report_type: "2".to_string(),
+		fatal_or_life_threatening: true,
+		..clock_input()
+	});
+	assert_eq!(susar.basis, DeadlineBasis::FatalOrLifeThreatening);
+	assert_eq!(susar.validation_profile, "mfds");
+	assert_eq!(
+		susar.due_date,
+		time::Date::from_calendar_date(2026, Month::March, 9).ok()
+	);
+
+	let follow_up = compute_reporting_clock(&ReportingClockInput {
+		version: 2,
+		..clock_input()
+	});
+	assert_eq!(
+		follow_up.clock_start_date,
+		time::Date::from_calendar_date(2026, Month::April, 10).unwrap()
+	);
+
+	let not_expedited = compute_reporting_clock(&ReportingClockInput {
+		fulfil_expedited_criteria: false,
+		..clock_input()
+	});
+	assert_eq!(not_expedited.basis, DeadlineBasis::NotExpedited);
+	assert_eq!(not_expedited.timeline_days, None);
+	assert_eq!(not_expedited.due_date, None);
+}
+
+#[serial]
+#[tokio::test]
+async fn overdue_imported_case_is_listed() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let safety_report_id = format!("US-DEADLINE-{}", unique_suffix());
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let case_id = import_sample_case(&ctx, &mm, &safety_report_id).await?;
+	let deadline = CaseReportingDeadlineBmc::get_by_case(&ctx, &mm, case_id).await?;
+	assert_eq!(deadline.timeline_days, Some(15));
+	assert_eq!(
+		audit_log_count(&mm, "case_reporting_deadlines", deadline.id, "CREATE")
+			.await?,
+		1
+	);
+
+	let received = OffsetDateTime::now_utc().date() - Duration::days(20);
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE safety_report_identification
+				 SET date_first_received_from_source = $2
+				 WHERE case_id = $1",
+			)
+			.bind(case_id)
+			.bind(received),
+		)
+		.await?;
+	// Writes that bypass the BMCs are picked up by the periodic refresh.
+	CaseReportingDeadlineBmc::refresh_open(&ctx, &mm).await?;
+
+	let due = CaseReportingDeadlineBmc::list_due(
+		&ctx,
+		&mm,
+		CaseDeadlineFilter {
+			within_days: None,
+			organization_id: Some(demo_org_id()),
+		},
+	)
+	.await?;
+	let item = due
+		.iter()
+		.find(|item| item.case_id == case_id)
+		.expect("overdue case listed");
+	assert!(item.overdue);
+	assert_eq!(item.days_remaining, -5);
+	assert_eq!(item.clock_start_date, received);
+
+	// Refreshing an unchanged clock does not write again.
+	let updates =
+		audit_log_count(&mm, "case_reporting_deadlines", deadline.id, "UPDATE")
+			.await?;
+	CaseReportingDeadlineBmc::refresh(&ctx, &mm, case_id).await?;
+	assert_eq!(
+		audit_log_count(&mm, "case_reporting_deadlines", deadline.id, "UPDATE")
+			.await?,
+		updates
+	);
+
+	let other_org = CaseReportingDeadlineBmc::list_due(
+		&ctx,
+		&mm,
+		CaseDeadlineFilter {
+			within_days: Some(30),
+			organization_id: Some(Uuid::new_v4()),
+		},
+	)
+	.await?;
+	assert!(other_org.is_empty());
+
+	let far = CaseReportingDeadlineBmc::list_due(
+		&ctx,
+		&mm,
+		CaseDeadlineFilter {
+			within_days: Some(i64::MAX),
+			organization_id: Some(demo_org_id()),
+		},
+	)
+	.await?;
+	assert!(far.iter().any(|item| item.case_id == case_id));
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn reaction_seriousness_edit_updates_listed_deadline() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let safety_report_id = format!("US-DEADLINE-{}", unique_suffix());
+	let filter = || CaseDeadlineFilter {
+		within_days: Some(30),
+		organization_id: Some(demo_org_id()),
+	};
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let case_id = import_sample_case(&ctx, &mm, &safety_report_id).await?;
+	let received = OffsetDateTime::now_utc().date() - Duration::days(10);
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE safety_report_identification
+				 SET date_first_received_from_source = $2,
+				     fulfil_expedited_criteria = true
+				 WHERE case_id = $1",
+			)
+			.bind(case_id)
+			.bind(received),
+		)
+		.await?;
+	mm.dbx()
+		.execute(
+			sqlx::query(
+				"UPDATE reactions
+				 SET serious = false, criteria_death = false,
+				     criteria_life_threatening = false
+				 WHERE case_id = $1",
+			)
+			.bind(case_id),
+		)
+		.await?;
+	// A study report through the safety report BMC refreshes the clock.
+	SafetyReportIdentificationBmc::update_by_case(
+		&ctx,
+		&mm,
+		case_id,
+		SafetyReportIdentificationForUpdate {
+			transmission_date: None,
+			report_type: Some("2".to_string()),
+			local_criteria_report_type: None,
+			combination_product_report_indicator: None,
+			worldwide_unique_id: None,
+			nullification_code: None,
+			nullification_reason: None,
+			receiver_organization: None,
+		},
+	)
+	.await?;
+
+	let due = CaseReportingDeadlineBmc::list_due(&ctx, &mm, filter()).await?;
+	let item = due
+		.iter()
+		.find(|item| item.case_id == case_id)
+		.expect("case listed");
+	assert_eq!(item.basis, DeadlineBasis::Expedited.as_str());
+	assert_eq!(item.days_remaining, 5);
+	assert!(!item.overdue);
+
+	let reaction = ReactionBmc::list_by_case(&ctx, &mm, case_id)
+		.await?
+		.into_iter()
+		.next()
+		.expect("sample has a reaction");
+	ReactionBmc::update_in_case(
+		&ctx,
+		&mm,
+		case_id,
+		reaction.id,
+		ReactionForUpdate {
+			serious: Some(true),
+			criteria_death: Some(true),
+			..Default::default()
+		},
+	)
+	.await?;
+
+	let due = CaseReportingDeadlineBmc::list_due(&ctx, &mm, filter()).await?;
+	let item = due
+		.iter()
+		.find(|item| item.case_id == case_id)
+		.expect("case listed");
+	assert_eq!(item.basis, DeadlineBasis::FatalOrLifeThreatening.as_str());
+	assert_eq!(item.timeline_days, Some(7));
+	assert_eq!(item.days_remaining, -3);
+	assert!(item.overdue);
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::{
+	audit_log_count, begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx,
+	demo_org_id, demo_user_id, force_case_status, init_test_mm, Result,
+};
+use lib_core::ctx::{Ctx, ROLE_USER};
+use lib_core::model::acs::CASE_APPROVE;
+use lib_core::model::case::{CaseBmc, CaseForUpdate};
+use lib_core::model::case_workflow::{
+	find_transition, next_statuses, CaseWorkflowBmc, TransitionTrigger,
+};
+use lib_core::model::Error;
+use serial_test::serial;
+
+#[test]
+fn transition_table_gates_submission_on_approve() {
+	let submit =
+		find_transition("validated", "submitted", TransitionTrigger::Manual)
+			.expect("validated -> submitted");
+	assert_eq!(submit.permission, CASE_APPROVE);
+	assert_eq!(submit.audit_action, Some("SUBMIT"));
+
+	assert!(
+		find_transition("draft", "submitted", TransitionTrigger::Manual).is_none()
+	);
+	assert!(
+		find_transition("checked", "validated", TransitionTrigger::Manual).is_none()
+	);
+	assert!(
+		find_transition("checked", "validated", TransitionTrigger::Validator)
+			.is_some()
+	);
+	assert_eq!(
+		next_statuses("draft", TransitionTrigger::Manual),
+		vec!["checked"]
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn transition_rejects_skipping_states() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let result = CaseWorkflowBmc::transition(
+		&ctx,
+		&mm,
+		case_id,
+		"archived",
+		TransitionTrigger::Manual,
+		None,
+	)
+	.await;
+	commit_test_ctx(&mm).await?;
+
+	assert!(matches!(
+		result,
+		Err(Error::CaseTransitionNotAllowed { ref from, ref to })
+			if from == "draft" && to == "archived"
+	));
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn submit_requires_approve_and_writes_audit_entries() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let user_ctx = Ctx::new(demo_user_id(), demo_org_id(), ROLE_USER.to_string())?;
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	force_case_status(&ctx, &mm, case_id, "validated").await?;
+	commit_test_ctx(&mm).await?;
+
+	begin_test_ctx(&mm, &user_ctx).await?;
+	let denied = CaseWorkflowBmc::transition(
+		&user_ctx,
+		&mm,
+		case_id,
+		"submitted",
+		TransitionTrigger::Manual,
+		None,
+	)
+	.await;
+	commit_test_ctx(&mm).await?;
+	assert!(matches!(
+		denied,
+		Err(Error::CaseTransitionPermissionDenied { .. })
+	));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let case = CaseWorkflowBmc::transition(
+		&ctx,
+		&mm,
+		case_id,
+		"submitted",
+		TransitionTrigger::Manual,
+		Some("initial submission".to_string()),
+	)
+	.await?;
+	assert_eq!(case.status, "submitted");
+	assert_eq!(case.submitted_by, Some(ctx.user_id()));
+	assert_eq!(audit_log_count(&mm, "cases", case_id, "SUBMIT").await?, 1);
+
+	let case = CaseWorkflowBmc::transition(
+		&ctx,
+		&mm,
+		case_id,
+		"nullified",
+		TransitionTrigger::Manual,
+		Some("duplicate report".to_string()),
+	)
+	.await?;
+	assert_eq!(case.status, "nullified");
+	assert_eq!(audit_log_count(&mm, "cases", case_id, "NULLIFY").await?, 1);
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn refused_transition_discards_field_edits() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let before = CaseBmc::get(&ctx, &mm, case_id).await?;
+	commit_test_ctx(&mm).await?;
+
+	let result = CaseWorkflowBmc::update_with_transition(
+		&ctx,
+		&mm,
+		case_id,
+		Some(CaseForUpdate {
+			safety_report_id: None,
+			dg_prd_key: Some("edited-before-refusal".to_string()),
+			status: None,
+			validation_profile: None,
+			submitted_by: None,
+			submitted_at: None,
+			raw_xml: None,
+			dirty_c: None,
+			dirty_d: None,
+			dirty_e: None,
+			dirty_f: None,
+			dirty_g: None,
+			dirty_h: None,
+		}),
+		"archived",
+		TransitionTrigger::Manual,
+		None,
+	)
+	.await;
+	assert!(matches!(
+		result,
+		Err(Error::CaseTransitionNotAllowed { .. })
+	));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let after = CaseBmc::get(&ctx, &mm, case_id).await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(after.dg_prd_key, before.dg_prd_key);
+	assert_eq!(after.status, "draft");
+	Ok(())
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx, demo_org_id,
+	demo_user_id, init_test_mm, set_current_user, Result,
+};
+use lib_core::model::case::CaseBmc;
+use lib_core::model::patient::{
+	PatientInformationBmc, PatientInformationForCreate, PatientInformationForUpdate,
+};
+use lib_core::model::terminology::E2bCodeListBmc;
+use lib_core::xml::validate::{
+	CODE_LIST_INVALID_RULE, FDA_ETHNICITY, FDA_RACE, KR_ASSESSMENT_METHOD,
+	REPORT_TYPE,
+};
+use serial_test::serial;
+
+fn race_update(race_code: &str) -> PatientInformationForUpdate {
+	PatientInformationForUpdate {
+		patient_initials: None,
+		patient_given_name: None,
+		patient_family_name: None,
+		birth_date: None,
+		age_at_time_of_onset: None,
+		age_unit: None,
+		gestation_period: None,
+		gestation_period_unit: None,
+		age_group: None,
+		race_code: Some(race_code.to_string()),
+		ethnicity_code: Some("C41222".to_string()),
+		weight_kg: None,
+		height_cm: None,
+		sex: None,
+		last_menstrual_period_date: None,
+		medical_history_text: None,
+		concomitant_therapy: None,
+	}
+}
+
+#[serial]
+#[tokio::test]
+async fn catalog_serves_seeded_ich_and_regional_lists() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let codes = E2bCodeListBmc::catalog(&ctx, &mm).await?;
+	let races = E2bCodeListBmc::get_by_list_name(&ctx, &mm, FDA_RACE).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(codes.contains(REPORT_TYPE, "1"), Some(true));
+	assert_eq!(codes.contains(REPORT_TYPE, "9"), Some(false));
+	assert_eq!(codes.contains(FDA_ETHNICITY, "C17459"), Some(true));
+	assert_eq!(codes.contains(KR_ASSESSMENT_METHOD, "2"), Some(true));
+	assert_eq!(codes.contains("no_such_list", "1"), None);
+	assert!(races.iter().all(|r| r.profile == "fda"
+		&& r.oid.as_deref() == Some("2.16.840.1.113883.3.26.1.1")));
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn fda_validation_flags_race_outside_code_list() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+
+	set_current_user(&mm, demo_user_id()).await?;
+	begin_test_ctx(&mm, &ctx).await?;
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+	PatientInformationBmc::create(
+		&ctx,
+		&mm,
+		PatientInformationForCreate {
+			case_id,
+			patient_initials: Some("CL".to_string()),
+			sex: Some("1".to_string()),
+			concomitant_therapy: None,
+		},
+	)
+	.await?;
+
+	PatientInformationBmc::update_by_case(&ctx, &mm, case_id, race_update("C41260"))
+		.await?;
+	let valid =
+		lib_core::xml::fda::validation::validate_case(&ctx, &mm, case_id).await?;
+
+	PatientInformationBmc::update_by_case(&ctx, &mm, case_id, race_update("2106-3"))
+		.await?;
+	let invalid =
+		lib_core::xml::fda::validation::validate_case(&ctx, &mm, case_id).await?;
+
+	PatientInformationBmc::delete_by_case(&ctx, &mm, case_id).await?;
+	CaseBmc::delete(&ctx, &mm, case_id).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert!(!valid
+		.issues
+		.iter()
+		.any(|issue| issue.code == CODE_LIST_INVALID_RULE));
+	let issue = invalid
+		.issues
+		.iter()
+		.find(|issue| issue.code == CODE_LIST_INVALID_RULE)
+		.expect("race code issue");
+	assert_eq!(issue.path, "patientInformation.raceCode");
+	assert!(issue.blocking);
+	assert!(issue.message.contains("[fda_race] '2106-3'"));
+	Ok(())
+}
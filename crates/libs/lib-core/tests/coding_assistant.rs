@@ -0,0 +1,288 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, init_test_mm, unique_suffix, Result,
+};
+use lib_core::model::coding_assistant::{
+	verbatim_key, CodingAcceptance, CodingAssistantBmc, CodingDictionary,
+	CodingMatchSource, CodingSuggestParams, CodingSuggestion,
+};
+use lib_core::model::meddra::MeddraHierarchyBmc;
+use lib_core::model::whodrug::WhodrugIngredientBmc;
+use lib_core::model::Error;
+use serial_test::serial;
+
+/// One PT (Hepatic injury) with a current synonym LLT, a retired LLT, and
+/// an unrelated PT (Rash).
+fn meddra_distribution() -> Vec<(String, Vec<u8>)> {
+	let files = [
+		(
+			"llt.asc",
+			"10000001$Hepatic injury$10000001$$$$$$$Y$$\n\
+			 10000002$Liver injury$10000001$$$$$$$Y$$\n\
+			 10000003$Liver damage$10000001$$$$$$$N$$\n\
+			 10000004$Rash$10000004$$$$$$$Y$$\n",
+		),
+		(
+			"pt.asc",
+			"10000001$Hepatic injury$$10000100$$$$$$$$\n10000004$Rash$$10000100$$$$$$$$\n",
+		),
+		("hlt.asc", "10000010$Hepatic injuries$$$$$$$$\n"),
+		("hlgt.asc", "10000050$Hepatic disorders$$$$$$$$\n"),
+		("soc.asc", "10000100$Hepatobiliary disorders$Hepat$$$$$$$\n"),
+		(
+			"mdhier.asc",
+			"10000001$10000010$10000050$10000100$Hepatic injury$Hepatic injuries$Hepatic disorders$Hepatobiliary disorders$Hepat$$10000100$Y$\n\
+			 10000004$10000010$10000050$10000100$Rash$Hepatic injuries$Hepatic disorders$Hepatobiliary disorders$Hepat$$10000100$Y$\n",
+		),
+	];
+	files
+		.into_iter()
+		.map(|(name, content)| (name.to_string(), content.as_bytes().to_vec()))
+		.collect()
+}
+
+fn dd(drn: &str, seq_1: &str, seq_2: &str, name: &str) -> String {
+	format!("{drn}{seq_1}{seq_2}0T24000010000102N241{name}\n")
+}
+
+/// Paracetamol (000001) with a trade name, and a codeine/paracetamol
+/// combination (000002).
+fn whodrug_distribution(tag: &str) -> Vec<(String, Vec<u8>)> {
+	let files = [
+		(
+			"DD.txt",
+			[
+				dd("000001", "01", "001", &format!("Paracetamol {tag}")),
+				dd("000001", "01", "002", &format!("Tylenol {tag}")),
+				dd("000002", "01", "001", &format!("Co-codamol {tag}")),
+			]
+			.concat(),
+		),
+		(
+			"ING.txt",
+			"20240101000000000100000101\n\
+			 20240101000000000200000201\n\
+			 20240101000000000100000201\n"
+				.to_string(),
+		),
+		(
+			"BNA.txt",
+			format!(
+				"{:<10}{:<10}{:<10}{}\n{:<10}{:<10}{:<10}{}\n",
+				"0000000001",
+				"103-90-2",
+				"ENGLISH",
+				"PARACETAMOL",
+				"0000000002",
+				"76-57-3",
+				"ENGLISH",
+				"CODEINE"
+			),
+		),
+	];
+	files
+		.into_iter()
+		.map(|(name, content)| (name.to_string(), content.into_bytes()))
+		.collect()
+}
+
+fn params(
+	dictionary: CodingDictionary,
+	verbatim: &str,
+	version: &str,
+) -> CodingSuggestParams {
+	CodingSuggestParams {
+		verbatim: verbatim.to_string(),
+		dictionary,
+		version: Some(version.to_string()),
+		language: None,
+		limit: 10,
+	}
+}
+
+fn find<'a>(
+	suggestions: &'a [CodingSuggestion],
+	code: &str,
+) -> &'a CodingSuggestion {
+	suggestions
+		.iter()
+		.find(|s| s.code == code)
+		.unwrap_or_else(|| panic!("no suggestion {code}"))
+}
+
+#[test]
+fn verbatim_key_ignores_case_and_spacing() {
+	assert_eq!(verbatim_key("  Liver\t INJURY \n"), "liver injury");
+	assert_eq!(verbatim_key("   "), "");
+}
+
+#[serial]
+#[tokio::test]
+async fn meddra_suggestions_rank_exact_synonym_and_fuzzy_matches() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let version = format!("S{}", &unique_suffix()[..6]);
+	MeddraHierarchyBmc::load_ascii(
+		&ctx,
+		&mm,
+		Some(&version),
+		None,
+		&meddra_distribution(),
+	)
+	.await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let exact = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(CodingDictionary::Meddra, "LIVER  injury", &version),
+	)
+	.await?;
+	let synonym = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(CodingDictionary::Meddra, "liver damage", &version),
+	)
+	.await?;
+	let fuzzy = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(CodingDictionary::Meddra, "livr injury", &version),
+	)
+	.await?;
+	let none = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(CodingDictionary::Meddra, "  ", &version),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(exact[0].code, "10000002");
+	assert_eq!(exact[0].score, 1.0);
+	assert!(exact[0].sources.contains(&CodingMatchSource::Exact));
+	assert!(exact[0].sources.contains(&CodingMatchSource::FullText));
+	assert_eq!(exact[0].pt_code.as_deref(), Some("10000001"));
+	// Full-text on "injury" also finds the PT-named LLT, ranked below.
+	let hepatic = find(&exact, "10000001");
+	assert!(hepatic.score < 1.0);
+	assert!(exact.iter().all(|s| s.code != "10000003"), "retired LLT");
+
+	// The retired LLT points to the current LLT of its PT.
+	let hepatic = find(&synonym, "10000001");
+	assert!(hepatic.sources.contains(&CodingMatchSource::Synonym));
+	assert_eq!(hepatic.score, 0.9);
+
+	assert_eq!(fuzzy[0].code, "10000002");
+	assert!(fuzzy[0].sources.contains(&CodingMatchSource::Fuzzy));
+	assert!(none.is_empty());
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn accepted_codes_are_remembered_per_organization() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+	let version = format!("M{}", &suffix[..6]);
+	let verbatim = format!("sore liver {}", &suffix[..8]);
+	MeddraHierarchyBmc::load_ascii(
+		&ctx,
+		&mm,
+		Some(&version),
+		None,
+		&meddra_distribution(),
+	)
+	.await?;
+
+	let accept = |code: &str| CodingAcceptance {
+		dictionary: CodingDictionary::Meddra,
+		verbatim: verbatim.clone(),
+		code: code.to_string(),
+		version: Some(version.clone()),
+		language: None,
+	};
+	let first = CodingAssistantBmc::accept(&ctx, &mm, accept("10000002")).await?;
+	assert_eq!(first.accept_count, 1);
+	assert_eq!(first.term, "Liver injury");
+	let second = CodingAssistantBmc::accept(&ctx, &mm, accept("10000002")).await?;
+	assert_eq!(second.id, first.id);
+	assert_eq!(second.accept_count, 2);
+
+	let unknown = CodingAssistantBmc::accept(&ctx, &mm, accept("19999999")).await;
+	assert!(matches!(
+		unknown,
+		Err(Error::CodingTermNotFound { ref code, .. }) if code == "19999999"
+	));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let remembered = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(CodingDictionary::Meddra, &verbatim.to_uppercase(), &version),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	let top = &remembered[0];
+	assert_eq!(top.code, "10000002");
+	assert!(top.sources.contains(&CodingMatchSource::Memory));
+	assert!(top.accept_count >= 2);
+	assert!(top.score > 0.9);
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn whodrug_suggestions_resolve_trade_and_ingredient_names() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+	let tag = &suffix[..8];
+	let version = format!("V{}", &suffix[..6]);
+	WhodrugIngredientBmc::load_b3(
+		&ctx,
+		&mm,
+		&version,
+		None,
+		&whodrug_distribution(tag),
+	)
+	.await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let trade = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(
+			CodingDictionary::Whodrug,
+			&format!("tylenol {tag}"),
+			&version,
+		),
+	)
+	.await?;
+	let ingredient = CodingAssistantBmc::suggest(
+		&ctx,
+		&mm,
+		&params(CodingDictionary::Whodrug, "Paracetamol", &version),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(trade[0].code, "00000101002");
+	assert!(trade[0].sources.contains(&CodingMatchSource::Exact));
+	let generic = find(&trade, "00000101001");
+	assert!(generic.sources.contains(&CodingMatchSource::Synonym));
+
+	// A single-ingredient record is suggested for its substance name; the
+	// codeine combination is not a synonym.
+	let generic = find(&ingredient, "00000101001");
+	assert!(generic.sources.contains(&CodingMatchSource::Synonym));
+	assert!(ingredient
+		.iter()
+		.filter(|s| s.code == "00000201001")
+		.all(|s| !s.sources.contains(&CodingMatchSource::Synonym)));
+	assert!(ingredient.iter().all(|s| s.pt_code.is_none()));
+	Ok(())
+}
@@ -1,9 +1,14 @@
 use lib_core::_dev_utils;
 use lib_core::ctx::Ctx;
+use lib_core::model::case::{CaseBmc, CaseForUpdate};
 use lib_core::model::store::{
 	set_full_context_dbx, set_org_context, set_user_context,
 };
 use lib_core::model::ModelManager;
+use lib_core::xml::pmda::export::{apply_jp_elements, JpElementValue, JpElements};
+use lib_core::xml::{import_e2b_xml, XmlImportRequest};
+use libxml::parser::Parser;
+use libxml::xpath::Context;
 use sqlx::types::Uuid;
 
 pub type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
@@ -118,6 +123,100 @@ pub async fn create_case_fixture(
 	Ok(case_id)
 }
 
+/// C.1.1 of the FAERS2022Scenario1 sample instance.
+#[allow(dead_code)]
+pub const SAMPLE_SAFETY_REPORT_ID: &str = "US-APHARMA-8744554B";
+
+/// The FAERS2022Scenario1 sample with its C.1.1 set to `safety_report_id`.
+#[allow(dead_code)]
+pub fn sample_xml(safety_report_id: &str) -> String {
+	let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.parent()
+		.and_then(|p| p.parent())
+		.and_then(|p| p.parent())
+		.expect("workspace root")
+		.to_path_buf();
+	std::fs::read_to_string(root.join("docs/refs/instances/FAERS2022Scenario1.xml"))
+		.expect("read sample xml")
+		.replace(SAMPLE_SAFETY_REPORT_ID, safety_report_id)
+}
+
+/// [`sample_xml`] carrying the given PMDA J elements as `(element_id, value)`.
+#[allow(dead_code)]
+pub fn pmda_sample_xml(safety_report_id: &str, elements: &[(&str, &str)]) -> String {
+	let parser = Parser::default();
+	let mut doc = parser
+		.parse_string(sample_xml(safety_report_id))
+		.expect("sample xml parses");
+	let mut xpath = Context::new(&doc).expect("xpath context");
+	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+	let jp = JpElements {
+		summaries: Vec::new(),
+		elements: elements
+			.iter()
+			.map(|(element_id, value)| JpElementValue {
+				element_id: element_id.to_string(),
+				value: Some(value.to_string()),
+			})
+			.collect(),
+	};
+	apply_jp_elements(&mut doc, &parser, &mut xpath, &jp).expect("J elements apply");
+	doc.to_string()
+}
+
+/// Imports [`sample_xml`] as a new case and returns its id.
+#[allow(dead_code)]
+pub async fn import_sample_case(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	safety_report_id: &str,
+) -> Result<Uuid> {
+	let result = import_e2b_xml(
+		ctx,
+		mm,
+		XmlImportRequest {
+			xml: sample_xml(safety_report_id).into_bytes(),
+			filename: None,
+		},
+	)
+	.await?;
+	Ok(Uuid::parse_str(
+		result.case_id.as_deref().expect("case_id"),
+	)?)
+}
+
+/// Sets `cases.status` directly, bypassing the workflow transitions.
+#[allow(dead_code)]
+pub async fn force_case_status(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	case_id: Uuid,
+	status: &str,
+) -> Result<()> {
+	CaseBmc::update(
+		ctx,
+		mm,
+		case_id,
+		CaseForUpdate {
+			safety_report_id: None,
+			dg_prd_key: None,
+			status: Some(status.to_string()),
+			validation_profile: None,
+			submitted_by: None,
+			submitted_at: None,
+			raw_xml: None,
+			dirty_c: None,
+			dirty_d: None,
+			dirty_e: None,
+			dirty_f: None,
+			dirty_g: None,
+			dirty_h: None,
+		},
+	)
+	.await?;
+	Ok(())
+}
+
 #[allow(dead_code)]
 pub async fn delete_case_fixture(mm: &ModelManager, case_id: Uuid) -> Result<()> {
 	sqlx::query("DELETE FROM cases WHERE id = $1")
@@ -54,6 +54,8 @@ async fn test_drug_information_crud() -> Result<()> {
 		parent_route_termid_version: None,
 		parent_dosage_text: None,
 		fda_additional_info_coded: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	DrugInformationBmc::update_in_case(&ctx, &mm, case_id, drug_id, drug_u).await?;
 	let drug = DrugInformationBmc::get_in_case(&ctx, &mm, case_id, drug_id).await?;
@@ -94,6 +96,8 @@ async fn test_drug_submodels_crud() -> Result<()> {
 		substance_termid_version: None,
 		strength_value: None,
 		strength_unit: None,
+		kr_ingredient_code_version: None,
+		kr_ingredient_code: None,
 	};
 	let substance_id =
 		DrugActiveSubstanceBmc::create(&ctx, &mm, substance_c).await?;
@@ -106,6 +110,8 @@ async fn test_drug_submodels_crud() -> Result<()> {
 		substance_termid_version: None,
 		strength_value: None,
 		strength_unit: Some("mg".to_string()),
+		kr_ingredient_code_version: None,
+		kr_ingredient_code: None,
 	};
 	DrugActiveSubstanceBmc::update(&ctx, &mm, substance_id, substance_u).await?;
 	let substance = DrugActiveSubstanceBmc::get(&ctx, &mm, substance_id).await?;
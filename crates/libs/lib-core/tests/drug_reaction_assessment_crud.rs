@@ -92,6 +92,9 @@ async fn test_drug_reaction_assessment_crud() -> Result<()> {
 		source_of_assessment: Some("Reporter".to_string()),
 		method_of_assessment: Some("Expert judgement".to_string()),
 		result_of_assessment: Some("Related".to_string()),
+		kr_method_of_assessment: None,
+		kr_result_who_umc: None,
+		kr_result_korean: None,
 	};
 	RelatednessAssessmentBmc::update(&ctx, &mm, related_id, related_u).await?;
 	let related = RelatednessAssessmentBmc::get(&ctx, &mm, related_id).await?;
@@ -691,6 +691,8 @@ async fn test_drug_update_in_wrong_case() -> Result<()> {
 		parent_route_termid_version: None,
 		parent_dosage_text: None,
 		fda_additional_info_coded: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	let result =
 		DrugInformationBmc::update_in_case(&ctx, &mm, case_id_2, drug_id, drug_u)
@@ -0,0 +1,530 @@
+use lib_core::xml::validate::{
+	build_report, check_fda_rules, fda_rule_coverage, find_fda_rule,
+	is_fda_rule_evaluated, FdaRuleBatch, FdaRuleCause, FdaRuleDevice,
+	FdaRuleDocument, FdaRuleDosage, FdaRuleDrug, FdaRulePair, FdaRuleParent,
+	FdaRulePastDrug, FdaRulePrimarySource, FdaRuleReaction, FdaRuleReport,
+	FdaRuleScope, FdaRuleSeverity, FdaRuleTest, FdaRuleValue, ValidationIssue,
+	ValidationProfile, FDA_REJECTION_WARNING_RULES, FDA_RULES_NOT_EVALUATED,
+};
+use lib_core::xml::validate_e2b_xml;
+use sqlx::types::Uuid;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+fn refs_dir() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../docs/refs")
+}
+
+/// Splits one CSV record, honouring quoted fields and doubled quotes.
+fn csv_fields(line: &str) -> Vec<String> {
+	let mut fields = Vec::new();
+	let mut field = String::new();
+	let mut quoted = false;
+	let mut chars = line.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'"' if quoted && chars.peek() == Some(&'"') => {
+				field.push('"');
+				chars.next();
+			}
+			'"' => quoted = !quoted,
+			',' if !quoted => fields.push(std::mem::take(&mut field)),
+			_ => field.push(c),
+		}
+	}
+	fields.push(field);
+	fields
+}
+
+/// CSV records; quoted fields may span lines.
+fn csv_records(content: &str) -> Vec<Vec<String>> {
+	let mut records = Vec::new();
+	let mut pending = String::new();
+	for line in content.lines() {
+		if !pending.is_empty() {
+			pending.push('\n');
+		}
+		pending.push_str(line);
+		if pending.matches('"').count() % 2 == 0 {
+			records.push(csv_fields(&pending));
+			pending.clear();
+		}
+	}
+	records
+}
+
+fn s(value: &str) -> Option<String> {
+	Some(value.to_string())
+}
+
+fn v(value: &str) -> FdaRuleValue {
+	FdaRuleValue::new(s(value))
+}
+
+fn only_a() -> FdaRulePair {
+	FdaRulePair::new(s("1"), None)
+}
+
+fn only_b() -> FdaRulePair {
+	FdaRulePair::new(None, s("1"))
+}
+
+fn both() -> FdaRulePair {
+	FdaRulePair::new(s("27.0"), s("10012345"))
+}
+
+fn report(receiver: &str) -> FdaRuleReport {
+	FdaRuleReport {
+		message_sender: s("SENDER"),
+		message_receiver: s(receiver),
+		..FdaRuleReport::default()
+	}
+}
+
+fn batch(receiver: &str, reports: Vec<FdaRuleReport>) -> FdaRuleBatch {
+	FdaRuleBatch {
+		batch_sender: s("SENDER"),
+		batch_receiver: s(receiver),
+		reports,
+	}
+}
+
+fn ids(batch: &FdaRuleBatch, scope: FdaRuleScope) -> BTreeSet<&'static str> {
+	check_fda_rules(batch, scope)
+		.iter()
+		.map(|hit| hit.rule.id)
+		.collect()
+}
+
+fn routing_batches() -> Vec<FdaRuleBatch> {
+	let mut wrong_sender = report("CDER");
+	wrong_sender.message_sender = s("OTHER");
+	let mut postmarket_ind = report("CBER");
+	postmarket_ind.ind_number = s("123456");
+	postmarket_ind.pre_anda_number = s("654321");
+	postmarket_ind.cross_reported_inds = vec![FdaRuleValue::default()];
+	postmarket_ind.drugs = vec![FdaRuleDrug {
+		characterization: s("1"),
+		..FdaRuleDrug::default()
+	}];
+	let mut ind_product = report("CDER");
+	ind_product.report_type = s("2");
+	ind_product.study_type = s("2");
+	ind_product.drugs = vec![FdaRuleDrug {
+		authorisation_number: s("IND123456"),
+		..FdaRuleDrug::default()
+	}];
+	let mut study_without_type = report("CDER");
+	study_without_type.report_type = s("2");
+	let mut spontaneous_with_study = report("CDER");
+	spontaneous_with_study.report_type = s("1");
+	spontaneous_with_study.study_type = s("1");
+
+	let mut ind_spontaneous = report("CDER_IND");
+	ind_spontaneous.ind_number = s("123456");
+	ind_spontaneous.report_type = s("1");
+	ind_spontaneous.study_type = s("1");
+	let mut ind_study = report("CDER_IND");
+	ind_study.ind_number = s("123456");
+	ind_study.report_type = s("2");
+	ind_study.linked_reports = 1;
+	ind_study.reactions = vec![FdaRuleReaction {
+		death: true,
+		..FdaRuleReaction::default()
+	}];
+	ind_study.drugs = vec![FdaRuleDrug {
+		characterization: s("1"),
+		..FdaRuleDrug::default()
+	}];
+	let mut exempt_spontaneous = report("CDER_IND_EXEMPT_BA_BE");
+	exempt_spontaneous.report_type = s("1");
+	let mut exempt_study = report("CDER_IND_EXEMPT_BA_BE");
+	exempt_study.report_type = s("2");
+	let mut ind_other = report("CDER_IND");
+	ind_other.report_type = s("3");
+	let mut ind_missing = report("CDER_IND");
+	ind_missing.report_type = s("2");
+
+	vec![
+		batch("OTHER", vec![wrong_sender, report("CDER_IND")]),
+		batch(
+			"ZZFDA",
+			vec![
+				postmarket_ind,
+				ind_product,
+				study_without_type,
+				spontaneous_with_study,
+			],
+		),
+		batch(
+			"ZZFDA_PREMKT",
+			vec![
+				report("CDER"),
+				ind_spontaneous,
+				ind_study,
+				exempt_spontaneous,
+				exempt_study,
+				ind_other,
+				ind_missing,
+			],
+		),
+	]
+}
+
+fn content_batch() -> FdaRuleBatch {
+	let past_drugs = vec![
+		FdaRulePastDrug {
+			other_data: true,
+			indication: only_b(),
+			reaction: only_b(),
+			..FdaRulePastDrug::default()
+		},
+		FdaRulePastDrug {
+			name: s("ASPIRIN"),
+			indication: only_a(),
+			reaction: only_a(),
+			..FdaRulePastDrug::default()
+		},
+	];
+	let causes = vec![
+		FdaRuleCause {
+			meddra: only_b(),
+			..FdaRuleCause::default()
+		},
+		FdaRuleCause {
+			meddra: only_a(),
+			..FdaRuleCause::default()
+		},
+		FdaRuleCause {
+			meddra: both(),
+			text: None,
+		},
+	];
+
+	let mut aggregate = report("CDER");
+	aggregate.initial = Some(true);
+	aggregate.documents_available = v("true");
+	aggregate.documents = vec![FdaRuleDocument {
+		title: None,
+		representation: s("TXT"),
+		compression: s("DF"),
+	}];
+	aggregate.expedited.null_flavor = s("NI");
+	aggregate.combination_product = v("true");
+	aggregate.local_criteria_report_type = s("3");
+	aggregate.other_identifiers_available = v("true");
+	aggregate.nullification_code = s("1");
+	aggregate.primary_sources = vec![FdaRulePrimarySource {
+		regulatory: s("1"),
+		..FdaRulePrimarySource::default()
+	}];
+	aggregate.sender_type = s("1");
+	let patient = &mut aggregate.patient;
+	patient.name = v("AGGREGATE");
+	patient.age = only_b();
+	patient.gestation = only_b();
+	patient.medical_history = vec![only_b(), only_a()];
+	patient.past_drugs = past_drugs.clone();
+	patient.death_date = v("20240101");
+	patient.reported_causes = causes.clone();
+	patient.autopsy_causes = causes;
+	patient.parent = Some(FdaRuleParent {
+		age: only_b(),
+		medical_history: vec![only_b(), only_a()],
+		past_drugs,
+		..FdaRuleParent::default()
+	});
+	aggregate.reactions = vec![
+		FdaRuleReaction {
+			term: s("Device malfunction"),
+			meddra_code: s("10067482"),
+			duration: only_b(),
+			..FdaRuleReaction::default()
+		},
+		FdaRuleReaction {
+			duration: only_a(),
+			..FdaRuleReaction::default()
+		},
+	];
+	aggregate.tests = vec![
+		FdaRuleTest {
+			name: s("Haemoglobin"),
+			..FdaRuleTest::default()
+		},
+		FdaRuleTest {
+			date: s("20240101"),
+			..FdaRuleTest::default()
+		},
+		FdaRuleTest {
+			date: s("20240101"),
+			meddra: only_b(),
+			..FdaRuleTest::default()
+		},
+		FdaRuleTest {
+			date: s("20240101"),
+			name: s("Haemoglobin"),
+			result_value: s("12"),
+			..FdaRuleTest::default()
+		},
+	];
+	aggregate.drugs = vec![
+		FdaRuleDrug {
+			characterization: s("2"),
+			..FdaRuleDrug::default()
+		},
+		FdaRuleDrug {
+			characterization: s("4"),
+			substance_strengths: vec![only_a()],
+			dosages: vec![
+				FdaRuleDosage {
+					dose: only_a(),
+					frequency: only_a(),
+					duration: only_b(),
+				},
+				FdaRuleDosage {
+					duration: only_a(),
+					..FdaRuleDosage::default()
+				},
+			],
+			cumulative_dose: only_b(),
+			gestation: only_b(),
+			indications: vec![only_b(), only_a()],
+			start_intervals: vec![only_b(), only_a()],
+			last_dose_intervals: vec![only_b(), only_a()],
+			devices: vec![
+				FdaRuleDevice {
+					malfunction: v("true"),
+					..FdaRuleDevice::default()
+				},
+				FdaRuleDevice {
+					problem_codes: vec!["NOT A CODE".to_string()],
+					brand_name: v("PEN"),
+					common_name: v("Injector"),
+					..FdaRuleDevice::default()
+				},
+			],
+			..FdaRuleDrug::default()
+		},
+	];
+	aggregate.diagnoses = vec![only_b(), only_a()];
+	aggregate.summaries = vec![FdaRulePair::new(s("Summary"), None)];
+
+	let mut expedited = report("CDER");
+	expedited.report_type = s("1");
+	expedited.expedited = v("true");
+	expedited.combination_product = v("true");
+	expedited.local_criteria_report_type = s("3");
+	expedited.other_identifiers = vec![only_a()];
+	expedited.patient.age = only_a();
+	expedited.patient.gestation = only_a();
+	expedited.patient.medical_history_provided = true;
+	expedited.patient.parent = Some(FdaRuleParent {
+		age: only_a(),
+		sex: v("1"),
+		..FdaRuleParent::default()
+	});
+	expedited.drugs = vec![FdaRuleDrug {
+		characterization: s("1"),
+		cumulative_dose: only_a(),
+		gestation: only_a(),
+		..FdaRuleDrug::default()
+	}];
+
+	let mut non_expedited = report("CDER");
+	non_expedited.expedited = v("true");
+	non_expedited.combination_product = v("false");
+	non_expedited.local_criteria_report_type = s("3");
+	let mut neither = report("CDER");
+	neither.expedited = v("false");
+	neither.combination_product = v("false");
+	neither.local_criteria_report_type = s("3");
+	let mut malfunction = report("CDER");
+	malfunction.local_criteria_report_type = s("4");
+	malfunction.drugs = vec![FdaRuleDrug {
+		characterization: s("1"),
+		devices: vec![FdaRuleDevice {
+			malfunction: v("true"),
+			problem_codes: vec!["A0401".to_string()],
+			product_code: s("FMF"),
+			..FdaRuleDevice::default()
+		}],
+		..FdaRuleDrug::default()
+	}];
+	let mut malfunction_only = report("CDER");
+	malfunction_only.local_criteria_report_type = s("5");
+
+	batch(
+		"ZZFDA",
+		vec![
+			aggregate,
+			expedited,
+			non_expedited,
+			neither,
+			malfunction,
+			malfunction_only,
+		],
+	)
+}
+
+#[test]
+fn test_rule_table_matches_published_rules() -> Result<(), Box<dyn Error>> {
+	let content =
+		fs::read_to_string(refs_dir().join("rejection_warning_rules.csv"))?;
+	let mut published = Vec::new();
+	let mut element = String::new();
+	for record in csv_records(&content) {
+		let field = |idx: usize| record.get(idx).map(|f| f.trim()).unwrap_or("");
+		if !field(0).is_empty() {
+			element = field(0).to_string();
+		}
+		let id = field(5);
+		if (id.starts_with('R') || id.starts_with('W'))
+			&& id[1..].parse::<u32>().is_ok()
+		{
+			let severity = if field(3).is_empty() {
+				FdaRuleSeverity::Warning
+			} else {
+				FdaRuleSeverity::Rejection
+			};
+			published.push((id.to_string(), element.clone(), severity));
+		}
+	}
+
+	assert_eq!(published.len(), FDA_REJECTION_WARNING_RULES.len());
+	for ((id, element, severity), rule) in
+		published.iter().zip(FDA_REJECTION_WARNING_RULES)
+	{
+		assert_eq!(rule.id, id);
+		assert_eq!(rule.code, format!("FDA.{id}"));
+		assert_eq!(rule.element, element, "{id} element");
+		assert_eq!(rule.severity, *severity, "{id} severity");
+		assert!(!rule.description.is_empty(), "{id} description");
+	}
+	Ok(())
+}
+
+#[test]
+fn test_coverage_lists_rules_without_an_evaluator() {
+	let coverage = fda_rule_coverage();
+	assert_eq!(coverage.total, FDA_REJECTION_WARNING_RULES.len());
+	let missing: BTreeSet<&str> = coverage.missing.iter().copied().collect();
+	assert_eq!(missing, FDA_RULES_NOT_EVALUATED.iter().copied().collect());
+	assert_eq!(
+		coverage.implemented,
+		coverage.total - FDA_RULES_NOT_EVALUATED.len()
+	);
+	let batch_rule = coverage
+		.rules
+		.iter()
+		.find(|rule| rule.id == "R0100")
+		.expect("R0100 listed");
+	assert!(batch_rule.xml && batch_rule.case);
+}
+
+#[test]
+fn test_every_evaluated_rule_fires_on_violating_batches() {
+	let mut batches = routing_batches();
+	batches.push(content_batch());
+	for scope in [FdaRuleScope::Xml, FdaRuleScope::Case] {
+		let fired: BTreeSet<&str> =
+			batches.iter().flat_map(|batch| ids(batch, scope)).collect();
+		let expected: BTreeSet<&str> = FDA_REJECTION_WARNING_RULES
+			.iter()
+			.map(|rule| rule.id)
+			.filter(|id| is_fda_rule_evaluated(id, scope))
+			.collect();
+		let never_fired: Vec<_> = expected.difference(&fired).collect();
+		let out_of_scope: Vec<_> = fired.difference(&expected).collect();
+		assert!(
+			never_fired.is_empty(),
+			"{scope:?} never fired: {never_fired:?}"
+		);
+		assert!(
+			out_of_scope.is_empty(),
+			"{scope:?} out of scope: {out_of_scope:?}"
+		);
+	}
+}
+
+#[test]
+fn test_batch_rule_flags_report_from_another_sender() {
+	let mut second = report("CDER");
+	second.message_sender = s("OTHER");
+	let batch = batch("ZZFDA", vec![report("CDER"), second]);
+	let hits = check_fda_rules(&batch, FdaRuleScope::Xml);
+	let batch_hits: Vec<_> =
+		hits.iter().filter(|hit| hit.rule.id == "R0100").collect();
+	assert_eq!(batch_hits.len(), 1);
+	assert_eq!(batch_hits[0].report, 1);
+	assert_eq!(batch_hits[0].value.as_deref(), Some("OTHER"));
+}
+
+#[test]
+fn test_case_report_lists_fired_rule_ids() {
+	let batch = batch("ZZFDA", vec![report("CBER")]);
+	let issues: Vec<ValidationIssue> = check_fda_rules(&batch, FdaRuleScope::Case)
+		.iter()
+		.map(|hit| ValidationIssue {
+			code: hit.rule.code.to_string(),
+			message: hit.message(),
+			path: hit.path.clone(),
+			section: hit.rule.section().to_string(),
+			blocking: hit.rule.blocking(),
+		})
+		.collect();
+	let report = build_report(ValidationProfile::Fda, Uuid::nil(), issues);
+	assert!(!report.ok);
+	assert_eq!(report.fda_rule_ids, ["R0006", "R0021"]);
+	assert_eq!(
+		find_fda_rule("R0006").map(|rule| rule.section()),
+		Some("case-identification")
+	);
+}
+
+#[test]
+fn test_faers_scenarios_pass_rejection_rules() -> Result<(), Box<dyn Error>> {
+	let dir = refs_dir().join("instances");
+	let mut checked = 0;
+	for entry in fs::read_dir(&dir)? {
+		let path = entry?.path();
+		let name = path
+			.file_name()
+			.unwrap_or_default()
+			.to_string_lossy()
+			.to_string();
+		if !name.starts_with("FAERS") || !name.ends_with(".xml") {
+			continue;
+		}
+		let xml = fs::read(&path)?;
+		let report = validate_e2b_xml(&xml, None)?;
+		let rejections: Vec<_> = report
+			.errors
+			.iter()
+			.filter(|e| e.message.starts_with("[FDA.R"))
+			.collect();
+		assert!(rejections.is_empty(), "{name}: {rejections:?}");
+		checked += 1;
+	}
+	assert!(checked > 0, "no FAERS scenarios in {}", dir.display());
+	Ok(())
+}
+
+#[test]
+fn test_rejection_rule_fails_xml() -> Result<(), Box<dyn Error>> {
+	let path = refs_dir().join("instances/FAERS2022Scenario1.xml");
+	let xml = fs::read_to_string(path)?
+		.replace("<id extension=\"CDER\"", "<id extension=\"CBER\"");
+	let report = validate_e2b_xml(xml.as_bytes(), None)?;
+	assert!(!report.ok);
+	assert!(
+		report
+			.errors
+			.iter()
+			.any(|e| e.message.starts_with("[FDA.R0006]")),
+		"R0006 not reported: {:?}",
+		report.errors
+	);
+	Ok(())
+}
@@ -94,6 +94,8 @@ async fn test_substance_list_with_limit() -> Result<()> {
 			substance_termid_version: None,
 			strength_value: None,
 			strength_unit: None,
+			kr_ingredient_code_version: None,
+			kr_ingredient_code: None,
 		};
 		let substance_id =
 			DrugActiveSubstanceBmc::create(&ctx, &mm, substance_c).await?;
@@ -151,6 +153,8 @@ async fn test_substance_list_with_offset() -> Result<()> {
 			substance_termid_version: None,
 			strength_value: None,
 			strength_unit: None,
+			kr_ingredient_code_version: None,
+			kr_ingredient_code: None,
 		};
 		let substance_id =
 			DrugActiveSubstanceBmc::create(&ctx, &mm, substance_c).await?;
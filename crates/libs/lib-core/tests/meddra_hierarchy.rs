@@ -0,0 +1,241 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx, demo_org_id,
+	demo_user_id, init_test_mm, unique_suffix, Result,
+};
+use lib_core::model::meddra::{
+	parse_meddra_ascii, MeddraHierarchyBmc, LEVEL_HLT, LEVEL_LLT, LEVEL_PT,
+	LEVEL_SOC,
+};
+use lib_core::model::reaction::{ReactionBmc, ReactionForCreate};
+use lib_core::model::terminology::MeddraTermBmc;
+use lib_core::model::Error;
+use serial_test::serial;
+
+/// A two-SOC miniature release: PT 10000001 sits under both SOCs with the
+/// first as primary; LLT 10000003 is non-current.
+fn distribution(tag: &str) -> Vec<(String, Vec<u8>)> {
+	let files = [
+		("MedAscii/meddra_release.asc", "99.0$English$$$$\n".to_string()),
+		(
+			"MedAscii/llt.asc",
+			format!(
+				"10000001$Hepatic injury {tag}$10000001$$$$$$$Y$$\n\
+				 10000002$Liver damage {tag}$10000001$$$$$$$Y$$\n\
+				 10000003$Liver trouble {tag}$10000001$$$$$$$N$$\n\
+				 10000004$Rash {tag}$10000004$$$$$$$Y$$\n"
+			),
+		),
+		(
+			"MedAscii/pt.asc",
+			format!(
+				"10000001$Hepatic injury {tag}$$10000100$$$$$$$$\n\
+				 10000004$Rash {tag}$$10000200$$$$$$$$\n"
+			),
+		),
+		(
+			"MedAscii/hlt.asc",
+			"10000010$Hepatic injuries$$$$$$$$\n10000020$Rashes$$$$$$$$\n10000030$Drug toxicities$$$$$$$$\n"
+				.to_string(),
+		),
+		(
+			"MedAscii/hlgt.asc",
+			"10000050$Hepatic disorders$$$$$$$$\n10000060$Epidermal conditions$$$$$$$$\n10000070$Toxicities$$$$$$$$\n"
+				.to_string(),
+		),
+		(
+			"MedAscii/soc.asc",
+			"10000100$Hepatobiliary disorders$Hepat$$$$$$$\n10000200$Skin disorders$Skin$$$$$$$\n10000300$Injury and poisoning$Inj&P$$$$$$$\n"
+				.to_string(),
+		),
+		(
+			"MedAscii/mdhier.asc",
+			"10000001$10000010$10000050$10000100$Hepatic injury$Hepatic injuries$Hepatic disorders$Hepatobiliary disorders$Hepat$$10000100$Y$\n\
+			 10000001$10000030$10000070$10000300$Hepatic injury$Drug toxicities$Toxicities$Injury and poisoning$Inj&P$$10000100$N$\n\
+			 10000004$10000020$10000060$10000200$Rash$Rashes$Epidermal conditions$Skin disorders$Skin$$10000200$Y$\n"
+				.to_string(),
+		),
+		("MedAscii/intl_ord.asc", "10000100$1$\n".to_string()),
+	];
+	files
+		.into_iter()
+		.map(|(name, content)| (name.to_string(), content.into_bytes()))
+		.collect()
+}
+
+#[test]
+fn parse_reads_terms_currency_and_primary_soc() {
+	let release = parse_meddra_ascii(&distribution("x")).expect("parse");
+	assert_eq!(release.version.as_deref(), Some("99.0"));
+	assert_eq!(release.language.as_deref(), Some("en"));
+	assert_eq!(release.count(LEVEL_LLT), 4);
+	assert_eq!(release.count(LEVEL_PT), 2);
+	assert_eq!(release.count(LEVEL_HLT), 3);
+	assert_eq!(release.count(LEVEL_SOC), 3);
+
+	let stale = release.terms.iter().find(|t| t.code == "10000003").unwrap();
+	assert!(!stale.is_current);
+	assert_eq!(stale.pt_code.as_deref(), Some("10000001"));
+
+	assert_eq!(release.hierarchy.len(), 3);
+	let primary: Vec<_> = release
+		.hierarchy
+		.iter()
+		.filter(|l| l.pt_code == "10000001" && l.primary_soc)
+		.collect();
+	assert_eq!(primary.len(), 1);
+	assert_eq!(primary[0].soc_code, "10000100");
+}
+
+#[test]
+fn parse_builds_hierarchy_from_link_files_without_mdhier() {
+	let mut files: Vec<_> = distribution("x")
+		.into_iter()
+		.filter(|(name, _)| !name.ends_with("mdhier.asc"))
+		.collect();
+	files.push((
+		"hlt_pt.asc".to_string(),
+		b"10000010$10000001$\n10000030$10000001$\n10000020$10000004$\n".to_vec(),
+	));
+	files.push((
+		"hlgt_hlt.asc".to_string(),
+		b"10000050$10000010$\n10000070$10000030$\n10000060$10000020$\n".to_vec(),
+	));
+	files.push((
+		"SOC_HLGT.ASC".to_string(),
+		b"10000100$10000050$\n10000300$10000070$\n10000200$10000060$\n".to_vec(),
+	));
+
+	let release = parse_meddra_ascii(&files).expect("parse");
+	assert_eq!(release.hierarchy.len(), 3);
+	assert!(release.hierarchy.iter().any(|l| l.pt_code == "10000001"
+		&& l.soc_code == "10000100"
+		&& l.primary_soc));
+	assert!(release.hierarchy.iter().any(|l| l.pt_code == "10000001"
+		&& l.soc_code == "10000300"
+		&& !l.primary_soc));
+}
+
+#[test]
+fn parse_reports_missing_and_malformed_files() {
+	let files: Vec<_> = distribution("x")
+		.into_iter()
+		.filter(|(name, _)| !name.ends_with("llt.asc"))
+		.collect();
+	assert!(matches!(
+		parse_meddra_ascii(&files),
+		Err(Error::TerminologyLoad { ref file, .. }) if file == "llt.asc"
+	));
+
+	let mut files = distribution("x");
+	files.push(("pt.asc".to_string(), b"10000001$$$\n".to_vec()));
+	assert!(matches!(
+		parse_meddra_ascii(&files),
+		Err(Error::TerminologyLoad { ref file, ref message }) if file == "pt.asc" && message.starts_with("line 1")
+	));
+}
+
+#[serial]
+#[tokio::test]
+async fn load_search_and_group_reactions_by_soc() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+	let tag = &suffix[..8];
+	let version = format!("T{}", &suffix[..6]);
+
+	let result = MeddraHierarchyBmc::load_ascii(
+		&ctx,
+		&mm,
+		Some(&version),
+		None,
+		&distribution(tag),
+	)
+	.await?;
+	assert_eq!(result.version, version);
+	assert_eq!(result.language, "en");
+	assert_eq!(result.llt_count, 4);
+	assert_eq!(result.hierarchy_count, 3);
+
+	// Reloading the same version replaces it instead of failing on duplicates.
+	MeddraHierarchyBmc::load_ascii(
+		&ctx,
+		&mm,
+		Some(&version),
+		None,
+		&distribution(tag),
+	)
+	.await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let hits = MeddraTermBmc::search_with_hierarchy(
+		&ctx,
+		&mm,
+		&format!("Liver damage {tag}"),
+		Some(&version),
+		10,
+	)
+	.await?;
+	assert_eq!(hits.len(), 1);
+	let path = hits[0].primary_path.as_ref().expect("primary path");
+	assert_eq!(path.pt_code, "10000001");
+	assert_eq!(
+		path.pt_term.as_deref(),
+		Some(format!("Hepatic injury {tag}").as_str())
+	);
+	assert_eq!(path.soc_term.as_deref(), Some("Hepatobiliary disorders"));
+
+	let paths =
+		MeddraHierarchyBmc::paths_for_code(&ctx, &mm, "10000003", Some(&version))
+			.await?;
+	assert_eq!(paths.len(), 2);
+	assert!(paths[0].primary_soc);
+	assert_eq!(paths[1].soc_code, "10000300");
+
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+	let mut reaction_ids = Vec::new();
+	for (seq, code) in [
+		(1, Some("10000002")),
+		(2, Some("10000004")),
+		(3, None),
+		(4, Some("10000001")),
+	] {
+		let id = ReactionBmc::create(
+			&ctx,
+			&mm,
+			ReactionForCreate {
+				case_id,
+				sequence_number: seq,
+				primary_source_reaction: format!("reaction {seq}"),
+			},
+		)
+		.await?;
+		mm.dbx()
+			.execute(
+				sqlx::query(
+					"UPDATE reactions SET reaction_meddra_code = $2, reaction_meddra_version = $3 WHERE id = $1",
+				)
+				.bind(id)
+				.bind(code)
+				.bind(&version),
+			)
+			.await?;
+		reaction_ids.push(id);
+	}
+
+	let groups = MeddraHierarchyBmc::reactions_by_soc(&ctx, &mm, case_id).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(groups.len(), 3);
+	assert_eq!(groups[0].soc_code.as_deref(), Some("10000100"));
+	assert_eq!(
+		groups[0].reaction_ids,
+		vec![reaction_ids[0], reaction_ids[3]]
+	);
+	assert_eq!(groups[1].soc_term.as_deref(), Some("Skin disorders"));
+	assert_eq!(groups[1].reaction_ids, vec![reaction_ids[1]]);
+	assert_eq!(groups[2].soc_code, None);
+	assert_eq!(groups[2].reaction_ids, vec![reaction_ids[2]]);
+	Ok(())
+}
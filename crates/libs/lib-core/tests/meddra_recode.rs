@@ -0,0 +1,223 @@
+mod common;
+
+use common::{
+	audit_log_count, begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx,
+	demo_org_id, demo_user_id, init_test_mm, unique_suffix, Result,
+};
+use lib_core::model::meddra::MeddraHierarchyBmc;
+use lib_core::model::meddra_recode::{
+	MeddraCodedField, MeddraRecodeBmc, MeddraRecodeParams, MeddraRecodeReason,
+};
+use lib_core::model::reaction::{ReactionBmc, ReactionForCreate, ReactionForUpdate};
+use lib_core::model::test_result::{
+	TestResultBmc, TestResultForCreate, TestResultForUpdate,
+};
+use lib_core::model::Error;
+use serial_test::serial;
+
+/// A one-SOC release; `llts` are `(code, pt_code, currency)` rows.
+fn distribution(llts: &[(&str, &str, &str)]) -> Vec<(String, Vec<u8>)> {
+	let llt = llts
+		.iter()
+		.map(|(code, pt, current)| {
+			format!("{code}$LLT {code}${pt}$$$$$$${current}$$\n")
+		})
+		.collect::<String>();
+	let files = [
+		("llt.asc", llt),
+		(
+			"pt.asc",
+			"10000001$Hepatic injury$$10000100$$$$$$$$\n10000004$Rash$$10000100$$$$$$$$\n"
+				.to_string(),
+		),
+		("hlt.asc", "10000010$Hepatic injuries$$$$$$$$\n".to_string()),
+		("hlgt.asc", "10000050$Hepatic disorders$$$$$$$$\n".to_string()),
+		(
+			"soc.asc",
+			"10000100$Hepatobiliary disorders$Hepat$$$$$$$\n".to_string(),
+		),
+		(
+			"mdhier.asc",
+			"10000001$10000010$10000050$10000100$Hepatic injury$Hepatic injuries$Hepatic disorders$Hepatobiliary disorders$Hepat$$10000100$Y$\n\
+			 10000004$10000010$10000050$10000100$Rash$Hepatic injuries$Hepatic disorders$Hepatobiliary disorders$Hepat$$10000100$Y$\n"
+				.to_string(),
+		),
+	];
+	files
+		.into_iter()
+		.map(|(name, content)| (name.to_string(), content.into_bytes()))
+		.collect()
+}
+
+#[serial]
+#[tokio::test]
+async fn recode_maps_stale_codes_with_dry_run_and_audit() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+	let old_version = format!("A{}", &suffix[..6]);
+	let new_version = format!("B{}", &suffix[..6]);
+
+	MeddraHierarchyBmc::load_ascii(
+		&ctx,
+		&mm,
+		Some(&old_version),
+		None,
+		&distribution(&[
+			("10000001", "10000001", "Y"),
+			("10000002", "10000001", "Y"),
+			("10000003", "10000001", "Y"),
+			("10000004", "10000004", "Y"),
+		]),
+	)
+	.await?;
+	// The new release retires LLT 10000003 and drops LLT 10000002.
+	MeddraHierarchyBmc::load_ascii(
+		&ctx,
+		&mm,
+		Some(&new_version),
+		None,
+		&distribution(&[
+			("10000001", "10000001", "Y"),
+			("10000003", "10000001", "N"),
+			("10000004", "10000004", "Y"),
+		]),
+	)
+	.await?;
+
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+	let mut reaction_ids = Vec::new();
+	for (seq, code) in [(1, "10000003"), (2, "10000004"), (3, "10000002")] {
+		let id = ReactionBmc::create(
+			&ctx,
+			&mm,
+			ReactionForCreate {
+				case_id,
+				sequence_number: seq,
+				primary_source_reaction: format!("reaction {seq}"),
+			},
+		)
+		.await?;
+		ReactionBmc::update(
+			&ctx,
+			&mm,
+			id,
+			ReactionForUpdate {
+				reaction_meddra_version: Some(old_version.clone()),
+				reaction_meddra_code: Some(code.to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+		reaction_ids.push(id);
+	}
+	let test_id = TestResultBmc::create(
+		&ctx,
+		&mm,
+		TestResultForCreate {
+			case_id,
+			sequence_number: 1,
+			test_name: "ALT".to_string(),
+		},
+	)
+	.await?;
+	TestResultBmc::update(
+		&ctx,
+		&mm,
+		test_id,
+		TestResultForUpdate {
+			test_meddra_version: Some(old_version.clone()),
+			test_meddra_code: Some("19999999".to_string()),
+			..Default::default()
+		},
+	)
+	.await?;
+
+	let params = |dry_run| MeddraRecodeParams {
+		target_version: new_version.clone(),
+		language: None,
+		dry_run,
+		case_ids: Some(vec![case_id]),
+	};
+
+	let preview = MeddraRecodeBmc::run(&ctx, &mm, params(true)).await?;
+	assert!(preview.dry_run);
+	assert_eq!(preview.cases_scanned, 1);
+	assert_eq!(preview.codes_scanned, 4);
+	assert_eq!(preview.changed, 3);
+	assert_eq!(preview.unresolved, 1);
+	let report = &preview.cases[0];
+	assert_eq!(report.case_id, case_id);
+	let change = |id| report.changes.iter().find(|c| c.record_id == id).unwrap();
+	assert_eq!(change(reaction_ids[0]).new_code, "10000001");
+	assert_eq!(
+		change(reaction_ids[0]).reason,
+		MeddraRecodeReason::NonCurrentLlt
+	);
+	assert_eq!(change(reaction_ids[1]).new_code, "10000004");
+	assert_eq!(
+		change(reaction_ids[1]).reason,
+		MeddraRecodeReason::VersionUpdate
+	);
+	assert_eq!(change(reaction_ids[2]).new_code, "10000001");
+	assert_eq!(
+		change(reaction_ids[2]).reason,
+		MeddraRecodeReason::NotInTargetVersion
+	);
+	assert_eq!(report.unresolved[0].record_id, test_id);
+	assert_eq!(report.unresolved[0].field, MeddraCodedField::TestName);
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let untouched = ReactionBmc::get(&ctx, &mm, reaction_ids[0]).await?;
+	let updates =
+		audit_log_count(&mm, "reactions", reaction_ids[0], "UPDATE").await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(untouched.reaction_meddra_code.as_deref(), Some("10000003"));
+	assert_eq!(updates, 1);
+
+	let applied = MeddraRecodeBmc::run(&ctx, &mm, params(false)).await?;
+	assert_eq!(applied.changed, 3);
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let recoded = ReactionBmc::get(&ctx, &mm, reaction_ids[0]).await?;
+	let stale_test = TestResultBmc::get(&ctx, &mm, test_id).await?;
+	let updates =
+		audit_log_count(&mm, "reactions", reaction_ids[0], "UPDATE").await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(recoded.reaction_meddra_code.as_deref(), Some("10000001"));
+	assert_eq!(
+		recoded.reaction_meddra_version.as_deref(),
+		Some(new_version.as_str())
+	);
+	assert_eq!(stale_test.test_meddra_code.as_deref(), Some("19999999"));
+	assert_eq!(updates, 2);
+
+	// Everything is on the target version now.
+	let again = MeddraRecodeBmc::run(&ctx, &mm, params(true)).await?;
+	assert_eq!(again.changed, 0);
+	assert_eq!(again.unresolved, 1);
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn recode_rejects_version_not_loaded() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let result = MeddraRecodeBmc::run(
+		&ctx,
+		&mm,
+		MeddraRecodeParams {
+			target_version: "0.0".to_string(),
+			language: None,
+			dry_run: true,
+			case_ids: None,
+		},
+	)
+	.await;
+	assert!(matches!(
+		result,
+		Err(Error::MeddraVersionNotLoaded { ref version, .. }) if version == "0.0"
+	));
+	Ok(())
+}
@@ -117,6 +117,8 @@ async fn test_parent_history_crud() -> Result<()> {
 		indication_meddra_code: Some("10078901".to_string()),
 		reaction_meddra_version: None,
 		reaction_meddra_code: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	ParentPastDrugHistoryBmc::update(&ctx, &mm, past_drug_id, past_drug_u).await?;
 	let past_drug = ParentPastDrugHistoryBmc::get(&ctx, &mm, past_drug_id).await?;
@@ -0,0 +1,214 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx, demo_org_id,
+	demo_user_id, init_test_mm, Result,
+};
+use lib_core::model::partial_date::{DatePrecision, PartialDate};
+use lib_core::model::reaction::{
+	Reaction, ReactionBmc, ReactionForCreate, ReactionForUpdate,
+};
+use lib_core::xml::export_sections::e_reaction::export_e_reactions_xml;
+use lib_core::xml::import_sections::e_reaction::parse_e_reactions;
+use lib_core::xml::validate_e2b_xml;
+use serial_test::serial;
+use sqlx::types::Uuid;
+use time::{Date, Month, OffsetDateTime};
+
+fn sample_xml() -> String {
+	let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.parent()
+		.and_then(|p| p.parent())
+		.and_then(|p| p.parent())
+		.expect("workspace root")
+		.to_path_buf();
+	std::fs::read_to_string(root.join("docs/refs/instances/FAERS2022Scenario1.xml"))
+		.expect("read sample xml")
+}
+
+#[test]
+fn parse_keeps_precision_and_round_trips() {
+	let cases = [
+		("2023", DatePrecision::Year),
+		("202311", DatePrecision::Month),
+		("20231105", DatePrecision::Day),
+		("202311051230+0900", DatePrecision::Minute),
+		("20231105123045-0500", DatePrecision::Second),
+	];
+	for (value, precision) in cases {
+		let date = PartialDate::parse(value).expect(value);
+		assert_eq!(date.precision(), precision, "{value}");
+		assert_eq!(date.to_string(), value);
+	}
+
+	let iso = PartialDate::parse("2023-11").expect("iso month");
+	assert_eq!(iso.to_string(), "202311");
+	let fraction = PartialDate::parse("20231105123045.123+0100").expect("fraction");
+	assert_eq!(fraction.to_string(), "20231105123045+0100");
+
+	for invalid in [
+		"",
+		"23",
+		"20231",
+		"20231301",
+		"20230230",
+		"2023110525",
+		"2023x",
+	] {
+		assert!(PartialDate::parse(invalid).is_none(), "{invalid}");
+	}
+}
+
+#[test]
+fn precision_checks_and_bounds() {
+	let month = PartialDate::parse("202402").unwrap();
+	assert!(month.meets(DatePrecision::Year));
+	assert!(!month.meets(DatePrecision::Day));
+	assert_eq!(month.to_date(), None);
+	assert_eq!(
+		month.earliest(),
+		Date::from_calendar_date(2024, Month::February, 1).unwrap()
+	);
+	assert_eq!(
+		month.latest(),
+		Date::from_calendar_date(2024, Month::February, 29).unwrap()
+	);
+	assert_eq!(DatePrecision::parse("CCYYMMDD"), Some(DatePrecision::Day));
+}
+
+#[test]
+fn serde_uses_e2b_string_and_accepts_legacy_dates() {
+	let date = PartialDate::parse("2023").unwrap();
+	assert_eq!(serde_json::to_string(&date).unwrap(), "\"2023\"");
+
+	let parsed: PartialDate = serde_json::from_str("\"202311\"").unwrap();
+	assert_eq!(parsed.precision(), DatePrecision::Month);
+	let legacy: PartialDate = serde_json::from_str("[2024, 32]").unwrap();
+	assert_eq!(legacy.to_string(), "20240201");
+	assert!(serde_json::from_str::<PartialDate>("\"20231\"").is_err());
+}
+
+#[test]
+fn import_and_export_keep_partial_reaction_dates() {
+	let xml =
+		sample_xml().replace("<low value=\"20141010\"/>", "<low value=\"201410\"/>");
+	let reactions = parse_e_reactions(xml.as_bytes()).expect("parse");
+	let start_date = reactions[0].start_date.expect("start date");
+	assert_eq!(start_date.precision(), DatePrecision::Month);
+
+	let reaction = Reaction {
+		id: Uuid::new_v4(),
+		case_id: Uuid::new_v4(),
+		sequence_number: 1,
+		primary_source_reaction: "Headache".to_string(),
+		reaction_language: None,
+		reaction_meddra_version: Some("24.1".to_string()),
+		reaction_meddra_code: Some("10019211".to_string()),
+		term_highlighted: None,
+		serious: Some(false),
+		criteria_death: false,
+		criteria_life_threatening: false,
+		criteria_hospitalization: false,
+		criteria_disabling: false,
+		criteria_congenital_anomaly: false,
+		criteria_other_medically_important: false,
+		required_intervention: None,
+		start_date: Some(start_date),
+		end_date: None,
+		duration_value: None,
+		duration_unit: None,
+		outcome: Some("1".to_string()),
+		medical_confirmation: None,
+		country_code: None,
+		created_at: OffsetDateTime::now_utc(),
+		updated_at: OffsetDateTime::now_utc(),
+		created_by: Uuid::new_v4(),
+		updated_by: None,
+	};
+	let exported = export_e_reactions_xml(&[reaction]).expect("export");
+	assert!(exported.contains("<low value=\"201410\"/>"), "{exported}");
+}
+
+#[test]
+fn validation_flags_imprecise_c_1_5() {
+	let xml = sample_xml();
+	let has_precision_error = |report: &lib_core::xml::XmlValidationReport| {
+		report
+			.errors
+			.iter()
+			.any(|e| e.message.contains("[ICH.C.1.5.PRECISION]"))
+	};
+
+	let report = validate_e2b_xml(xml.as_bytes(), None).expect("validate");
+	assert!(!has_precision_error(&report));
+
+	let imprecise = xml.replace(
+		"<availabilityTime value=\"20220614101010-0500\"/>",
+		"<availabilityTime value=\"202206\"/>",
+	);
+	let report = validate_e2b_xml(imprecise.as_bytes(), None).expect("validate");
+	assert!(has_precision_error(&report), "{:?}", report.errors);
+	assert!(!report.ok);
+}
+
+#[serial]
+#[tokio::test]
+async fn partial_dates_persist_with_their_precision() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let reaction_id = ReactionBmc::create(
+		&ctx,
+		&mm,
+		ReactionForCreate {
+			case_id,
+			sequence_number: 1,
+			primary_source_reaction: "Rash".to_string(),
+		},
+	)
+	.await?;
+	ReactionBmc::update_in_case(
+		&ctx,
+		&mm,
+		case_id,
+		reaction_id,
+		ReactionForUpdate {
+			primary_source_reaction: None,
+			reaction_meddra_code: None,
+			reaction_meddra_version: None,
+			reaction_language: None,
+			term_highlighted: None,
+			serious: None,
+			criteria_death: None,
+			criteria_life_threatening: None,
+			criteria_hospitalization: None,
+			criteria_disabling: None,
+			criteria_congenital_anomaly: None,
+			criteria_other_medically_important: None,
+			required_intervention: None,
+			country_code: None,
+			start_date: PartialDate::parse("2023"),
+			end_date: PartialDate::parse("202311"),
+			duration_value: None,
+			duration_unit: None,
+			medical_confirmation: None,
+			outcome: None,
+		},
+	)
+	.await?;
+	let reaction = ReactionBmc::get_in_case(&ctx, &mm, case_id, reaction_id).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(
+		reaction.start_date.map(|d| d.to_string()).as_deref(),
+		Some("2023")
+	);
+	assert_eq!(
+		reaction.end_date.map(|d| d.precision()),
+		Some(DatePrecision::Month)
+	);
+
+	Ok(())
+}
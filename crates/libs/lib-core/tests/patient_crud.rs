@@ -132,6 +132,8 @@ async fn test_patient_submodels_crud() -> Result<()> {
 		indication_meddra_code: None,
 		reaction_meddra_version: None,
 		reaction_meddra_code: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	let past_id = PastDrugHistoryBmc::create(&ctx, &mm, past_c).await?;
 	let past = PastDrugHistoryBmc::get(&ctx, &mm, past_id).await?;
@@ -149,6 +151,8 @@ async fn test_patient_submodels_crud() -> Result<()> {
 		indication_meddra_code: None,
 		reaction_meddra_version: None,
 		reaction_meddra_code: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 	};
 	PastDrugHistoryBmc::update(&ctx, &mm, past_id, past_u).await?;
 	let past = PastDrugHistoryBmc::get(&ctx, &mm, past_id).await?;
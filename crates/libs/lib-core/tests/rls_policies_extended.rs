@@ -282,6 +282,8 @@ async fn test_rls_case_related_tables_org_isolation() -> Result<()> {
 			indication_meddra_code: None,
 			reaction_meddra_version: None,
 			reaction_meddra_code: None,
+			kr_product_code_version: None,
+			kr_product_code: None,
 		},
 	)
 	.await?;
@@ -302,6 +304,8 @@ async fn test_rls_case_related_tables_org_isolation() -> Result<()> {
 			indication_meddra_code: None,
 			reaction_meddra_version: None,
 			reaction_meddra_code: None,
+			kr_product_code_version: None,
+			kr_product_code: None,
 		},
 	)
 	.await?;
@@ -401,6 +405,8 @@ async fn test_rls_case_related_tables_org_isolation() -> Result<()> {
 			substance_termid_version: None,
 			strength_value: None,
 			strength_unit: None,
+			kr_ingredient_code_version: None,
+			kr_ingredient_code: None,
 		},
 	)
 	.await?;
@@ -415,6 +421,8 @@ async fn test_rls_case_related_tables_org_isolation() -> Result<()> {
 			substance_termid_version: None,
 			strength_value: None,
 			strength_unit: None,
+			kr_ingredient_code_version: None,
+			kr_ingredient_code: None,
 		},
 	)
 	.await?;
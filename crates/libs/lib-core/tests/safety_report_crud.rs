@@ -108,6 +108,7 @@ async fn test_safety_report_submodels_crud() -> Result<()> {
 		telephone: None,
 		fax: None,
 		email: None,
+		kr_health_professional_type: None,
 	};
 	SenderInformationBmc::update(&ctx, &mm, sender_id, sender_u).await?;
 	let sender = SenderInformationBmc::get(&ctx, &mm, sender_id).await?;
@@ -138,6 +139,7 @@ async fn test_safety_report_submodels_crud() -> Result<()> {
 		email: None,
 		qualification: None,
 		primary_source_regulatory: Some("1".to_string()),
+		kr_other_health_professional: None,
 	};
 	PrimarySourceBmc::update(&ctx, &mm, primary_id, primary_u).await?;
 	let primary = PrimarySourceBmc::get(&ctx, &mm, primary_id).await?;
@@ -181,6 +183,7 @@ async fn test_safety_report_submodels_crud() -> Result<()> {
 		study_name: Some("Study B".to_string()),
 		sponsor_study_number: None,
 		study_type_reaction: Some("01".to_string()),
+		kr_other_study_type: None,
 	};
 	StudyInformationBmc::update(&ctx, &mm, study_id, study_u).await?;
 	let study = StudyInformationBmc::get(&ctx, &mm, study_id).await?;
@@ -0,0 +1,152 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, demo_user_id, init_test_mm,
+	unique_suffix, Result, DEMO_ROLE,
+};
+use lib_core::ctx::Ctx;
+use lib_core::model::organization::{OrganizationBmc, OrganizationForCreate};
+use lib_core::model::terminology::E2bCodeListBmc;
+use lib_core::model::terminology_version::{
+	parse_code_lists, validate_terminology_package, TerminologyActivationForCreate,
+	TerminologyDictionary, TerminologyPackage, TerminologyVersionBmc,
+	CODE_LISTS_FILE,
+};
+use lib_core::model::Error;
+use serial_test::serial;
+
+fn code_list_package(list_name: &str, version: &str) -> TerminologyPackage {
+	let content = format!(
+		"list_name\tcode\tdisplay_name\tdescription\tsort_order\n\
+		 {list_name}\t1\tYes\t\t1\n\
+		 {list_name}\t2\tNo\tNegative answer\t2\n"
+	);
+	TerminologyPackage {
+		dictionary: TerminologyDictionary::E2bCodeLists,
+		version: Some(version.to_string()),
+		language: None,
+		files: vec![(format!("pkg/{CODE_LISTS_FILE}"), content.into_bytes())],
+	}
+}
+
+#[test]
+fn validate_counts_code_lists_and_rejects_bad_rows() {
+	let package = code_list_package("yes_no", "T1");
+	let validation = validate_terminology_package(&package).expect("validate");
+	assert_eq!(validation.version.as_deref(), Some("T1"));
+	assert_eq!(validation.counts.get("code"), Some(&2));
+
+	let codes = parse_code_lists(&package.files).expect("parse");
+	assert_eq!(codes[1].description.as_deref(), Some("Negative answer"));
+	assert_eq!(codes[1].sort_order, Some(2));
+	assert_eq!(codes[1].profile, "ich");
+
+	let files = vec![(
+		CODE_LISTS_FILE.to_string(),
+		b"yes_no\t1\tYes\nyes_no\t1\tAgain\n".to_vec(),
+	)];
+	assert!(matches!(
+		parse_code_lists(&files),
+		Err(Error::TerminologyLoad { ref message, .. }) if message.starts_with("line 2")
+	));
+
+	let files = vec![("countries.txt".to_string(), b"US\tUnited States\n".to_vec())];
+	assert!(matches!(
+		parse_code_lists(&files),
+		Err(Error::TerminologyLoad { ref file, .. }) if file == CODE_LISTS_FILE
+	));
+}
+
+#[serial]
+#[tokio::test]
+async fn import_is_inactive_until_the_organization_activates_it() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+	let list_name = format!("test_list_{}", &suffix[..8]);
+	let first = format!("A{}", &suffix[..8]);
+	let second = format!("B{}", &suffix[..8]);
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let org_id = OrganizationBmc::create(
+		&ctx,
+		&mm,
+		OrganizationForCreate {
+			name: format!("Terminology Org {suffix}"),
+			org_type: Some("internal".to_string()),
+			address: None,
+			contact_email: None,
+		},
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+	let org_ctx = Ctx::new(demo_user_id(), org_id, DEMO_ROLE.to_string())?;
+
+	let loaded = TerminologyVersionBmc::import(
+		&ctx,
+		&mm,
+		code_list_package(&list_name, &first),
+	)
+	.await?;
+	assert_eq!(loaded.dictionary, "e2b_code_lists");
+	assert_eq!(loaded.language, "en");
+	TerminologyVersionBmc::import(&ctx, &mm, code_list_package(&list_name, &second))
+		.await?;
+
+	// Registered versions are not served until activated.
+	begin_test_ctx(&mm, &org_ctx).await?;
+	let codes = E2bCodeListBmc::get_by_list_name(&org_ctx, &mm, &list_name).await?;
+	commit_test_ctx(&mm).await?;
+	assert!(codes.is_empty());
+
+	let again = TerminologyVersionBmc::import(
+		&ctx,
+		&mm,
+		code_list_package(&list_name, &first),
+	)
+	.await;
+	assert!(matches!(
+		again,
+		Err(Error::TerminologyVersionExists { ref version, .. }) if *version == first
+	));
+
+	let activate = |version: &str| TerminologyActivationForCreate {
+		dictionary: TerminologyDictionary::E2bCodeLists,
+		version: version.to_string(),
+		language: None,
+		organization_id: Some(org_id),
+	};
+	TerminologyVersionBmc::activate(&ctx, &mm, activate(&first)).await?;
+	let activation =
+		TerminologyVersionBmc::activate(&ctx, &mm, activate(&second)).await?;
+	assert_eq!(activation.organization_id, org_id);
+	assert_eq!(activation.previous_version.as_deref(), Some(first.as_str()));
+
+	begin_test_ctx(&mm, &org_ctx).await?;
+	let codes = E2bCodeListBmc::get_by_list_name(&org_ctx, &mm, &list_name).await?;
+	let active = TerminologyVersionBmc::active_version(
+		&org_ctx,
+		&mm,
+		TerminologyDictionary::E2bCodeLists,
+	)
+	.await?;
+	let history = TerminologyVersionBmc::list_activations(
+		&org_ctx,
+		&mm,
+		Some(TerminologyDictionary::E2bCodeLists),
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(codes.len(), 2);
+	assert_eq!(active, Some(second.clone()));
+	let versions: Vec<_> = history.iter().map(|a| a.version.as_str()).collect();
+	assert_eq!(versions, vec![second.as_str(), first.as_str()]);
+	assert!(history.iter().all(|a| a.created_by == demo_user_id()));
+
+	let missing = TerminologyVersionBmc::activate(&ctx, &mm, activate("nope")).await;
+	assert!(matches!(
+		missing,
+		Err(Error::TerminologyVersionNotFound { ref version, .. }) if version == "nope"
+	));
+	Ok(())
+}
@@ -0,0 +1,111 @@
+use lib_core::model::ucum::{convert_ucum, is_valid_ucum, normalize_ucum, UcumUnit};
+use lib_core::model::Error;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+fn dec(value: &str) -> Decimal {
+	Decimal::from_str(value).expect("decimal")
+}
+
+#[test]
+fn parses_e2b_dose_and_lab_units() {
+	for unit in [
+		"mg",
+		"mg/kg",
+		"mg/dL",
+		"10*3/uL",
+		"10*9/L",
+		"mmol/L",
+		"[iU]/kg",
+		"mL/min/{1.73_m2}",
+		"mg/m2",
+		"ug/(kg.min)",
+		"/min",
+		"{tbl}",
+		"%",
+		"mm[Hg]",
+		"Cel",
+		"s-1",
+		"{decade}",
+	] {
+		assert!(is_valid_ucum(unit), "{unit} should be valid");
+	}
+	for unit in [
+		"", "mcg", "MG", "mg/", "kmin", "mg//kg", "[iU", "{tbl", "mg kg",
+	] {
+		assert!(!is_valid_ucum(unit), "{unit} should be invalid");
+	}
+}
+
+#[test]
+fn normalizes_case_and_synonyms() -> lib_core::model::Result<()> {
+	let cases = [
+		("mg/dl", "mg/dL"),
+		("MG/DL", "mg/dL"),
+		("MG", "mg"),
+		("ML", "mL"),
+		("MMOL/L", "mmol/L"),
+		("mcg", "ug"),
+		("\u{00b5}g/kg", "ug/kg"),
+		("mcg/kg/min", "ug/kg/min"),
+		("IU", "[iU]"),
+		("[IU]/mL", "[iU]/mL"),
+		("10*3/ul", "10*3/uL"),
+		("cc", "mL"),
+		(" hrs ", "h"),
+		("Days", "d"),
+		("Decade", "{decade}"),
+		("mmHg", "mm[Hg]"),
+		("min", "min"),
+	];
+	for (raw, expected) in cases {
+		assert_eq!(normalize_ucum(raw)?, expected, "normalizing {raw}");
+	}
+	assert!(matches!(
+		normalize_ucum("tablets please"),
+		Err(Error::UcumInvalidUnit { .. })
+	));
+	Ok(())
+}
+
+#[test]
+fn classifies_time_units() -> lib_core::model::Result<()> {
+	for unit in ["a", "mo", "wk", "d", "h", "min", "s", "{decade}"] {
+		assert!(UcumUnit::parse(unit)?.is_time(), "{unit} is time");
+	}
+	assert!(!UcumUnit::parse("mg")?.is_time());
+	assert!(!UcumUnit::parse("/d")?.is_time());
+	Ok(())
+}
+
+#[test]
+fn converts_commensurable_units() -> lib_core::model::Result<()> {
+	assert_eq!(convert_ucum(dec("180"), "mg/dL", "g/L")?, dec("1.8"));
+	assert_eq!(convert_ucum(dec("1.5"), "h", "min")?, dec("90"));
+	assert_eq!(convert_ucum(dec("2"), "wk", "d")?, dec("14"));
+	assert_eq!(convert_ucum(dec("500"), "ug", "mg")?, dec("0.5"));
+	assert_eq!(convert_ucum(dec("5"), "10*3/uL", "10*9/L")?, dec("5"));
+	assert_eq!(convert_ucum(dec("37"), "Cel", "[degF]")?, dec("98.6"));
+	assert_eq!(convert_ucum(dec("0"), "Cel", "K")?, dec("273.15"));
+	Ok(())
+}
+
+#[test]
+fn rejects_incompatible_conversions() {
+	assert!(matches!(
+		convert_ucum(dec("1"), "mg", "mL"),
+		Err(Error::UcumNotConvertible { .. })
+	));
+	assert!(matches!(
+		convert_ucum(dec("1"), "[iU]", "mg"),
+		Err(Error::UcumNotConvertible { .. })
+	));
+	assert!(matches!(
+		convert_ucum(dec("1"), "Cel/h", "K/h"),
+		Err(Error::UcumNotConvertible { .. })
+	));
+	assert!(matches!(
+		convert_ucum(dec("1"), "mg", "mgg"),
+		Err(Error::UcumInvalidUnit { .. })
+	));
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, demo_user_id, init_test_mm,
+	unique_suffix, Result, DEMO_ROLE,
+};
+use lib_core::ctx::Ctx;
+use lib_core::model::organization::{OrganizationBmc, OrganizationForCreate};
+use lib_core::model::validation_rule::{
+	ValidationRuleBmc, ValidationRuleForSave, ValidationRuleOverrideBmc,
+	ValidationRuleOverrideForSet,
+};
+use lib_core::model::Error;
+use lib_core::xml::validate::{
+	load_rule_set, save_validation_rule, set_validation_rule_override,
+	ValidationIssue,
+};
+use serial_test::serial;
+
+const RULE: &str = "ICH.D.1.REQUIRED";
+
+fn initials_issue(message: &str) -> ValidationIssue {
+	ValidationIssue {
+		code: RULE.to_string(),
+		message: message.to_string(),
+		path: "patientInformation.patientInitials".to_string(),
+		section: "patient".to_string(),
+		blocking: true,
+	}
+}
+
+#[serial]
+#[tokio::test]
+async fn organization_override_changes_severity_and_version() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let org_id = OrganizationBmc::create(
+		&ctx,
+		&mm,
+		OrganizationForCreate {
+			name: format!("Validation Rules Org {suffix}"),
+			org_type: Some("internal".to_string()),
+			address: None,
+			contact_email: None,
+		},
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+	let org_ctx = Ctx::new(demo_user_id(), org_id, DEMO_ROLE.to_string())?;
+
+	begin_test_ctx(&mm, &org_ctx).await?;
+	let before = load_rule_set(&org_ctx, &mm).await?;
+	commit_test_ctx(&mm).await?;
+	let default = before.rule(RULE).expect("compiled rule");
+	assert!(!default.overridden);
+	let default_blocking = default.blocking;
+	let default_message = default.message.clone();
+
+	let saved = set_validation_rule_override(
+		&ctx,
+		&mm,
+		RULE,
+		ValidationRuleOverrideForSet {
+			blocking: !default_blocking,
+			organization_id: Some(org_id),
+		},
+	)
+	.await?;
+	assert_eq!(saved.organization_id, org_id);
+	assert!(saved.revision > 0);
+
+	begin_test_ctx(&mm, &org_ctx).await?;
+	let after = load_rule_set(&org_ctx, &mm).await?;
+	commit_test_ctx(&mm).await?;
+	let rule = after.rule(RULE).expect("rule");
+	assert!(rule.overridden);
+	assert_eq!(rule.blocking, !default_blocking);
+	assert!(after.revision() >= saved.revision);
+	assert_ne!(after.version(None), before.version(None));
+	let issues = after.apply(vec![initials_issue(&default_message)]);
+	assert_eq!(issues[0].blocking, !default_blocking);
+
+	// Other organizations keep the default severity.
+	begin_test_ctx(&mm, &ctx).await?;
+	let demo = load_rule_set(&ctx, &mm).await?;
+	commit_test_ctx(&mm).await?;
+	assert!(!demo.rule(RULE).expect("rule").overridden);
+
+	assert!(ValidationRuleOverrideBmc::clear(&ctx, &mm, RULE, Some(org_id)).await?);
+	assert!(!ValidationRuleOverrideBmc::clear(&ctx, &mm, RULE, Some(org_id)).await?);
+
+	begin_test_ctx(&mm, &org_ctx).await?;
+	let cleared = load_rule_set(&org_ctx, &mm).await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(cleared.rule(RULE).expect("rule").blocking, default_blocking);
+	assert_eq!(cleared.version(None), before.version(None));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn invalid_definitions_and_unknown_rules_are_rejected() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let code = format!("TEST.{}", &unique_suffix()[..8]);
+	let rule = |condition: &str| ValidationRuleForSave {
+		profile: "ich".to_string(),
+		section: "patient".to_string(),
+		blocking: true,
+		message: "Test rule".to_string(),
+		xpath: None,
+		condition: Some(condition.to_string()),
+		active: Some(true),
+	};
+
+	let bad_condition =
+		save_validation_rule(&ctx, &mm, &code, rule("always &&")).await;
+	assert!(matches!(
+		bad_condition,
+		Err(Error::ValidationRuleInvalid { code: ref c, .. }) if *c == code
+	));
+	let bad_profile = save_validation_rule(
+		&ctx,
+		&mm,
+		&code,
+		ValidationRuleForSave {
+			profile: "unknown".to_string(),
+			..rule("always")
+		},
+	)
+	.await;
+	assert!(matches!(
+		bad_profile,
+		Err(Error::ValidationRuleInvalid { .. })
+	));
+
+	let unknown = set_validation_rule_override(
+		&ctx,
+		&mm,
+		&code,
+		ValidationRuleOverrideForSet {
+			blocking: false,
+			organization_id: None,
+		},
+	)
+	.await;
+	assert!(matches!(unknown, Err(Error::ValidationRuleInvalid { .. })));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let stored = ValidationRuleBmc::list(&ctx, &mm).await?;
+	commit_test_ctx(&mm).await?;
+	assert!(stored.iter().all(|rule| rule.code != code));
+
+	Ok(())
+}
@@ -0,0 +1,230 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, create_case_fixture, demo_ctx, demo_org_id,
+	demo_user_id, init_test_mm, unique_suffix, Result,
+};
+use lib_core::model::drug::{
+	DrugActiveSubstanceBmc, DrugActiveSubstanceForCreate,
+	DrugActiveSubstanceFromWhodrug, DrugInformationBmc, DrugInformationForCreate,
+};
+use lib_core::model::terminology::WhodrugProductBmc;
+use lib_core::model::whodrug::{parse_whodrug_b3, WhodrugIngredientBmc};
+use lib_core::model::Error;
+use serial_test::serial;
+
+fn dd(drn: &str, seq_1: &str, seq_2: &str, name: &str) -> String {
+	// drn, seq 1, seq 2, check digit, designation, source year, source,
+	// company, ingredient count, salt/ester, year quarter, drug name
+	format!("{drn}{seq_1}{seq_2}0T24000010000102N241{name}\n")
+}
+
+/// Paracetamol (000001) with a trade name that inherits its ingredient and
+/// ATC code, and a codeine/paracetamol combination (000002).
+fn distribution(tag: &str) -> Vec<(String, Vec<u8>)> {
+	let files = [
+		(
+			"B3/DD.txt",
+			[
+				dd("000001", "01", "001", &format!("Paracetamol {tag}")),
+				dd("000001", "01", "002", &format!("Tylenol {tag}")),
+				dd("000002", "01", "001", &format!("Co-codamol {tag}")),
+			]
+			.concat(),
+		),
+		(
+			"B3/ING.txt",
+			"20240101000000000100000101\n\
+			 20240101000000000200000201\n\
+			 20240101000000000100000201\n"
+				.to_string(),
+		),
+		(
+			"B3/BNA.txt",
+			format!(
+				"{:<10}{:<10}{:<10}{}\n{:<10}{:<10}{:<10}{}\n",
+				"0000000001",
+				"103-90-2",
+				"ENGLISH",
+				"PARACETAMOL",
+				"0000000002",
+				"76-57-3",
+				"ENGLISH",
+				"CODEINE"
+			),
+		),
+		(
+			"B3/DDA.txt",
+			"000001010010N02BE01241Y\n000002010010N02AJ06241Y\n".to_string(),
+		),
+		(
+			"B3/INA.txt",
+			"N      1NERVOUS SYSTEM\n\
+			 N02    2ANALGESICS\n\
+			 N02B   3OTHER ANALGESICS AND ANTIPYRETICS\n\
+			 N02BE  4ANILIDES\n\
+			 N02BE015PARACETAMOL\n"
+				.to_string(),
+		),
+	];
+	files
+		.into_iter()
+		.map(|(name, content)| (name.to_string(), content.into_bytes()))
+		.collect()
+}
+
+#[test]
+fn parse_b3_resolves_ingredients_and_atc_for_trade_names() {
+	let release = parse_whodrug_b3(&distribution("x")).expect("parse");
+	assert_eq!(release.records.len(), 3);
+	assert_eq!(release.records[1].code(), "00000101002");
+	assert_eq!(release.records[1].drug_name, "Tylenol x");
+
+	let of = |code: &str| {
+		release
+			.ingredients
+			.iter()
+			.filter(|i| i.product_code == code)
+			.map(|i| i.substance_name.as_str())
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(of("00000101002"), vec!["PARACETAMOL"]);
+	assert_eq!(of("00000201001"), vec!["CODEINE", "PARACETAMOL"]);
+	assert_eq!(
+		release.ingredients[0].cas_number.as_deref(),
+		Some("103-90-2")
+	);
+
+	let trade_atc = release
+		.product_atc
+		.iter()
+		.find(|a| a.product_code == "00000101002")
+		.expect("inherited ATC");
+	assert_eq!(trade_atc.atc_code, "N02BE01");
+	assert!(trade_atc.official);
+	assert_eq!(release.atc_classes.len(), 5);
+}
+
+#[test]
+fn parse_b3_reports_missing_files_and_unknown_substances() {
+	let files: Vec<_> = distribution("x")
+		.into_iter()
+		.filter(|(name, _)| !name.ends_with("BNA.txt"))
+		.collect();
+	assert!(matches!(
+		parse_whodrug_b3(&files),
+		Err(Error::TerminologyLoad { ref file, .. }) if file == "BNA.txt"
+	));
+
+	let mut files = distribution("x");
+	files.push((
+		"ing.txt".to_string(),
+		b"20240101000000009900000101\n".to_vec(),
+	));
+	assert!(matches!(
+		parse_whodrug_b3(&files),
+		Err(Error::TerminologyLoad { ref message, .. }) if message.starts_with("line 1")
+	));
+}
+
+#[serial]
+#[tokio::test]
+async fn load_search_and_fill_active_substances() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = unique_suffix();
+	let tag = &suffix[..8];
+	let version = format!("W{}", &suffix[..6]);
+
+	let result =
+		WhodrugIngredientBmc::load_b3(&ctx, &mm, &version, None, &distribution(tag))
+			.await?;
+	assert_eq!(result.product_count, 3);
+	assert_eq!(result.ingredient_count, 4);
+	assert_eq!(result.atc_class_count, 5);
+	// Reloading the same version replaces it.
+	WhodrugIngredientBmc::load_b3(&ctx, &mm, &version, None, &distribution(tag))
+		.await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let hits = WhodrugProductBmc::search_with_ingredients(
+		&ctx,
+		&mm,
+		&format!("Tylenol {tag}"),
+		Some(&version),
+		10,
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(hits.len(), 1);
+	let hit = &hits[0];
+	assert_eq!(hit.product.drug_record_number.as_deref(), Some("000001"));
+	assert_eq!(hit.product.atc_code.as_deref(), Some("N02BE01"));
+	assert_eq!(hit.ingredients.len(), 1);
+	assert_eq!(hit.ingredients[0].substance_name, "PARACETAMOL");
+	assert_eq!(hit.atc.len(), 1);
+	let path: Vec<_> = hit.atc[0].path.iter().map(|c| c.level).collect();
+	assert_eq!(path, vec![1, 2, 3, 4, 5]);
+	assert_eq!(hit.atc[0].path[1].text, "ANALGESICS");
+
+	let case_id = create_case_fixture(&mm, demo_org_id(), demo_user_id()).await?;
+	let drug_id = DrugInformationBmc::create(
+		&ctx,
+		&mm,
+		DrugInformationForCreate {
+			case_id,
+			sequence_number: 1,
+			drug_characterization: "1".to_string(),
+			medicinal_product: format!("Co-codamol {tag}"),
+		},
+	)
+	.await?;
+	DrugActiveSubstanceBmc::create(
+		&ctx,
+		&mm,
+		DrugActiveSubstanceForCreate {
+			drug_id,
+			sequence_number: 1,
+			substance_name: Some("Codeine".to_string()),
+			substance_termid: None,
+			substance_termid_version: None,
+			strength_value: None,
+			strength_unit: None,
+			kr_ingredient_code_version: None,
+			kr_ingredient_code: None,
+		},
+	)
+	.await?;
+
+	let substances = DrugActiveSubstanceBmc::create_from_whodrug(
+		&ctx,
+		&mm,
+		drug_id,
+		DrugActiveSubstanceFromWhodrug {
+			code: "00000201001".to_string(),
+			version: Some(version.clone()),
+		},
+	)
+	.await?;
+	let names: Vec<_> = substances
+		.iter()
+		.map(|s| (s.sequence_number, s.substance_name.as_deref().unwrap()))
+		.collect();
+	assert_eq!(names, vec![(1, "Codeine"), (2, "PARACETAMOL")]);
+
+	let missing = DrugActiveSubstanceBmc::create_from_whodrug(
+		&ctx,
+		&mm,
+		drug_id,
+		DrugActiveSubstanceFromWhodrug {
+			code: "99999901001".to_string(),
+			version: Some(version),
+		},
+	)
+	.await;
+	assert!(matches!(
+		missing,
+		Err(Error::WhodrugProductNotFound { ref code, .. }) if code == "99999901001"
+	));
+	Ok(())
+}
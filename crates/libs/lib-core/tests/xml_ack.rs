@@ -0,0 +1,425 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, force_case_status,
+	import_sample_case, init_test_mm, sample_xml, Result, SAMPLE_SAFETY_REPORT_ID,
+};
+use lib_core::model::acknowledgment::CaseAcknowledgmentBmc;
+use lib_core::model::case::CaseBmc;
+use lib_core::model::message_header::{MessageHeaderBmc, MessageHeaderForUpdate};
+use lib_core::xml::ack::{
+	batch_ack_code_for_import, case_status_for_ack_code, AckBatch, AckMessage,
+};
+use lib_core::xml::{
+	build_import_ack_xml, import_ack_xml, import_e2b_xml_batch,
+	import_e2b_xml_batch_with_ack, parse_ack_xml, render_ack_xml,
+	XmlAckGenerationRequest, XmlBatchImportResult, XmlImportReportError,
+	XmlImportReportResult, XmlImportRequest, XmlValidationError,
+	XmlValidationReport,
+};
+use serial_test::serial;
+use uuid::Uuid;
+
+const POSITIVE_TARGET: &str =
+	"21ec1492-3aea-4069-a2ab-08002b30309d US-APHARMA-8744554B";
+const NEGATIVE_TARGET: &str = "US-APHARMA-7644554";
+
+fn instance(name: &str) -> String {
+	let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.parent()
+		.and_then(|p| p.parent())
+		.and_then(|p| p.parent())
+		.expect("workspace root")
+		.to_path_buf();
+	std::fs::read_to_string(root.join("docs/refs/instances").join(name))
+		.expect("read instance")
+}
+
+/// Imports the sample as a new case and marks it submitted.
+async fn import_submitted_case(
+	ctx: &lib_core::ctx::Ctx,
+	mm: &lib_core::model::ModelManager,
+	safety_report_id: &str,
+) -> Result<Uuid> {
+	let case_id = import_sample_case(ctx, mm, safety_report_id).await?;
+	force_case_status(ctx, mm, case_id, "submitted").await?;
+	Ok(case_id)
+}
+
+#[test]
+fn parse_positive_ack_sample() -> Result<()> {
+	let ack = parse_ack_xml(instance("R3ack-positive.ack").as_bytes())?;
+	assert_eq!(ack.ack_batch_number.as_deref(), Some("33439257"));
+	assert_eq!(ack.batch_ack_code.as_deref(), Some("AA"));
+	assert_eq!(ack.messages.len(), 1);
+	let message = &ack.messages[0];
+	assert_eq!(message.ack_code.as_deref(), Some("CA"));
+	assert_eq!(message.local_report_number.as_deref(), Some("19426648"));
+	assert_eq!(
+		message.identifiers(),
+		vec![
+			"21ec1492-3aea-4069-a2ab-08002b30309d".to_string(),
+			SAMPLE_SAFETY_REPORT_ID.to_string()
+		]
+	);
+	Ok(())
+}
+
+#[test]
+fn parse_negative_ack_sample() -> Result<()> {
+	let ack = parse_ack_xml(instance("R3ack-negative.ack").as_bytes())?;
+	assert_eq!(ack.batch_ack_code.as_deref(), Some("AE"));
+	assert_eq!(
+		ack.batch_number.as_deref(),
+		Some("FAERS2022Scenario2-4069-a2dd-08002b30309d")
+	);
+	assert_eq!(
+		ack.batch_error_text.as_deref(),
+		Some("some ICSR message(s) need further action")
+	);
+	let message = &ack.messages[0];
+	assert_eq!(message.ack_code.as_deref(), Some("CR"));
+	assert!(message
+		.error_text
+		.as_deref()
+		.unwrap_or_default()
+		.contains("SafetyCasePriority"));
+	Ok(())
+}
+
+#[test]
+fn parse_ack_rejects_other_roots() {
+	let err = parse_ack_xml(instance("FAERS2022Scenario1.xml").as_bytes())
+		.expect_err("ICSR batch is not an ACK");
+	assert!(format!("{err}").contains("UnsupportedRoot"));
+}
+
+#[serial]
+#[tokio::test]
+async fn import_ack_updates_case_status() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = Uuid::new_v4();
+	let accepted_id = format!("ACK-OK-{suffix}");
+	let rejected_id = format!("ACK-NO-{suffix}");
+	let accepted = import_submitted_case(&ctx, &mm, &accepted_id).await?;
+	let rejected = import_submitted_case(&ctx, &mm, &rejected_id).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let header = MessageHeaderBmc::get_by_case(&ctx, &mm, accepted).await?;
+	let positive = instance("R3ack-positive.ack").replace(
+		POSITIVE_TARGET,
+		&format!("{} {accepted_id}", header.message_number),
+	);
+	let negative = instance("R3ack-negative.ack").replace(NEGATIVE_TARGET, &rejected_id);
+
+	let result = import_ack_xml(&ctx, &mm, positive.as_bytes()).await?;
+	assert_eq!(result.matched, 1);
+	assert_eq!(result.messages[0].case_id, Some(accepted));
+	assert_eq!(result.messages[0].case_status.as_deref(), Some("acknowledged"));
+
+	let result = import_ack_xml(&ctx, &mm, negative.as_bytes()).await?;
+	assert_eq!(result.matched, 1);
+	assert_eq!(result.messages[0].case_id, Some(rejected));
+	assert_eq!(result.messages[0].case_status.as_deref(), Some("rejected"));
+
+	assert_eq!(CaseBmc::get(&ctx, &mm, accepted).await?.status, "acknowledged");
+	assert_eq!(CaseBmc::get(&ctx, &mm, rejected).await?.status, "rejected");
+	let acks = CaseAcknowledgmentBmc::list_by_case(&ctx, &mm, rejected).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(acks.len(), 1);
+	assert_eq!(acks[0].ack_code.as_deref(), Some("CR"));
+	assert_eq!(acks[0].batch_ack_code.as_deref(), Some("AE"));
+	assert!(acks[0]
+		.error_text
+		.as_deref()
+		.unwrap_or_default()
+		.contains("SafetyCasePriority"));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn import_ack_reports_unmatched_messages() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let unknown = format!("UNKNOWN-{}", Uuid::new_v4());
+	let xml = instance("R3ack-positive.ack").replace(POSITIVE_TARGET, &unknown);
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let result = import_ack_xml(&ctx, &mm, xml.as_bytes()).await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(result.total, 1);
+	assert_eq!(result.unmatched, 1);
+	assert!(result.messages[0].case_id.is_none());
+	assert!(result.messages[0].error.is_some());
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn batch_only_rejection_rejects_every_batch_case() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = Uuid::new_v4();
+	let batch_number = format!("AR-BATCH-{suffix}");
+	let first = import_submitted_case(&ctx, &mm, &format!("AR-A-{suffix}")).await?;
+	let second = import_submitted_case(&ctx, &mm, &format!("AR-B-{suffix}")).await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	for case_id in [first, second] {
+		MessageHeaderBmc::update_by_case(
+			&ctx,
+			&mm,
+			case_id,
+			MessageHeaderForUpdate {
+				batch_number: Some(batch_number.clone()),
+				batch_sender_identifier: None,
+				batch_receiver_identifier: None,
+				batch_transmission_date: None,
+				message_number: None,
+				message_sender_identifier: None,
+				message_receiver_identifier: None,
+			},
+		)
+		.await?;
+	}
+	let ack = render_ack_xml(&AckBatch {
+		ack_batch_number: Some(format!("ACK-{suffix}")),
+		batch_number: Some(batch_number),
+		batch_ack_code: Some("AR".to_string()),
+		batch_error_text: Some("batch failed XSD validation".to_string()),
+		..Default::default()
+	});
+	let result = import_ack_xml(&ctx, &mm, ack.as_bytes()).await?;
+	let first_status = CaseBmc::get(&ctx, &mm, first).await?.status;
+	let second_status = CaseBmc::get(&ctx, &mm, second).await?.status;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(result.matched, 2);
+	assert!(result
+		.messages
+		.iter()
+		.all(|m| m.case_status.as_deref() == Some("rejected")));
+	assert_eq!(first_status, "rejected");
+	assert_eq!(second_status, "rejected");
+	Ok(())
+}
+
+fn import_result(imported: usize, failed: usize) -> XmlBatchImportResult {
+	XmlBatchImportResult {
+		batch_number: Some("BATCH-1".to_string()),
+		total: imported + failed,
+		imported,
+		failed,
+		reports: Vec::new(),
+		ack_xml: None,
+	}
+}
+
+fn failed_report(index: usize, safety_report_id: &str) -> XmlImportReportResult {
+	XmlImportReportResult {
+		index,
+		message_number: Some(format!("MSG-{safety_report_id}")),
+		safety_report_id: Some(safety_report_id.to_string()),
+		case_id: None,
+		case_version: None,
+		parsed_json_id: None,
+		error: Some(XmlImportReportError {
+			message: "Business rule validation failed".to_string(),
+			xpath: format!("/hl7:MCCI_IN200100UV01/hl7:PORR_IN049016UV[{}]", index + 1),
+			errors: vec![XmlValidationError {
+				message: "C.1.3 is required".to_string(),
+				line: None,
+				column: None,
+				xpath: None,
+			}],
+		}),
+	}
+}
+
+#[test]
+fn ack_codes_map_to_case_status() {
+	assert_eq!(case_status_for_ack_code("CA"), Some("acknowledged"));
+	assert_eq!(case_status_for_ack_code("CR"), Some("rejected"));
+	assert_eq!(case_status_for_ack_code("AA"), Some("acknowledged"));
+	assert_eq!(case_status_for_ack_code("ae"), Some("acknowledged"));
+	assert_eq!(case_status_for_ack_code("AR"), Some("rejected"));
+	assert_eq!(case_status_for_ack_code("XX"), None);
+}
+
+#[test]
+fn batch_ack_code_follows_import_outcome() {
+	let invalid = XmlValidationReport {
+		ok: false,
+		errors: Vec::new(),
+		warnings: Vec::new(),
+		root_element: None,
+	};
+	assert_eq!(batch_ack_code_for_import(&import_result(2, 0), None), "AA");
+	assert_eq!(batch_ack_code_for_import(&import_result(1, 1), None), "AE");
+	assert_eq!(batch_ack_code_for_import(&import_result(0, 2), None), "AR");
+	assert_eq!(
+		batch_ack_code_for_import(&import_result(2, 0), Some(&invalid)),
+		"AR"
+	);
+}
+
+#[test]
+fn render_ack_round_trips_through_parser() -> Result<()> {
+	let batch = AckBatch {
+		ack_batch_number: Some("ACK-1".to_string()),
+		sender_identifier: Some("DSJP".to_string()),
+		receiver_identifier: Some("PARTNER".to_string()),
+		ack_date: Some("20240131120000".to_string()),
+		batch_number: Some("BATCH-1".to_string()),
+		transmission_date: Some("20240130100000".to_string()),
+		batch_ack_code: Some("AE".to_string()),
+		batch_error_text: Some("1 of 2 ICSR message(s) were rejected".to_string()),
+		messages: vec![AckMessage {
+			message_number: Some("MSG-1".to_string()),
+			local_report_number: Some("LOCAL-1".to_string()),
+			receiver_identifier: Some("PARTNER".to_string()),
+			sender_identifier: Some("DSJP".to_string()),
+			message_date: Some("20240130090000".to_string()),
+			ack_code: Some("CR".to_string()),
+			error_text: Some("C.1.3 <missing> & required".to_string()),
+		}],
+	};
+
+	let parsed = parse_ack_xml(render_ack_xml(&batch).as_bytes())?;
+	assert_eq!(parsed.ack_batch_number, batch.ack_batch_number);
+	assert_eq!(parsed.sender_identifier, batch.sender_identifier);
+	assert_eq!(parsed.receiver_identifier, batch.receiver_identifier);
+	assert_eq!(parsed.batch_number, batch.batch_number);
+	assert_eq!(parsed.transmission_date, batch.transmission_date);
+	assert_eq!(parsed.batch_ack_code, batch.batch_ack_code);
+	assert_eq!(parsed.batch_error_text, batch.batch_error_text);
+	let message = &parsed.messages[0];
+	assert_eq!(message.message_number.as_deref(), Some("MSG-1"));
+	assert_eq!(message.local_report_number.as_deref(), Some("LOCAL-1"));
+	assert_eq!(message.message_date.as_deref(), Some("20240130090000"));
+	assert_eq!(message.ack_code.as_deref(), Some("CR"));
+	assert_eq!(
+		message.error_text.as_deref(),
+		Some("C.1.3 <missing> & required")
+	);
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn build_import_ack_uses_imported_message_header() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let safety_report_id = format!("ACK-OUT-{}", Uuid::new_v4());
+	let xml = sample_xml(&safety_report_id);
+	let mut result = import_e2b_xml_batch(
+		&ctx,
+		&mm,
+		XmlImportRequest {
+			xml: xml.into_bytes(),
+			filename: None,
+		},
+	)
+	.await?;
+	result.reports.push(failed_report(1, "ACK-OUT-FAILED"));
+	result.total += 1;
+	result.failed += 1;
+	let case_id = Uuid::parse_str(result.reports[0].case_id.as_deref().expect("case_id"))?;
+	let inbound_message_number = result.reports[0].message_number.clone();
+	assert!(inbound_message_number.is_some());
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let header = MessageHeaderBmc::get_by_case(&ctx, &mm, case_id).await?;
+	let ack_xml = build_import_ack_xml(
+		&ctx,
+		&mm,
+		&XmlAckGenerationRequest {
+			import_result: result,
+			validation: None,
+		},
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	let ack = parse_ack_xml(ack_xml.as_bytes())?;
+	assert_eq!(ack.batch_ack_code.as_deref(), Some("AE"));
+	assert_eq!(ack.batch_number, header.batch_number);
+	assert_eq!(
+		ack.sender_identifier.as_deref(),
+		header
+			.batch_receiver_identifier
+			.as_deref()
+			.or(Some(header.message_receiver_identifier.as_str()))
+	);
+	assert_eq!(ack.messages.len(), 2);
+
+	let accepted = &ack.messages[0];
+	assert_eq!(accepted.ack_code.as_deref(), Some("CA"));
+	assert_eq!(accepted.message_number, inbound_message_number);
+	assert_ne!(
+		accepted.message_number.as_deref(),
+		Some(header.message_number.as_str()),
+		"ACK must not echo the case-suffixed stored message number"
+	);
+	assert_eq!(
+		accepted.receiver_identifier.as_deref(),
+		Some(header.message_sender_identifier.as_str())
+	);
+
+	let rejected = &ack.messages[1];
+	assert_eq!(rejected.ack_code.as_deref(), Some("CR"));
+	assert_eq!(rejected.message_number.as_deref(), Some("MSG-ACK-OUT-FAILED"));
+	assert!(rejected
+		.error_text
+		.as_deref()
+		.unwrap_or_default()
+		.contains("C.1.3 is required"));
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn import_returns_ack_and_skips_rejected_files() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let safety_report_id = format!("ACK-IMP-{}", Uuid::new_v4());
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let accepted = import_e2b_xml_batch_with_ack(
+		&ctx,
+		&mm,
+		XmlImportRequest {
+			xml: sample_xml(&safety_report_id).into_bytes(),
+			filename: None,
+		},
+	)
+	.await?;
+	let unreadable = import_e2b_xml_batch_with_ack(
+		&ctx,
+		&mm,
+		XmlImportRequest {
+			xml: b"<MCCI_IN200100UV01 xmlns=\"urn:hl7-org:v3\">".to_vec(),
+			filename: None,
+		},
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(accepted.imported, 1);
+	let ack = parse_ack_xml(accepted.ack_xml.as_deref().expect("ack").as_bytes())?;
+	assert_eq!(ack.batch_ack_code.as_deref(), Some("AA"));
+	assert_eq!(ack.messages.len(), 1);
+	assert_eq!(ack.messages[0].ack_code.as_deref(), Some("CA"));
+
+	assert_eq!(unreadable.total, 0);
+	let ack = parse_ack_xml(unreadable.ack_xml.as_deref().expect("ack").as_bytes())?;
+	assert_eq!(ack.batch_ack_code.as_deref(), Some("AR"));
+	assert!(ack.messages.is_empty());
+	assert!(ack.batch_error_text.is_some());
+	Ok(())
+}
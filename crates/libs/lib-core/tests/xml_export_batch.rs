@@ -0,0 +1,192 @@
+mod common;
+
+use common::{
+	begin_test_ctx, commit_test_ctx, demo_ctx, force_case_status,
+	import_sample_case, init_test_mm, sample_xml, Result,
+};
+use lib_core::model::message_header::{MessageHeaderBmc, MessageHeaderForUpdate};
+use lib_core::xml::batch::{
+	assemble_batch_reports, split_batch_reports, BatchHeader,
+};
+use lib_core::xml::{
+	export_cases_batch_xml, XmlBatchExportRequest, XmlBatchExportResult,
+};
+use serial_test::serial;
+use uuid::Uuid;
+
+/// libxml documents are not `Send`, so the export runs on a blocking thread
+/// the same way the REST handler drives it.
+async fn export_batch(
+	ctx: &lib_core::ctx::Ctx,
+	mm: &lib_core::model::ModelManager,
+	req: XmlBatchExportRequest,
+) -> lib_core::xml::Result<XmlBatchExportResult> {
+	let (ctx, mm) = (ctx.clone(), mm.clone());
+	tokio::task::spawn_blocking(move || {
+		tokio::runtime::Handle::current()
+			.block_on(export_cases_batch_xml(&ctx, &mm, req))
+	})
+	.await
+	.expect("export task")
+}
+
+#[test]
+fn assemble_batch_keeps_every_message_and_sets_header() -> Result<()> {
+	let ids = ["ASM-A", "ASM-B"];
+	let documents: Vec<String> = ids.iter().map(|id| sample_xml(id)).collect();
+	let header = BatchHeader {
+		batch_number: "BATCH-ASM-1".to_string(),
+		sender_identifier: "SENDER-1".to_string(),
+		receiver_identifier: "ZZFDA".to_string(),
+		transmission_date: "20240131120000".to_string(),
+	};
+
+	let xml = assemble_batch_reports(&documents, &header)?;
+	assert!(xml.contains("extension=\"BATCH-ASM-1\""));
+	assert!(xml.contains("value=\"20240131120000\""));
+	assert!(xml.contains("extension=\"SENDER-1\""));
+
+	let slices = split_batch_reports(xml.as_bytes())?;
+	assert_eq!(slices.len(), ids.len());
+	for (slice, id) in slices.iter().zip(ids) {
+		assert_eq!(slice.safety_report_id.as_deref(), Some(id));
+	}
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn export_batch_records_batch_number_on_each_case() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = Uuid::new_v4();
+	let first = import_sample_case(&ctx, &mm, &format!("EXP-A-{suffix}")).await?;
+	let second = import_sample_case(&ctx, &mm, &format!("EXP-B-{suffix}")).await?;
+	for case_id in [first, second] {
+		force_case_status(&ctx, &mm, case_id, "validated").await?;
+	}
+	let batch_number = format!("BATCH-{suffix}");
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let result = export_batch(
+		&ctx,
+		&mm,
+		XmlBatchExportRequest {
+			case_ids: vec![first, second],
+			batch_number: Some(batch_number.clone()),
+			batch_sender_identifier: None,
+		},
+	)
+	.await?;
+	commit_test_ctx(&mm).await?;
+
+	assert_eq!(result.batch_number, batch_number);
+	assert_eq!(result.case_ids, vec![first, second]);
+	let slices = split_batch_reports(result.xml.as_bytes())?;
+	assert_eq!(slices.len(), 2);
+	assert_eq!(
+		slices[1].safety_report_id.as_deref(),
+		Some(format!("EXP-B-{suffix}").as_str())
+	);
+
+	begin_test_ctx(&mm, &ctx).await?;
+	for case_id in [first, second] {
+		let header = MessageHeaderBmc::get_by_case(&ctx, &mm, case_id).await?;
+		assert_eq!(header.batch_number.as_deref(), Some(batch_number.as_str()));
+		assert_eq!(
+			header.batch_receiver_identifier.as_deref(),
+			Some(result.batch_receiver_identifier.as_str())
+		);
+		assert!(header.batch_transmission_date.is_some());
+	}
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn export_batch_rejects_mixed_receivers() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = Uuid::new_v4();
+	let first = import_sample_case(&ctx, &mm, &format!("MIX-A-{suffix}")).await?;
+	let second = import_sample_case(&ctx, &mm, &format!("MIX-B-{suffix}")).await?;
+	for case_id in [first, second] {
+		force_case_status(&ctx, &mm, case_id, "validated").await?;
+	}
+	MessageHeaderBmc::update_by_case(
+		&ctx,
+		&mm,
+		second,
+		MessageHeaderForUpdate {
+			batch_number: None,
+			batch_sender_identifier: None,
+			batch_receiver_identifier: Some("OTHER-RECEIVER".to_string()),
+			batch_transmission_date: None,
+			message_number: None,
+			message_sender_identifier: None,
+			message_receiver_identifier: None,
+		},
+	)
+	.await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let before = MessageHeaderBmc::get_by_case(&ctx, &mm, first).await?;
+	let result = export_batch(
+		&ctx,
+		&mm,
+		XmlBatchExportRequest {
+			case_ids: vec![first, second],
+			batch_number: None,
+			batch_sender_identifier: None,
+		},
+	)
+	.await;
+	commit_test_ctx(&mm).await?;
+
+	let err = result.expect_err("mixed receivers must be rejected");
+	assert!(format!("{err}").contains("OTHER-RECEIVER"));
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let after = MessageHeaderBmc::get_by_case(&ctx, &mm, first).await?;
+	commit_test_ctx(&mm).await?;
+	assert_eq!(
+		after.batch_number, before.batch_number,
+		"rejected batch must not touch message headers"
+	);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn export_batch_rejects_cases_not_validated() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let suffix = Uuid::new_v4();
+	let first = import_sample_case(&ctx, &mm, &format!("VAL-A-{suffix}")).await?;
+	let second = import_sample_case(&ctx, &mm, &format!("VAL-B-{suffix}")).await?;
+	force_case_status(&ctx, &mm, first, "validated").await?;
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let before = MessageHeaderBmc::get_by_case(&ctx, &mm, first).await?;
+	let result = export_batch(
+		&ctx,
+		&mm,
+		XmlBatchExportRequest {
+			case_ids: vec![first, second],
+			batch_number: None,
+			batch_sender_identifier: None,
+		},
+	)
+	.await;
+	let after = MessageHeaderBmc::get_by_case(&ctx, &mm, first).await?;
+	commit_test_ctx(&mm).await?;
+
+	let err = result.expect_err("draft case must be rejected");
+	assert!(format!("{err}").contains(&second.to_string()));
+	assert_eq!(after.batch_number, before.batch_number);
+
+	Ok(())
+}
@@ -26,7 +26,7 @@ fn export_e_reaction_basic() {
 		criteria_congenital_anomaly: false,
 		criteria_other_medically_important: false,
 		required_intervention: None,
-		start_date: Some(Date::from_calendar_date(2024, Month::January, 2).unwrap()),
+		start_date: Some(Date::from_calendar_date(2024, Month::January, 2).unwrap().into()),
 		end_date: None,
 		duration_value: None,
 		duration_unit: None,
@@ -13,7 +13,7 @@ fn export_f_test_basic() {
 		id: Uuid::new_v4(),
 		case_id: Uuid::new_v4(),
 		sequence_number: 1,
-		test_date: Some(Date::from_calendar_date(2024, Month::January, 3).unwrap()),
+		test_date: Some(Date::from_calendar_date(2024, Month::January, 3).unwrap().into()),
 		test_name: "ALT".to_string(),
 		test_meddra_version: Some("24.1".to_string()),
 		test_meddra_code: Some("10001552".to_string()),
@@ -38,6 +38,8 @@ fn export_g_drug_basic() {
 		parent_route_termid_version: Some("1".to_string()),
 		parent_dosage_text: Some("Parent dose".to_string()),
 		fda_additional_info_coded: Some("1".to_string()),
+		kr_product_code_version: None,
+		kr_product_code: None,
 		created_at: OffsetDateTime::now_utc(),
 		updated_at: OffsetDateTime::now_utc(),
 		created_by: Uuid::new_v4(),
@@ -53,6 +55,8 @@ fn export_g_drug_basic() {
 		substance_termid_version: Some("1".to_string()),
 		strength_value: Some(1.into()),
 		strength_unit: Some("mg".to_string()),
+		kr_ingredient_code_version: None,
+		kr_ingredient_code: None,
 		created_at: OffsetDateTime::now_utc(),
 		updated_at: OffsetDateTime::now_utc(),
 		created_by: Uuid::new_v4(),
@@ -69,11 +73,11 @@ fn export_g_drug_basic() {
 		frequency_value: Some(1.into()),
 		frequency_unit: Some("d".to_string()),
 		first_administration_date: Some(
-			Date::from_calendar_date(2024, Month::January, 1).unwrap(),
+			Date::from_calendar_date(2024, Month::January, 1).unwrap().into(),
 		),
 		first_administration_time: Some(Time::from_hms(8, 0, 0).unwrap()),
 		last_administration_date: Some(
-			Date::from_calendar_date(2024, Month::January, 2).unwrap(),
+			Date::from_calendar_date(2024, Month::January, 2).unwrap().into(),
 		),
 		last_administration_time: Some(Time::from_hms(8, 0, 0).unwrap()),
 		duration_value: Some(1.into()),
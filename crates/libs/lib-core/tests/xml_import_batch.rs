@@ -0,0 +1,139 @@
+mod common;
+
+use common::{begin_test_ctx, commit_test_ctx, demo_ctx, init_test_mm, Result};
+use lib_core::model::case::CaseBmc;
+use lib_core::xml::batch::split_batch_reports;
+use lib_core::xml::{import_e2b_xml_batch, XmlImportRequest};
+use serial_test::serial;
+use uuid::Uuid;
+
+const SAMPLE_SAFETY_REPORT_ID: &str = "US-APHARMA-8744554B";
+
+fn sample_xml() -> String {
+	let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.parent()
+		.and_then(|p| p.parent())
+		.and_then(|p| p.parent())
+		.expect("workspace root")
+		.to_path_buf();
+	std::fs::read_to_string(root.join("docs/refs/instances/FAERS2022Scenario1.xml"))
+		.expect("read sample xml")
+}
+
+/// Builds an MCCI_IN200100UV01 batch from the sample by repeating its single
+/// PORR_IN049016UV message once per entry in `messages`.
+fn batch_xml(messages: &[String]) -> String {
+	let xml = sample_xml();
+	let start = xml.find("<PORR_IN049016UV>").expect("message start");
+	let end_tag = "</PORR_IN049016UV>";
+	let end = xml.find(end_tag).expect("message end") + end_tag.len();
+	format!("{}{}{}", &xml[..start], messages.join("\n"), &xml[end..])
+}
+
+fn sample_message(safety_report_id: &str) -> String {
+	let xml = sample_xml();
+	let start = xml.find("<PORR_IN049016UV>").expect("message start");
+	let end_tag = "</PORR_IN049016UV>";
+	let end = xml.find(end_tag).expect("message end") + end_tag.len();
+	xml[start..end].replace(SAMPLE_SAFETY_REPORT_ID, safety_report_id)
+}
+
+#[test]
+fn split_single_report_keeps_source_bytes() -> Result<()> {
+	let xml = sample_xml();
+	let slices = split_batch_reports(xml.as_bytes())?;
+	assert_eq!(slices.len(), 1);
+	assert_eq!(slices[0].xml, xml.as_bytes());
+	assert_eq!(
+		slices[0].safety_report_id.as_deref(),
+		Some(SAMPLE_SAFETY_REPORT_ID)
+	);
+	Ok(())
+}
+
+#[test]
+fn split_batch_isolates_each_message() -> Result<()> {
+	let ids = ["SPLIT-A", "SPLIT-B", "SPLIT-C"];
+	let messages: Vec<String> = ids.iter().map(|id| sample_message(id)).collect();
+	let xml = batch_xml(&messages);
+
+	let slices = split_batch_reports(xml.as_bytes())?;
+	assert_eq!(slices.len(), ids.len());
+	for (idx, slice) in slices.iter().enumerate() {
+		let doc = String::from_utf8(slice.xml.clone())?;
+		assert_eq!(slice.index, idx);
+		assert_eq!(slice.safety_report_id.as_deref(), Some(ids[idx]));
+		assert_eq!(doc.matches("<PORR_IN049016UV>").count(), 1);
+		assert!(doc.contains(ids[idx]));
+		assert!(
+			doc.contains("FAERS2022Scenario1-4069-a2dd-08002b30309d"),
+			"batch header should be kept on every slice"
+		);
+		assert_eq!(
+			slice.xpath,
+			format!("/hl7:MCCI_IN200100UV01/hl7:PORR_IN049016UV[{}]", idx + 1)
+		);
+	}
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn import_batch_reports_each_icsr_independently() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+
+	let suffix = Uuid::new_v4();
+	let first = format!("BATCH-A-{suffix}");
+	let second = format!("BATCH-B-{suffix}");
+	// The middle message carries no identifiers at all and must fail on its own.
+	let messages = vec![
+		sample_message(&first),
+		"<PORR_IN049016UV></PORR_IN049016UV>".to_string(),
+		sample_message(&second),
+	];
+	let xml = batch_xml(&messages);
+
+	let result = import_e2b_xml_batch(
+		&ctx,
+		&mm,
+		XmlImportRequest {
+			xml: xml.into_bytes(),
+			filename: None,
+		},
+	)
+	.await?;
+
+	assert_eq!(result.total, 3);
+	assert_eq!(result.imported, 2);
+	assert_eq!(result.failed, 1);
+	assert_eq!(
+		result.batch_number.as_deref(),
+		Some("FAERS2022Scenario1-4069-a2dd-08002b30309d")
+	);
+
+	let failed = &result.reports[1];
+	assert!(failed.case_id.is_none());
+	let error = failed.error.as_ref().expect("middle report error");
+	assert_eq!(error.xpath, "/hl7:MCCI_IN200100UV01/hl7:PORR_IN049016UV[2]");
+
+	begin_test_ctx(&mm, &ctx).await?;
+	for (report, expected_id) in
+		[(&result.reports[0], &first), (&result.reports[2], &second)]
+	{
+		assert!(
+			report.error.is_none(),
+			"unexpected error: {:?}",
+			report.error
+		);
+		assert_eq!(report.case_version, Some(1));
+		let case_id = Uuid::parse_str(report.case_id.as_deref().expect("case_id"))?;
+		let case = CaseBmc::get(&ctx, &mm, case_id).await?;
+		assert_eq!(&case.safety_report_id, expected_id);
+		let raw_xml = String::from_utf8(case.raw_xml.unwrap_or_default())?;
+		assert_eq!(raw_xml.matches("<PORR_IN049016UV>").count(), 1);
+	}
+	commit_test_ctx(&mm).await?;
+
+	Ok(())
+}
@@ -14,3 +14,50 @@ fn import_g_drug_basic() {
 	let drugs = parse_g_drugs(&xml).expect("parse");
 	assert!(!drugs.is_empty());
 }
+
+#[test]
+fn import_g_drug_mfds_kr_codes_round_trip() {
+	use lib_core::xml::mfds::export::{apply_kr_elements, KrCode, KrElements};
+	use libxml::parser::Parser;
+	use libxml::xpath::Context;
+
+	let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.parent()
+		.and_then(|p| p.parent())
+		.and_then(|p| p.parent())
+		.expect("workspace root")
+		.to_path_buf();
+	let xml =
+		std::fs::read_to_string(root.join("docs/refs/instances/FAERS2022Scenario1.xml"))
+			.expect("read sample xml");
+
+	let parser = Parser::default();
+	let mut doc = parser.parse_string(&xml).expect("parse sample xml");
+	let mut xpath = Context::new(&doc).expect("xpath context");
+	let _ = xpath.register_namespace("hl7", "urn:hl7-org:v3");
+	let kr = KrElements {
+		drugs: vec![KrCode {
+			version: Some("1".to_string()),
+			code: Some("KR-PRODUCT".to_string()),
+		}],
+		ingredients: vec![vec![KrCode {
+			version: Some("2".to_string()),
+			code: Some("KR-INGREDIENT".to_string()),
+		}]],
+		..Default::default()
+	};
+	apply_kr_elements(&mut doc, &parser, &mut xpath, &kr).expect("apply kr");
+
+	let drugs = parse_g_drugs(doc.to_string().as_bytes()).expect("parse");
+	assert_eq!(drugs[0].kr_product_code.as_deref(), Some("KR-PRODUCT"));
+	assert_eq!(drugs[0].kr_product_code_version.as_deref(), Some("1"));
+	assert_eq!(
+		drugs[0].substances[0].kr_ingredient_code.as_deref(),
+		Some("KR-INGREDIENT")
+	);
+	assert_eq!(
+		drugs[0].substances[0].kr_ingredient_code_version.as_deref(),
+		Some("2")
+	);
+	assert!(drugs.iter().skip(1).all(|drug| drug.kr_product_code.is_none()));
+}
@@ -18,7 +18,7 @@ fn patch_d_section_updates_values() {
 	let patch = DPatientPatch {
 		patient_name: Some("Jane Doe"),
 		sex: Some("2"),
-		birth_date: Some(Date::from_calendar_date(1985, Month::May, 4).unwrap()),
+		birth_date: Some(Date::from_calendar_date(1985, Month::May, 4).unwrap().into()),
 		age_value: Some("38"),
 		age_unit: Some("a"),
 		weight_kg: Some("72"),
@@ -44,6 +44,8 @@ fn patch_g_drug_updates_raw_xml() {
 		parent_route_termid_version: None,
 		parent_dosage_text: None,
 		fda_additional_info_coded: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 		created_at: OffsetDateTime::now_utc(),
 		updated_at: OffsetDateTime::now_utc(),
 		created_by: Uuid::new_v4(),
@@ -106,6 +108,8 @@ fn patch_g_drug_normalizes_characterization_for_causality() {
 		parent_route_termid_version: None,
 		parent_dosage_text: None,
 		fda_additional_info_coded: None,
+		kr_product_code_version: None,
+		kr_product_code: None,
 		created_at: OffsetDateTime::now_utc(),
 		updated_at: OffsetDateTime::now_utc(),
 		created_by: Uuid::new_v4(),
@@ -0,0 +1,227 @@
+mod common;
+
+use common::{begin_test_ctx, commit_test_ctx, demo_ctx, init_test_mm, Result};
+use lib_core::model::drug::DrugInformationBmc;
+use lib_core::model::partial_date::DatePrecision;
+use lib_core::model::patient::PatientInformationBmc;
+use lib_core::model::reaction::ReactionBmc;
+use lib_core::model::safety_report::SafetyReportIdentificationBmc;
+use lib_core::xml::{export_case_r2_xml, import_r2_xml, parse_r2_xml};
+use rust_decimal::Decimal;
+use serial_test::serial;
+use uuid::Uuid;
+
+fn sample_r2(safety_report_id: &str) -> String {
+	format!(
+		r#"<?xml version="1.0" encoding="UTF-8"?>
+<ichicsr lang="en">
+	<ichicsrmessageheader>
+		<messagetype>ichicsr</messagetype>
+		<messageformatversion>2.1</messageformatversion>
+		<messageformatrelease>2.0</messageformatrelease>
+		<messagenumb>MSG-{safety_report_id}</messagenumb>
+		<messagesenderidentifier>ACME</messagesenderidentifier>
+		<messagereceiveridentifier>EVHUMAN</messagereceiveridentifier>
+		<messagedateformat>204</messagedateformat>
+		<messagedate>20240305120000</messagedate>
+	</ichicsrmessageheader>
+	<safetyreport>
+		<safetyreportversion>1</safetyreportversion>
+		<safetyreportid>{safety_report_id}</safetyreportid>
+		<primarysourcecountry>GB</primarysourcecountry>
+		<occurcountry>FR</occurcountry>
+		<transmissiondateformat>102</transmissiondateformat>
+		<transmissiondate>20240305</transmissiondate>
+		<reporttype>1</reporttype>
+		<serious>1</serious>
+		<seriousnessdeath>2</seriousnessdeath>
+		<seriousnesslifethreatening>2</seriousnesslifethreatening>
+		<seriousnesshospitalization>1</seriousnesshospitalization>
+		<seriousnessdisabling>2</seriousnessdisabling>
+		<seriousnesscongenitalanomali>2</seriousnesscongenitalanomali>
+		<seriousnessother>2</seriousnessother>
+		<receivedateformat>610</receivedateformat>
+		<receivedate>202402</receivedate>
+		<receiptdateformat>102</receiptdateformat>
+		<receiptdate>20240301</receiptdate>
+		<fulfillexpeditecriteria>1</fulfillexpeditecriteria>
+		<authoritynumb>FR-AUTH-1</authoritynumb>
+		<companynumb>{safety_report_id}</companynumb>
+		<medicallyconfirm>1</medicallyconfirm>
+		<primarysource>
+			<reportergivename>Ann</reportergivename>
+			<reporterfamilyname>Smith</reporterfamilyname>
+			<qualification>1</qualification>
+			<literaturereference>Smith A. Case report. J Drug Saf. 2024</literaturereference>
+		</primarysource>
+		<sender>
+			<sendertype>1</sendertype>
+			<senderorganization>ACME Pharma</senderorganization>
+			<sendercountrycode>GB</sendercountrycode>
+		</sender>
+		<patient>
+			<patientinitial>JD</patientinitial>
+			<patientonsetage>54</patientonsetage>
+			<patientonsetageunit>801</patientonsetageunit>
+			<patientsex>2</patientsex>
+			<resultstestsprocedures>Chest X-ray unremarkable</resultstestsprocedures>
+			<reaction>
+				<primarysourcereaction>Liver injury</primarysourcereaction>
+				<reactionmeddraversionllt>26.1</reactionmeddraversionllt>
+				<reactionmeddrallt>10067125</reactionmeddrallt>
+				<termhighlighted>3</termhighlighted>
+				<reactionstartdateformat>102</reactionstartdateformat>
+				<reactionstartdate>20240210</reactionstartdate>
+				<reactionoutcome>6</reactionoutcome>
+			</reaction>
+			<test>
+				<testdateformat>102</testdateformat>
+				<testdate>20240211</testdate>
+				<testname>ALT</testname>
+				<testresult>250</testresult>
+				<testunit>U/L</testunit>
+			</test>
+			<drug>
+				<drugcharacterization>1</drugcharacterization>
+				<medicinalproduct>Examplamab</medicinalproduct>
+				<drugstructuredosagenumb>10</drugstructuredosagenumb>
+				<drugstructuredosageunit>003</drugstructuredosageunit>
+				<drugintervaldosageunitnumb>1</drugintervaldosageunitnumb>
+				<drugintervaldosagedefinition>804</drugintervaldosagedefinition>
+				<drugstartdateformat>102</drugstartdateformat>
+				<drugstartdate>20240201</drugstartdate>
+				<drugstartperiod>9</drugstartperiod>
+				<drugstartperiodunit>804</drugstartperiodunit>
+				<actiondrug>1</actiondrug>
+				<activesubstance>
+					<activesubstancename>examplamab</activesubstancename>
+				</activesubstance>
+				<drugreactionrelatedness>
+					<drugreactionassesmeddraversion>26.1</drugreactionassesmeddraversion>
+					<drugreactionasses>10067125</drugreactionasses>
+					<drugassessmentsource>Reporter</drugassessmentsource>
+					<drugassessmentmethod>Global introspection</drugassessmentmethod>
+					<drugresult>Related</drugresult>
+				</drugreactionrelatedness>
+			</drug>
+			<summary>
+				<narrativeincludeclinical>Patient developed liver injury after Examplamab.</narrativeincludeclinical>
+			</summary>
+		</patient>
+	</safetyreport>
+</ichicsr>
+"#
+	)
+}
+
+fn has_code(
+	issues: &[lib_core::xml::validate::ValidationIssue],
+	code: &str,
+) -> bool {
+	issues.iter().any(|i| i.code == code && !i.blocking)
+}
+
+#[test]
+fn parse_maps_r2_elements_onto_r3_lists() {
+	let message = parse_r2_xml(sample_r2("GB-ACME-R2-1").as_bytes()).expect("parse");
+	assert_eq!(
+		message.header.message_number.as_deref(),
+		Some("MSG-GB-ACME-R2-1")
+	);
+	assert_eq!(message.reports.len(), 1);
+	let report = &message.reports[0];
+
+	assert_eq!(report.safety_report_id.as_deref(), Some("GB-ACME-R2-1"));
+	assert_eq!(
+		report.receive_date.as_ref().map(|d| d.precision()),
+		Some(DatePrecision::Month)
+	);
+	assert_eq!(report.worldwide_unique_id.as_deref(), Some("FR-AUTH-1"));
+	assert!(has_code(&report.issues, "BFC.A.1.10.2.DROPPED"));
+	assert_eq!(report.literature.len(), 1);
+
+	let patient = report.patient.as_ref().expect("patient");
+	assert_eq!(patient.onset_age_unit.as_deref(), Some("a"));
+
+	let reaction = &report.reactions[0];
+	assert_eq!(reaction.outcome.as_deref(), Some("0"));
+	assert_eq!(reaction.term_highlighted, Some(true));
+	assert_eq!(reaction.serious, Some(true));
+	assert!(reaction.criteria_hospitalization);
+	assert_eq!(reaction.country_code.as_deref(), Some("FR"));
+	assert_eq!(reaction.medical_confirmation, Some(true));
+
+	assert!(report.tests.iter().any(
+		|t| t.result_unstructured.as_deref() == Some("Chest X-ray unremarkable")
+	));
+
+	let drug = &report.drugs[0];
+	assert_eq!(drug.dosage.dose_unit.as_deref(), Some("mg"));
+	assert_eq!(drug.dosage.frequency_unit.as_deref(), Some("d"));
+	assert_eq!(drug.start_period, Some((Decimal::from(9), "d".to_string())));
+	assert_eq!(drug.relatedness[0].reaction, "10067125");
+}
+
+#[test]
+fn parse_rejects_non_ichicsr_root() {
+	assert!(parse_r2_xml(b"<MCCI_IN200100UV01/>").is_err());
+}
+
+#[serial]
+#[tokio::test]
+async fn r2_import_and_export_round_trip() -> Result<()> {
+	let mm = init_test_mm().await;
+	let ctx = demo_ctx();
+	let safety_report_id = format!("GB-ACME-R2-{}", Uuid::new_v4());
+
+	let result =
+		import_r2_xml(&ctx, &mm, sample_r2(&safety_report_id).as_bytes()).await?;
+	assert_eq!(result.total, 1);
+	assert_eq!(result.imported, 1, "{:?}", result.reports[0].error);
+	let report = &result.reports[0];
+	assert!(has_code(&report.issues, "BFC.A.1.10.2.DROPPED"));
+	assert!(has_code(&report.issues, "BFC.A.1.6.PADDED"));
+	let case_id = report.case_id.expect("case id");
+
+	begin_test_ctx(&mm, &ctx).await?;
+	let identification =
+		SafetyReportIdentificationBmc::get_by_case(&ctx, &mm, case_id).await?;
+	assert_eq!(identification.report_type, "1");
+	let patient = PatientInformationBmc::get_by_case(&ctx, &mm, case_id).await?;
+	assert_eq!(patient.age_unit.as_deref(), Some("a"));
+	let reactions = ReactionBmc::list_by_case(&ctx, &mm, case_id).await?;
+	assert_eq!(reactions.len(), 1);
+	assert_eq!(reactions[0].outcome.as_deref(), Some("0"));
+	assert!(reactions[0].criteria_hospitalization);
+	let drugs = DrugInformationBmc::list_by_case(&ctx, &mm, case_id).await?;
+	assert_eq!(drugs.len(), 1);
+	commit_test_ctx(&mm).await?;
+
+	let export = export_case_r2_xml(&ctx, &mm, case_id).await?;
+	let xml = export.xml;
+	assert!(xml.contains(&format!(
+		"<safetyreportid>{safety_report_id}</safetyreportid>"
+	)));
+	assert!(xml.contains("<occurcountry>FR</occurcountry>"));
+	assert!(xml.contains("<receivedate>20240201</receivedate>"));
+	assert!(xml.contains("<reactionstartdate>20240210</reactionstartdate>"));
+	assert!(
+		xml.contains("<seriousnesshospitalization>1</seriousnesshospitalization>")
+	);
+	assert!(xml.contains("<reactionoutcome>6</reactionoutcome>"));
+	assert!(xml.contains("<termhighlighted>3</termhighlighted>"));
+	assert!(xml.contains("<patientonsetageunit>801</patientonsetageunit>"));
+	assert!(xml.contains("<drugstructuredosageunit>003</drugstructuredosageunit>"));
+	assert!(xml.contains("<drugstartperiodunit>804</drugstartperiodunit>"));
+	assert!(xml.contains("<drugreactionasses>10067125</drugreactionasses>"));
+	assert!(xml.contains(
+		"<resultstestsprocedures>Chest X-ray unremarkable</resultstestsprocedures>"
+	));
+	assert!(export.issues.iter().all(|i| !i.blocking));
+
+	// The exported message imports again as the next version of the case.
+	let again = import_r2_xml(&ctx, &mm, xml.as_bytes()).await?;
+	assert_eq!(again.imported, 1, "{:?}", again.reports[0].error);
+	assert_eq!(again.reports[0].case_version, Some(2));
+	Ok(())
+}
@@ -1,4 +1,4 @@
-use lib_core::xml::validate_e2b_xml;
+use lib_core::xml::{validate_e2b_xml, XmlValidatorConfig};
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -475,3 +475,55 @@ fn test_fda_pre_anda_not_allowed_postmarket() -> Result<(), Box<dyn Error>> {
 
 	Ok(())
 }
+
+#[test]
+fn test_schematron_rules_merge_into_report() -> Result<(), Box<dyn Error>> {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../docs/refs");
+	let xml = fs::read_to_string(root.join("instances/FAERS2022Scenario1.xml"))?;
+	let config = XmlValidatorConfig {
+		xsd_path: None,
+		..XmlValidatorConfig::default()
+	}
+	.with_rule_paths(&[root.join("schematron")])?;
+	assert!(XmlValidatorConfig::default()
+		.with_rule_paths(&[root.join("schematron/missing.sch")])
+		.is_err());
+
+	let report = validate_e2b_xml(xml.as_bytes(), Some(config.clone()))?;
+	let warning = report
+		.warnings
+		.iter()
+		.find(|w| w.message.contains("[ICH.SCH.E.i.1.1a.RECOMMENDED]"))
+		.expect("reported-term warning");
+	assert!(warning
+		.xpath
+		.as_deref()
+		.is_some_and(|xpath| xpath.ends_with("/hl7:observation[1]")));
+	let line = warning.line.expect("warning line");
+	assert!(xml.lines().nth(line - 1).unwrap_or("").contains("<observation"));
+	assert!(!report
+		.errors
+		.iter()
+		.any(|e| e.message.contains("[ICH.SCH.C.1.1.REQUIRED]")));
+
+	let broken = xml.replacen(
+		"extension=\"US-APHARMA-8744554B\" root=\"2.16.840.1.113883.3.989.2.1.3.1\"",
+		"root=\"2.16.840.1.113883.3.989.2.1.3.1\"",
+		1,
+	);
+	assert_ne!(broken, xml, "failed to drop C.1.1");
+	let report = validate_e2b_xml(broken.as_bytes(), Some(config))?;
+	assert!(!report.ok, "expected C.1.1 rule error");
+	let error = report
+		.errors
+		.iter()
+		.find(|e| e.message.contains("[ICH.SCH.C.1.1.REQUIRED]"))
+		.expect("C.1.1 rule error");
+	assert!(error
+		.xpath
+		.as_deref()
+		.is_some_and(|xpath| xpath.ends_with("/hl7:investigationEvent[1]")));
+	assert!(error.line.is_some());
+
+	Ok(())
+}
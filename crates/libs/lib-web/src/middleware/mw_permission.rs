@@ -362,6 +362,16 @@ define_permission_marker!(
 	acs::TERMINOLOGY_READ,
 	"Terminology.Read"
 );
+define_permission_marker!(
+	TerminologyUpdate,
+	acs::TERMINOLOGY_UPDATE,
+	"Terminology.Update"
+);
+define_permission_marker!(
+	TerminologyImport,
+	acs::TERMINOLOGY_IMPORT,
+	"Terminology.Import"
+);
 
 // XML permissions
 define_permission_marker!(XmlExport, acs::XML_EXPORT, "Xml.Export");
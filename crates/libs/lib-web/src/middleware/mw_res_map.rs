@@ -58,6 +58,136 @@ pub async fn mw_response_map(
 				debug_detail = Some(serde_json::Value::String(format!("{err:?}")));
 				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
 			}
+			lib_rest_core::Error::Model(
+				model::Error::CaseTransitionPermissionDenied {
+					required_permission,
+				},
+			) => (
+				StatusCode::FORBIDDEN,
+				ClientError::PERMISSION_DENIED {
+					required_permission: required_permission.clone(),
+				},
+			),
+			lib_rest_core::Error::Model(model::Error::CaseTransitionNotAllowed {
+				from,
+				to,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"case status transition not allowed: {from} -> {to}"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::CaseSuperseded {
+				case_id,
+				superseded_by,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"case {case_id} is read-only: superseded by case {superseded_by}"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::MeddraVersionNotLoaded {
+				version,
+				language,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"MedDRA version {version} ({language}) is not loaded"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::WhodrugProductNotFound {
+				code,
+				version,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"WHODrug product {code} not found in version {}",
+					version.as_deref().unwrap_or("(any)")
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::CodingTermNotFound {
+				dictionary,
+				code,
+				version,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"{dictionary} code {code} not found in version {}",
+					version.as_deref().unwrap_or("(any)")
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::UcumInvalidUnit {
+				unit,
+				message,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"invalid UCUM unit '{unit}': {message}"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::UcumNotConvertible {
+				from,
+				to,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"unit '{from}' cannot be converted to '{to}'"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::ValidationRuleInvalid {
+				code,
+				message,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"invalid validation rule '{code}': {message}"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(model::Error::TerminologyLoad {
+				file,
+				message,
+			}) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"terminology load failed ({file}): {message}"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(
+				model::Error::TerminologyVersionExists {
+					dictionary,
+					version,
+					language,
+				},
+			) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"{dictionary} version {version} ({language}) is already loaded"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			lib_rest_core::Error::Model(
+				model::Error::TerminologyVersionNotFound {
+					dictionary,
+					version,
+					language,
+				},
+			) => {
+				debug_detail = Some(serde_json::Value::String(format!(
+					"{dictionary} version {version} ({language}) is not loaded"
+				)));
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
+			// Raised by the superseded-case trigger (object_not_in_prerequisite_state).
+			lib_rest_core::Error::Model(err)
+				if err
+					.as_database_error()
+					.and_then(|db_err| db_err.code())
+					.is_some_and(|code| code == "55000") =>
+			{
+				debug_detail = err.as_database_error().map(|db_err| {
+					serde_json::Value::String(db_err.message().to_string())
+				});
+				(StatusCode::BAD_REQUEST, ClientError::SERVICE_ERROR)
+			}
 			lib_rest_core::Error::Model(model::Error::EntityNotFound {
 				entity,
 				id,
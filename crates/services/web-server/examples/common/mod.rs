@@ -468,6 +468,7 @@ impl FlowClient {
         let value: Value = serde_json::from_str(&text)?;
         let case_id = value
             .get("data")
+            .and_then(|v| v.get("reports").and_then(|r| r.get(0)).or(Some(v)))
             .and_then(|v| {
                 v.get("case_id")
                     .or_else(|| v.get("caseId"))
@@ -1,5 +1,5 @@
 use derive_more::From;
-use lib_core::model;
+use lib_core::{model, xml};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -8,6 +8,8 @@ pub enum Error {
 	// -- Modules
 	#[from]
 	Model(model::Error),
+	#[from]
+	Xml(xml::Error),
 }
 
 // region:    --- Error Boilerplate
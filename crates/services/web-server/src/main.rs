@@ -26,6 +26,9 @@ async fn main() -> Result<()> {
 	// -- FOR DEV ONLY (skips automatically if SKIP_DEV_INIT=1)
 	_dev_utils::init_dev().await;
 
+	// -- Fail fast on unreadable or malformed XML rule files.
+	lib_core::xml::init_default_xml_rules()?;
+
 	let mm = ModelManager::new().await?;
 
 	// -- Define Routes
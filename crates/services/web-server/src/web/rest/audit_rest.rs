@@ -1,19 +1,43 @@
 // Audit Log REST endpoints
 
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use lib_core::model::acs::{has_permission, AUDIT_LIST};
 use lib_core::model::audit::{
 	AuditLog, AuditLogBmc, AuditLogFilter, CaseVersion, CaseVersionBmc,
 };
+use lib_core::model::audit_export::{
+	AuditExportBmc, AuditExportParams, AUDIT_EXPORT_CSV_FILE,
+	AUDIT_EXPORT_JSON_FILE,
+};
 use lib_core::model::ModelManager;
 use lib_rest_core::rest_params::ParamsList;
 use lib_rest_core::rest_result::DataRestResult;
 use lib_web::middleware::mw_auth::CtxW;
 use lib_web::{Error as WebError, Result};
+use serde::Deserialize;
 use uuid::Uuid;
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+	/// Manifest plus both files, as JSON.
+	#[default]
+	Package,
+	Csv,
+	Json,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditExportQuery {
+	#[serde(default)]
+	pub format: AuditExportFormat,
+	#[serde(flatten)]
+	pub params: AuditExportParams,
+}
+
 /// Verifies that the current user has audit list permission
 fn require_audit_permission(ctx: &lib_core::ctx::Ctx) -> Result<()> {
 	if !has_permission(ctx.role(), AUDIT_LIST) {
@@ -71,6 +95,58 @@ pub async fn list_audit_logs_by_record(
 	Ok((StatusCode::OK, Json(DataRestResult { data: logs })))
 }
 
+/// GET /api/audit-logs/export?case_id=&organization_id=&from=&to=&format=
+/// Inspection export of the audit trail of one case (or every case) and its
+/// child tables, with field-level before/after values and user names.
+/// `format=csv|json` returns that file alone with its SHA-256 in the
+/// `X-Content-SHA256` header; the default returns the manifest and both files.
+/// **Requires AuditLog.List permission (admin or manager)**
+pub async fn export_audit_logs(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Query(query): Query<AuditExportQuery>,
+) -> Result<Response> {
+	let ctx = ctx_w.0;
+	tracing::debug!("{:<12} - rest export_audit_logs", "HANDLER");
+
+	// Verify audit permission
+	require_audit_permission(&ctx)?;
+
+	let package = AuditExportBmc::export(&ctx, &mm, query.params)
+		.await
+		.map_err(WebError::Model)?;
+
+	let (file_name, content) = match query.format {
+		AuditExportFormat::Package => {
+			return Ok((StatusCode::OK, Json(DataRestResult { data: package }))
+				.into_response());
+		}
+		AuditExportFormat::Csv => (AUDIT_EXPORT_CSV_FILE, package.csv),
+		AuditExportFormat::Json => (AUDIT_EXPORT_JSON_FILE, package.json),
+	};
+	let file = package
+		.manifest
+		.files
+		.iter()
+		.find(|file| file.file_name == file_name)
+		.expect("exported file is in the manifest");
+
+	let mut response = (StatusCode::OK, content).into_response();
+	let headers = response.headers_mut();
+	if let Ok(value) = HeaderValue::from_str(&file.content_type) {
+		headers.insert(header::CONTENT_TYPE, value);
+	}
+	if let Ok(value) =
+		HeaderValue::from_str(&format!("attachment; filename=\"{file_name}\""))
+	{
+		headers.insert(header::CONTENT_DISPOSITION, value);
+	}
+	if let Ok(value) = HeaderValue::from_str(&file.sha256) {
+		headers.insert("x-content-sha256", value);
+	}
+	Ok(response)
+}
+
 /// GET /api/cases/{case_id}/versions
 /// List all versions for a specific case
 /// **Requires AuditLog.Read permission (admin or manager)**
@@ -1,18 +1,36 @@
+use axum::extract::Query;
 use axum::http::{header, HeaderMap};
 use axum::response::Response;
 use lib_core::model::acs::{
-	CASE_CREATE, CASE_DELETE, CASE_LIST, CASE_READ, CASE_UPDATE, XML_EXPORT,
+	AUDIT_LIST, CASE_APPROVE, CASE_CREATE, CASE_DELETE, CASE_LIST, CASE_READ,
+	CASE_UPDATE, XML_EXPORT,
 };
+use lib_core::model::acknowledgment::{CaseAcknowledgment, CaseAcknowledgmentBmc};
 use lib_core::model::case::{Case, CaseBmc, CaseFilter, CaseForCreate, CaseForUpdate};
+use lib_core::model::case_diff::{CaseDiff, CaseDiffBmc, CaseDiffParams};
+use lib_core::model::case_workflow::{CaseWorkflowBmc, TransitionTrigger};
 use lib_core::model::drug::DrugInformationBmc;
-use lib_core::model::message_header::{MessageHeaderBmc, MessageHeaderForCreate};
+use lib_core::model::follow_up::{CaseFollowUpBmc, CaseFollowUpForCreate};
+use lib_core::model::message_header::{
+	MessageHeaderBmc, MessageHeaderForCreate, MessageHeaderForUpdate,
+};
 use lib_core::model::patient::{PatientIdentifierBmc, PatientIdentifierFilter, PatientInformationBmc};
+use lib_core::model::partial_date::PartialDate;
 use lib_core::model::safety_report::{
 	PrimarySourceBmc, PrimarySourceFilter, SafetyReportIdentificationBmc,
 	SafetyReportIdentificationForCreate, StudyInformationBmc, StudyInformationFilter,
 };
 use lib_core::model::reaction::ReactionBmc;
-use lib_core::xml::{export_case_xml, validate_e2b_xml};
+use lib_core::model::reporting_deadline::{
+	CaseDeadlineFilter, CaseDeadlineListItem, CaseReportingDeadline,
+	CaseReportingDeadlineBmc, MAX_WITHIN_DAYS,
+};
+use lib_core::xml::{
+	diff_case_version_snapshots, export_case_r2_xml, export_case_xml,
+	export_cases_batch_xml, nullify_case_xml, validate_e2b_xml,
+	XmlBatchExportRequest, XmlBatchExportResult, XmlNullificationResult,
+	XmlR2ExportResult,
+};
 use lib_core::xml::validate::ValidationProfile;
 use lib_rest_core::prelude::*;
 use lib_rest_core::rest_params::{ParamsForCreate, ParamsForUpdate};
@@ -55,7 +73,7 @@ pub async fn update_case_guarded(
 ) -> Result<(StatusCode, Json<DataRestResult<Case>>)> {
 	let ctx = ctx_w.0;
 	require_permission(&ctx, CASE_UPDATE)?;
-	let ParamsForUpdate { data } = params;
+	let ParamsForUpdate { mut data } = params;
 
 	let wants_validated = data
 		.status
@@ -68,8 +86,69 @@ pub async fn update_case_guarded(
 		});
 	}
 
-	CaseBmc::update(&ctx, &mm, id, data).await?;
-	let entity = CaseBmc::get(&ctx, &mm, id).await?;
+	// Status changes go through the workflow; submission stamps are owned by it.
+	// Field edits and the transition commit together.
+	let status = data.status.take();
+	data.submitted_by = None;
+	data.submitted_at = None;
+	let Some(status) = status else {
+		CaseBmc::update(&ctx, &mm, id, data).await?;
+		CaseReportingDeadlineBmc::refresh(&ctx, &mm, id).await?;
+		let entity = CaseBmc::get(&ctx, &mm, id).await?;
+		return Ok((StatusCode::OK, Json(DataRestResult { data: entity })));
+	};
+	let entity = CaseWorkflowBmc::update_with_transition(
+		&ctx,
+		&mm,
+		id,
+		Some(data),
+		&status.to_ascii_lowercase(),
+		TransitionTrigger::Manual,
+		None,
+	)
+	.await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: entity })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CaseTransitionInput {
+	pub status: String,
+	pub reason: Option<String>,
+}
+
+/// POST /api/cases/{id}/transitions
+/// Moves a case along the lifecycle (e.g. validated -> submitted, which needs
+/// the Approve permission). Validator and ACK transitions are not available here.
+pub async fn transition_case(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+	Json(params): Json<ParamsForCreate<CaseTransitionInput>>,
+) -> Result<(StatusCode, Json<DataRestResult<Case>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+	let ParamsForCreate { data } = params;
+
+	let status = data.status.trim().to_ascii_lowercase();
+	if status == "validated" {
+		return Err(Error::BadRequest {
+			message: "cannot set case to validated manually: status is managed by validator".to_string(),
+		});
+	}
+	let reason = data
+		.reason
+		.map(|r| r.trim().to_string())
+		.filter(|r| !r.is_empty());
+
+	let entity = CaseWorkflowBmc::transition(
+		&ctx,
+		&mm,
+		id,
+		&status,
+		TransitionTrigger::Manual,
+		reason,
+	)
+	.await?;
 	Ok((StatusCode::OK, Json(DataRestResult { data: entity })))
 }
 
@@ -116,6 +195,12 @@ pub async fn mark_case_validated_by_validator(
 		ValidationProfile::Mfds => {
 			lib_core::xml::mfds::validation::validate_case(&ctx, &mm, id).await?
 		}
+		ValidationProfile::Ema => {
+			lib_core::xml::ema::validation::validate_case(&ctx, &mm, id).await?
+		}
+		ValidationProfile::Pmda => {
+			lib_core::xml::pmda::validation::validate_case(&ctx, &mm, id).await?
+		}
 	};
 	if !report.ok {
 		return Err(Error::BadRequest {
@@ -126,31 +211,164 @@ pub async fn mark_case_validated_by_validator(
 		});
 	}
 
-	CaseBmc::update(
+	let entity = CaseWorkflowBmc::transition(
 		&ctx,
 		&mm,
 		id,
-		CaseForUpdate {
-			safety_report_id: None,
-			dg_prd_key: None,
-			status: Some("validated".to_string()),
-			validation_profile: None,
-			submitted_by: None,
-			submitted_at: None,
-			raw_xml: None,
-			dirty_c: None,
-			dirty_d: None,
-			dirty_e: None,
-			dirty_f: None,
-			dirty_g: None,
-			dirty_h: None,
-		},
+		"validated",
+		TransitionTrigger::Validator,
+		None,
 	)
 	.await?;
-	let entity = CaseBmc::get(&ctx, &mm, id).await?;
 	Ok((StatusCode::OK, Json(DataRestResult { data: entity })))
 }
 
+/// POST /api/cases/{id}/follow-up
+/// Copies the case into version N+1 (follow-up or amendment) and locks the
+/// source version.
+pub async fn create_case_follow_up(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+	Json(params): Json<ParamsForCreate<CaseFollowUpForCreate>>,
+) -> Result<(StatusCode, Json<DataRestResult<Case>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_CREATE)?;
+	let ParamsForCreate { data } = params;
+
+	let new_id = CaseFollowUpBmc::create(&ctx, &mm, id, data).await?;
+	let entity = CaseBmc::get(&ctx, &mm, new_id).await?;
+	Ok((StatusCode::CREATED, Json(DataRestResult { data: entity })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CaseNullificationInput {
+	/// C.1.11.2 Reason for Nullification/Amendment.
+	pub reason: String,
+}
+
+/// POST /api/cases/{id}/nullify
+/// Creates the C.1.11 nullification report (version N+1) for a submitted case,
+/// marks the case nullified and returns the exported nullification ICSR.
+pub async fn nullify_case(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+	Json(params): Json<ParamsForCreate<CaseNullificationInput>>,
+) -> Result<(StatusCode, Json<DataRestResult<XmlNullificationResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_APPROVE)?;
+	require_permission(&ctx, XML_EXPORT)?;
+	let ParamsForCreate { data } = params;
+	let reason = data.reason.trim().to_string();
+	if reason.is_empty() {
+		return Err(Error::BadRequest {
+			message: "nullification reason is required".to_string(),
+		});
+	}
+
+	let result = task::spawn_blocking(move || {
+		Handle::current().block_on(nullify_case_xml(&ctx, &mm, id, reason))
+	})
+	.await
+	.map_err(|err| Error::BadRequest {
+		message: format!("nullification task failed: {err}"),
+	})??;
+
+	Ok((StatusCode::CREATED, Json(DataRestResult { data: result })))
+}
+
+/// GET /api/cases/{id}/diff?from={version}&to={version}
+/// Section-by-section diff between two versions of the case's safety report;
+/// by default the case against the version before it.
+pub async fn diff_case_versions(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+	Query(params): Query<CaseDiffParams>,
+) -> Result<(StatusCode, Json<DataRestResult<CaseDiff>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_READ)?;
+
+	let diff = CaseDiffBmc::diff_versions(&ctx, &mm, id, params).await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: diff })))
+}
+
+/// GET /api/cases/{id}/versions/diff?from={version}&to={version}
+/// Same as `/diff`, but compares the E2B XML each version exports from its
+/// stored state.
+pub async fn diff_case_version_snapshots_xml(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+	Query(params): Query<CaseDiffParams>,
+) -> Result<(StatusCode, Json<DataRestResult<CaseDiff>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, AUDIT_LIST)?;
+
+	let diff = task::spawn_blocking(move || {
+		Handle::current().block_on(diff_case_version_snapshots(&ctx, &mm, id, params))
+	})
+	.await
+	.map_err(|err| Error::BadRequest {
+		message: format!("snapshot diff task failed: {err}"),
+	})??;
+	Ok((StatusCode::OK, Json(DataRestResult { data: diff })))
+}
+
+/// GET /api/cases/deadlines?within_days={n}&organization_id={uuid}
+/// Open cases that are overdue or due within `within_days`, earliest first.
+pub async fn list_case_deadlines(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Query(filter): Query<CaseDeadlineFilter>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<CaseDeadlineListItem>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_LIST)?;
+	if let Some(days) = filter.within_days {
+		if !(0..=MAX_WITHIN_DAYS).contains(&days) {
+			return Err(Error::BadRequest {
+				message: format!(
+					"within_days must be between 0 and {MAX_WITHIN_DAYS}"
+				),
+			});
+		}
+	}
+
+	let items = CaseReportingDeadlineBmc::list_due(&ctx, &mm, filter).await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: items })))
+}
+
+/// POST /api/cases/deadlines/refresh
+/// Recomputes the deadlines of every open case; for a periodic job that
+/// catches writes made outside the safety report and reaction endpoints.
+pub async fn refresh_case_deadlines(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+) -> Result<StatusCode> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+
+	CaseReportingDeadlineBmc::refresh_open(&ctx, &mm).await?;
+	Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/cases/{id}/deadline
+/// Recomputes and returns the expedited reporting deadline of the case.
+pub async fn get_case_deadline(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<DataRestResult<CaseReportingDeadline>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_READ)?;
+
+	CaseBmc::get(&ctx, &mm, id).await?;
+	CaseReportingDeadlineBmc::refresh(&ctx, &mm, id).await?;
+	let deadline = CaseReportingDeadlineBmc::get_by_case(&ctx, &mm, id).await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: deadline })))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CaseIntakeCheckInput {
 	pub safety_report_id: String,
@@ -165,7 +383,7 @@ pub struct CaseIntakeCheckInput {
 	pub dg_prd_key: Option<String>,
 	pub reaction_meddra_version: Option<String>,
 	pub reaction_meddra_code: Option<String>,
-	pub ae_start_date: Option<Date>,
+	pub ae_start_date: Option<PartialDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -186,7 +404,7 @@ pub struct CaseIntakeDuplicateMatch {
 	pub dg_prd_key: Option<String>,
 	pub reaction_meddra_version: Option<String>,
 	pub reaction_meddra_code: Option<String>,
-	pub ae_start_date: Option<Date>,
+	pub ae_start_date: Option<PartialDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -212,7 +430,7 @@ pub struct CaseFromIntakeInput {
 	pub dg_prd_key: Option<String>,
 	pub reaction_meddra_version: Option<String>,
 	pub reaction_meddra_code: Option<String>,
-	pub ae_start_date: Option<Date>,
+	pub ae_start_date: Option<PartialDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -464,6 +682,27 @@ fn message_receiver_identifier(profile: ValidationProfile) -> String {
 			std::env::var("E2BR3_DEFAULT_MESSAGE_RECEIVER_MFDS")
 				.unwrap_or_else(|_| "MFDS".to_string())
 		}
+		ValidationProfile::Ema => std::env::var("E2BR3_DEFAULT_MESSAGE_RECEIVER_EMA")
+			.unwrap_or_else(|_| "EVHUMAN".to_string()),
+		ValidationProfile::Pmda => {
+			std::env::var("E2BR3_DEFAULT_MESSAGE_RECEIVER_PMDA")
+				.unwrap_or_else(|_| "PMDA".to_string())
+		}
+	}
+}
+
+/// N.1.4 default for profiles whose gateway expects an explicit batch
+/// receiver; others fall back to the message receiver on export.
+fn batch_receiver_identifier(profile: ValidationProfile) -> Option<String> {
+	match profile {
+		ValidationProfile::Ema => Some(
+			std::env::var("E2BR3_DEFAULT_BATCH_RECEIVER_EMA")
+				.unwrap_or_else(|_| message_receiver_identifier(profile)),
+		),
+		ValidationProfile::Ich
+		| ValidationProfile::Fda
+		| ValidationProfile::Mfds
+		| ValidationProfile::Pmda => None,
 	}
 }
 
@@ -592,7 +831,7 @@ pub async fn create_case_from_intake(
 		Some(value) => ValidationProfile::parse(value)
 			.ok_or_else(|| Error::BadRequest {
 				message: format!(
-					"invalid validation profile '{value}' (expected: ich, fda or mfds)"
+					"invalid validation profile '{value}' (expected: ich, fda, mfds, ema or pmda)"
 				),
 			})?
 			.as_str()
@@ -601,7 +840,7 @@ pub async fn create_case_from_intake(
 	};
 	let profile_enum = ValidationProfile::parse(&profile).ok_or_else(|| Error::BadRequest {
 		message: format!(
-			"invalid validation profile '{profile}' (expected: ich, fda or mfds)"
+			"invalid validation profile '{profile}' (expected: ich, fda, mfds, ema or pmda)"
 		),
 	})?;
 
@@ -636,6 +875,23 @@ pub async fn create_case_from_intake(
 		},
 	)
 	.await?;
+	if let Some(batch_receiver) = batch_receiver_identifier(profile_enum) {
+		MessageHeaderBmc::update_by_case(
+			&ctx,
+			&mm,
+			case_id,
+			MessageHeaderForUpdate {
+				batch_number: None,
+				batch_sender_identifier: None,
+				batch_receiver_identifier: Some(batch_receiver),
+				batch_transmission_date: None,
+				message_number: None,
+				message_sender_identifier: None,
+				message_receiver_identifier: None,
+			},
+		)
+		.await?;
+	}
 
 	SafetyReportIdentificationBmc::create(
 		&ctx,
@@ -713,6 +969,55 @@ pub async fn export_case(
 	Ok(response)
 }
 
+/// GET /api/cases/{id}/export/xml/r2
+/// Renders the case as an E2B(R2) `ichicsr` message following the ICH BFC;
+/// content R2 cannot carry is listed as non-blocking issues.
+pub async fn export_case_r2(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<DataRestResult<XmlR2ExportResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, XML_EXPORT)?;
+	let result = export_case_r2_xml(&ctx, &mm, id).await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: result })))
+}
+
+/// GET /api/cases/{case_id}/acknowledgments
+/// Inbound regulator acknowledgments recorded for the case, newest first.
+pub async fn list_case_acknowledgments(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(case_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<CaseAcknowledgment>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_READ)?;
+	let acks = CaseAcknowledgmentBmc::list_by_case(&ctx, &mm, case_id).await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: acks })))
+}
+
+/// POST /api/cases/export/xml/batch
+/// Exports several validated cases (same receiver and profile) as one
+/// MCCI_IN200100UV01 batch and records the batch number on each case.
+pub async fn export_cases_batch(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Json(params): Json<ParamsForCreate<XmlBatchExportRequest>>,
+) -> Result<(StatusCode, Json<DataRestResult<XmlBatchExportResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, XML_EXPORT)?;
+	let ParamsForCreate { data } = params;
+	let result = task::spawn_blocking(move || {
+		Handle::current().block_on(export_cases_batch_xml(&ctx, &mm, data))
+	})
+	.await
+	.map_err(|err| Error::BadRequest {
+		message: format!("batch export task failed: {err}"),
+	})??;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: result })))
+}
+
 fn should_validate_export_xml(profile: ValidationProfile) -> bool {
 	if let Ok(value) = std::env::var("E2BR3_EXPORT_VALIDATE_FDA") {
 		if matches!(
@@ -27,7 +27,7 @@ async fn resolve_profile(
 	if let Some(value) = profile {
 		return ValidationProfile::parse(value).ok_or_else(|| Error::BadRequest {
 			message: format!(
-				"invalid validation profile '{value}' (expected: ich, fda or mfds)"
+				"invalid validation profile '{value}' (expected: ich, fda, mfds, ema or pmda)"
 			),
 			});
 	}
@@ -54,18 +54,9 @@ async fn resolve_profile(
 	let inferred = header
 		.as_ref()
 		.and_then(|h| h.batch_receiver_identifier.as_deref())
-		.map(|v| v.trim().to_ascii_uppercase());
+		.unwrap_or_default();
 
-	let is_mfds = inferred
-		.as_deref()
-		.map(|value| value.contains("MFDS"))
-		.unwrap_or(false);
-
-	Ok(if is_mfds {
-		ValidationProfile::Mfds
-	} else {
-		ValidationProfile::Fda
-	})
+	Ok(ValidationProfile::for_receiver(inferred))
 }
 
 /// GET /api/cases/{case_id}/validation
@@ -92,6 +83,13 @@ pub async fn validate_case(
 			lib_core::xml::mfds::validation::validate_case(&ctx, &mm, case_id)
 				.await?
 		}
+		ValidationProfile::Ema => {
+			lib_core::xml::ema::validation::validate_case(&ctx, &mm, case_id).await?
+		}
+		ValidationProfile::Pmda => {
+			lib_core::xml::pmda::validation::validate_case(&ctx, &mm, case_id)
+				.await?
+		}
 	};
 
 	Ok((StatusCode::OK, Json(DataRestResult { data: report })))
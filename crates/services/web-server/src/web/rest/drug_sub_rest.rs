@@ -14,6 +14,7 @@ use lib_core::model::drug::{
 	DosageInformation, DosageInformationBmc, DosageInformationFilter,
 	DosageInformationForCreate, DosageInformationForUpdate, DrugActiveSubstance,
 	DrugActiveSubstanceBmc, DrugActiveSubstanceFilter, DrugActiveSubstanceForCreate,
+	DrugActiveSubstanceFromWhodrug,
 	DrugActiveSubstanceForUpdate, DrugIndication, DrugIndicationBmc,
 	DrugIndicationFilter, DrugIndicationForCreate, DrugIndicationForUpdate,
 };
@@ -62,6 +63,23 @@ pub async fn create_drug_active_substance(
 	Ok((StatusCode::CREATED, Json(DataRestResult { data: entity })))
 }
 
+/// POST /api/cases/{case_id}/drugs/{drug_id}/active-substances/from-whodrug
+/// Fills the drug's active substances from a WHODrug product's ingredients
+pub async fn create_drug_active_substances_from_whodrug(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path((_case_id, drug_id)): Path<(Uuid, Uuid)>,
+	Json(params): Json<ParamsForCreate<DrugActiveSubstanceFromWhodrug>>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<DrugActiveSubstance>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, DRUG_SUBSTANCE_CREATE)?;
+	let ParamsForCreate { data } = params;
+
+	let entities =
+		DrugActiveSubstanceBmc::create_from_whodrug(&ctx, &mm, drug_id, data).await?;
+	Ok((StatusCode::CREATED, Json(DataRestResult { data: entities })))
+}
+
 /// GET /api/cases/{case_id}/drugs/{drug_id}/active-substances
 pub async fn list_drug_active_substances(
 	State(mm): State<ModelManager>,
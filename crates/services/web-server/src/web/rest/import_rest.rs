@@ -4,7 +4,9 @@ use axum::Json;
 use lib_core::model::acs::XML_IMPORT;
 use lib_core::model::ModelManager;
 use lib_core::xml::{
-	import_e2b_xml, validate_e2b_xml, XmlImportRequest, XmlValidationReport,
+	import_ack_xml, import_e2b_xml_batch_with_ack, import_r2_xml, validate_e2b_xml,
+	XmlAckImportResult, XmlBatchImportResult, XmlImportRequest, XmlR2ImportResult,
+	XmlValidationReport,
 };
 use lib_rest_core::rest_result::DataRestResult;
 use lib_rest_core::{require_permission, Error, Result};
@@ -49,20 +51,19 @@ pub async fn validate_xml(
 }
 
 /// POST /api/import/xml
-/// Parse + import E2B(R3) XML (pipeline WIP)
+/// Parse + import E2B(R3) XML. Batches are split per ICSR message and the
+/// response lists the outcome of each one (case_id/version or error + XPath),
+/// plus the MCCI_IN200101UV01 acknowledgment to send back in `ack_xml`.
 pub async fn import_xml(
 	State(mm): State<ModelManager>,
 	ctx_w: CtxW,
 	multipart: Multipart,
-) -> Result<(
-	StatusCode,
-	Json<DataRestResult<lib_core::xml::XmlImportResult>>,
-)> {
+) -> Result<(StatusCode, Json<DataRestResult<XmlBatchImportResult>>)> {
 	let ctx = ctx_w.0;
 	require_permission(&ctx, XML_IMPORT)?;
 
 	let xml = read_xml_multipart(multipart).await?;
-	let result = import_e2b_xml(
+	let result = import_e2b_xml_batch_with_ack(
 		&ctx,
 		&mm,
 		XmlImportRequest {
@@ -74,3 +75,37 @@ pub async fn import_xml(
 
 	Ok((StatusCode::OK, Json(DataRestResult { data: result })))
 }
+
+/// POST /api/import/xml/r2
+/// Imports an E2B(R2) `ichicsr` message, one case per `safetyreport`. Values
+/// the BFC conversion drops or changes are returned as non-blocking issues.
+pub async fn import_xml_r2(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	multipart: Multipart,
+) -> Result<(StatusCode, Json<DataRestResult<XmlR2ImportResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, XML_IMPORT)?;
+
+	let xml = read_xml_multipart(multipart).await?;
+	let result = import_r2_xml(&ctx, &mm, &xml).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: result })))
+}
+
+/// POST /api/import/ack
+/// Records an inbound MCCI_IN200101UV01 acknowledgment: each ICSR ACK is
+/// matched to its submitted case and moves it to `acknowledged`/`rejected`.
+pub async fn import_ack(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	multipart: Multipart,
+) -> Result<(StatusCode, Json<DataRestResult<XmlAckImportResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, XML_IMPORT)?;
+
+	let xml = read_xml_multipart(multipart).await?;
+	let result = import_ack_xml(&ctx, &mm, &xml).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: result })))
+}
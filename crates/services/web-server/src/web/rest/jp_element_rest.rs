@@ -0,0 +1,107 @@
+// PMDA J Elements REST endpoints (J.2 - J.20)
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use lib_core::model::acs::{CASE_LIST, CASE_READ, CASE_UPDATE};
+use lib_core::model::jp_element::{
+	CaseJpElement, CaseJpElementBmc, CaseJpElementForCreate, CaseJpElementForUpdate,
+};
+use lib_core::model::ModelManager;
+use lib_rest_core::rest_params::{ParamsForCreate, ParamsForUpdate};
+use lib_rest_core::rest_result::DataRestResult;
+use lib_rest_core::{require_permission, Result};
+use lib_web::middleware::mw_auth::CtxW;
+use uuid::Uuid;
+
+/// POST /api/cases/{case_id}/jp-elements
+pub async fn create_jp_element(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(case_id): Path<Uuid>,
+	Json(params): Json<ParamsForCreate<CaseJpElementForCreate>>,
+) -> Result<(StatusCode, Json<DataRestResult<CaseJpElement>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+	tracing::debug!(
+		"{:<12} - rest create_jp_element case_id={}",
+		"HANDLER",
+		case_id
+	);
+
+	let ParamsForCreate { data } = params;
+	let mut data = data;
+	data.case_id = case_id;
+
+	let id = CaseJpElementBmc::create(&ctx, &mm, data).await?;
+	let entity = CaseJpElementBmc::get(&ctx, &mm, id).await?;
+
+	Ok((StatusCode::CREATED, Json(DataRestResult { data: entity })))
+}
+
+/// GET /api/cases/{case_id}/jp-elements
+pub async fn list_jp_elements(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(case_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<CaseJpElement>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_LIST)?;
+	tracing::debug!(
+		"{:<12} - rest list_jp_elements case_id={}",
+		"HANDLER",
+		case_id
+	);
+
+	let entities = CaseJpElementBmc::list_by_case(&ctx, &mm, case_id).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: entities })))
+}
+
+/// GET /api/cases/{case_id}/jp-elements/{id}
+pub async fn get_jp_element(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path((_case_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, Json<DataRestResult<CaseJpElement>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_READ)?;
+	tracing::debug!("{:<12} - rest get_jp_element id={}", "HANDLER", id);
+
+	let entity = CaseJpElementBmc::get(&ctx, &mm, id).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: entity })))
+}
+
+/// PUT /api/cases/{case_id}/jp-elements/{id}
+pub async fn update_jp_element(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path((_case_id, id)): Path<(Uuid, Uuid)>,
+	Json(params): Json<ParamsForUpdate<CaseJpElementForUpdate>>,
+) -> Result<(StatusCode, Json<DataRestResult<CaseJpElement>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+	tracing::debug!("{:<12} - rest update_jp_element id={}", "HANDLER", id);
+
+	let ParamsForUpdate { data } = params;
+	CaseJpElementBmc::update(&ctx, &mm, id, data).await?;
+	let entity = CaseJpElementBmc::get(&ctx, &mm, id).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: entity })))
+}
+
+/// DELETE /api/cases/{case_id}/jp-elements/{id}
+pub async fn delete_jp_element(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path((_case_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+	tracing::debug!("{:<12} - rest delete_jp_element id={}", "HANDLER", id);
+
+	CaseJpElementBmc::delete(&ctx, &mm, id).await?;
+
+	Ok(StatusCode::NO_CONTENT)
+}
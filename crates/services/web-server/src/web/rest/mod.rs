@@ -19,6 +19,7 @@ pub mod drug_reaction_assessment_rest;
 pub mod drug_recurrence_rest;
 pub mod drug_sub_rest;
 pub mod import_rest;
+pub mod jp_element_rest;
 pub mod narrative_sub_rest;
 pub mod parent_history_rest;
 pub mod patient_sub_rest;
@@ -29,6 +30,7 @@ pub mod safety_report_sub_rest;
 pub mod terminology_rest;
 pub mod validation_rules_rest;
 
+use axum::extract::DefaultBodyLimit;
 use axum::routing::get;
 use axum::Router;
 use lib_core::model::ModelManager;
@@ -56,6 +58,29 @@ pub fn routes_cases(mm: ModelManager) -> Router {
 		"/cases/{id}/validator/mark-validated",
 		axum::routing::post(case_rest::mark_case_validated_by_validator),
 	)
+	.route(
+		"/cases/{id}/transitions",
+		axum::routing::post(case_rest::transition_case),
+	)
+	.route(
+		"/cases/{id}/follow-up",
+		axum::routing::post(case_rest::create_case_follow_up),
+	)
+	.route(
+		"/cases/{id}/nullify",
+		axum::routing::post(case_rest::nullify_case),
+	)
+	.route("/cases/{id}/diff", get(case_rest::diff_case_versions))
+	.route("/cases/deadlines", get(case_rest::list_case_deadlines))
+	.route(
+		"/cases/deadlines/refresh",
+		axum::routing::post(case_rest::refresh_case_deadlines),
+	)
+	.route("/cases/{id}/deadline", get(case_rest::get_case_deadline))
+	.route(
+		"/cases/{id}/versions/diff",
+		get(case_rest::diff_case_version_snapshots_xml),
+	)
 	// Patient (singleton per case)
 	.route(
 		"/cases/{case_id}/patient",
@@ -153,6 +178,10 @@ pub fn routes_cases(mm: ModelManager) -> Router {
 		"/cases/{case_id}/reactions",
 		get(reaction_rest::list_reactions).post(reaction_rest::create_reaction),
 	)
+	.route(
+		"/cases/{case_id}/reactions/by-soc",
+		get(reaction_rest::list_reactions_by_soc),
+	)
 	.route(
 		"/cases/{case_id}/reactions/{id}",
 		get(reaction_rest::get_reaction)
@@ -177,6 +206,12 @@ pub fn routes_cases(mm: ModelManager) -> Router {
 		get(drug_sub_rest::list_drug_active_substances)
 			.post(drug_sub_rest::create_drug_active_substance),
 	)
+	.route(
+		"/cases/{case_id}/drugs/{drug_id}/active-substances/from-whodrug",
+		axum::routing::post(
+			drug_sub_rest::create_drug_active_substances_from_whodrug,
+		),
+	)
 	.route(
 		"/cases/{case_id}/drugs/{drug_id}/active-substances/{id}",
 		get(drug_sub_rest::get_drug_active_substance)
@@ -395,6 +430,18 @@ pub fn routes_cases(mm: ModelManager) -> Router {
 			.put(case_identifiers_rest::update_linked_report_number)
 			.delete(case_identifiers_rest::delete_linked_report_number),
 	)
+	// PMDA J Elements (collection per case) - J.2 - J.20
+	.route(
+		"/cases/{case_id}/jp-elements",
+		get(jp_element_rest::list_jp_elements)
+			.post(jp_element_rest::create_jp_element),
+	)
+	.route(
+		"/cases/{case_id}/jp-elements/{id}",
+		get(jp_element_rest::get_jp_element)
+			.put(jp_element_rest::update_jp_element)
+			.delete(jp_element_rest::delete_jp_element),
+	)
 	// Parent Medical History (collection per parent) - D.10.7.1.r
 	.route(
 		"/cases/{case_id}/patient/parent/{parent_id}/medical-history",
@@ -428,7 +475,16 @@ pub fn routes_cases(mm: ModelManager) -> Router {
 		"/cases/{case_id}/validation",
 		get(case_validation_rest::validate_case),
 	)
+	.route(
+		"/cases/{case_id}/acknowledgments",
+		get(case_rest::list_case_acknowledgments),
+	)
 	.route("/cases/{id}/export/xml", get(case_rest::export_case))
+	.route("/cases/{id}/export/xml/r2", get(case_rest::export_case_r2))
+	.route(
+		"/cases/export/xml/batch",
+		axum::routing::post(case_rest::export_cases_batch),
+	)
 	.with_state(mm)
 }
 
@@ -486,14 +542,53 @@ pub fn routes_presave_templates(mm: ModelManager) -> Router {
 		.with_state(mm)
 }
 
+/// Upload limit for terminology packages; an uncompressed WHODrug B3
+/// distribution runs to several hundred MB.
+const TERMINOLOGY_UPLOAD_LIMIT: usize = 1024 * 1024 * 1024;
+
 /// Routes for /api/terminology
 pub fn routes_terminology(mm: ModelManager) -> Router {
 	Router::new()
 		.route("/terminology/meddra", get(terminology_rest::search_meddra))
+		.route(
+			"/terminology/meddra/{code}/hierarchy",
+			get(terminology_rest::get_meddra_hierarchy),
+		)
+		.route(
+			"/terminology/meddra/recode",
+			axum::routing::post(terminology_rest::recode_meddra),
+		)
 		.route(
 			"/terminology/whodrug",
 			get(terminology_rest::search_whodrug),
 		)
+		.route(
+			"/terminology/suggestions",
+			get(terminology_rest::suggest_codes),
+		)
+		.route(
+			"/terminology/coding-memory",
+			axum::routing::post(terminology_rest::accept_coding_suggestion),
+		)
+		.route(
+			"/terminology/imports/validate",
+			axum::routing::post(terminology_rest::validate_terminology_import)
+				.layer(DefaultBodyLimit::max(TERMINOLOGY_UPLOAD_LIMIT)),
+		)
+		.route(
+			"/terminology/imports",
+			axum::routing::post(terminology_rest::import_terminology)
+				.layer(DefaultBodyLimit::max(TERMINOLOGY_UPLOAD_LIMIT)),
+		)
+		.route(
+			"/terminology/versions",
+			get(terminology_rest::list_terminology_versions),
+		)
+		.route(
+			"/terminology/activations",
+			get(terminology_rest::list_terminology_activations)
+				.post(terminology_rest::activate_terminology_version),
+		)
 		.route(
 			"/terminology/countries",
 			get(terminology_rest::list_countries),
@@ -513,6 +608,11 @@ pub fn routes_import(mm: ModelManager) -> Router {
 			axum::routing::post(import_rest::validate_xml),
 		)
 		.route("/import/xml", axum::routing::post(import_rest::import_xml))
+		.route(
+			"/import/xml/r2",
+			axum::routing::post(import_rest::import_xml_r2),
+		)
+		.route("/import/ack", axum::routing::post(import_rest::import_ack))
 		.with_state(mm)
 }
 
@@ -520,6 +620,7 @@ pub fn routes_import(mm: ModelManager) -> Router {
 pub fn routes_audit(mm: ModelManager) -> Router {
 	Router::new()
 		.route("/audit-logs", get(audit_rest::list_audit_logs))
+		.route("/audit-logs/export", get(audit_rest::export_audit_logs))
 		.route(
 			"/audit-logs/by-record/{table_name}/{record_id}",
 			get(audit_rest::list_audit_logs_by_record),
@@ -534,5 +635,26 @@ pub fn routes_validation(mm: ModelManager) -> Router {
 			"/validation/rules",
 			get(validation_rules_rest::list_validation_rules),
 		)
+		.route(
+			"/validation/rules/seed",
+			axum::routing::post(validation_rules_rest::seed_rules),
+		)
+		.route(
+			"/validation/rules/overrides",
+			get(validation_rules_rest::list_rule_overrides),
+		)
+		.route(
+			"/validation/rules/{code}",
+			axum::routing::put(validation_rules_rest::save_rule),
+		)
+		.route(
+			"/validation/rules/{code}/override",
+			axum::routing::put(validation_rules_rest::set_rule_override)
+				.delete(validation_rules_rest::clear_rule_override),
+		)
+		.route(
+			"/validation/fda-rules/coverage",
+			get(validation_rules_rest::get_fda_rule_coverage),
+		)
 		.with_state(mm)
 }
@@ -1,8 +1,10 @@
 use lib_core::model::acs::{
 	REACTION_CREATE, REACTION_DELETE, REACTION_LIST, REACTION_READ, REACTION_UPDATE,
 };
+use lib_core::model::meddra::{MeddraHierarchyBmc, SocReactionGroup};
 use lib_core::model::reaction::{ReactionBmc, ReactionForCreate, ReactionForUpdate};
 use lib_rest_core::prelude::*;
+use lib_web::middleware::mw_auth::CtxW;
 
 // Case-scoped CRUD functions:
 // - create_reaction
@@ -22,3 +24,16 @@ generate_case_rest_fns! {
 	PermDelete: REACTION_DELETE,
 	PermList: REACTION_LIST
 }
+
+/// GET /api/cases/{case_id}/reactions/by-soc
+/// Reactions grouped by the primary SOC of their MedDRA code.
+pub async fn list_reactions_by_soc(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(case_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<SocReactionGroup>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, REACTION_LIST)?;
+	let groups = MeddraHierarchyBmc::reactions_by_soc(&ctx, &mm, case_id).await?;
+	Ok((StatusCode::OK, Json(DataRestResult { data: groups })))
+}
@@ -1,16 +1,32 @@
 // Terminology REST endpoints for MedDRA, WHODrug, ISO Countries, E2B Code Lists
 
-use axum::extract::{Query, State};
+use axum::extract::{Multipart, Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
-use lib_core::model::acs::TERMINOLOGY_READ;
+use lib_core::model::acs::{
+	CASE_UPDATE, TERMINOLOGY_IMPORT, TERMINOLOGY_READ, TERMINOLOGY_UPDATE,
+};
+use lib_core::model::coding_assistant::{
+	CodingAcceptance, CodingAssistantBmc, CodingMemoryEntry, CodingSuggestParams,
+	CodingSuggestion,
+};
+use lib_core::model::meddra::{MeddraHierarchyBmc, MeddraPath};
+use lib_core::model::meddra_recode::{
+	MeddraRecodeBmc, MeddraRecodeParams, MeddraRecodeResult,
+};
 use lib_core::model::terminology::{
-	E2bCodeList, E2bCodeListBmc, IsoCountry, IsoCountryBmc, MeddraTerm,
-	MeddraTermBmc, WhodrugProduct, WhodrugProductBmc,
+	E2bCodeList, E2bCodeListBmc, IsoCountry, IsoCountryBmc, MeddraSearchHit,
+	MeddraTermBmc, WhodrugProductBmc, WhodrugSearchHit,
+};
+use lib_core::model::terminology_version::{
+	validate_terminology_package, TerminologyActivation,
+	TerminologyActivationForCreate, TerminologyDictionary, TerminologyPackage,
+	TerminologyValidation, TerminologyVersion, TerminologyVersionBmc,
 };
 use lib_core::model::ModelManager;
+use lib_rest_core::rest_params::ParamsForCreate;
 use lib_rest_core::rest_result::DataRestResult;
-use lib_rest_core::{require_permission, Result};
+use lib_rest_core::{require_permission, Error, Result};
 use lib_web::middleware::mw_auth::CtxW;
 use serde::Deserialize;
 
@@ -26,18 +42,28 @@ fn default_limit() -> i64 {
 	20
 }
 
+#[derive(Deserialize)]
+pub struct MeddraHierarchyParams {
+	pub version: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TerminologyVersionParams {
+	pub dictionary: Option<TerminologyDictionary>,
+}
+
 #[derive(Deserialize)]
 pub struct CodeListParams {
 	pub list_name: String,
 }
 
 /// GET /api/terminology/meddra?q={term}&limit={count}&version={version}
-/// Search MedDRA terms by name
+/// Search MedDRA terms by name; LLT/PT hits carry their primary SOC path
 pub async fn search_meddra(
 	State(mm): State<ModelManager>,
 	ctx_w: CtxW,
 	Query(params): Query<TerminologySearchParams>,
-) -> Result<(StatusCode, Json<DataRestResult<Vec<MeddraTerm>>>)> {
+) -> Result<(StatusCode, Json<DataRestResult<Vec<MeddraSearchHit>>>)> {
 	let ctx = ctx_w.0;
 	require_permission(&ctx, TERMINOLOGY_READ)?;
 	tracing::debug!(
@@ -47,7 +73,7 @@ pub async fn search_meddra(
 		params.limit
 	);
 
-	let terms = MeddraTermBmc::search(
+	let terms = MeddraTermBmc::search_with_hierarchy(
 		&ctx,
 		&mm,
 		&params.q,
@@ -59,13 +85,59 @@ pub async fn search_meddra(
 	Ok((StatusCode::OK, Json(DataRestResult { data: terms })))
 }
 
-/// GET /api/terminology/whodrug?q={term}&limit={count}
-/// Search WHODrug products by name
+/// GET /api/terminology/meddra/{code}/hierarchy?version={version}
+/// PT -> HLT -> HLGT -> SOC paths of an LLT or PT, primary SOC first
+pub async fn get_meddra_hierarchy(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(code): Path<String>,
+	Query(params): Query<MeddraHierarchyParams>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<MeddraPath>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_READ)?;
+	tracing::debug!("{:<12} - rest get_meddra_hierarchy code={}", "HANDLER", code);
+
+	let paths = MeddraHierarchyBmc::paths_for_code(
+		&ctx,
+		&mm,
+		&code,
+		params.version.as_deref(),
+	)
+	.await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: paths })))
+}
+
+/// POST /api/terminology/meddra/recode
+/// Recodes the organization's case MedDRA fields to a loaded MedDRA version;
+/// returns a per-case change report. `dry_run` writes nothing.
+pub async fn recode_meddra(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Json(params): Json<ParamsForCreate<MeddraRecodeParams>>,
+) -> Result<(StatusCode, Json<DataRestResult<MeddraRecodeResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+	let ParamsForCreate { data } = params;
+	tracing::debug!(
+		"{:<12} - rest recode_meddra target={} dry_run={}",
+		"HANDLER",
+		data.target_version,
+		data.dry_run
+	);
+
+	let result = MeddraRecodeBmc::run(&ctx, &mm, data).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: result })))
+}
+
+/// GET /api/terminology/whodrug?q={term}&limit={count}&version={version}
+/// Search WHODrug products by name, with active ingredients and ATC codes
 pub async fn search_whodrug(
 	State(mm): State<ModelManager>,
 	ctx_w: CtxW,
 	Query(params): Query<TerminologySearchParams>,
-) -> Result<(StatusCode, Json<DataRestResult<Vec<WhodrugProduct>>>)> {
+) -> Result<(StatusCode, Json<DataRestResult<Vec<WhodrugSearchHit>>>)> {
 	let ctx = ctx_w.0;
 	require_permission(&ctx, TERMINOLOGY_READ)?;
 	tracing::debug!(
@@ -75,12 +147,61 @@ pub async fn search_whodrug(
 		params.limit
 	);
 
-	let products =
-		WhodrugProductBmc::search(&ctx, &mm, &params.q, params.limit).await?;
+	let products = WhodrugProductBmc::search_with_ingredients(
+		&ctx,
+		&mm,
+		&params.q,
+		params.version.as_deref(),
+		params.limit,
+	)
+	.await?;
 
 	Ok((StatusCode::OK, Json(DataRestResult { data: products })))
 }
 
+/// GET /api/terminology/suggestions?verbatim={text}&dictionary={meddra|whodrug}&version={version}&limit={count}
+/// Ranked MedDRA LLT or WHODrug candidates for a verbatim term
+pub async fn suggest_codes(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Query(params): Query<CodingSuggestParams>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<CodingSuggestion>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_READ)?;
+	tracing::debug!(
+		"{:<12} - rest suggest_codes dictionary={} verbatim={}",
+		"HANDLER",
+		params.dictionary.as_str(),
+		params.verbatim
+	);
+
+	let suggestions = CodingAssistantBmc::suggest(&ctx, &mm, &params).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: suggestions })))
+}
+
+/// POST /api/terminology/coding-memory
+/// Records the code accepted for a verbatim term in the organization
+pub async fn accept_coding_suggestion(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Json(params): Json<ParamsForCreate<CodingAcceptance>>,
+) -> Result<(StatusCode, Json<DataRestResult<CodingMemoryEntry>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, CASE_UPDATE)?;
+	let ParamsForCreate { data } = params;
+	tracing::debug!(
+		"{:<12} - rest accept_coding_suggestion dictionary={} code={}",
+		"HANDLER",
+		data.dictionary.as_str(),
+		data.code
+	);
+
+	let entry = CodingAssistantBmc::accept(&ctx, &mm, data).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: entry })))
+}
+
 /// GET /api/terminology/countries
 /// List all active ISO countries
 pub async fn list_countries(
@@ -116,3 +237,156 @@ pub async fn get_code_list(
 
 	Ok((StatusCode::OK, Json(DataRestResult { data: codes })))
 }
+
+/// Reads a terminology package upload: `dictionary`, `version` and
+/// `language` text fields, and every field carrying a file name as a file.
+async fn read_terminology_package(
+	mut multipart: Multipart,
+) -> Result<TerminologyPackage> {
+	let mut dictionary = None;
+	let mut version = None;
+	let mut language = None;
+	let mut files = Vec::new();
+	while let Some(field) =
+		multipart
+			.next_field()
+			.await
+			.map_err(|err| Error::BadRequest {
+				message: format!("multipart error: {err}"),
+			})? {
+		let name = field.name().unwrap_or_default().to_string();
+		let file_name = field.file_name().map(|v| v.to_string());
+		let bytes = field.bytes().await.map_err(|err| Error::BadRequest {
+			message: format!("multipart read error: {err}"),
+		})?;
+		if let Some(file_name) = file_name {
+			files.push((file_name, bytes.to_vec()));
+			continue;
+		}
+		let value = String::from_utf8_lossy(&bytes).trim().to_string();
+		match name.as_str() {
+			"dictionary" => dictionary = Some(value),
+			"version" if !value.is_empty() => version = Some(value),
+			"language" if !value.is_empty() => language = Some(value),
+			_ => {}
+		}
+	}
+
+	let dictionary = dictionary
+		.as_deref()
+		.and_then(TerminologyDictionary::parse)
+		.ok_or_else(|| Error::BadRequest {
+			message:
+				"dictionary must be meddra, whodrug, iso_countries or e2b_code_lists"
+					.to_string(),
+		})?;
+	if files.is_empty() {
+		return Err(Error::BadRequest {
+			message: "no terminology files uploaded".to_string(),
+		});
+	}
+	Ok(TerminologyPackage {
+		dictionary,
+		version,
+		language,
+		files,
+	})
+}
+
+/// POST /api/terminology/imports/validate
+/// Parses an uploaded terminology package and reports what it would load
+pub async fn validate_terminology_import(
+	State(_mm): State<ModelManager>,
+	ctx_w: CtxW,
+	multipart: Multipart,
+) -> Result<(StatusCode, Json<DataRestResult<TerminologyValidation>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_IMPORT)?;
+	tracing::debug!("{:<12} - rest validate_terminology_import", "HANDLER");
+
+	let package = read_terminology_package(multipart).await?;
+	let validation = validate_terminology_package(&package)?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: validation })))
+}
+
+/// POST /api/terminology/imports
+/// Loads an uploaded terminology package as a new, inactive version
+pub async fn import_terminology(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	multipart: Multipart,
+) -> Result<(StatusCode, Json<DataRestResult<TerminologyVersion>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_IMPORT)?;
+
+	let package = read_terminology_package(multipart).await?;
+	tracing::debug!(
+		"{:<12} - rest import_terminology dictionary={} files={}",
+		"HANDLER",
+		package.dictionary.as_str(),
+		package.files.len()
+	);
+	let version = TerminologyVersionBmc::import(&ctx, &mm, package).await?;
+
+	Ok((StatusCode::CREATED, Json(DataRestResult { data: version })))
+}
+
+/// GET /api/terminology/versions?dictionary={dictionary}
+/// Loaded terminology versions, newest first
+pub async fn list_terminology_versions(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Query(params): Query<TerminologyVersionParams>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<TerminologyVersion>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_READ)?;
+	tracing::debug!("{:<12} - rest list_terminology_versions", "HANDLER");
+
+	let versions = TerminologyVersionBmc::list(&ctx, &mm, params.dictionary).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: versions })))
+}
+
+/// POST /api/terminology/activations
+/// Activates a loaded version for an organization; default searches use it
+pub async fn activate_terminology_version(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Json(params): Json<ParamsForCreate<TerminologyActivationForCreate>>,
+) -> Result<(StatusCode, Json<DataRestResult<TerminologyActivation>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_UPDATE)?;
+	let ParamsForCreate { data } = params;
+	tracing::debug!(
+		"{:<12} - rest activate_terminology_version dictionary={} version={}",
+		"HANDLER",
+		data.dictionary.as_str(),
+		data.version
+	);
+
+	let activation = TerminologyVersionBmc::activate(&ctx, &mm, data).await?;
+
+	Ok((
+		StatusCode::CREATED,
+		Json(DataRestResult { data: activation }),
+	))
+}
+
+/// GET /api/terminology/activations?dictionary={dictionary}
+/// Activation history of the organization, most recent first
+pub async fn list_terminology_activations(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Query(params): Query<TerminologyVersionParams>,
+) -> Result<(StatusCode, Json<DataRestResult<Vec<TerminologyActivation>>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, TERMINOLOGY_READ)?;
+	tracing::debug!("{:<12} - rest list_terminology_activations", "HANDLER");
+
+	let activations =
+		TerminologyVersionBmc::list_activations(&ctx, &mm, params.dictionary)
+			.await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: activations })))
+}
@@ -1,23 +1,34 @@
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
-use lib_core::model::acs::CASE_READ;
+use lib_core::model::acs::{VALIDATION_RULE_READ, VALIDATION_RULE_UPDATE};
+use lib_core::model::validation_rule::{
+	ValidationRule, ValidationRuleForSave, ValidationRuleOverride,
+	ValidationRuleOverrideBmc, ValidationRuleOverrideForSet,
+};
 use lib_core::model::ModelManager;
 use lib_core::xml::validate::{
-	canonical_rules_all, canonical_rules_for_profile, canonical_rules_version,
-	ValidationProfile,
+	fda_rule_coverage, load_rule_set, save_validation_rule, seed_validation_rules,
+	set_validation_rule_override, FdaRuleCoverage, ValidationProfile,
 };
+use lib_rest_core::rest_params::ParamsForUpdate;
 use lib_rest_core::rest_result::DataRestResult;
 use lib_rest_core::{require_permission, Error, Result};
 use lib_web::middleware::mw_auth::CtxW;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 pub struct ValidationRulesQuery {
 	pub profile: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ValidationRuleOverrideQuery {
+	pub organization_id: Option<Uuid>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ValidationRuleDto {
 	pub code: String,
@@ -27,24 +38,36 @@ pub struct ValidationRuleDto {
 	pub message: String,
 	pub condition: String,
 	pub export_directive: Option<String>,
+	pub xpath: Option<String>,
+	pub active: bool,
+	/// `compiled` or `stored`
+	pub source: String,
+	/// Whether `blocking` comes from an organization override
+	pub overridden: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationRuleSeedResult {
+	pub inserted: u64,
 }
 
 /// GET /api/validation/rules
 /// Optional query: ?profile=ich|fda|mfds
+/// Effective rules for the caller's organization
 pub async fn list_validation_rules(
-	State(_mm): State<ModelManager>,
+	State(mm): State<ModelManager>,
 	ctx_w: CtxW,
 	headers: HeaderMap,
 	Query(query): Query<ValidationRulesQuery>,
 ) -> Result<Response> {
 	let ctx = ctx_w.0;
-	require_permission(&ctx, CASE_READ)?;
+	require_permission(&ctx, VALIDATION_RULE_READ)?;
 
 	let profile = if let Some(profile) = query.profile.as_deref() {
 		let profile = ValidationProfile::parse(profile).ok_or_else(|| {
 			Error::BadRequest {
 				message: format!(
-					"invalid validation profile '{profile}' (expected: ich, fda or mfds)"
+					"invalid validation profile '{profile}' (expected: ich, fda, mfds, ema or pmda)"
 				),
 			}
 		})?;
@@ -52,12 +75,8 @@ pub async fn list_validation_rules(
 	} else {
 		None
 	};
-	let rules = if let Some(profile) = profile {
-		canonical_rules_for_profile(profile)
-	} else {
-		canonical_rules_all()
-	};
-	let version = canonical_rules_version(profile);
+	let rule_set = load_rule_set(&ctx, &mm).await?;
+	let version = rule_set.version(profile);
 	let etag = format!("\"validation-rules-{version}\"");
 
 	let mut response_headers = HeaderMap::new();
@@ -70,6 +89,10 @@ pub async fn list_validation_rules(
 		HeaderValue::from_str(&version)
 			.expect("generated version must be a valid header"),
 	);
+	response_headers.insert(
+		"x-validation-rules-revision",
+		HeaderValue::from(rule_set.revision()),
+	);
 
 	if let Some(if_none_match) = headers
 		.get(header::IF_NONE_MATCH)
@@ -83,16 +106,21 @@ pub async fn list_validation_rules(
 		}
 	}
 
-	let data: Vec<ValidationRuleDto> = rules
+	let data: Vec<ValidationRuleDto> = rule_set
+		.rules_for_profile(profile)
 		.into_iter()
 		.map(|rule| ValidationRuleDto {
-			code: rule.code.to_string(),
+			code: rule.code.clone(),
 			profile: rule.profile.as_str().to_string(),
-			section: rule.section.to_string(),
+			section: rule.section.clone(),
 			blocking: rule.blocking,
-			message: rule.message.to_string(),
-			condition: rule.condition.as_str().to_string(),
+			message: rule.message.clone(),
+			condition: rule.condition.clone(),
 			export_directive: rule.export_directive.map(|d| d.as_str().to_string()),
+			xpath: rule.xpath.clone(),
+			active: rule.active,
+			source: rule.source.as_str().to_string(),
+			overridden: rule.overridden,
 		})
 		.collect();
 
@@ -103,3 +131,120 @@ pub async fn list_validation_rules(
 	)
 		.into_response())
 }
+
+/// PUT /api/validation/rules/{code}
+/// Creates or replaces the stored definition of a rule
+pub async fn save_rule(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(code): Path<String>,
+	Json(params): Json<ParamsForUpdate<ValidationRuleForSave>>,
+) -> Result<(StatusCode, Json<DataRestResult<ValidationRule>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, VALIDATION_RULE_UPDATE)?;
+	tracing::debug!("{:<12} - rest save_rule code={}", "HANDLER", code);
+
+	let ParamsForUpdate { data } = params;
+	let rule = save_validation_rule(&ctx, &mm, &code, data).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: rule })))
+}
+
+/// POST /api/validation/rules/seed
+/// Stores the compiled-in catalog for rules without a stored definition
+pub async fn seed_rules(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+) -> Result<(StatusCode, Json<DataRestResult<ValidationRuleSeedResult>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, VALIDATION_RULE_UPDATE)?;
+	tracing::debug!("{:<12} - rest seed_rules", "HANDLER");
+
+	let inserted = seed_validation_rules(&ctx, &mm).await?;
+
+	Ok((
+		StatusCode::OK,
+		Json(DataRestResult {
+			data: ValidationRuleSeedResult { inserted },
+		}),
+	))
+}
+
+/// GET /api/validation/fda-rules/coverage
+/// FDA rejection/warning error IDs and which validators evaluate them
+pub async fn get_fda_rule_coverage(
+	ctx_w: CtxW,
+) -> Result<(StatusCode, Json<DataRestResult<FdaRuleCoverage>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, VALIDATION_RULE_READ)?;
+	tracing::debug!("{:<12} - rest get_fda_rule_coverage", "HANDLER");
+
+	Ok((
+		StatusCode::OK,
+		Json(DataRestResult {
+			data: fda_rule_coverage(),
+		}),
+	))
+}
+
+/// GET /api/validation/rules/overrides
+/// Severity overrides of the caller's organization
+pub async fn list_rule_overrides(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+) -> Result<(
+	StatusCode,
+	Json<DataRestResult<Vec<ValidationRuleOverride>>>,
+)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, VALIDATION_RULE_READ)?;
+	tracing::debug!("{:<12} - rest list_rule_overrides", "HANDLER");
+
+	let overrides =
+		ValidationRuleOverrideBmc::list_for_org(&ctx, &mm, ctx.organization_id())
+			.await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: overrides })))
+}
+
+/// PUT /api/validation/rules/{code}/override
+/// Sets whether the rule blocks submission for an organization
+pub async fn set_rule_override(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(code): Path<String>,
+	Json(params): Json<ParamsForUpdate<ValidationRuleOverrideForSet>>,
+) -> Result<(StatusCode, Json<DataRestResult<ValidationRuleOverride>>)> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, VALIDATION_RULE_UPDATE)?;
+	tracing::debug!("{:<12} - rest set_rule_override code={}", "HANDLER", code);
+
+	let ParamsForUpdate { data } = params;
+	let saved = set_validation_rule_override(&ctx, &mm, &code, data).await?;
+
+	Ok((StatusCode::OK, Json(DataRestResult { data: saved })))
+}
+
+/// DELETE /api/validation/rules/{code}/override
+/// Optional query: ?organization_id={uuid} (defaults to the caller's)
+pub async fn clear_rule_override(
+	State(mm): State<ModelManager>,
+	ctx_w: CtxW,
+	Path(code): Path<String>,
+	Query(query): Query<ValidationRuleOverrideQuery>,
+) -> Result<StatusCode> {
+	let ctx = ctx_w.0;
+	require_permission(&ctx, VALIDATION_RULE_UPDATE)?;
+	tracing::debug!("{:<12} - rest clear_rule_override code={}", "HANDLER", code);
+
+	let removed =
+		ValidationRuleOverrideBmc::clear(&ctx, &mm, &code, query.organization_id)
+			.await?;
+	if !removed {
+		return Err(Error::BadRequest {
+			message: format!("no override for validation rule '{code}'"),
+		});
+	}
+
+	Ok(StatusCode::NO_CONTENT)
+}
@@ -1,9 +1,13 @@
 mod common;
 
-use axum::body::Body;
+use axum::body::{to_bytes, Body};
 use axum::http::{Request, StatusCode};
-use common::{cookie_header, init_test_mm, seed_org_with_users, Result};
+use common::{
+	cookie_header, init_test_mm, seed_org_with_users, seed_two_orgs_manager_cases,
+	Result,
+};
 use lib_auth::token::generate_web_token;
+use lib_core::model::audit_export::sha256_hex;
 use serial_test::serial;
 use tower::ServiceExt;
 
@@ -36,3 +40,40 @@ async fn test_audit_list_requires_permission() -> Result<()> {
 
 	Ok(())
 }
+
+#[serial]
+#[tokio::test]
+async fn test_audit_export_csv_matches_sha256_header() -> Result<()> {
+	let mm = init_test_mm().await?;
+	let seed = seed_two_orgs_manager_cases(&mm).await?;
+	let token = generate_web_token(&seed.manager.email, seed.manager.token_salt)?;
+	let app = web_server::app(mm);
+
+	let req = Request::builder()
+		.method("GET")
+		.uri(format!(
+			"/api/audit-logs/export?case_id={}&from=2020-01-01&format=csv",
+			seed.case_org1
+		))
+		.header("cookie", cookie_header(&token.to_string()))
+		.body(Body::empty())?;
+	let res = app.clone().oneshot(req).await?;
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(res.headers()["content-type"], "text/csv");
+	let sha256 = res.headers()["x-content-sha256"].to_str()?.to_string();
+	let body = to_bytes(res.into_body(), usize::MAX).await?;
+	assert_eq!(sha256, sha256_hex(&body));
+	let csv = String::from_utf8(body.to_vec())?;
+	assert!(csv.contains(&format!(",CREATE,cases,{},", seed.case_org1)));
+
+	// Cases of another organization are not exported.
+	let req = Request::builder()
+		.method("GET")
+		.uri(format!("/api/audit-logs/export?case_id={}", seed.case_org2))
+		.header("cookie", cookie_header(&token.to_string()))
+		.body(Body::empty())?;
+	let res = app.oneshot(req).await?;
+	assert!(res.status().is_client_error());
+
+	Ok(())
+}
@@ -236,7 +236,7 @@ async fn test_audit_trail_case_crud() -> Result<()> {
 
 	let update_body = json!({
 		"data": {
-			"status": "submitted"
+			"status": "checked"
 		}
 	});
 	let req = Request::builder()
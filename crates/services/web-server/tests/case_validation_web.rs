@@ -221,6 +221,31 @@ async fn update_case_status(
 	Ok((status, value))
 }
 
+async fn transition_case(
+	app: &axum::Router,
+	cookie: &str,
+	case_id: Uuid,
+	status_value: &str,
+) -> Result<(StatusCode, Value)> {
+	let body = json!({
+		"data": {
+			"status": status_value,
+			"reason": "ready for submission"
+		}
+	});
+	let req = Request::builder()
+		.method("POST")
+		.uri(format!("/api/cases/{case_id}/transitions"))
+		.header("cookie", cookie)
+		.header("content-type", "application/json")
+		.body(Body::from(body.to_string()))?;
+	let res = app.clone().oneshot(req).await?;
+	let status = res.status();
+	let body = to_bytes(res.into_body(), usize::MAX).await?;
+	let value = serde_json::from_slice::<Value>(&body)?;
+	Ok((status, value))
+}
+
 async fn validator_mark_validated(
 	app: &axum::Router,
 	cookie: &str,
@@ -408,7 +433,6 @@ async fn test_validator_endpoint_marks_validated_when_clean() -> Result<()> {
 
 #[serial]
 #[tokio::test]
-#[ignore = "requires DB migration/owner privileges to add 'checked' to case_status_valid constraint"]
 async fn test_case_can_be_marked_checked() -> Result<()> {
 	let mm = init_test_mm().await?;
 	let seed = seed_org_with_users(&mm, "adminpwd", "viewpwd").await?;
@@ -424,6 +448,61 @@ async fn test_case_can_be_marked_checked() -> Result<()> {
 	Ok(())
 }
 
+#[serial]
+#[tokio::test]
+async fn test_case_cannot_skip_to_submitted() -> Result<()> {
+	let mm = init_test_mm().await?;
+	let seed = seed_org_with_users(&mm, "adminpwd", "viewpwd").await?;
+	let token = generate_web_token(&seed.admin.email, seed.admin.token_salt)?;
+	let cookie = cookie_header(&token.to_string());
+	let app = web_server::app(mm);
+
+	let case_id = create_case(&app, &cookie, seed.org_id).await?;
+	let (status, body) =
+		update_case_status(&app, &cookie, case_id, "submitted").await?;
+	assert_eq!(status, StatusCode::BAD_REQUEST, "{body:?}");
+	let (status, body) = transition_case(&app, &cookie, case_id, "archived").await?;
+	assert_eq!(status, StatusCode::BAD_REQUEST, "{body:?}");
+	assert!(body["error"]["data"]["detail"]
+		.as_str()
+		.unwrap_or_default()
+		.contains("draft -> archived"));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn test_validated_case_can_be_submitted_via_transition() -> Result<()> {
+	std::env::set_var("E2BR3_VALIDATOR_TOKEN", "validator-secret");
+	let mm = init_test_mm().await?;
+	let seed = seed_org_with_users(&mm, "adminpwd", "viewpwd").await?;
+	let token = generate_web_token(&seed.admin.email, seed.admin.token_salt)?;
+	let cookie = cookie_header(&token.to_string());
+	let app = web_server::app(mm);
+
+	let case_id = create_case(&app, &cookie, seed.org_id).await?;
+	create_safety_report(&app, &cookie, case_id).await?;
+	create_message_header(&app, &cookie, case_id).await?;
+	let (status, body) =
+		validator_mark_validated(&app, &cookie, case_id, Some("validator-secret"))
+			.await?;
+	assert_eq!(status, StatusCode::OK, "{body:?}");
+
+	let (status, body) = transition_case(&app, &cookie, case_id, "submitted").await?;
+	assert_eq!(status, StatusCode::OK, "{body:?}");
+	assert_eq!(body["data"]["status"].as_str(), Some("submitted"));
+	assert_eq!(
+		body["data"]["submitted_by"].as_str(),
+		Some(seed.admin.id.to_string().as_str())
+	);
+
+	let (status, body) = update_case_status(&app, &cookie, case_id, "draft").await?;
+	assert_eq!(status, StatusCode::BAD_REQUEST, "{body:?}");
+
+	Ok(())
+}
+
 #[serial]
 #[tokio::test]
 async fn test_validation_infers_mfds_profile_from_batch_receiver() -> Result<()> {
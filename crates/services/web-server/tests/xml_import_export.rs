@@ -68,9 +68,11 @@ async fn test_import_then_export_xml() -> Result<()> {
 	let value: Value = serde_json::from_slice(&body)?;
 	let case_id = value
 		.get("data")
+		.and_then(|v| v.get("reports"))
+		.and_then(|v| v.get(0))
 		.and_then(|v| v.get("case_id"))
 		.and_then(|v| v.as_str())
-		.ok_or("missing case_id in import response")?;
+		.ok_or("missing reports[0].case_id in import response")?;
 
 	let req = Request::builder()
 		.method("GET")
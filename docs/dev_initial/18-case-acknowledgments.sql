@@ -0,0 +1,78 @@
+-- ============================================================================
+-- Inbound acknowledgments (MCCI_IN200101UV01) per submitted case
+-- ============================================================================
+
+-- Record the regulator's verdict after submission.
+ALTER TABLE cases
+DROP CONSTRAINT IF EXISTS case_status_valid;
+
+ALTER TABLE cases
+ADD CONSTRAINT case_status_valid
+CHECK (status IN ('draft', 'checked', 'validated', 'submitted', 'acknowledged', 'rejected', 'archived', 'nullified'));
+
+CREATE TABLE IF NOT EXISTS case_acknowledgments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    case_id UUID NOT NULL REFERENCES cases(id) ON DELETE CASCADE,
+
+    -- ACK.M.1 - Acknowledgement Batch Number
+    ack_batch_number VARCHAR(100),
+
+    -- ACK.A.1 - ICSR Batch Number
+    batch_number VARCHAR(100),
+
+    -- ACK.A.4 - Transmission Acknowledgement Code (AA/AE/AR)
+    batch_ack_code VARCHAR(2),
+
+    -- ACK.B.r.1 - ICSR Message Number
+    message_number VARCHAR(200),
+
+    -- ACK.B.r.2 - Local Report Number
+    local_report_number VARCHAR(100),
+
+    -- ACK.B.r.6 - Acknowledgement Code for an ICSR Message (CA/CR)
+    ack_code VARCHAR(2),
+
+    -- ACK.B.r.7 / ACK.A.5 - Error / Warning Message or Comment
+    error_text TEXT,
+
+    -- ACK.M.4 - Acknowledgement Date of Batch Transmission
+    ack_date VARCHAR(30),
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT
+);
+
+CREATE INDEX IF NOT EXISTS idx_case_acknowledgments_case_id
+    ON case_acknowledgments(case_id, created_at DESC);
+
+CREATE TRIGGER update_case_acknowledgments_updated_at
+    BEFORE UPDATE ON case_acknowledgments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER audit_case_acknowledgments
+    AFTER INSERT OR UPDATE OR DELETE ON case_acknowledgments
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+
+ALTER TABLE case_acknowledgments ENABLE ROW LEVEL SECURITY;
+ALTER TABLE case_acknowledgments FORCE ROW LEVEL SECURITY;
+CREATE POLICY case_acknowledgments_via_case ON case_acknowledgments
+    FOR ALL TO e2br3_app_role
+    USING (
+        EXISTS (
+            SELECT 1 FROM cases c
+            WHERE c.id = case_acknowledgments.case_id
+            AND (c.organization_id = current_organization_id() OR is_current_user_admin())
+        )
+    )
+    WITH CHECK (
+        EXISTS (
+            SELECT 1 FROM cases c
+            WHERE c.id = case_acknowledgments.case_id
+            AND (c.organization_id = current_organization_id() OR is_current_user_admin())
+        )
+    );
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON case_acknowledgments TO e2br3_app_role;
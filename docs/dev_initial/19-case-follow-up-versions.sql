@@ -0,0 +1,75 @@
+-- ============================================================================
+-- Follow-up / amendment versions: a case copied into version N+1 locks version N
+-- ============================================================================
+
+ALTER TABLE cases
+    ADD COLUMN IF NOT EXISTS superseded_by UUID REFERENCES cases(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_cases_superseded_by ON cases(superseded_by);
+
+-- A superseded case keeps its lifecycle (ACKs, nullification) but its data is
+-- frozen. Every section table marks the owning case dirty through the
+-- mark_case_dirty_* triggers, so a nested UPDATE of a superseded case means one
+-- of its sections is being written and is rejected as well.
+CREATE OR REPLACE FUNCTION prevent_superseded_case_changes() RETURNS trigger AS $$
+BEGIN
+    IF OLD.superseded_by IS NULL
+       OR NEW.superseded_by IS DISTINCT FROM OLD.superseded_by THEN
+        RETURN NEW;
+    END IF;
+
+    IF pg_trigger_depth() > 1
+       OR (NEW.organization_id, NEW.safety_report_id, NEW.version, NEW.dg_prd_key,
+           NEW.validation_profile, NEW.raw_xml)
+          IS DISTINCT FROM
+          (OLD.organization_id, OLD.safety_report_id, OLD.version, OLD.dg_prd_key,
+           OLD.validation_profile, OLD.raw_xml) THEN
+        RAISE EXCEPTION 'case % (version %) is read-only: superseded by case %',
+            OLD.id, OLD.version, OLD.superseded_by
+            USING ERRCODE = 'object_not_in_prerequisite_state';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS prevent_superseded_case_changes ON cases;
+CREATE TRIGGER prevent_superseded_case_changes
+    BEFORE UPDATE ON cases
+    FOR EACH ROW EXECUTE FUNCTION prevent_superseded_case_changes();
+
+-- message_headers has no mark_case_dirty_* trigger, so the guard above never
+-- sees its writes; check the owning case directly. A cascaded delete runs after
+-- the case row is gone and passes.
+CREATE OR REPLACE FUNCTION prevent_superseded_message_header_changes() RETURNS trigger AS $$
+DECLARE
+    target_case_id UUID;
+    target_superseded_by UUID;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        target_case_id := OLD.case_id;
+    ELSE
+        target_case_id := NEW.case_id;
+    END IF;
+
+    SELECT superseded_by INTO target_superseded_by
+      FROM cases
+     WHERE id = target_case_id;
+
+    IF target_superseded_by IS NOT NULL THEN
+        RAISE EXCEPTION 'case % is read-only: superseded by case %',
+            target_case_id, target_superseded_by
+            USING ERRCODE = 'object_not_in_prerequisite_state';
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS prevent_superseded_message_header_changes ON message_headers;
+CREATE TRIGGER prevent_superseded_message_header_changes
+    BEFORE INSERT OR UPDATE OR DELETE ON message_headers
+    FOR EACH ROW EXECUTE FUNCTION prevent_superseded_message_header_changes();
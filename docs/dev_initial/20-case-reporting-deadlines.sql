@@ -0,0 +1,59 @@
+-- ============================================================================
+-- Regulatory reporting clock: expedited due date per case
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS case_reporting_deadlines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    case_id UUID NOT NULL UNIQUE REFERENCES cases(id) ON DELETE CASCADE,
+
+    -- Profile whose timelines were applied (fda, mfds, ich)
+    validation_profile VARCHAR(10) NOT NULL,
+
+    -- C.1.4 for an initial report, C.1.5 for a follow-up version
+    clock_start_date DATE NOT NULL,
+
+    -- Calendar days allowed (7 or 15); NULL when no expedited report is due
+    timeline_days INTEGER,
+    due_date DATE,
+
+    -- Why this timeline applies (fatal_or_life_threatening, serious, expedited, not_expedited)
+    basis VARCHAR(30) NOT NULL,
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT
+);
+
+CREATE INDEX IF NOT EXISTS idx_case_reporting_deadlines_due_date
+    ON case_reporting_deadlines(due_date);
+
+CREATE TRIGGER update_case_reporting_deadlines_updated_at
+    BEFORE UPDATE ON case_reporting_deadlines
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER audit_case_reporting_deadlines
+    AFTER INSERT OR UPDATE OR DELETE ON case_reporting_deadlines
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+
+ALTER TABLE case_reporting_deadlines ENABLE ROW LEVEL SECURITY;
+ALTER TABLE case_reporting_deadlines FORCE ROW LEVEL SECURITY;
+CREATE POLICY case_reporting_deadlines_via_case ON case_reporting_deadlines
+    FOR ALL TO e2br3_app_role
+    USING (
+        EXISTS (
+            SELECT 1 FROM cases c
+            WHERE c.id = case_reporting_deadlines.case_id
+            AND (c.organization_id = current_organization_id() OR is_current_user_admin())
+        )
+    )
+    WITH CHECK (
+        EXISTS (
+            SELECT 1 FROM cases c
+            WHERE c.id = case_reporting_deadlines.case_id
+            AND (c.organization_id = current_organization_id() OR is_current_user_admin())
+        )
+    );
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON case_reporting_deadlines TO e2br3_app_role;
@@ -0,0 +1,56 @@
+-- ============================================================================
+-- Partial dates: E2B(R3) date elements keep the precision they were given
+-- (CCYY, CCYYMM, CCYYMMDD, CCYYMMDDhhmm or CCYYMMDDhhmmss, optional +/-ZZzz)
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION is_e2b_partial_date(value TEXT)
+RETURNS BOOLEAN AS $$
+    SELECT value ~ '^[0-9]{4}([0-9]{2}([0-9]{2}([0-9]{4}([0-9]{2})?)?)?)?([+-][0-9]{4})?$'
+$$ LANGUAGE sql IMMUTABLE;
+
+DO $$
+DECLARE
+    col RECORD;
+BEGIN
+    FOR col IN
+        SELECT * FROM (VALUES
+            ('patient_information', 'birth_date'),                     -- D.2.1
+            ('patient_information', 'last_menstrual_period_date'),     -- D.6
+            ('medical_history_episodes', 'start_date'),                -- D.7.1.r.2
+            ('medical_history_episodes', 'end_date'),                  -- D.7.1.r.4
+            ('past_drug_history', 'start_date'),                       -- D.8.r.4
+            ('past_drug_history', 'end_date'),                         -- D.8.r.5
+            ('patient_death_information', 'date_of_death'),            -- D.9.1
+            ('parent_information', 'parent_birth_date'),               -- D.10.2.1
+            ('parent_information', 'last_menstrual_period_date'),      -- D.10.3
+            ('parent_medical_history', 'start_date'),                  -- D.10.7.1.r.2
+            ('parent_medical_history', 'end_date'),                    -- D.10.7.1.r.4
+            ('parent_past_drug_history', 'start_date'),                -- D.10.8.r.4
+            ('parent_past_drug_history', 'end_date'),                  -- D.10.8.r.5
+            ('reactions', 'start_date'),                               -- E.i.4
+            ('reactions', 'end_date'),                                 -- E.i.5
+            ('test_results', 'test_date'),                             -- F.r.1
+            ('dosage_information', 'first_administration_date'),       -- G.k.4.r.4
+            ('dosage_information', 'last_administration_date')         -- G.k.4.r.5
+        ) AS v(table_name, column_name)
+    LOOP
+        IF EXISTS (
+            SELECT 1 FROM information_schema.columns c
+            WHERE c.table_schema = 'public'
+              AND c.table_name = col.table_name
+              AND c.column_name = col.column_name
+              AND c.data_type = 'date'
+        ) THEN
+            EXECUTE format(
+                'ALTER TABLE %I ALTER COLUMN %I TYPE VARCHAR(20) USING to_char(%I, ''YYYYMMDD'')',
+                col.table_name, col.column_name, col.column_name
+            );
+            EXECUTE format(
+                'ALTER TABLE %I ADD CONSTRAINT %I CHECK (%I IS NULL OR is_e2b_partial_date(%I))',
+                col.table_name,
+                col.table_name || '_' || col.column_name || '_partial_date',
+                col.column_name, col.column_name
+            );
+        END IF;
+    END LOOP;
+END $$;
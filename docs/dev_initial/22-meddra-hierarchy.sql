@@ -0,0 +1,63 @@
+-- ============================================================================
+-- MedDRA hierarchy: LLT -> PT -> HLT -> HLGT -> SOC, per MedDRA version
+-- ============================================================================
+
+-- Every PT is also distributed as an LLT with the same code, so a term is
+-- identified by its level as well.
+ALTER TABLE meddra_terms DROP CONSTRAINT IF EXISTS unique_meddra_code_version;
+ALTER TABLE meddra_terms
+    ADD CONSTRAINT unique_meddra_code_level_version UNIQUE (code, level, version, language);
+
+-- LLT: the PT it belongs to (llt.asc pt_code); PT: its own code
+ALTER TABLE meddra_terms ADD COLUMN IF NOT EXISTS pt_code VARCHAR(20);
+-- LLT currency flag (llt.asc llt_currency = 'Y'); always true for other levels
+ALTER TABLE meddra_terms ADD COLUMN IF NOT EXISTS is_current BOOLEAN NOT NULL DEFAULT true;
+
+CREATE INDEX IF NOT EXISTS idx_meddra_pt_code ON meddra_terms(version, pt_code);
+
+-- Multi-axial PT placement (mdhier.asc): one row per PT/HLT/HLGT/SOC path
+CREATE TABLE IF NOT EXISTS meddra_hierarchy (
+    id BIGSERIAL PRIMARY KEY,
+    audit_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    version VARCHAR(10) NOT NULL,
+    language VARCHAR(2) NOT NULL DEFAULT 'en',
+    pt_code VARCHAR(20) NOT NULL,
+    hlt_code VARCHAR(20) NOT NULL,
+    hlgt_code VARCHAR(20) NOT NULL,
+    soc_code VARCHAR(20) NOT NULL,
+    -- mdhier.asc primary_soc_fg = 'Y'; exactly one path per PT
+    primary_soc BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_meddra_hierarchy_path
+        UNIQUE (version, language, pt_code, hlt_code, hlgt_code, soc_code),
+    CONSTRAINT unique_meddra_hierarchy_audit_id UNIQUE (audit_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_meddra_hierarchy_pt
+    ON meddra_hierarchy(version, pt_code);
+CREATE INDEX IF NOT EXISTS idx_meddra_hierarchy_soc
+    ON meddra_hierarchy(version, soc_code);
+
+CREATE TRIGGER audit_meddra_hierarchy
+    AFTER INSERT OR UPDATE OR DELETE ON meddra_hierarchy
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function_with_audit_id();
+
+ALTER TABLE meddra_hierarchy ENABLE ROW LEVEL SECURITY;
+ALTER TABLE meddra_hierarchy FORCE ROW LEVEL SECURITY;
+CREATE POLICY meddra_hierarchy_read ON meddra_hierarchy
+    FOR SELECT TO e2br3_app_role
+    USING (is_current_user_admin());
+CREATE POLICY meddra_hierarchy_insert ON meddra_hierarchy
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY meddra_hierarchy_update ON meddra_hierarchy
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY meddra_hierarchy_delete ON meddra_hierarchy
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON meddra_hierarchy TO e2br3_app_role;
+GRANT USAGE, SELECT ON SEQUENCE meddra_hierarchy_id_seq TO e2br3_app_role;
@@ -0,0 +1,134 @@
+-- ============================================================================
+-- WHODrug Global (B3 format): drug records, ingredients and ATC classification
+-- ============================================================================
+
+-- code = drug record number + sequence number 1 + sequence number 2
+ALTER TABLE whodrug_products ADD COLUMN IF NOT EXISTS drug_record_number VARCHAR(6);
+ALTER TABLE whodrug_products ADD COLUMN IF NOT EXISTS sequence_number_1 VARCHAR(2);
+ALTER TABLE whodrug_products ADD COLUMN IF NOT EXISTS sequence_number_2 VARCHAR(3);
+-- B3 drug names run up to 1500 characters
+ALTER TABLE whodrug_products ALTER COLUMN drug_name TYPE VARCHAR(1500);
+
+CREATE INDEX IF NOT EXISTS idx_whodrug_drug_record
+    ON whodrug_products(version, drug_record_number);
+
+-- Active ingredients of a drug record (ING.txt joined to BNA.txt)
+CREATE TABLE IF NOT EXISTS whodrug_ingredients (
+    id BIGSERIAL PRIMARY KEY,
+    audit_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    version VARCHAR(10) NOT NULL,
+    language VARCHAR(2) NOT NULL DEFAULT 'en',
+    product_code VARCHAR(20) NOT NULL,
+    sequence_number INTEGER NOT NULL,
+    substance_number VARCHAR(10) NOT NULL,
+    substance_name VARCHAR(250) NOT NULL,
+    cas_number VARCHAR(10),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_whodrug_ingredient
+        UNIQUE (version, language, product_code, substance_number),
+    CONSTRAINT unique_whodrug_ingredients_audit_id UNIQUE (audit_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_whodrug_ingredients_product
+    ON whodrug_ingredients(version, product_code);
+
+-- ATC codes of a drug record (DDA.txt)
+CREATE TABLE IF NOT EXISTS whodrug_product_atc (
+    id BIGSERIAL PRIMARY KEY,
+    audit_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    version VARCHAR(10) NOT NULL,
+    language VARCHAR(2) NOT NULL DEFAULT 'en',
+    product_code VARCHAR(20) NOT NULL,
+    atc_code VARCHAR(7) NOT NULL,
+    -- DDA.txt official ATC code flag = 'Y' (WHO assigned)
+    official BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_whodrug_product_atc
+        UNIQUE (version, language, product_code, atc_code),
+    CONSTRAINT unique_whodrug_product_atc_audit_id UNIQUE (audit_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_whodrug_product_atc_product
+    ON whodrug_product_atc(version, product_code);
+
+-- ATC classification texts, levels 1-5 (INA.txt)
+CREATE TABLE IF NOT EXISTS whodrug_atc_classes (
+    id BIGSERIAL PRIMARY KEY,
+    audit_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    version VARCHAR(10) NOT NULL,
+    language VARCHAR(2) NOT NULL DEFAULT 'en',
+    atc_code VARCHAR(7) NOT NULL,
+    level SMALLINT NOT NULL,
+    text VARCHAR(110) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_whodrug_atc_class UNIQUE (version, language, atc_code),
+    CONSTRAINT unique_whodrug_atc_classes_audit_id UNIQUE (audit_id)
+);
+
+CREATE TRIGGER audit_whodrug_ingredients
+    AFTER INSERT OR UPDATE OR DELETE ON whodrug_ingredients
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function_with_audit_id();
+CREATE TRIGGER audit_whodrug_product_atc
+    AFTER INSERT OR UPDATE OR DELETE ON whodrug_product_atc
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function_with_audit_id();
+CREATE TRIGGER audit_whodrug_atc_classes
+    AFTER INSERT OR UPDATE OR DELETE ON whodrug_atc_classes
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function_with_audit_id();
+
+ALTER TABLE whodrug_ingredients ENABLE ROW LEVEL SECURITY;
+ALTER TABLE whodrug_ingredients FORCE ROW LEVEL SECURITY;
+CREATE POLICY whodrug_ingredients_read ON whodrug_ingredients
+    FOR SELECT TO e2br3_app_role
+    USING (is_current_user_admin());
+CREATE POLICY whodrug_ingredients_insert ON whodrug_ingredients
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY whodrug_ingredients_update ON whodrug_ingredients
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY whodrug_ingredients_delete ON whodrug_ingredients
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+ALTER TABLE whodrug_product_atc ENABLE ROW LEVEL SECURITY;
+ALTER TABLE whodrug_product_atc FORCE ROW LEVEL SECURITY;
+CREATE POLICY whodrug_product_atc_read ON whodrug_product_atc
+    FOR SELECT TO e2br3_app_role
+    USING (is_current_user_admin());
+CREATE POLICY whodrug_product_atc_insert ON whodrug_product_atc
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY whodrug_product_atc_update ON whodrug_product_atc
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY whodrug_product_atc_delete ON whodrug_product_atc
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+ALTER TABLE whodrug_atc_classes ENABLE ROW LEVEL SECURITY;
+ALTER TABLE whodrug_atc_classes FORCE ROW LEVEL SECURITY;
+CREATE POLICY whodrug_atc_classes_read ON whodrug_atc_classes
+    FOR SELECT TO e2br3_app_role
+    USING (is_current_user_admin());
+CREATE POLICY whodrug_atc_classes_insert ON whodrug_atc_classes
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY whodrug_atc_classes_update ON whodrug_atc_classes
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY whodrug_atc_classes_delete ON whodrug_atc_classes
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON whodrug_ingredients TO e2br3_app_role;
+GRANT SELECT, INSERT, UPDATE, DELETE ON whodrug_product_atc TO e2br3_app_role;
+GRANT SELECT, INSERT, UPDATE, DELETE ON whodrug_atc_classes TO e2br3_app_role;
+GRANT USAGE, SELECT ON SEQUENCE whodrug_ingredients_id_seq TO e2br3_app_role;
+GRANT USAGE, SELECT ON SEQUENCE whodrug_product_atc_id_seq TO e2br3_app_role;
+GRANT USAGE, SELECT ON SEQUENCE whodrug_atc_classes_id_seq TO e2br3_app_role;
@@ -0,0 +1,66 @@
+-- ============================================================================
+-- Coding assistant: trigram matching and per-organization coding memory
+-- ============================================================================
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Fuzzy (trigram) lookup of verbatim text against dictionary terms
+CREATE INDEX IF NOT EXISTS idx_meddra_term_trgm
+    ON meddra_terms USING gin(lower(term) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_whodrug_name_trgm
+    ON whodrug_products USING gin(lower(drug_name) gin_trgm_ops);
+
+-- Codes accepted for a verbatim term within an organization
+CREATE TABLE IF NOT EXISTS coding_memory (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    -- meddra (LLT code) or whodrug (product code)
+    dictionary VARCHAR(10) NOT NULL CHECK (dictionary IN ('meddra', 'whodrug')),
+
+    -- Verbatim as typed, and its lower-cased, whitespace-collapsed lookup key
+    verbatim VARCHAR(1500) NOT NULL,
+    verbatim_key VARCHAR(1500) NOT NULL,
+
+    code VARCHAR(20) NOT NULL,
+    version VARCHAR(10) NOT NULL,
+    term VARCHAR(1500) NOT NULL,
+
+    accept_count INTEGER NOT NULL DEFAULT 1,
+    last_accepted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT,
+
+    CONSTRAINT unique_coding_memory
+        UNIQUE (organization_id, dictionary, verbatim_key, code)
+);
+
+CREATE INDEX IF NOT EXISTS idx_coding_memory_lookup
+    ON coding_memory(organization_id, dictionary, verbatim_key);
+CREATE INDEX IF NOT EXISTS idx_coding_memory_key_trgm
+    ON coding_memory USING gin(verbatim_key gin_trgm_ops);
+
+CREATE TRIGGER update_coding_memory_updated_at
+    BEFORE UPDATE ON coding_memory
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER audit_coding_memory
+    AFTER INSERT OR UPDATE OR DELETE ON coding_memory
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+
+ALTER TABLE coding_memory ENABLE ROW LEVEL SECURITY;
+ALTER TABLE coding_memory FORCE ROW LEVEL SECURITY;
+CREATE POLICY coding_memory_org_isolation ON coding_memory
+    FOR ALL TO e2br3_app_role
+    USING (
+        organization_id = current_organization_id() OR is_current_user_admin()
+    )
+    WITH CHECK (
+        organization_id = current_organization_id() OR is_current_user_admin()
+    );
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON coding_memory TO e2br3_app_role;
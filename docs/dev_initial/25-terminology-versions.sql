@@ -0,0 +1,106 @@
+-- ============================================================================
+-- Terminology versions: bulk-loaded releases and per-organization activation
+-- ============================================================================
+
+-- Countries and code lists become versioned like MedDRA/WHODrug; the rows
+-- seeded by 11-terminology.sql are version 'base'.
+ALTER TABLE iso_countries ADD COLUMN IF NOT EXISTS version VARCHAR(10) NOT NULL DEFAULT 'base';
+ALTER TABLE iso_countries DROP CONSTRAINT IF EXISTS iso_countries_pkey;
+ALTER TABLE iso_countries ADD PRIMARY KEY (code, version);
+
+ALTER TABLE e2b_code_lists ADD COLUMN IF NOT EXISTS version VARCHAR(10) NOT NULL DEFAULT 'base';
+ALTER TABLE e2b_code_lists DROP CONSTRAINT IF EXISTS unique_code_per_list;
+ALTER TABLE e2b_code_lists
+    ADD CONSTRAINT unique_code_per_list UNIQUE (list_name, code, version);
+
+-- A release loaded through the import endpoints. Rows of a registered version
+-- are only served by default to organizations that activated it.
+CREATE TABLE IF NOT EXISTS terminology_versions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- meddra, whodrug, iso_countries, e2b_code_lists
+    dictionary VARCHAR(20) NOT NULL
+        CHECK (dictionary IN ('meddra', 'whodrug', 'iso_countries', 'e2b_code_lists')),
+    version VARCHAR(10) NOT NULL,
+    language VARCHAR(2) NOT NULL DEFAULT 'en',
+
+    -- Row counts per loaded table
+    summary JSONB NOT NULL DEFAULT '{}'::jsonb,
+    file_names TEXT[] NOT NULL DEFAULT '{}',
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT,
+
+    CONSTRAINT unique_terminology_version UNIQUE (dictionary, version, language)
+);
+
+-- Activation history: the latest row per organization and dictionary is the
+-- version in use; rows are never updated.
+CREATE TABLE IF NOT EXISTS terminology_activations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    terminology_version_id UUID NOT NULL
+        REFERENCES terminology_versions(id) ON DELETE RESTRICT,
+    dictionary VARCHAR(20) NOT NULL,
+    version VARCHAR(10) NOT NULL,
+    language VARCHAR(2) NOT NULL,
+    -- Version active before this one, NULL for the first activation
+    previous_version VARCHAR(10),
+
+    activated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT
+);
+
+CREATE INDEX IF NOT EXISTS idx_terminology_activations_org
+    ON terminology_activations(organization_id, dictionary, activated_at DESC);
+
+CREATE TRIGGER update_terminology_versions_updated_at
+    BEFORE UPDATE ON terminology_versions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER audit_terminology_versions
+    AFTER INSERT OR UPDATE OR DELETE ON terminology_versions
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+CREATE TRIGGER audit_terminology_activations
+    AFTER INSERT OR UPDATE OR DELETE ON terminology_activations
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+
+-- Every organization needs to see which versions are registered, so default
+-- searches can leave out versions it has not activated.
+ALTER TABLE terminology_versions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE terminology_versions FORCE ROW LEVEL SECURITY;
+CREATE POLICY terminology_versions_read ON terminology_versions
+    FOR SELECT TO e2br3_app_role
+    USING (true);
+CREATE POLICY terminology_versions_insert ON terminology_versions
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY terminology_versions_update ON terminology_versions
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY terminology_versions_delete ON terminology_versions
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+ALTER TABLE terminology_activations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE terminology_activations FORCE ROW LEVEL SECURITY;
+CREATE POLICY terminology_activations_org_isolation ON terminology_activations
+    FOR ALL TO e2br3_app_role
+    USING (
+        organization_id = current_organization_id() OR is_current_user_admin()
+    )
+    WITH CHECK (
+        organization_id = current_organization_id() OR is_current_user_admin()
+    );
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON terminology_versions TO e2br3_app_role;
+GRANT SELECT, INSERT, UPDATE, DELETE ON terminology_activations TO e2br3_app_role;
@@ -0,0 +1,170 @@
+-- ============================================================================
+-- E2B code lists: OIDs, regional profiles and the remaining ICH/FDA/MFDS lists
+-- ============================================================================
+
+-- Code system OID of each code (as sent in @codeSystem) and the validation
+-- profile whose messages use the list; ICH lists apply to every profile.
+ALTER TABLE e2b_code_lists ADD COLUMN IF NOT EXISTS oid VARCHAR(64);
+ALTER TABLE e2b_code_lists ADD COLUMN IF NOT EXISTS profile VARCHAR(10) NOT NULL DEFAULT 'ich'
+    CHECK (profile IN ('ich', 'fda', 'mfds'));
+
+CREATE INDEX IF NOT EXISTS idx_code_lists_name_code ON e2b_code_lists(list_name, code);
+
+-- Import, validation and export look coded values up for every user, not only
+-- admins; changes to the lists stay admin-only.
+DROP POLICY IF EXISTS e2b_code_lists_read ON e2b_code_lists;
+CREATE POLICY e2b_code_lists_read ON e2b_code_lists
+    FOR SELECT TO e2br3_app_role
+    USING (true);
+
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.2' WHERE list_name = 'report_type';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.6' WHERE list_name = 'qualification';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.7' WHERE list_name = 'sender_type';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.8' WHERE list_name = 'study_type';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.9' WHERE list_name = 'age_group';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.10' WHERE list_name = 'term_highlighted';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.11' WHERE list_name = 'reaction_outcome';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.13' WHERE list_name = 'drug_characterization';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.14' WHERE list_name = 'route_of_administration';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.15' WHERE list_name = 'drug_action';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.3.989.2.1.1.16' WHERE list_name = 'rechallenge';
+UPDATE e2b_code_lists SET oid = '1.0.5218' WHERE list_name = 'sex';
+UPDATE e2b_code_lists SET oid = '2.16.840.1.113883.5.1008' WHERE list_name = 'null_flavor';
+
+INSERT INTO e2b_code_lists (list_name, code, display_name, sort_order, oid, profile) VALUES
+-- Nullification / Amendment (C.1.11.1)
+('nullification_code', '1', 'Nullification', 1, '2.16.840.1.113883.3.989.2.1.1.5', 'ich'),
+('nullification_code', '2', 'Amendment', 2, '2.16.840.1.113883.3.989.2.1.1.5', 'ich'),
+
+-- Test Result Assessment (F.r.3.1)
+('test_result_assessment', '1', 'Positive', 1, '2.16.840.1.113883.3.989.2.1.1.12', 'ich'),
+('test_result_assessment', '2', 'Negative', 2, '2.16.840.1.113883.3.989.2.1.1.12', 'ich'),
+('test_result_assessment', '3', 'Borderline', 3, '2.16.840.1.113883.3.989.2.1.1.12', 'ich'),
+('test_result_assessment', '4', 'Inconclusive', 4, '2.16.840.1.113883.3.989.2.1.1.12', 'ich'),
+
+-- Additional Information on Drug (G.k.10.r)
+('additional_drug_information', '1', 'Counterfeit', 1, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '2', 'Overdose', 2, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '3', 'Drug taken by the father', 3, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '4', 'Drug taken beyond expiry date', 4, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '5', 'Batch and lot tested and found within specifications', 5, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '6', 'Batch and lot tested and found not within specifications', 6, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '7', 'Medication error', 7, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '8', 'Misuse', 8, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '9', 'Abuse', 9, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '10', 'Occupational exposure', 10, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+('additional_drug_information', '11', 'Off label use', 11, '2.16.840.1.113883.3.989.2.1.1.17', 'ich'),
+
+-- Age Unit (D.2.2b, D.10.2.2b) as UCUM, used by E2B(R3) messages
+('age_unit', 'a', 'Year', 7, '2.16.840.1.113883.6.8', 'ich'),
+('age_unit', 'mo', 'Month', 8, '2.16.840.1.113883.6.8', 'ich'),
+('age_unit', 'wk', 'Week', 9, '2.16.840.1.113883.6.8', 'ich'),
+('age_unit', 'd', 'Day', 10, '2.16.840.1.113883.6.8', 'ich'),
+('age_unit', 'h', 'Hour', 11, '2.16.840.1.113883.6.8', 'ich'),
+('age_unit', 'min', 'Minute', 12, '2.16.840.1.113883.6.8', 'ich'),
+('age_unit', '{decade}', 'Decade', 13, '2.16.840.1.113883.6.8', 'ich'),
+
+-- Gestation Period Unit (D.2.2.1b)
+('gestation_period_unit', 'd', 'Day', 1, '2.16.840.1.113883.6.8', 'ich'),
+('gestation_period_unit', 'wk', 'Week', 2, '2.16.840.1.113883.6.8', 'ich'),
+('gestation_period_unit', 'mo', 'Month', 3, '2.16.840.1.113883.6.8', 'ich'),
+('gestation_period_unit', '{trimester}', 'Trimester', 4, '2.16.840.1.113883.6.8', 'ich'),
+
+-- Time Unit for durations and dosing frequency (E.i.6b, G.k.4.r.3, G.k.4.r.6b)
+('time_unit', 'a', 'Year', 1, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', 'mo', 'Month', 2, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', 'wk', 'Week', 3, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', 'd', 'Day', 4, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', 'h', 'Hour', 5, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', 'min', 'Minute', 6, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', 's', 'Second', 7, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', '{cyclical}', 'Cyclical', 8, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', '{asnecessary}', 'As necessary', 9, '2.16.840.1.113883.6.8', 'ich'),
+('time_unit', '{total}', 'Total', 10, '2.16.840.1.113883.6.8', 'ich'),
+
+-- Dose Unit (G.k.4.r.1b, G.k.5b) - UCUM units of the ICH constrained list
+('dose_unit', 'kg', 'Kilogram', 1, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'g', 'Gram', 2, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mg', 'Milligram', 3, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'ug', 'Microgram', 4, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'ng', 'Nanogram', 5, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'pg', 'Picogram', 6, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mg/kg', 'Milligram per kilogram', 7, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'ug/kg', 'Microgram per kilogram', 8, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mg/m2', 'Milligram per square meter', 9, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'ug/m2', 'Microgram per square meter', 10, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'L', 'Litre', 11, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mL', 'Millilitre', 12, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'uL', 'Microlitre', 13, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mol', 'Mole', 14, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mmol', 'Millimole', 15, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'umol', 'Micromole', 16, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', '[iU]', 'International unit', 17, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'k[iU]', 'Kilo international unit', 18, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'M[iU]', 'Mega international unit', 19, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', '[iU]/kg', 'International unit per kilogram', 20, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'meq', 'Milliequivalent', 21, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', '%', 'Percent', 22, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', '[drp]', 'Drop', 23, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'Bq', 'Becquerel', 24, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'MBq', 'Megabecquerel', 25, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'Ci', 'Curie', 26, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'mCi', 'Millicurie', 27, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', 'Gy', 'Gray', 28, '2.16.840.1.113883.6.8', 'ich'),
+('dose_unit', '{DF}', 'Dosage form', 29, '2.16.840.1.113883.6.8', 'ich'),
+
+-- FDA Local Criteria Report Type (FDA.C.1.7.1)
+('local_criteria_report_type', '1', '15-Day', 1, '2.16.840.1.113883.3.989.5.1.2.2.1.1.1', 'fda'),
+('local_criteria_report_type', '2', 'Non-Expedited AE', 2, '2.16.840.1.113883.3.989.5.1.2.2.1.1.1', 'fda'),
+('local_criteria_report_type', '3', 'Other', 3, '2.16.840.1.113883.3.989.5.1.2.2.1.1.1', 'fda'),
+('local_criteria_report_type', '4', '5-Day', 4, '2.16.840.1.113883.3.989.5.1.2.2.1.1.1', 'fda'),
+('local_criteria_report_type', '5', '30-Day', 5, '2.16.840.1.113883.3.989.5.1.2.2.1.1.1', 'fda'),
+
+-- FDA Patient Race (FDA.D.11), NCI Thesaurus
+('fda_race', 'C41259', 'American Indian or Alaska Native', 1, '2.16.840.1.113883.3.26.1.1', 'fda'),
+('fda_race', 'C41260', 'Asian', 2, '2.16.840.1.113883.3.26.1.1', 'fda'),
+('fda_race', 'C16352', 'Black or African American', 3, '2.16.840.1.113883.3.26.1.1', 'fda'),
+('fda_race', 'C41219', 'Native Hawaiian or Other Pacific Islander', 4, '2.16.840.1.113883.3.26.1.1', 'fda'),
+('fda_race', 'C41261', 'White', 5, '2.16.840.1.113883.3.26.1.1', 'fda'),
+
+-- FDA Patient Ethnicity (FDA.D.12), NCI Thesaurus
+('fda_ethnicity', 'C17459', 'Hispanic or Latino', 1, '2.16.840.1.113883.3.26.1.1', 'fda'),
+('fda_ethnicity', 'C41222', 'Not Hispanic or Latino', 2, '2.16.840.1.113883.3.26.1.1', 'fda'),
+
+-- MFDS Other Health Professional Type (C.2.r.4.KR.1)
+('kr_other_health_professional', '1', 'Nurse', 1, NULL, 'mfds'),
+('kr_other_health_professional', '2', 'Other', 2, NULL, 'mfds'),
+
+-- MFDS Health Professional Type of Sender (C.3.1.KR.1)
+('kr_health_professional_type', '1', 'Clinic/hospital', 1, NULL, 'mfds'),
+('kr_health_professional_type', '2', 'Pharmacy', 2, NULL, 'mfds'),
+('kr_health_professional_type', '3', 'Public health center', 3, NULL, 'mfds'),
+('kr_health_professional_type', '4', 'Other', 4, NULL, 'mfds'),
+
+-- MFDS Other Studies Type (C.5.4.KR.1)
+('kr_other_study_type', '1', 'Investigation of usage', 1, NULL, 'mfds'),
+('kr_other_study_type', '2', 'Post-marketing clinical trial', 2, NULL, 'mfds'),
+('kr_other_study_type', '3', 'Special investigation', 3, NULL, 'mfds'),
+('kr_other_study_type', '4', 'Other', 4, NULL, 'mfds'),
+
+-- MFDS Method of Assessment (G.k.9.i.2.r.2.KR.1)
+('kr_assessment_method', '1', 'WHO-UMC', 1, NULL, 'mfds'),
+('kr_assessment_method', '2', 'Korean algorithm', 2, NULL, 'mfds'),
+('kr_assessment_method', '3', 'Other', 3, NULL, 'mfds'),
+
+-- MFDS Result of Assessment, WHO-UMC (G.k.9.i.2.r.3.KR.1)
+('kr_assessment_result_who_umc', '1', 'Certain', 1, NULL, 'mfds'),
+('kr_assessment_result_who_umc', '2', 'Probable/likely', 2, NULL, 'mfds'),
+('kr_assessment_result_who_umc', '3', 'Possible', 3, NULL, 'mfds'),
+('kr_assessment_result_who_umc', '4', 'Unlikely', 4, NULL, 'mfds'),
+('kr_assessment_result_who_umc', '5', 'Conditional/unclassified', 5, NULL, 'mfds'),
+('kr_assessment_result_who_umc', '6', 'Unassessable/unclassifiable', 6, NULL, 'mfds'),
+
+-- MFDS Result of Assessment, Korean algorithm (G.k.9.i.2.r.3.KR.2)
+('kr_assessment_result_korean', '1', 'Certain', 1, NULL, 'mfds'),
+('kr_assessment_result_korean', '2', 'Probable', 2, NULL, 'mfds'),
+('kr_assessment_result_korean', '3', 'Possible', 3, NULL, 'mfds'),
+('kr_assessment_result_korean', '4', 'Unlikely', 4, NULL, 'mfds'),
+('kr_assessment_result_korean', '5', 'Conditional', 5, NULL, 'mfds'),
+('kr_assessment_result_korean', '6', 'Unassessable', 6, NULL, 'mfds')
+ON CONFLICT ON CONSTRAINT unique_code_per_list DO NOTHING;
@@ -0,0 +1,103 @@
+-- ============================================================================
+-- Validation rules: stored definitions and per-organization severity overrides
+-- ============================================================================
+
+-- Every write to a rule or an override takes the next revision, so the rule
+-- set version changes with each edit.
+CREATE SEQUENCE IF NOT EXISTS validation_rule_revision_seq;
+
+-- Rule definitions; codes without a row use the compiled-in catalog, which
+-- POST /api/validation/rules/seed copies here.
+CREATE TABLE IF NOT EXISTS validation_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(120) NOT NULL,
+
+    profile VARCHAR(10) NOT NULL CHECK (profile IN ('ich', 'fda', 'mfds')),
+    section VARCHAR(60) NOT NULL,
+    blocking BOOLEAN NOT NULL,
+    message TEXT NOT NULL,
+    xpath TEXT,
+    -- Boolean expression over named conditions and rule facts
+    condition TEXT NOT NULL DEFAULT 'always',
+    active BOOLEAN NOT NULL DEFAULT true,
+    revision BIGINT NOT NULL DEFAULT nextval('validation_rule_revision_seq'),
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT,
+
+    CONSTRAINT unique_validation_rule_code UNIQUE (code)
+);
+
+-- Severity chosen by an organization for a rule, stored or compiled-in.
+CREATE TABLE IF NOT EXISTS validation_rule_overrides (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    rule_code VARCHAR(120) NOT NULL,
+    blocking BOOLEAN NOT NULL,
+    revision BIGINT NOT NULL DEFAULT nextval('validation_rule_revision_seq'),
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT,
+
+    CONSTRAINT unique_validation_rule_override UNIQUE (organization_id, rule_code)
+);
+
+CREATE TRIGGER update_validation_rules_updated_at
+    BEFORE UPDATE ON validation_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+CREATE TRIGGER update_validation_rule_overrides_updated_at
+    BEFORE UPDATE ON validation_rule_overrides
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER audit_validation_rules
+    AFTER INSERT OR UPDATE OR DELETE ON validation_rules
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+CREATE TRIGGER audit_validation_rule_overrides
+    AFTER INSERT OR UPDATE OR DELETE ON validation_rule_overrides
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+
+-- Every validation reads the definitions; only admins edit them.
+ALTER TABLE validation_rules ENABLE ROW LEVEL SECURITY;
+ALTER TABLE validation_rules FORCE ROW LEVEL SECURITY;
+CREATE POLICY validation_rules_read ON validation_rules
+    FOR SELECT TO e2br3_app_role
+    USING (true);
+CREATE POLICY validation_rules_insert ON validation_rules
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY validation_rules_update ON validation_rules
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY validation_rules_delete ON validation_rules
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+-- Organizations read their own overrides; only admins write them.
+ALTER TABLE validation_rule_overrides ENABLE ROW LEVEL SECURITY;
+ALTER TABLE validation_rule_overrides FORCE ROW LEVEL SECURITY;
+CREATE POLICY validation_rule_overrides_read ON validation_rule_overrides
+    FOR SELECT TO e2br3_app_role
+    USING (
+        organization_id = current_organization_id() OR is_current_user_admin()
+    );
+CREATE POLICY validation_rule_overrides_insert ON validation_rule_overrides
+    FOR INSERT TO e2br3_app_role
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY validation_rule_overrides_update ON validation_rule_overrides
+    FOR UPDATE TO e2br3_app_role
+    USING (is_current_user_admin())
+    WITH CHECK (is_current_user_admin());
+CREATE POLICY validation_rule_overrides_delete ON validation_rule_overrides
+    FOR DELETE TO e2br3_app_role
+    USING (is_current_user_admin());
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON validation_rules TO e2br3_app_role;
+GRANT SELECT, INSERT, UPDATE, DELETE ON validation_rule_overrides TO e2br3_app_role;
+GRANT USAGE, SELECT ON SEQUENCE validation_rule_revision_seq TO e2br3_app_role;
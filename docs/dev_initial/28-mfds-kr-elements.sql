@@ -0,0 +1,35 @@
+-- ============================================================================
+-- MFDS (Korea) regional data elements
+-- ============================================================================
+
+-- C.2.r.4.KR.1 - Other Health Professional type (when C.2.r.4 = 3)
+ALTER TABLE primary_sources ADD COLUMN IF NOT EXISTS kr_other_health_professional VARCHAR(10);
+
+-- C.3.1.KR.1 - Health Professional type (when C.3.1 = 3)
+ALTER TABLE sender_information ADD COLUMN IF NOT EXISTS kr_health_professional_type VARCHAR(10);
+
+-- C.5.4.KR.1 - Other Studies type (when C.5.4 = 3)
+ALTER TABLE study_information ADD COLUMN IF NOT EXISTS kr_other_study_type VARCHAR(10);
+
+-- D.8.r.1.KR.1a / D.8.r.1.KR.1b - MFDS Product Code Version / Product Code
+ALTER TABLE past_drug_history ADD COLUMN IF NOT EXISTS kr_product_code_version VARCHAR(10);
+ALTER TABLE past_drug_history ADD COLUMN IF NOT EXISTS kr_product_code VARCHAR(100);
+
+-- D.10.8.r.1.KR.1a / D.10.8.r.1.KR.1b - MFDS Product Code Version / Product Code
+ALTER TABLE parent_past_drug_history ADD COLUMN IF NOT EXISTS kr_product_code_version VARCHAR(10);
+ALTER TABLE parent_past_drug_history ADD COLUMN IF NOT EXISTS kr_product_code VARCHAR(100);
+
+-- G.k.2.1.KR.1a / G.k.2.1.KR.1b - MFDS Product Code Version / Product Code
+ALTER TABLE drug_information ADD COLUMN IF NOT EXISTS kr_product_code_version VARCHAR(10);
+ALTER TABLE drug_information ADD COLUMN IF NOT EXISTS kr_product_code VARCHAR(100);
+
+-- G.k.2.3.r.1.KR.1a / G.k.2.3.r.1.KR.1b - MFDS Ingredient Code Version / Ingredient Code
+ALTER TABLE drug_active_substances ADD COLUMN IF NOT EXISTS kr_ingredient_code_version VARCHAR(10);
+ALTER TABLE drug_active_substances ADD COLUMN IF NOT EXISTS kr_ingredient_code VARCHAR(100);
+
+-- G.k.9.i.2.r.2.KR.1 - KR Method of Assessment
+-- G.k.9.i.2.r.3.KR.1 - KR Result of Assessment (WHO-UMC)
+-- G.k.9.i.2.r.3.KR.2 - KR Result of Assessment (Korean algorithm)
+ALTER TABLE relatedness_assessments ADD COLUMN IF NOT EXISTS kr_method_of_assessment VARCHAR(10);
+ALTER TABLE relatedness_assessments ADD COLUMN IF NOT EXISTS kr_result_who_umc VARCHAR(10);
+ALTER TABLE relatedness_assessments ADD COLUMN IF NOT EXISTS kr_result_korean VARCHAR(10);
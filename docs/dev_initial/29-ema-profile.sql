@@ -0,0 +1,17 @@
+-- ============================================================================
+-- EMA (EudraVigilance) validation profile
+-- ============================================================================
+
+-- Cases, code lists and stored rules accept the `ema` profile next to
+-- ich, fda and mfds.
+ALTER TABLE cases DROP CONSTRAINT IF EXISTS case_validation_profile_valid;
+ALTER TABLE cases ADD CONSTRAINT case_validation_profile_valid
+    CHECK (validation_profile IS NULL OR validation_profile IN ('ich', 'fda', 'mfds', 'ema'));
+
+ALTER TABLE e2b_code_lists DROP CONSTRAINT IF EXISTS e2b_code_lists_profile_check;
+ALTER TABLE e2b_code_lists ADD CONSTRAINT e2b_code_lists_profile_check
+    CHECK (profile IN ('ich', 'fda', 'mfds', 'ema'));
+
+ALTER TABLE validation_rules DROP CONSTRAINT IF EXISTS validation_rules_profile_check;
+ALTER TABLE validation_rules ADD CONSTRAINT validation_rules_profile_check
+    CHECK (profile IN ('ich', 'fda', 'mfds', 'ema'));
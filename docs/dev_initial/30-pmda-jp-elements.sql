@@ -0,0 +1,97 @@
+-- ============================================================================
+-- PMDA (Japan) validation profile and J regional data elements
+-- ============================================================================
+
+-- Cases, code lists and stored rules accept the `pmda` profile.
+ALTER TABLE cases DROP CONSTRAINT IF EXISTS case_validation_profile_valid;
+ALTER TABLE cases ADD CONSTRAINT case_validation_profile_valid
+    CHECK (validation_profile IS NULL OR validation_profile IN ('ich', 'fda', 'mfds', 'ema', 'pmda'));
+
+ALTER TABLE e2b_code_lists DROP CONSTRAINT IF EXISTS e2b_code_lists_profile_check;
+ALTER TABLE e2b_code_lists ADD CONSTRAINT e2b_code_lists_profile_check
+    CHECK (profile IN ('ich', 'fda', 'mfds', 'ema', 'pmda'));
+
+ALTER TABLE validation_rules DROP CONSTRAINT IF EXISTS validation_rules_profile_check;
+ALTER TABLE validation_rules ADD CONSTRAINT validation_rules_profile_check
+    CHECK (profile IN ('ich', 'fda', 'mfds', 'ema', 'pmda'));
+
+-- H.5.r.2 - Case Summary Language: messages carry ISO 639-2 codes (eng, jpn)
+-- as well as ISO 639-1 (ja), so keep whatever the sender used.
+ALTER TABLE case_summary_information ALTER COLUMN language_code TYPE VARCHAR(3);
+
+-- ============================================================================
+-- J.2 - J.20: PMDA regional data elements (Repeating)
+-- One row per element occurrence; coded elements (J.2 reporting category,
+-- J.4 reporting deadline) hold the code, the others free text.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS case_jp_elements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    case_id UUID NOT NULL REFERENCES cases(id) ON DELETE CASCADE,
+
+    -- Element ID (J.2 ... J.20)
+    element_id VARCHAR(10) NOT NULL,
+    sequence_number INTEGER NOT NULL DEFAULT 1,
+
+    value TEXT,
+
+    -- Audit fields (standardized UUID-based)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    updated_by UUID REFERENCES users(id) ON DELETE RESTRICT,
+
+    CONSTRAINT unique_jp_element_sequence UNIQUE (case_id, element_id, sequence_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_case_jp_elements_case ON case_jp_elements(case_id);
+
+CREATE TRIGGER update_case_jp_elements_updated_at
+    BEFORE UPDATE ON case_jp_elements
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER audit_case_jp_elements
+    AFTER INSERT OR UPDATE OR DELETE ON case_jp_elements
+    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+
+CREATE TRIGGER trg_dirty_c_case_jp_elements
+    AFTER INSERT OR UPDATE OR DELETE ON case_jp_elements
+    FOR EACH ROW EXECUTE FUNCTION mark_case_dirty_c();
+
+ALTER TABLE case_jp_elements ENABLE ROW LEVEL SECURITY;
+ALTER TABLE case_jp_elements FORCE ROW LEVEL SECURITY;
+CREATE POLICY case_jp_elements_via_case ON case_jp_elements
+    FOR ALL TO e2br3_app_role
+    USING (
+        EXISTS (
+            SELECT 1 FROM cases c
+            WHERE c.id = case_jp_elements.case_id
+            AND (c.organization_id = current_organization_id() OR is_current_user_admin())
+        )
+    )
+    WITH CHECK (
+        EXISTS (
+            SELECT 1 FROM cases c
+            WHERE c.id = case_jp_elements.case_id
+            AND (c.organization_id = current_organization_id() OR is_current_user_admin())
+        )
+    );
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON case_jp_elements TO e2br3_app_role;
+
+-- PMDA code lists (draft values, see docs/PMDA_E2B_MAPPING.md)
+INSERT INTO e2b_code_lists (list_name, code, display_name, sort_order, oid, profile) VALUES
+-- J.2 Reporting Category
+('jp_reporting_category', '1', 'Domestic adverse reaction', 1, NULL, 'pmda'),
+('jp_reporting_category', '2', 'Foreign adverse reaction', 2, NULL, 'pmda'),
+('jp_reporting_category', '3', 'Domestic infection', 3, NULL, 'pmda'),
+('jp_reporting_category', '4', 'Foreign infection', 4, NULL, 'pmda'),
+('jp_reporting_category', '5', 'Research report', 5, NULL, 'pmda'),
+('jp_reporting_category', '6', 'Foreign regulatory action', 6, NULL, 'pmda'),
+('jp_reporting_category', '7', 'Clinical trial', 7, NULL, 'pmda'),
+
+-- J.4 Reporting Deadline
+('jp_reporting_deadline', '1', '15-day report', 1, NULL, 'pmda'),
+('jp_reporting_deadline', '2', '30-day report', 2, NULL, 'pmda'),
+('jp_reporting_deadline', '3', 'Periodic report', 3, NULL, 'pmda')
+ON CONFLICT ON CONSTRAINT unique_code_per_list DO NOTHING;